@@ -41,6 +41,9 @@ enum Commands {
         /// Output markdown file (or - for stdout)
         #[arg(short, long)]
         output: Option<String>,
+        /// Skip checksum verification (salvage a corrupted file)
+        #[arg(long)]
+        no_verify: bool,
     },
     /// Show compression statistics
     Stats {
@@ -52,6 +55,23 @@ enum Commands {
         /// Input .mq file
         input: String,
     },
+    /// Publish a trained dictionary as DNS TXT records (zonefile output)
+    PublishDict {
+        /// Dictionary name - becomes the record label (e.g. team-dict-v1)
+        name: String,
+        /// DNS zone to publish under; records land at <name>._mqdict.<zone>
+        #[arg(long)]
+        zone: String,
+        /// Trained dictionary source: a .mq file or a raw token=pattern file
+        #[arg(short, long)]
+        input: String,
+        /// Output zonefile (or - for stdout)
+        #[arg(long, default_value = "-")]
+        out: String,
+        /// Record TTL in seconds
+        #[arg(long, default_value = "3600")]
+        ttl: u32,
+    },
     /// Aggregate multiple markdown files into a single .mq
     Aggregate {
         /// Root directory to search for markdown files
@@ -135,7 +155,11 @@ fn main() -> Result<()> {
             }
         }
 
-        Commands::Decompress { input, output } => {
+        Commands::Decompress {
+            input,
+            output,
+            no_verify,
+        } => {
             let compressed = if input == "-" {
                 let mut buffer = String::new();
                 io::stdin().read_to_string(&mut buffer)?;
@@ -144,7 +168,8 @@ fn main() -> Result<()> {
                 fs::read_to_string(&input)?
             };
 
-            let decompressed = MarqantFormatter::decompress_marqant(&compressed)?;
+            let decompressed =
+                MarqantFormatter::decompress_marqant_with_verification(&compressed, !no_verify)?;
 
             if let Some(output_path) = output {
                 if output_path == "-" {
@@ -267,6 +292,52 @@ fn main() -> Result<()> {
                 println!("📊 Sections: {}", sections.join(", "));
             }
             println!("🏳️  Flags: {}", flags);
+            let (checksum, _) = MarqantFormatter::extract_checksum(&content);
+            match checksum {
+                Some(checksum) => println!("🔏 Checksum: sha256:{}", checksum),
+                None => println!("🔏 Checksum: none (pre-checksum file)"),
+            }
+        }
+
+        Commands::PublishDict {
+            name,
+            zone,
+            input,
+            out,
+            ttl,
+        } => {
+            let content = fs::read_to_string(&input)?;
+            let dictionary = extract_dictionary(&content);
+            if dictionary.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "No token=pattern entries found in {} - train a dictionary first \
+                     (any .mq file works as input)",
+                    input
+                ));
+            }
+
+            let payload = dictionary.join("\n") + "\n";
+            let zonefile = build_dict_zonefile(&name, &zone, ttl, &payload)?;
+
+            // Round-trip verification: reassemble the records we just wrote
+            // and check they decode back to the exact payload. A dictionary
+            // that doesn't survive its own zonefile must never be published.
+            verify_dict_zonefile(&zonefile, &name, &zone, &payload)?;
+
+            let record_count = zonefile.lines().count();
+            if out == "-" {
+                io::stdout().write_all(zonefile.as_bytes())?;
+            } else {
+                fs::write(&out, &zonefile)?;
+                println!("✅ Published {} -> {}", name, out);
+            }
+            eprintln!(
+                "📡 {} entries in {} TXT records under {}._mqdict.{} (verified)",
+                dictionary.len(),
+                record_count,
+                name,
+                zone
+            );
         }
 
         Commands::Aggregate {
@@ -474,4 +545,102 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Characters of base64 per TXT record chunk - comfortably under the
+/// 255-byte DNS character-string limit, with room for quoting.
+const TXT_CHUNK_CHARS: usize = 200;
+
+/// Pull the token=pattern lines out of a dictionary source. Accepts a full
+/// .mq file (entries live between the header and the `---` separator) or a
+/// raw dictionary file that is nothing but entries.
+fn extract_dictionary(content: &str) -> Vec<String> {
+    let lines: Box<dyn Iterator<Item = &str>> = if content.starts_with("MARQANT") {
+        Box::new(
+            content
+                .lines()
+                .skip(1)
+                .take_while(|line| *line != "---"),
+        )
+    } else {
+        Box::new(content.lines())
+    };
+    lines
+        .filter(|line| line.contains('=') && !line.starts_with("::"))
+        .map(String::from)
+        .collect()
+}
+
+/// Encode a dictionary payload into a zonefile: one index TXT record
+/// carrying version, chunk count, size, and sha256, then the zlib+base64
+/// payload chunked into numbered TXT records.
+fn build_dict_zonefile(name: &str, zone: &str, ttl: u32, payload: &str) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(payload.as_bytes())?;
+    let compressed = encoder.finish()?;
+    let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &compressed);
+
+    let digest = hex::encode(Sha256::digest(payload.as_bytes()));
+    let chunks: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(TXT_CHUNK_CHARS)
+        .map(|chunk| std::str::from_utf8(chunk).expect("base64 is ASCII"))
+        .collect();
+
+    let mut zonefile = format!(
+        "{}._mqdict.{}. {} IN TXT \"v=mqdict1; chunks={}; size={}; sha256={}\"\n",
+        name,
+        zone,
+        ttl,
+        chunks.len(),
+        payload.len(),
+        digest
+    );
+    for (index, chunk) in chunks.iter().enumerate() {
+        zonefile.push_str(&format!(
+            "{}.{}._mqdict.{}. {} IN TXT \"{}\"\n",
+            name, index, zone, ttl, chunk
+        ));
+    }
+    Ok(zonefile)
+}
+
+/// Reassemble the chunked records from a zonefile and check they decode back
+/// to `payload` - the same walk a DNS resolver will do at decompression time.
+fn verify_dict_zonefile(zonefile: &str, name: &str, zone: &str, payload: &str) -> Result<()> {
+    let mut chunks: Vec<(usize, String)> = Vec::new();
+    for line in zonefile.lines() {
+        let Some((owner, rest)) = line.split_once(' ') else {
+            continue;
+        };
+        let Some(label) = owner.strip_suffix(&format!("._mqdict.{}.", zone)) else {
+            continue;
+        };
+        let Some(index) = label
+            .strip_prefix(&format!("{}.", name))
+            .and_then(|i| i.parse::<usize>().ok())
+        else {
+            continue; // The index record, or someone else's dictionary.
+        };
+        let Some(value) = rest.split('"').nth(1) else {
+            continue;
+        };
+        chunks.push((index, value.to_string()));
+    }
+    chunks.sort_by_key(|(index, _)| *index);
+
+    let encoded: String = chunks.into_iter().map(|(_, chunk)| chunk).collect();
+    let compressed =
+        base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &encoded)?;
+    let mut decoded = String::new();
+    flate2::read::ZlibDecoder::new(compressed.as_slice()).read_to_string(&mut decoded)?;
+
+    if decoded != payload {
+        return Err(anyhow::anyhow!(
+            "Zonefile verification failed: reassembled dictionary does not match the input"
+        ));
+    }
+    Ok(())
+}
+
 // 🎸 "Compression so good, even your README will sing!" - The Cheet
@@ -24,7 +24,7 @@ use st::formatters::{
     json::JsonFormatter, quantum::QuantumFormatter, stats::StatsFormatter, Formatter,
     PathDisplayMode,
 };
-use st::mcp::wave_memory::{MemoryType, WaveMemoryManager};
+use st::mcp::wave_memory::{MemoryScope, MemoryType, WaveMemoryManager};
 use st::scanner::{Scanner, ScannerConfig};
 use st_protocol::{Address, AuthLevel, Frame, Payload, PayloadDecoder, SecurityContext, Verb};
 
@@ -468,6 +468,7 @@ async fn handle_remember(payload: Payload, state: &Arc<RwLock<DaemonState>>) ->
         arousal,
         "daemon".to_string(),
         None,
+        MemoryScope::Global,
     ) {
         Ok(id) => {
             // Save to disk
@@ -504,7 +505,9 @@ async fn handle_recall(payload: Payload, state: &Arc<RwLock<DaemonState>>) -> Fr
     debug!("RECALL keywords={:?} max={}", keywords, max_results);
 
     let mut state = state.write().await;
-    let memories = state.memory.find_by_keywords(&keywords, max_results);
+    let memories = state
+        .memory
+        .find_by_keywords(&keywords, max_results, &MemoryScope::Global);
 
     let results: Vec<_> = memories
         .iter()
@@ -604,6 +607,7 @@ async fn handle_audio(payload: Payload, state: &Arc<RwLock<DaemonState>>) -> Fra
             arousal,
             "audio".to_string(),
             None,
+            MemoryScope::Global,
         ) {
             Ok(id) => {
                 let _ = state.memory.save();
@@ -647,6 +651,7 @@ async fn handle_audio(payload: Payload, state: &Arc<RwLock<DaemonState>>) -> Fra
             arousal,
             "audio".to_string(),
             None,
+            MemoryScope::Global,
         ) {
             Ok(id) => {
                 let _ = state.memory.save();
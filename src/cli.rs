@@ -57,6 +57,40 @@ pub struct Cli {
     #[arg(long, help_heading = "Interactive Modes")]
     pub spicy: bool,
 
+    /// Launch the TUI explorer - navigate the scanned tree, filter, sort
+    /// by size, preview files, open/trash/copy-path
+    #[arg(long, help_heading = "Interactive Modes")]
+    pub tui: bool,
+
+    /// Watch the directory and live-update the classic/ls tree in place
+    #[arg(long, help_heading = "Interactive Modes")]
+    pub watch: bool,
+
+    /// Only repaint --watch for these semantic categories (comma-separated:
+    /// source,tests,config,docs,build,scripts,data,assets)
+    #[arg(
+        long,
+        value_name = "CATEGORIES",
+        value_delimiter = ',',
+        help_heading = "Interactive Modes"
+    )]
+    pub watch_filter: Vec<String>,
+
+    /// Debounce window for --watch in milliseconds - events inside one
+    /// window coalesce per path and rename halves are paired
+    #[arg(
+        long,
+        value_name = "MS",
+        default_value_t = 250,
+        help_heading = "Interactive Modes"
+    )]
+    pub watch_debounce: u64,
+
+    /// Internal: privileged stat-only helper spawned by --sudo-helper.
+    /// Walks PATH and prints "<entries> <bytes>" - nothing else.
+    #[arg(long, value_name = "PATH", hide = true)]
+    pub stat_helper: Option<PathBuf>,
+
     /// Launch Smart Tree Terminal Interface (STTI)
     #[arg(long, exclusive = true, help_heading = "Interactive Modes")]
     pub terminal: bool,
@@ -84,6 +118,11 @@ pub struct Cli {
     #[arg(long, exclusive = true, help_heading = "MCP Server")]
     pub mcp: bool,
 
+    /// Serve MCP over HTTP+SSE on this address instead of stdio
+    /// (e.g. 127.0.0.1:8421)
+    #[arg(long, value_name = "ADDR", requires = "mcp", help_heading = "MCP Server")]
+    pub listen: Option<String>,
+
     /// Install Smart Tree as MCP server in Claude Desktop
     #[arg(long, exclusive = true, help_heading = "MCP Server")]
     pub mcp_install: bool,
@@ -186,6 +225,11 @@ pub struct Cli {
     #[arg(long, exclusive = true, help_heading = "Security")]
     pub cleanup: bool,
 
+    /// Never spawn external processes (git decorations, openers, and
+    /// helpers degrade gracefully instead; also via ST_NO_EXEC=1)
+    #[arg(long, help_heading = "Security")]
+    pub no_exec: bool,
+
     // =========================================================================
     // HOOKS
     // =========================================================================
@@ -250,6 +294,10 @@ pub struct Cli {
     /// Path to analyze (directory, file, URL, or stream)
     pub path: Option<String>,
 
+    /// Additional roots to scan in the same run (`st a b c`) - each
+    /// renders as a sibling tree, with per-root and combined totals after
+    pub extra_paths: Vec<String>,
+
     /// Specify input type explicitly (filesystem, qcp, sse, openapi, mem8)
     #[arg(long, value_name = "TYPE")]
     pub input: Option<String>,
@@ -260,18 +308,73 @@ pub struct Cli {
 
 #[derive(Parser, Debug)]
 pub struct ScanArgs {
+    // =========================================================================
+    // PROFILES - Named flag bundles from ~/.st/config.toml
+    // =========================================================================
+    /// Apply a named scan profile from ~/.st/config.toml
+    /// (a `[profile.NAME]` table); explicit flags override profile values
+    #[arg(long, value_name = "NAME", help_heading = "Getting Started")]
+    pub profile: Option<String>,
+
     // =========================================================================
     // OUTPUT FORMAT
     // =========================================================================
-    /// Output format (classic, ai, quantum, json, etc.)
-    #[arg(
-        short,
-        long,
-        value_enum,
-        default_value = "auto",
-        help_heading = "Output Format"
-    )]
-    pub mode: OutputMode,
+    /// Output format (classic, ai, quantum, json, etc. - resolved through
+    /// the formatter registry, so plugin-registered modes work too)
+    #[arg(short, long, default_value = "auto", help_heading = "Output Format")]
+    pub mode: String,
+
+    /// Write output to FILE instead of stdout (required for --mode sqlite).
+    /// The extension picks the formatter (.json/.html/.csv/.md/.mq/.sqlite)
+    /// unless --mode is given; a .gz extension gzips transparently
+    #[arg(short = 'o', long, value_name = "FILE", help_heading = "Output Format")]
+    pub output: Option<PathBuf>,
+
+    /// Field delimiter for csv mode, e.g. ';' or '\t' (default ',')
+    #[arg(long, value_name = "CHAR", help_heading = "Output Format")]
+    pub csv_delimiter: Option<String>,
+
+    /// Prefix csv output with a UTF-8 BOM so Excel opens it correctly
+    #[arg(long, help_heading = "Output Format")]
+    pub csv_bom: bool,
+
+    /// Choose columns and their order for ls/classic modes,
+    /// e.g. 'size,mtime,owner,perms,name' or 'name,category'
+    #[arg(long, value_name = "LIST", help_heading = "Output Format")]
+    pub columns: Option<String>,
+
+    /// Sizes in powers of 1000 instead of 1024 (ls mode, like ls --si)
+    #[arg(long, help_heading = "Output Format")]
+    pub si: bool,
+
+    /// Sizes as 1K-block counts (ls mode, like ls -k)
+    #[arg(short = 'k', long, help_heading = "Output Format")]
+    pub kibibytes: bool,
+
+    /// Timestamp style for ls mode: full-iso, long-iso, iso, locale,
+    /// or +FORMAT (chrono strftime)
+    #[arg(long, value_name = "STYLE", help_heading = "Output Format")]
+    pub time_style: Option<String>,
+
+    /// Middle-truncate names longer than N characters in classic mode,
+    /// keeping the extension visible
+    #[arg(long, value_name = "N", help_heading = "Output Format")]
+    pub max_name_width: Option<usize>,
+
+    /// Emit only these hex-mode fields, in order (see `st explain-format hex`),
+    /// e.g. 'perms,size,mtime'
+    #[arg(long, value_name = "LIST", help_heading = "Output Format")]
+    pub hex_fields: Option<String>,
+
+    /// Color classic-mode names cold→hot by relative size or modification
+    /// recency within the scan (size|age)
+    #[arg(long, value_name = "MODE", help_heading = "Output Format")]
+    pub heatmap: Option<String>,
+
+    /// With --mode digest: one Merkle digest per directory instead of a
+    /// single hash, so two runs diff down to the changed subtrees
+    #[arg(long, help_heading = "Output Format")]
+    pub merkle: bool,
 
     // =========================================================================
     // FILTERING - What to include/exclude
@@ -280,6 +383,11 @@ pub struct ScanArgs {
     #[arg(long, help_heading = "Filtering")]
     pub find: Option<String>,
 
+    /// Find files matching a glob (repeatable; `!` prefix excludes;
+    /// braces expand, e.g. --glob "**/*.{ts,tsx}" --glob "!**/dist/**")
+    #[arg(long, value_name = "PATTERN", help_heading = "Filtering")]
+    pub glob: Vec<String>,
+
     /// Filter by file extension (e.g., --type rs)
     #[arg(long = "type", help_heading = "Filtering")]
     pub filter_type: Option<String>,
@@ -288,6 +396,10 @@ pub struct ScanArgs {
     #[arg(long = "entry-type", value_parser = ["f", "d"], help_heading = "Filtering")]
     pub entry_type: Option<String>,
 
+    /// Include or skip device files, sockets, and FIFOs (default: include)
+    #[arg(long = "special-files", value_parser = ["include", "skip"], help_heading = "Filtering")]
+    pub special_files: Option<String>,
+
     /// Only files larger than size (e.g., --min-size 1M)
     #[arg(long, help_heading = "Filtering")]
     pub min_size: Option<String>,
@@ -304,6 +416,20 @@ pub struct ScanArgs {
     #[arg(long, help_heading = "Filtering")]
     pub older_than: Option<String>,
 
+    /// Show only files in one git state: tracked, untracked, ignored, modified
+    #[arg(long, value_enum, help_heading = "Filtering")]
+    pub git_filter: Option<GitFilterMode>,
+
+    /// Don't descend into directories matching glob (repeatable; the
+    /// entry stays, carrying its aggregate size)
+    #[arg(long, value_name = "PATTERN", help_heading = "Filtering")]
+    pub prune: Vec<String>,
+
+    /// Show at most N entries per directory, plus a "(+12,345 more)"
+    /// summary node for the rest
+    #[arg(long, value_name = "N", help_heading = "Filtering")]
+    pub max_files_per_dir: Option<usize>,
+
     // =========================================================================
     // TRAVERSAL - How to scan
     // =========================================================================
@@ -331,6 +457,67 @@ pub struct ScanArgs {
     #[arg(long, help_heading = "Traversal")]
     pub everything: bool,
 
+    /// Skip the --everything size-estimate confirmation prompt
+    #[arg(long, help_heading = "Traversal")]
+    pub yes: bool,
+
+    /// Scan budget for CI: stop cleanly after N entries (stats marked
+    /// truncated)
+    #[arg(long, value_name = "N", help_heading = "Traversal")]
+    pub max_entries: Option<usize>,
+
+    /// Scan budget for CI: stop cleanly after this many file bytes
+    /// (e.g. 500M)
+    #[arg(long, value_name = "SIZE", help_heading = "Traversal")]
+    pub max_bytes_scanned: Option<String>,
+
+    /// Scan budget for CI: stop cleanly after this many seconds
+    #[arg(long, value_name = "SECONDS", help_heading = "Traversal")]
+    pub timeout: Option<u64>,
+
+    /// Descend into archives (.zip, .tar.gz, .jar, .whl) as virtual subtrees
+    #[arg(long, help_heading = "Traversal")]
+    pub scan_archives: bool,
+
+    /// Count hard-linked files once in size totals (shows apparent size vs
+    /// disk usage - essential on backup trees full of hard links)
+    #[arg(long, help_heading = "Traversal")]
+    pub dedupe_hardlinks: bool,
+
+    /// Report allocated sizes (st_blocks * 512) like `du` instead of logical
+    /// sizes, with sparse-file detection
+    #[arg(long, help_heading = "Traversal")]
+    pub du: bool,
+
+    /// List permission-denied paths in a separate section instead of
+    /// silently skipping them (useful when scanning /var or /etc)
+    #[arg(long, help_heading = "Traversal")]
+    pub report_denied: bool,
+
+    /// With --report-denied: spawn a minimal `sudo` helper for stat-only
+    /// counts of denied paths (the main process stays unprivileged)
+    #[arg(long, help_heading = "Traversal")]
+    pub sudo_helper: bool,
+
+    /// Show recursive (ncdu-style) size totals next to each directory
+    #[arg(long, help_heading = "Traversal")]
+    pub dir_sizes: bool,
+
+    /// Force the classic walkdir scanner (large scans default to the faster
+    /// native quantum walker when it has feature parity)
+    #[arg(long, help_heading = "Traversal")]
+    pub classic_walker: bool,
+
+    /// List NTFS alternate data streams as virtual child entries
+    /// (Windows only - ignored elsewhere)
+    #[arg(long, help_heading = "Traversal")]
+    pub ads: bool,
+
+    /// Collect extended attributes and POSIX ACLs (security.*, user.*, ...)
+    /// and show them in ls/json/ai modes (Linux only)
+    #[arg(long, help_heading = "Traversal")]
+    pub xattrs: bool,
+
     // =========================================================================
     // SMART SCANNING - Intelligent context-aware output
     // =========================================================================
@@ -366,10 +553,21 @@ pub struct ScanArgs {
     #[arg(short = 'z', long, help_heading = "Display")]
     pub compress: bool,
 
+    /// Algorithm for -z: zlib (default) or zstd[:level]. Zstd emits a
+    /// COMPRESSED_ZSTD payload `st decompress` can expand, and uses the
+    /// shared dictionary from `st dict train` when one exists
+    #[arg(long, value_name = "ALGO", help_heading = "Display", requires = "compress")]
+    pub compression: Option<String>,
+
     /// Optimize for MCP/API (compression + no colors/emoji)
     #[arg(long, help_heading = "Display")]
     pub mcp_optimize: bool,
 
+    /// Keep output under ~N tokens by reducing depth, with a note on
+    /// what was omitted (token ≈ 4 characters)
+    #[arg(long, value_name = "N", help_heading = "Display")]
+    pub token_budget: Option<usize>,
+
     /// Compact JSON (single line)
     #[arg(long, help_heading = "Display")]
     pub compact: bool,
@@ -409,14 +607,88 @@ pub struct ScanArgs {
     // =========================================================================
     // SEARCH & ANALYSIS
     // =========================================================================
-    /// Search file contents (e.g., --search "TODO")
+    /// Search file contents (e.g., --search "TODO"); repeat for multiple patterns
+    #[arg(long, value_name = "PATTERN", help_heading = "Search & Analysis")]
+    pub search: Vec<String>,
+
+    /// Treat --search patterns as regular expressions
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub search_regex: bool,
+
+    /// Case-insensitive content search
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub search_ignore_case: bool,
+
+    /// Match --search patterns only at word boundaries
     #[arg(long, help_heading = "Search & Analysis")]
-    pub search: Option<String>,
+    pub search_word: bool,
+
+    /// Require every --search pattern to match (AND); default is any (OR)
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub search_all: bool,
 
     /// Group by semantic similarity
     #[arg(long, help_heading = "Search & Analysis")]
     pub semantic: bool,
 
+    /// Verify duplicates by content hash in stats mode (blake3, sha256, xxhash)
+    #[arg(
+        long,
+        value_name = "ALGO",
+        default_missing_value = "blake3",
+        num_args = 0..=1,
+        help_heading = "Search & Analysis"
+    )]
+    pub hash: Option<String>,
+
+    /// Per-directory extension statistics in stats/json modes (recursive)
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub per_dir: bool,
+
+    /// Emit an SBOM document in licenses mode (cyclonedx or spdx)
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_parser = ["cyclonedx", "spdx"],
+        help_heading = "Search & Analysis"
+    )]
+    pub sbom: Option<String>,
+
+    /// Graph syntax for deps mode (mermaid or dot)
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_parser = ["mermaid", "dot"],
+        help_heading = "Search & Analysis"
+    )]
+    pub graph: Option<String>,
+
+    /// Count code/comment/blank lines per language (stats, markdown modes)
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub loc: bool,
+
+    /// Length of the largest/newest/oldest lists in stats mode
+    #[arg(long, value_name = "N", help_heading = "Search & Analysis")]
+    pub stats_top: Option<usize>,
+
+    /// Restrict stats mode to relative paths matching this regex
+    #[arg(long, value_name = "REGEX", help_heading = "Search & Analysis")]
+    pub stats_filter: Option<String>,
+
+    /// Fuzzy-find filenames fzf-style and print ranked matches
+    #[arg(long, value_name = "PATTERN", help_heading = "Search & Analysis")]
+    pub fuzzy: Option<String>,
+
+    /// Sniff magic bytes so extension-less and mislabeled files get the
+    /// right category (one small read per file)
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub detect_content: bool,
+
+    /// Probe media headers for image dimensions, EXIF date, and audio/video
+    /// duration (ls, json, stats modes)
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub media_info: bool,
+
     /// Focus analysis on specific file (relations mode)
     #[arg(long, value_name = "FILE", help_heading = "Search & Analysis")]
     pub focus: Option<PathBuf>,
@@ -425,17 +697,90 @@ pub struct ScanArgs {
     #[arg(long, value_name = "TYPE", help_heading = "Search & Analysis")]
     pub relations_filter: Option<String>,
 
+    /// Render relations as Graphviz DOT (use with --mode relations)
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub dot: bool,
+
+    /// Export the function-level call graph: dot or json (relations mode)
+    #[arg(long, value_name = "FORMAT", help_heading = "Search & Analysis")]
+    pub call_graph: Option<String>,
+
+    /// Exit non-zero when modules form dependency cycles (relations mode)
+    #[arg(long, help_heading = "Search & Analysis")]
+    pub fail_on_cycles: bool,
+
+    /// Decorate entries with git status markers: M/A/D/?? (classic and ls modes)
+    #[arg(long, help_heading = "Output Format")]
+    pub git_status: bool,
+
+    /// Show last commit age and author per file (classic and ls modes)
+    #[arg(long, help_heading = "Output Format")]
+    pub git_blame_summary: bool,
+
     // =========================================================================
     // SORTING
     // =========================================================================
-    /// Sort by: a-to-z, z-to-a, largest, smallest, newest, oldest, type
-    #[arg(long, value_enum, help_heading = "Sorting")]
-    pub sort: Option<SortField>,
+    /// Sort by one or more keys, later keys breaking ties
+    /// (e.g. --sort type,largest): a-to-z, z-to-a, largest, smallest,
+    /// newest, oldest, type
+    #[arg(long, value_enum, value_delimiter = ',', help_heading = "Sorting")]
+    pub sort: Vec<SortField>,
+
+    /// List directories before files at every level
+    /// (also answers to tree's --dirsfirst spelling)
+    #[arg(
+        long,
+        alias = "dirsfirst",
+        help_heading = "Sorting",
+        conflicts_with = "files_first"
+    )]
+    pub dirs_first: bool,
+
+    /// List files before directories at every level
+    #[arg(long, help_heading = "Sorting")]
+    pub files_first: bool,
 
     /// Show only top N results (use with --sort)
     #[arg(long, value_name = "N", help_heading = "Sorting")]
     pub top: Option<usize>,
 
+    // =========================================================================
+    // TREE(1) COMPATIBILITY - Soft landing for migrating scripts
+    // =========================================================================
+    /// Compatibility mode: `--compat tree` renders byte-compatible GNU
+    /// tree output, `--compat fd` emits fd's plain relative-path lines,
+    /// so scripts that parse those tools keep working unchanged
+    #[arg(long, value_name = "TOOL", value_parser = ["tree", "fd"], help_heading = "Compatibility")]
+    pub compat: Option<String>,
+
+    /// tree -L: descend at most LEVEL directories (alias for --depth)
+    #[arg(
+        short = 'L',
+        long,
+        value_name = "LEVEL",
+        help_heading = "Compatibility"
+    )]
+    pub level: Option<usize>,
+
+    /// tree -P: list only files matching the wildcard (repeatable;
+    /// matches basenames at any depth, like tree)
+    #[arg(short = 'P', value_name = "PATTERN", help_heading = "Compatibility")]
+    pub tree_pattern: Vec<String>,
+
+    /// tree -I: skip files and directories matching the wildcard
+    /// (repeatable)
+    #[arg(short = 'I', value_name = "PATTERN", help_heading = "Compatibility")]
+    pub tree_ignore: Vec<String>,
+
+    /// tree -J: JSON output (alias for --mode json)
+    #[arg(short = 'J', help_heading = "Compatibility")]
+    pub tree_json: bool,
+
+    /// tree -H: HTML output (alias for --mode html; the baseHREF
+    /// argument is accepted for compatibility but not embedded)
+    #[arg(short = 'H', value_name = "BASEHREF", help_heading = "Compatibility")]
+    pub tree_html: Option<String>,
+
     // =========================================================================
     // MERMAID & MARKDOWN OPTIONS
     // =========================================================================
@@ -448,6 +793,20 @@ pub struct ScanArgs {
     )]
     pub mermaid_style: MermaidStyleArg,
 
+    /// Link diagram nodes to this base URL (e.g. a repo blob URL) via
+    /// mermaid click directives
+    #[arg(long, value_name = "URL", help_heading = "Mermaid & Markdown")]
+    pub mermaid_links: Option<String>,
+
+    /// Group diagram nodes into subgraphs per detected package
+    #[arg(long, help_heading = "Mermaid & Markdown")]
+    pub mermaid_subgraphs: bool,
+
+    /// TOML report template: custom title/logo/footer/org plus an ordered
+    /// section selection (markdown mode)
+    #[arg(long, value_name = "FILE", help_heading = "Mermaid & Markdown")]
+    pub report_template: Option<PathBuf>,
+
     /// Exclude mermaid diagrams from markdown
     #[arg(long, help_heading = "Mermaid & Markdown")]
     pub no_markdown_mermaid: bool,
@@ -482,6 +841,37 @@ pub struct ScanArgs {
 
 #[derive(Debug, Subcommand)]
 pub enum Cmd {
+    /// Scan a directory - the explicit spelling of the default action,
+    /// so `st scan PATH` and `st PATH` are the same command
+    Scan {
+        /// Path to analyze
+        path: Option<String>,
+
+        #[command(flatten)]
+        scan_opts: Box<ScanArgs>,
+    },
+
+    /// Search file contents while scanning (`st search TODO src/` is
+    /// shorthand for `st scan src/ --search TODO`)
+    Search {
+        /// Pattern to search for
+        #[arg(required = true)]
+        pattern: String,
+
+        /// Path to scan
+        path: Option<String>,
+
+        #[command(flatten)]
+        scan_opts: Box<ScanArgs>,
+    },
+
+    /// Run as MCP server for AI assistants (same as --mcp)
+    Mcp,
+
+    /// Control the always-on daemon (same as the --daemon-* flags)
+    #[command(subcommand)]
+    Daemon(DaemonCmd),
+
     /// Manage the smart-tree daemon (Linux: systemd, macOS: launchctl, Windows: Task Scheduler)
     #[command(subcommand)]
     Service(Service),
@@ -489,6 +879,198 @@ pub enum Cmd {
     /// Manage project tags
     #[command(subcommand, name = "project-tags")]
     ProjectTags(ProjectTags),
+
+    /// Print the field layout of a machine-oriented output format
+    #[command(name = "explain-format")]
+    ExplainFormat {
+        /// Format to describe (currently: hex)
+        #[arg(required = true)]
+        format: String,
+    },
+
+    /// Expand a COMPRESSED_V1/V2/ZSTD output file back to plain text
+    Decompress {
+        /// File containing the compressed payload (or "-" for stdin)
+        #[arg(required = true)]
+        file: PathBuf,
+    },
+
+    /// Decode an archived st output (COMPRESSED container, quantum,
+    /// marqant, summary-ai - auto-detected) back to a readable format
+    Decode {
+        /// File containing the output (or "-" for stdin)
+        #[arg(required = true)]
+        file: PathBuf,
+
+        /// Target format (auto picks the natural one per input)
+        #[arg(long, default_value = "auto", value_parser = ["auto", "json", "classic", "markdown"])]
+        to: String,
+    },
+
+    /// Inspect the MCP audit trail (.st/audit.jsonl)
+    #[command(subcommand)]
+    Audit(AuditCmd),
+
+    /// Inspect or empty the persistent scan result cache (~/.st/cache)
+    #[command(subcommand)]
+    Cache(CacheCmd),
+
+    /// Manage the shared zstd dictionary used by --compression zstd
+    #[command(subcommand)]
+    Dict(DictCmd),
+
+    /// Check the environment (config, daemon, MCP wiring, index, terminal)
+    /// and suggest fixes for anything off
+    Doctor {
+        /// Emit the report as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage the persistent content-search index (used by --search)
+    #[command(subcommand)]
+    Index(IndexCmd),
+
+    /// Back up or restore wave memories in open formats
+    #[command(subcommand)]
+    Memory(Memory),
+
+    /// Render a word cloud SVG from file names and/or README content
+    Wordcloud {
+        /// Directory to analyze
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Output SVG file (or "-" for stdout)
+        #[arg(long, default_value = "cloud.svg")]
+        out: String,
+        /// Color palette
+        #[arg(long, default_value = "ocean", value_parser = ["ocean", "sunset", "forest", "mono"])]
+        theme: String,
+        /// Stopword list ("code" drops src/test/main etc., "none" keeps all)
+        #[arg(long, default_value = "en", value_parser = ["en", "de", "fr", "es", "code", "none"])]
+        stopwords: String,
+        /// Maximum words in the cloud
+        #[arg(long, default_value = "60")]
+        max_words: usize,
+        /// Shape mask the layout flows into
+        #[arg(long, default_value = "rect", value_parser = ["rect", "ellipse"])]
+        shape: String,
+        /// Word source
+        #[arg(long, default_value = "both", value_parser = ["names", "readme", "both"])]
+        source: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DaemonCmd {
+    /// Start the Smart Tree daemon
+    Start,
+    /// Stop the Smart Tree daemon
+    Stop,
+    /// Show daemon status
+    Status,
+    /// Get context from the daemon
+    Context,
+    /// List projects tracked by the daemon
+    Projects,
+    /// Show Foken credits from the daemon
+    Credits,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AuditCmd {
+    /// List recorded tool calls, oldest first
+    Show {
+        /// Project root containing .st/audit.jsonl
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Only the last N entries
+        #[arg(long)]
+        tail: Option<usize>,
+        /// Only calls to this tool
+        #[arg(long)]
+        tool: Option<String>,
+    },
+    /// Reconstruct what an AI session did to the project - a narrative
+    /// of the calls with every write highlighted
+    Replay {
+        /// Project root containing .st/audit.jsonl
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCmd {
+    /// Report entry count, disk usage, and the eviction cap
+    Stats,
+    /// Delete every cached scan result
+    Clear,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DictCmd {
+    /// Train a shared dictionary from sample st outputs. Save a pile of
+    /// representative outputs first (e.g. `st -m ai src > sample.ai`) -
+    /// the more samples, the better the dictionary
+    Train {
+        /// Sample output files to train on
+        #[arg(required = true)]
+        samples: Vec<PathBuf>,
+        /// Maximum dictionary size in bytes
+        #[arg(long, default_value = "16384")]
+        max_size: usize,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum IndexCmd {
+    /// Build (or rebuild) the trigram index for a directory
+    Build {
+        /// Directory to index
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Report index freshness: indexed, stale, and missing files
+    Status {
+        /// Directory whose index to inspect
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Delete the stored index for a directory
+    Clear {
+        /// Directory whose index to delete
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Memory {
+    /// Export wave memories as JSONL (one memory per line)
+    Export {
+        /// Export format (only jsonl for now)
+        #[arg(long, default_value = "jsonl", value_parser = ["jsonl"])]
+        format: String,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Directory whose .st/mem8 store to export (defaults to current dir)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Import wave memories from JSONL (existing ids are kept, not overwritten)
+    Import {
+        /// File to import (or "-" for stdin)
+        #[arg(required = true)]
+        file: PathBuf,
+        /// Import format (only jsonl for now)
+        #[arg(long, default_value = "jsonl", value_parser = ["jsonl"])]
+        format: String,
+        /// Directory whose .st/mem8 store to import into (defaults to current dir)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -523,6 +1105,23 @@ pub enum ProjectTags {
     },
 }
 
+/// Git states selectable with `--git-filter`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GitFilterMode {
+    /// Files git knows about (the index)
+    #[value(name = "tracked")]
+    Tracked,
+    /// Files git does not know about and does not ignore
+    #[value(name = "untracked")]
+    Untracked,
+    /// Files excluded by .gitignore rules
+    #[value(name = "ignored")]
+    Ignored,
+    /// Tracked files with uncommitted worktree changes
+    #[value(name = "modified")]
+    Modified,
+}
+
 /// Sort field options with intuitive names
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum SortField {
@@ -15,6 +15,10 @@ pub struct CompressionState {
     /// Whether the client supports compressed content
     pub client_supports_compression: Option<bool>,
 
+    /// Session-level preference: compress by default when a tool call
+    /// doesn't say either way (negotiated at initialize)
+    pub session_preference: Option<bool>,
+
     /// Maximum tokens before auto-compression kicks in
     pub max_tokens: usize,
 
@@ -40,6 +44,7 @@ impl Default for CompressionState {
     fn default() -> Self {
         Self {
             client_supports_compression: None, // Unknown until tested
+            session_preference: None,          // No preference until negotiated
             max_tokens: 20000,                 // Safe limit (MCP allows 25k)
             force_compression: false,
             disable_compression: false,
@@ -61,21 +66,42 @@ pub fn create_compression_test() -> Value {
 }
 
 /// Check if a client response indicates compression support
+///
+/// Accepts `params.compression: "ok"` (legacy acknowledgment) as well as
+/// `params.capabilities.compression` as a bool or the strings
+/// "ok"/"true"/"preferred". "preferred" additionally records a session-level
+/// preference, so tool calls without an explicit `compress` parameter
+/// default to compressed output for this client.
 pub fn check_client_compression_support(request: &Value) -> bool {
     // Check for explicit compression acknowledgment
     if let Some(params) = request.get("params") {
         if let Some(compression) = params.get("compression") {
-            if compression.as_str() == Some("ok") {
-                set_compression_support(true);
-                return true;
+            match compression.as_str() {
+                Some("ok") => {
+                    set_compression_support(true);
+                    return true;
+                }
+                Some("preferred") => {
+                    set_compression_support(true);
+                    set_session_preference(Some(true));
+                    return true;
+                }
+                _ => {}
             }
         }
 
         // Check for compression capability in client info
         if let Some(capabilities) = params.get("capabilities") {
             if let Some(compression) = capabilities.get("compression") {
-                let supported = compression.as_bool().unwrap_or(false);
+                let supported = match compression {
+                    Value::Bool(b) => *b,
+                    Value::String(s) => matches!(s.as_str(), "ok" | "true" | "preferred"),
+                    _ => false,
+                };
                 set_compression_support(supported);
+                if compression.as_str() == Some("preferred") {
+                    set_session_preference(Some(true));
+                }
                 return supported;
             }
         }
@@ -95,6 +121,77 @@ pub fn set_compression_support(supported: bool) {
     }
 }
 
+/// Set the session-level compression preference (negotiated at initialize)
+pub fn set_session_preference(preference: Option<bool>) {
+    if let Ok(mut state) = COMPRESSION_STATE.write() {
+        state.session_preference = preference;
+    }
+}
+
+/// The outcome of compression negotiation for a single tool call
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressionDecision {
+    /// Whether the response should actually be compressed
+    pub compress: bool,
+    /// Set when compression was wanted but we fell back to decompressed
+    /// output because the client never advertised support - tools should
+    /// surface this as a `warning` field on the response
+    pub warning: Option<String>,
+}
+
+/// Negotiate compression for one tool call
+///
+/// Precedence: operator overrides (disable/force, `MCP_NO_COMPRESS`) beat
+/// the explicit per-call `compress` parameter, which beats the session-level
+/// preference negotiated at initialize. Compressed output is only ever sent
+/// to clients that advertised support; otherwise we fall back to
+/// decompressed output and report why in `warning`.
+pub fn negotiate_compression(per_call: Option<bool>) -> CompressionDecision {
+    let state = COMPRESSION_STATE.read().unwrap().clone();
+    let env_disabled = std::env::var("MCP_NO_COMPRESS")
+        .is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+    negotiate_with(&state, per_call, env_disabled)
+}
+
+/// Pure negotiation logic, split out so it can be tested without touching
+/// the global state
+fn negotiate_with(
+    state: &CompressionState,
+    per_call: Option<bool>,
+    env_disabled: bool,
+) -> CompressionDecision {
+    if state.disable_compression || env_disabled {
+        return CompressionDecision {
+            compress: false,
+            warning: None,
+        };
+    }
+    if state.force_compression {
+        return CompressionDecision {
+            compress: true,
+            warning: None,
+        };
+    }
+
+    let wanted = per_call.unwrap_or_else(|| state.session_preference.unwrap_or(false));
+    if wanted && state.client_supports_compression != Some(true) {
+        return CompressionDecision {
+            compress: false,
+            warning: Some(
+                "Compression was requested but this client never advertised support, so the \
+                 output is decompressed. Advertise capabilities.compression (true or 'preferred') \
+                 during initialize, or send compression:'ok', to enable compressed responses."
+                    .to_string(),
+            ),
+        };
+    }
+
+    CompressionDecision {
+        compress: wanted,
+        warning: None,
+    }
+}
+
 /// Check if we should compress a response based on its size
 pub fn should_compress_response(content: &str) -> bool {
     let state = COMPRESSION_STATE.read().unwrap();
@@ -138,6 +235,329 @@ pub fn compress_string(content: &str) -> Result<String> {
     Ok(format!("COMPRESSED_V1:{}", hex::encode(&compressed)))
 }
 
+// ---------------------------------------------------------------------------
+// Compression container (COMPRESSED_V2)
+//
+// COMPRESSED_V1 is just `COMPRESSED_V1:<hex of zlib>` - no metadata at all.
+// Once such an output lands in a file, nobody can tell what mode produced it,
+// where it was scanned, or whether the bytes are intact. The V2 container
+// prepends a small binary header so archived outputs stay self-describing:
+//
+//   magic "STCZ" | version u8 | timestamp u64 | original_size u64 |
+//   crc32 u32 | mode (u16 len + utf8) | root (u16 len + utf8) | zlib payload
+//
+// All integers little-endian. The whole thing is hex-encoded after the
+// `COMPRESSED_V2:` prefix, same transport-safety as V1.
+// ---------------------------------------------------------------------------
+
+/// Magic bytes identifying a Smart Tree compression container.
+pub const CONTAINER_MAGIC: &[u8; 4] = b"STCZ";
+
+/// Current container format version.
+pub const CONTAINER_VERSION: u8 = 1;
+
+/// Metadata header embedded in a COMPRESSED_V2 container.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerHeader {
+    /// Output mode that produced the payload (e.g., "ai", "classic")
+    pub mode: String,
+    /// Root path that was scanned
+    pub root: String,
+    /// Unix timestamp (seconds) when the container was created
+    pub timestamp: u64,
+    /// Size of the original (uncompressed) content in bytes
+    pub original_size: u64,
+    /// CRC32 of the original content, validated on decompression
+    pub checksum: u32,
+}
+
+impl ContainerHeader {
+    /// Serialize the header to its binary form.
+    fn encode(&self) -> Vec<u8> {
+        let mode_bytes = self.mode.as_bytes();
+        let root_bytes = self.root.as_bytes();
+        let mut out = Vec::with_capacity(4 + 1 + 8 + 8 + 4 + 2 + mode_bytes.len() + 2 + root_bytes.len());
+        out.extend_from_slice(CONTAINER_MAGIC);
+        out.push(CONTAINER_VERSION);
+        out.extend_from_slice(&self.timestamp.to_le_bytes());
+        out.extend_from_slice(&self.original_size.to_le_bytes());
+        out.extend_from_slice(&self.checksum.to_le_bytes());
+        out.extend_from_slice(&(mode_bytes.len() as u16).to_le_bytes());
+        out.extend_from_slice(mode_bytes);
+        out.extend_from_slice(&(root_bytes.len() as u16).to_le_bytes());
+        out.extend_from_slice(root_bytes);
+        out
+    }
+
+    /// Parse a header from the front of `bytes`, returning the header and
+    /// the number of bytes it occupied.
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        let mut pos = 0usize;
+        let take = |bytes: &[u8], pos: &mut usize, n: usize| -> Result<Vec<u8>> {
+            if *pos + n > bytes.len() {
+                anyhow::bail!("Truncated container header");
+            }
+            let slice = bytes[*pos..*pos + n].to_vec();
+            *pos += n;
+            Ok(slice)
+        };
+
+        let magic = take(bytes, &mut pos, 4)?;
+        if magic != CONTAINER_MAGIC {
+            anyhow::bail!("Not a Smart Tree container (bad magic)");
+        }
+        let version = take(bytes, &mut pos, 1)?[0];
+        if version != CONTAINER_VERSION {
+            anyhow::bail!("Unsupported container version: {}", version);
+        }
+        let timestamp = u64::from_le_bytes(take(bytes, &mut pos, 8)?.try_into().unwrap());
+        let original_size = u64::from_le_bytes(take(bytes, &mut pos, 8)?.try_into().unwrap());
+        let checksum = u32::from_le_bytes(take(bytes, &mut pos, 4)?.try_into().unwrap());
+        let mode_len = u16::from_le_bytes(take(bytes, &mut pos, 2)?.try_into().unwrap()) as usize;
+        let mode = String::from_utf8(take(bytes, &mut pos, mode_len)?)?;
+        let root_len = u16::from_le_bytes(take(bytes, &mut pos, 2)?.try_into().unwrap()) as usize;
+        let root = String::from_utf8(take(bytes, &mut pos, root_len)?)?;
+
+        Ok((
+            Self {
+                mode,
+                root,
+                timestamp,
+                original_size,
+                checksum,
+            },
+            pos,
+        ))
+    }
+}
+
+/// Compress content into a self-describing COMPRESSED_V2 container.
+pub fn compress_with_container(content: &str, mode: &str, root: &str) -> Result<String> {
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    let compressed = encoder.finish()?;
+
+    let header = ContainerHeader {
+        mode: mode.to_string(),
+        root: root.to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        original_size: content.len() as u64,
+        checksum: crc32fast::hash(content.as_bytes()),
+    };
+
+    let mut container = header.encode();
+    container.extend_from_slice(&compressed);
+
+    // Update stats (same accounting as compress_string)
+    if let Ok(mut state) = COMPRESSION_STATE.write() {
+        state.stats.total_compressions += 1;
+        state.stats.bytes_saved += content.len().saturating_sub(container.len());
+        state.stats.tokens_saved += (content.len() / 4).saturating_sub(container.len() / 4);
+    }
+
+    Ok(format!("COMPRESSED_V2:{}", hex::encode(&container)))
+}
+
+/// Expand a COMPRESSED_V1, COMPRESSED_V2, or COMPRESSED_ZSTD string back to
+/// the original content.
+///
+/// V2 containers are validated (magic, version, size, CRC32) and return their
+/// header; legacy V1 and zstd payloads have no metadata, so the header is
+/// `None`. Zstd payloads compressed with the shared dictionary decompress
+/// transparently when that dictionary is present under `~/.st`.
+pub fn decompress_container(input: &str) -> Result<(String, Option<ContainerHeader>)> {
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    let input = input.trim();
+
+    if let Some(rest) = input.strip_prefix("COMPRESSED_ZSTD:") {
+        let bytes = hex::decode(rest.trim())?;
+        let content =
+            decompress_zstd(&bytes, load_dictionary().as_deref()).inspect_err(|_| {
+                if let Ok(mut state) = COMPRESSION_STATE.write() {
+                    state.stats.failed_decompressions += 1;
+                }
+            })?;
+        return Ok((String::from_utf8(content)?, None));
+    }
+
+    let (hex_payload, versioned) = if let Some(rest) = input.strip_prefix("COMPRESSED_V2:") {
+        (rest, true)
+    } else if let Some(rest) = input.strip_prefix("COMPRESSED_V1:") {
+        (rest, false)
+    } else {
+        anyhow::bail!("Input is not a COMPRESSED_V1 or COMPRESSED_V2 payload");
+    };
+
+    let bytes = hex::decode(hex_payload.trim())?;
+
+    let (header, payload) = if versioned {
+        let (header, header_len) = ContainerHeader::decode(&bytes)?;
+        (Some(header), &bytes[header_len..])
+    } else {
+        (None, &bytes[..])
+    };
+
+    let mut decoder = ZlibDecoder::new(payload);
+    let mut content = String::new();
+    decoder.read_to_string(&mut content).map_err(|e| {
+        if let Ok(mut state) = COMPRESSION_STATE.write() {
+            state.stats.failed_decompressions += 1;
+        }
+        anyhow::anyhow!("Failed to decompress payload: {}", e)
+    })?;
+
+    if let Some(ref header) = header {
+        if content.len() as u64 != header.original_size {
+            anyhow::bail!(
+                "Container size mismatch: header says {} bytes, got {}",
+                header.original_size,
+                content.len()
+            );
+        }
+        let actual_crc = crc32fast::hash(content.as_bytes());
+        if actual_crc != header.checksum {
+            anyhow::bail!(
+                "Container checksum mismatch: expected {:08x}, got {:08x}",
+                header.checksum,
+                actual_crc
+            );
+        }
+    }
+
+    Ok((content, header))
+}
+
+// ---------------------------------------------------------------------------
+// Zstandard (COMPRESSED_ZSTD)
+//
+// Zlib is fine, but tree outputs are wildly repetitive - the same paths,
+// extensions, and emoji over and over - which is exactly what zstd's trained
+// dictionaries were built for. `st dict train` feeds sample outputs to the
+// zstd dictionary builder and stores the result under `~/.st`; once trained,
+// every zstd compression and decompression on this machine shares it, so even
+// tiny outputs compress like they were part of one big archive.
+// ---------------------------------------------------------------------------
+
+/// Which algorithm `-z` should use, parsed from `--compression`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionAlgorithm {
+    /// Legacy zlib (the default, what COMPRESSED_V1/V2 use)
+    #[default]
+    Zlib,
+    /// Zstandard at the given level, using the shared dictionary if trained
+    Zstd { level: i32 },
+}
+
+impl CompressionAlgorithm {
+    /// Parse a `--compression` spec: "zlib", "zstd", or "zstd:LEVEL"
+    /// (levels 1-22; bare "zstd" uses zstd's default level 3).
+    pub fn parse(spec: &str) -> Result<Self> {
+        match spec {
+            "zlib" => Ok(Self::Zlib),
+            "zstd" => Ok(Self::Zstd { level: 3 }),
+            _ => {
+                let level = spec
+                    .strip_prefix("zstd:")
+                    .and_then(|l| l.parse::<i32>().ok())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Unknown compression spec '{}' - use zlib, zstd, or zstd:LEVEL",
+                            spec
+                        )
+                    })?;
+                if !(1..=22).contains(&level) {
+                    anyhow::bail!("zstd level must be 1-22, got {}", level);
+                }
+                Ok(Self::Zstd { level })
+            }
+        }
+    }
+}
+
+/// Where the shared dictionary lives - same `~/.st` home as scan states
+/// and the search index.
+pub fn dictionary_path() -> std::path::PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join(".st")
+        .join("output.dict")
+}
+
+/// Load the trained dictionary, if one exists.
+fn load_dictionary() -> Option<Vec<u8>> {
+    std::fs::read(dictionary_path())
+        .ok()
+        .filter(|bytes| !bytes.is_empty())
+}
+
+/// Train a shared dictionary from sample outputs (the `st dict train`
+/// backend). More samples make better dictionaries - zstd recommends at
+/// least a hundred times the dictionary size in total sample bytes.
+pub fn train_dictionary(samples: &[Vec<u8>], max_size: usize) -> Result<Vec<u8>> {
+    zstd::dict::from_samples(samples, max_size)
+        .map_err(|e| anyhow::anyhow!("Dictionary training failed: {} (try more or larger samples)", e))
+}
+
+/// Compress with the requested algorithm. Zlib produces the legacy
+/// COMPRESSED_V1 payload; zstd produces COMPRESSED_ZSTD, dictionary-aware
+/// when one has been trained.
+pub fn compress_string_with(content: &str, algorithm: CompressionAlgorithm) -> Result<String> {
+    let level = match algorithm {
+        CompressionAlgorithm::Zlib => return compress_string(content),
+        CompressionAlgorithm::Zstd { level } => level,
+    };
+
+    let compressed = compress_zstd(content.as_bytes(), level, load_dictionary().as_deref())?;
+
+    // Update stats (same accounting as compress_string)
+    if let Ok(mut state) = COMPRESSION_STATE.write() {
+        state.stats.total_compressions += 1;
+        state.stats.bytes_saved += content.len().saturating_sub(compressed.len());
+        state.stats.tokens_saved += (content.len() / 4).saturating_sub(compressed.len() / 4);
+    }
+
+    Ok(format!("COMPRESSED_ZSTD:{}", hex::encode(&compressed)))
+}
+
+fn compress_zstd(data: &[u8], level: i32, dictionary: Option<&[u8]>) -> Result<Vec<u8>> {
+    match dictionary {
+        Some(dict) => {
+            let mut compressor = zstd::bulk::Compressor::with_dictionary(level, dict)?;
+            Ok(compressor.compress(data)?)
+        }
+        None => Ok(zstd::bulk::compress(data, level)?),
+    }
+}
+
+fn decompress_zstd(data: &[u8], dictionary: Option<&[u8]>) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    // Plain frames decode directly; dictionary frames only decode once the
+    // matching dictionary is loaded, so try plain first and fall back.
+    if let Ok(content) = zstd::stream::decode_all(data) {
+        return Ok(content);
+    }
+    let Some(dict) = dictionary else {
+        anyhow::bail!(
+            "Failed to decompress zstd payload - it may need the shared dictionary \
+             it was compressed with (train one with `st dict train`)"
+        );
+    };
+    let mut decoder = zstd::stream::read::Decoder::with_dictionary(data, dict)?;
+    let mut content = Vec::new();
+    decoder.read_to_end(&mut content)?;
+    Ok(content)
+}
+
 /// Smart compress any MCP response content
 pub fn smart_compress_mcp_response(response: &mut Value) -> Result<()> {
     // Look for content in the response
@@ -249,6 +669,128 @@ mod tests {
         assert!(compressed.len() < content.len());
     }
 
+    #[test]
+    fn test_container_roundtrip() {
+        let content = "tree output ".repeat(500);
+        let packed = compress_with_container(&content, "ai", "/home/hue/project").unwrap();
+        assert!(packed.starts_with("COMPRESSED_V2:"));
+
+        let (restored, header) = decompress_container(&packed).unwrap();
+        assert_eq!(restored, content);
+
+        let header = header.expect("V2 container should carry a header");
+        assert_eq!(header.mode, "ai");
+        assert_eq!(header.root, "/home/hue/project");
+        assert_eq!(header.original_size, content.len() as u64);
+    }
+
+    #[test]
+    fn test_container_accepts_legacy_v1() {
+        let content = "legacy payload".repeat(100);
+        let packed = compress_string(&content).unwrap();
+        let (restored, header) = decompress_container(&packed).unwrap();
+        assert_eq!(restored, content);
+        assert!(header.is_none(), "V1 payloads carry no metadata");
+    }
+
+    #[test]
+    fn test_container_detects_corruption() {
+        let packed = compress_with_container("important data", "hex", "/tmp").unwrap();
+        // Flip a byte deep in the payload (past the hex-encoded header)
+        let mut corrupted = packed.into_bytes();
+        let last = corrupted.len() - 1;
+        corrupted[last] = if corrupted[last] == b'0' { b'1' } else { b'0' };
+        let corrupted = String::from_utf8(corrupted).unwrap();
+        assert!(decompress_container(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_compression_spec_parsing() {
+        assert_eq!(
+            CompressionAlgorithm::parse("zlib").unwrap(),
+            CompressionAlgorithm::Zlib
+        );
+        assert_eq!(
+            CompressionAlgorithm::parse("zstd").unwrap(),
+            CompressionAlgorithm::Zstd { level: 3 }
+        );
+        assert_eq!(
+            CompressionAlgorithm::parse("zstd:19").unwrap(),
+            CompressionAlgorithm::Zstd { level: 19 }
+        );
+        assert!(CompressionAlgorithm::parse("zstd:99").is_err());
+        assert!(CompressionAlgorithm::parse("brotli").is_err());
+    }
+
+    #[test]
+    fn test_zstd_roundtrip_without_dictionary() {
+        let content = "src/main.rs 1234\nsrc/lib.rs 567\n".repeat(200);
+        let compressed = compress_zstd(content.as_bytes(), 3, None).unwrap();
+        assert!(compressed.len() < content.len());
+        let restored = decompress_zstd(&compressed, None).unwrap();
+        assert_eq!(restored, content.as_bytes());
+    }
+
+    #[test]
+    fn test_zstd_dictionary_roundtrip() {
+        // Raw content dictionaries work without ZDICT training - enough to
+        // prove the dictionary plumbing end to end.
+        let dict = b"src/ tests/ Cargo.toml README.md .rs .toml .md ".repeat(20);
+        let content = "src/scanner.rs\nsrc/formatters/classic.rs\ntests/cli.rs\n";
+
+        let compressed = compress_zstd(content.as_bytes(), 3, Some(&dict)).unwrap();
+        let restored = decompress_zstd(&compressed, Some(&dict)).unwrap();
+        assert_eq!(restored, content.as_bytes());
+
+        // Without the dictionary the frame must refuse to decode, not
+        // silently produce garbage.
+        assert!(decompress_zstd(&compressed, None).is_err());
+    }
+
+    #[test]
+    fn test_negotiation_precedence() {
+        let supported = CompressionState {
+            client_supports_compression: Some(true),
+            ..Default::default()
+        };
+
+        // Per-call override wins over the (absent) session preference
+        assert!(negotiate_with(&supported, Some(true), false).compress);
+        assert!(!negotiate_with(&supported, Some(false), false).compress);
+
+        // Session preference applies when the call doesn't say either way
+        let preferring = CompressionState {
+            client_supports_compression: Some(true),
+            session_preference: Some(true),
+            ..Default::default()
+        };
+        assert!(negotiate_with(&preferring, None, false).compress);
+        assert!(!negotiate_with(&preferring, Some(false), false).compress);
+
+        // Operator overrides beat everything
+        assert!(!negotiate_with(&preferring, Some(true), true).compress);
+        let disabled = CompressionState {
+            disable_compression: true,
+            ..preferring.clone()
+        };
+        assert!(!negotiate_with(&disabled, Some(true), false).compress);
+    }
+
+    #[test]
+    fn test_negotiation_fallback_warns_without_support() {
+        // Client never advertised support: requested compression falls back
+        // to decompressed output with a warning
+        let unknown = CompressionState::default();
+        let decision = negotiate_with(&unknown, Some(true), false);
+        assert!(!decision.compress);
+        assert!(decision.warning.is_some());
+
+        // No compression wanted: no warning either
+        let decision = negotiate_with(&unknown, None, false);
+        assert!(!decision.compress);
+        assert!(decision.warning.is_none());
+    }
+
     #[test]
     fn test_should_compress() {
         set_compression_support(true);
@@ -27,6 +27,82 @@ pub struct StConfig {
     /// Safety/trust settings
     #[serde(default)]
     pub safety: SafetyConfig,
+
+    /// Named scan profiles: `[profile.audit]`, selected via `st --profile audit`
+    #[serde(default, rename = "profile")]
+    pub profiles: HashMap<String, ScanProfile>,
+
+    /// External command policy (see exec_policy module)
+    #[serde(default)]
+    pub exec: ExecConfig,
+}
+
+/// Which external programs st may spawn (`[exec]` table).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ExecConfig {
+    /// Program names st may execute; empty means the built-in defaults
+    /// (git, the OS opener, clipboard tools, service managers)
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+/// One named scan bundle from a `[profile.<name>]` table.
+///
+/// Every field is optional so a profile only pins what it cares about -
+/// explicit CLI flags (or MCP parameters) override whatever it sets:
+///
+/// ```toml
+/// [profile.audit]
+/// mode = "perms"
+/// depth = 10
+/// all = true
+/// no_ignore = true
+///
+/// [profile.quick]
+/// mode = "classic"
+/// depth = 2
+/// glob = ["!**/target/**"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ScanProfile {
+    /// Output format (--mode)
+    pub mode: Option<String>,
+    /// Traversal depth (--depth)
+    pub depth: Option<usize>,
+    /// Show hidden files (--all)
+    pub all: Option<bool>,
+    /// Don't respect .gitignore (--no-ignore)
+    pub no_ignore: Option<bool>,
+    /// Don't apply built-in default ignores (--no-default-ignore)
+    pub no_default_ignore: Option<bool>,
+    /// Show ignored directories in brackets (--show-ignored)
+    pub show_ignored: Option<bool>,
+    /// Regex for matching files (--find)
+    pub find: Option<String>,
+    /// Glob patterns, `!` prefix excludes (--glob)
+    #[serde(default)]
+    pub glob: Vec<String>,
+    /// File extension filter (--type)
+    #[serde(rename = "type")]
+    pub file_type: Option<String>,
+    /// Entry type filter: "f" or "d" (--entry-type)
+    pub entry_type: Option<String>,
+    /// Minimum file size, e.g. "1M" (--min-size)
+    pub min_size: Option<String>,
+    /// Maximum file size, e.g. "100K" (--max-size)
+    pub max_size: Option<String>,
+    /// Comma-separated sort spec (--sort)
+    pub sort: Option<String>,
+    /// Directories before files (--dirs-first)
+    pub dirs_first: Option<bool>,
+    /// Files before directories (--files-first)
+    pub files_first: Option<bool>,
+    /// Keep only the top N results (--top)
+    pub top: Option<usize>,
+    /// Suppress emoji (--no-emoji)
+    pub no_emoji: Option<bool>,
+    /// Compress output (--compress)
+    pub compress: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -76,6 +152,9 @@ pub struct DaemonConfig {
     pub auto_start: bool,
     /// Allow external connections (not just localhost)
     pub allow_external: bool,
+    /// Scan result cache settings
+    #[serde(default)]
+    pub cache: CacheConfig,
 }
 
 impl Default for DaemonConfig {
@@ -84,6 +163,25 @@ impl Default for DaemonConfig {
             port: 8420,
             auto_start: false,
             allow_external: false,
+            cache: CacheConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Shared cache root for multi-user hosts (CI runners). Each user gets
+    /// their own namespace under it. Unset = private ~/.st/cache.
+    pub shared_dir: Option<PathBuf>,
+    /// Per-user cache quota in bytes; oldest entries evicted past it
+    pub max_bytes: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            shared_dir: None,
+            max_bytes: 512 * 1024 * 1024, // 512 MB per user
         }
     }
 }
@@ -180,6 +278,27 @@ impl StConfig {
         })
     }
 
+    /// Look up a named scan profile, listing the defined ones when the
+    /// name doesn't match (typos shouldn't mean re-reading the config)
+    pub fn scan_profile(&self, name: &str) -> Result<&ScanProfile> {
+        self.profiles.get(name).ok_or_else(|| {
+            if self.profiles.is_empty() {
+                anyhow::anyhow!(
+                    "No profiles defined in ~/.st/config.toml (expected a [profile.{}] table)",
+                    name
+                )
+            } else {
+                let mut known: Vec<_> = self.profiles.keys().cloned().collect();
+                known.sort();
+                anyhow::anyhow!(
+                    "Unknown profile '{}'. Defined profiles: {}",
+                    name,
+                    known.join(", ")
+                )
+            }
+        })
+    }
+
     /// Check if a model is blocked
     pub fn is_model_blocked(&self, model: &str) -> bool {
         self.models.blocked.iter().any(|b| model.contains(b))
@@ -1,10 +1,14 @@
 //! Content detection engine - "Understanding what's in your directories" - Omni
 //! Analyzes directory contents to determine the type of project/collection
 
-use crate::scanner::FileNode;
+use crate::scanner::{FileCategory, FileNode};
 use std::collections::HashMap;
 use std::path::Path;
 
+/// Bytes read for magic sniffing - enough to reach the tar "ustar" marker
+/// at offset 257.
+const SNIFF_BYTES: usize = 512;
+
 /// Types of content that can be detected in a directory
 #[derive(Debug, Clone, PartialEq)]
 pub enum DirectoryType {
@@ -84,6 +88,130 @@ pub enum Framework {
 pub struct ContentDetector;
 
 impl ContentDetector {
+    /// Display name for a source language, keyed by file extension
+    /// (lowercase, no dot). Returns None for non-source files so callers
+    /// can skip binaries, images, and data without their own deny-list.
+    pub fn language_name(ext: &str) -> Option<&'static str> {
+        Some(match ext {
+            "rs" => "Rust",
+            "py" | "pyi" => "Python",
+            "js" | "mjs" | "cjs" | "jsx" => "JavaScript",
+            "ts" | "tsx" => "TypeScript",
+            "go" => "Go",
+            "java" => "Java",
+            "c" | "h" => "C",
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" => "C++",
+            "cs" => "C#",
+            "rb" => "Ruby",
+            "php" => "PHP",
+            "swift" => "Swift",
+            "kt" | "kts" => "Kotlin",
+            "scala" => "Scala",
+            "sh" | "bash" | "zsh" => "Shell",
+            "pl" | "pm" => "Perl",
+            "r" => "R",
+            "lua" => "Lua",
+            "sql" => "SQL",
+            "hs" => "Haskell",
+            "dart" => "Dart",
+            "zig" => "Zig",
+            "html" | "htm" => "HTML",
+            "xml" => "XML",
+            "css" | "scss" | "sass" => "CSS",
+            "toml" => "TOML",
+            "yaml" | "yml" => "YAML",
+            "md" | "markdown" => "Markdown",
+            "json" => "JSON",
+            _ => return None,
+        })
+    }
+
+    /// Sniff a file's magic bytes and return the category they prove.
+    ///
+    /// Extensions lie (or go missing); the first bytes of an ELF binary, a
+    /// PNG, or a SQLite database do not. Returns None for anything the
+    /// magic table doesn't recognize so extension-based categorization
+    /// still gets the last word on plain text.
+    pub fn sniff_category(path: &Path) -> Option<FileCategory> {
+        use std::io::Read;
+        let mut buf = [0u8; SNIFF_BYTES];
+        let mut file = std::fs::File::open(path).ok()?;
+        let mut read = 0;
+        while read < buf.len() {
+            match file.read(&mut buf[read..]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(_) => return None,
+            }
+        }
+        Self::sniff_magic(&buf[..read])
+    }
+
+    /// The magic table itself, pure so it's testable without files.
+    pub fn sniff_magic(buf: &[u8]) -> Option<FileCategory> {
+        // --- Executables ---
+        if buf.starts_with(b"\x7fELF") {
+            return Some(FileCategory::Binary);
+        }
+        // Mach-O: thin (feedface/feedfacf, both endiannesses) and fat (cafebabe,
+        // which doubles as the Java class magic - still Binary either way)
+        if buf.len() >= 4 {
+            let magic = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]);
+            if matches!(
+                magic,
+                0xfeedface | 0xfeedfacf | 0xcefaedfe | 0xcffaedfe | 0xcafebabe
+            ) {
+                return Some(FileCategory::Binary);
+            }
+        }
+        // PE (and every other MZ-stub Windows executable)
+        if buf.starts_with(b"MZ") {
+            return Some(FileCategory::Binary);
+        }
+
+        // --- Images ---
+        if buf.starts_with(b"\x89PNG\r\n\x1a\n")
+            || buf.starts_with(b"\xff\xd8\xff")
+            || buf.starts_with(b"GIF87a")
+            || buf.starts_with(b"GIF89a")
+            || buf.starts_with(b"BM")
+            || (buf.len() >= 12 && &buf[0..4] == b"RIFF" && &buf[8..12] == b"WEBP")
+        {
+            return Some(FileCategory::Image);
+        }
+
+        // --- Documents ---
+        if buf.starts_with(b"%PDF") {
+            return Some(FileCategory::Pdf);
+        }
+
+        // --- Databases ---
+        if buf.starts_with(b"SQLite format 3\0") {
+            return Some(FileCategory::Database);
+        }
+
+        // --- Archives ---
+        if buf.starts_with(b"PK\x03\x04")
+            || buf.starts_with(b"\x1f\x8b")
+            || buf.starts_with(b"BZh")
+            || buf.starts_with(b"\xfd7zXZ\x00")
+            || buf.starts_with(b"7z\xbc\xaf\x27\x1c")
+            || (buf.len() >= 262 && &buf[257..262] == b"ustar")
+        {
+            return Some(FileCategory::Archive);
+        }
+
+        // --- Media ---
+        if buf.len() >= 12 && &buf[4..8] == b"ftyp" {
+            return Some(FileCategory::Video); // MP4/MOV family
+        }
+        if buf.starts_with(b"OggS") || buf.starts_with(b"fLaC") || buf.starts_with(b"ID3") {
+            return Some(FileCategory::Audio);
+        }
+
+        None
+    }
+
     /// Analyze nodes and detect directory type
     pub fn detect(nodes: &[FileNode], root_path: &Path) -> DirectoryType {
         // Count file extensions
@@ -0,0 +1,277 @@
+// -----------------------------------------------------------------------------
+// Content Hasher - Because size alone is a terrible judge of character! 🔍
+//
+// Grouping files by size gives you *candidates* for duplication, not proof.
+// This module provides the real evidence: pluggable content hashing (blake3
+// by default, sha256 for the paranoid, xxhash for the impatient) with a
+// partial-hash prefilter so we never read a gigabyte twice just to learn the
+// first 64KB already differ. Hashing runs in parallel via rayon.
+//
+// Used by the waste formatter, the find_duplicates MCP tool, and
+// `st --mode stats --hash`.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::FileNode;
+use anyhow::Result;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How many bytes the partial-hash prefilter reads from the front of a file.
+/// Large enough to discriminate almost everything, small enough to be cheap.
+const PARTIAL_HASH_BYTES: u64 = 64 * 1024;
+
+/// Files at or below this size are fully hashed right away - the prefilter
+/// would read the whole file anyway.
+const SMALL_FILE_THRESHOLD: u64 = PARTIAL_HASH_BYTES;
+
+/// Supported content hash algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    /// Fast, cryptographic, parallel-friendly - the default.
+    #[default]
+    Blake3,
+    /// For when the output needs to match `sha256sum`.
+    Sha256,
+    /// Non-cryptographic but blazing fast (xxh3-128).
+    XxHash,
+}
+
+impl HashAlgorithm {
+    /// Short lowercase name, as accepted on the CLI and in MCP args.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::XxHash => "xxhash",
+        }
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "blake3" | "b3" => Ok(HashAlgorithm::Blake3),
+            "sha256" | "sha-256" => Ok(HashAlgorithm::Sha256),
+            "xxhash" | "xxh3" | "xx" => Ok(HashAlgorithm::XxHash),
+            other => anyhow::bail!(
+                "Unknown hash algorithm '{}' (expected blake3, sha256, or xxhash)",
+                other
+            ),
+        }
+    }
+}
+
+/// A set of files verified to share identical content.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DuplicateGroup {
+    /// Content hash shared by every file in the group
+    pub hash: String,
+    /// Size of each file in bytes
+    pub size: u64,
+    /// All paths with this content (at least two)
+    pub paths: Vec<PathBuf>,
+}
+
+impl DuplicateGroup {
+    /// Bytes reclaimable by keeping one copy and removing the rest.
+    pub fn wasted_bytes(&self) -> u64 {
+        self.size * (self.paths.len().saturating_sub(1)) as u64
+    }
+}
+
+/// The hashing engine. Cheap to construct, safe to share across threads.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContentHasher {
+    algorithm: HashAlgorithm,
+}
+
+impl ContentHasher {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Self { algorithm }
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Hash the full content of a file.
+    pub fn hash_file(&self, path: &Path) -> Result<String> {
+        self.hash_prefix(path, u64::MAX)
+    }
+
+    /// Hash only the first `PARTIAL_HASH_BYTES` of a file (the prefilter).
+    pub fn partial_hash(&self, path: &Path) -> Result<String> {
+        self.hash_prefix(path, PARTIAL_HASH_BYTES)
+    }
+
+    /// Hash up to `limit` bytes from the front of a file, streaming in
+    /// 64KB chunks so memory stays flat regardless of file size.
+    fn hash_prefix(&self, path: &Path, limit: u64) -> Result<String> {
+        let file = File::open(path)?;
+        let mut reader = file.take(limit);
+        let mut buf = [0u8; 64 * 1024];
+
+        match self.algorithm {
+            HashAlgorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                loop {
+                    let n = reader.read(&mut buf)?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buf[..n]);
+                }
+                Ok(hasher.finalize().to_hex().to_string())
+            }
+            HashAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                loop {
+                    let n = reader.read(&mut buf)?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buf[..n]);
+                }
+                Ok(hex::encode(hasher.finalize()))
+            }
+            HashAlgorithm::XxHash => {
+                let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+                loop {
+                    let n = reader.read(&mut buf)?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buf[..n]);
+                }
+                Ok(format!("{:032x}", hasher.digest128()))
+            }
+        }
+    }
+
+    /// Find groups of files with identical content.
+    ///
+    /// Three-stage pipeline, each stage cheaper than the next would be:
+    /// 1. Group by size (free - metadata already scanned)
+    /// 2. Partial hash the first 64KB of surviving candidates (parallel)
+    /// 3. Full hash whatever still collides (parallel)
+    ///
+    /// Unreadable files are silently dropped from consideration - a file we
+    /// can't read can't be confirmed a duplicate of anything.
+    pub fn find_duplicate_groups(&self, nodes: &[FileNode]) -> Vec<DuplicateGroup> {
+        // Stage 1: size groups
+        let mut size_groups: HashMap<u64, Vec<&FileNode>> = HashMap::new();
+        for node in nodes {
+            if !node.is_dir && node.size > 0 && !node.permission_denied && !node.is_symlink {
+                size_groups.entry(node.size).or_default().push(node);
+            }
+        }
+        size_groups.retain(|_, files| files.len() > 1);
+
+        let candidates: Vec<(u64, Vec<&FileNode>)> = size_groups.into_iter().collect();
+
+        // Stage 2 + 3, parallel across size groups
+        let mut groups: Vec<DuplicateGroup> = candidates
+            .par_iter()
+            .flat_map(|(size, files)| {
+                let needs_prefilter = *size > SMALL_FILE_THRESHOLD;
+
+                // Partial-hash prefilter (or straight to full hash for small files)
+                let mut partial_groups: HashMap<String, Vec<&FileNode>> = HashMap::new();
+                for file in files {
+                    let key = if needs_prefilter {
+                        self.partial_hash(&file.path)
+                    } else {
+                        self.hash_file(&file.path)
+                    };
+                    if let Ok(key) = key {
+                        partial_groups.entry(key).or_default().push(file);
+                    }
+                }
+
+                let mut verified = Vec::new();
+                for (partial_key, candidates) in partial_groups {
+                    if candidates.len() < 2 {
+                        continue;
+                    }
+                    if !needs_prefilter {
+                        // Small files: the "partial" hash was already the full hash
+                        verified.push(DuplicateGroup {
+                            hash: partial_key,
+                            size: *size,
+                            paths: candidates.iter().map(|f| f.path.clone()).collect(),
+                        });
+                        continue;
+                    }
+                    // Stage 3: full hash to confirm
+                    let mut full_groups: HashMap<String, Vec<&FileNode>> = HashMap::new();
+                    for file in candidates {
+                        if let Ok(hash) = self.hash_file(&file.path) {
+                            full_groups.entry(hash).or_default().push(file);
+                        }
+                    }
+                    for (hash, dupes) in full_groups {
+                        if dupes.len() > 1 {
+                            verified.push(DuplicateGroup {
+                                hash,
+                                size: *size,
+                                paths: dupes.iter().map(|f| f.path.clone()).collect(),
+                            });
+                        }
+                    }
+                }
+                verified
+            })
+            .collect();
+
+        // Biggest waste first - that's what people want to see
+        groups.sort_by_key(|g| std::cmp::Reverse(g.wasted_bytes()));
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm_parsing() {
+        assert_eq!(
+            HashAlgorithm::from_str("blake3").unwrap(),
+            HashAlgorithm::Blake3
+        );
+        assert_eq!(
+            HashAlgorithm::from_str("SHA256").unwrap(),
+            HashAlgorithm::Sha256
+        );
+        assert_eq!(
+            HashAlgorithm::from_str("xxh3").unwrap(),
+            HashAlgorithm::XxHash
+        );
+        assert!(HashAlgorithm::from_str("md5").is_err());
+    }
+
+    #[test]
+    fn hash_file_distinguishes_content() {
+        let dir = std::env::temp_dir().join(format!("st_hash_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let a = dir.join("a.txt");
+        let b = dir.join("b.txt");
+        let c = dir.join("c.txt");
+        std::fs::write(&a, "same content").unwrap();
+        std::fs::write(&b, "same content").unwrap();
+        std::fs::write(&c, "diff content").unwrap();
+
+        let hasher = ContentHasher::new(HashAlgorithm::Blake3);
+        assert_eq!(hasher.hash_file(&a).unwrap(), hasher.hash_file(&b).unwrap());
+        assert_ne!(hasher.hash_file(&a).unwrap(), hasher.hash_file(&c).unwrap());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,419 @@
+// -----------------------------------------------------------------------------
+// ⚡ Content Search - the ripgrep-class engine behind --search
+// -----------------------------------------------------------------------------
+// The old line-by-line BufReader loop allocated a String per line and walked
+// them one thread at a time. This engine instead:
+//
+//   * memory-maps each file (mmap is free for the page cache to share),
+//   * finds matches with memchr's SIMD substring search over raw bytes,
+//   * sniffs the first chunk for NUL bytes and skips binaries outright,
+//   * refuses files over MAX_SEARCH_FILE_SIZE so one stray core dump
+//     can't stall the scan,
+//   * and fans the per-file work across rayon for the collected-scan path.
+//
+// Match positions, the 100-hit truncation cap, and the optional line-content
+// capture all mirror what the scanner always reported - only the speed
+// changed.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::{FileNode, MatchCapture, SearchMatches};
+use anyhow::{Context, Result};
+use memmap2::Mmap;
+use rayon::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/// Files larger than this are never content-searched (guards against
+/// mapping giant artifacts nobody greps by hand either).
+pub const MAX_SEARCH_FILE_SIZE: u64 = 50 * 1024 * 1024;
+
+/// How many leading bytes the binary sniff inspects.
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// Matches (and `SearchMatches::positions`) stop accumulating here, the
+/// same cap the old engine enforced.
+const MAX_MATCHES: usize = 100;
+
+/// A NUL byte in the leading chunk marks the file as binary - the same
+/// heuristic grep and ripgrep lead with.
+pub fn looks_binary(prefix: &[u8]) -> bool {
+    memchr::memchr(0, &prefix[..prefix.len().min(BINARY_SNIFF_BYTES)]).is_some()
+}
+
+/// How multiple patterns combine: does one hit suffice, or must every
+/// pattern appear somewhere in the file?
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// Any pattern matching includes the file (grep -e ... -e ...).
+    #[default]
+    Any,
+    /// Every pattern must match at least once; reported matches still
+    /// cover all patterns.
+    All,
+}
+
+/// A compiled content-search query: one or more patterns plus the options
+/// shared by the CLI `--search` flags and the MCP `search_in_files` tool.
+///
+/// Single case-sensitive literals keep the memchr SIMD fast path; anything
+/// fancier (regex, case folding, word boundaries, several patterns) compiles
+/// to byte-oriented regexes so matching still runs over the raw mmap without
+/// UTF-8 validation.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    /// Fast path: the sole pattern, when it needs no regex machinery.
+    literal: Option<String>,
+    /// Compiled patterns, in user order.
+    regexes: Vec<regex::bytes::Regex>,
+    /// AND vs OR across patterns.
+    mode: MatchMode,
+    /// The patterns as the user wrote them (for index prefiltering).
+    raw_patterns: Vec<String>,
+    /// Whether `raw_patterns` are regexes rather than literals.
+    is_regex: bool,
+}
+
+impl SearchQuery {
+    /// Compile patterns with the given options. Non-regex patterns are
+    /// escaped, so `--search "a.b"` still means a literal dot unless
+    /// `--search-regex` is on.
+    pub fn build(
+        patterns: &[String],
+        use_regex: bool,
+        ignore_case: bool,
+        whole_word: bool,
+        match_all: bool,
+    ) -> Result<Self> {
+        let patterns: Vec<&String> = patterns.iter().filter(|p| !p.is_empty()).collect();
+        if patterns.is_empty() {
+            anyhow::bail!("No search pattern given");
+        }
+        let raw_patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+
+        // One plain literal needs none of the regex machinery.
+        if patterns.len() == 1 && !use_regex && !ignore_case && !whole_word {
+            return Ok(Self {
+                literal: Some(patterns[0].clone()),
+                regexes: Vec::new(),
+                mode: MatchMode::Any,
+                raw_patterns,
+                is_regex: false,
+            });
+        }
+
+        let mut regexes = Vec::with_capacity(patterns.len());
+        for pattern in patterns {
+            let body = if use_regex {
+                pattern.to_string()
+            } else {
+                regex::escape(pattern)
+            };
+            let body = if whole_word {
+                format!(r"\b(?:{})\b", body)
+            } else {
+                body
+            };
+            let compiled = regex::bytes::RegexBuilder::new(&body)
+                .case_insensitive(ignore_case)
+                .build()
+                .with_context(|| format!("Invalid search pattern: {}", pattern))?;
+            regexes.push(compiled);
+        }
+
+        Ok(Self {
+            literal: None,
+            regexes,
+            mode: if match_all {
+                MatchMode::All
+            } else {
+                MatchMode::Any
+            },
+            raw_patterns,
+            is_regex: use_regex,
+        })
+    }
+
+    /// Convenience for the common one-literal case.
+    pub fn literal(keyword: &str) -> Result<Self> {
+        Self::build(&[keyword.to_string()], false, false, false, false)
+    }
+
+    /// The raw patterns, when they are plain literals an index can
+    /// prefilter on. Regex queries return None - their trigram sets are
+    /// not knowable from the pattern text.
+    pub fn literal_patterns(&self) -> Option<&[String]> {
+        if self.is_regex {
+            None
+        } else {
+            Some(&self.raw_patterns)
+        }
+    }
+
+    /// True when every pattern must match (AND semantics).
+    pub fn requires_all(&self) -> bool {
+        self.mode == MatchMode::All
+    }
+}
+
+/// Search one file with a compiled query. Returns None for unreadable,
+/// empty, oversized, or binary files, and for files without a hit.
+pub fn search_file(
+    path: &Path,
+    query: &SearchQuery,
+    include_line_content: bool,
+) -> Option<SearchMatches> {
+    let file = fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    if len == 0 || len > MAX_SEARCH_FILE_SIZE {
+        return None;
+    }
+    // Safety: the map is read-only and lives only for this search; a file
+    // mutated mid-scan can at worst change which matches we report, which
+    // is inherent to scanning a live tree.
+    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    search_bytes(&mmap, query, include_line_content)
+}
+
+/// One parallel pass over collected nodes: every candidate (per
+/// `candidate`) gets its `search_matches` filled in, all cores busy.
+pub fn search_nodes(
+    nodes: &mut [FileNode],
+    query: &SearchQuery,
+    include_line_content: bool,
+    candidate: impl Fn(&FileNode) -> bool + Sync,
+) {
+    nodes
+        .par_iter_mut()
+        .filter(|node| !node.is_dir && candidate(node))
+        .for_each(|node| {
+            node.search_matches = search_file(&node.path, query, include_line_content);
+        });
+}
+
+/// One raw match before line/column resolution: (byte offset, captured
+/// groups if the pattern had any).
+type RawMatch = (usize, Option<Vec<String>>);
+
+/// The actual matcher, separated from IO so tests can feed it buffers.
+fn search_bytes(
+    haystack: &[u8],
+    query: &SearchQuery,
+    include_line_content: bool,
+) -> Option<SearchMatches> {
+    if looks_binary(haystack) {
+        return None;
+    }
+
+    // Gather match offsets (and captures) from every pattern, then merge
+    // into byte order so the newline cursor below stays a single forward walk.
+    let mut raw: Vec<RawMatch> = Vec::new();
+    if let Some(literal) = &query.literal {
+        raw.extend(
+            memchr::memmem::find_iter(haystack, literal.as_bytes())
+                .take(MAX_MATCHES + 1)
+                .map(|offset| (offset, None)),
+        );
+    } else {
+        // AND semantics: bail before collecting anything if a pattern is absent.
+        if query.mode == MatchMode::All && !query.regexes.iter().all(|re| re.is_match(haystack)) {
+            return None;
+        }
+        for re in &query.regexes {
+            let has_groups = re.captures_len() > 1;
+            if has_groups {
+                for caps in re.captures_iter(haystack).take(MAX_MATCHES + 1) {
+                    let offset = caps.get(0).map(|m| m.start()).unwrap_or(0);
+                    let groups: Vec<String> = (1..caps.len())
+                        .map(|i| {
+                            caps.get(i)
+                                .map(|m| String::from_utf8_lossy(m.as_bytes()).into_owned())
+                                .unwrap_or_default()
+                        })
+                        .collect();
+                    raw.push((offset, Some(groups)));
+                }
+            } else {
+                raw.extend(
+                    re.find_iter(haystack)
+                        .take(MAX_MATCHES + 1)
+                        .map(|m| (m.start(), None)),
+                );
+            }
+        }
+        raw.sort_by_key(|(offset, _)| *offset);
+    }
+
+    let mut positions = Vec::new();
+    let mut line_content_vec = Vec::new();
+    let mut captures: Vec<MatchCapture> = Vec::new();
+    let mut first_match: Option<(usize, usize)> = None;
+    let mut total_count = 0;
+    let mut truncated = false;
+
+    // Cursor over newline offsets: matches arrive in byte order, so one
+    // forward walk converts offsets to 1-based (line, column) pairs.
+    let mut line_number = 1usize;
+    let mut line_start = 0usize;
+    let mut newlines = memchr::memchr_iter(b'\n', haystack).peekable();
+    let mut last_content_line = 0usize;
+
+    for (offset, groups) in raw {
+        while let Some(&nl) = newlines.peek() {
+            if nl < offset {
+                newlines.next();
+                line_number += 1;
+                line_start = nl + 1;
+            } else {
+                break;
+            }
+        }
+        let column = offset - line_start + 1;
+
+        total_count += 1;
+        if total_count > MAX_MATCHES {
+            truncated = true;
+            break;
+        }
+        if first_match.is_none() {
+            first_match = Some((line_number, column));
+        }
+        if positions.len() < MAX_MATCHES {
+            positions.push((line_number, column));
+        }
+        if let Some(groups) = groups {
+            if captures.len() < MAX_MATCHES {
+                captures.push(MatchCapture {
+                    line: line_number,
+                    column,
+                    groups,
+                });
+            }
+        }
+        if include_line_content
+            && line_number != last_content_line
+            && line_content_vec.len() < MAX_MATCHES
+        {
+            let line_end = memchr::memchr(b'\n', &haystack[line_start..])
+                .map(|i| line_start + i)
+                .unwrap_or(haystack.len());
+            let line = String::from_utf8_lossy(&haystack[line_start..line_end])
+                .trim_end_matches('\r')
+                .to_string();
+            line_content_vec.push((line_number, line, column));
+            last_content_line = line_number;
+        }
+    }
+
+    first_match.map(|first| SearchMatches {
+        first_match: first,
+        total_count,
+        positions,
+        truncated,
+        line_content: (include_line_content && !line_content_vec.is_empty())
+            .then_some(line_content_vec),
+        captures: (!captures.is_empty()).then_some(captures),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(patterns: &[&str], regex: bool, ci: bool, word: bool, all: bool) -> SearchQuery {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        SearchQuery::build(&patterns, regex, ci, word, all).unwrap()
+    }
+
+    #[test]
+    fn test_positions_are_one_based_lines_and_columns() {
+        let text = b"alpha\nneedle here\nand a needle again\n";
+        let q = SearchQuery::literal("needle").unwrap();
+        let matches = search_bytes(text, &q, false).expect("should match");
+        assert_eq!(matches.first_match, (2, 1));
+        assert_eq!(matches.total_count, 2);
+        assert_eq!(matches.positions, vec![(2, 1), (3, 7)]);
+        assert!(!matches.truncated);
+    }
+
+    #[test]
+    fn test_line_content_capture_once_per_line() {
+        let text = b"x\nkey key key\n";
+        let q = SearchQuery::literal("key").unwrap();
+        let matches = search_bytes(text, &q, true).expect("should match");
+        assert_eq!(matches.total_count, 3);
+        let lines = matches.line_content.expect("content requested");
+        assert_eq!(lines, vec![(2, "key key key".to_string(), 1)]);
+    }
+
+    #[test]
+    fn test_truncation_caps_at_one_hundred() {
+        let text = "hit ".repeat(250);
+        let q = SearchQuery::literal("hit").unwrap();
+        let matches = search_bytes(text.as_bytes(), &q, false).expect("should match");
+        assert!(matches.truncated);
+        assert_eq!(matches.total_count, 101);
+        assert_eq!(matches.positions.len(), 100);
+    }
+
+    #[test]
+    fn test_binary_files_are_skipped() {
+        let mut bytes = b"needle".to_vec();
+        bytes.push(0);
+        assert!(looks_binary(&bytes));
+        let q = SearchQuery::literal("needle").unwrap();
+        assert!(search_bytes(&bytes, &q, false).is_none());
+    }
+
+    #[test]
+    fn test_search_file_respects_size_guard() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small.txt");
+        std::fs::write(&path, "a needle in a haystack\n").unwrap();
+        let q = SearchQuery::literal("needle").unwrap();
+        let matches = search_file(&path, &q, false).expect("should match");
+        assert_eq!(matches.first_match, (1, 3));
+        // Empty files short-circuit to None; empty patterns refuse to build.
+        let empty = dir.path().join("empty.txt");
+        std::fs::write(&empty, "").unwrap();
+        assert!(search_file(&empty, &q, false).is_none());
+        assert!(SearchQuery::build(&[String::new()], false, false, false, false).is_err());
+    }
+
+    #[test]
+    fn test_case_and_word_options() {
+        let text = b"Need needle NEEDLE kneed\n";
+        // Case-insensitive plain: hits needle, NEEDLE (and inside kneed? no -
+        // 'kneed' has no 'needle'), not Need.
+        let q = query(&["needle"], false, true, false, false);
+        assert_eq!(search_bytes(text, &q, false).unwrap().total_count, 2);
+        // Whole-word: "need" must not match inside "needle" or "kneed".
+        let q = query(&["need"], false, true, true, false);
+        assert_eq!(search_bytes(text, &q, false).unwrap().total_count, 1);
+    }
+
+    #[test]
+    fn test_multi_pattern_any_and_all() {
+        let text = b"alpha\nbeta\n";
+        let any = query(&["alpha", "missing"], false, false, false, false);
+        assert_eq!(search_bytes(text, &any, false).unwrap().total_count, 1);
+        let all = query(&["alpha", "missing"], false, false, false, true);
+        assert!(search_bytes(text, &all, false).is_none());
+        let both = query(&["alpha", "beta"], false, false, false, true);
+        let matches = search_bytes(text, &both, false).unwrap();
+        assert_eq!(matches.total_count, 2);
+        assert_eq!(matches.positions, vec![(1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn test_regex_captures_reported() {
+        let text = b"version = \"1.2.3\"\n";
+        let q = query(&[r#"version = "(\d+)\.(\d+)"#], true, false, false, false);
+        let matches = search_bytes(text, &q, false).expect("should match");
+        let captures = matches.captures.expect("groups requested");
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].line, 1);
+        assert_eq!(captures[0].groups, vec!["1".to_string(), "2".to_string()]);
+        // Invalid regex surfaces as a build error, not a silent no-match.
+        assert!(SearchQuery::build(&["(".to_string()], true, false, false, false).is_err());
+    }
+}
@@ -99,6 +99,8 @@ pub struct DaemonState {
     pub github_oauth: Option<GitHubOAuthConfig>,
     /// Hot Watcher - Wave-powered real-time directory intelligence (MEM8)
     pub hot_watcher: Arc<RwLock<HotWatcher>>,
+    /// Scan result cache - shared-dir aware, per-user namespaced
+    pub shared_cache: Arc<crate::shared_cache::SharedCache>,
 }
 
 /// System-wide context
@@ -201,6 +203,13 @@ pub async fn start_daemon(config: DaemonConfig) -> Result<()> {
     let hot_watcher = Arc::new(RwLock::new(HotWatcher::new()));
     println!("  🔥 Hot Watcher: ready (MEM8 waves)");
 
+    // Open the scan cache - shared dir (CI runners) or private ~/.st/cache
+    let cache_config = crate::config::StConfig::load()
+        .map(|c| c.daemon.cache)
+        .unwrap_or_default();
+    let shared_cache = Arc::new(crate::shared_cache::SharedCache::open(&cache_config)?);
+    println!("  📦 Cache: {}", shared_cache.user_dir().display());
+
     let state = Arc::new(RwLock::new(DaemonState {
         context: SystemContext::default(),
         credits: CreditTracker::default(),
@@ -212,6 +221,7 @@ pub async fn start_daemon(config: DaemonConfig) -> Result<()> {
         sessions,
         github_oauth,
         hot_watcher,
+        shared_cache,
     }));
 
     println!("  🤖 LLM Providers: {} available", provider_count);
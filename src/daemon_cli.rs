@@ -7,25 +7,13 @@
 //! "The meat stays in the daemon!" - Hue
 
 use crate::formatters::{
-    ai::AiFormatter,
     classic::ClassicFormatter,
-    csv::CsvFormatter,
-    digest::DigestFormatter,
-    hex::HexFormatter,
-    json::JsonFormatter,
-    ls::LsFormatter,
-    markdown::MarkdownFormatter,
-    marqant::MarqantFormatter,
-    mermaid::{MermaidFormatter, MermaidStyle},
-    projects::ProjectsFormatter,
-    quantum::QuantumFormatter,
-    semantic::SemanticFormatter,
-    smart::SmartFormatter,
-    stats::StatsFormatter,
-    tsv::TsvFormatter,
-    waste::WasteFormatter,
-    Formatter, PathDisplayMode,
+    relations_formatter::{
+        CallGraphFormat, CallGraphFormatter, RelationsDotFormatter, RelationsFormatter,
+    },
+    Formatter, FormatterOptions, FormatterRegistry, PathDisplayMode,
 };
+use crate::compression_manager::{compress_string_with, CompressionAlgorithm};
 use crate::{parse_size, Scanner, ScannerConfig, TreeStats};
 use anyhow::{Context, Result};
 use axum::{
@@ -80,12 +68,24 @@ pub struct CliScanRequest {
     /// Find pattern (regex for filename matching)
     pub find: Option<String>,
 
+    /// Glob patterns with include/exclude semantics (--glob; `!` excludes)
+    #[serde(default)]
+    pub glob: Vec<String>,
+
     /// File type filter (e.g., "rs", "py")
     pub file_type: Option<String>,
 
     /// Entry type filter ("f" for files, "d" for directories)
     pub entry_type: Option<String>,
 
+    /// "skip" removes device files, sockets, and FIFOs (--special-files)
+    #[serde(default)]
+    pub special_files: Option<String>,
+
+    /// Git state filter ("tracked", "untracked", "ignored", "modified")
+    #[serde(default)]
+    pub git_filter: Option<String>,
+
     /// Min file size (e.g., "1M", "500K")
     pub min_size: Option<String>,
 
@@ -101,10 +101,38 @@ pub struct CliScanRequest {
     /// Search content keyword
     pub search: Option<String>,
 
+    /// Additional search patterns (repeated --search)
+    #[serde(default)]
+    pub search_patterns: Vec<String>,
+
+    /// Treat search patterns as regular expressions
+    #[serde(default)]
+    pub search_regex: bool,
+
+    /// Case-insensitive content search
+    #[serde(default)]
+    pub search_ignore_case: bool,
+
+    /// Match search patterns only at word boundaries
+    #[serde(default)]
+    pub search_word: bool,
+
+    /// Require every search pattern to match (AND semantics)
+    #[serde(default)]
+    pub search_all: bool,
+
     /// Enable zlib compression on output
     #[serde(default)]
     pub compress: bool,
 
+    /// Compression algorithm when `compress` is set ("zlib" or "zstd[:level]")
+    #[serde(default)]
+    pub compression: Option<String>,
+
+    /// Keep formatted output under roughly this many tokens
+    #[serde(default)]
+    pub token_budget: Option<usize>,
+
     /// No emoji in output
     #[serde(default)]
     pub no_emoji: bool,
@@ -123,6 +151,34 @@ pub struct CliScanRequest {
     /// Relations filter
     pub relations_filter: Option<String>,
 
+    /// Render relations as Graphviz DOT instead of text
+    #[serde(default)]
+    pub dot: bool,
+
+    /// Export the function-level call graph: "dot" or "json"
+    #[serde(default)]
+    pub call_graph: Option<String>,
+
+    /// Append the cycle-failure marker so the client can gate its exit code
+    #[serde(default)]
+    pub fail_on_cycles: bool,
+
+    /// Decorate entries with git status markers (classic/ls modes)
+    #[serde(default)]
+    pub git_status: bool,
+
+    /// Append last commit age and author per file (classic/ls modes)
+    #[serde(default)]
+    pub git_blame_summary: bool,
+
+    /// Semantic categories that --watch repaints for (empty = all)
+    #[serde(default)]
+    pub watch_filter: Vec<String>,
+
+    /// Debounce window for --watch, in milliseconds
+    #[serde(default = "default_watch_debounce")]
+    pub watch_debounce: u64,
+
     /// Show filesystem type indicators
     #[serde(default)]
     pub show_filesystems: bool,
@@ -135,6 +191,159 @@ pub struct CliScanRequest {
     #[serde(default)]
     pub compact: bool,
 
+    /// Descend into archives (.zip, .tar.gz, .jar, .whl) as virtual subtrees
+    #[serde(default)]
+    pub scan_archives: bool,
+
+    /// Count each hard-linked inode once in size totals
+    #[serde(default)]
+    pub dedupe_hardlinks: bool,
+
+    /// Append a section listing permission-denied paths
+    #[serde(default)]
+    pub report_denied: bool,
+
+    /// With report_denied: stat denied paths via a minimal sudo helper
+    #[serde(default)]
+    pub sudo_helper: bool,
+
+    /// Report allocated block sizes like `du` (with sparse-file detection)
+    #[serde(default)]
+    pub du: bool,
+
+    /// Roll file sizes up into directories, ncdu-style (classic mode)
+    #[serde(default)]
+    pub dir_sizes: bool,
+
+    /// Force the classic walkdir scanner instead of the native quantum walker
+    #[serde(default)]
+    pub classic_walker: bool,
+
+    /// List directories before files in sorted output
+    #[serde(default)]
+    pub dirs_first: bool,
+
+    /// List files before directories in sorted output
+    #[serde(default)]
+    pub files_first: bool,
+
+    /// Stop descending into directories matching these globs, keeping the
+    /// directory entry with its aggregate size
+    #[serde(default)]
+    pub prune: Vec<String>,
+
+    /// Cap direct entries per directory, collapsing overflow into a
+    /// "(+N more)" summary node
+    #[serde(default)]
+    pub max_files_per_dir: Option<usize>,
+
+    /// Scan budget: stop cleanly after this many entries
+    #[serde(default)]
+    pub max_entries: Option<usize>,
+
+    /// Scan budget: stop cleanly after this many file bytes (e.g. "500M")
+    #[serde(default)]
+    pub max_bytes_scanned: Option<String>,
+
+    /// Scan budget: stop cleanly after this many seconds
+    #[serde(default)]
+    pub timeout: Option<u64>,
+
+    /// List NTFS alternate data streams as virtual child entries (Windows)
+    #[serde(default)]
+    pub ads: bool,
+
+    /// Collect extended attributes and POSIX ACLs per entry (Linux)
+    #[serde(default)]
+    pub xattrs: bool,
+
+    /// Verify duplicates by content hash in stats mode (blake3, sha256, xxhash)
+    pub hash: Option<String>,
+
+    /// Per-directory extension statistics in stats/json modes (recursive)
+    #[serde(default)]
+    pub per_dir: bool,
+
+    /// SBOM document format for licenses mode (cyclonedx or spdx)
+    #[serde(default)]
+    pub sbom: Option<String>,
+
+    /// Graph syntax for deps mode (mermaid or dot)
+    #[serde(default)]
+    pub graph: Option<String>,
+
+    /// Per-language code/comment/blank line counts (stats, markdown modes)
+    #[serde(default)]
+    pub loc: bool,
+
+    /// Sniff magic bytes to correct file categories (--detect-content)
+    #[serde(default)]
+    pub detect_content: bool,
+
+    /// Base URL for mermaid click directives (--mermaid-links)
+    #[serde(default)]
+    pub mermaid_links: Option<String>,
+
+    /// Group mermaid nodes into per-package subgraphs (--mermaid-subgraphs)
+    #[serde(default)]
+    pub mermaid_subgraphs: bool,
+
+    /// TOML report template path for markdown mode (--report-template)
+    #[serde(default)]
+    pub report_template: Option<String>,
+
+    /// Probe media headers for dimensions/duration (--media-info)
+    #[serde(default)]
+    pub media_info: bool,
+
+    /// Single-character CSV field delimiter (--csv-delimiter)
+    #[serde(default)]
+    pub csv_delimiter: Option<String>,
+
+    /// Prefix CSV output with a UTF-8 BOM (--csv-bom)
+    #[serde(default)]
+    pub csv_bom: bool,
+
+    /// Column spec for ls/classic modes (--columns)
+    #[serde(default)]
+    pub columns: Option<String>,
+
+    /// Sizes in powers of 1000 in ls mode (--si)
+    #[serde(default)]
+    pub si: bool,
+
+    /// Sizes as 1K-block counts in ls mode (-k)
+    #[serde(default)]
+    pub kibibytes: bool,
+
+    /// Timestamp style for ls mode (--time-style)
+    #[serde(default)]
+    pub time_style: Option<String>,
+
+    /// Middle-truncate long names in classic mode (--max-name-width)
+    #[serde(default)]
+    pub max_name_width: Option<usize>,
+
+    /// Subset/order of hex-mode fields (--hex-fields)
+    #[serde(default)]
+    pub hex_fields: Option<String>,
+
+    /// Cold→hot gradient on classic-mode names (--heatmap size|age)
+    #[serde(default)]
+    pub heatmap: Option<String>,
+
+    /// Per-directory Merkle digests in digest mode (--merkle)
+    #[serde(default)]
+    pub merkle: bool,
+
+    /// Length of the stats-mode top lists (--stats-top)
+    #[serde(default)]
+    pub stats_top: Option<usize>,
+
+    /// Regex narrowing stats mode to matching relative paths (--stats-filter)
+    #[serde(default)]
+    pub stats_filter: Option<String>,
+
     // --- Smart Scanning Options (Phase 2: Intelligent Context-Aware Scanning) ---
 
     /// Enable smart mode - groups by interest, shows changes, minimal output
@@ -166,6 +375,10 @@ fn default_path_mode() -> String {
     "relative".to_string()
 }
 
+fn default_watch_debounce() -> u64 {
+    250
+}
+
 /// CLI scan response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CliScanResponse {
@@ -222,27 +435,46 @@ pub async fn cli_scan_handler(
             .join(&path)
     };
 
-    // Create scanner and scan
-    let scanner = Scanner::new(&path, config).map_err(|e| {
-        (
-            StatusCode::BAD_REQUEST,
-            Json(CliErrorResponse {
-                error: "Failed to create scanner".to_string(),
-                details: Some(e.to_string()),
-            }),
-        )
-    })?;
+    // Backend selection: the native quantum walker is the default for large
+    // scans it can serve with full parity (it shares the classic scanner's
+    // rule engine, so output is identical). --classic-walker forces the
+    // walkdir-based scanner, which remains the reference implementation.
+    let use_native = !req.classic_walker
+        && crate::quantum_scanner::supports(&config)
+        && crate::quantum_scanner::looks_large(&path);
 
     let scan_start = Instant::now();
-    let (nodes, tree_stats) = scanner.scan().map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(CliErrorResponse {
-                error: "Scan failed".to_string(),
-                details: Some(e.to_string()),
-            }),
-        )
-    })?;
+    let (nodes, tree_stats) = if use_native {
+        crate::quantum_scanner::scan_nodes(&path, config).map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(CliErrorResponse {
+                    error: "Scan failed".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+        })?
+    } else {
+        let scanner = Scanner::new(&path, config).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(CliErrorResponse {
+                    error: "Failed to create scanner".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+        })?;
+
+        scanner.scan().map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(CliErrorResponse {
+                    error: "Scan failed".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+        })?
+    };
     let scan_time = scan_start.elapsed();
 
     // Select formatter and format output
@@ -250,7 +482,24 @@ pub async fn cli_scan_handler(
     let path_display = parse_path_mode(&req.path_mode);
 
     let mut output_buffer = Vec::new();
-    format_output(&req, &mut output_buffer, &nodes, &tree_stats, &path, path_display).map_err(
+    let format_result = if let Some(budget) = req.token_budget {
+        // --token-budget: re-render shallower until the estimate fits,
+        // then append a note saying what was cut
+        crate::token_budget::fit_to_budget(budget, &nodes, |subset| {
+            let mut buf = Vec::new();
+            format_output(&req, &mut buf, subset, &tree_stats, &path, path_display)?;
+            Ok(String::from_utf8_lossy(&buf).into_owned())
+        })
+        .map(|(fitted, outcome)| {
+            output_buffer.extend_from_slice(fitted.as_bytes());
+            if let Some(note) = outcome.note() {
+                output_buffer.extend_from_slice(format!("\n{}\n", note).as_bytes());
+            }
+        })
+    } else {
+        format_output(&req, &mut output_buffer, &nodes, &tree_stats, &path, path_display)
+    };
+    format_result.map_err(
         |e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -264,7 +513,33 @@ pub async fn cli_scan_handler(
     let format_time = format_start.elapsed();
 
     // Optionally compress
-    let (output, compressed) = if req.compress {
+    let algorithm = match req.compression.as_deref() {
+        Some(spec) => CompressionAlgorithm::parse(spec).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(CliErrorResponse {
+                    error: "Invalid compression spec".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+        })?,
+        None => CompressionAlgorithm::Zlib,
+    };
+    let (output, compressed) = if req.compress && algorithm != CompressionAlgorithm::Zlib {
+        // Zstd payloads are self-describing (COMPRESSED_ZSTD hex) so
+        // `st decompress` can expand them later, dictionary and all.
+        let text = String::from_utf8_lossy(&output_buffer);
+        let packed = compress_string_with(&text, algorithm).map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(CliErrorResponse {
+                    error: "Compression failed".to_string(),
+                    details: Some(e.to_string()),
+                }),
+            )
+        })?;
+        (packed, true)
+    } else if req.compress {
         let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
         encoder.write_all(&output_buffer).map_err(|e| {
             (
@@ -331,13 +606,19 @@ pub async fn cli_stream_handler(
 }
 
 /// Build ScannerConfig from CliScanRequest
-fn build_scanner_config(req: &CliScanRequest) -> Result<ScannerConfig> {
+pub(crate) fn build_scanner_config(req: &CliScanRequest) -> Result<ScannerConfig> {
     let find_pattern = if let Some(ref pattern) = req.find {
         Some(Regex::new(pattern).context("Invalid find pattern regex")?)
     } else {
         None
     };
 
+    let glob_filter = if req.glob.is_empty() {
+        None
+    } else {
+        Some(crate::scanner::GlobFilter::new(&req.glob)?)
+    };
+
     let min_size = if let Some(ref s) = req.min_size {
         Some(parse_size(s).context("Invalid min_size")?)
     } else {
@@ -350,6 +631,12 @@ fn build_scanner_config(req: &CliScanRequest) -> Result<ScannerConfig> {
         None
     };
 
+    let budget_bytes = if let Some(ref s) = req.max_bytes_scanned {
+        Some(parse_size(s).context("Invalid max_bytes_scanned")?)
+    } else {
+        None
+    };
+
     // Determine depth based on mode if not specified
     let max_depth = if req.depth == 0 {
         get_ideal_depth_for_mode(&req.mode)
@@ -360,18 +647,29 @@ fn build_scanner_config(req: &CliScanRequest) -> Result<ScannerConfig> {
     Ok(ScannerConfig {
         max_depth,
         follow_symlinks: false,
-        respect_gitignore: req.respect_gitignore,
+        // --git-filter ignored has to actually walk the ignored entries it
+        // is selecting, so .gitignore pruning is suspended for that mode
+        respect_gitignore: req.respect_gitignore && req.git_filter.as_deref() != Some("ignored"),
         show_hidden: req.all,
         show_ignored: req.show_ignored,
         find_pattern,
+        glob_filter,
         file_type_filter: req.file_type.clone(),
         entry_type_filter: req.entry_type.clone(),
+        skip_special_files: req.special_files.as_deref() == Some("skip"),
+        git_filter: req.git_filter.clone(),
+        detect_content: req.detect_content,
         min_size,
         max_size,
         newer_than: None, // TODO: parse date strings
         older_than: None,
         use_default_ignores: req.default_ignores,
         search_keyword: req.search.clone(),
+        search_patterns: req.search_patterns.clone(),
+        search_regex: req.search_regex,
+        search_ignore_case: req.search_ignore_case,
+        search_whole_word: req.search_word,
+        search_match_all: req.search_all,
         show_filesystems: req.show_filesystems,
         sort_field: req.sort.clone(),
         top_n: req.top,
@@ -384,6 +682,21 @@ fn build_scanner_config(req: &CliScanRequest) -> Result<ScannerConfig> {
         changes_only: req.changes_only,
         compare_state: None,
         smart_mode: req.smart,
+        scan_archives: req.scan_archives,
+        dedupe_hardlinks: req.dedupe_hardlinks,
+        use_allocated_size: req.du,
+        compute_dir_sizes: req.dir_sizes,
+        list_ads: req.ads,
+        collect_xattrs: req.xattrs,
+        dirs_first: req.dirs_first,
+        files_first: req.files_first,
+        prune_patterns: req.prune.clone(),
+        max_files_per_dir: req.max_files_per_dir,
+        scan_budget: crate::scanner_safety::ScanBudget {
+            max_entries: req.max_entries,
+            max_bytes: budget_bytes,
+            timeout: req.timeout.map(std::time::Duration::from_secs),
+        },
     })
 }
 
@@ -392,7 +705,7 @@ fn get_ideal_depth_for_mode(mode: &str) -> usize {
     match mode.to_lowercase().as_str() {
         "quantum" | "quantum_semantic" => 10,
         "ai" | "semantic" | "smart" => 5,
-        "digest" | "stats" => 20,
+        "digest" | "stats" | "perms" | "secrets" | "licenses" | "deps" => 20,
         "relations" => 3,
         "projects" => 5,
         _ => 3, // Default for classic, json, etc.
@@ -417,93 +730,151 @@ fn format_output(
     root_path: &std::path::Path,
     path_display: PathDisplayMode,
 ) -> Result<()> {
-    let mode = req.mode.to_lowercase();
-    let no_emoji = req.no_emoji;
-    let use_color = req.use_color;
-
-    match mode.as_str() {
-        "classic" => {
-            let formatter = ClassicFormatter::new(no_emoji, use_color, path_display);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "hex" => {
-            let formatter = HexFormatter::new(
-                use_color,
-                no_emoji,
-                req.show_ignored,
-                path_display,
-                req.show_filesystems,
-            );
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "json" => {
-            let formatter = JsonFormatter::new(req.compact);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "ls" => {
-            let formatter = LsFormatter::new(!no_emoji, use_color);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "ai" => {
-            let formatter = AiFormatter::new(no_emoji, path_display);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "stats" => {
-            let formatter = StatsFormatter::new();
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "csv" => {
-            let formatter = CsvFormatter::new();
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "tsv" => {
-            let formatter = TsvFormatter::new();
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "digest" => {
-            let formatter = DigestFormatter::new();
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "quantum" => {
-            let formatter = QuantumFormatter::new();
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "semantic" => {
-            let formatter = SemanticFormatter::new(path_display, no_emoji);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "projects" => {
-            let formatter = ProjectsFormatter::new();
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "mermaid" => {
-            let formatter = MermaidFormatter::new(MermaidStyle::Flowchart, no_emoji, path_display);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "markdown" => {
-            let formatter = MarkdownFormatter::new(path_display, no_emoji, true, true, true);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "waste" => {
-            let formatter = WasteFormatter::new();
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "marqant" => {
-            let formatter = MarqantFormatter::new(path_display, no_emoji);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        "smart" => {
-            // The star of the show! Surface what matters, not everything.
-            let formatter = SmartFormatter::new(use_color, !no_emoji)
-                .with_path_mode(path_display);
-            formatter.format(writer, nodes, stats, root_path)?;
-        }
-        // Default to classic for unknown modes
-        _ => {
-            let formatter = ClassicFormatter::new(no_emoji, use_color, path_display);
-            formatter.format(writer, nodes, stats, root_path)?;
+    // Relations is analyzer-backed rather than node-backed, so it bypasses
+    // the registry. --dot swaps the text report for Graphviz DOT output;
+    // --call-graph exports the function-level graph as DOT or JSON.
+    if req.mode.eq_ignore_ascii_case("relations") {
+        let focus = req.focus.as_ref().map(std::path::PathBuf::from);
+        let formatter: Box<dyn Formatter> = if let Some(format) = &req.call_graph {
+            Box::new(
+                CallGraphFormatter::new(CallGraphFormat::parse(format)?)
+                    .with_fail_on_cycles(req.fail_on_cycles),
+            )
+        } else if req.dot {
+            Box::new(
+                RelationsDotFormatter::new(req.relations_filter.clone(), focus)
+                    .with_fail_on_cycles(req.fail_on_cycles),
+            )
+        } else {
+            Box::new(
+                RelationsFormatter::new(req.relations_filter.clone(), focus)
+                    .with_fail_on_cycles(req.fail_on_cycles),
+            )
+        };
+        formatter.format(writer, nodes, stats, root_path)?;
+        if req.report_denied {
+            write_denied_report(writer, nodes, req.sudo_helper)?;
         }
+        return Ok(());
+    }
+
+    let options = FormatterOptions {
+        no_emoji: req.no_emoji,
+        use_color: req.use_color,
+        path_mode: path_display,
+        compact: req.compact,
+        show_ignored: req.show_ignored,
+        show_filesystems: req.show_filesystems,
+        hash: req.hash.clone(),
+        git_status: req.git_status,
+        git_blame_summary: req.git_blame_summary,
+        dir_sizes: req.dir_sizes,
+        sort: req.sort.clone(),
+        dirs_first: req.dirs_first,
+        files_first: req.files_first,
+        per_dir: req.per_dir,
+        sbom: req.sbom.clone(),
+        graph: req.graph.clone(),
+        loc: req.loc,
+        mermaid_links: req.mermaid_links.clone(),
+        mermaid_subgraphs: req.mermaid_subgraphs,
+        report_template: req.report_template.clone(),
+        media_info: req.media_info,
+        csv_delimiter: req.csv_delimiter.clone(),
+        csv_bom: req.csv_bom,
+        columns: req.columns.clone(),
+        si: req.si,
+        kibibytes: req.kibibytes,
+        time_style: req.time_style.clone(),
+        max_name_width: req.max_name_width,
+        hex_fields: req.hex_fields.clone(),
+        heatmap: req.heatmap.clone(),
+        merkle: req.merkle,
+        stats_top: req.stats_top,
+        stats_filter: req.stats_filter.clone(),
+    };
+
+    let registry = FormatterRegistry::global()
+        .read()
+        .map_err(|_| anyhow::anyhow!("Formatter registry lock poisoned"))?;
+
+    // Registered formatters (built-in or plugin) win; unknown modes fall
+    // back to classic, exactly as the old hard-coded match did
+    let formatter = match registry.create(&req.mode.to_lowercase(), &options) {
+        Some(formatter) => formatter?,
+        None => Box::new(ClassicFormatter::new(
+            options.no_emoji,
+            options.use_color,
+            path_display,
+        )),
+    };
+    formatter.format(writer, nodes, stats, root_path)?;
+
+    // --report-denied: surface what the scan couldn't read instead of
+    // letting permission errors vanish silently
+    if req.report_denied {
+        write_denied_report(writer, nodes, req.sudo_helper)?;
+    }
+
+    Ok(())
+}
+
+/// Append the permission-denied section: one path per line, with stat-only
+/// counts from the sudo helper when that's enabled (and `sudo -n` succeeds).
+fn write_denied_report(
+    writer: &mut dyn Write,
+    nodes: &[crate::FileNode],
+    sudo_helper: bool,
+) -> Result<()> {
+    let denied: Vec<_> = nodes.iter().filter(|n| n.permission_denied).collect();
+    if denied.is_empty() {
+        return Ok(());
     }
 
+    writeln!(writer)?;
+    writeln!(writer, "🔒 Permission denied ({} paths):", denied.len())?;
+    for node in &denied {
+        match sudo_stat_summary(&node.path, sudo_helper) {
+            Some((entries, bytes)) => writeln!(
+                writer,
+                "  {} ({} entries, {} - via sudo helper)",
+                node.path.display(),
+                entries,
+                humansize::format_size(bytes, humansize::BINARY)
+            )?,
+            None => writeln!(writer, "  {}", node.path.display())?,
+        }
+    }
+    if !sudo_helper {
+        writeln!(
+            writer,
+            "  (add --sudo-helper for stat-only counts of these paths)"
+        )?;
+    }
     Ok(())
 }
+
+/// Spawn `sudo -n st --stat-helper <path>` - a minimal privileged helper that
+/// only walks and stats. The daemon itself never escalates. Returns
+/// `(entries, bytes)`, or `None` when sudo needs a password or the helper
+/// fails (both are fine: the path is simply listed without counts).
+fn sudo_stat_summary(path: &std::path::Path, enabled: bool) -> Option<(u64, u64)> {
+    if !enabled {
+        return None;
+    }
+    let exe = std::env::current_exe().ok()?;
+    let output = crate::exec_policy::command("sudo")
+        .ok()?
+        .arg("-n") // Never prompt - the daemon has no terminal to ask on.
+        .arg(exe)
+        .arg("--stat-helper")
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut parts = text.split_whitespace();
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
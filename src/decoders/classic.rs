@@ -1,11 +1,19 @@
 // Classic Decoder - Convert quantum format to human-readable tree
-// TODO: Implement classic tree visualization from quantum stream
+//
+// Without the original node list we can't know which sibling is last, so
+// this renders plain two-space indentation instead of box-drawing
+// connectors - honest about being a reconstruction, not a re-scan.
 
-use super::{QuantumDecoder, QuantumEntry};
+use super::{QuantumDecoder, QuantumEntry, TraversalCode};
 use anyhow::Result;
+use humansize::{format_size, BINARY};
 use std::io::Write;
 
-pub struct ClassicDecoder;
+pub struct ClassicDecoder {
+    depth: usize,
+    dirs: u64,
+    files: u64,
+}
 
 impl Default for ClassicDecoder {
     fn default() -> Self {
@@ -15,23 +23,56 @@ impl Default for ClassicDecoder {
 
 impl ClassicDecoder {
     pub fn new() -> Self {
-        Self
+        Self {
+            depth: 0,
+            dirs: 0,
+            files: 0,
+        }
     }
 }
 
 impl QuantumDecoder for ClassicDecoder {
     fn init(&mut self, _writer: &mut dyn Write) -> Result<()> {
-        // TODO: Implement initialization
         Ok(())
     }
 
-    fn decode_entry(&mut self, _entry: &QuantumEntry, _writer: &mut dyn Write) -> Result<()> {
-        // TODO: Implement classic tree drawing
+    fn decode_entry(&mut self, entry: &QuantumEntry, writer: &mut dyn Write) -> Result<()> {
+        // Bare traversal codes parse as empty-named entries - they only
+        // move the cursor, nothing gets printed for them.
+        if !entry.name.is_empty() {
+            let indent = "  ".repeat(self.depth);
+            let marker = if entry.is_dir {
+                self.dirs += 1;
+                "/"
+            } else {
+                self.files += 1;
+                ""
+            };
+            match entry.size {
+                Some(size) if !entry.is_dir => writeln!(
+                    writer,
+                    "{}{}{} ({})",
+                    indent,
+                    entry.name,
+                    marker,
+                    format_size(size, BINARY)
+                )?,
+                _ => writeln!(writer, "{}{}{}", indent, entry.name, marker)?,
+            }
+        }
+
+        match entry.traversal {
+            TraversalCode::Deeper => self.depth += 1,
+            TraversalCode::Back => self.depth = self.depth.saturating_sub(1),
+            TraversalCode::Same | TraversalCode::Summary => {}
+        }
+
         Ok(())
     }
 
-    fn finish(&mut self, _writer: &mut dyn Write) -> Result<()> {
-        // TODO: Implement summary
+    fn finish(&mut self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer)?;
+        writeln!(writer, "{} directories, {} files", self.dirs, self.files)?;
         Ok(())
     }
 }
@@ -7,6 +7,95 @@ use std::io::Write;
 pub mod classic;
 pub mod hex;
 pub mod json;
+pub mod summary_ai;
+
+/// Requested output for `st decode`. `Auto` picks the natural rendering
+/// per input: marqant -> markdown, quantum and summary-ai -> classic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeTarget {
+    Auto,
+    Classic,
+    Json,
+    Markdown,
+}
+
+/// Decode an archived st output back to readable text, sniffing the
+/// format from its header: COMPRESSED_V1/V2/ZSTD containers (unwrapped and
+/// re-sniffed), MARQANT streams, MEM8_QUANTUM_V1 streams, and
+/// SUMMARY_AI_V1 digests. Plain content passes through unchanged.
+pub fn decode_auto(input: &[u8], target: DecodeTarget) -> Result<String> {
+    if input.starts_with(b"COMPRESSED_V1:")
+        || input.starts_with(b"COMPRESSED_V2:")
+        || input.starts_with(b"COMPRESSED_ZSTD:")
+    {
+        let text = std::str::from_utf8(input)
+            .map_err(|_| anyhow::anyhow!("COMPRESSED payload is not valid UTF-8"))?;
+        let (content, _header) = crate::compression_manager::decompress_container(text)?;
+        // Containers wrap whatever the formatter produced - sniff again.
+        return decode_auto(content.as_bytes(), target);
+    }
+
+    if input.starts_with(b"MARQANT") {
+        let text = std::str::from_utf8(input)
+            .map_err(|_| anyhow::anyhow!("MARQANT stream is not valid UTF-8"))?;
+        let markdown = crate::formatters::marqant::MarqantFormatter::decompress_marqant(text)?;
+        return match target {
+            DecodeTarget::Auto | DecodeTarget::Markdown => Ok(markdown),
+            _ => anyhow::bail!("Marqant streams decode to markdown - use --to markdown"),
+        };
+    }
+
+    if input.starts_with(b"MEM8_QUANTUM_V1_STREAM:") {
+        anyhow::bail!("Streamed quantum output has no data markers and cannot be decoded offline");
+    }
+
+    if input.starts_with(b"MEM8_QUANTUM_V1:") {
+        let payload = quantum_payload(input)?;
+        let mut output = Vec::new();
+        match target {
+            DecodeTarget::Auto | DecodeTarget::Classic => {
+                decode_quantum_stream(payload, &mut classic::ClassicDecoder::new(), &mut output)?
+            }
+            DecodeTarget::Json => {
+                decode_quantum_stream(payload, &mut json::JsonDecoder::new(), &mut output)?
+            }
+            DecodeTarget::Markdown => {
+                anyhow::bail!("Quantum tree streams decode to classic or json - use --to classic")
+            }
+        }
+        return Ok(String::from_utf8_lossy(&output).into_owned());
+    }
+
+    if input.starts_with(b"SUMMARY_AI_V1:") {
+        let text = std::str::from_utf8(input)
+            .map_err(|_| anyhow::anyhow!("SUMMARY_AI stream is not valid UTF-8"))?;
+        return summary_ai::decode(text, target);
+    }
+
+    // No recognizable header - already plain output, nothing to decode.
+    Ok(String::from_utf8_lossy(input).into_owned())
+}
+
+/// Slice the binary section out of a MEM8_QUANTUM_V1 stream - everything
+/// between the `---BEGIN_DATA---` and `---END_DATA---` markers.
+fn quantum_payload(input: &[u8]) -> Result<&[u8]> {
+    const BEGIN: &[u8] = b"---BEGIN_DATA---\n";
+    const END: &[u8] = b"\n---END_DATA---";
+
+    let start = find_subslice(input, BEGIN)
+        .ok_or_else(|| anyhow::anyhow!("Quantum stream is missing its BEGIN_DATA marker"))?
+        + BEGIN.len();
+    let end = find_subslice(&input[start..], END)
+        .map(|offset| start + offset)
+        .ok_or_else(|| anyhow::anyhow!("Quantum stream is missing its END_DATA marker"))?;
+    Ok(&input[start..end])
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
 
 /// Quantum entry components after parsing
 #[derive(Debug, Clone)]
@@ -0,0 +1,88 @@
+// Summary-AI Decoder - expand SUMMARY_AI_V1 streams back to human form
+//
+// The summary-ai format is a lossy digest (hex-packed stats, compact
+// key/value lines), so "decoding" means unpacking those lines into a
+// readable report or a JSON object - not reconstructing the tree.
+
+use super::DecodeTarget;
+use anyhow::Result;
+use humansize::{format_size, BINARY};
+use serde_json::json;
+
+/// Decode a `SUMMARY_AI_V1:` text stream into the requested target.
+pub fn decode(text: &str, target: DecodeTarget) -> Result<String> {
+    let mut path = None;
+    let mut stats = None;
+    // Everything else passes through as (key, value) in stream order.
+    let mut fields: Vec<(String, String)> = Vec::new();
+
+    for line in text.lines().skip(1) {
+        if line == "END_SUMMARY_AI" {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key {
+            "PATH" => path = Some(value.to_string()),
+            "STATS" => stats = parse_stats(value),
+            _ => fields.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    match target {
+        DecodeTarget::Json => {
+            let mut obj = json!({ "format": "summary-ai" });
+            if let Some(path) = path {
+                obj["path"] = json!(path);
+            }
+            if let Some((files, dirs, size)) = stats {
+                obj["files"] = json!(files);
+                obj["dirs"] = json!(dirs);
+                obj["size"] = json!(size);
+            }
+            for (key, value) in fields {
+                obj[key.to_lowercase()] = json!(value);
+            }
+            Ok(format!("{}\n", serde_json::to_string_pretty(&obj)?))
+        }
+        _ => {
+            // Classic and markdown read the same; markdown just gets a
+            // heading instead of the banner line.
+            let mut out = String::new();
+            if target == DecodeTarget::Markdown {
+                out.push_str("# Directory Summary\n\n");
+            } else {
+                out.push_str("Directory Summary (decoded from summary-ai)\n");
+            }
+            if let Some(path) = path {
+                out.push_str(&format!("Path: {}\n", path));
+            }
+            if let Some((files, dirs, size)) = stats {
+                out.push_str(&format!(
+                    "Files: {}  Dirs: {}  Size: {} ({})\n",
+                    files,
+                    dirs,
+                    size,
+                    format_size(size, BINARY)
+                ));
+            }
+            for (key, value) in fields {
+                out.push_str(&format!("{}: {}\n", key, value));
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Unpack `F{:x}D{:x}S{:x}` into (files, dirs, size).
+fn parse_stats(packed: &str) -> Option<(u64, u64, u64)> {
+    let rest = packed.strip_prefix('F')?;
+    let (files_hex, rest) = rest.split_once('D')?;
+    let (dirs_hex, size_hex) = rest.split_once('S')?;
+    Some((
+        u64::from_str_radix(files_hex, 16).ok()?,
+        u64::from_str_radix(dirs_hex, 16).ok()?,
+        u64::from_str_radix(size_hex, 16).ok()?,
+    ))
+}
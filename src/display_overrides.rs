@@ -0,0 +1,177 @@
+// -----------------------------------------------------------------------------
+// 🎨 Per-Directory Display Overrides - Let Projects Curate Themselves!
+// -----------------------------------------------------------------------------
+// A directory can carry a small `.st/display.toml` describing how it wants
+// to be presented - collapse noisy subtrees by default, swap in a custom
+// emoji, or attach a one-line annotation for visitors and AI agents:
+//
+//     # .st/display.toml (describes this directory itself)
+//     emoji = "🧪"
+//     annotation = "Golden fixtures - regenerate with `make fixtures`"
+//
+//     # ...and its children, files included
+//     [entries."vendored"]
+//     collapse = true
+//     annotation = "Third-party code, don't review"
+//
+// The directory's own top-level keys describe itself; the parent's
+// [entries] table wins when both speak about the same entry, so a project
+// can curate children it doesn't control. Malformed or unreadable files
+// are ignored - a typo in display.toml should never break `st`.
+// -----------------------------------------------------------------------------
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Curated presentation for one entry - every field optional so a file
+/// only pins what it cares about.
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct EntryOverride {
+    /// Hide this directory's children behind a `[…]` marker
+    pub collapse: Option<bool>,
+    /// Replace the category emoji (suppressed by --no-emoji like any other)
+    pub emoji: Option<String>,
+    /// One-line note rendered after the name
+    pub annotation: Option<String>,
+}
+
+impl EntryOverride {
+    /// Layer `other` on top of self: set fields in `other` win.
+    fn overlaid(mut self, other: &EntryOverride) -> EntryOverride {
+        if other.collapse.is_some() {
+            self.collapse = other.collapse;
+        }
+        if other.emoji.is_some() {
+            self.emoji = other.emoji.clone();
+        }
+        if other.annotation.is_some() {
+            self.annotation = other.annotation.clone();
+        }
+        self
+    }
+}
+
+/// One parsed `.st/display.toml`: the directory's self-description plus
+/// per-child overrides.
+#[derive(Debug, Clone, Deserialize, Default)]
+struct DisplayFile {
+    #[serde(flatten)]
+    own: EntryOverride,
+    #[serde(default)]
+    entries: HashMap<String, EntryOverride>,
+}
+
+/// Lazy, cached lookup of display overrides for a whole render pass.
+/// Each directory's `.st/display.toml` is read at most once; absence and
+/// parse failures are cached the same as content.
+#[derive(Default)]
+pub struct DisplayOverrides {
+    cache: RwLock<HashMap<PathBuf, Option<DisplayFile>>>,
+}
+
+impl DisplayOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The resolved override for a node: its own self-description (for
+    /// directories) overlaid with whatever its parent's [entries] says.
+    pub fn for_node(&self, path: &Path, is_dir: bool) -> EntryOverride {
+        let mut resolved = EntryOverride::default();
+
+        if is_dir {
+            if let Some(own) = self.with_file(path, |file| Some(file.own.clone())) {
+                resolved = own;
+            }
+        }
+
+        if let (Some(parent), Some(name)) = (path.parent(), path.file_name()) {
+            let name = name.to_string_lossy().to_string();
+            if let Some(entry) =
+                self.with_file(parent, |file| file.entries.get(name.as_str()).cloned())
+            {
+                resolved = resolved.overlaid(&entry);
+            }
+        }
+
+        resolved
+    }
+
+    /// True when the directory asked to be rendered collapsed.
+    pub fn is_collapsed(&self, path: &Path, is_dir: bool) -> bool {
+        is_dir && self.for_node(path, is_dir).collapse == Some(true)
+    }
+
+    /// Run `f` against the parsed display file for `dir`, loading and
+    /// caching it on first touch.
+    fn with_file<T>(&self, dir: &Path, f: impl Fn(&DisplayFile) -> Option<T>) -> Option<T> {
+        if let Some(cached) = self.cache.read().ok()?.get(dir) {
+            return cached.as_ref().and_then(&f);
+        }
+
+        let loaded = std::fs::read_to_string(dir.join(".st").join("display.toml"))
+            .ok()
+            .and_then(|content| toml::from_str::<DisplayFile>(&content).ok());
+        let result = loaded.as_ref().and_then(&f);
+        if let Ok(mut cache) = self.cache.write() {
+            cache.insert(dir.to_path_buf(), loaded);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_display(dir: &Path, content: &str) {
+        let st_dir = dir.join(".st");
+        std::fs::create_dir_all(&st_dir).unwrap();
+        std::fs::write(st_dir.join("display.toml"), content).unwrap();
+    }
+
+    #[test]
+    fn test_self_description_and_parent_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let sub = tmp.path().join("vendored");
+        std::fs::create_dir(&sub).unwrap();
+
+        write_display(&sub, "emoji = \"📦\"\nannotation = \"self says\"\n");
+        write_display(
+            tmp.path(),
+            "[entries.\"vendored\"]\ncollapse = true\nannotation = \"parent wins\"\n",
+        );
+
+        let overrides = DisplayOverrides::new();
+        let resolved = overrides.for_node(&sub, true);
+        assert_eq!(resolved.emoji.as_deref(), Some("📦"));
+        assert_eq!(resolved.annotation.as_deref(), Some("parent wins"));
+        assert!(overrides.is_collapsed(&sub, true));
+    }
+
+    #[test]
+    fn test_malformed_file_is_ignored() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_display(tmp.path(), "this is not [ valid toml");
+
+        let overrides = DisplayOverrides::new();
+        let resolved = overrides.for_node(&tmp.path().join("child.rs"), false);
+        assert!(resolved.annotation.is_none());
+        assert!(!overrides.is_collapsed(tmp.path(), true));
+    }
+
+    #[test]
+    fn test_files_can_be_annotated_by_parent() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_display(
+            tmp.path(),
+            "[entries.\"generated.rs\"]\nannotation = \"machine-written\"\n",
+        );
+
+        let overrides = DisplayOverrides::new();
+        let resolved = overrides.for_node(&tmp.path().join("generated.rs"), false);
+        assert_eq!(resolved.annotation.as_deref(), Some("machine-written"));
+    }
+}
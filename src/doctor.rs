@@ -0,0 +1,572 @@
+//! `st doctor` - one-stop environment checkup.
+//!
+//! When something feels off - MCP tools missing in Claude, a daemon that
+//! won't answer, a config file mangled by a stray edit - the fix is usually
+//! thirty seconds once you know where to look. This module does the looking:
+//! config validity, `~/.st` health, daemon status, MCP wiring for detected
+//! agents, search-index freshness, and terminal capabilities, each with an
+//! actionable suggestion. Trish says a good doctor never just says "it's
+//! broken" - they hand you the prescription too.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// Outcome of one diagnostic check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    /// Working as intended.
+    Ok,
+    /// Not configured / not applicable - fine, just stating the facts.
+    Info,
+    /// Works, but degraded or about to bite.
+    Warn,
+    /// Broken - comes with a fix suggestion.
+    Fail,
+}
+
+impl CheckStatus {
+    fn icon(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "✅",
+            CheckStatus::Info => "ℹ️ ",
+            CheckStatus::Warn => "⚠️ ",
+            CheckStatus::Fail => "❌",
+        }
+    }
+}
+
+/// One line of the doctor's report.
+#[derive(Debug, Serialize)]
+pub struct Check {
+    /// Stable machine-friendly identifier ("config", "daemon", ...).
+    pub name: &'static str,
+    pub status: CheckStatus,
+    /// Human-readable finding.
+    pub detail: String,
+    /// What to run or change to clear a Warn/Fail.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// The full checkup, serializable as-is for `st doctor --json`.
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    pub version: &'static str,
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// How many checks landed on `status`.
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+}
+
+/// Run every check against `project` (normally the current directory).
+pub async fn diagnose(project: &Path) -> DoctorReport {
+    let mut checks = Vec::new();
+
+    checks.push(check_config());
+    checks.extend(check_st_home());
+    checks.push(check_project_st(project));
+    checks.push(check_daemon().await);
+    checks.extend(check_mcp_agents(project));
+    checks.push(check_index(project));
+    checks.extend(check_terminal());
+
+    DoctorReport {
+        version: env!("CARGO_PKG_VERSION"),
+        checks,
+    }
+}
+
+/// Entry point for `st doctor` - prints the report and exits non-zero when
+/// anything actually failed (warnings keep exit code 0 so cron stays quiet).
+pub async fn run(project: &Path, json: bool) -> Result<()> {
+    let report = diagnose(project).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_human(&report);
+    }
+
+    if report.count(CheckStatus::Fail) > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn print_human(report: &DoctorReport) {
+    println!("🩺 Smart Tree doctor (v{})", report.version);
+    println!("{}", "─".repeat(50));
+
+    for check in &report.checks {
+        println!("{} {:<12} {}", check.status.icon(), check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("   ↳ fix: {}", fix);
+        }
+    }
+
+    println!("{}", "─".repeat(50));
+    let (ok, warn, fail) = (
+        report.count(CheckStatus::Ok),
+        report.count(CheckStatus::Warn),
+        report.count(CheckStatus::Fail),
+    );
+    if warn == 0 && fail == 0 {
+        println!("All clear: {} checks passed. Go scan something! 🌳", ok);
+    } else {
+        println!("{} ok, {} warning(s), {} problem(s)", ok, warn, fail);
+    }
+}
+
+// =============================================================================
+// Individual checks
+// =============================================================================
+
+/// Is `~/.st/config.toml` parseable? A typo there silently breaks profiles,
+/// API keys, and the exec allowlist all at once.
+fn check_config() -> Check {
+    let path = match crate::config::StConfig::config_path() {
+        Ok(path) => path,
+        Err(e) => {
+            return Check::new(
+                "config",
+                CheckStatus::Fail,
+                format!("cannot determine config path: {}", e),
+            )
+        }
+    };
+
+    if !path.exists() {
+        return Check::new(
+            "config",
+            CheckStatus::Info,
+            format!("{} not created yet (defaults apply)", path.display()),
+        );
+    }
+
+    match fs::read_to_string(&path) {
+        Ok(content) => match toml::from_str::<crate::config::StConfig>(&content) {
+            Ok(_) => Check::new("config", CheckStatus::Ok, format!("{} valid", path.display())),
+            Err(e) => Check::new(
+                "config",
+                CheckStatus::Fail,
+                format!("{} does not parse: {}", path.display(), first_line(&e.to_string())),
+            )
+            .with_fix("correct the TOML (or move the file aside to regenerate defaults)"),
+        },
+        Err(e) => Check::new(
+            "config",
+            CheckStatus::Fail,
+            format!("{} unreadable: {}", path.display(), e),
+        )
+        .with_fix(format!("chmod u+rw {}", path.display())),
+    }
+}
+
+/// `~/.st` and its cache subdirectories must be writable or scan states,
+/// search indexes, and feedback queues all fail silently.
+fn check_st_home() -> Vec<Check> {
+    let Some(home) = dirs::home_dir() else {
+        return vec![Check::new(
+            "st-home",
+            CheckStatus::Fail,
+            "cannot determine home directory",
+        )
+        .with_fix("set the HOME environment variable")];
+    };
+    let st_dir = home.join(".st");
+
+    if !st_dir.exists() {
+        return vec![Check::new(
+            "st-home",
+            CheckStatus::Info,
+            format!("{} not created yet (made on first use)", st_dir.display()),
+        )];
+    }
+
+    let mut checks = vec![match probe_writable(&st_dir) {
+        Ok(()) => Check::new("st-home", CheckStatus::Ok, format!("{} writable", st_dir.display())),
+        Err(e) => Check::new(
+            "st-home",
+            CheckStatus::Fail,
+            format!("{} not writable: {}", st_dir.display(), e),
+        )
+        .with_fix(format!("chown -R $(whoami) {}", st_dir.display())),
+    }];
+
+    // Cache subdirs sprout lazily; only existing ones can have gone bad.
+    for sub in ["scan_states", "index"] {
+        let dir = st_dir.join(sub);
+        if dir.exists() {
+            if let Err(e) = probe_writable(&dir) {
+                checks.push(
+                    Check::new(
+                        "st-home",
+                        CheckStatus::Fail,
+                        format!("{} not writable: {}", dir.display(), e),
+                    )
+                    .with_fix(format!("chown -R $(whoami) {}", dir.display())),
+                );
+            }
+        }
+    }
+
+    checks
+}
+
+/// Project-local `.st/` health (display overrides, relations cache, mem8).
+fn check_project_st(project: &Path) -> Check {
+    let dir = project.join(".st");
+    if !dir.exists() {
+        return Check::new(
+            "project-st",
+            CheckStatus::Info,
+            format!("{} not present (created on demand)", dir.display()),
+        );
+    }
+    match probe_writable(&dir) {
+        Ok(()) => Check::new(
+            "project-st",
+            CheckStatus::Ok,
+            format!("{} writable", dir.display()),
+        ),
+        Err(e) => Check::new(
+            "project-st",
+            CheckStatus::Fail,
+            format!("{} not writable: {}", dir.display(), e),
+        )
+        .with_fix(format!("chown -R $(whoami) {}", dir.display())),
+    }
+}
+
+/// Is the std daemon answering on its socket?
+async fn check_daemon() -> Check {
+    let socket = crate::std_client::socket_path();
+    if crate::std_client::is_daemon_running().await {
+        Check::new(
+            "daemon",
+            CheckStatus::Ok,
+            format!("running on {}", socket.display()),
+        )
+    } else {
+        Check::new(
+            "daemon",
+            CheckStatus::Info,
+            format!("not running ({})", socket.display()),
+        )
+        .with_fix("st daemon start")
+    }
+}
+
+/// MCP wiring for every agent we can detect on this machine. An agent that
+/// isn't installed is simply not reported - no point prescribing for
+/// patients who never showed up.
+fn check_mcp_agents(project: &Path) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    // Claude Desktop: detected when its config directory exists at all.
+    if let Some(config_path) = crate::claude_init::McpInstaller::get_claude_desktop_config_path() {
+        if config_path.parent().is_some_and(|p| p.exists()) {
+            checks.push(check_claude_desktop(&config_path));
+        }
+    }
+
+    // Claude Code: detected via a .claude directory (project or user).
+    let mut settings_files = Vec::new();
+    let project_claude = project.join(".claude");
+    if project_claude.exists() {
+        settings_files.push(project_claude.join("settings.json"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        let user_claude = home.join(".claude");
+        if user_claude.exists() {
+            settings_files.push(user_claude.join("settings.json"));
+        }
+    }
+    for settings in settings_files {
+        checks.push(check_claude_code_settings(&settings));
+    }
+
+    if checks.is_empty() {
+        checks.push(Check::new(
+            "mcp",
+            CheckStatus::Info,
+            "no AI agents detected (Claude Desktop / Claude Code)",
+        ));
+    }
+
+    checks
+}
+
+/// Validate the Claude Desktop config: JSON parses, smart-tree entry
+/// present, and the binary it points at still exists.
+fn check_claude_desktop(config_path: &Path) -> Check {
+    if !config_path.exists() {
+        return Check::new(
+            "mcp",
+            CheckStatus::Info,
+            "Claude Desktop found, MCP server not installed",
+        )
+        .with_fix("st -i  (installs the smart-tree MCP server)");
+    }
+
+    let config: serde_json::Value = match fs::read_to_string(config_path)
+        .map_err(|e| e.to_string())
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| e.to_string()))
+    {
+        Ok(config) => config,
+        Err(e) => {
+            return Check::new(
+                "mcp",
+                CheckStatus::Fail,
+                format!("{} is not valid JSON: {}", config_path.display(), first_line(&e)),
+            )
+            .with_fix("repair the JSON (a backup from the last install sits next to it)");
+        }
+    };
+
+    let Some(entry) = config["mcpServers"].get("smart-tree") else {
+        return Check::new(
+            "mcp",
+            CheckStatus::Info,
+            "Claude Desktop found, smart-tree not in mcpServers",
+        )
+        .with_fix("st -i  (installs the smart-tree MCP server)");
+    };
+
+    let command = entry["command"].as_str().unwrap_or("st");
+    let binary = PathBuf::from(command);
+    if binary.is_absolute() && !binary.exists() {
+        return Check::new(
+            "mcp",
+            CheckStatus::Fail,
+            format!("Claude Desktop points at missing binary {}", command),
+        )
+        .with_fix("st -i  (rewrites the entry with the current binary path)");
+    }
+
+    Check::new(
+        "mcp",
+        CheckStatus::Ok,
+        format!("Claude Desktop wired to {}", command),
+    )
+}
+
+/// Hook-format validation for a Claude Code settings.json.
+fn check_claude_code_settings(path: &Path) -> Check {
+    match crate::claude_init::validate_settings(path) {
+        Ok(None) => {
+            if path.exists() {
+                Check::new("hooks", CheckStatus::Ok, format!("{} valid", path.display()))
+            } else {
+                Check::new(
+                    "hooks",
+                    CheckStatus::Info,
+                    format!("{} not present", path.display()),
+                )
+            }
+        }
+        Ok(Some(problem)) => Check::new(
+            "hooks",
+            CheckStatus::Fail,
+            format!("{}: {}", path.display(), problem),
+        )
+        .with_fix("st init  (rewrites hooks in the current format)"),
+        Err(e) => Check::new(
+            "hooks",
+            CheckStatus::Warn,
+            format!("{} unreadable: {}", path.display(), e),
+        ),
+    }
+}
+
+/// Search-index freshness for this project - a stale index is silently
+/// slower, not wrong, hence Warn rather than Fail.
+fn check_index(project: &Path) -> Check {
+    let root = match project.canonicalize() {
+        Ok(root) => root,
+        Err(_) => project.to_path_buf(),
+    };
+    match crate::search_index::SearchIndex::status(&root) {
+        Ok(None) => Check::new("index", CheckStatus::Info, "no search index for this project")
+            .with_fix("st index build  (makes repeated --search instant)"),
+        Ok(Some(status)) => {
+            if status.stale + status.missing == 0 {
+                Check::new(
+                    "index",
+                    CheckStatus::Ok,
+                    format!("fresh ({} files indexed)", status.files),
+                )
+            } else {
+                Check::new(
+                    "index",
+                    CheckStatus::Warn,
+                    format!(
+                        "{} of {} indexed files stale or missing",
+                        status.stale + status.missing,
+                        status.files
+                    ),
+                )
+                .with_fix("st index build")
+            }
+        }
+        Err(e) => Check::new(
+            "index",
+            CheckStatus::Warn,
+            format!("index unreadable: {}", first_line(&e.to_string())),
+        )
+        .with_fix("st index clear && st index build"),
+    }
+}
+
+/// Terminal and locale capabilities - emoji trees in a non-UTF-8 locale
+/// come out looking like a ransom note.
+fn check_terminal() -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    if !std::io::stdout().is_terminal() {
+        checks.push(Check::new(
+            "terminal",
+            CheckStatus::Info,
+            "stdout is not a terminal (color and emoji auto-disable)",
+        ));
+        return checks;
+    }
+
+    let term = std::env::var("TERM").unwrap_or_default();
+    if term.is_empty() || term == "dumb" {
+        checks.push(
+            Check::new(
+                "terminal",
+                CheckStatus::Warn,
+                format!("TERM is '{}' - color output disabled", term),
+            )
+            .with_fix("export TERM=xterm-256color"),
+        );
+    } else {
+        let colors = if std::env::var_os("NO_COLOR").is_some() {
+            "color off (NO_COLOR set)"
+        } else if std::env::var("COLORTERM")
+            .map(|v| v.contains("truecolor") || v.contains("24bit"))
+            .unwrap_or(false)
+        {
+            "truecolor"
+        } else if term.contains("256color") {
+            "256 colors"
+        } else {
+            "basic colors"
+        };
+        checks.push(Check::new(
+            "terminal",
+            CheckStatus::Ok,
+            format!("TERM={} ({})", term, colors),
+        ));
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_default();
+    if locale_is_utf8(&locale) {
+        checks.push(Check::new(
+            "locale",
+            CheckStatus::Ok,
+            format!("{} (UTF-8, emoji safe)", locale),
+        ));
+    } else {
+        checks.push(
+            Check::new(
+                "locale",
+                CheckStatus::Warn,
+                format!(
+                    "locale '{}' is not UTF-8 - emoji and box-drawing may garble",
+                    locale
+                ),
+            )
+            .with_fix("export LANG=en_US.UTF-8  (or pass --no-emoji)"),
+        );
+    }
+
+    checks
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Can we actually create a file here? `metadata().permissions()` lies on
+/// shared dirs and ACLs - writing a probe file does not.
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".doctor_probe.{}", std::process::id()));
+    fs::write(&probe, b"st doctor")?;
+    fs::remove_file(&probe)
+}
+
+/// Does this locale string advertise UTF-8?
+fn locale_is_utf8(locale: &str) -> bool {
+    let folded = locale.to_ascii_lowercase().replace('-', "");
+    folded.contains("utf8")
+}
+
+/// Error chains can be paragraphs - the report wants one line.
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or(text).trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_is_utf8_accepts_common_spellings() {
+        assert!(locale_is_utf8("en_US.UTF-8"));
+        assert!(locale_is_utf8("C.utf8"));
+        assert!(!locale_is_utf8("POSIX"));
+        assert!(!locale_is_utf8(""));
+    }
+
+    #[test]
+    fn test_report_counts_by_status() {
+        let report = DoctorReport {
+            version: "0.0.0",
+            checks: vec![
+                Check::new("a", CheckStatus::Ok, "fine"),
+                Check::new("b", CheckStatus::Warn, "meh").with_fix("do the thing"),
+                Check::new("c", CheckStatus::Ok, "also fine"),
+            ],
+        };
+        assert_eq!(report.count(CheckStatus::Ok), 2);
+        assert_eq!(report.count(CheckStatus::Warn), 1);
+        assert_eq!(report.count(CheckStatus::Fail), 0);
+    }
+
+    #[test]
+    fn test_check_serializes_without_empty_fix() {
+        let json = serde_json::to_string(&Check::new("a", CheckStatus::Ok, "fine")).unwrap();
+        assert!(!json.contains("fix"));
+        assert!(json.contains("\"status\":\"ok\""));
+    }
+}
@@ -0,0 +1,140 @@
+// -----------------------------------------------------------------------------
+// 🔒 Exec Policy - The One Gate Every Shell-Out Passes Through
+// -----------------------------------------------------------------------------
+// Several features spawn external commands: git for status, churn, and
+// projects decorations, the OS opener and clipboard tools from the TUI,
+// service managers from `st service`, tar/sudo from the self-updater, the
+// daemon's sudo stat helper, and the MCP run_checks tool. This module
+// decides whether they may:
+//
+//   * `--no-exec` (or ST_NO_EXEC=1) is the hard kill-switch - with it set,
+//     st never spawns a process. Features degrade gracefully: git
+//     decorations vanish, the TUI opener does nothing, nothing crashes.
+//   * `[exec] allowlist = ["git", ...]` in ~/.st/config.toml narrows which
+//     programs are permitted. Unset means the built-in defaults below.
+//
+// New shell-outs should call `exec_policy::command("prog")` instead of
+// `Command::new("prog")` so the policy can't be bypassed by accident.
+// -----------------------------------------------------------------------------
+
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+/// Programs a stock install shells out to. A config allowlist replaces
+/// (not extends) this set, so locked-down hosts can pare it to just "git"
+/// or to nothing at all.
+const DEFAULT_ALLOWLIST: &[&str] = &[
+    "git",
+    "sudo",
+    "open",
+    "xdg-open",
+    "explorer",
+    "wl-copy",
+    "xclip",
+    "xsel",
+    "pbcopy",
+    "systemctl",
+    "launchctl",
+    "schtasks",
+    "journalctl",
+    "tail",
+    "which",
+    "gpg",
+    "chmod",
+    "tar",
+    "powershell",
+];
+
+/// Process-wide kill-switch, set once from --no-exec during startup.
+static NO_EXEC: AtomicBool = AtomicBool::new(false);
+
+/// Record the --no-exec flag (called early in main, before any feature
+/// gets a chance to spawn).
+pub fn set_no_exec(no_exec: bool) {
+    NO_EXEC.store(no_exec, Ordering::Relaxed);
+}
+
+/// True when process spawning is forbidden, via flag or ST_NO_EXEC env.
+pub fn no_exec() -> bool {
+    if NO_EXEC.load(Ordering::Relaxed) {
+        return true;
+    }
+    matches!(
+        std::env::var("ST_NO_EXEC").as_deref(),
+        Ok("1") | Ok("true") | Ok("yes")
+    )
+}
+
+/// The effective allowlist: `[exec] allowlist` from ~/.st/config.toml,
+/// or the built-in defaults when the config doesn't say.
+fn allowlist() -> &'static [String] {
+    static ALLOWLIST: OnceLock<Vec<String>> = OnceLock::new();
+    ALLOWLIST.get_or_init(|| {
+        let configured = crate::config::StConfig::load()
+            .map(|config| config.exec.allowlist)
+            .unwrap_or_default();
+        if configured.is_empty() {
+            DEFAULT_ALLOWLIST.iter().map(|s| s.to_string()).collect()
+        } else {
+            configured
+        }
+    })
+}
+
+/// Is this program permitted? Compared by basename so "/usr/bin/git"
+/// and "git" are the same answer.
+pub fn is_allowed(program: &str) -> bool {
+    let name = Path::new(program)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| program.to_string());
+    allowlist().iter().any(|allowed| allowed == &name)
+}
+
+/// Bail with a policy-shaped error when `program` may not run.
+pub fn ensure_allowed(program: &str) -> Result<()> {
+    if no_exec() {
+        anyhow::bail!(
+            "--no-exec is active: refusing to run '{}' (external commands are disabled)",
+            program
+        );
+    }
+    if !is_allowed(program) {
+        anyhow::bail!(
+            "'{}' is not in the exec allowlist; add it under [exec] allowlist in ~/.st/config.toml",
+            program
+        );
+    }
+    Ok(())
+}
+
+/// Policy-checked replacement for `Command::new`. Callers that treat
+/// shell-out failure as "feature unavailable" can keep doing so with
+/// `exec_policy::command("git").ok()?`.
+pub fn command(program: &str) -> Result<Command> {
+    ensure_allowed(program)?;
+    Ok(Command::new(program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_allowlist_covers_git() {
+        assert!(is_allowed("git"));
+        assert!(is_allowed("/usr/bin/git"));
+        assert!(!is_allowed("curl"));
+    }
+
+    #[test]
+    fn test_no_exec_blocks_everything() {
+        set_no_exec(true);
+        assert!(command("git").is_err());
+        set_no_exec(false);
+        assert!(command("git").is_ok());
+    }
+}
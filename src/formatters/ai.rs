@@ -127,6 +127,36 @@ impl Formatter for AiFormatter {
             writeln!(writer, "DATES: {:x}-{:x}", oldest, newest)?;
         }
 
+        // Security-relevant extended attributes (--xattrs): one line per
+        // flagged entry so audits can grep straight for SELinux labels/ACLs
+        let flagged: Vec<_> = nodes
+            .iter()
+            .filter(|node| node.xattrs.as_ref().is_some_and(|x| !x.is_empty()))
+            .collect();
+        if !flagged.is_empty() {
+            writeln!(writer, "XATTRS:")?;
+            for node in flagged.iter().take(100) {
+                let rel_path = node.path.strip_prefix(root_path).unwrap_or(&node.path);
+                let attrs: Vec<String> = node
+                    .xattrs
+                    .as_ref()
+                    .unwrap()
+                    .iter()
+                    .map(|(name, value)| {
+                        if value.is_empty() {
+                            name.clone()
+                        } else {
+                            format!("{}={}", name, value)
+                        }
+                    })
+                    .collect();
+                writeln!(writer, "  {}: {}", rel_path.display(), attrs.join(" "))?;
+            }
+            if flagged.len() > 100 {
+                writeln!(writer, "  ... and {} more", flagged.len() - 100)?;
+            }
+        }
+
         writeln!(writer, "END_AI")?;
 
         Ok(())
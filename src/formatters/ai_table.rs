@@ -0,0 +1,189 @@
+// -----------------------------------------------------------------------------
+// AI TABLE FORMATTER - Columnar output that doesn't repeat itself! 📊
+//
+// JSON file listings burn ~60% of their tokens on repeated keys. This mode
+// emits the schema once, then fixed-order tab-separated rows:
+//
+//   AI_TABLE_V1 cols=path,type,size,mtime,perms,depth enc=hex root=/proj
+//   src	d	0	68AF3C10	1ED	1
+//   src/main.rs	f	1000	68AF3C10	1A4	2
+//   END files=1 dirs=1 size=1000
+//
+// Sizes, mtimes, and counts are hex (enc=hex), perms are octal - same
+// conventions as the hex formatter. Trivially parseable: split the header
+// on `cols=`, split rows on tabs.
+// -----------------------------------------------------------------------------
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+pub struct AiTableFormatter;
+
+impl AiTableFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Header line: format marker, column order, encoding, root
+    pub fn write_header(writer: &mut dyn Write, root_path: &Path) -> Result<()> {
+        writeln!(
+            writer,
+            "AI_TABLE_V1 cols=path,type,size,mtime,perms,depth enc=hex root={}",
+            root_path.display()
+        )?;
+        Ok(())
+    }
+
+    /// One row per node, path relative to root, tab-separated
+    pub fn write_row(writer: &mut dyn Write, node: &FileNode, root_path: &Path) -> Result<()> {
+        let relative = node.path.strip_prefix(root_path).unwrap_or(&node.path);
+        let kind = if node.is_symlink {
+            'l'
+        } else if node.is_dir {
+            'd'
+        } else {
+            'f'
+        };
+        let mtime = node
+            .modified
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        writeln!(
+            writer,
+            "{}\t{}\t{:X}\t{:X}\t{:o}\t{:X}",
+            escape_field(&relative.to_string_lossy()),
+            kind,
+            node.size,
+            mtime,
+            node.permissions & 0o7777,
+            node.depth
+        )?;
+        Ok(())
+    }
+
+    /// Trailer with totals (hex, matching enc=hex)
+    pub fn write_trailer(writer: &mut dyn Write, stats: &TreeStats) -> Result<()> {
+        writeln!(
+            writer,
+            "END files={:X} dirs={:X} size={:X}",
+            stats.total_files, stats.total_dirs, stats.total_size
+        )?;
+        Ok(())
+    }
+}
+
+impl Default for AiTableFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for AiTableFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        Self::write_header(writer, root_path)?;
+        for node in nodes {
+            if node.path == root_path {
+                continue;
+            }
+            Self::write_row(writer, node, root_path)?;
+        }
+        Self::write_trailer(writer, stats)?;
+        Ok(())
+    }
+}
+
+/// Keep rows one-per-line and columns tab-delimited no matter the filename
+pub fn escape_field(text: &str) -> String {
+    if text.contains(['\t', '\n', '\r']) {
+        text.replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn node(path: &str, is_dir: bool, size: u64, depth: usize) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::UNIX_EPOCH,
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth,
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_table_layout() {
+        let nodes = vec![
+            node("/proj/src", true, 0, 1),
+            node("/proj/src/main.rs", false, 4096, 2),
+        ];
+        let mut stats = TreeStats::default();
+        for n in &nodes {
+            stats.update_file(n);
+        }
+
+        let mut output = Vec::new();
+        AiTableFormatter::new()
+            .format(&mut output, &nodes, &stats, Path::new("/proj"))
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].starts_with("AI_TABLE_V1 cols=path,type,size,mtime,perms,depth"));
+        assert_eq!(lines[1], "src\td\t0\t0\t644\t1");
+        assert_eq!(lines[2], "src/main.rs\tf\t1000\t0\t644\t2");
+        assert!(lines[3].starts_with("END files=1 dirs=1 size=1000"));
+    }
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("plain.rs"), "plain.rs");
+        assert_eq!(escape_field("tab\there"), "tab\\there");
+        assert_eq!(escape_field("new\nline"), "new\\nline");
+    }
+}
@@ -0,0 +1,288 @@
+// -----------------------------------------------------------------------------
+// 🔥 CHURN FORMATTER - Where Has All the Editing Gone? 📈
+//
+// `st --mode churn` walks the git history (one bounded `git log --numstat`,
+// never per-file subprocesses) and scores every file by how often and how
+// recently it changes. High score = hotspot = the place a refactor is most
+// likely to hurt. Think of it as a weather map for your repo: the red blobs
+// are where the storms keep landing.
+//
+// The same report backs the `analyze_churn` MCP tool, and can render as a
+// mermaid treemap when you'd rather see blobs than rows.
+// -----------------------------------------------------------------------------
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// History window - enough for a meaningful signal without making `st` on a
+/// kernel-sized repo feel like `git gc`.
+const LOG_COMMIT_LIMIT: usize = 5000;
+
+/// A commit's contribution to the hotspot score halves every this-many days,
+/// so last week's firefighting outweighs 2019's.
+const HALF_LIFE_DAYS: f64 = 90.0;
+
+/// Rows shown in the table / nodes in the treemap by default.
+const DEFAULT_LIMIT: usize = 25;
+
+/// Per-file history tally, accumulated across the log window.
+#[derive(Debug, Default, Clone)]
+pub struct FileChurn {
+    /// Commits that touched this file.
+    pub commits: u64,
+    /// Lines added across those commits ("-" for binary files counts as 0).
+    pub lines_added: u64,
+    /// Lines removed across those commits.
+    pub lines_removed: u64,
+    /// Unix time of the most recent commit touching the file.
+    pub last_touched: i64,
+    /// Recency-weighted hotspot score (higher = riskier to refactor blind).
+    pub score: f64,
+}
+
+/// The whole repo's churn picture, sorted hottest-first.
+pub struct ChurnReport {
+    /// (relative path, tally) pairs, descending by score.
+    pub files: Vec<(PathBuf, FileChurn)>,
+    /// How many commits the log window actually covered.
+    pub commits_analyzed: u64,
+}
+
+impl ChurnReport {
+    /// Walk the history under `root` and build the report. `None` when the
+    /// path isn't inside a git work tree (or `git` isn't on PATH).
+    pub fn gather(root: &Path) -> Option<Self> {
+        let output = crate::exec_policy::command("git").ok()?
+            .arg("-C")
+            .arg(root)
+            .args([
+                "log",
+                "--numstat",
+                "--no-renames",
+                "--format=%x01%at",
+                "-n",
+            ])
+            .arg(LOG_COMMIT_LIMIT.to_string())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        Some(Self::parse_log(&String::from_utf8_lossy(&output.stdout), now))
+    }
+
+    /// Parse `git log --numstat --format=%x01%at` output. Pure so the tests
+    /// don't need a repo to poke at.
+    fn parse_log(log: &str, now: i64) -> Self {
+        let mut files: HashMap<PathBuf, FileChurn> = HashMap::new();
+        let mut commits_analyzed = 0u64;
+        let mut commit_weight = 0.0f64;
+        let mut commit_time = 0i64;
+
+        for line in log.lines() {
+            if let Some(timestamp) = line.strip_prefix('\u{1}') {
+                commits_analyzed += 1;
+                commit_time = timestamp.trim().parse().unwrap_or(now);
+                let age_days = ((now - commit_time).max(0) as f64) / 86_400.0;
+                commit_weight = 0.5f64.powf(age_days / HALF_LIFE_DAYS);
+                continue;
+            }
+            // numstat rows: "<added>\t<removed>\t<path>" ("-" for binaries)
+            let mut fields = line.splitn(3, '\t');
+            let (Some(added), Some(removed), Some(path)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let added: u64 = added.parse().unwrap_or(0);
+            let removed: u64 = removed.parse().unwrap_or(0);
+
+            let entry = files.entry(PathBuf::from(path)).or_default();
+            entry.commits += 1;
+            entry.lines_added += added;
+            entry.lines_removed += removed;
+            entry.last_touched = entry.last_touched.max(commit_time);
+            // Frequency carries the score; line volume nudges it (log-scaled
+            // so one generated-file mega-commit can't drown the signal).
+            entry.score += commit_weight * (1.0 + ((added + removed) as f64).ln_1p());
+        }
+
+        let mut files: Vec<(PathBuf, FileChurn)> = files.into_iter().collect();
+        files.sort_by(|a, b| {
+            b.1.score
+                .partial_cmp(&a.1.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Self {
+            files,
+            commits_analyzed,
+        }
+    }
+
+    /// The sorted hotspot table - shared by `--mode churn` and `analyze_churn`.
+    pub fn render_table(&self, writer: &mut dyn Write, root: &Path, limit: usize) -> Result<()> {
+        writeln!(writer, "🔥 Churn Hotspots: {}", root.display())?;
+        writeln!(
+            writer,
+            "   ({} commits analyzed, score half-life {} days)",
+            self.commits_analyzed, HALF_LIFE_DAYS as u64
+        )?;
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{:>8} {:>7} {:>8} {:>8} {:>6}  FILE",
+            "SCORE", "COMMITS", "+LINES", "-LINES", "LAST"
+        )?;
+        for (path, churn) in self.files.iter().take(limit) {
+            writeln!(
+                writer,
+                "{:>8.1} {:>7} {:>8} {:>8} {:>6}  {}",
+                churn.score,
+                churn.commits,
+                churn.lines_added,
+                churn.lines_removed,
+                age_label(churn.last_touched),
+                path.display()
+            )?;
+        }
+        if self.files.len() > limit {
+            writeln!(writer, "... and {} quieter files", self.files.len() - limit)?;
+        }
+        Ok(())
+    }
+
+    /// Mermaid treemap rendering - hotspot scores become blob sizes.
+    pub fn render_treemap(&self, writer: &mut dyn Write, root: &Path, limit: usize) -> Result<()> {
+        writeln!(writer, "treemap-beta")?; // Treemap is a Mermaid Beta feature.
+        writeln!(writer, "\"churn: {}\"", root.display())?;
+        for (path, churn) in self.files.iter().take(limit) {
+            // Mermaid treemap labels can't contain quotes; paths shouldn't,
+            // but belt and braces.
+            let label = path.display().to_string().replace('"', "'");
+            writeln!(writer, "    \"{}\": {:.1}", label, churn.score)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compact "how long ago" column: 3h, 12d, 2y.
+fn age_label(unix_time: i64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let secs = (now - unix_time).max(0);
+    match secs {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s if s < 31_536_000 => format!("{}d", s / 86_400),
+        s => format!("{}y", s / 31_536_000),
+    }
+}
+
+/// `st --mode churn` - the hotspot table (or treemap) as a formatter.
+pub struct ChurnFormatter {
+    /// Render the mermaid treemap instead of the table.
+    pub treemap: bool,
+}
+
+impl Default for ChurnFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChurnFormatter {
+    pub fn new() -> Self {
+        Self { treemap: false }
+    }
+
+    pub fn with_treemap(mut self, treemap: bool) -> Self {
+        self.treemap = treemap;
+        self
+    }
+}
+
+impl Formatter for ChurnFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        _nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        let Some(report) = ChurnReport::gather(root_path) else {
+            writeln!(
+                writer,
+                "Not a git repository (churn needs history): {}",
+                root_path.display()
+            )?;
+            return Ok(());
+        };
+        if self.treemap {
+            report.render_treemap(writer, root_path, DEFAULT_LIMIT)
+        } else {
+            report.render_table(writer, root_path, DEFAULT_LIMIT)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn log_fixture() -> String {
+        // Two commits: a fresh one touching main.rs, an ancient one touching
+        // both files. lib.rs only ever changed long ago.
+        format!(
+            "\u{1}{}\n10\t2\tsrc/main.rs\n\n\u{1}{}\n5\t5\tsrc/main.rs\n100\t0\tsrc/lib.rs\n",
+            NOW - 3600,
+            NOW - 86_400 * 365,
+        )
+    }
+
+    #[test]
+    fn test_parse_log_counts_and_sorts() {
+        let report = ChurnReport::parse_log(&log_fixture(), NOW);
+        assert_eq!(report.commits_analyzed, 2);
+        assert_eq!(report.files.len(), 2);
+
+        let (hottest, churn) = &report.files[0];
+        assert_eq!(hottest, &PathBuf::from("src/main.rs"));
+        assert_eq!(churn.commits, 2);
+        assert_eq!(churn.lines_added, 15);
+        assert_eq!(churn.lines_removed, 7);
+        assert_eq!(churn.last_touched, NOW - 3600);
+    }
+
+    #[test]
+    fn test_recency_outweighs_volume() {
+        // lib.rs moved 100 lines but a year ago; main.rs is active now.
+        let report = ChurnReport::parse_log(&log_fixture(), NOW);
+        let main = &report.files[0].1;
+        let lib = &report.files[1].1;
+        assert!(main.score > lib.score);
+    }
+
+    #[test]
+    fn test_binary_numstat_rows_count_as_commits() {
+        let log = format!("\u{1}{}\n-\t-\tassets/logo.png\n", NOW - 60);
+        let report = ChurnReport::parse_log(&log, NOW);
+        let (path, churn) = &report.files[0];
+        assert_eq!(path, &PathBuf::from("assets/logo.png"));
+        assert_eq!(churn.commits, 1);
+        assert_eq!(churn.lines_added, 0);
+    }
+}
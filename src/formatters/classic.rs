@@ -1,4 +1,6 @@
+use super::columns::{column_widths, visible_width, Column};
 use super::{Formatter, PathDisplayMode};
+use crate::display_overrides::DisplayOverrides;
 use crate::emoji_mapper;
 use crate::scanner::{FileCategory, FileNode, TreeStats};
 use anyhow::Result;
@@ -8,11 +10,46 @@ use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+/// What drives the cold→hot name gradient (--heatmap)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heatmap {
+    /// Big files burn red, small ones stay blue (log-scaled)
+    Size,
+    /// Freshly modified entries burn red, ancient ones stay blue
+    Age,
+}
+
+impl Heatmap {
+    pub fn parse(spec: &str) -> Result<Self> {
+        match spec.trim().to_lowercase().as_str() {
+            "size" => Ok(Heatmap::Size),
+            "age" => Ok(Heatmap::Age),
+            other => anyhow::bail!("Unknown heatmap '{}' (expected 'size' or 'age')", other),
+        }
+    }
+}
+
 pub struct ClassicFormatter {
     pub no_emoji: bool,
     pub use_color: bool,
     pub path_mode: PathDisplayMode,
     pub sort_field: Option<String>,
+    pub git_status: bool,
+    pub git_blame_summary: bool,
+    pub dir_sizes: bool,
+    pub dirs_first: bool,
+    pub files_first: bool,
+    /// Metadata columns shown bracketed before the tree (--columns)
+    pub columns: Option<Vec<Column>>,
+    /// Middle-truncate names longer than this many characters
+    /// (--max-name-width); tree guides stay intact
+    pub max_name_width: Option<usize>,
+    /// Color names along a cold→hot gradient by relative size or
+    /// modification recency within this scan (--heatmap)
+    pub heatmap: Option<Heatmap>,
+    /// Per-directory .st/display.toml curation (collapse/emoji/annotation),
+    /// loaded lazily and cached for the duration of the formatter
+    overrides: DisplayOverrides,
 }
 
 impl ClassicFormatter {
@@ -22,6 +59,15 @@ impl ClassicFormatter {
             use_color,
             path_mode,
             sort_field: None,
+            git_status: false,
+            git_blame_summary: false,
+            dir_sizes: false,
+            dirs_first: false,
+            files_first: false,
+            columns: None,
+            max_name_width: None,
+            heatmap: None,
+            overrides: DisplayOverrides::new(),
         }
     }
 
@@ -30,6 +76,119 @@ impl ClassicFormatter {
         self
     }
 
+    /// Decorate entries with git status markers and/or last-commit summaries
+    pub fn with_git(mut self, status: bool, blame_summary: bool) -> Self {
+        self.git_status = status;
+        self.git_blame_summary = blame_summary;
+        self
+    }
+
+    /// Show recursive size totals next to directories (--dir-sizes; the
+    /// scanner pre-computes them so we just display node.size)
+    pub fn with_dir_sizes(mut self, dir_sizes: bool) -> Self {
+        self.dir_sizes = dir_sizes;
+        self
+    }
+
+    /// Override the default directories-first grouping (--dirs-first /
+    /// --files-first)
+    pub fn with_dir_order(mut self, dirs_first: bool, files_first: bool) -> Self {
+        self.dirs_first = dirs_first;
+        self.files_first = files_first;
+        self
+    }
+
+    /// Prefix each tree line with the chosen metadata columns, tree(1)
+    /// `-pugs` style - `[drwxr-xr-x hue 4.0 KiB] ├── src`. The name stays
+    /// in its tree position; a `name` entry in the spec is simply skipped.
+    pub fn with_columns(mut self, columns: Option<Vec<Column>>) -> Self {
+        self.columns =
+            columns.map(|cols| cols.into_iter().filter(|c| *c != Column::Name).collect());
+        self
+    }
+
+    /// Cap name width, replacing the middle with '…' so both the prefix
+    /// and the (usually telling) extension survive (--max-name-width)
+    pub fn with_max_name_width(mut self, max_name_width: Option<usize>) -> Self {
+        self.max_name_width = max_name_width;
+        self
+    }
+
+    /// Paint names along a relative cold→hot gradient (--heatmap size|age)
+    pub fn with_heatmap(mut self, heatmap: Option<Heatmap>) -> Self {
+        self.heatmap = heatmap;
+        self
+    }
+
+    /// Normalized 0.0..=1.0 heat per entry. Size uses a log scale so a
+    /// 4 KiB source file and a 4 GiB tarball don't flatten everything
+    /// between them; age maps oldest→0, newest→1 within this scan.
+    fn heat_values(&self, tree_structure: &[(FileNode, Vec<bool>)]) -> Option<Vec<f32>> {
+        let mode = self.heatmap?;
+        if !self.use_color || crate::theme::Theme::global().suppress_color() {
+            return None;
+        }
+        let raw: Vec<f64> = tree_structure
+            .iter()
+            .map(|(node, _)| match mode {
+                Heatmap::Size => (node.size as f64 + 1.0).ln(),
+                Heatmap::Age => node
+                    .modified
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs_f64())
+                    .unwrap_or(0.0),
+            })
+            .collect();
+        let min = raw.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = raw.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let span = max - min;
+        Some(
+            raw.iter()
+                .map(|v| {
+                    if span > 0.0 {
+                        ((v - min) / span) as f32
+                    } else {
+                        0.5
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Map heat 0.0 (cold) → 1.0 (hot) onto a blue→cyan→green→yellow→red
+    /// sweep: hue 240° down to 0° at full saturation.
+    fn heat_color(t: f32) -> Color {
+        let hue = 240.0 * (1.0 - t.clamp(0.0, 1.0));
+        let sector = (hue / 60.0).floor() as u32 % 6;
+        let f = hue / 60.0 - (hue / 60.0).floor();
+        let q = ((1.0 - f) * 255.0) as u8;
+        let t8 = (f * 255.0) as u8;
+        let (r, g, b) = match sector {
+            0 => (255, t8, 0),
+            1 => (q, 255, 0),
+            2 => (0, 255, t8),
+            3 => (0, q, 255),
+            _ => (t8, 0, 255),
+        };
+        Color::TrueColor { r, g, b }
+    }
+
+    /// "a_very_long_module_name.rs" -> "a_very…name.rs". Counts chars,
+    /// not bytes, so multibyte names don't split mid-codepoint.
+    fn middle_truncate(name: &str, max_width: usize) -> String {
+        let chars: Vec<char> = name.chars().collect();
+        if chars.len() <= max_width || max_width < 3 {
+            return name.to_string();
+        }
+        let keep = max_width - 1;
+        let front = keep / 2;
+        let back = keep - front;
+        let mut out: String = chars[..front].iter().collect();
+        out.push('…');
+        out.extend(&chars[chars.len() - back..]);
+        out
+    }
+
     /// Calculate visual weight based on directory size and depth
     /// Larger directories and shallower depths get higher visual weight (thicker lines)
     #[allow(dead_code)]
@@ -66,12 +225,19 @@ impl ClassicFormatter {
         weight.min(5)
     }
 
+    /// Size-gradient backgrounds can be switched off by the theme
+    /// (`size_gradient = false`, or the mono preset)
+    fn gradient_enabled(&self) -> bool {
+        let theme = crate::theme::Theme::global();
+        !theme.suppress_color() && theme.size_gradient().unwrap_or(true)
+    }
+
     /// Get terminal characters with gradient background based on file size
     /// Returns formatted string with gradient background that fades to the right
     fn get_terminal_chars(&self, file_size: u64, is_last: bool) -> String {
         let base_char = if is_last { "└── " } else { "├── " };
 
-        if self.no_emoji || !self.use_color {
+        if self.no_emoji || !self.use_color || !self.gradient_enabled() {
             // No color/emoji mode - just return plain characters
             return base_char.to_string();
         }
@@ -88,7 +254,7 @@ impl ClassicFormatter {
     fn get_continuation_chars(&self, file_size: u64, is_vertical: bool) -> String {
         let base_char = if is_vertical { "│   " } else { "    " };
 
-        if self.no_emoji || !self.use_color {
+        if self.no_emoji || !self.use_color || !self.gradient_enabled() {
             // No color/emoji mode - just return plain characters
             return base_char.to_string();
         }
@@ -210,76 +376,28 @@ impl ClassicFormatter {
                 let node_a = &sorted_nodes[a];
                 let node_b = &sorted_nodes[b];
 
-                // Apply custom sorting if specified
-                match sort_field.as_deref() {
-                    Some("size") | Some("largest") => node_b.size.cmp(&node_a.size),
-                    Some("smallest") => node_a.size.cmp(&node_b.size),
-                    Some("date") | Some("newest") => node_b.modified.cmp(&node_a.modified),
-                    Some("oldest") => node_a.modified.cmp(&node_b.modified),
-                    Some("name") | Some("a-to-z") => {
-                        let name_a = node_a
-                            .path
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy();
-                        let name_b = node_b
-                            .path
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy();
-                        name_a.cmp(&name_b)
-                    }
-                    Some("z-to-a") => {
-                        let name_a = node_a
-                            .path
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy();
-                        let name_b = node_b
-                            .path
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy();
-                        name_b.cmp(&name_a)
-                    }
-                    Some("type") => {
-                        // Sort by extension, then by name
-                        let ext_a = node_a
-                            .path
-                            .extension()
-                            .unwrap_or_default()
-                            .to_string_lossy();
-                        let ext_b = node_b
-                            .path
-                            .extension()
-                            .unwrap_or_default()
-                            .to_string_lossy();
-                        match ext_a.cmp(&ext_b) {
-                            std::cmp::Ordering::Equal => {
-                                let name_a = node_a
-                                    .path
-                                    .file_name()
-                                    .unwrap_or_default()
-                                    .to_string_lossy();
-                                let name_b = node_b
-                                    .path
-                                    .file_name()
-                                    .unwrap_or_default()
-                                    .to_string_lossy();
-                                name_a.cmp(&name_b)
-                            }
-                            other => other,
-                        }
-                    }
-                    _ => {
-                        // Default: directories first, then by name
-                        match (node_a.is_dir, node_b.is_dir) {
-                            (true, false) => std::cmp::Ordering::Less,
-                            (false, true) => std::cmp::Ordering::Greater,
-                            _ => node_a.path.file_name().cmp(&node_b.path.file_name()),
-                        }
-                    }
-                }
+                // Dirs/files-first toggle wins; without one, the classic
+                // default (directories first) applies unless a sort spec
+                // was given.
+                let dir_order = if self.dirs_first {
+                    node_b.is_dir.cmp(&node_a.is_dir)
+                } else if self.files_first {
+                    node_a.is_dir.cmp(&node_b.is_dir)
+                } else if sort_field.is_none() {
+                    node_b.is_dir.cmp(&node_a.is_dir)
+                } else {
+                    std::cmp::Ordering::Equal
+                };
+
+                // Then the shared multi-key comparator (natural name order),
+                // so children sort exactly like the scanner's flat list.
+                dir_order.then_with(|| match sort_field.as_deref() {
+                    Some(spec) => crate::scanner::compare_nodes(node_a, node_b, spec),
+                    None => crate::scanner::natural_cmp(
+                        &node_a.path.file_name().unwrap_or_default().to_string_lossy(),
+                        &node_b.path.file_name().unwrap_or_default().to_string_lossy(),
+                    ),
+                })
             });
         }
 
@@ -290,16 +408,31 @@ impl ClassicFormatter {
             children_map: &HashMap<PathBuf, Vec<usize>>,
             result: &mut Vec<(FileNode, Vec<bool>)>,
             is_last_stack: Vec<bool>,
+            overrides: &DisplayOverrides,
         ) {
             let node = &nodes[node_idx];
             result.push((node.clone(), is_last_stack.clone()));
 
+            // .st/display.toml curation: a collapsed directory shows only
+            // itself (the root always expands - collapsing the whole scan
+            // would just be confusing)
+            if !is_last_stack.is_empty() && overrides.is_collapsed(&node.path, node.is_dir) {
+                return;
+            }
+
             if let Some(children) = children_map.get(&node.path) {
                 for (i, &child_idx) in children.iter().enumerate() {
                     let is_last = i == children.len() - 1;
                     let mut new_stack = is_last_stack.clone();
                     new_stack.push(is_last);
-                    add_node_to_result(child_idx, nodes, children_map, result, new_stack);
+                    add_node_to_result(
+                        child_idx,
+                        nodes,
+                        children_map,
+                        result,
+                        new_stack,
+                        overrides,
+                    );
                 }
             }
         }
@@ -307,7 +440,14 @@ impl ClassicFormatter {
         // Find root node (should only be the scan root)
         for (i, node) in sorted_nodes.iter().enumerate() {
             if node.path == root_path {
-                add_node_to_result(i, &sorted_nodes, &children_map, &mut result, vec![]);
+                add_node_to_result(
+                    i,
+                    &sorted_nodes,
+                    &children_map,
+                    &mut result,
+                    vec![],
+                    &self.overrides,
+                );
                 break;
             }
         }
@@ -320,6 +460,16 @@ impl ClassicFormatter {
             return None;
         }
 
+        // Theme overrides (or suppression, for the mono preset) come first;
+        // the hard-coded palette below is the "default" preset
+        let theme = crate::theme::Theme::global();
+        if theme.suppress_color() {
+            return None;
+        }
+        if let Some(color) = theme.category_color(category) {
+            return Some(color);
+        }
+
         match category {
             // Programming languages
             FileCategory::Rust => Some(Color::TrueColor {
@@ -631,7 +781,14 @@ impl ClassicFormatter {
         }
     }
 
-    fn format_node(&self, node: &FileNode, is_last: &[bool], root_path: &Path) -> String {
+    fn format_node(
+        &self,
+        node: &FileNode,
+        is_last: &[bool],
+        root_path: &Path,
+        git: Option<&crate::git_status::GitAnnotations>,
+        heat: Option<f32>,
+    ) -> String {
         let mut prefix = String::new();
 
         // Build tree prefix with gradient backgrounds based on file size
@@ -649,7 +806,13 @@ impl ClassicFormatter {
             }
         }
 
-        let emoji = self.get_file_emoji(node);
+        // .st/display.toml curation: custom emoji replaces the category
+        // one (still suppressed by --no-emoji), annotations trail the line
+        let curated = self.overrides.for_node(&node.path, node.is_dir);
+        let emoji = match (&curated.emoji, self.no_emoji) {
+            (Some(custom), false) => custom.clone(),
+            _ => self.get_file_emoji(node).to_string(),
+        };
 
         // Determine what name to show based on path mode
         let name = match self.path_mode {
@@ -677,18 +840,42 @@ impl ClassicFormatter {
             PathDisplayMode::Full => node.path.display().to_string(),
         };
 
-        let size_str = if node.is_dir {
+        // Long names get their middles elided so columns stay readable
+        let name = match self.max_name_width {
+            Some(max) => Self::middle_truncate(&name, max),
+            None => name,
+        };
+
+        // When a size column already sits in the --columns gutter, the
+        // inline "(4.0 KiB)" would just repeat it
+        let gutter_has_size = self
+            .columns
+            .as_ref()
+            .is_some_and(|cols| cols.contains(&Column::Size));
+
+        let size_str = if gutter_has_size {
             String::new()
+        } else if node.is_dir {
+            if self.dir_sizes {
+                // Recursive total, rolled up by the scanner's single pass.
+                format!(" ({})", format_size(node.size, BINARY))
+            } else {
+                String::new()
+            }
         } else {
             format!(" ({})", format_size(node.size, BINARY))
         };
 
         let indicator = if node.permission_denied {
-            " [*]"
+            " [*]".to_string()
         } else if node.is_ignored {
-            " [ignored]"
+            " [ignored]".to_string()
+        } else if let Some(marker) = node.file_type.special_marker() {
+            // Sockets, FIFOs, and devices announce themselves - size and
+            // category mean little for them
+            format!(" {}", marker)
         } else {
-            ""
+            String::new()
         };
 
         // Add search match indicator
@@ -711,11 +898,55 @@ impl ClassicFormatter {
             String::new()
         };
 
-        // Apply color to the name based on file category
-        let colored_name = if node.is_dir {
-            // Directories get bright yellow and bold
-            let dir_name = if self.use_color {
-                name.bright_yellow().bold().to_string()
+        // Git decoration: status marker first, blame summary after the name
+        let mut git_indicator = String::new();
+        if let Some(git) = git {
+            if self.git_status {
+                if let Some(code) = git.status_code(&node.path) {
+                    git_indicator.push_str(&if self.use_color {
+                        format!(" [{}]", code.red())
+                    } else {
+                        format!(" [{}]", code)
+                    });
+                }
+            }
+            if self.git_blame_summary && !node.is_dir {
+                if let Some(summary) = git.blame_summary(&node.path) {
+                    git_indicator.push_str(&if self.use_color {
+                        format!(" ({})", summary.dimmed())
+                    } else {
+                        format!(" ({})", summary)
+                    });
+                }
+            }
+        }
+
+        // Apply color to the name - an explicit --heatmap outranks theme
+        // and palette alike (heat_values already checked suppression)
+        let theme = crate::theme::Theme::global();
+        let colored_name = if let Some(t) = heat {
+            let hot_name = if node.is_dir {
+                name.color(Self::heat_color(t)).bold().to_string()
+            } else {
+                name.color(Self::heat_color(t)).to_string()
+            };
+            if let Some(ref branch) = node.git_branch {
+                format!("{} [{}]", hot_name, branch.cyan())
+            } else {
+                hot_name
+            }
+        } else if node.is_dir {
+            // Directories get bright yellow and bold unless the theme says
+            // otherwise (mono suppresses, presets restyle)
+            let dir_name = if self.use_color && !theme.suppress_color() {
+                match theme.directory_color() {
+                    Some(color) => name.color(color).bold().to_string(),
+                    // Fall through to the user's dircolors, then our default
+                    None => match crate::ls_colors::LsColors::global().paint(node, &name) {
+                        Some(painted) => painted,
+                        None => name.bright_yellow().bold().to_string(),
+                    },
+                }
             } else {
                 name
             };
@@ -730,22 +961,59 @@ impl ClassicFormatter {
             } else {
                 dir_name
             }
-        } else if let Some(color) = self.get_color_for_category(node.category) {
-            name.color(color).to_string()
+        } else if self.use_color && !theme.suppress_color() {
+            // Theme rules, then LS_COLORS, then the category palette
+            if let Some(color) = theme.node_color(node) {
+                name.color(color).to_string()
+            } else if let Some(painted) = crate::ls_colors::LsColors::global().paint(node, &name) {
+                painted
+            } else {
+                match self.get_color_for_category(node.category) {
+                    Some(color) => name.color(color).to_string(),
+                    None => name,
+                }
+            }
         } else {
             name
         };
 
+        // Collapsed directories announce their hidden children; curated
+        // annotations trail everything else, dimmed when color is on
+        let mut curated_suffix = String::new();
+        if !is_last.is_empty() && node.is_dir && curated.collapse == Some(true) {
+            curated_suffix.push_str(" […]");
+        }
+        if let Some(note) = &curated.annotation {
+            curated_suffix.push_str(&if self.use_color {
+                format!(" # {}", note.dimmed())
+            } else {
+                format!(" # {}", note)
+            });
+        }
+
         if is_last.is_empty() {
             // Root node
             format!(
-                "{} {}{}{}{}",
-                emoji, colored_name, size_str, indicator, search_indicator
+                "{} {}{}{}{}{}{}",
+                emoji,
+                colored_name,
+                size_str,
+                git_indicator,
+                indicator,
+                search_indicator,
+                curated_suffix
             )
         } else {
             format!(
-                "{}{} {}{}{}{}",
-                prefix, emoji, colored_name, size_str, indicator, search_indicator
+                "{}{} {}{}{}{}{}{}",
+                prefix,
+                emoji,
+                colored_name,
+                size_str,
+                git_indicator,
+                indicator,
+                search_indicator,
+                curated_suffix
             )
         }
     }
@@ -761,8 +1029,57 @@ impl Formatter for ClassicFormatter {
     ) -> Result<()> {
         let tree_structure = self.build_tree_structure(nodes, root_path);
 
-        for (node, is_last) in tree_structure {
-            writeln!(writer, "{}", self.format_node(&node, &is_last, root_path))?;
+        // One gather per format call - never a subprocess per file
+        let git = if self.git_status || self.git_blame_summary {
+            crate::git_status::GitAnnotations::gather(root_path, self.git_blame_summary)
+        } else {
+            None
+        };
+
+        // Metadata columns (--columns): bracketed gutter before the tree,
+        // auto-sized so the connectors line up down the page
+        let column_rows: Option<Vec<Vec<String>>> = self.columns.as_ref().map(|columns| {
+            tree_structure
+                .iter()
+                .map(|(node, _)| columns.iter().map(|c| c.value(node)).collect())
+                .collect()
+        });
+        let widths = column_rows.as_deref().map(column_widths);
+
+        // Heat is relative to this scan: min/max are recomputed every run
+        let heats = self.heat_values(&tree_structure);
+
+        for (i, (node, is_last)) in tree_structure.iter().enumerate() {
+            let gutter = match (&column_rows, &widths, &self.columns) {
+                (Some(rows), Some(widths), Some(columns)) => {
+                    let cells: Vec<String> = rows[i]
+                        .iter()
+                        .zip(widths.iter().zip(columns.iter()))
+                        .map(|(cell, (width, column))| {
+                            let pad = " ".repeat(width.saturating_sub(visible_width(cell)));
+                            if column.right_aligned() {
+                                format!("{}{}", pad, cell)
+                            } else {
+                                format!("{}{}", cell, pad)
+                            }
+                        })
+                        .collect();
+                    format!("[{}] ", cells.join(" "))
+                }
+                _ => String::new(),
+            };
+            writeln!(
+                writer,
+                "{}{}",
+                gutter,
+                self.format_node(
+                    node,
+                    is_last,
+                    root_path,
+                    git.as_ref(),
+                    heats.as_ref().map(|h| h[i])
+                )
+            )?;
         }
 
         // Print summary
@@ -0,0 +1,211 @@
+// -----------------------------------------------------------------------------
+// 📐 COLUMNS - Pick Your Own Layout for ls and Classic Modes!
+// -----------------------------------------------------------------------------
+// `--columns size,mtime,name` replaces the fixed ls/classic layouts with
+// exactly the fields you asked for, in the order you asked for them. Each
+// column knows how to render a value from a FileNode and whether it
+// right-aligns; widths are auto-sized from the actual rows so nothing
+// wobbles. Trish reorders hers weekly. We've stopped asking why.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::FileNode;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use humansize::{format_size, BINARY};
+
+/// A selectable output column for ls/classic modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    /// Unix permission string (drwxr-xr-x)
+    Perms,
+    /// Owner name (numeric uid off-unix or when lookup fails)
+    Owner,
+    /// Group name
+    Group,
+    /// Human-readable size
+    Size,
+    /// Modification time (ls-style "Jul  9 14:56")
+    Mtime,
+    /// File category from the scanner (rust, image, archive, ...)
+    Category,
+    /// Filename - rendered by the calling formatter so emoji/color rules
+    /// stay in one place
+    Name,
+}
+
+/// Everything `--columns` accepts, in help-text order.
+const COLUMN_NAMES: &[(&str, Column)] = &[
+    ("perms", Column::Perms),
+    ("owner", Column::Owner),
+    ("group", Column::Group),
+    ("size", Column::Size),
+    ("mtime", Column::Mtime),
+    ("category", Column::Category),
+    ("name", Column::Name),
+];
+
+impl Column {
+    /// Right-aligned columns (numbers read better flush right).
+    pub fn right_aligned(&self) -> bool {
+        matches!(self, Column::Size)
+    }
+
+    /// Render this column's value for one node. `Name` is intentionally
+    /// not handled here - the formatters own name styling.
+    pub fn value(&self, node: &FileNode) -> String {
+        match self {
+            Column::Perms => permission_string(node),
+            Column::Owner => owner_name(node.uid),
+            Column::Group => group_name(node.gid),
+            Column::Size => format_size(node.size, BINARY),
+            Column::Mtime => {
+                let datetime: DateTime<Local> = node.modified.into();
+                datetime.format("%b %d %H:%M").to_string()
+            }
+            // Debug names are stable and human enough once lowercased
+            // (rust, image, archive, systemfile, ...)
+            Column::Category => format!("{:?}", node.category).to_lowercase(),
+            Column::Name => String::new(),
+        }
+    }
+}
+
+/// Parse a comma-separated column spec, rejecting unknown names with the
+/// full menu so nobody has to go read the source.
+pub fn parse_columns(spec: &str) -> Result<Vec<Column>> {
+    let mut columns = Vec::new();
+    for name in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        match COLUMN_NAMES.iter().find(|(n, _)| *n == name) {
+            Some((_, column)) => columns.push(*column),
+            None => anyhow::bail!(
+                "Unknown column '{}' (available: {})",
+                name,
+                COLUMN_NAMES
+                    .iter()
+                    .map(|(n, _)| *n)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+    if columns.is_empty() {
+        anyhow::bail!("--columns needs at least one column name");
+    }
+    Ok(columns)
+}
+
+/// Widest cell per column across all rows, counting visible characters.
+pub fn column_widths(rows: &[Vec<String>]) -> Vec<usize> {
+    let count = rows.first().map(|r| r.len()).unwrap_or(0);
+    (0..count)
+        .map(|i| {
+            rows.iter()
+                .map(|row| visible_width(&row[i]))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Display width ignoring ANSI color sequences - colored names would
+/// otherwise blow up the padding math.
+pub fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for ch in s.chars() {
+        if in_escape {
+            if ch.is_ascii_alphabetic() {
+                in_escape = false;
+            }
+        } else if ch == '\x1b' {
+            in_escape = true;
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Classic ls permission string built from the scanner's mode bits -
+/// no extra stat() call needed.
+fn permission_string(node: &FileNode) -> String {
+    let file_type = node.file_type.mode_char();
+    let mode = node.permissions;
+    let mut out = String::with_capacity(10);
+    out.push(file_type);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        out.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        out.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        out.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    out
+}
+
+#[cfg(unix)]
+fn owner_name(uid: u32) -> String {
+    use std::ffi::CStr;
+    unsafe {
+        let passwd = libc::getpwuid(uid);
+        if passwd.is_null() {
+            uid.to_string()
+        } else {
+            CStr::from_ptr((*passwd).pw_name)
+                .to_string_lossy()
+                .to_string()
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn owner_name(uid: u32) -> String {
+    uid.to_string()
+}
+
+#[cfg(unix)]
+fn group_name(gid: u32) -> String {
+    use std::ffi::CStr;
+    unsafe {
+        let grp = libc::getgrgid(gid);
+        if grp.is_null() {
+            gid.to_string()
+        } else {
+            CStr::from_ptr((*grp).gr_name).to_string_lossy().to_string()
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn group_name(gid: u32) -> String {
+    gid.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_columns() {
+        let cols = parse_columns("size, mtime,name").unwrap();
+        assert_eq!(cols, vec![Column::Size, Column::Mtime, Column::Name]);
+
+        let err = parse_columns("size,banana").unwrap_err();
+        assert!(err.to_string().contains("Unknown column 'banana'"));
+        assert!(err.to_string().contains("available"));
+    }
+
+    #[test]
+    fn test_visible_width_skips_ansi() {
+        assert_eq!(visible_width("plain"), 5);
+        assert_eq!(visible_width("\x1b[34mblue\x1b[0m"), 4);
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let rows = vec![
+            vec!["1.2K".to_string(), "a".to_string()],
+            vec!["45M".to_string(), "longer".to_string()],
+        ];
+        assert_eq!(column_widths(&rows), vec![4, 6]);
+    }
+}
@@ -2,11 +2,19 @@ use super::Formatter;
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
 use chrono::{DateTime, Local};
-use csv::Writer;
+use csv::WriterBuilder;
 use std::io::Write;
 use std::path::Path;
 
-pub struct CsvFormatter;
+/// UTF-8 byte order mark - Excel needs it to recognize UTF-8 CSVs.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+pub struct CsvFormatter {
+    /// Field delimiter (--csv-delimiter, defaults to ',')
+    delimiter: u8,
+    /// Prefix the output with a UTF-8 BOM for Excel (--csv-bom)
+    bom: bool,
+}
 
 impl Default for CsvFormatter {
     fn default() -> Self {
@@ -16,7 +24,22 @@ impl Default for CsvFormatter {
 
 impl CsvFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            delimiter: b',',
+            bom: false,
+        }
+    }
+
+    /// Use a custom field delimiter (e.g. ';' for European Excel locales).
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Emit a UTF-8 byte order mark so Excel opens the file correctly.
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
     }
 }
 
@@ -28,7 +51,16 @@ impl Formatter for CsvFormatter {
         _stats: &TreeStats,
         root_path: &Path,
     ) -> Result<()> {
-        let mut csv_writer = Writer::from_writer(writer);
+        if self.bom {
+            writer.write_all(UTF8_BOM)?;
+        }
+
+        // The csv crate handles RFC 4180 quoting for us - fields containing
+        // the delimiter, quotes, or newlines (yes, filenames can embed
+        // newlines) come out properly quoted and doubled
+        let mut csv_writer = WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
 
         // Write header
         csv_writer.write_record([
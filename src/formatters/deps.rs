@@ -0,0 +1,434 @@
+// -----------------------------------------------------------------------------
+// 🕸️ DEPENDENCY GRAPH FORMATTER - Who Depends on What (and on Which Version)?
+//
+// `st --mode deps` parses every manifest the scan surfaced - Cargo.toml,
+// package.json, pyproject.toml, go.mod - and renders the cross-project
+// dependency graph as mermaid (default) or Graphviz DOT (`--graph dot`).
+// Dependencies pinned to different versions by different projects get
+// flagged red: that's the drift a workspace cleanup wants to find first.
+//
+// The same graph backs the `analyze_dependencies` MCP tool.
+// -----------------------------------------------------------------------------
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Manifests larger than this are skipped - no hand-written manifest is a
+/// megabyte, but a vendored lockfile rename might be.
+const MAX_MANIFEST_BYTES: u64 = 512 * 1024;
+
+/// One project's manifest: who it is and what it pulls in.
+#[derive(Debug, Clone)]
+pub struct ProjectDeps {
+    pub name: String,
+    /// The manifest this came from, relative to the scan root.
+    pub manifest: PathBuf,
+    /// (dependency name, declared version requirement) pairs.
+    pub deps: Vec<(String, String)>,
+}
+
+/// The workspace-wide dependency picture.
+pub struct DepsReport {
+    /// Projects in manifest-path order.
+    pub projects: Vec<ProjectDeps>,
+}
+
+impl DepsReport {
+    /// Parse every manifest among the scanned nodes.
+    pub fn gather(nodes: &[FileNode], root: &Path) -> Self {
+        let mut projects = Vec::new();
+        for node in nodes {
+            if node.is_dir
+                || node.is_symlink
+                || node.permission_denied
+                || node.size > MAX_MANIFEST_BYTES
+            {
+                continue;
+            }
+            let Some(name) = node.path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(&node.path) else {
+                continue;
+            };
+            let rel = node
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&node.path)
+                .to_path_buf();
+            let parsed = match name {
+                "Cargo.toml" => parse_cargo_deps(&content, rel),
+                "package.json" => parse_package_json_deps(&content, rel),
+                "pyproject.toml" => parse_pyproject_deps(&content, rel),
+                "go.mod" => parse_go_mod_deps(&content, rel),
+                _ => None,
+            };
+            if let Some(project) = parsed {
+                projects.push(project);
+            }
+        }
+        projects.sort_by(|a, b| a.manifest.cmp(&b.manifest));
+        Self { projects }
+    }
+
+    /// Dependencies declared with more than one distinct version across the
+    /// workspace: name -> version -> projects declaring it.
+    pub fn duplicate_versions(&self) -> BTreeMap<&str, BTreeMap<&str, Vec<&str>>> {
+        let mut versions: BTreeMap<&str, BTreeMap<&str, Vec<&str>>> = BTreeMap::new();
+        for project in &self.projects {
+            for (dep, version) in &project.deps {
+                versions
+                    .entry(dep)
+                    .or_default()
+                    .entry(version)
+                    .or_default()
+                    .push(&project.name);
+            }
+        }
+        versions.retain(|_, by_version| by_version.len() > 1);
+        versions
+    }
+
+    /// Mermaid flowchart - the `--mode deps` default.
+    pub fn render_mermaid(&self, writer: &mut dyn Write, root: &Path) -> Result<()> {
+        let duplicates = self.duplicate_versions();
+        writeln!(writer, "flowchart LR")?;
+        writeln!(writer, "    %% dependency graph: {}", root.display())?;
+
+        for project in &self.projects {
+            writeln!(
+                writer,
+                "    {}[\"{}\"]:::project",
+                node_id(&project.name),
+                project.name
+            )?;
+            for (dep, version) in &project.deps {
+                writeln!(
+                    writer,
+                    "    {} -->|{}| {}[\"{}\"]",
+                    node_id(&project.name),
+                    version,
+                    node_id(dep),
+                    dep
+                )?;
+            }
+        }
+
+        writeln!(writer, "    classDef project fill:#bbf")?;
+        if !duplicates.is_empty() {
+            writeln!(writer, "    classDef dup fill:#f66")?;
+            for dep in duplicates.keys() {
+                writeln!(writer, "    class {} dup", node_id(dep))?;
+            }
+            for (dep, by_version) in &duplicates {
+                let mix: Vec<String> = by_version
+                    .iter()
+                    .map(|(version, projects)| format!("{} ({})", version, projects.join(", ")))
+                    .collect();
+                writeln!(writer, "    %% duplicate versions: {}: {}", dep, mix.join("; "))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Graphviz DOT - pipe straight into `dot -Tsvg` (`--graph dot`).
+    pub fn render_dot(&self, writer: &mut dyn Write, root: &Path) -> Result<()> {
+        let duplicates = self.duplicate_versions();
+        writeln!(writer, "digraph deps {{")?;
+        writeln!(writer, "    // dependency graph: {}", root.display())?;
+        writeln!(writer, "    rankdir=LR;")?;
+
+        for project in &self.projects {
+            writeln!(
+                writer,
+                "    \"{}\" [shape=box, style=filled, fillcolor=lightblue];",
+                project.name
+            )?;
+            for (dep, version) in &project.deps {
+                writeln!(
+                    writer,
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    project.name, dep, version
+                )?;
+            }
+        }
+
+        for (dep, by_version) in &duplicates {
+            let mix: Vec<String> = by_version
+                .iter()
+                .map(|(version, projects)| format!("{} ({})", version, projects.join(", ")))
+                .collect();
+            writeln!(
+                writer,
+                "    \"{}\" [color=red, tooltip=\"duplicate versions: {}\"];",
+                dep,
+                mix.join("; ")
+            )?;
+        }
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+}
+
+/// Mermaid-safe node id: alphanumerics survive, everything else folds to _.
+fn node_id(name: &str) -> String {
+    let id: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("n_{}", id)
+}
+
+fn parse_cargo_deps(content: &str, manifest: PathBuf) -> Option<ProjectDeps> {
+    let doc: toml::Value = content.parse().ok()?;
+    let name = doc
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .map(String::from)
+        .or_else(|| manifest_dir_name(&manifest))?;
+
+    let mut deps = Vec::new();
+    for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        if let Some(table) = doc.get(section).and_then(|s| s.as_table()) {
+            for (dep, value) in table {
+                // "1.0", { version = "1.0", ... }, or a path/git dep with
+                // no version at all
+                let version = value
+                    .as_str()
+                    .map(String::from)
+                    .or_else(|| {
+                        value
+                            .get("version")
+                            .and_then(|v| v.as_str())
+                            .map(String::from)
+                    })
+                    .unwrap_or_else(|| "*".to_string());
+                deps.push((dep.clone(), version));
+            }
+        }
+    }
+    Some(ProjectDeps {
+        name,
+        manifest,
+        deps,
+    })
+}
+
+fn parse_package_json_deps(content: &str, manifest: PathBuf) -> Option<ProjectDeps> {
+    let doc: serde_json::Value = serde_json::from_str(content).ok()?;
+    let name = doc
+        .get("name")
+        .and_then(|n| n.as_str())
+        .map(String::from)
+        .or_else(|| manifest_dir_name(&manifest))?;
+
+    let mut deps = Vec::new();
+    for section in ["dependencies", "devDependencies"] {
+        if let Some(map) = doc.get(section).and_then(|s| s.as_object()) {
+            for (dep, version) in map {
+                deps.push((
+                    dep.clone(),
+                    version.as_str().unwrap_or("*").to_string(),
+                ));
+            }
+        }
+    }
+    Some(ProjectDeps {
+        name,
+        manifest,
+        deps,
+    })
+}
+
+fn parse_pyproject_deps(content: &str, manifest: PathBuf) -> Option<ProjectDeps> {
+    let doc: toml::Value = content.parse().ok()?;
+    let project = doc.get("project")?;
+    let name = project
+        .get("name")
+        .and_then(|n| n.as_str())
+        .map(String::from)
+        .or_else(|| manifest_dir_name(&manifest))?;
+
+    let mut deps = Vec::new();
+    if let Some(list) = project.get("dependencies").and_then(|d| d.as_array()) {
+        for entry in list.iter().filter_map(|e| e.as_str()) {
+            // PEP 508: "requests>=2.28,<3" - the name ends at the first
+            // operator, bracket, space, or marker separator
+            let split = entry
+                .find(|c: char| "<>=!~[; ".contains(c))
+                .unwrap_or(entry.len());
+            let (dep, version) = entry.split_at(split);
+            let version = version.trim();
+            deps.push((
+                dep.to_string(),
+                if version.is_empty() {
+                    "*".to_string()
+                } else {
+                    version.to_string()
+                },
+            ));
+        }
+    }
+    Some(ProjectDeps {
+        name,
+        manifest,
+        deps,
+    })
+}
+
+fn parse_go_mod_deps(content: &str, manifest: PathBuf) -> Option<ProjectDeps> {
+    let name = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))?
+        .trim()
+        .to_string();
+
+    let mut deps = Vec::new();
+    let mut in_require = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("require (") {
+            in_require = true;
+            continue;
+        }
+        if in_require && trimmed == ")" {
+            in_require = false;
+            continue;
+        }
+        let entry = if in_require {
+            Some(trimmed)
+        } else {
+            trimmed.strip_prefix("require ")
+        };
+        if let Some(entry) = entry {
+            let mut parts = entry.split_whitespace();
+            if let (Some(dep), Some(version)) = (parts.next(), parts.next()) {
+                deps.push((dep.to_string(), version.to_string()));
+            }
+        }
+    }
+    Some(ProjectDeps {
+        name,
+        manifest,
+        deps,
+    })
+}
+
+/// Fall back to the manifest's directory name when a manifest is anonymous.
+fn manifest_dir_name(manifest: &Path) -> Option<String> {
+    manifest
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .and_then(|name| name.to_str())
+        .map(String::from)
+}
+
+/// `st --mode deps` - the workspace graph as a formatter.
+pub struct DepsFormatter {
+    /// Graph syntax: "mermaid" (default) or "dot" (--graph flag).
+    pub graph: Option<String>,
+}
+
+impl Default for DepsFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DepsFormatter {
+    pub fn new() -> Self {
+        Self { graph: None }
+    }
+
+    pub fn with_graph(mut self, graph: Option<String>) -> Self {
+        self.graph = graph;
+        self
+    }
+}
+
+impl Formatter for DepsFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        let report = DepsReport::gather(nodes, root_path);
+        match self.graph.as_deref() {
+            None | Some("mermaid") => report.render_mermaid(writer, root_path),
+            Some("dot") => report.render_dot(writer, root_path),
+            Some(other) => {
+                anyhow::bail!("Unknown graph format '{}' (expected mermaid or dot)", other)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_deps_string_and_table_forms() {
+        let content = "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1.0\"\nlocal = { path = \"../local\" }\ntokio = { version = \"1.38\", features = [\"full\"] }\n";
+        let project = parse_cargo_deps(content, PathBuf::from("Cargo.toml")).unwrap();
+
+        assert_eq!(project.name, "app");
+        let deps: BTreeMap<_, _> = project.deps.into_iter().collect();
+        assert_eq!(deps["serde"], "1.0");
+        assert_eq!(deps["tokio"], "1.38");
+        // Path-only deps still appear, with a wildcard version.
+        assert_eq!(deps["local"], "*");
+    }
+
+    #[test]
+    fn test_parse_pyproject_splits_pep508_specs() {
+        let content =
+            "[project]\nname = \"tool\"\ndependencies = [\"requests>=2.28\", \"rich\"]\n";
+        let project = parse_pyproject_deps(content, PathBuf::from("pyproject.toml")).unwrap();
+
+        assert_eq!(project.deps[0], ("requests".to_string(), ">=2.28".to_string()));
+        assert_eq!(project.deps[1], ("rich".to_string(), "*".to_string()));
+    }
+
+    #[test]
+    fn test_parse_go_mod_block_and_inline_requires() {
+        let content = "module example.com/app\n\nrequire golang.org/x/text v0.14.0\n\nrequire (\n\tgithub.com/pkg/errors v0.9.1\n)\n";
+        let project = parse_go_mod_deps(content, PathBuf::from("go.mod")).unwrap();
+
+        assert_eq!(project.name, "example.com/app");
+        assert_eq!(project.deps.len(), 2);
+        assert_eq!(project.deps[1].0, "github.com/pkg/errors");
+    }
+
+    #[test]
+    fn test_duplicate_versions_flags_only_mixed_pins() {
+        let report = DepsReport {
+            projects: vec![
+                ProjectDeps {
+                    name: "a".into(),
+                    manifest: PathBuf::from("a/Cargo.toml"),
+                    deps: vec![("serde".into(), "1.0".into()), ("anyhow".into(), "1".into())],
+                },
+                ProjectDeps {
+                    name: "b".into(),
+                    manifest: PathBuf::from("b/Cargo.toml"),
+                    deps: vec![("serde".into(), "0.9".into()), ("anyhow".into(), "1".into())],
+                },
+            ],
+        };
+
+        let duplicates = report.duplicate_versions();
+        assert!(duplicates.contains_key("serde"));
+        // anyhow agrees across projects - not a duplicate.
+        assert!(!duplicates.contains_key("anyhow"));
+        assert_eq!(duplicates["serde"]["1.0"], vec!["a"]);
+        assert_eq!(duplicates["serde"]["0.9"], vec!["b"]);
+    }
+}
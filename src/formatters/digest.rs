@@ -2,10 +2,218 @@ use super::Formatter;
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::io::Write;
 use std::path::Path;
+use std::time::UNIX_EPOCH;
 
-pub struct DigestFormatter;
+/// Per-directory Merkle digests (`--mode digest --merkle`)
+///
+/// Each directory's hash folds its direct children: files as leaves of
+/// (name, size, mtime), subdirectories by their own hash. A change to one
+/// file therefore moves exactly its ancestor chain of hashes - comparing
+/// two documents pinpoints the changed subtrees without a full diff.
+/// Keys are root-relative paths ("." is the root), one digest per line in
+/// the rendered form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleDigest {
+    pub dirs: BTreeMap<String, String>,
+}
+
+/// What changed between two Merkle documents
+#[derive(Debug, Default)]
+pub struct MerkleDiff {
+    /// Directories present in both whose hash moved
+    pub changed: Vec<String>,
+    /// Directories only the newer document has
+    pub added: Vec<String>,
+    /// Directories only the older document has
+    pub removed: Vec<String>,
+}
+
+impl MerkleDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// The deepest changed directories - the ones where edits actually
+    /// live, with the ancestor chain of knock-on hash moves filtered out
+    pub fn localized(&self) -> Vec<String> {
+        self.changed
+            .iter()
+            .filter(|dir| {
+                !self
+                    .changed
+                    .iter()
+                    .chain(self.added.iter())
+                    .chain(self.removed.iter())
+                    .any(|other| other != *dir && is_under(dir, other))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Is `descendant` strictly inside `ancestor`? ("." contains everything)
+fn is_under(ancestor: &str, descendant: &str) -> bool {
+    if ancestor == "." {
+        return descendant != ".";
+    }
+    descendant != ancestor && Path::new(descendant).starts_with(Path::new(ancestor))
+}
+
+impl MerkleDigest {
+    /// Build the document from one scan's nodes, bottom-up
+    pub fn build(nodes: &[FileNode], root: &Path) -> Self {
+        let rel = |path: &Path| -> String {
+            let stripped = path.strip_prefix(root).unwrap_or(path);
+            if stripped.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                stripped.to_string_lossy().to_string()
+            }
+        };
+
+        // Group direct children by parent directory
+        let mut dir_paths: Vec<String> = vec![".".to_string()];
+        let mut file_leaves: BTreeMap<String, Vec<(String, [u8; 32])>> = BTreeMap::new();
+        let mut child_dirs: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+        for node in nodes {
+            let node_rel = rel(&node.path);
+            if node_rel == "." {
+                continue;
+            }
+            if node.is_dir {
+                dir_paths.push(node_rel);
+                continue;
+            }
+            let name = name_of(&node.path);
+            let parent = node
+                .path
+                .parent()
+                .map(&rel)
+                .unwrap_or_else(|| ".".to_string());
+            file_leaves.entry(parent).or_default().push((name, leaf_hash(node)));
+        }
+        for dir in &dir_paths {
+            if dir == "." {
+                continue;
+            }
+            let parent = Path::new(dir)
+                .parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|| ".".to_string());
+            child_dirs
+                .entry(parent)
+                .or_default()
+                .push((name_of(Path::new(dir)), dir.clone()));
+        }
+
+        // Deepest directories first, so child hashes exist before parents
+        // ("." is depth 0, not the 1 component Path gives it - it must
+        // come last or the root would fold empty child hashes)
+        dir_paths.sort_by_key(|d| {
+            std::cmp::Reverse(if d == "." {
+                0
+            } else {
+                Path::new(d).components().count()
+            })
+        });
+        dir_paths.dedup();
+
+        let mut dirs: BTreeMap<String, String> = BTreeMap::new();
+        for dir in dir_paths {
+            // Children sorted by name: (tag, name, hash) per entry
+            let mut entries: Vec<(String, u8, Vec<u8>)> = Vec::new();
+            for (name, hash) in file_leaves.remove(&dir).unwrap_or_default() {
+                entries.push((name, b'f', hash.to_vec()));
+            }
+            for (name, child) in child_dirs.remove(&dir).unwrap_or_default() {
+                let hash = dirs.get(&child).cloned().unwrap_or_default();
+                entries.push((name, b'd', hash.into_bytes()));
+            }
+            entries.sort();
+
+            let mut hasher = Sha256::new();
+            for (name, tag, hash) in entries {
+                hasher.update(name.as_bytes());
+                hasher.update([0, tag]);
+                hasher.update(&hash);
+            }
+            // First 16 hex chars, same brevity as the flat digest
+            dirs.insert(dir, hex::encode(&hasher.finalize()[..8]));
+        }
+        Self { dirs }
+    }
+
+    /// The root directory's digest - moves if anything anywhere moved
+    pub fn root_hash(&self) -> &str {
+        self.dirs.get(".").map(String::as_str).unwrap_or("")
+    }
+
+    /// Parse a rendered document (the `MERKLE: v1` header plus one
+    /// "hash path" line per directory)
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+        let header = lines.next().unwrap_or_default();
+        if !header.starts_with("MERKLE: v1") {
+            anyhow::bail!("Not a Merkle digest document (expected a 'MERKLE: v1' header)");
+        }
+        let mut dirs = BTreeMap::new();
+        for line in lines {
+            let Some((hash, path)) = line.trim().split_once(' ') else {
+                anyhow::bail!("Malformed Merkle digest line: {:?}", line);
+            };
+            dirs.insert(path.to_string(), hash.to_string());
+        }
+        Ok(Self { dirs })
+    }
+
+    /// Compare this (older) document against a newer one
+    pub fn diff(&self, newer: &Self) -> MerkleDiff {
+        let mut diff = MerkleDiff::default();
+        for (dir, hash) in &self.dirs {
+            match newer.dirs.get(dir) {
+                Some(new_hash) if new_hash != hash => diff.changed.push(dir.clone()),
+                Some(_) => {}
+                None => diff.removed.push(dir.clone()),
+            }
+        }
+        for dir in newer.dirs.keys() {
+            if !self.dirs.contains_key(dir) {
+                diff.added.push(dir.clone());
+            }
+        }
+        diff
+    }
+}
+
+fn name_of(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+/// A file's leaf hash: name, size, and mtime - content changes move the
+/// mtime, so the leaf moves without reading a byte
+fn leaf_hash(node: &FileNode) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(name_of(&node.path).as_bytes());
+    hasher.update(node.size.to_le_bytes());
+    let mtime = node
+        .modified
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    hasher.update(mtime.to_le_bytes());
+    hasher.finalize().into()
+}
+
+pub struct DigestFormatter {
+    merkle: bool,
+}
 
 impl Default for DigestFormatter {
     fn default() -> Self {
@@ -15,7 +223,13 @@ impl Default for DigestFormatter {
 
 impl DigestFormatter {
     pub fn new() -> Self {
-        Self
+        Self { merkle: false }
+    }
+
+    /// Emit one Merkle digest per directory instead of a single flat hash
+    pub fn with_merkle(mut self, merkle: bool) -> Self {
+        self.merkle = merkle;
+        self
     }
 
     /// Calculate a SHA256 hash of the tree structure for consistency verification
@@ -50,8 +264,26 @@ impl Formatter for DigestFormatter {
         writer: &mut dyn Write,
         nodes: &[FileNode],
         stats: &TreeStats,
-        _root_path: &Path,
+        root_path: &Path,
     ) -> Result<()> {
+        // Merkle form: header plus one "hash path" line per directory,
+        // compact enough to store next to a scan and diff later
+        if self.merkle {
+            let merkle = MerkleDigest::build(nodes, root_path);
+            writeln!(
+                writer,
+                "MERKLE: v1 {} F:{} D:{} S:{:x}",
+                merkle.root_hash(),
+                stats.total_files,
+                stats.total_dirs,
+                stats.total_size,
+            )?;
+            for (path, hash) in &merkle.dirs {
+                writeln!(writer, "{} {}", hash, path)?;
+            }
+            return Ok(());
+        }
+
         // Calculate SHA256 hash of the tree structure
         let tree_hash = self.calculate_tree_hash(nodes);
 
@@ -82,3 +314,102 @@ impl Formatter for DigestFormatter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn node(path: &str, is_dir: bool, size: u64, mtime_secs: u64) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: UNIX_EPOCH + Duration::from_secs(mtime_secs),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth: path.matches('/').count(),
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    fn sample_tree(a_mtime: u64) -> Vec<FileNode> {
+        vec![
+            node("/t/src", true, 0, 100),
+            node("/t/src/a.rs", false, 10, a_mtime),
+            node("/t/src/b.rs", false, 20, 100),
+            node("/t/docs", true, 0, 100),
+            node("/t/docs/readme.md", false, 30, 100),
+        ]
+    }
+
+    #[test]
+    fn test_merkle_localizes_a_change() {
+        let root = Path::new("/t");
+        let before = MerkleDigest::build(&sample_tree(100), root);
+        let after = MerkleDigest::build(&sample_tree(999), root);
+
+        let diff = before.diff(&after);
+        // The touched file moves src and the root, but not docs
+        assert_eq!(diff.changed, vec![".".to_string(), "src".to_string()]);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.localized(), vec!["src".to_string()]);
+    }
+
+    #[test]
+    fn test_merkle_sees_added_and_removed_dirs() {
+        let root = Path::new("/t");
+        let before = MerkleDigest::build(&sample_tree(100), root);
+
+        let mut grown = sample_tree(100);
+        grown.push(node("/t/tests", true, 0, 100));
+        grown.push(node("/t/tests/it.rs", false, 5, 100));
+        let after = MerkleDigest::build(&grown, root);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added, vec!["tests".to_string()]);
+        assert_eq!(diff.changed, vec![".".to_string()]);
+        // And the reverse direction reports it as removed
+        assert_eq!(after.diff(&before).removed, vec!["tests".to_string()]);
+    }
+
+    #[test]
+    fn test_merkle_render_parse_roundtrip() {
+        let nodes = sample_tree(100);
+        let built = MerkleDigest::build(&nodes, Path::new("/t"));
+
+        let mut buf = Vec::new();
+        DigestFormatter::new()
+            .with_merkle(true)
+            .format(&mut buf, &nodes, &TreeStats::default(), Path::new("/t"))
+            .unwrap();
+        let text = String::from_utf8(buf).unwrap();
+
+        assert!(text.starts_with(&format!("MERKLE: v1 {}", built.root_hash())));
+        assert_eq!(MerkleDigest::parse(&text).unwrap(), built);
+        assert!(MerkleDigest::parse("HASH: abcd F:1").is_err());
+    }
+}
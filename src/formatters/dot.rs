@@ -0,0 +1,320 @@
+// -----------------------------------------------------------------------------
+// DOT FORMATTER - Graphviz output for the pipe-to-anything crowd! 🎯
+//
+// Renders the directory tree as a Graphviz digraph so you can do
+// `st --mode dot | dot -Tsvg -o tree.svg` and get a proper rendered graph.
+// Top-level directories become cluster subgraphs, and file nodes are scaled
+// by size so the big stuff jumps out at you.
+//
+// "If Mermaid is for READMEs, DOT is for posters." - Hue
+// -----------------------------------------------------------------------------
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+pub struct DotFormatter {
+    no_emoji: bool,
+}
+
+impl DotFormatter {
+    pub fn new(no_emoji: bool) -> Self {
+        Self { no_emoji }
+    }
+
+    /// Quote-safe DOT node ID derived from the path
+    fn node_id(path: &Path) -> String {
+        format!("n_{:x}", fnv1a(path.to_string_lossy().as_bytes()))
+    }
+
+    /// Escape a string for use inside a double-quoted DOT label
+    fn escape_label(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    fn label(&self, node: &FileNode) -> String {
+        let name = node
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| node.path.to_string_lossy().to_string());
+        let emoji = if self.no_emoji {
+            ""
+        } else if node.is_dir {
+            "📁 "
+        } else {
+            "📄 "
+        };
+
+        if node.is_dir || node.size == 0 {
+            format!("{}{}", emoji, Self::escape_label(&name))
+        } else {
+            format!(
+                "{}{}\\n{}",
+                emoji,
+                Self::escape_label(&name),
+                format_size(node.size)
+            )
+        }
+    }
+
+    /// Scale font size by how big this file is relative to the largest one.
+    /// Log-scaled so a single giant binary doesn't flatten everything else.
+    fn font_size(size: u64, max_size: u64) -> u32 {
+        if size == 0 || max_size == 0 {
+            return 10;
+        }
+        let ratio = (size as f64).ln() / (max_size as f64).ln().max(1.0);
+        10 + (ratio.clamp(0.0, 1.0) * 10.0) as u32
+    }
+
+    /// Fill color by size tier - big files run hot
+    fn fill_color(node: &FileNode, max_size: u64) -> &'static str {
+        if node.is_dir {
+            return "lightsteelblue";
+        }
+        if max_size == 0 || node.size == 0 {
+            return "whitesmoke";
+        }
+        match node.size as f64 / max_size as f64 {
+            r if r > 0.5 => "salmon",
+            r if r > 0.1 => "khaki",
+            _ => "whitesmoke",
+        }
+    }
+
+    fn write_node(&self, writer: &mut dyn Write, node: &FileNode, max_size: u64) -> Result<()> {
+        let shape = if node.is_dir { "folder" } else { "note" };
+        writeln!(
+            writer,
+            "        {} [label=\"{}\", shape={}, fillcolor=\"{}\", fontsize={}];",
+            Self::node_id(&node.path),
+            self.label(node),
+            shape,
+            Self::fill_color(node, max_size),
+            Self::font_size(node.size, max_size)
+        )?;
+        Ok(())
+    }
+}
+
+impl Formatter for DotFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        let root_name = root_path
+            .file_name()
+            .unwrap_or(root_path.as_os_str())
+            .to_string_lossy();
+        let max_size = nodes
+            .iter()
+            .filter(|n| !n.is_dir)
+            .map(|n| n.size)
+            .max()
+            .unwrap_or(0);
+
+        writeln!(writer, "digraph SmartTree {{")?;
+        writeln!(
+            writer,
+            "    // {} files, {} dirs - generated by st --mode dot",
+            stats.total_files, stats.total_dirs
+        )?;
+        writeln!(writer, "    rankdir=LR;")?;
+        writeln!(writer, "    node [style=filled, fontname=\"Helvetica\"];")?;
+        writeln!(writer, "    edge [color=gray50];")?;
+        writeln!(writer)?;
+
+        // Root node sits outside the clusters
+        let root_id = Self::node_id(root_path);
+        writeln!(
+            writer,
+            "    {} [label=\"{}\", shape=folder, fillcolor=\"lightsteelblue\"];",
+            root_id,
+            Self::escape_label(&root_name)
+        )?;
+
+        // Group nodes by their top-level directory; everything directly
+        // under the root stays ungrouped
+        let mut clusters: HashMap<String, Vec<&FileNode>> = HashMap::new();
+        let mut top_level: Vec<&FileNode> = Vec::new();
+        for node in nodes {
+            if node.path == root_path {
+                continue;
+            }
+            let relative = node.path.strip_prefix(root_path).unwrap_or(&node.path);
+            let mut components = relative.components();
+            let first = components
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().to_string());
+            let is_nested = components.next().is_some();
+            match first {
+                Some(top) if is_nested || node.is_dir => {
+                    clusters.entry(top).or_default().push(node)
+                }
+                _ => top_level.push(node),
+            }
+        }
+
+        // One cluster subgraph per top-level directory
+        let mut cluster_names: Vec<&String> = clusters.keys().collect();
+        cluster_names.sort();
+        for (idx, name) in cluster_names.iter().enumerate() {
+            writeln!(writer, "    subgraph cluster_{} {{", idx)?;
+            writeln!(writer, "        label=\"{}\";", Self::escape_label(name))?;
+            writeln!(writer, "        style=rounded;")?;
+            writeln!(writer, "        color=gray70;")?;
+            for node in &clusters[*name] {
+                self.write_node(writer, node, max_size)?;
+            }
+            writeln!(writer, "    }}")?;
+        }
+
+        // Loose files directly under the root
+        for node in &top_level {
+            self.write_node(writer, node, max_size)?;
+        }
+
+        // Parent -> child edges
+        writeln!(writer)?;
+        for node in nodes {
+            if node.path == root_path {
+                continue;
+            }
+            if let Some(parent) = node.path.parent() {
+                let parent_id = if parent == root_path {
+                    root_id.clone()
+                } else {
+                    Self::node_id(parent)
+                };
+                writeln!(
+                    writer,
+                    "    {} -> {};",
+                    parent_id,
+                    Self::node_id(&node.path)
+                )?;
+            }
+        }
+
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+}
+
+/// Tiny FNV-1a hash - stable node IDs without pulling in a hasher dep
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+fn format_size(size: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut size = size as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size as u64, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn node(path: &str, is_dir: bool, size: u64, depth: usize) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth,
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_dot_output_structure() {
+        let nodes = vec![
+            node("/proj/src", true, 0, 1),
+            node("/proj/src/main.rs", false, 4096, 2),
+            node("/proj/README.md", false, 512, 1),
+        ];
+        let stats = TreeStats::default();
+        let formatter = DotFormatter::new(true);
+        let mut output = Vec::new();
+        formatter
+            .format(&mut output, &nodes, &stats, Path::new("/proj"))
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("digraph SmartTree {"));
+        assert!(output.trim_end().ends_with('}'));
+        assert!(output.contains("subgraph cluster_0"));
+        assert!(output.contains("label=\"src\""));
+        assert!(output.contains("shape=folder"));
+        assert!(output.contains("->"));
+    }
+
+    #[test]
+    fn test_dot_escapes_quotes() {
+        let nodes = vec![node("/proj/we\"ird.txt", false, 10, 1)];
+        let stats = TreeStats::default();
+        let formatter = DotFormatter::new(true);
+        let mut output = Vec::new();
+        formatter
+            .format(&mut output, &nodes, &stats, Path::new("/proj"))
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("we\\\"ird.txt"));
+    }
+
+    #[test]
+    fn test_font_size_scaling() {
+        assert_eq!(DotFormatter::font_size(0, 1000), 10);
+        let max = DotFormatter::font_size(1000, 1000);
+        assert_eq!(max, 20);
+        let mid = DotFormatter::font_size(32, 1000);
+        assert!(mid > 10 && mid < max);
+    }
+}
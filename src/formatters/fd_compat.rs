@@ -0,0 +1,145 @@
+// fd-compatible path listing - the landing pad for `--compat fd`.
+//
+// fd's contract with scripts is beautifully small: one relative path per
+// line, nothing else. This formatter honors it exactly - no root entry, no
+// trailing slashes, no summary line - so `st --compat fd` can slot into
+// existing pipelines while the scanner's filters (glob, type, size, ignore
+// rules) do the narrowing. When color is on, paths are painted the way fd
+// paints them: directory components in the LS_COLORS `di` style and the
+// final component by its own rule.
+
+use super::Formatter;
+use crate::ls_colors::LsColors;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+
+pub struct FdCompatFormatter {
+    use_color: bool,
+}
+
+impl FdCompatFormatter {
+    pub fn new(use_color: bool) -> Self {
+        FdCompatFormatter { use_color }
+    }
+
+    /// Paint a relative path fd-style: every parent component in the `di`
+    /// color, the last component per the node's own LS_COLORS rule.
+    fn paint_path(&self, node: &FileNode, relative: &Path) -> String {
+        let colors = LsColors::global();
+        if !self.use_color || colors.is_empty() {
+            return relative.display().to_string();
+        }
+
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+        let dir_style = colors.style_for_type("di");
+
+        let mut painted = Vec::with_capacity(components.len());
+        for (i, component) in components.iter().enumerate() {
+            let is_last = i == components.len() - 1;
+            let style = if is_last {
+                colors.style_for(node)
+            } else {
+                dir_style
+            };
+            painted.push(match style {
+                Some(code) => format!("\x1b[{code}m{component}\x1b[0m"),
+                None => component.clone(),
+            });
+        }
+        painted.join("/")
+    }
+}
+
+impl Formatter for FdCompatFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        for node in nodes {
+            if node.path == root_path {
+                continue; // fd never lists the search root itself
+            }
+            let relative = node.path.strip_prefix(root_path).unwrap_or(&node.path);
+            writeln!(writer, "{}", self.paint_path(node, relative))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn node(path: &str, is_dir: bool, depth: usize) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size: 0,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth,
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_plain_relative_paths_one_per_line() {
+        let nodes = vec![
+            node("/proj", true, 0),
+            node("/proj/src", true, 1),
+            node("/proj/src/main.rs", false, 2),
+            node("/proj/README.md", false, 1),
+        ];
+        let mut output = Vec::new();
+        FdCompatFormatter::new(false)
+            .format(&mut output, &nodes, &TreeStats::default(), Path::new("/proj"))
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        // Exactly what fd emits: relative paths, no root, no decoration.
+        assert_eq!(output, "src\nsrc/main.rs\nREADME.md\n");
+    }
+
+    #[test]
+    fn test_no_color_codes_without_color() {
+        let nodes = vec![node("/proj", true, 0), node("/proj/a.rs", false, 1)];
+        let mut output = Vec::new();
+        FdCompatFormatter::new(false)
+            .format(&mut output, &nodes, &TreeStats::default(), Path::new("/proj"))
+            .unwrap();
+        assert!(!String::from_utf8(output).unwrap().contains('\x1b'));
+    }
+}
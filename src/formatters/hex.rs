@@ -5,12 +5,157 @@ use anyhow::Result;
 use std::io::Write;
 use std::path::Path;
 
+/// One hex-mode field - the single source of truth for rendering,
+/// `--hex-fields` parsing, and `st explain-format hex`. Order here is the
+/// default output order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexField {
+    Depth,
+    Perms,
+    Uid,
+    Gid,
+    Size,
+    Mtime,
+}
+
+impl HexField {
+    /// Default field set, in output order.
+    pub const ALL: &'static [HexField] = &[
+        HexField::Depth,
+        HexField::Perms,
+        HexField::Uid,
+        HexField::Gid,
+        HexField::Size,
+        HexField::Mtime,
+    ];
+
+    /// The `--hex-fields` spelling of this field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HexField::Depth => "depth",
+            HexField::Perms => "perms",
+            HexField::Uid => "uid",
+            HexField::Gid => "gid",
+            HexField::Size => "size",
+            HexField::Mtime => "mtime",
+        }
+    }
+
+    /// Fixed width in hex digits.
+    pub fn width(&self) -> usize {
+        match self {
+            HexField::Depth => 1,
+            HexField::Perms => 3,
+            HexField::Uid | HexField::Gid => 4,
+            HexField::Size | HexField::Mtime => 8,
+        }
+    }
+
+    /// Human description for explain-format output.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            HexField::Depth => "tree depth from the scan root",
+            HexField::Perms => "unix permission bits (octal mode, hex-printed)",
+            HexField::Uid => "owner user id",
+            HexField::Gid => "owner group id",
+            HexField::Size => "file size in bytes (0 for directories)",
+            HexField::Mtime => "modification time, unix seconds",
+        }
+    }
+
+    /// ANSI color used for this field in colored output.
+    fn color(&self) -> &'static str {
+        match self {
+            HexField::Depth => "\x1b[36m",               // cyan
+            HexField::Perms => "\x1b[33m",               // yellow
+            HexField::Uid | HexField::Gid => "\x1b[35m", // magenta
+            HexField::Size => "\x1b[32m",                // green
+            HexField::Mtime => "\x1b[34m",               // blue
+        }
+    }
+
+    /// Render this field's fixed-width hex value for a node.
+    fn render(&self, node: &FileNode) -> String {
+        match self {
+            HexField::Depth => format!("{:x}", node.depth),
+            HexField::Perms => format!("{:03x}", node.permissions),
+            HexField::Uid => format!("{:04x}", node.uid),
+            HexField::Gid => format!("{:04x}", node.gid),
+            HexField::Size => {
+                if node.is_dir {
+                    format!("{:08x}", 0)
+                } else {
+                    format!("{:08x}", node.size)
+                }
+            }
+            HexField::Mtime => format!(
+                "{:08x}",
+                node.modified
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            ),
+        }
+    }
+
+    /// Parse a `--hex-fields` spec like "perms,size,mtime".
+    pub fn parse_spec(spec: &str) -> Result<Vec<HexField>> {
+        let mut fields = Vec::new();
+        for name in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match HexField::ALL.iter().find(|f| f.name() == name) {
+                Some(field) => fields.push(*field),
+                None => anyhow::bail!(
+                    "Unknown hex field '{}' (available: {})",
+                    name,
+                    HexField::ALL
+                        .iter()
+                        .map(|f| f.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+        if fields.is_empty() {
+            anyhow::bail!("--hex-fields needs at least one field name");
+        }
+        Ok(fields)
+    }
+}
+
+/// Field layout documentation for `st explain-format hex`, generated from
+/// the same table that drives rendering - it cannot drift.
+pub fn explain_format() -> String {
+    let mut out = String::from(
+        "hex mode - fixed-width hex fields, one entry per line\n\n\
+         Line layout (default field order):\n\n",
+    );
+    for field in HexField::ALL {
+        out.push_str(&format!(
+            "  {:<6} {:>2} hex digits  {}\n",
+            field.name(),
+            field.width(),
+            field.describe()
+        ));
+    }
+    out.push_str(
+        "\nFields are space-separated and followed by an emoji (unless\n\
+         --no-emoji) and the entry name. Names in [brackets] are permission\n\
+         denied or ignored entries; directories may append [git-branch] and\n\
+         search hits append [SEARCH:Lline:Ccol] markers.\n\n\
+         Select a subset with --hex-fields, e.g.:\n\n\
+           st --mode hex --hex-fields perms,size,mtime\n",
+    );
+    out
+}
+
 pub struct HexFormatter {
     pub use_color: bool,
     pub no_emoji: bool,
     pub show_ignored: bool,
     pub path_mode: PathDisplayMode,
     pub show_filesystems: bool,
+    /// Subset/order of hex fields to emit (--hex-fields); None = all
+    pub fields: Option<Vec<HexField>>,
 }
 
 impl HexFormatter {
@@ -27,9 +172,16 @@ impl HexFormatter {
             show_ignored,
             path_mode,
             show_filesystems,
+            fields: None,
         }
     }
 
+    /// Emit only the chosen fields, in the chosen order (--hex-fields)
+    pub fn with_fields(mut self, fields: Option<Vec<HexField>>) -> Self {
+        self.fields = fields;
+        self
+    }
+
     /// Get context-aware emoji based on file type and node properties
     /// Returns different emojis for empty files, empty directories, and locked directories
     fn get_file_emoji(&self, node: &FileNode) -> &'static str {
@@ -37,23 +189,6 @@ impl HexFormatter {
     }
 
     fn format_node(&self, node: &FileNode, root_path: &Path) -> String {
-        let depth_hex = format!("{:x}", node.depth);
-        let perms_hex = format!("{:03x}", node.permissions);
-        let uid_hex = format!("{:04x}", node.uid);
-        let gid_hex = format!("{:04x}", node.gid);
-        let size_hex = if node.is_dir {
-            format!("{:08x}", 0)
-        } else {
-            format!("{:08x}", node.size)
-        };
-        let time_hex = format!(
-            "{:08x}",
-            node.modified
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs()
-        );
-
         let emoji = self.get_file_emoji(node);
 
         // Add filesystem indicator if enabled
@@ -126,51 +261,25 @@ impl HexFormatter {
             display_name
         };
 
-        if self.use_color {
-            // ANSI color codes
-            const CYAN: &str = "\x1b[36m";
-            const YELLOW: &str = "\x1b[33m";
-            const MAGENTA: &str = "\x1b[35m";
-            const GREEN: &str = "\x1b[32m";
-            const BLUE: &str = "\x1b[34m";
-            const RESET: &str = "\x1b[0m";
-
-            format!(
-                "{}{}{} {}{}{} {}{} {}{} {}{}{} {}{}{} {}{} {}",
-                CYAN,
-                depth_hex,
-                RESET,
-                YELLOW,
-                perms_hex,
-                RESET,
-                MAGENTA,
-                uid_hex,
-                gid_hex,
-                RESET,
-                GREEN,
-                size_hex,
-                RESET,
-                BLUE,
-                time_hex,
-                RESET,
-                fs_indicator,
-                emoji,
-                display_name_with_search
-            )
-        } else {
-            format!(
-                "{} {} {} {} {} {} {}{} {}",
-                depth_hex,
-                perms_hex,
-                uid_hex,
-                gid_hex,
-                size_hex,
-                time_hex,
-                fs_indicator,
-                emoji,
-                display_name_with_search
-            )
-        }
+        // Field values in the selected (or default) order, colored per field
+        const RESET: &str = "\x1b[0m";
+        let selected = self.fields.as_deref().unwrap_or(HexField::ALL);
+        let field_str = selected
+            .iter()
+            .map(|field| {
+                if self.use_color {
+                    format!("{}{}{}", field.color(), field.render(node), RESET)
+                } else {
+                    field.render(node)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            "{} {}{} {}",
+            field_str, fs_indicator, emoji, display_name_with_search
+        )
     }
 }
 
@@ -0,0 +1,347 @@
+// -----------------------------------------------------------------------------
+// HTML FORMATTER - Shareable interactive reports for the non-CLI crowd! 🌐
+//
+// `st --mode html > report.html` produces a single self-contained page:
+// collapsible directory tree, sortable largest-files table, and a file-type
+// bar chart. No external assets, no CDN calls - inline CSS/JS only, so the
+// file works offline and survives being emailed around.
+//
+// "Trisha can finally see the tree without opening a terminal!" - Hue
+// -----------------------------------------------------------------------------
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub struct HtmlFormatter {
+    no_emoji: bool,
+}
+
+impl HtmlFormatter {
+    pub fn new(no_emoji: bool) -> Self {
+        Self { no_emoji }
+    }
+
+    /// Escape text for safe embedding in HTML
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
+    fn icon(&self, node: &FileNode) -> &'static str {
+        if self.no_emoji {
+            return "";
+        }
+        if node.is_dir {
+            "📁 "
+        } else {
+            "📄 "
+        }
+    }
+
+    /// Recursively render one directory level as a nested list
+    fn write_level(
+        &self,
+        writer: &mut dyn Write,
+        children: &HashMap<PathBuf, Vec<&FileNode>>,
+        parent: &Path,
+        depth: usize,
+    ) -> Result<()> {
+        let Some(entries) = children.get(parent) else {
+            return Ok(());
+        };
+
+        writeln!(writer, "<ul>")?;
+        for node in entries {
+            let name = node
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| node.path.to_string_lossy().to_string());
+            let name = Self::escape(&name);
+
+            if node.is_dir {
+                // Open the first couple of levels so the report isn't a
+                // wall of closed folders
+                let open = if depth < 2 { " open" } else { "" };
+                writeln!(writer, "<li><details{}>", open)?;
+                writeln!(
+                    writer,
+                    "<summary>{}<span class=\"dir\">{}</span></summary>",
+                    self.icon(node),
+                    name
+                )?;
+                self.write_level(writer, children, &node.path, depth + 1)?;
+                writeln!(writer, "</details></li>")?;
+            } else {
+                writeln!(
+                    writer,
+                    "<li>{}<span class=\"file\">{}</span><span class=\"size\">{}</span></li>",
+                    self.icon(node),
+                    name,
+                    format_size(node.size)
+                )?;
+            }
+        }
+        writeln!(writer, "</ul>")?;
+        Ok(())
+    }
+
+    /// CSS bar chart of the most common file types
+    fn write_type_chart(&self, writer: &mut dyn Write, stats: &TreeStats) -> Result<()> {
+        let mut types: Vec<(&String, &u64)> = stats.file_types.iter().collect();
+        types.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        types.truncate(10);
+
+        let max = types.first().map(|(_, c)| **c).unwrap_or(0);
+        if max == 0 {
+            return Ok(());
+        }
+
+        writeln!(writer, "<h2>File types</h2>")?;
+        writeln!(writer, "<div class=\"chart\">")?;
+        for (ext, count) in types {
+            let width = (*count as f64 / max as f64 * 100.0).max(1.0);
+            writeln!(
+                writer,
+                "<div class=\"row\"><span class=\"ext\">.{}</span>\
+                 <div class=\"bar\" style=\"width:{:.0}%\"></div>\
+                 <span class=\"count\">{}</span></div>",
+                Self::escape(ext),
+                width,
+                count
+            )?;
+        }
+        writeln!(writer, "</div>")?;
+        Ok(())
+    }
+
+    /// Sortable table of the largest files
+    fn write_largest_table(
+        &self,
+        writer: &mut dyn Write,
+        stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        if stats.largest_files.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(writer, "<h2>Largest files</h2>")?;
+        writeln!(writer, "<table id=\"largest\">")?;
+        writeln!(
+            writer,
+            "<thead><tr>\
+             <th onclick=\"sortTable(0, false)\">Path</th>\
+             <th onclick=\"sortTable(1, true)\">Size</th>\
+             </tr></thead><tbody>"
+        )?;
+        for (size, path) in &stats.largest_files {
+            let relative = path.strip_prefix(root_path).unwrap_or(path);
+            writeln!(
+                writer,
+                "<tr><td>{}</td><td data-bytes=\"{}\">{}</td></tr>",
+                Self::escape(&relative.to_string_lossy()),
+                size,
+                format_size(*size)
+            )?;
+        }
+        writeln!(writer, "</tbody></table>")?;
+        Ok(())
+    }
+}
+
+impl Formatter for HtmlFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        let root_name = Self::escape(
+            &root_path
+                .file_name()
+                .unwrap_or(root_path.as_os_str())
+                .to_string_lossy(),
+        );
+
+        // Group children by parent, directories before files
+        let mut children: HashMap<PathBuf, Vec<&FileNode>> = HashMap::new();
+        for node in nodes {
+            if node.path == root_path {
+                continue;
+            }
+            if let Some(parent) = node.path.parent() {
+                children.entry(parent.to_path_buf()).or_default().push(node);
+            }
+        }
+        for entries in children.values_mut() {
+            entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.path.cmp(&b.path)));
+        }
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\"><head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<title>Smart Tree Report - {}</title>", root_name)?;
+        writeln!(writer, "<style>{}</style>", INLINE_CSS)?;
+        writeln!(writer, "</head><body>")?;
+
+        writeln!(writer, "<h1>{} {}</h1>", if self.no_emoji { "" } else { "🌳" }, root_name)?;
+        writeln!(
+            writer,
+            "<p class=\"summary\">{} files, {} directories, {} total</p>",
+            stats.total_files,
+            stats.total_dirs,
+            format_size(stats.total_size)
+        )?;
+
+        writeln!(writer, "<h2>Tree</h2>")?;
+        writeln!(writer, "<div class=\"tree\">")?;
+        self.write_level(writer, &children, root_path, 0)?;
+        writeln!(writer, "</div>")?;
+
+        self.write_type_chart(writer, stats)?;
+        self.write_largest_table(writer, stats, root_path)?;
+
+        writeln!(writer, "<script>{}</script>", INLINE_JS)?;
+        writeln!(writer, "</body></html>")?;
+        Ok(())
+    }
+}
+
+const INLINE_CSS: &str = "\
+body{font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;margin:2em auto;max-width:60em;color:#222;padding:0 1em}\
+h1{border-bottom:2px solid #2e7d32}\
+.summary{color:#555}\
+.tree ul{list-style:none;padding-left:1.2em;border-left:1px dotted #bbb;margin:0.1em 0}\
+.tree li{padding:0.1em 0}\
+.tree summary{cursor:pointer}\
+.dir{font-weight:600;color:#1565c0}\
+.file{color:#333}\
+.size{color:#888;font-size:0.85em;margin-left:0.6em}\
+.chart .row{display:flex;align-items:center;margin:0.2em 0}\
+.chart .ext{width:5em;font-family:monospace;text-align:right;padding-right:0.5em}\
+.chart .bar{background:#66bb6a;height:1em;border-radius:2px}\
+.chart .count{padding-left:0.5em;color:#555;font-size:0.85em}\
+table{border-collapse:collapse;margin-top:0.5em}\
+th{cursor:pointer;background:#e8f5e9;text-align:left}\
+th,td{border:1px solid #ccc;padding:0.25em 0.6em}\
+tr:nth-child(even){background:#fafafa}";
+
+const INLINE_JS: &str = "\
+function sortTable(col, numeric){\
+  var tbody=document.querySelector('#largest tbody');\
+  var rows=Array.from(tbody.rows);\
+  var dir=tbody.dataset.dir==='asc'?-1:1;\
+  tbody.dataset.dir=dir===1?'asc':'desc';\
+  rows.sort(function(a,b){\
+    var x=a.cells[col], y=b.cells[col];\
+    if(numeric){return dir*((+x.dataset.bytes)-(+y.dataset.bytes));}\
+    return dir*x.textContent.localeCompare(y.textContent);\
+  });\
+  rows.forEach(function(r){tbody.appendChild(r);});\
+}";
+
+fn format_size(size: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut size = size as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size as u64, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::time::SystemTime;
+
+    fn node(path: &str, is_dir: bool, size: u64, depth: usize) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth,
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_html_report_structure() {
+        let nodes = vec![
+            node("/proj/src", true, 0, 1),
+            node("/proj/src/main.rs", false, 4096, 2),
+            node("/proj/README.md", false, 512, 1),
+        ];
+        let mut stats = TreeStats::default();
+        for n in &nodes {
+            stats.update_file(n);
+        }
+
+        let formatter = HtmlFormatter::new(true);
+        let mut output = Vec::new();
+        formatter
+            .format(&mut output, &nodes, &stats, Path::new("/proj"))
+            .unwrap();
+        let html = String::from_utf8(output).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<details open>"));
+        assert!(html.contains("main.rs"));
+        assert!(html.contains("sortTable"));
+        // Self-contained: no external asset references
+        assert!(!html.contains("http://"));
+        assert!(!html.contains("https://"));
+    }
+
+    #[test]
+    fn test_html_escapes_names() {
+        let nodes = vec![node("/proj/<script>.txt", false, 10, 1)];
+        let stats = TreeStats::default();
+        let formatter = HtmlFormatter::new(true);
+        let mut output = Vec::new();
+        formatter
+            .format(&mut output, &nodes, &stats, Path::new("/proj"))
+            .unwrap();
+        let html = String::from_utf8(output).unwrap();
+        assert!(html.contains("&lt;script&gt;.txt"));
+        assert!(!html.contains("<script>.txt"));
+    }
+}
@@ -1,3 +1,4 @@
+use super::stats::{dir_extension_stats, ExtStat};
 use super::Formatter;
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
@@ -8,11 +9,31 @@ use std::path::{Path, PathBuf};
 
 pub struct JsonFormatter {
     pub compact: bool,
+    /// Attach recursive per-extension totals to directory nodes (--per-dir)
+    pub ext_stats: bool,
+    /// Attach media metadata (dimensions, duration) to file nodes (--media-info)
+    pub media_info: bool,
 }
 
 impl JsonFormatter {
     pub fn new(compact: bool) -> Self {
-        Self { compact }
+        Self {
+            compact,
+            ext_stats: false,
+            media_info: false,
+        }
+    }
+
+    /// Enable per-directory extension statistics on directory nodes.
+    pub fn with_ext_stats(mut self, ext_stats: bool) -> Self {
+        self.ext_stats = ext_stats;
+        self
+    }
+
+    /// Enable media metadata probing on file nodes.
+    pub fn with_media_info(mut self, media_info: bool) -> Self {
+        self.media_info = media_info;
+        self
     }
 
     fn build_json_tree(&self, nodes: &[FileNode], root_path: &Path) -> Value {
@@ -34,6 +55,8 @@ impl JsonFormatter {
         fn node_to_json(
             node: &FileNode,
             children_map: &HashMap<PathBuf, Vec<&FileNode>>,
+            ext_stats: Option<&HashMap<PathBuf, HashMap<String, ExtStat>>>,
+            media_info: bool,
             _root_path: &Path,
         ) -> Value {
             let name = node
@@ -79,26 +102,90 @@ impl JsonFormatter {
                 obj["symlink"] = json!(true);
             }
 
+            // Extended attributes (--xattrs): name -> display value
+            if let Some(xattrs) = node.xattrs.as_ref().filter(|x| !x.is_empty()) {
+                let map: serde_json::Map<String, serde_json::Value> = xattrs
+                    .iter()
+                    .map(|(name, value)| (name.clone(), json!(value)))
+                    .collect();
+                obj["xattrs"] = json!(map);
+            }
+
+            // Media metadata (--media-info): dimensions, duration, codec,
+            // EXIF date - only present when the header yielded something
+            if media_info && !node.is_dir {
+                if let Some(info) = crate::media_info::probe(&node.path) {
+                    let mut media = serde_json::Map::new();
+                    if let Some((w, h)) = info.dimensions {
+                        media.insert("width".to_string(), json!(w));
+                        media.insert("height".to_string(), json!(h));
+                    }
+                    if let Some(secs) = info.duration_secs {
+                        media.insert("duration_secs".to_string(), json!(secs));
+                    }
+                    if let Some(codec) = &info.codec {
+                        media.insert("codec".to_string(), json!(codec));
+                    }
+                    if let Some(date) = &info.exif_date {
+                        media.insert("exif_date".to_string(), json!(date));
+                    }
+                    obj["media"] = json!(media);
+                }
+            }
+
+            // Recursive extension totals for directories (--per-dir):
+            // ext -> {count, bytes}, biggest first
+            if node.is_dir {
+                if let Some(exts) = ext_stats.and_then(|stats| stats.get(&node.path)) {
+                    let mut sorted: Vec<(&String, &ExtStat)> = exts.iter().collect();
+                    sorted.sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes).then_with(|| a.0.cmp(b.0)));
+                    let map: serde_json::Map<String, Value> = sorted
+                        .into_iter()
+                        .map(|(ext, stat)| {
+                            (
+                                ext.clone(),
+                                json!({ "count": stat.count, "bytes": stat.bytes }),
+                            )
+                        })
+                        .collect();
+                    obj["ext_stats"] = json!(map);
+                }
+            }
+
             // Add children for directories
             if let Some(children) = children_map.get(&node.path) {
                 let mut sorted_children = children.to_vec();
                 sorted_children.sort_by(|a, b| match (a.is_dir, b.is_dir) {
                     (true, false) => std::cmp::Ordering::Less,
                     (false, true) => std::cmp::Ordering::Greater,
-                    _ => a.path.file_name().cmp(&b.path.file_name()),
+                    // Natural order so file2 lands before file10
+                    _ => crate::scanner::natural_cmp(
+                        &a.path.file_name().unwrap_or_default().to_string_lossy(),
+                        &b.path.file_name().unwrap_or_default().to_string_lossy(),
+                    ),
                 });
 
                 obj["children"] = json!(sorted_children
                     .iter()
-                    .map(|child| node_to_json(child, children_map, _root_path))
+                    .map(|child| node_to_json(child, children_map, ext_stats, media_info, _root_path))
                     .collect::<Vec<_>>());
             }
 
             obj
         }
 
+        let ext_stats = self
+            .ext_stats
+            .then(|| dir_extension_stats(nodes, root_path));
+
         if let Some(root) = root_node {
-            node_to_json(root, &children_map, root_path)
+            node_to_json(
+                root,
+                &children_map,
+                ext_stats.as_ref(),
+                self.media_info,
+                root_path,
+            )
         } else {
             json!({})
         }
@@ -0,0 +1,463 @@
+// -----------------------------------------------------------------------------
+// 📜 LICENSE INVENTORY FORMATTER - What Are We Actually Allowed to Ship? ⚖️
+//
+// `st --mode licenses` reads LICENSE/COPYING files and per-file
+// `SPDX-License-Identifier:` headers, summarizes the project's license mix,
+// and inventories the components it finds manifests for (Cargo.toml,
+// package.json, pyproject.toml, go.mod). With `--sbom cyclonedx` or
+// `--sbom spdx` the same inventory comes out as a machine-readable SBOM
+// document instead of the text report - compliance review straight from the
+// tree scanner, no extra tooling.
+// -----------------------------------------------------------------------------
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// License texts and sources larger than this are skipped - real license
+/// files and headers live near the top of small text files.
+const MAX_SCAN_BYTES: u64 = 256 * 1024;
+
+/// Lines examined for an SPDX header - conventionally the first few.
+const SPDX_HEADER_LINES: usize = 10;
+
+/// One dependency manifest's identity: what it is and what it claims.
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub name: String,
+    pub version: Option<String>,
+    pub license: Option<String>,
+    /// The manifest this came from, relative to the scan root.
+    pub manifest: PathBuf,
+}
+
+/// Everything the license sweep learned about the tree.
+pub struct LicenseReport {
+    /// (relative path, detected SPDX id or "unrecognized") per license file.
+    pub license_files: Vec<(PathBuf, String)>,
+    /// SPDX header id -> number of source files carrying it.
+    pub spdx_headers: HashMap<String, usize>,
+    /// Components discovered via manifests, in manifest-path order.
+    pub components: Vec<Component>,
+}
+
+impl LicenseReport {
+    /// Sweep the scanned nodes: license files, SPDX headers, manifests.
+    pub fn gather(nodes: &[FileNode], root: &Path) -> Self {
+        let spdx_re = Regex::new(r"SPDX-License-Identifier:\s*([A-Za-z0-9 .()+-]+?)\s*(?:\*/)?\s*$")
+            .expect("SPDX header regex must compile");
+        let mut license_files = Vec::new();
+        let mut spdx_headers: HashMap<String, usize> = HashMap::new();
+        let mut components = Vec::new();
+
+        for node in nodes {
+            if node.is_dir
+                || node.is_symlink
+                || node.permission_denied
+                || node.size > MAX_SCAN_BYTES
+            {
+                continue;
+            }
+            let Some(name) = node.path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let rel = node
+                .path
+                .strip_prefix(root)
+                .unwrap_or(&node.path)
+                .to_path_buf();
+
+            if is_license_file(name) {
+                if let Ok(content) = std::fs::read_to_string(&node.path) {
+                    let id = detect_license_text(&content)
+                        .unwrap_or("unrecognized")
+                        .to_string();
+                    license_files.push((rel, id));
+                }
+                continue;
+            }
+
+            if let Some(component) = parse_manifest(name, &node.path, rel.clone()) {
+                components.push(component);
+                continue;
+            }
+
+            // SPDX header sniff: first few lines of anything text-like.
+            if let Ok(content) = std::fs::read_to_string(&node.path) {
+                for line in content.lines().take(SPDX_HEADER_LINES) {
+                    if let Some(captures) = spdx_re.captures(line) {
+                        *spdx_headers.entry(captures[1].to_string()).or_insert(0) += 1;
+                        break;
+                    }
+                }
+            }
+        }
+
+        license_files.sort();
+        components.sort_by(|a, b| a.manifest.cmp(&b.manifest));
+        Self {
+            license_files,
+            spdx_headers,
+            components,
+        }
+    }
+
+    /// The human-readable inventory - the `--mode licenses` default.
+    pub fn render(&self, writer: &mut dyn Write, root: &Path) -> Result<()> {
+        writeln!(writer, "📜 License Inventory: {}", root.display())?;
+        writeln!(
+            writer,
+            "   ({} license files, {} SPDX-tagged sources, {} components)",
+            self.license_files.len(),
+            self.spdx_headers.values().sum::<usize>(),
+            self.components.len()
+        )?;
+        writeln!(writer)?;
+
+        if !self.license_files.is_empty() {
+            writeln!(writer, "License Files:")?;
+            for (path, id) in &self.license_files {
+                writeln!(writer, "  {}: {}", path.display(), id)?;
+            }
+            writeln!(writer)?;
+        }
+
+        if !self.spdx_headers.is_empty() {
+            writeln!(writer, "SPDX Header Mix:")?;
+            let mut mix: Vec<(&String, &usize)> = self.spdx_headers.iter().collect();
+            mix.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            for (id, count) in mix {
+                writeln!(writer, "  {}: {} files", id, count)?;
+            }
+            writeln!(writer)?;
+        }
+
+        if !self.components.is_empty() {
+            writeln!(writer, "Components:")?;
+            for component in &self.components {
+                writeln!(
+                    writer,
+                    "  {} {} ({}) - {}",
+                    component.name,
+                    component.version.as_deref().unwrap_or("?"),
+                    component.manifest.display(),
+                    component.license.as_deref().unwrap_or("no license declared")
+                )?;
+            }
+        }
+
+        if self.license_files.is_empty()
+            && self.spdx_headers.is_empty()
+            && self.components.is_empty()
+        {
+            writeln!(writer, "Nothing license-shaped found in this tree.")?;
+        }
+        Ok(())
+    }
+
+    /// Minimal CycloneDX 1.5 JSON document (`--sbom cyclonedx`).
+    pub fn render_cyclonedx(&self, writer: &mut dyn Write, root: &Path) -> Result<()> {
+        let components: Vec<serde_json::Value> = self
+            .components
+            .iter()
+            .map(|c| {
+                let mut obj = json!({
+                    "type": "application",
+                    "name": c.name,
+                });
+                if let Some(version) = &c.version {
+                    obj["version"] = json!(version);
+                }
+                if let Some(license) = &c.license {
+                    obj["licenses"] = json!([{ "license": { "id": license } }]);
+                }
+                obj
+            })
+            .collect();
+
+        let doc = json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "metadata": {
+                "component": {
+                    "type": "application",
+                    "name": root
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("project"),
+                }
+            },
+            "components": components
+        });
+        writeln!(writer, "{}", serde_json::to_string_pretty(&doc)?)?;
+        Ok(())
+    }
+
+    /// Minimal SPDX 2.3 JSON document (`--sbom spdx`).
+    pub fn render_spdx(&self, writer: &mut dyn Write, root: &Path) -> Result<()> {
+        let packages: Vec<serde_json::Value> = self
+            .components
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                json!({
+                    "name": c.name,
+                    "SPDXID": format!("SPDXRef-Package-{}", i + 1),
+                    "versionInfo": c.version.as_deref().unwrap_or("NOASSERTION"),
+                    "licenseDeclared": c.license.as_deref().unwrap_or("NOASSERTION"),
+                    "downloadLocation": "NOASSERTION"
+                })
+            })
+            .collect();
+
+        let doc = json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": root
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("project"),
+            "documentNamespace": format!("https://spdx.org/spdxdocs/st-{}", std::process::id()),
+            "packages": packages
+        });
+        writeln!(writer, "{}", serde_json::to_string_pretty(&doc)?)?;
+        Ok(())
+    }
+}
+
+/// Is this filename a license text by convention?
+fn is_license_file(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    ["LICENSE", "LICENCE", "COPYING", "NOTICE", "UNLICENSE"]
+        .iter()
+        .any(|prefix| upper == *prefix || upper.starts_with(&format!("{}.", prefix)))
+        || upper.starts_with("LICENSE-")
+}
+
+/// Identify a license text by its tell-tale phrases. Returns an SPDX id.
+fn detect_license_text(content: &str) -> Option<&'static str> {
+    let text = content.to_lowercase();
+    if text.contains("apache license") && text.contains("version 2.0") {
+        Some("Apache-2.0")
+    } else if text.contains("gnu lesser general public license") {
+        if text.contains("version 3") {
+            Some("LGPL-3.0-only")
+        } else {
+            Some("LGPL-2.1-only")
+        }
+    } else if text.contains("gnu affero general public license") {
+        Some("AGPL-3.0-only")
+    } else if text.contains("gnu general public license") {
+        if text.contains("version 3") {
+            Some("GPL-3.0-only")
+        } else {
+            Some("GPL-2.0-only")
+        }
+    } else if text.contains("mozilla public license") && text.contains("2.0") {
+        Some("MPL-2.0")
+    } else if text.contains("permission is hereby granted, free of charge") {
+        Some("MIT")
+    } else if text.contains("permission to use, copy, modify, and") {
+        Some("ISC")
+    } else if text.contains("redistribution and use in source and binary forms") {
+        if text.contains("neither the name") {
+            Some("BSD-3-Clause")
+        } else {
+            Some("BSD-2-Clause")
+        }
+    } else if text.contains("this is free and unencumbered software") {
+        Some("Unlicense")
+    } else {
+        None
+    }
+}
+
+/// Parse one dependency manifest into a component, if the filename is one we
+/// understand. Parse failures yield None - a broken manifest is not a
+/// component claim.
+fn parse_manifest(name: &str, path: &Path, rel: PathBuf) -> Option<Component> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match name {
+        "Cargo.toml" => parse_cargo_toml(&content, rel),
+        "package.json" => parse_package_json(&content, rel),
+        "pyproject.toml" => parse_pyproject_toml(&content, rel),
+        "go.mod" => parse_go_mod(&content, rel),
+        _ => None,
+    }
+}
+
+fn parse_cargo_toml(content: &str, manifest: PathBuf) -> Option<Component> {
+    let doc: toml::Value = content.parse().ok()?;
+    let package = doc.get("package")?;
+    Some(Component {
+        name: package.get("name")?.as_str()?.to_string(),
+        version: package
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        license: package
+            .get("license")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        manifest,
+    })
+}
+
+fn parse_package_json(content: &str, manifest: PathBuf) -> Option<Component> {
+    let doc: serde_json::Value = serde_json::from_str(content).ok()?;
+    Some(Component {
+        name: doc.get("name")?.as_str()?.to_string(),
+        version: doc
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        // Modern "license": "MIT" or legacy {"type": "MIT"}
+        license: doc.get("license").and_then(|v| {
+            v.as_str()
+                .map(String::from)
+                .or_else(|| v.get("type").and_then(|t| t.as_str()).map(String::from))
+        }),
+        manifest,
+    })
+}
+
+fn parse_pyproject_toml(content: &str, manifest: PathBuf) -> Option<Component> {
+    let doc: toml::Value = content.parse().ok()?;
+    let project = doc.get("project")?;
+    Some(Component {
+        name: project.get("name")?.as_str()?.to_string(),
+        version: project
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        // PEP 621: "license": "MIT" (expression) or {"text": "..."}
+        license: project.get("license").and_then(|v| {
+            v.as_str()
+                .map(String::from)
+                .or_else(|| v.get("text").and_then(|t| t.as_str()).map(String::from))
+        }),
+        manifest,
+    })
+}
+
+fn parse_go_mod(content: &str, manifest: PathBuf) -> Option<Component> {
+    let module = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))?;
+    Some(Component {
+        name: module.trim().to_string(),
+        version: None,
+        license: None,
+        manifest,
+    })
+}
+
+/// `st --mode licenses` - the inventory as a formatter.
+pub struct LicensesFormatter {
+    /// SBOM output format ("cyclonedx" or "spdx"); None renders the text
+    /// report (--sbom flag).
+    pub sbom: Option<String>,
+}
+
+impl Default for LicensesFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LicensesFormatter {
+    pub fn new() -> Self {
+        Self { sbom: None }
+    }
+
+    pub fn with_sbom(mut self, sbom: Option<String>) -> Self {
+        self.sbom = sbom;
+        self
+    }
+}
+
+impl Formatter for LicensesFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        let report = LicenseReport::gather(nodes, root_path);
+        match self.sbom.as_deref() {
+            Some("cyclonedx") => report.render_cyclonedx(writer, root_path),
+            Some("spdx") => report.render_spdx(writer, root_path),
+            Some(other) => {
+                anyhow::bail!("Unknown SBOM format '{}' (expected cyclonedx or spdx)", other)
+            }
+            None => report.render(writer, root_path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_license_text_by_phrase() {
+        assert_eq!(
+            detect_license_text("Permission is hereby granted, free of charge, to any person"),
+            Some("MIT")
+        );
+        assert_eq!(
+            detect_license_text("Apache License\nVersion 2.0, January 2004"),
+            Some("Apache-2.0")
+        );
+        assert_eq!(
+            detect_license_text(
+                "Redistribution and use in source and binary forms...\
+                 Neither the name of the copyright holder"
+            ),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(detect_license_text("all rights reserved, mine mine mine"), None);
+    }
+
+    #[test]
+    fn test_is_license_file_conventions() {
+        assert!(is_license_file("LICENSE"));
+        assert!(is_license_file("license.txt"));
+        assert!(is_license_file("LICENSE-MIT"));
+        assert!(is_license_file("COPYING"));
+        assert!(!is_license_file("licenses.rs"));
+        assert!(!is_license_file("main.rs"));
+    }
+
+    #[test]
+    fn test_parse_cargo_toml_component() {
+        let content = "[package]\nname = \"demo\"\nversion = \"1.2.3\"\nlicense = \"MIT\"\n";
+        let component = parse_cargo_toml(content, PathBuf::from("Cargo.toml")).unwrap();
+        assert_eq!(component.name, "demo");
+        assert_eq!(component.version.as_deref(), Some("1.2.3"));
+        assert_eq!(component.license.as_deref(), Some("MIT"));
+
+        // A workspace-only manifest has no [package] - not a component.
+        assert!(parse_cargo_toml("[workspace]\nmembers = []\n", PathBuf::new()).is_none());
+    }
+
+    #[test]
+    fn test_parse_package_json_license_shapes() {
+        let modern = r#"{"name": "app", "version": "1.0.0", "license": "ISC"}"#;
+        let component = parse_package_json(modern, PathBuf::from("package.json")).unwrap();
+        assert_eq!(component.license.as_deref(), Some("ISC"));
+
+        let legacy = r#"{"name": "app", "license": {"type": "MIT", "url": "x"}}"#;
+        let component = parse_package_json(legacy, PathBuf::from("package.json")).unwrap();
+        assert_eq!(component.license.as_deref(), Some("MIT"));
+        assert_eq!(component.version, None);
+    }
+}
@@ -17,11 +17,13 @@
 // and Aye gets to show off some Rust file system wizardry! 🎭
 // -----------------------------------------------------------------------------
 
+use super::columns::{column_widths, visible_width, Column};
 use super::Formatter;
 use crate::emoji_mapper;
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
 use chrono::{DateTime, Local};
+use colored::Colorize;
 use std::fs;
 use std::io::Write;
 use std::path::Path;
@@ -42,8 +44,32 @@ use std::os::unix::fs::{MetadataExt, PermissionsExt};
 pub struct LsFormatter {
     /// Whether to show emojis alongside filenames (default: true)
     show_emojis: bool,
-    /// Whether to use colors in output (default: true)  
+    /// Whether to use colors in output (default: true)
     use_colors: bool,
+    /// Prefix each line with a git status marker (M/A/D/??)
+    git_status: bool,
+    /// Append last commit age and author per file
+    git_blame_summary: bool,
+    /// Append media metadata (dimensions, duration) per file (--media-info)
+    media_info: bool,
+    /// Custom column selection and order (--columns); None = classic ls layout
+    columns: Option<Vec<Column>>,
+    /// How the size column is rendered (--si / -k)
+    size_style: SizeStyle,
+    /// GNU ls --time-style: full-iso, long-iso, iso, locale, or +FORMAT
+    time_style: Option<String>,
+}
+
+/// Size column rendering, mirroring the GNU ls options people actually use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeStyle {
+    /// Human-readable, powers of 1024 (the ls -h default here)
+    #[default]
+    Human,
+    /// Human-readable, powers of 1000 (ls --si)
+    Si,
+    /// Raw 1K-block counts (ls -k)
+    Kibibytes,
 }
 
 impl Default for LsFormatter {
@@ -62,9 +88,46 @@ impl LsFormatter {
         Self {
             show_emojis,
             use_colors,
+            git_status: false,
+            git_blame_summary: false,
+            media_info: false,
+            columns: None,
+            size_style: SizeStyle::default(),
+            time_style: None,
         }
     }
 
+    /// Decorate entries with git status markers and/or last-commit summaries
+    pub fn with_git(mut self, status: bool, blame_summary: bool) -> Self {
+        self.git_status = status;
+        self.git_blame_summary = blame_summary;
+        self
+    }
+
+    /// Append image dimensions / playback duration after media filenames
+    pub fn with_media_info(mut self, media_info: bool) -> Self {
+        self.media_info = media_info;
+        self
+    }
+
+    /// Replace the fixed ls layout with user-chosen columns (--columns)
+    pub fn with_columns(mut self, columns: Option<Vec<Column>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Choose how sizes are rendered (--si / -k)
+    pub fn with_size_style(mut self, size_style: SizeStyle) -> Self {
+        self.size_style = size_style;
+        self
+    }
+
+    /// Override the timestamp format, GNU ls --time-style spellings
+    pub fn with_time_style(mut self, time_style: Option<String>) -> Self {
+        self.time_style = time_style;
+        self
+    }
+
     /// Format file permissions in the classic Unix style (e.g., drwxrwxr-x)
     ///
     /// This creates the familiar 10-character permission string that every
@@ -77,13 +140,7 @@ impl LsFormatter {
             Err(_) => return "?---------".to_string(), // Permission denied or file missing
         };
 
-        let file_type = if metadata.is_dir() {
-            'd'
-        } else if metadata.is_symlink() {
-            'l'
-        } else {
-            '-'
-        };
+        let file_type = node.file_type.mode_char();
 
         #[cfg(unix)]
         {
@@ -134,10 +191,19 @@ impl LsFormatter {
 
     /// Format file size in human-readable format (like ls -h)
     ///
-    /// Converts bytes to human-readable units (B, K, M, G, T)
-    /// Uses binary units (1024) like traditional ls command
+    /// Default is binary units (1024) like traditional ls; --si switches
+    /// to powers of 1000 and -k to raw 1K-block counts.
     fn format_size(&self, size: u64) -> String {
-        const UNITS: &[&str] = &["B", "K", "M", "G", "T"];
+        if self.size_style == SizeStyle::Kibibytes {
+            // ls -k: 1K blocks, rounded up, no suffix
+            return size.div_ceil(1024).to_string();
+        }
+
+        let (units, step): (&[&str], f64) = if self.size_style == SizeStyle::Si {
+            (&["B", "k", "M", "G", "T"], 1000.0)
+        } else {
+            (&["B", "K", "M", "G", "T"], 1024.0)
+        };
 
         if size == 0 {
             return "0".to_string();
@@ -146,20 +212,54 @@ impl LsFormatter {
         let mut size_f = size as f64;
         let mut unit_index = 0;
 
-        while size_f >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size_f /= 1024.0;
+        while size_f >= step && unit_index < units.len() - 1 {
+            size_f /= step;
             unit_index += 1;
         }
 
         if unit_index == 0 {
             format!("{}", size)
         } else if size_f >= 10.0 {
-            format!("{:.0}{}", size_f, UNITS[unit_index])
+            format!("{:.0}{}", size_f, units[unit_index])
         } else {
-            format!("{:.1}{}", size_f, UNITS[unit_index])
+            format!("{:.1}{}", size_f, units[unit_index])
         }
     }
 
+    /// Render the modification time per --time-style, defaulting to the
+    /// classic "Jul  9 14:56" ls look.
+    fn format_mtime(&self, datetime: &DateTime<Local>) -> String {
+        let format = match self.time_style.as_deref() {
+            Some("full-iso") => "%Y-%m-%d %H:%M:%S.%9f %z",
+            Some("long-iso") => "%Y-%m-%d %H:%M",
+            Some("iso") => "%m-%d %H:%M",
+            // "locale" and unset both get the traditional layout
+            Some(custom) if custom.starts_with('+') => &custom[1..],
+            _ => "%b %d %H:%M",
+        };
+        datetime.format(format).to_string()
+    }
+
+    /// Device major/minor numbers for block/char device nodes - shown in
+    /// the size column like real ls.
+    #[cfg(unix)]
+    fn device_numbers(&self, node: &FileNode) -> Option<(u64, u64)> {
+        use crate::scanner::FileType;
+        if !matches!(node.file_type, FileType::BlockDevice | FileType::CharDevice) {
+            return None;
+        }
+        let rdev = fs::metadata(&node.path).ok()?.rdev();
+        // Linux dev_t encoding: 12-bit major, 20-bit minor, split oddly
+        let major = ((rdev >> 8) & 0xfff) | ((rdev >> 32) & !0xfffu64);
+        let minor = (rdev & 0xff) | ((rdev >> 12) & !0xffu64);
+        Some((major, minor))
+    }
+
+    #[cfg(not(unix))]
+    fn device_numbers(&self, _node: &FileNode) -> Option<(u64, u64)> {
+        None
+    }
+
     /// Get the appropriate emoji for a file node
     ///
     /// This adds visual flair to the output, making it easier to quickly
@@ -190,10 +290,27 @@ impl LsFormatter {
             format!("{} ", emoji)
         };
 
-        if self.use_colors {
+        let theme = crate::theme::Theme::global();
+        let ls_colors = crate::ls_colors::LsColors::global();
+        if self.use_colors && !theme.suppress_color() {
             if node.is_dir {
-                // Blue color for directories (ANSI color code 34)
-                format!("{}\x1b[34m{}\x1b[0m", emoji_field, filename)
+                match theme.directory_color() {
+                    Some(color) => {
+                        format!("{}{}", emoji_field, filename.color(color))
+                    }
+                    None => match ls_colors.paint(node, &filename) {
+                        // The user's dircolors directory style
+                        Some(painted) => format!("{}{}", emoji_field, painted),
+                        // Blue color for directories (ANSI color code 34)
+                        None => format!("{}\x1b[34m{}\x1b[0m", emoji_field, filename),
+                    },
+                }
+            } else if let Some(color) = theme.node_color(node) {
+                // ~/.st/theme.toml extension/age/category rules
+                format!("{}{}", emoji_field, filename.color(color))
+            } else if let Some(painted) = ls_colors.paint(node, &filename) {
+                // LS_COLORS extension and special-type rules
+                format!("{}{}", emoji_field, painted)
             } else if node.path.extension().and_then(|s| s.to_str()) == Some("rs") {
                 // Orange color for Rust files (Hue's favorite!)
                 format!("{}\x1b[38;5;208m{}\x1b[0m", emoji_field, filename)
@@ -281,7 +398,7 @@ impl Formatter for LsFormatter {
         &self,
         writer: &mut dyn Write,
         nodes: &[FileNode],
-        _stats: &TreeStats,
+        stats: &TreeStats,
         root_path: &Path,
     ) -> Result<()> {
         // Check if this appears to be a filtered result set (from --find or other filters)
@@ -335,22 +452,84 @@ impl Formatter for LsFormatter {
             return Ok(());
         }
 
+        // Custom column layout (--columns): render exactly the requested
+        // fields in the requested order, auto-sized to the widest cell
+        if let Some(columns) = &self.columns {
+            let rows: Vec<Vec<String>> = display_nodes
+                .iter()
+                .map(|node| {
+                    columns
+                        .iter()
+                        .map(|column| match column {
+                            Column::Name => self.format_filename(node),
+                            other => other.value(node),
+                        })
+                        .collect()
+                })
+                .collect();
+            let widths = column_widths(&rows);
+            for row in &rows {
+                let mut line = String::new();
+                for (i, cell) in row.iter().enumerate() {
+                    if i > 0 {
+                        line.push(' ');
+                    }
+                    let pad = widths[i].saturating_sub(visible_width(cell));
+                    if columns[i].right_aligned() {
+                        line.push_str(&" ".repeat(pad));
+                        line.push_str(cell);
+                    } else if i + 1 == row.len() {
+                        // Last column never needs trailing padding
+                        line.push_str(cell);
+                    } else {
+                        line.push_str(cell);
+                        line.push_str(&" ".repeat(pad));
+                    }
+                }
+                writeln!(writer, "{}", line)?;
+            }
+            return Ok(());
+        }
+
         // Note: Nodes are already sorted by the scanner based on user's --sort preference
         // We don't re-sort here to preserve the requested sort order
 
+        // One gather per format call - never a subprocess per file
+        let git = if self.git_status || self.git_blame_summary {
+            crate::git_status::GitAnnotations::gather(root_path, self.git_blame_summary)
+        } else {
+            None
+        };
+
         // Format each file/directory in ls -Alh style
         for node in display_nodes {
-            let permissions = self.format_permissions(node);
+            // ls-style '+' marker when the entry carries xattrs or ACLs
+            let permissions = match &node.xattrs {
+                Some(xattrs) if !xattrs.is_empty() => {
+                    format!("{}+", self.format_permissions(node))
+                }
+                _ => self.format_permissions(node),
+            };
             let link_count = self.get_link_count(node);
             let (owner, group) = self.get_owner_group(node);
-            let size = self.format_size(node.size);
+            // Device nodes show major,minor where the size would be, like
+            // real ls. Otherwise --du swaps logical size for allocated
+            // blocks; sparse files get an 's' suffix so the shortfall is
+            // visible at a glance
+            let size = if let Some((major, minor)) = self.device_numbers(node) {
+                format!("{}, {}", major, minor)
+            } else if node.is_sparse() {
+                format!("{}s", self.format_size(node.display_size()))
+            } else {
+                self.format_size(node.display_size())
+            };
 
             // Format the modification time
             let modified_time = match fs::metadata(&node.path) {
                 Ok(meta) => match meta.modified() {
                     Ok(time) => {
                         let datetime: DateTime<Local> = time.into();
-                        datetime.format("%b %d %H:%M").to_string()
+                        self.format_mtime(&datetime)
                     }
                     Err(_) => "??? ?? ??:??".to_string(),
                 },
@@ -390,11 +569,65 @@ impl Formatter for LsFormatter {
                 self.format_filename(node)
             };
 
+            // Git decoration: fixed-width status column before the
+            // permissions, blame summary after the name
+            let status_col = if self.git_status {
+                let code = git
+                    .as_ref()
+                    .and_then(|g| g.status_code(&node.path))
+                    .unwrap_or("");
+                format!("{:<2} ", code)
+            } else {
+                String::new()
+            };
+            let blame_col = if self.git_blame_summary {
+                git.as_ref()
+                    .and_then(|g| g.blame_summary(&node.path))
+                    .map(|summary| format!("  ({})", summary))
+                    .unwrap_or_default()
+            } else {
+                String::new()
+            };
+
+            // Attribute names after the line, values on demand via json mode
+            let xattr_col = node
+                .xattrs
+                .as_ref()
+                .filter(|xattrs| !xattrs.is_empty())
+                .map(|xattrs| {
+                    let names: Vec<&str> = xattrs.iter().map(|(name, _)| name.as_str()).collect();
+                    format!("  [{}]", names.join(" "))
+                })
+                .unwrap_or_default();
+
+            // Media metadata (--media-info): dimensions for images,
+            // duration + codec for audio/video, probed from headers only
+            let media_col = if self.media_info && !node.is_dir {
+                crate::media_info::probe(&node.path)
+                    .map(|info| format!("  [{}]", info.describe()))
+                    .unwrap_or_default()
+            } else {
+                String::new()
+            };
+
             // Write the ls -Alh formatted line
             writeln!(
                 writer,
-                "{:<10} {:>1} {:<4} {:<4} {:>6} {} {}",
-                permissions, link_count, owner, group, size, modified_time, filename
+                "{}{:<10} {:>1} {:<4} {:<4} {:>6} {} {}{}{}{}",
+                status_col, permissions, link_count, owner, group, size, modified_time, filename,
+                blame_col, media_col, xattr_col
+            )?;
+        }
+
+        // Apparent vs on-disk totals, only when --dedupe-hardlinks actually
+        // found files sharing inodes
+        if stats.hardlink_duplicates > 0 {
+            writeln!(
+                writer,
+                "total {} apparent, {} on disk ({} hard-linked duplicates)",
+                self.format_size(stats.total_size),
+                self.format_size(stats.disk_usage),
+                stats.hardlink_duplicates
             )?;
         }
 
@@ -425,6 +658,34 @@ mod tests {
         assert_eq!(formatter.format_size(1073741824), "1.0G");
     }
 
+    #[test]
+    fn test_size_styles() {
+        let si = LsFormatter::new(false, false).with_size_style(SizeStyle::Si);
+        assert_eq!(si.format_size(1000), "1.0k");
+        assert_eq!(si.format_size(1500000), "1.5M");
+
+        let blocks = LsFormatter::new(false, false).with_size_style(SizeStyle::Kibibytes);
+        assert_eq!(blocks.format_size(0), "0");
+        assert_eq!(blocks.format_size(1), "1"); // rounded up, like ls -k
+        assert_eq!(blocks.format_size(2048), "2");
+    }
+
+    #[test]
+    fn test_time_styles() {
+        use chrono::TimeZone;
+        let datetime = Local.with_ymd_and_hms(2025, 7, 9, 14, 56, 1).unwrap();
+
+        let default = LsFormatter::new(false, false);
+        assert_eq!(default.format_mtime(&datetime), "Jul 09 14:56");
+
+        let long_iso =
+            LsFormatter::new(false, false).with_time_style(Some("long-iso".to_string()));
+        assert_eq!(long_iso.format_mtime(&datetime), "2025-07-09 14:56");
+
+        let custom = LsFormatter::new(false, false).with_time_style(Some("+%Y".to_string()));
+        assert_eq!(custom.format_mtime(&datetime), "2025");
+    }
+
     #[test]
     fn test_emoji_selection() {
         let formatter = LsFormatter::new(true, false);
@@ -453,6 +714,9 @@ mod tests {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         };
         assert_eq!(formatter.get_emoji(&empty_dir), "📂");
 
@@ -480,6 +744,9 @@ mod tests {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         };
         assert_eq!(formatter.get_emoji(&empty_file), "🪹");
     }
@@ -513,6 +780,9 @@ mod tests {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         };
 
         let perms = formatter.format_permissions(&test_node);
@@ -22,12 +22,67 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::path::Path;
 
+/// Section names a report template may select, in their default order.
+const TEMPLATE_SECTIONS: &[&str] = &[
+    "mermaid",
+    "file-types",
+    "pie-charts",
+    "loc",
+    "largest",
+    "recent",
+];
+
+/// Branded report configuration (`--report-template report.toml`):
+///
+/// ```toml
+/// title = "Weekly Asset Report"
+/// org = "Aye & Hue Industries"
+/// logo = "https://example.com/logo.png"
+/// footer = "Confidential - internal use only"
+/// sections = ["file-types", "largest"]
+/// ```
+///
+/// Every field is optional; `sections` (when present) replaces the
+/// all-or-nothing include flags with an explicit ordered selection.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct ReportTemplate {
+    pub title: Option<String>,
+    pub org: Option<String>,
+    pub logo: Option<String>,
+    pub footer: Option<String>,
+    #[serde(default)]
+    pub sections: Vec<String>,
+}
+
+impl ReportTemplate {
+    /// Load and validate a template from a TOML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Could not read report template {}: {}", path.display(), e))?;
+        let template: ReportTemplate = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid report template {}: {}", path.display(), e))?;
+        for section in &template.sections {
+            if !TEMPLATE_SECTIONS.contains(&section.as_str()) {
+                anyhow::bail!(
+                    "Unknown report section '{}' (available: {})",
+                    section,
+                    TEMPLATE_SECTIONS.join(", ")
+                );
+            }
+        }
+        Ok(template)
+    }
+}
+
 pub struct MarkdownFormatter {
     no_emoji: bool,
     include_mermaid: bool,
     include_tables: bool,
     include_pie_charts: bool,
+    include_loc: bool,
     max_pie_slices: usize,
+    /// Branding and section selection (--report-template)
+    template: Option<ReportTemplate>,
 }
 
 impl MarkdownFormatter {
@@ -43,10 +98,24 @@ impl MarkdownFormatter {
             include_mermaid,
             include_tables,
             include_pie_charts,
+            include_loc: false,
             max_pie_slices: 10, // Limit pie chart slices for readability
+            template: None,
         }
     }
 
+    /// Add a tokei-style lines-of-code table (--loc flag).
+    pub fn with_loc(mut self, loc: bool) -> Self {
+        self.include_loc = loc;
+        self
+    }
+
+    /// Apply a report template for branding and section selection.
+    pub fn with_template(mut self, template: Option<ReportTemplate>) -> Self {
+        self.template = template;
+        self
+    }
+
     fn escape_mermaid(text: &str) -> String {
         text.replace('|', "&#124;")
             .replace('<', "&lt;")
@@ -109,9 +178,21 @@ impl MarkdownFormatter {
             .unwrap_or(root_path.as_os_str())
             .to_string_lossy();
 
-        writeln!(writer, "# 📊 Directory Analysis Report")?;
+        let template = self.template.as_ref();
+        if let Some(logo) = template.and_then(|t| t.logo.as_deref()) {
+            writeln!(writer, "![logo]({})", logo)?;
+            writeln!(writer)?;
+        }
+        match template.and_then(|t| t.title.as_deref()) {
+            Some(title) => writeln!(writer, "# {}", title)?,
+            None if self.no_emoji => writeln!(writer, "# Directory Analysis Report")?,
+            None => writeln!(writer, "# 📊 Directory Analysis Report")?,
+        }
         writeln!(writer)?;
-        writeln!(writer, "**Generated by Smart Tree** | {}", timestamp)?;
+        match template.and_then(|t| t.org.as_deref()) {
+            Some(org) => writeln!(writer, "**{}** | {}", org, timestamp)?,
+            None => writeln!(writer, "**Generated by Smart Tree** | {}", timestamp)?,
+        }
         writeln!(writer)?;
         writeln!(writer, "## 📁 Overview")?;
         writeln!(writer)?;
@@ -484,6 +565,33 @@ impl MarkdownFormatter {
         Ok(())
     }
 
+    fn write_loc_table(&self, writer: &mut dyn Write, nodes: &[FileNode]) -> Result<()> {
+        let totals = crate::loc_counter::tally_by_language(nodes);
+        if totals.is_empty() {
+            return Ok(());
+        }
+
+        if self.no_emoji {
+            writeln!(writer, "## Lines of Code")?;
+        } else {
+            writeln!(writer, "## 📏 Lines of Code")?;
+        }
+        writeln!(writer)?;
+        writeln!(writer, "| Language | Files | Code | Comments | Blanks |")?;
+        writeln!(writer, "|----------|-------|------|----------|--------|")?;
+
+        for lang in &totals {
+            writeln!(
+                writer,
+                "| {} | {} | {} | {} | {} |",
+                lang.language, lang.files, lang.lines.code, lang.lines.comments, lang.lines.blanks
+            )?;
+        }
+
+        writeln!(writer)?;
+        Ok(())
+    }
+
     fn write_size_distribution_pie(
         &self,
         writer: &mut dyn Write,
@@ -613,6 +721,14 @@ impl MarkdownFormatter {
     }
 
     fn write_summary(&self, writer: &mut dyn Write, _stats: &TreeStats) -> Result<()> {
+        // A template footer replaces the stock sign-off entirely
+        if let Some(footer) = self.template.as_ref().and_then(|t| t.footer.as_deref()) {
+            writeln!(writer, "---")?;
+            writeln!(writer)?;
+            writeln!(writer, "{}", footer)?;
+            return Ok(());
+        }
+
         writeln!(writer, "## 📈 Summary")?;
         writeln!(writer)?;
 
@@ -647,6 +763,46 @@ impl Formatter for MarkdownFormatter {
         // Header with overview
         self.write_header(writer, root_path, stats)?;
 
+        // Template-selected sections, in the template's order
+        if let Some(sections) = self
+            .template
+            .as_ref()
+            .filter(|t| !t.sections.is_empty())
+            .map(|t| &t.sections)
+        {
+            for section in sections {
+                match section.as_str() {
+                    "mermaid" => self.write_mermaid_diagram(writer, nodes, root_path)?,
+                    "file-types" => {
+                        if !stats.file_types.is_empty() {
+                            self.write_file_type_table(writer, stats)?;
+                        }
+                    }
+                    "pie-charts" => {
+                        if !stats.file_types.is_empty() {
+                            self.write_file_type_pie(writer, stats)?;
+                        }
+                        self.write_size_distribution_pie(writer, stats)?;
+                    }
+                    "loc" => self.write_loc_table(writer, nodes)?,
+                    "largest" => {
+                        if !stats.largest_files.is_empty() {
+                            self.write_largest_files_table(writer, stats)?;
+                        }
+                    }
+                    "recent" => {
+                        if !stats.newest_files.is_empty() {
+                            self.write_recent_files_table(writer, stats)?;
+                        }
+                    }
+                    // ReportTemplate::load validated the names already
+                    _ => {}
+                }
+            }
+            self.write_summary(writer, stats)?;
+            return Ok(());
+        }
+
         // Mermaid directory diagram
         if self.include_mermaid {
             self.write_mermaid_diagram(writer, nodes, root_path)?;
@@ -666,6 +822,11 @@ impl Formatter for MarkdownFormatter {
             self.write_size_distribution_pie(writer, stats)?;
         }
 
+        // Lines of code (opt-in - reads file contents)
+        if self.include_loc {
+            self.write_loc_table(writer, nodes)?;
+        }
+
         // Largest files
         if self.include_tables && !stats.largest_files.is_empty() {
             self.write_largest_files_table(writer, stats)?;
@@ -724,6 +885,9 @@ mod tests {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         }];
 
         let mut stats = TreeStats::default();
@@ -737,4 +901,47 @@ mod tests {
         assert!(output_str.contains("# 📊 Directory Analysis Report"));
         assert!(output_str.contains("mermaid"));
     }
+
+    #[test]
+    fn test_report_template_branding_and_section_selection() {
+        let template: ReportTemplate = toml::from_str(
+            "title = \"Weekly Asset Report\"\n\
+             org = \"Aye & Hue Industries\"\n\
+             footer = \"Confidential\"\n\
+             sections = [\"file-types\"]\n",
+        )
+        .unwrap();
+        let formatter = MarkdownFormatter::new(PathDisplayMode::Off, false, true, true, true)
+            .with_template(Some(template));
+
+        let stats = TreeStats {
+            total_files: 1,
+            file_types: [("rs".to_string(), 1u64)].into_iter().collect(),
+            ..TreeStats::default()
+        };
+
+        let mut output = Vec::new();
+        formatter
+            .format(&mut output, &[], &stats, &PathBuf::from("."))
+            .unwrap();
+        let output_str = String::from_utf8(output).unwrap();
+
+        assert!(output_str.contains("# Weekly Asset Report"));
+        assert!(output_str.contains("**Aye & Hue Industries**"));
+        assert!(output_str.contains("Confidential"));
+        assert!(output_str.contains("File Types Breakdown"));
+        // Only the selected section renders - no diagram, no stock sign-off
+        assert!(!output_str.contains("```mermaid"));
+        assert!(!output_str.contains("brought to you by"));
+    }
+
+    #[test]
+    fn test_report_template_rejects_unknown_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.toml");
+        std::fs::write(&path, "sections = [\"nonsense\"]\n").unwrap();
+
+        let err = ReportTemplate::load(&path).unwrap_err();
+        assert!(err.to_string().contains("Unknown report section"));
+    }
 }
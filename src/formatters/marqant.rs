@@ -14,10 +14,16 @@ use super::{Formatter, PathDisplayMode};
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
 use marqant::Marqant as MarqantCore;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::io::Write;
 use std::path::Path;
 
+/// Integrity line embedded right after the MARQANT header:
+/// `::checksum:sha256:<hex>::` - the hash of the ORIGINAL markdown, so a
+/// corrupted stream can't silently decompress to garbage.
+const CHECKSUM_PREFIX: &str = "::checksum:sha256:";
+
 /// Marqant formatter - Quantum compression for markdown files
 pub struct MarqantFormatter {
     no_emoji: bool,
@@ -30,12 +36,67 @@ impl MarqantFormatter {
 
     /// Compress markdown content into marqant format
     pub fn compress_markdown(content: &str) -> Result<String> {
-        MarqantCore::compress_markdown(content)
+        Ok(Self::embed_checksum(
+            MarqantCore::compress_markdown(content)?,
+            content,
+        ))
     }
 
     /// Compress markdown content with optional flags
     pub fn compress_markdown_with_flags(content: &str, flags: Option<&str>) -> Result<String> {
-        MarqantCore::compress_markdown_with_flags(content, flags)
+        Ok(Self::embed_checksum(
+            MarqantCore::compress_markdown_with_flags(content, flags)?,
+            content,
+        ))
+    }
+
+    /// SHA-256 of the source markdown, hex-encoded. Hashes the
+    /// trailing-whitespace-trimmed form, because that's what survives the
+    /// .mq round trip - the core stores lines `\n`-joined, so a final
+    /// newline is not preserved and must not fail verification.
+    pub fn content_checksum(content: &str) -> String {
+        hex::encode(Sha256::digest(content.trim_end().as_bytes()))
+    }
+
+    /// Insert the checksum line directly after the MARQANT header line
+    fn embed_checksum(compressed: String, source: &str) -> String {
+        match compressed.split_once('\n') {
+            Some((header, rest)) => format!(
+                "{}\n{}{}::\n{}",
+                header,
+                CHECKSUM_PREFIX,
+                Self::content_checksum(source),
+                rest
+            ),
+            // A header-only stream shouldn't happen, but don't mangle it
+            None => compressed,
+        }
+    }
+
+    /// Split a compressed stream into its embedded checksum (if any) and
+    /// the checksum-free stream the core decompressor understands.
+    ///
+    /// Pre-checksum .mq files simply come back as `(None, stream)`.
+    pub fn extract_checksum(compressed: &str) -> (Option<String>, String) {
+        match compressed.split_once('\n') {
+            Some((header, rest)) => {
+                if let Some(line) = rest.lines().next() {
+                    if let Some(checksum) = line
+                        .strip_prefix(CHECKSUM_PREFIX)
+                        .and_then(|s| s.strip_suffix("::"))
+                    {
+                        let remainder = &rest[line.len()..];
+                        let remainder = remainder.strip_prefix('\n').unwrap_or(remainder);
+                        return (
+                            Some(checksum.to_string()),
+                            format!("{}\n{}", header, remainder),
+                        );
+                    }
+                }
+                (None, compressed.to_string())
+            }
+            None => (None, compressed.to_string()),
+        }
     }
 
     /// Add semantic section tags to markdown content
@@ -73,9 +134,31 @@ impl MarqantFormatter {
         MarqantCore::tokenize_content(content)
     }
 
-    /// Decompress marqant content back to markdown
+    /// Decompress marqant content back to markdown, verifying the embedded
+    /// checksum when one is present
     pub fn decompress_marqant(compressed: &str) -> Result<String> {
-        MarqantCore::decompress_marqant(compressed)
+        Self::decompress_marqant_with_verification(compressed, true)
+    }
+
+    /// Decompress with explicit verification control - `verify: false` is
+    /// the `--no-verify` escape hatch for salvaging a damaged file
+    pub fn decompress_marqant_with_verification(compressed: &str, verify: bool) -> Result<String> {
+        let (expected, stream) = Self::extract_checksum(compressed);
+        let decompressed = MarqantCore::decompress_marqant(&stream)?;
+        if verify {
+            if let Some(expected) = expected {
+                let actual = Self::content_checksum(&decompressed);
+                if actual != expected {
+                    anyhow::bail!(
+                        "Checksum mismatch: file claims sha256:{} but content is sha256:{} - \
+                         the .mq stream is corrupted (use --no-verify to decompress anyway)",
+                        expected,
+                        actual
+                    );
+                }
+            }
+        }
+        Ok(decompressed)
     }
 }
 
@@ -204,6 +287,56 @@ fn main() {
         assert!(compressed.len() > 20, "Should have header and content");
     }
 
+    #[test]
+    fn test_checksum_embedded_and_verified() {
+        let markdown = "# Title\n\nSome content worth protecting.\n";
+        let compressed = MarqantFormatter::compress_markdown(markdown).unwrap();
+
+        let (checksum, stream) = MarqantFormatter::extract_checksum(&compressed);
+        assert_eq!(
+            checksum.as_deref(),
+            Some(MarqantFormatter::content_checksum(markdown).as_str())
+        );
+        // The stripped stream is exactly what the core decompressor expects
+        assert!(stream.starts_with("MARQANT"));
+        assert!(!stream.contains(CHECKSUM_PREFIX));
+
+        let decompressed = MarqantFormatter::decompress_marqant(&compressed).unwrap();
+        assert_eq!(decompressed.trim(), markdown.trim());
+    }
+
+    #[test]
+    fn test_checksum_mismatch_rejected_unless_no_verify() {
+        let markdown = "# Title\n\nSome content worth protecting.\n";
+        let compressed = MarqantFormatter::compress_markdown(markdown).unwrap();
+
+        // Forge the checksum line - simulates a corrupted content section
+        let (checksum, _) = MarqantFormatter::extract_checksum(&compressed);
+        let forged = compressed.replace(&checksum.unwrap(), &"0".repeat(64));
+
+        let err = MarqantFormatter::decompress_marqant(&forged).unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+
+        // --no-verify still salvages the content
+        let salvaged =
+            MarqantFormatter::decompress_marqant_with_verification(&forged, false).unwrap();
+        assert_eq!(salvaged.trim(), markdown.trim());
+    }
+
+    #[test]
+    fn test_legacy_files_without_checksum_still_decompress() {
+        let markdown = "# Legacy\n\nPre-checksum file.\n";
+        // A stream straight from the core, as older writers produced
+        let legacy = MarqantCore::compress_markdown(markdown).unwrap();
+
+        let (checksum, stream) = MarqantFormatter::extract_checksum(&legacy);
+        assert!(checksum.is_none());
+        assert_eq!(stream, legacy);
+
+        let decompressed = MarqantFormatter::decompress_marqant(&legacy).unwrap();
+        assert_eq!(decompressed.trim(), markdown.trim());
+    }
+
     #[test]
     fn test_token_assignment() {
         // Test with markdown content that definitely triggers tokenization
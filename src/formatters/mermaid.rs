@@ -20,6 +20,11 @@ pub struct MermaidFormatter {
     no_emoji: bool,
     path_mode: PathDisplayMode,
     max_label_length: usize,
+    /// Base URL for click directives (--mermaid-links) - nodes link to
+    /// `<base>/<relative path>`, so diagrams in docs jump to the source
+    link_base: Option<String>,
+    /// Group nodes into subgraphs per detected package (--mermaid-subgraphs)
+    package_subgraphs: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -37,9 +42,26 @@ impl MermaidFormatter {
             no_emoji,
             path_mode,
             max_label_length: 50, // Prevent overly long labels
+            link_base: None,
+            package_subgraphs: false,
         }
     }
 
+    /// Emit click directives linking each node to `<base>/<relative path>`.
+    pub fn with_links(mut self, base: Option<String>) -> Self {
+        // Normalize away a trailing slash so joins stay clean
+        self.link_base = base.map(|b| b.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Group flowchart nodes into subgraphs by detected package
+    /// (any directory holding a Cargo.toml, package.json, pyproject.toml,
+    /// or go.mod).
+    pub fn with_package_subgraphs(mut self, enabled: bool) -> Self {
+        self.package_subgraphs = enabled;
+        self
+    }
+
     fn sanitize_node_id(path: &std::path::Path) -> String {
         // Create safe node IDs for Mermaid
         let path_str = path.to_string_lossy();
@@ -118,6 +140,44 @@ impl MermaidFormatter {
         label
     }
 
+    /// One node declaration line: id, shape, and label.
+    fn node_declaration(&self, node: &FileNode) -> String {
+        let node_id = Self::sanitize_node_id(&node.path);
+        let label = self.format_label(node);
+
+        // Determine node shape based on type
+        let (open_shape, close_shape) = if node.is_dir {
+            ("[\"", "\"]") // Rectangle for directories - use quotes to handle emojis
+        } else {
+            match node.path.extension().and_then(|e| e.to_str()) {
+                Some("md") | Some("txt") | Some("rst") => ("([\"", "\"])"), // Stadium for docs
+                Some("rs") | Some("py") | Some("js") | Some("ts") => ("{{\"", "\"}}"), // Hexagon for code
+                Some("toml") | Some("yaml") | Some("yml") | Some("json") => ("[\"", "\"]"), // Rectangle for config (simpler than cylinder)
+                _ => ("[\"", "\"]"), // Rectangle for other files (safer than circles)
+            }
+        };
+
+        format!("{}{}{}{}", node_id, open_shape, label, close_shape)
+    }
+
+    /// Directories that hold a package manifest, sorted for stable output.
+    fn detect_package_roots(nodes: &[FileNode]) -> Vec<std::path::PathBuf> {
+        let mut roots: Vec<std::path::PathBuf> = nodes
+            .iter()
+            .filter(|n| {
+                !n.is_dir
+                    && matches!(
+                        n.path.file_name().and_then(|f| f.to_str()),
+                        Some("Cargo.toml" | "package.json" | "pyproject.toml" | "go.mod")
+                    )
+            })
+            .filter_map(|n| n.path.parent().map(|p| p.to_path_buf()))
+            .collect();
+        roots.sort();
+        roots.dedup();
+        roots
+    }
+
     fn write_flowchart(
         &self,
         writer: &mut dyn Write,
@@ -161,38 +221,79 @@ impl MermaidFormatter {
             root_id, root_emoji, escaped_root_name
         )?;
 
-        // Write all nodes and connections
-        for node in nodes {
-            let node_id = Self::sanitize_node_id(&node.path);
-            let label = self.format_label(node);
-
-            // Determine node shape based on type
-            let (open_shape, close_shape) = if node.is_dir {
-                ("[\"", "\"]") // Rectangle for directories - use quotes to handle emojis
-            } else {
-                match node.path.extension().and_then(|e| e.to_str()) {
-                    Some("md") | Some("txt") | Some("rst") => ("([\"", "\"])"), // Stadium for docs
-                    Some("rs") | Some("py") | Some("js") | Some("ts") => ("{{\"", "\"}}"), // Hexagon for code
-                    Some("toml") | Some("yaml") | Some("yml") | Some("json") => ("[\"", "\"]"), // Rectangle for config (simpler than cylinder)
-                    _ => ("[\"", "\"]"), // Rectangle for other files (safer than circles)
-                }
-            };
+        // Which package (if any) owns each node - deepest manifest dir wins
+        let package_roots = if self.package_subgraphs {
+            Self::detect_package_roots(nodes)
+        } else {
+            Vec::new()
+        };
+        let package_for = |node: &FileNode| -> Option<&std::path::PathBuf> {
+            package_roots
+                .iter()
+                .filter(|root| node.path.starts_with(root) && node.path != **root)
+                .max_by_key(|root| root.components().count())
+        };
 
+        // Node declarations - package members inside their subgraph, the
+        // rest at top level
+        for node in nodes {
+            if package_for(node).is_none() {
+                writeln!(writer, "    {}", self.node_declaration(node))?;
+            }
+        }
+        for package in &package_roots {
+            let package_name = package
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("package");
+            let package_emoji = if !self.no_emoji { "📦 " } else { "" };
             writeln!(
                 writer,
-                "    {}{}{}{}",
-                node_id, open_shape, label, close_shape
+                "    subgraph {}_pkg[\"{}{}\"]",
+                Self::sanitize_node_id(package),
+                package_emoji,
+                Self::escape_label(package_name)
             )?;
+            for node in nodes {
+                if package_for(node) == Some(package) {
+                    writeln!(writer, "        {}", self.node_declaration(node))?;
+                }
+            }
+            writeln!(writer, "    end")?;
+        }
 
-            // Connect to parent
+        // Connections (edges may cross subgraph boundaries freely)
+        for node in nodes {
             if let Some(parent_path) = node.path.parent() {
                 let parent_id = if parent_path == root_path {
                     root_id.clone()
                 } else {
                     Self::sanitize_node_id(parent_path)
                 };
+                writeln!(
+                    writer,
+                    "    {} --> {}",
+                    parent_id,
+                    Self::sanitize_node_id(&node.path)
+                )?;
+            }
+        }
 
-                writeln!(writer, "    {} --> {}", parent_id, node_id)?;
+        // Click directives - the diagram becomes navigation, not just a map
+        if let Some(ref base) = self.link_base {
+            writeln!(writer)?;
+            writeln!(writer, "    %% Links")?;
+            for node in nodes {
+                let rel = node.path.strip_prefix(root_path).unwrap_or(&node.path);
+                let rel = rel.to_string_lossy().replace('\\', "/");
+                writeln!(
+                    writer,
+                    "    click {} \"{}/{}\" \"{}\"",
+                    Self::sanitize_node_id(&node.path),
+                    base,
+                    rel,
+                    rel
+                )?;
             }
         }
 
@@ -474,6 +575,69 @@ mod tests {
         assert!(!id.contains('-'));
     }
 
+    fn node(path: &str, is_dir: bool) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size: if is_dir { 0 } else { 100 },
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_ignored: false,
+            search_matches: None,
+            is_hidden: false,
+            permission_denied: false,
+            depth: PathBuf::from(path).components().count(),
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_links_and_package_subgraphs() {
+        let formatter = MermaidFormatter::new(MermaidStyle::Flowchart, true, PathDisplayMode::Off)
+            .with_links(Some("https://example.com/blob/main/".to_string()))
+            .with_package_subgraphs(true);
+
+        let nodes = vec![
+            node("app", true),
+            node("app/Cargo.toml", false),
+            node("app/main.rs", false),
+            node("notes.md", false),
+        ];
+        let stats = TreeStats::default();
+
+        let mut output = Vec::new();
+        formatter
+            .format(&mut output, &nodes, &stats, &PathBuf::from("."))
+            .unwrap();
+        let output_str = String::from_utf8(output).unwrap();
+
+        // app/ holds a Cargo.toml, so its members live in a subgraph
+        assert!(output_str.contains("subgraph app_pkg"));
+        assert!(output_str.contains("    end"));
+        // Trailing slash on the base folds away cleanly
+        assert!(output_str.contains("click app_main_rs \"https://example.com/blob/main/app/main.rs\""));
+        // Files outside any package stay at top level but still get links
+        assert!(output_str.contains("click notes_md \"https://example.com/blob/main/notes.md\""));
+    }
+
     #[test]
     fn test_mermaid_flowchart() {
         let formatter = MermaidFormatter::new(MermaidStyle::Flowchart, false, PathDisplayMode::Off);
@@ -502,6 +666,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
             FileNode {
                 path: PathBuf::from("src/main.rs"),
@@ -526,6 +693,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
         ];
 
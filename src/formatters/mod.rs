@@ -1,39 +1,53 @@
 pub mod ai;
 pub mod ai_json;
+pub mod ai_table; // Columnar schema-once output - ~60% fewer tokens than JSON listings
+pub mod churn; // Git-history hotspots - find the risky files before a refactor
 pub mod classic;
+pub mod columns; // shared --columns layout machinery for ls/classic
 pub mod context;
 pub mod csv;
+pub mod deps; // Cross-project dependency graph from manifests - mermaid or DOT
 pub mod digest;
+pub mod dot; // Graphviz DOT output - pipe straight into `dot -Tsvg`!
 pub mod emotional_new; // The FUN emotional formatter with personality!
+pub mod fd_compat; // fd-compatible path listing - `--compat fd`
 pub mod function_markdown;
 pub mod hex;
 pub mod hextree; // HexTree - quantum meets readable tree structure
+pub mod html; // Self-contained interactive HTML report - share audits with anyone!
 pub mod json;
+pub mod licenses; // License mix + SBOM inventory - compliance straight from the scan
 pub mod ls;
 pub mod markdown;
 pub mod marqant;
 pub mod mermaid;
+pub mod perms; // Permission audit - world-writable, setuid, odd owners, prioritized
 pub mod projects; // Projects discovery mode - find all your forgotten 3am coding gems!
 pub mod quantum;
 pub mod quantum_semantic;
 pub mod relations;
 pub mod relations_formatter;
+pub mod secrets; // Credential sweep - regex+entropy rules, redacted by default
 pub mod semantic;
 pub mod smart; // Smart formatter - surface what matters, not everything!
 pub mod sse;
 pub mod stats;
 pub mod summary;
 pub mod summary_ai;
+pub mod tree_compat; // GNU tree(1) byte-compatible rendering - `--compat tree`
 pub mod tsv;
 pub mod waste;
 
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
+use std::collections::HashMap;
 use std::io::Write;
+use std::sync::{OnceLock, RwLock};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 pub enum PathDisplayMode {
     Off,
+    #[default]
     Relative,
     Full,
 }
@@ -68,3 +82,363 @@ pub trait StreamingFormatter {
         root_path: &std::path::Path,
     ) -> Result<()>;
 }
+
+/// Everything a formatter factory might need to configure itself from the
+/// CLI or daemon request - one struct so registering a new formatter never
+/// means threading another parameter through the dispatch path
+#[derive(Debug, Clone, Default)]
+pub struct FormatterOptions {
+    pub no_emoji: bool,
+    pub use_color: bool,
+    pub path_mode: PathDisplayMode,
+    pub compact: bool,
+    pub show_ignored: bool,
+    pub show_filesystems: bool,
+    /// Hash algorithm name for stats mode (e.g. "blake3")
+    pub hash: Option<String>,
+    /// Decorate entries with git status markers (classic/ls)
+    pub git_status: bool,
+    /// Append last commit age and author per file (classic/ls)
+    pub git_blame_summary: bool,
+    /// Show recursive size totals next to directories (classic)
+    pub dir_sizes: bool,
+    /// Comma-separated sort spec, so tree formatters order children the
+    /// same way the scanner ordered the flat list (classic)
+    pub sort: Option<String>,
+    /// List directories before files at every level (classic)
+    pub dirs_first: bool,
+    /// List files before directories at every level (classic)
+    pub files_first: bool,
+    /// Recursive per-directory extension statistics (stats/json)
+    pub per_dir: bool,
+    /// SBOM document format for licenses mode ("cyclonedx" or "spdx")
+    pub sbom: Option<String>,
+    /// Graph syntax for deps mode ("mermaid" or "dot")
+    pub graph: Option<String>,
+    /// Per-language line counts in stats/markdown modes (--loc flag)
+    pub loc: bool,
+    /// Base URL for mermaid click directives (--mermaid-links)
+    pub mermaid_links: Option<String>,
+    /// Group mermaid nodes into per-package subgraphs (--mermaid-subgraphs)
+    pub mermaid_subgraphs: bool,
+    /// TOML report template path for markdown mode (--report-template)
+    pub report_template: Option<String>,
+    /// Probe media files for dimensions/duration in ls/json/stats (--media-info)
+    pub media_info: bool,
+    /// Single-character CSV field delimiter (--csv-delimiter)
+    pub csv_delimiter: Option<String>,
+    /// Prefix CSV output with a UTF-8 BOM for Excel (--csv-bom)
+    pub csv_bom: bool,
+    /// Comma-separated column spec for ls/classic modes (--columns)
+    pub columns: Option<String>,
+    /// Sizes in powers of 1000 in ls mode (--si)
+    pub si: bool,
+    /// Sizes as 1K-block counts in ls mode (-k)
+    pub kibibytes: bool,
+    /// GNU ls --time-style spelling for ls mode timestamps
+    pub time_style: Option<String>,
+    /// Middle-truncate long names in classic mode (--max-name-width)
+    pub max_name_width: Option<usize>,
+    /// Subset/order of hex-mode fields (--hex-fields)
+    pub hex_fields: Option<String>,
+    /// Cold→hot gradient on classic-mode names (--heatmap size|age)
+    pub heatmap: Option<String>,
+    /// Per-directory Merkle digests in digest mode (--merkle)
+    pub merkle: bool,
+    /// Length of the stats-mode top lists (--stats-top)
+    pub stats_top: Option<usize>,
+    /// Regex narrowing stats mode to matching relative paths (--stats-filter)
+    pub stats_filter: Option<String>,
+}
+
+/// Factory producing a configured formatter from the request options
+pub type FormatterFactory =
+    Box<dyn Fn(&FormatterOptions) -> Result<Box<dyn Formatter>> + Send + Sync>;
+
+/// Name → formatter registry - the extension point for output modes
+///
+/// Built-in modes are pre-registered; external crates (or a scripting
+/// layer) embedding Smart Tree can add their own before handing control to
+/// the CLI, and `--mode <custom>` resolves here instead of a hard-coded
+/// enum match:
+///
+/// ```ignore
+/// st::formatters::FormatterRegistry::global()
+///     .write()
+///     .unwrap()
+///     .register("mycorp", |opts| Ok(Box::new(MyCorpFormatter::new(opts.no_emoji))));
+/// ```
+pub struct FormatterRegistry {
+    factories: HashMap<String, FormatterFactory>,
+}
+
+impl FormatterRegistry {
+    /// Registry with only the built-in formatters
+    pub fn with_builtins() -> Self {
+        let mut registry = FormatterRegistry {
+            factories: HashMap::new(),
+        };
+
+        registry.register("classic", |o| {
+            let columns = o
+                .columns
+                .as_deref()
+                .map(columns::parse_columns)
+                .transpose()?;
+            let heatmap = o
+                .heatmap
+                .as_deref()
+                .map(classic::Heatmap::parse)
+                .transpose()?;
+            Ok(Box::new(
+                classic::ClassicFormatter::new(o.no_emoji, o.use_color, o.path_mode)
+                    .with_git(o.git_status, o.git_blame_summary)
+                    .with_dir_sizes(o.dir_sizes)
+                    .with_sort(o.sort.clone())
+                    .with_dir_order(o.dirs_first, o.files_first)
+                    .with_columns(columns)
+                    .with_max_name_width(o.max_name_width)
+                    .with_heatmap(heatmap),
+            ))
+        });
+        registry.register("hex", |o| {
+            let fields = o
+                .hex_fields
+                .as_deref()
+                .map(hex::HexField::parse_spec)
+                .transpose()?;
+            Ok(Box::new(
+                hex::HexFormatter::new(
+                    o.use_color,
+                    o.no_emoji,
+                    o.show_ignored,
+                    o.path_mode,
+                    o.show_filesystems,
+                )
+                .with_fields(fields),
+            ))
+        });
+        registry.register("json", |o| {
+            Ok(Box::new(
+                json::JsonFormatter::new(o.compact)
+                    .with_ext_stats(o.per_dir)
+                    .with_media_info(o.media_info),
+            ))
+        });
+        registry.register("ls", |o| {
+            let size_style = if o.kibibytes {
+                ls::SizeStyle::Kibibytes
+            } else if o.si {
+                ls::SizeStyle::Si
+            } else {
+                ls::SizeStyle::Human
+            };
+            Ok(Box::new(
+                ls::LsFormatter::new(!o.no_emoji, o.use_color)
+                    .with_git(o.git_status, o.git_blame_summary)
+                    .with_media_info(o.media_info)
+                    .with_columns(o.columns.as_deref().map(columns::parse_columns).transpose()?)
+                    .with_size_style(size_style)
+                    .with_time_style(o.time_style.clone()),
+            ))
+        });
+        registry.register("ai", |o| {
+            Ok(Box::new(ai::AiFormatter::new(o.no_emoji, o.path_mode)))
+        });
+        // Both spellings: CLI folds "ai-table" to "aitable", MCP sends it raw
+        registry.register("ai-table", |_| Ok(Box::new(ai_table::AiTableFormatter::new())));
+        registry.register("aitable", |_| Ok(Box::new(ai_table::AiTableFormatter::new())));
+        registry.register("stats", |o| {
+            let stats_filter = o
+                .stats_filter
+                .as_deref()
+                .map(regex::Regex::new)
+                .transpose()?;
+            let mut formatter = stats::StatsFormatter::new()
+                .with_per_dir(o.per_dir)
+                .with_loc(o.loc)
+                .with_media_info(o.media_info)
+                .with_top(o.stats_top)
+                .with_filter(stats_filter);
+            if let Some(ref algo) = o.hash {
+                formatter = formatter.with_hashing(algo.parse()?);
+            }
+            Ok(Box::new(formatter))
+        });
+        registry.register("csv", |o| {
+            let mut formatter = csv::CsvFormatter::new().with_bom(o.csv_bom);
+            if let Some(delim) = &o.csv_delimiter {
+                // "\t" spelled out on the command line means a real tab
+                let delim = if delim == "\\t" { "\t" } else { delim.as_str() };
+                match delim.as_bytes() {
+                    [byte] => formatter = formatter.with_delimiter(*byte),
+                    _ => anyhow::bail!(
+                        "--csv-delimiter must be a single ASCII character (got {:?})",
+                        delim
+                    ),
+                }
+            }
+            Ok(Box::new(formatter))
+        });
+        registry.register("fd", |o| {
+            Ok(Box::new(fd_compat::FdCompatFormatter::new(o.use_color)))
+        });
+        registry.register("tree", |o| {
+            Ok(Box::new(tree_compat::TreeCompatFormatter::new(o.dirs_first)))
+        });
+        registry.register("tsv", |_| Ok(Box::new(tsv::TsvFormatter::new())));
+        registry.register("digest", |o| {
+            Ok(Box::new(digest::DigestFormatter::new().with_merkle(o.merkle)))
+        });
+        registry.register("dot", |o| Ok(Box::new(dot::DotFormatter::new(o.no_emoji))));
+        registry.register("html", |o| Ok(Box::new(html::HtmlFormatter::new(o.no_emoji))));
+        registry.register("quantum", |_| Ok(Box::new(quantum::QuantumFormatter::new())));
+        registry.register("semantic", |o| {
+            Ok(Box::new(semantic::SemanticFormatter::new(
+                o.path_mode,
+                o.no_emoji,
+            )))
+        });
+        registry.register("projects", |_| {
+            Ok(Box::new(projects::ProjectsFormatter::new()))
+        });
+        registry.register("mermaid", |o| {
+            Ok(Box::new(
+                mermaid::MermaidFormatter::new(
+                    mermaid::MermaidStyle::Flowchart,
+                    o.no_emoji,
+                    o.path_mode,
+                )
+                .with_links(o.mermaid_links.clone())
+                .with_package_subgraphs(o.mermaid_subgraphs),
+            ))
+        });
+        registry.register("markdown", |o| {
+            let template = o
+                .report_template
+                .as_deref()
+                .map(|path| markdown::ReportTemplate::load(std::path::Path::new(path)))
+                .transpose()?;
+            Ok(Box::new(
+                markdown::MarkdownFormatter::new(o.path_mode, o.no_emoji, true, true, true)
+                    .with_loc(o.loc)
+                    .with_template(template),
+            ))
+        });
+        registry.register("waste", |_| Ok(Box::new(waste::WasteFormatter::new())));
+        registry.register("perms", |_| Ok(Box::new(perms::PermsFormatter::new())));
+        registry.register("secrets", |_| {
+            Ok(Box::new(secrets::SecretsFormatter::new()))
+        });
+        registry.register("licenses", |o| {
+            Ok(Box::new(
+                licenses::LicensesFormatter::new().with_sbom(o.sbom.clone()),
+            ))
+        });
+        registry.register("deps", |o| {
+            Ok(Box::new(
+                deps::DepsFormatter::new().with_graph(o.graph.clone()),
+            ))
+        });
+        registry.register("churn", |_| Ok(Box::new(churn::ChurnFormatter::new())));
+        registry.register("marqant", |o| {
+            Ok(Box::new(marqant::MarqantFormatter::new(
+                o.path_mode,
+                o.no_emoji,
+            )))
+        });
+        registry.register("smart", |o| {
+            // The star of the show! Surface what matters, not everything.
+            Ok(Box::new(
+                smart::SmartFormatter::new(o.use_color, !o.no_emoji).with_path_mode(o.path_mode),
+            ))
+        });
+
+        registry
+    }
+
+    /// The process-wide registry - built-ins plus whatever was registered
+    pub fn global() -> &'static RwLock<FormatterRegistry> {
+        static REGISTRY: OnceLock<RwLock<FormatterRegistry>> = OnceLock::new();
+        REGISTRY.get_or_init(|| RwLock::new(FormatterRegistry::with_builtins()))
+    }
+
+    /// Register a formatter under a name (lowercased; replaces any previous
+    /// registration, so plugins can also override built-ins)
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&FormatterOptions) -> Result<Box<dyn Formatter>> + Send + Sync + 'static,
+    {
+        self.factories
+            .insert(name.to_lowercase(), Box::new(factory));
+    }
+
+    /// Instantiate the formatter registered under `name`, if any
+    pub fn create(
+        &self,
+        name: &str,
+        options: &FormatterOptions,
+    ) -> Option<Result<Box<dyn Formatter>>> {
+        self.factories
+            .get(&name.to_lowercase())
+            .map(|factory| factory(options))
+    }
+
+    /// Is a formatter registered under this name?
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(&name.to_lowercase())
+    }
+
+    /// Registered mode names, sorted (for error messages and docs)
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.factories.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// CLI mode spellings that are valid but don't (yet) resolve through the
+/// registry - they are handled by dedicated pipelines or fall back to
+/// classic downstream, exactly as before
+const LEGACY_MODES: &[&str] = &[
+    "auto",
+    "hextree",
+    "emotional",
+    "summary",
+    "summaryai",
+    "context",
+    "relations",
+    "quantumsemantic",
+    "sse",
+    "functionmarkdown",
+    "sqlite",
+    "parquet",
+];
+
+/// Resolve a `--mode` argument to the name the pipeline dispatches on
+///
+/// Registered formatters (built-in or plugin) win; legacy enum spellings
+/// like `summary-ai` are folded to their historical wire form; anything
+/// else is an error listing what's available.
+pub fn resolve_mode_name(raw: &str) -> Result<String> {
+    let lower = raw.to_lowercase();
+    let registry = FormatterRegistry::global()
+        .read()
+        .map_err(|_| anyhow::anyhow!("Formatter registry lock poisoned"))?;
+
+    if registry.contains(&lower) {
+        return Ok(lower);
+    }
+
+    let folded = lower.replace(['-', '_'], "");
+    if registry.contains(&folded) || LEGACY_MODES.contains(&folded.as_str()) {
+        return Ok(folded);
+    }
+
+    anyhow::bail!(
+        "Unknown output mode '{}'. Available modes: {}, {}",
+        raw,
+        registry.names().join(", "),
+        LEGACY_MODES.join(", ")
+    )
+}
@@ -0,0 +1,381 @@
+// -----------------------------------------------------------------------------
+// 🔐 PERMISSION AUDIT FORMATTER - Who Left the Door Unlocked? 🚪
+//
+// `st --mode perms` walks the tree with a security hat on: world-writable
+// files and directories, setuid/setgid binaries, entries owned by a uid the
+// rest of the tree has never heard of, and directories whose permission bits
+// contradict themselves (readable but not traversable). Findings come back
+// prioritized - CRIT first - so the scary stuff is at the top of the page.
+//
+// The same report backs the `audit_permissions` MCP tool.
+// -----------------------------------------------------------------------------
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Findings shown per severity section before eliding the rest.
+const DEFAULT_LIMIT: usize = 50;
+
+/// How urgently a finding needs eyes. Ordering matters: reports sort by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Severity {
+    /// Fixed-width tag for the report rows.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Critical => "CRIT",
+            Severity::High => "HIGH",
+            Severity::Medium => "MED ",
+            Severity::Low => "LOW ",
+        }
+    }
+}
+
+/// One flagged entry: where, how bad, and why.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub path: PathBuf,
+    pub detail: String,
+}
+
+/// What the audit needs to know about one entry. `FileNode.permissions` is
+/// masked to `0o777`, so the mode here is re-read from disk to recover the
+/// setuid/setgid/sticky bits the scanner drops.
+struct AuditEntry {
+    path: PathBuf,
+    is_dir: bool,
+    mode: u32,
+    uid: u32,
+}
+
+/// The whole tree's permission picture, worst-first.
+pub struct PermsReport {
+    /// All findings, sorted by severity then path.
+    pub findings: Vec<Finding>,
+    /// The uid owning the majority of the tree, when one does - files owned
+    /// by anyone else get flagged against it.
+    pub baseline_uid: Option<u32>,
+    /// Entries the audit actually examined.
+    pub entries_checked: usize,
+}
+
+impl PermsReport {
+    /// Audit the scanned nodes. Modes are re-stat'd per entry (symlinks and
+    /// permission-denied entries are skipped - their bits describe the
+    /// target, or are unknowable).
+    pub fn gather(nodes: &[FileNode]) -> Self {
+        let entries: Vec<AuditEntry> = nodes
+            .iter()
+            .filter(|node| !node.is_symlink && !node.permission_denied)
+            .map(|node| AuditEntry {
+                path: node.path.clone(),
+                is_dir: node.is_dir,
+                mode: full_mode(node),
+                uid: node.uid,
+            })
+            .collect();
+        Self::from_entries(entries)
+    }
+
+    /// Build the report from prepared entries. Pure so the tests can hand in
+    /// synthetic modes without touching the filesystem.
+    fn from_entries(entries: Vec<AuditEntry>) -> Self {
+        let baseline_uid = majority_uid(&entries);
+        let mut findings: Vec<Finding> = entries
+            .iter()
+            .flat_map(|entry| entry_findings(entry, baseline_uid))
+            .collect();
+        findings.sort_by(|a, b| a.severity.cmp(&b.severity).then_with(|| a.path.cmp(&b.path)));
+        Self {
+            findings,
+            baseline_uid,
+            entries_checked: entries.len(),
+        }
+    }
+
+    /// The prioritized report - shared by `--mode perms` and
+    /// `audit_permissions`.
+    pub fn render(&self, writer: &mut dyn Write, root: &Path, limit: usize) -> Result<()> {
+        writeln!(writer, "🔐 Permission Audit: {}", root.display())?;
+        writeln!(
+            writer,
+            "   ({} entries checked, {} findings{})",
+            self.entries_checked,
+            self.findings.len(),
+            match self.baseline_uid {
+                Some(uid) => format!(", baseline owner uid {}", uid),
+                None => String::new(),
+            }
+        )?;
+        writeln!(writer)?;
+
+        if self.findings.is_empty() {
+            writeln!(writer, "No findings - nothing world-writable, no setuid/setgid binaries, ownership consistent.")?;
+            return Ok(());
+        }
+
+        for severity in [
+            Severity::Critical,
+            Severity::High,
+            Severity::Medium,
+            Severity::Low,
+        ] {
+            let section: Vec<&Finding> = self
+                .findings
+                .iter()
+                .filter(|f| f.severity == severity)
+                .collect();
+            if section.is_empty() {
+                continue;
+            }
+            for finding in section.iter().take(limit) {
+                let rel = finding.path.strip_prefix(root).unwrap_or(&finding.path);
+                writeln!(
+                    writer,
+                    "[{}] {} - {}",
+                    severity.label(),
+                    rel.display(),
+                    finding.detail
+                )?;
+            }
+            if section.len() > limit {
+                writeln!(
+                    writer,
+                    "[{}] ... and {} more",
+                    severity.label(),
+                    section.len() - limit
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Findings for a single entry, judged against the tree's baseline owner.
+fn entry_findings(entry: &AuditEntry, baseline_uid: Option<u32>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mode = entry.mode & 0o7777;
+    let mut push = |severity, detail: String| {
+        findings.push(Finding {
+            severity,
+            path: entry.path.clone(),
+            detail,
+        })
+    };
+
+    if entry.is_dir {
+        // World-writable directory: anyone can plant or replace files.
+        // The sticky bit (tmp-style) downgrades but does not excuse it.
+        // Write without traversal can't actually plant anything - that
+        // lands in the chmod-typo check below instead.
+        if mode & 0o003 == 0o003 {
+            if mode & 0o1000 == 0 {
+                push(
+                    Severity::Critical,
+                    format!("world-writable directory without sticky bit (mode {:04o})", mode),
+                );
+            } else {
+                push(
+                    Severity::Medium,
+                    format!("world-writable directory (sticky bit set, mode {:04o})", mode),
+                );
+            }
+        }
+        // Mismatched bits: a class can list or write the directory but not
+        // traverse it (r or w without x) - usually a chmod typo.
+        for (class, shift) in [("group", 3u32), ("other", 0u32)] {
+            let bits = (mode >> shift) & 0o7;
+            if bits & 0o1 == 0 && bits & 0o6 != 0 {
+                push(
+                    Severity::Medium,
+                    format!(
+                        "directory grants {} read/write without traversal (mode {:04o})",
+                        class, mode
+                    ),
+                );
+            }
+        }
+    } else {
+        // Setuid/setgid: the classic privilege-escalation foothold.
+        if mode & 0o4000 != 0 {
+            push(
+                Severity::Critical,
+                format!("setuid binary, runs as uid {} (mode {:04o})", entry.uid, mode),
+            );
+        }
+        if mode & 0o2000 != 0 {
+            push(
+                Severity::High,
+                format!("setgid binary (mode {:04o})", mode),
+            );
+        }
+        // World-writable file: critical when it is also executable (or
+        // setuid - anyone can rewrite what then runs privileged).
+        if mode & 0o002 != 0 {
+            if mode & 0o4111 != 0 {
+                push(
+                    Severity::Critical,
+                    format!("world-writable executable (mode {:04o})", mode),
+                );
+            } else {
+                push(
+                    Severity::High,
+                    format!("world-writable file (mode {:04o})", mode),
+                );
+            }
+        }
+    }
+
+    // Unexpected owner: flagged only when the tree has a clear majority
+    // owner to judge against.
+    if let Some(baseline) = baseline_uid {
+        if entry.uid != baseline {
+            push(
+                Severity::Medium,
+                format!("owned by uid {} - rest of the tree is uid {}", entry.uid, baseline),
+            );
+        }
+    }
+
+    findings
+}
+
+/// The uid owning more than half the entries, if any. Trees with genuinely
+/// mixed ownership produce no baseline - and no ownership findings.
+fn majority_uid(entries: &[AuditEntry]) -> Option<u32> {
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    for entry in entries {
+        *counts.entry(entry.uid).or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .find(|(_, count)| *count * 2 > entries.len())
+        .map(|(uid, _)| uid)
+}
+
+/// Full mode bits including setuid/setgid/sticky, which the scanner masks
+/// off. Falls back to the (masked) scanned permissions when the entry
+/// vanished between scan and audit.
+#[cfg(unix)]
+fn full_mode(node: &FileNode) -> u32 {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::symlink_metadata(&node.path)
+        .map(|meta| meta.mode())
+        .unwrap_or(node.permissions)
+}
+
+#[cfg(not(unix))]
+fn full_mode(node: &FileNode) -> u32 {
+    node.permissions
+}
+
+/// `st --mode perms` - the audit report as a formatter.
+pub struct PermsFormatter {
+    /// Findings shown per severity section.
+    pub limit: usize,
+}
+
+impl Default for PermsFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PermsFormatter {
+    pub fn new() -> Self {
+        Self {
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Formatter for PermsFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        PermsReport::gather(nodes).render(writer, root_path, self.limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, is_dir: bool, mode: u32, uid: u32) -> AuditEntry {
+        AuditEntry {
+            path: PathBuf::from(path),
+            is_dir,
+            mode,
+            uid,
+        }
+    }
+
+    #[test]
+    fn test_setuid_and_world_writable_flagged_worst_first() {
+        let report = PermsReport::from_entries(vec![
+            entry("/t/ok.txt", false, 0o644, 1000),
+            entry("/t/loose.txt", false, 0o666, 1000),
+            entry("/t/sudo", false, 0o4755, 1000),
+        ]);
+
+        assert_eq!(report.entries_checked, 3);
+        assert_eq!(report.findings.len(), 2);
+        // Sorted by severity: the setuid binary outranks the sloppy file.
+        assert_eq!(report.findings[0].severity, Severity::Critical);
+        assert_eq!(report.findings[0].path, PathBuf::from("/t/sudo"));
+        assert_eq!(report.findings[1].severity, Severity::High);
+    }
+
+    #[test]
+    fn test_directory_findings() {
+        let report = PermsReport::from_entries(vec![
+            entry("/t/open", true, 0o777, 1000),
+            entry("/t/tmpish", true, 0o1777, 1000),
+            entry("/t/typo", true, 0o766, 1000),
+        ]);
+
+        let severities: Vec<Severity> =
+            report.findings.iter().map(|f| f.severity).collect();
+        // open: critical (no sticky); tmpish: medium; typo: medium
+        // (group/other can write but not traverse).
+        assert_eq!(severities[0], Severity::Critical);
+        assert!(severities[1..].iter().all(|s| *s == Severity::Medium));
+        assert_eq!(report.findings.len(), 4);
+    }
+
+    #[test]
+    fn test_unexpected_owner_needs_a_majority() {
+        // Clear majority: the odd uid out is flagged.
+        let report = PermsReport::from_entries(vec![
+            entry("/t/a", false, 0o644, 1000),
+            entry("/t/b", false, 0o644, 1000),
+            entry("/t/root_owned", false, 0o644, 0),
+        ]);
+        assert_eq!(report.baseline_uid, Some(1000));
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].path, PathBuf::from("/t/root_owned"));
+
+        // 50/50 split: no baseline, no ownership findings.
+        let report = PermsReport::from_entries(vec![
+            entry("/t/a", false, 0o644, 1000),
+            entry("/t/b", false, 0o644, 0),
+        ]);
+        assert_eq!(report.baseline_uid, None);
+        assert!(report.findings.is_empty());
+    }
+}
@@ -592,15 +592,13 @@ impl ProjectsFormatter {
 
     /// Get git information using command-line git
     fn get_git_info(&self, path: &Path) -> Option<GitInfo> {
-        use std::process::Command;
-
         // Check if this is a git repository
         if !path.join(".git").exists() {
             return None;
         }
 
         // Get current branch
-        let branch = Command::new("git")
+        let branch = crate::exec_policy::command("git").ok()?
             .arg("branch")
             .arg("--show-current")
             .current_dir(path)
@@ -611,7 +609,7 @@ impl ProjectsFormatter {
             .unwrap_or_else(|| "unknown".to_string());
 
         // Get current commit hash (short)
-        let commit = Command::new("git")
+        let commit = crate::exec_policy::command("git").ok()?
             .arg("rev-parse")
             .arg("--short")
             .arg("HEAD")
@@ -623,7 +621,7 @@ impl ProjectsFormatter {
             .unwrap_or_else(|| "unknown".to_string());
 
         // Get last commit message (first line)
-        let commit_message = Command::new("git")
+        let commit_message = crate::exec_policy::command("git").ok()?
             .arg("log")
             .arg("-1")
             .arg("--pretty=%s")
@@ -635,7 +633,7 @@ impl ProjectsFormatter {
             .unwrap_or_default();
 
         // Check if repository is dirty
-        let is_dirty = Command::new("git")
+        let is_dirty = crate::exec_policy::command("git").ok()?
             .arg("status")
             .arg("--porcelain")
             .current_dir(path)
@@ -645,7 +643,7 @@ impl ProjectsFormatter {
             .unwrap_or(false);
 
         // Get commits ahead/behind (if tracking upstream)
-        let (ahead, behind) = Command::new("git")
+        let (ahead, behind) = crate::exec_policy::command("git").ok()?
             .arg("rev-list")
             .arg("--left-right")
             .arg("--count")
@@ -665,7 +663,7 @@ impl ProjectsFormatter {
             .unwrap_or((0, 0));
 
         // Get last commit timestamp
-        let last_commit_date = Command::new("git")
+        let last_commit_date = crate::exec_policy::command("git").ok()?
             .arg("log")
             .arg("-1")
             .arg("--pretty=%ct")
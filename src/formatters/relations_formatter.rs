@@ -2,21 +2,72 @@
 //! "Making relations a first-class mode!" - Omni
 
 use crate::formatters::Formatter;
-use crate::relations::RelationAnalyzer;
+use crate::relations::{RelationAnalyzer, RelationType, CYCLE_FAIL_MARKER};
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
+use std::collections::BTreeMap;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Render the strongly-connected-components report shared by the relations
+/// renderers. `comment` prefixes every line ("// " keeps it valid inside
+/// DOT output); when `fail` is set and cycles exist, the marker line the
+/// thin client greps for closes the report.
+fn write_cycle_report(
+    writer: &mut dyn Write,
+    cycles: &[Vec<PathBuf>],
+    root_path: &Path,
+    comment: &str,
+    fail: bool,
+) -> Result<()> {
+    if cycles.is_empty() {
+        return Ok(());
+    }
+    writeln!(writer, "{}🔄 Dependency Cycles ({}):", comment, cycles.len())?;
+    for cycle in cycles {
+        let members: Vec<String> = cycle
+            .iter()
+            .map(|f| {
+                f.strip_prefix(root_path)
+                    .unwrap_or(f)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        writeln!(writer, "{}  ↻ {}", comment, members.join(" → "))?;
+    }
+    if fail {
+        writeln!(
+            writer,
+            "{}{} ({}) - failing per --fail-on-cycles",
+            comment,
+            CYCLE_FAIL_MARKER,
+            cycles.len()
+        )?;
+    }
+    Ok(())
+}
 
 /// Main relations formatter - delegates to text formatter by default
 pub struct RelationsFormatter {
     filter: Option<String>,
     focus: Option<std::path::PathBuf>,
+    fail_on_cycles: bool,
 }
 
 impl RelationsFormatter {
     pub fn new(filter: Option<String>, focus: Option<std::path::PathBuf>) -> Self {
-        Self { filter, focus }
+        Self {
+            filter,
+            focus,
+            fail_on_cycles: false,
+        }
+    }
+
+    /// Gate the exit code on cycle-freedom (--fail-on-cycles)
+    pub fn with_fail_on_cycles(mut self, enabled: bool) -> Self {
+        self.fail_on_cycles = enabled;
+        self
     }
 }
 
@@ -82,7 +133,6 @@ impl Formatter for RelationsFormatter {
         }
 
         // Group relations by type
-        use crate::relations::RelationType;
         let mut imports = Vec::new();
         let mut calls = Vec::new();
         let mut types = Vec::new();
@@ -166,6 +216,13 @@ impl Formatter for RelationsFormatter {
             writeln!(writer)?;
         }
 
+        // Cycles are reported over the whole graph, not just the focus
+        let cycles = analyzer.find_cycles();
+        write_cycle_report(writer, &cycles, root_path, "", self.fail_on_cycles)?;
+        if !cycles.is_empty() {
+            writeln!(writer)?;
+        }
+
         // Summary
         writeln!(writer, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
         writeln!(writer, "Total relationships: {}", relations.len())?;
@@ -178,3 +235,212 @@ impl Formatter for RelationsFormatter {
         Ok(())
     }
 }
+
+/// Relations rendered as Graphviz DOT (`--mode relations --dot`)
+///
+/// Runs the same analysis as [`RelationsFormatter`] but delegates rendering
+/// to [`crate::formatters::relations::DotRelationFormatter`], so the result
+/// pipes straight into `dot -Tsvg`.
+pub struct RelationsDotFormatter {
+    filter: Option<String>,
+    focus: Option<std::path::PathBuf>,
+    fail_on_cycles: bool,
+}
+
+impl RelationsDotFormatter {
+    pub fn new(filter: Option<String>, focus: Option<std::path::PathBuf>) -> Self {
+        Self {
+            filter,
+            focus,
+            fail_on_cycles: false,
+        }
+    }
+
+    /// Gate the exit code on cycle-freedom (--fail-on-cycles)
+    pub fn with_fail_on_cycles(mut self, enabled: bool) -> Self {
+        self.fail_on_cycles = enabled;
+        self
+    }
+}
+
+impl Formatter for RelationsDotFormatter {
+    fn format(
+        &self,
+        mut writer: &mut dyn Write,
+        _nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        let mut analyzer = RelationAnalyzer::new();
+
+        eprintln!("🔍 Analyzing code relationships...");
+        analyzer.analyze_directory(root_path)?;
+
+        if let Some(filter_type) = &self.filter {
+            eprintln!("📋 Filtering by: {}", filter_type);
+        }
+        if let Some(focus_file) = &self.focus {
+            eprintln!("📄 Focus: {}", focus_file.display());
+        }
+
+        crate::formatters::relations::DotRelationFormatter.format(&mut writer, &analyzer, root_path)?;
+
+        // DOT-level comments keep the output valid for `dot -Tsvg`
+        write_cycle_report(
+            writer,
+            &analyzer.find_cycles(),
+            root_path,
+            "// ",
+            self.fail_on_cycles,
+        )
+    }
+}
+
+/// Output shape for the call graph export
+pub enum CallGraphFormat {
+    Dot,
+    Json,
+}
+
+impl CallGraphFormat {
+    pub fn parse(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "dot" => Ok(Self::Dot),
+            "json" => Ok(Self::Json),
+            other => anyhow::bail!("Unknown call graph format '{}'; use dot or json", other),
+        }
+    }
+}
+
+/// Function-level call graph exporter (`--mode relations --call-graph dot|json`)
+///
+/// Callers are file nodes, callees are `file::function` nodes - the regex
+/// parsers don't track which function a call sits inside, so the caller
+/// side stays at file granularity. Cycles ride along in both formats so a
+/// CI job gets the graph and the verdict in one pass.
+pub struct CallGraphFormatter {
+    format: CallGraphFormat,
+    fail_on_cycles: bool,
+}
+
+impl CallGraphFormatter {
+    pub fn new(format: CallGraphFormat) -> Self {
+        Self {
+            format,
+            fail_on_cycles: false,
+        }
+    }
+
+    /// Gate the exit code on cycle-freedom (--fail-on-cycles)
+    pub fn with_fail_on_cycles(mut self, enabled: bool) -> Self {
+        self.fail_on_cycles = enabled;
+        self
+    }
+}
+
+impl Formatter for CallGraphFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        _nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        let mut analyzer = RelationAnalyzer::new();
+
+        eprintln!("🔍 Analyzing code relationships...");
+        analyzer.analyze_directory(root_path)?;
+
+        let rel_path = |file: &PathBuf| -> String {
+            file.strip_prefix(root_path)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        // (caller, callee file, function) triples from the call edges
+        let mut edges: Vec<(String, String, String)> = Vec::new();
+        let mut functions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for rel in analyzer.get_relations() {
+            if rel.relation_type != RelationType::FunctionCall {
+                continue;
+            }
+            let caller = rel_path(&rel.source);
+            let callee = rel_path(&rel.target);
+            for function in &rel.items {
+                let known = functions.entry(callee.clone()).or_default();
+                if !known.contains(function) {
+                    known.push(function.clone());
+                }
+                edges.push((caller.clone(), callee.clone(), function.clone()));
+            }
+        }
+        edges.sort();
+        let cycles = analyzer.find_cycles();
+
+        match self.format {
+            CallGraphFormat::Dot => {
+                writeln!(writer, "digraph CallGraph {{")?;
+                writeln!(writer, "    // Smart Tree Function Call Graph")?;
+                writeln!(writer, "    rankdir=LR;")?;
+                writeln!(writer, "    node [shape=ellipse, style=filled, fillcolor=lightyellow];")?;
+                writeln!(writer)?;
+                for (file, names) in &functions {
+                    for name in names {
+                        writeln!(writer, "    \"{}::{}\";", file, name)?;
+                    }
+                }
+                writeln!(writer)?;
+                for caller in edges.iter().map(|(c, _, _)| c).collect::<std::collections::BTreeSet<_>>() {
+                    writeln!(
+                        writer,
+                        "    \"{}\" [shape=box, fillcolor=lightgray];",
+                        caller
+                    )?;
+                }
+                writeln!(writer)?;
+                for (caller, callee, function) in &edges {
+                    writeln!(writer, "    \"{}\" -> \"{}::{}\";", caller, callee, function)?;
+                }
+                writeln!(writer, "}}")?;
+                write_cycle_report(writer, &cycles, root_path, "// ", self.fail_on_cycles)?;
+            }
+            CallGraphFormat::Json => {
+                let cycle_paths: Vec<Vec<String>> = cycles
+                    .iter()
+                    .map(|cycle| cycle.iter().map(&rel_path).collect())
+                    .collect();
+                let edge_objects: Vec<serde_json::Value> = edges
+                    .iter()
+                    .map(|(caller, callee, function)| {
+                        serde_json::json!({
+                            "from": caller,
+                            "to": callee,
+                            "function": function,
+                        })
+                    })
+                    .collect();
+                let doc = serde_json::json!({
+                    "version": 1,
+                    "root": root_path.display().to_string(),
+                    "functions": functions,
+                    "edges": edge_objects,
+                    "cycles": cycle_paths,
+                });
+                writeln!(writer, "{}", serde_json::to_string_pretty(&doc)?)?;
+                // Trailing marker, outside the JSON body - CI greps it,
+                // jq consumers read everything up to the last line
+                if self.fail_on_cycles && !cycles.is_empty() {
+                    writeln!(
+                        writer,
+                        "{} ({}) - failing per --fail-on-cycles",
+                        CYCLE_FAIL_MARKER,
+                        cycles.len()
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -227,6 +227,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
             FileNode {
                 path: PathBuf::from("src/main.rs"),
@@ -251,6 +254,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
             FileNode {
                 path: PathBuf::from("tests/test_main.rs"),
@@ -275,6 +281,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
         ];
 
@@ -498,6 +498,9 @@ mod tests {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         }
     }
 
@@ -518,6 +521,13 @@ mod tests {
             largest_files: vec![],
             newest_files: vec![],
             oldest_files: vec![],
+            disk_usage: 0,
+            hardlink_duplicates: 0,
+            seen_inodes: std::collections::HashSet::new(),
+            allocated_size: 0,
+            sparse_files: 0,
+            pruned: std::collections::HashMap::new(),
+            truncated: false,
         };
 
         let mut output = Vec::new();
@@ -1,12 +1,103 @@
 use super::Formatter;
+use crate::content_hasher::{ContentHasher, HashAlgorithm};
+use crate::loc_counter::{tally_by_language, LineCounts};
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
 use chrono::{DateTime, Local};
 use humansize::{format_size, BINARY};
+use regex::Regex;
+use std::collections::HashMap;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub struct StatsFormatter;
+/// Directories shown in the per-directory extension breakdown.
+const PER_DIR_LIMIT: usize = 15;
+
+/// Extensions shown per directory in the breakdown.
+const PER_DIR_EXT_LIMIT: usize = 5;
+
+/// Default length of the Largest Files list (--stats-top overrides).
+const TOP_LARGEST: usize = 10;
+
+/// Default length of the Newest/Oldest Files lists (--stats-top overrides).
+const TOP_RECENT: usize = 5;
+
+/// Total bytes per file category, largest share first.
+///
+/// Categories come from the scanner's classification, so "how much of this
+/// tree is video vs logs vs code" needs no extension guessing here.
+pub fn category_byte_shares(nodes: &[FileNode]) -> Vec<(String, u64)> {
+    let mut totals: HashMap<String, u64> = HashMap::new();
+    for node in nodes {
+        if node.is_dir || node.permission_denied {
+            continue;
+        }
+        let label = format!("{:?}", node.category).to_lowercase();
+        *totals.entry(label).or_insert(0) += node.size;
+    }
+    let mut shares: Vec<(String, u64)> = totals.into_iter().collect();
+    shares.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    shares
+}
+
+/// Count and byte totals for one extension within one directory's subtree.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExtStat {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+/// Recursive per-directory extension totals (`--per-dir`)
+///
+/// Every file is attributed to each of its ancestor directories up to the
+/// scan root, so a directory's entry covers its whole subtree - that's what
+/// answers "which directory holds the .log bulk". Files without an
+/// extension are skipped, matching `TreeStats::file_types`.
+pub fn dir_extension_stats(
+    nodes: &[FileNode],
+    root: &Path,
+) -> HashMap<PathBuf, HashMap<String, ExtStat>> {
+    let mut map: HashMap<PathBuf, HashMap<String, ExtStat>> = HashMap::new();
+    for node in nodes {
+        if node.is_dir || node.permission_denied {
+            continue;
+        }
+        let Some(ext) = node.path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        let mut current = node.path.parent();
+        while let Some(dir) = current {
+            let stat = map
+                .entry(dir.to_path_buf())
+                .or_default()
+                .entry(ext.to_string())
+                .or_default();
+            stat.count += 1;
+            stat.bytes += node.size;
+            if dir == root {
+                break;
+            }
+            current = dir.parent();
+        }
+    }
+    map
+}
+
+pub struct StatsFormatter {
+    /// When set, verify duplicate files by content hash (--hash flag)
+    hash_algorithm: Option<HashAlgorithm>,
+    /// Show recursive per-directory extension totals (--per-dir flag)
+    per_dir: bool,
+    /// Show tokei-style code/comment/blank counts per language (--loc flag)
+    loc: bool,
+    /// Aggregate media totals - image count, audio/video minutes (--media-info)
+    media_info: bool,
+    /// Override for the largest/newest/oldest list lengths (--stats-top)
+    top: Option<usize>,
+    /// Restrict every section to relative paths matching this pattern
+    /// (--stats-filter)
+    filter: Option<Regex>,
+}
 
 impl Default for StatsFormatter {
     fn default() -> Self {
@@ -16,7 +107,76 @@ impl Default for StatsFormatter {
 
 impl StatsFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            hash_algorithm: None,
+            per_dir: false,
+            loc: false,
+            media_info: false,
+            top: None,
+            filter: None,
+        }
+    }
+
+    /// Enable content-hash duplicate verification in the stats report.
+    pub fn with_hashing(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = Some(algorithm);
+        self
+    }
+
+    /// Enable the recursive per-directory extension breakdown.
+    pub fn with_per_dir(mut self, per_dir: bool) -> Self {
+        self.per_dir = per_dir;
+        self
+    }
+
+    /// Enable per-language line counting (code/comments/blanks).
+    pub fn with_loc(mut self, loc: bool) -> Self {
+        self.loc = loc;
+        self
+    }
+
+    /// Enable aggregated media totals (image count, playback time).
+    pub fn with_media_info(mut self, media_info: bool) -> Self {
+        self.media_info = media_info;
+        self
+    }
+
+    /// Override the largest/newest/oldest list lengths.
+    pub fn with_top(mut self, top: Option<usize>) -> Self {
+        self.top = top;
+        self
+    }
+
+    /// Restrict the whole report to relative paths matching `filter`.
+    pub fn with_filter(mut self, filter: Option<Regex>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// A scanner-built top list as-is when the defaults apply, or recomputed
+    /// from the nodes when --stats-top / --stats-filter changed what "top"
+    /// means (the scanner only ever keeps its default-sized lists).
+    fn top_list<K>(
+        &self,
+        from_scanner: Vec<(K, PathBuf)>,
+        n: usize,
+        nodes: &[FileNode],
+        key: impl Fn(&FileNode) -> (K, PathBuf),
+        order: impl Fn(&(K, PathBuf), &(K, PathBuf)) -> std::cmp::Ordering,
+    ) -> Vec<(K, PathBuf)> {
+        if self.top.is_none() && self.filter.is_none() {
+            let mut list = from_scanner;
+            list.truncate(n);
+            return list;
+        }
+        let mut list: Vec<(K, PathBuf)> = nodes
+            .iter()
+            .filter(|node| !node.is_dir && !node.permission_denied)
+            .map(key)
+            .collect();
+        list.sort_by(&order);
+        list.truncate(n);
+        list
     }
 }
 
@@ -24,13 +184,47 @@ impl Formatter for StatsFormatter {
     fn format(
         &self,
         writer: &mut dyn Write,
-        _nodes: &[FileNode],
+        nodes: &[FileNode],
         stats: &TreeStats,
         root_path: &Path,
     ) -> Result<()> {
+        // --stats-filter narrows every section to matching relative paths;
+        // totals and top lists are recomputed over the subset so the report
+        // stays internally consistent
+        let filtered: Option<Vec<FileNode>> = self.filter.as_ref().map(|pattern| {
+            nodes
+                .iter()
+                .filter(|n| {
+                    let rel = n.path.strip_prefix(root_path).unwrap_or(&n.path);
+                    pattern.is_match(&rel.to_string_lossy())
+                })
+                .cloned()
+                .collect()
+        });
+        let nodes: &[FileNode] = filtered.as_deref().unwrap_or(nodes);
+        let recomputed: Option<TreeStats> = filtered.as_ref().map(|subset| {
+            let mut subset_stats = TreeStats::default();
+            for node in subset {
+                subset_stats.update_file(node);
+            }
+            // Truncation happened at scan time - the subset inherits it.
+            subset_stats.truncated = stats.truncated;
+            subset_stats
+        });
+        let stats: &TreeStats = recomputed.as_ref().unwrap_or(stats);
+
         writeln!(writer, "{}", "=".repeat(60))?;
         writeln!(writer, "Directory Statistics for: {}", root_path.display())?;
+        if let Some(pattern) = &self.filter {
+            writeln!(writer, "Filtered to paths matching: {}", pattern)?;
+        }
         writeln!(writer, "{}", "=".repeat(60))?;
+        if stats.truncated {
+            writeln!(
+                writer,
+                "⚠️  Scan truncated by a budget or safety limit - totals cover the walked portion only"
+            )?;
+        }
         writeln!(
             writer,
             "Total Files: {} ({:x} hex)",
@@ -48,8 +242,59 @@ impl Formatter for StatsFormatter {
             stats.total_size,
             format_size(stats.total_size, BINARY)
         )?;
+        // Allocated (du-style) size - only shown when --du collected block
+        // counts and they actually differ from the logical total
+        if stats.allocated_size != stats.total_size || stats.sparse_files > 0 {
+            writeln!(
+                writer,
+                "Allocated Size (du): {} bytes ({:x} hex) ({})",
+                stats.allocated_size,
+                stats.allocated_size,
+                format_size(stats.allocated_size, BINARY)
+            )?;
+            if stats.sparse_files > 0 {
+                writeln!(writer, "Sparse Files: {}", stats.sparse_files)?;
+            }
+        }
+        // Apparent vs on-disk: the numbers only diverge when
+        // --dedupe-hardlinks found multi-link files
+        if stats.hardlink_duplicates > 0 {
+            writeln!(
+                writer,
+                "Disk Usage (each inode once): {} bytes ({:x} hex) ({})",
+                stats.disk_usage,
+                stats.disk_usage,
+                format_size(stats.disk_usage, BINARY)
+            )?;
+            writeln!(
+                writer,
+                "Hard-Linked Duplicates: {} entries sharing {} of apparent size",
+                stats.hardlink_duplicates,
+                format_size(stats.total_size - stats.disk_usage, BINARY)
+            )?;
+        }
         writeln!(writer)?;
 
+        // What the ignore rules kept out - helps users tune their ignore
+        // settings. Pruned directories are never walked, so they report
+        // entries but no bytes.
+        if !stats.pruned.is_empty() {
+            writeln!(writer, "Pruned by Ignore Rules (not shown above):")?;
+            let mut rules: Vec<_> = stats.pruned.iter().collect();
+            rules.sort_by_key(|(_, count)| std::cmp::Reverse(count.entries));
+
+            for (rule, count) in rules {
+                writeln!(
+                    writer,
+                    "  {}: {} entries, {} skipped",
+                    rule,
+                    count.entries,
+                    format_size(count.bytes, BINARY)
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
         // File types by count
         if !stats.file_types.is_empty() {
             writeln!(writer, "File Types (by count):")?;
@@ -62,10 +307,151 @@ impl Formatter for StatsFormatter {
             writeln!(writer)?;
         }
 
-        // Largest files
-        if !stats.largest_files.is_empty() {
+        // Byte share per scanner category - "is this tree code or videos?"
+        let shares = category_byte_shares(nodes);
+        if !shares.is_empty() && stats.total_size > 0 {
+            writeln!(writer, "File Categories (by bytes):")?;
+            for (category, bytes) in shares.iter().take(20) {
+                writeln!(
+                    writer,
+                    "  {:<12} {:>10}  {:>5.1}%",
+                    category,
+                    format_size(*bytes, BINARY),
+                    *bytes as f64 * 100.0 / stats.total_size as f64
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
+        // Per-directory extension breakdown (--per-dir): which directories
+        // hold the bulk of each type, biggest subtrees first
+        if self.per_dir {
+            let per_dir = dir_extension_stats(nodes, root_path);
+            let mut dirs: Vec<(&PathBuf, u64)> = per_dir
+                .iter()
+                .map(|(dir, exts)| (dir, exts.values().map(|s| s.bytes).sum()))
+                .collect();
+            dirs.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+            writeln!(writer, "Per-Directory Extension Totals (recursive):")?;
+            for (dir, total) in dirs.iter().take(PER_DIR_LIMIT) {
+                let rel = dir.strip_prefix(root_path).unwrap_or(dir);
+                let label = if rel.as_os_str().is_empty() {
+                    ".".to_string()
+                } else {
+                    rel.display().to_string()
+                };
+                writeln!(writer, "  {} ({}):", label, format_size(*total, BINARY))?;
+
+                let mut exts: Vec<(&String, &ExtStat)> = per_dir[*dir].iter().collect();
+                exts.sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes).then_with(|| a.0.cmp(b.0)));
+                for (ext, stat) in exts.iter().take(PER_DIR_EXT_LIMIT) {
+                    writeln!(
+                        writer,
+                        "    .{}: {} files, {}",
+                        ext,
+                        stat.count,
+                        format_size(stat.bytes, BINARY)
+                    )?;
+                }
+                if exts.len() > PER_DIR_EXT_LIMIT {
+                    writeln!(
+                        writer,
+                        "    ... and {} more extensions",
+                        exts.len() - PER_DIR_EXT_LIMIT
+                    )?;
+                }
+            }
+            if dirs.len() > PER_DIR_LIMIT {
+                writeln!(
+                    writer,
+                    "  ... and {} more directories",
+                    dirs.len() - PER_DIR_LIMIT
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
+        // Lines of code per language - reads file contents, so opt-in
+        if self.loc {
+            let totals = tally_by_language(nodes);
+            writeln!(writer, "Lines of Code by Language:")?;
+            writeln!(
+                writer,
+                "  {:<12} {:>7} {:>10} {:>10} {:>8} {:>10}",
+                "Language", "Files", "Code", "Comments", "Blanks", "Total"
+            )?;
+            let mut grand = LineCounts::default();
+            let mut grand_files = 0;
+            for lang in &totals {
+                writeln!(
+                    writer,
+                    "  {:<12} {:>7} {:>10} {:>10} {:>8} {:>10}",
+                    lang.language,
+                    lang.files,
+                    lang.lines.code,
+                    lang.lines.comments,
+                    lang.lines.blanks,
+                    lang.lines.total()
+                )?;
+                grand_files += lang.files;
+                grand.code += lang.lines.code;
+                grand.comments += lang.lines.comments;
+                grand.blanks += lang.lines.blanks;
+            }
+            writeln!(
+                writer,
+                "  {:<12} {:>7} {:>10} {:>10} {:>8} {:>10}",
+                "Total",
+                grand_files,
+                grand.code,
+                grand.comments,
+                grand.blanks,
+                grand.total()
+            )?;
+            writeln!(writer)?;
+        }
+
+        // Media totals - probes file headers, so opt-in like --loc
+        if self.media_info {
+            let media = crate::media_info::summarize(nodes);
+            if !media.is_empty() {
+                writeln!(writer, "Media:")?;
+                if media.images > 0 {
+                    writeln!(writer, "  Images: {}", media.images)?;
+                }
+                if media.audio_files > 0 {
+                    writeln!(
+                        writer,
+                        "  Audio:  {} files, {} total",
+                        media.audio_files,
+                        crate::media_info::format_duration(media.audio_secs)
+                    )?;
+                }
+                if media.video_files > 0 {
+                    writeln!(
+                        writer,
+                        "  Video:  {} files, {} total",
+                        media.video_files,
+                        crate::media_info::format_duration(media.video_secs)
+                    )?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
+        // Largest files. The scanner only keeps its default-sized lists, so
+        // --stats-top (or a filter) recomputes them from the nodes here.
+        let largest = self.top_list(
+            stats.largest_files.clone(),
+            self.top.unwrap_or(TOP_LARGEST),
+            nodes,
+            |n| (n.size, n.path.clone()),
+            |a, b| b.0.cmp(&a.0),
+        );
+        if !largest.is_empty() {
             writeln!(writer, "Largest Files:")?;
-            for (size, path) in stats.largest_files.iter().take(10) {
+            for (size, path) in &largest {
                 let rel_path = path.strip_prefix(root_path).unwrap_or(path);
                 writeln!(
                     writer,
@@ -80,9 +466,16 @@ impl Formatter for StatsFormatter {
         }
 
         // Newest files
-        if !stats.newest_files.is_empty() {
+        let newest = self.top_list(
+            stats.newest_files.clone(),
+            self.top.unwrap_or(TOP_RECENT),
+            nodes,
+            |n| (n.modified, n.path.clone()),
+            |a, b| b.0.cmp(&a.0),
+        );
+        if !newest.is_empty() {
             writeln!(writer, "Newest Files:")?;
-            for (mtime, path) in stats.newest_files.iter().take(5) {
+            for (mtime, path) in &newest {
                 let datetime = DateTime::<Local>::from(*mtime);
                 let rel_path = path.strip_prefix(root_path).unwrap_or(path);
                 writeln!(
@@ -96,9 +489,16 @@ impl Formatter for StatsFormatter {
         }
 
         // Oldest files
-        if !stats.oldest_files.is_empty() {
+        let oldest = self.top_list(
+            stats.oldest_files.clone(),
+            self.top.unwrap_or(TOP_RECENT),
+            nodes,
+            |n| (n.modified, n.path.clone()),
+            |a, b| a.0.cmp(&b.0),
+        );
+        if !oldest.is_empty() {
             writeln!(writer, "Oldest Files:")?;
-            for (mtime, path) in stats.oldest_files.iter().take(5) {
+            for (mtime, path) in &oldest {
                 let datetime = DateTime::<Local>::from(*mtime);
                 let rel_path = path.strip_prefix(root_path).unwrap_or(path);
                 writeln!(
@@ -110,6 +510,123 @@ impl Formatter for StatsFormatter {
             }
         }
 
+        // Content-verified duplicate report (only with --hash)
+        if let Some(algorithm) = self.hash_algorithm {
+            let groups = ContentHasher::new(algorithm).find_duplicate_groups(nodes);
+            writeln!(writer)?;
+            writeln!(
+                writer,
+                "Duplicate Files (verified by {}):",
+                algorithm.as_str()
+            )?;
+            if groups.is_empty() {
+                writeln!(writer, "  None found - every file is an original!")?;
+            } else {
+                let wasted: u64 = groups.iter().map(|g| g.wasted_bytes()).sum();
+                writeln!(
+                    writer,
+                    "  {} groups, {} reclaimable",
+                    groups.len(),
+                    format_size(wasted, BINARY)
+                )?;
+                for group in groups.iter().take(10) {
+                    writeln!(
+                        writer,
+                        "  {} x {} ({}):",
+                        group.paths.len(),
+                        format_size(group.size, BINARY),
+                        &group.hash[..group.hash.len().min(12)]
+                    )?;
+                    for path in &group.paths {
+                        let rel_path = path.strip_prefix(root_path).unwrap_or(path);
+                        writeln!(writer, "    {}", rel_path.display())?;
+                    }
+                }
+                if groups.len() > 10 {
+                    writeln!(writer, "  ... and {} more groups", groups.len() - 10)?;
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::time::SystemTime;
+
+    fn file(path: &str, size: u64) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir: false,
+            size,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth: 2,
+            file_type: FileType::RegularFile,
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Ext4,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_category_byte_shares_sorted_by_bytes() {
+        let mut video = file("/r/clip.mp4", 5000);
+        video.category = FileCategory::Video;
+        let mut code = file("/r/main.rs", 100);
+        code.category = FileCategory::Rust;
+        let mut more_code = file("/r/lib.rs", 200);
+        more_code.category = FileCategory::Rust;
+
+        let shares = category_byte_shares(&[video, code, more_code]);
+
+        assert_eq!(shares[0], ("video".to_string(), 5000));
+        assert_eq!(shares[1], ("rust".to_string(), 300));
+    }
+
+    #[test]
+    fn test_dir_extension_stats_attributes_to_every_ancestor() {
+        let root = Path::new("/r");
+        let nodes = vec![
+            file("/r/logs/app.log", 100),
+            file("/r/logs/old/app.log", 50),
+            file("/r/src/main.rs", 10),
+            file("/r/README", 7), // no extension - not counted
+        ];
+
+        let stats = dir_extension_stats(&nodes, root);
+
+        // The root sees the whole subtree's totals.
+        let root_log = stats[Path::new("/r")]["log"];
+        assert_eq!(root_log.count, 2);
+        assert_eq!(root_log.bytes, 150);
+        assert_eq!(stats[Path::new("/r")]["rs"].count, 1);
+
+        // logs/ rolls up its nested directory; src/ never hears about logs.
+        assert_eq!(stats[Path::new("/r/logs")]["log"].bytes, 150);
+        assert_eq!(stats[Path::new("/r/logs/old")]["log"].bytes, 50);
+        assert!(!stats[Path::new("/r/src")].contains_key("log"));
+
+        // Extensionless files appear nowhere.
+        assert!(stats.values().all(|exts| !exts.contains_key("README")));
+    }
+}
@@ -40,6 +40,24 @@ impl SummaryFormatter {
         }
     }
 
+    /// One line on what the ignore rules pruned - silent when nothing was
+    /// skipped so clean scans stay clean.
+    fn write_pruned_line(&self, writer: &mut dyn Write, stats: &TreeStats) -> Result<()> {
+        if stats.pruned.is_empty() {
+            return Ok(());
+        }
+        let entries: u64 = stats.pruned.values().map(|c| c.entries).sum();
+        let bytes: u64 = stats.pruned.values().map(|c| c.bytes).sum();
+        writeln!(
+            writer,
+            "🙈 {}: {} entries ({}) hidden by ignore rules",
+            self.colorize("Pruned", "cyan"),
+            self.colorize(&entries.to_string(), "yellow"),
+            self.colorize(&format_size(bytes), "yellow")
+        )?;
+        Ok(())
+    }
+
     fn is_high_level_directory(&self, nodes: &[FileNode], _stats: &TreeStats) -> bool {
         // Heuristics for detecting high-level directories:
         // 1. More than 20 subdirectories in root
@@ -146,6 +164,7 @@ impl SummaryFormatter {
             self.colorize(&stats.total_dirs.to_string(), "green"),
             self.colorize(&format_size(stats.total_size), "green")
         )?;
+        self.write_pruned_line(writer, stats)?;
         writeln!(writer)?;
 
         // Analyze subdirectories (skip root-level files)
@@ -335,6 +354,7 @@ impl Formatter for SummaryFormatter {
             self.colorize(&stats.total_dirs.to_string(), "green"),
             self.colorize(&format_size(stats.total_size), "green")
         )?;
+        self.write_pruned_line(writer, stats)?;
         writeln!(writer)?;
 
         // Content-specific analysis
@@ -708,6 +728,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
             FileNode {
                 path: PathBuf::from("/test/Cargo.toml"),
@@ -732,6 +755,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
             FileNode {
                 path: PathBuf::from("/test/src"),
@@ -756,6 +782,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
         ]
     }
@@ -772,6 +801,13 @@ mod tests {
             largest_files: vec![],
             newest_files: vec![],
             oldest_files: vec![],
+            disk_usage: 0,
+            hardlink_duplicates: 0,
+            seen_inodes: std::collections::HashSet::new(),
+            allocated_size: 0,
+            sparse_files: 0,
+            pruned: HashMap::new(),
+            truncated: false,
         };
 
         let mut output = Vec::new();
@@ -814,6 +850,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             });
         }
 
@@ -825,6 +864,13 @@ mod tests {
             largest_files: vec![],
             newest_files: vec![],
             oldest_files: vec![],
+            disk_usage: 0,
+            hardlink_duplicates: 0,
+            seen_inodes: std::collections::HashSet::new(),
+            allocated_size: 0,
+            sparse_files: 0,
+            pruned: HashMap::new(),
+            truncated: false,
         };
 
         let is_high_level = formatter.is_high_level_directory(&nodes, &stats);
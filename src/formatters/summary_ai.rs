@@ -12,11 +12,22 @@ use std::path::Path;
 pub struct SummaryAiFormatter {
     #[allow(dead_code)]
     compress: bool,
+    /// Emit per-language line counts (--loc flag)
+    loc: bool,
 }
 
 impl SummaryAiFormatter {
     pub fn new(compress: bool) -> Self {
-        Self { compress }
+        Self {
+            compress,
+            loc: false,
+        }
+    }
+
+    /// Add a LOC: line with per-language code/comment/blank counts.
+    pub fn with_loc(mut self, loc: bool) -> Self {
+        self.loc = loc;
+        self
     }
 }
 
@@ -168,6 +179,30 @@ impl Formatter for SummaryAiFormatter {
             }
         }
 
+        // Per-language line counts, hex like the rest of the summary
+        // (opt-in: it reads file contents)
+        if self.loc {
+            let totals = crate::loc_counter::tally_by_language(nodes);
+            if !totals.is_empty() {
+                write!(writer, "LOC:")?;
+                for (i, lang) in totals.iter().enumerate() {
+                    if i > 0 {
+                        write!(writer, ",")?;
+                    }
+                    write!(
+                        writer,
+                        "{}[F{:x},C{:x},M{:x},B{:x}]",
+                        lang.language,
+                        lang.files,
+                        lang.lines.code,
+                        lang.lines.comments,
+                        lang.lines.blanks
+                    )?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
         // Structure summary - top-level directories
         let mut dir_sizes: HashMap<String, (usize, u64)> = HashMap::new();
         for node in nodes {
@@ -300,6 +335,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
             FileNode {
                 path: PathBuf::from("/test/Cargo.toml"),
@@ -324,6 +362,9 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
             },
         ];
 
@@ -335,6 +376,13 @@ mod tests {
             largest_files: vec![],
             newest_files: vec![],
             oldest_files: vec![],
+            disk_usage: 0,
+            hardlink_duplicates: 0,
+            seen_inodes: std::collections::HashSet::new(),
+            allocated_size: 0,
+            sparse_files: 0,
+            pruned: HashMap::new(),
+            truncated: false,
         };
 
         let mut output = Vec::new();
@@ -0,0 +1,233 @@
+// GNU tree(1) byte-compatible rendering - the landing pad for `--compat tree`.
+//
+// Scripts that parse `tree` output are surprisingly common (and surprisingly
+// brittle), so this formatter reproduces the classic cases byte for byte:
+// the root line as given, "├── "/"└── " connectors with "│   " continuation,
+// pure C-locale alphabetical ordering (no dirs-first unless --dirsfirst),
+// "name -> target" for symlinks, and the trailing "N directories, M files"
+// report. No emoji, no color, no cleverness - that's what `st` itself is for.
+
+use super::Formatter;
+use crate::scanner::{FileNode, TreeStats};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub struct TreeCompatFormatter {
+    /// tree's --dirsfirst: directories before files at every level
+    dirs_first: bool,
+}
+
+impl TreeCompatFormatter {
+    pub fn new(dirs_first: bool) -> Self {
+        TreeCompatFormatter { dirs_first }
+    }
+
+    /// File name exactly as tree prints it: the final component, with
+    /// `name -> target` for symlinks (resolved at render time, like tree).
+    fn entry_name(node: &FileNode) -> String {
+        let name = node
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| node.path.display().to_string());
+
+        if node.is_symlink {
+            if let Ok(target) = std::fs::read_link(&node.path) {
+                return format!("{} -> {}", name, target.display());
+            }
+        }
+        name
+    }
+
+    /// Recursively print one directory level with tree's connector set.
+    fn write_level(
+        &self,
+        writer: &mut dyn Write,
+        parent: &Path,
+        children_map: &HashMap<PathBuf, Vec<usize>>,
+        nodes: &[FileNode],
+        prefix: &str,
+    ) -> Result<()> {
+        let Some(children) = children_map.get(parent) else {
+            return Ok(());
+        };
+
+        for (i, &idx) in children.iter().enumerate() {
+            let node = &nodes[idx];
+            let is_last = i == children.len() - 1;
+            let connector = if is_last { "└── " } else { "├── " };
+            writeln!(writer, "{}{}{}", prefix, connector, Self::entry_name(node))?;
+
+            if node.is_dir {
+                let extension = if is_last { "    " } else { "│   " };
+                self.write_level(
+                    writer,
+                    &node.path,
+                    children_map,
+                    nodes,
+                    &format!("{prefix}{extension}"),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Formatter for TreeCompatFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        nodes: &[FileNode],
+        _stats: &TreeStats,
+        root_path: &Path,
+    ) -> Result<()> {
+        // Group children under their parent directory. tree sorts each
+        // level with strcoll; in the C locale that is plain byte order,
+        // which is what byte-compatibility tests run under.
+        let mut children_map: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        for (i, node) in nodes.iter().enumerate() {
+            if node.path == root_path {
+                continue; // the root is the header line, not an entry
+            }
+            if let Some(parent) = node.path.parent() {
+                children_map.entry(parent.to_path_buf()).or_default().push(i);
+            }
+        }
+        for children in children_map.values_mut() {
+            children.sort_by(|&a, &b| {
+                let dir_order = if self.dirs_first {
+                    nodes[b].is_dir.cmp(&nodes[a].is_dir)
+                } else {
+                    std::cmp::Ordering::Equal
+                };
+                dir_order.then_with(|| {
+                    nodes[a]
+                        .path
+                        .file_name()
+                        .unwrap_or_default()
+                        .cmp(nodes[b].path.file_name().unwrap_or_default())
+                })
+            });
+        }
+
+        writeln!(writer, "{}", root_path.display())?;
+        self.write_level(writer, root_path, &children_map, nodes, "")?;
+
+        // tree's report counts what was displayed, excluding the root,
+        // and uses the singular forms for exactly one of either.
+        let dirs = nodes
+            .iter()
+            .filter(|n| n.is_dir && n.path != root_path)
+            .count();
+        let files = nodes
+            .iter()
+            .filter(|n| !n.is_dir && n.path != root_path)
+            .count();
+        writeln!(
+            writer,
+            "\n{} {}, {} {}",
+            dirs,
+            if dirs == 1 { "directory" } else { "directories" },
+            files,
+            if files == 1 { "file" } else { "files" }
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::time::SystemTime;
+
+    fn node(path: &str, is_dir: bool, depth: usize) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size: 0,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth,
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    fn render(formatter: &TreeCompatFormatter, nodes: &[FileNode], root: &str) -> String {
+        let mut output = Vec::new();
+        formatter
+            .format(&mut output, nodes, &TreeStats::default(), Path::new(root))
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_byte_compatible_classic_case() {
+        let nodes = vec![
+            node("/proj", true, 0),
+            node("/proj/README.md", false, 1),
+            node("/proj/src", true, 1),
+            node("/proj/src/lib.rs", false, 2),
+            node("/proj/src/main.rs", false, 2),
+        ];
+        let output = render(&TreeCompatFormatter::new(false), &nodes, "/proj");
+
+        // Exactly what GNU tree prints for this layout in the C locale.
+        assert_eq!(
+            output,
+            "/proj\n\
+             ├── README.md\n\
+             └── src\n\
+            \u{20}   ├── lib.rs\n\
+            \u{20}   └── main.rs\n\
+             \n\
+             1 directory, 3 files\n"
+        );
+    }
+
+    #[test]
+    fn test_dirsfirst_reorders_levels() {
+        let nodes = vec![
+            node("/proj", true, 0),
+            node("/proj/aaa.txt", false, 1),
+            node("/proj/zzz", true, 1),
+        ];
+        let plain = render(&TreeCompatFormatter::new(false), &nodes, "/proj");
+        let dirsfirst = render(&TreeCompatFormatter::new(true), &nodes, "/proj");
+
+        assert!(plain.find("aaa.txt").unwrap() < plain.find("zzz").unwrap());
+        assert!(dirsfirst.find("zzz").unwrap() < dirsfirst.find("aaa.txt").unwrap());
+    }
+
+    #[test]
+    fn test_singular_report_forms() {
+        let nodes = vec![node("/proj", true, 0), node("/proj/only.rs", false, 1)];
+        let output = render(&TreeCompatFormatter::new(false), &nodes, "/proj");
+        assert!(output.ends_with("0 directories, 1 file\n"));
+    }
+}
@@ -17,6 +17,19 @@ impl TsvFormatter {
     pub fn new() -> Self {
         Self
     }
+
+    /// Backslash-escape the characters that would break a TSV row - tabs
+    /// and newlines are legal in filenames, sadly.
+    fn escape_field(field: &str) -> String {
+        if !field.contains(['\t', '\n', '\r', '\\']) {
+            return field.to_string();
+        }
+        field
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    }
 }
 
 impl Formatter for TsvFormatter {
@@ -54,7 +67,7 @@ impl Formatter for TsvFormatter {
             writeln!(
                 writer,
                 "{}\t{}\t{}\t{:o}\t{}\t{}\t{}\t{}",
-                rel_path,
+                Self::escape_field(&rel_path),
                 file_type,
                 node.size,
                 node.permissions,
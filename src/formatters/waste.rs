@@ -15,6 +15,7 @@
 // -----------------------------------------------------------------------------
 
 use super::Formatter;
+use crate::content_hasher::{ContentHasher, DuplicateGroup, HashAlgorithm};
 use crate::scanner::{FileNode, TreeStats};
 use anyhow::Result;
 use humansize::{format_size, BINARY};
@@ -30,6 +31,8 @@ pub struct WasteFormatter {
     pub large_file_threshold: u64,
     /// Maximum number of duplicates to show per group
     pub max_duplicates_shown: usize,
+    /// Hash algorithm used to verify duplicate content (default: blake3)
+    pub hash_algorithm: HashAlgorithm,
 }
 
 impl Default for WasteFormatter {
@@ -44,9 +47,15 @@ impl WasteFormatter {
             show_suggestions: true,
             large_file_threshold: 10 * 1024 * 1024, // 10MB
             max_duplicates_shown: 5,
+            hash_algorithm: HashAlgorithm::default(),
         }
     }
 
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = algorithm;
+        self
+    }
+
     pub fn with_threshold(mut self, threshold: u64) -> Self {
         self.large_file_threshold = threshold;
         self
@@ -57,19 +66,11 @@ impl WasteFormatter {
         self
     }
 
-    /// Analyze files for potential duplicates based on size and name patterns
-    fn analyze_duplicates<'a>(&self, nodes: &'a [FileNode]) -> HashMap<u64, Vec<&'a FileNode>> {
-        let mut size_groups: HashMap<u64, Vec<&FileNode>> = HashMap::new();
-
-        for node in nodes {
-            if !node.is_dir && node.size > 0 && !node.permission_denied {
-                size_groups.entry(node.size).or_default().push(node);
-            }
-        }
-
-        // Only keep groups with multiple files of the same size
-        size_groups.retain(|_, files| files.len() > 1);
-        size_groups
+    /// Analyze files for duplicates, verified by content hash.
+    /// Size grouping alone gives false positives (two different 4KB configs
+    /// are not duplicates!), so the ContentHasher confirms actual content.
+    fn analyze_duplicates(&self, nodes: &[FileNode]) -> Vec<DuplicateGroup> {
+        ContentHasher::new(self.hash_algorithm).find_duplicate_groups(nodes)
     }
 
     /// Detect common build artifacts and temporary files
@@ -171,17 +172,15 @@ impl WasteFormatter {
     /// Calculate potential space savings
     fn calculate_savings(
         &self,
-        duplicates: &HashMap<u64, Vec<&FileNode>>,
+        duplicates: &[DuplicateGroup],
         build_artifacts: &[&FileNode],
         _large_files: &[&FileNode],
     ) -> u64 {
         let mut total_savings = 0u64;
 
         // Savings from duplicate removal (keep one, remove others)
-        for (size, files) in duplicates {
-            if files.len() > 1 {
-                total_savings += size * (files.len() - 1) as u64;
-            }
+        for group in duplicates {
+            total_savings += group.wasted_bytes();
         }
 
         // Savings from build artifact cleanup (conservative estimate: 70%)
@@ -194,7 +193,7 @@ impl WasteFormatter {
     /// Generate cleanup suggestions
     fn generate_suggestions(
         &self,
-        duplicates: &HashMap<u64, Vec<&FileNode>>,
+        duplicates: &[DuplicateGroup],
         build_artifacts: &[&FileNode],
         dependency_waste: &HashMap<String, Vec<&FileNode>>,
         _root_path: &Path,
@@ -272,9 +271,8 @@ impl Formatter for WasteFormatter {
 
         // Calculate total waste and potential savings
         let total_waste_size: u64 = duplicates
-            .values()
-            .flat_map(|files| files.iter())
-            .map(|node| node.size)
+            .iter()
+            .map(|group| group.size * group.paths.len() as u64)
             .sum::<u64>()
             + build_artifacts.iter().map(|node| node.size).sum::<u64>();
 
@@ -287,6 +285,26 @@ impl Formatter for WasteFormatter {
             "├── Total Project Size: {}",
             format_size(stats.total_size, BINARY)
         )?;
+        // With --du the allocated total is what cleanup can actually
+        // reclaim - sparse files make the logical size a lie
+        if stats.allocated_size != stats.total_size || stats.sparse_files > 0 {
+            writeln!(
+                writer,
+                "├── Allocated on Disk (du): {} ({} sparse files)",
+                format_size(stats.allocated_size, BINARY),
+                stats.sparse_files
+            )?;
+        }
+        // With --dedupe-hardlinks the apparent size overstates reality -
+        // show what the tree actually occupies on disk
+        if stats.hardlink_duplicates > 0 {
+            writeln!(
+                writer,
+                "├── On-Disk Size (hard links deduped): {} ({} entries share inodes)",
+                format_size(stats.disk_usage, BINARY),
+                stats.hardlink_duplicates
+            )?;
+        }
         writeln!(
             writer,
             "├── Potential Waste: {} ({:.1}% of project)",
@@ -309,34 +327,36 @@ impl Formatter for WasteFormatter {
         )?;
         writeln!(writer)?;
 
-        // Duplicate files analysis
+        // Duplicate files analysis (content-verified, not just same-size!)
         if !duplicates.is_empty() {
-            writeln!(writer, "🔄 DUPLICATE FILES DETECTED:")?;
-            let mut sorted_duplicates: Vec<_> = duplicates.iter().collect();
-            sorted_duplicates
-                .sort_by(|a, b| (b.1.len() * *b.0 as usize).cmp(&(a.1.len() * *a.0 as usize)));
-
-            for (size, files) in sorted_duplicates.iter().take(10) {
+            writeln!(
+                writer,
+                "🔄 DUPLICATE FILES DETECTED (verified by {}):",
+                self.hash_algorithm.as_str()
+            )?;
+            // Groups arrive sorted by wasted bytes - biggest offenders first
+            for group in duplicates.iter().take(10) {
                 writeln!(
                     writer,
                     "├── {} files of size {} each:",
-                    files.len(),
-                    format_size(**size, BINARY)
+                    group.paths.len(),
+                    format_size(group.size, BINARY)
                 )?;
-                for (i, file) in files.iter().take(self.max_duplicates_shown).enumerate() {
-                    let rel_path = file.path.strip_prefix(root_path).unwrap_or(&file.path);
-                    let prefix = if i == files.len() - 1 || i == self.max_duplicates_shown - 1 {
+                for (i, path) in group.paths.iter().take(self.max_duplicates_shown).enumerate() {
+                    let rel_path = path.strip_prefix(root_path).unwrap_or(path);
+                    let prefix = if i == group.paths.len() - 1 || i == self.max_duplicates_shown - 1
+                    {
                         "└──"
                     } else {
                         "├──"
                     };
                     writeln!(writer, "│   {} {}", prefix, rel_path.display())?;
                 }
-                if files.len() > self.max_duplicates_shown {
+                if group.paths.len() > self.max_duplicates_shown {
                     writeln!(
                         writer,
                         "│   └── ... and {} more",
-                        files.len() - self.max_duplicates_shown
+                        group.paths.len() - self.max_duplicates_shown
                     )?;
                 }
             }
@@ -490,36 +510,23 @@ mod tests {
     fn test_duplicate_detection() {
         let formatter = WasteFormatter::new();
 
-        // Create test nodes with same size
-        let nodes = vec![
-            FileNode {
-                path: PathBuf::from("/test/file1.txt"),
-                is_dir: false,
-                size: 1024,
-                permissions: 644,
-                uid: 1000,
-                gid: 1000,
-                modified: SystemTime::now(),
-                is_symlink: false,
-                is_hidden: false,
-                permission_denied: false,
-                is_ignored: false,
-                depth: 1,
-                file_type: FileType::RegularFile,
-                category: FileCategory::Markdown,
-                search_matches: None,
-                filesystem_type: FilesystemType::Ext4,
-                git_branch: None,
-                traversal_context: None,
-                interest: None,
-                security_findings: Vec::new(),
-                change_status: None,
-                content_hash: None,
-            },
-            FileNode {
-                path: PathBuf::from("/test/file2.txt"),
+        // Real files on disk - duplicates are now verified by content hash,
+        // so same-size-different-content must NOT count as a duplicate.
+        let dir = std::env::temp_dir().join(format!("st_waste_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file1 = dir.join("file1.txt");
+        let file2 = dir.join("file2.txt");
+        let file3 = dir.join("file3.txt");
+        std::fs::write(&file1, "identical bytes!").unwrap();
+        std::fs::write(&file2, "identical bytes!").unwrap();
+        std::fs::write(&file3, "different bytes!").unwrap(); // same size, other content
+
+        let nodes: Vec<FileNode> = [&file1, &file2, &file3]
+            .iter()
+            .map(|path| FileNode {
+                path: path.to_path_buf(),
                 is_dir: false,
-                size: 1024, // Same size as file1
+                size: 16,
                 permissions: 644,
                 uid: 1000,
                 gid: 1000,
@@ -530,7 +537,7 @@ mod tests {
                 is_ignored: false,
                 depth: 1,
                 file_type: FileType::RegularFile,
-                category: FileCategory::Markdown,
+                category: FileCategory::Txt,
                 search_matches: None,
                 filesystem_type: FilesystemType::Ext4,
                 git_branch: None,
@@ -539,12 +546,18 @@ mod tests {
                 security_findings: Vec::new(),
                 change_status: None,
                 content_hash: None,
-            },
-        ];
+                hardlink_id: None,
+                allocated_size: None,
+                xattrs: None,
+            })
+            .collect();
 
         let duplicates = formatter.analyze_duplicates(&nodes);
         assert_eq!(duplicates.len(), 1);
-        assert_eq!(duplicates.get(&1024).unwrap().len(), 2);
+        assert_eq!(duplicates[0].paths.len(), 2);
+        assert_eq!(duplicates[0].size, 16);
+
+        std::fs::remove_dir_all(&dir).ok();
     }
 
     #[test]
@@ -574,6 +587,9 @@ mod tests {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         }];
 
         let artifacts = formatter.analyze_build_artifacts(&nodes);
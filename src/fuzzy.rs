@@ -0,0 +1,138 @@
+// -----------------------------------------------------------------------------
+// 🔍 Fuzzy - fzf-style ranked filename matching, one module, three callers
+// -----------------------------------------------------------------------------
+// `st --fuzzy PATTERN` prints ranked matches, the TUI explorer uses the same
+// ranking for its live picker, and MCP find_files accepts `fuzzy: true` -
+// all through these two functions, so scoring never drifts between entry
+// points. The scoring itself is SkimMatcherV2 (subsequence matching with
+// start-of-word and consecutive-run bonuses), the same algorithm the spicy
+// content search already trusts.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::FileNode;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::path::Path;
+
+/// Score one candidate against a pattern, with the matched character
+/// positions for highlighting. None means the pattern is not a
+/// subsequence of the candidate at all.
+pub fn score(pattern: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let matcher = SkimMatcherV2::default().smart_case();
+    matcher.fuzzy_indices(candidate, pattern)
+}
+
+/// Rank scan results against a pattern: `(score, index into nodes)`,
+/// best match first. Candidates are root-relative paths, so `srcmain`
+/// finds `src/main.rs` the way fzf would. The scan root itself never
+/// competes. Ties break by path for stable output.
+pub fn rank_nodes(nodes: &[FileNode], root: &Path, pattern: &str) -> Vec<(i64, usize)> {
+    let matcher = SkimMatcherV2::default().smart_case();
+    let mut hits: Vec<(i64, usize)> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.path != root)
+        .filter_map(|(i, node)| {
+            let rel = node.path.strip_prefix(root).unwrap_or(&node.path);
+            matcher
+                .fuzzy_match(&rel.to_string_lossy(), pattern)
+                .map(|score| {
+                    // An exact filename (or stem) hit outranks a run that
+                    // merely starts inside some longer name - `main` should
+                    // find main.rs before maintenance.md, like fzf does.
+                    let exact = node
+                        .path
+                        .file_stem()
+                        .map(|s| s.eq_ignore_ascii_case(pattern))
+                        .unwrap_or(false)
+                        || node
+                            .path
+                            .file_name()
+                            .map(|s| s.eq_ignore_ascii_case(pattern))
+                            .unwrap_or(false);
+                    (if exact { score + 128 } else { score }, i)
+                })
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| nodes[a.1].path.cmp(&nodes[b.1].path))
+    });
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn node(path: &str) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir: false,
+            size: 0,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth: 2,
+            file_type: FileType::RegularFile,
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_nodes_orders_best_first() {
+        let root = Path::new("/r");
+        let nodes = vec![
+            node("/r/src/main.rs"),
+            node("/r/docs/maintenance.md"),
+            node("/r/Cargo.toml"),
+        ];
+
+        let hits = rank_nodes(&nodes, root, "main");
+
+        // Both main-ish paths match; Cargo.toml does not.
+        assert_eq!(hits.len(), 2);
+        // The exact filename hit outranks the scattered subsequence.
+        assert_eq!(nodes[hits[0].1].path, PathBuf::from("/r/src/main.rs"));
+        assert!(hits[0].0 >= hits[1].0);
+    }
+
+    #[test]
+    fn test_rank_nodes_skips_root_and_non_matches() {
+        let root = Path::new("/r");
+        let mut root_node = node("/r");
+        root_node.is_dir = true;
+        let nodes = vec![root_node, node("/r/notes.txt")];
+
+        assert!(rank_nodes(&nodes, root, "zzz").is_empty());
+        // The root entry never competes, even when the pattern would hit it.
+        let hits = rank_nodes(&nodes, root, "notes");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].1, 1);
+    }
+
+    #[test]
+    fn test_score_returns_match_positions() {
+        let (_, indices) = score("mn", "main.rs").expect("subsequence should match");
+        assert_eq!(indices, vec![0, 3]);
+    }
+}
@@ -0,0 +1,288 @@
+//! Git-aware tree annotations (`--git-status`, `--git-blame-summary`)
+//!
+//! The scanner already respects .gitignore; this layer adds what git itself
+//! knows about each entry. Everything is gathered up front - one
+//! `git status --porcelain` call per scan, plus one bounded `git log` walk
+//! when the blame summary is requested - and cached in maps keyed by
+//! absolute path. Formatters then decorate entries with pure lookups;
+//! there is never a subprocess per file.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Commits examined for the blame summary - enough history for active
+/// trees without walking ten years of log on every scan
+const LOG_COMMIT_LIMIT: usize = 2000;
+
+/// Author and time of the last commit touching a file
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    pub author: String,
+    pub unix_time: i64,
+}
+
+/// Per-path git state for one repository, gathered once per scan
+pub struct GitAnnotations {
+    status: HashMap<PathBuf, String>,
+    last_commit: HashMap<PathBuf, CommitInfo>,
+}
+
+impl GitAnnotations {
+    /// Gather annotations for the repository containing `root`, if any
+    ///
+    /// Returns None when `root` is not inside a git work tree (or git is
+    /// not installed) - callers simply skip decoration then.
+    pub fn gather(root: &Path, with_blame: bool) -> Option<Self> {
+        let repo_root = find_repo_root(root)?;
+        let status = gather_status(&repo_root).unwrap_or_default();
+        let last_commit = if with_blame {
+            gather_last_commits(&repo_root).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        Some(Self {
+            status,
+            last_commit,
+        })
+    }
+
+    /// Porcelain status code for a path: "M", "A", "D", "??", "R", ...
+    pub fn status_code(&self, path: &Path) -> Option<&str> {
+        self.status.get(path).map(|code| code.as_str())
+    }
+
+    /// Last commit touching this path, when the log walk found one
+    pub fn last_commit(&self, path: &Path) -> Option<&CommitInfo> {
+        self.last_commit.get(path)
+    }
+
+    /// "3d ago by alice" - ready to append to a tree entry
+    pub fn blame_summary(&self, path: &Path) -> Option<String> {
+        let info = self.last_commit.get(path)?;
+        Some(format!("{} by {}", age_string(info.unix_time), info.author))
+    }
+}
+
+/// The set of paths in one git state, for `--git-filter`
+///
+/// Like [`GitAnnotations`] this is gathered once per scan - a single
+/// `git ls-files` call with mode-specific flags - and then consulted with
+/// pure hash lookups while filtering.
+pub struct GitFileSet {
+    paths: HashSet<PathBuf>,
+}
+
+impl GitFileSet {
+    /// Gather every path in `mode` ("tracked", "untracked", "ignored",
+    /// "modified") for the repository containing `root`
+    ///
+    /// Returns None when `root` is not inside a git work tree, git is not
+    /// installed, or the mode is unknown - callers skip filtering then.
+    pub fn gather(root: &Path, mode: &str) -> Option<Self> {
+        let repo_root = find_repo_root(root)?;
+        // ls-files enumerates individual files even inside untracked
+        // directories, which porcelain status collapses to "dir/"
+        let args: &[&str] = match mode {
+            "tracked" => &["ls-files", "-z", "--cached"],
+            "untracked" => &["ls-files", "-z", "--others", "--exclude-standard"],
+            "ignored" => &["ls-files", "-z", "--others", "--ignored", "--exclude-standard"],
+            "modified" => &["ls-files", "-z", "--modified"],
+            _ => return None,
+        };
+        let output = crate::exec_policy::command("git").ok()?
+            .arg("-C")
+            .arg(&repo_root)
+            .args(args)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(Self {
+            paths: parse_ls_files(&String::from_utf8_lossy(&output.stdout), &repo_root),
+        })
+    }
+
+    /// Is this absolute path in the gathered state?
+    pub fn contains(&self, path: &Path) -> bool {
+        self.paths.contains(path)
+    }
+}
+
+/// Parse NUL-delimited `git ls-files -z` output into absolute paths
+fn parse_ls_files(raw: &str, repo_root: &Path) -> HashSet<PathBuf> {
+    raw.split('\0')
+        .filter(|rel| !rel.is_empty())
+        .map(|rel| repo_root.join(rel))
+        .collect()
+}
+
+/// Walk up from `start` to the directory holding `.git`
+fn find_repo_root(start: &Path) -> Option<PathBuf> {
+    let mut dir = if start.is_dir() { start } else { start.parent()? };
+    loop {
+        if dir.join(".git").exists() {
+            return Some(dir.to_path_buf());
+        }
+        dir = dir.parent()?;
+    }
+}
+
+/// One `git status --porcelain -z` call, parsed into a path -> code map
+fn gather_status(repo_root: &Path) -> Result<HashMap<PathBuf, String>> {
+    let output = crate::exec_policy::command("git")?
+        .arg("-C")
+        .arg(repo_root)
+        .args(["status", "--porcelain", "-z"])
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("git status failed in {}", repo_root.display());
+    }
+    Ok(parse_porcelain(
+        &String::from_utf8_lossy(&output.stdout),
+        repo_root,
+    ))
+}
+
+/// Parse NUL-delimited porcelain v1 output
+fn parse_porcelain(raw: &str, repo_root: &Path) -> HashMap<PathBuf, String> {
+    let mut map = HashMap::new();
+    let mut fields = raw.split('\0');
+    while let Some(entry) = fields.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let code = entry[..2].trim().to_string();
+        let rel = &entry[3..];
+        // Renames and copies carry the old name in the next NUL field
+        if code.starts_with('R') || code.starts_with('C') {
+            let _ = fields.next();
+        }
+        map.insert(repo_root.join(rel), code);
+    }
+    map
+}
+
+/// One bounded `git log --name-only` walk: newest-first, so the first
+/// sighting of a path is its most recent commit
+fn gather_last_commits(repo_root: &Path) -> Result<HashMap<PathBuf, CommitInfo>> {
+    let output = crate::exec_policy::command("git")?
+        .arg("-C")
+        .arg(repo_root)
+        .args(["-c", "core.quotepath=off"])
+        .args(["log", "--name-only", "--format=%x01%at%x09%an", "-n"])
+        .arg(LOG_COMMIT_LIMIT.to_string())
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("git log failed in {}", repo_root.display());
+    }
+    Ok(parse_log(
+        &String::from_utf8_lossy(&output.stdout),
+        repo_root,
+    ))
+}
+
+/// Parse `--format=%x01%at%x09%an --name-only` output
+fn parse_log(raw: &str, repo_root: &Path) -> HashMap<PathBuf, CommitInfo> {
+    let mut map = HashMap::new();
+    let mut current: Option<CommitInfo> = None;
+    for line in raw.lines() {
+        if let Some(header) = line.strip_prefix('\u{1}') {
+            current = header.split_once('\t').map(|(time, author)| CommitInfo {
+                unix_time: time.parse().unwrap_or(0),
+                author: author.to_string(),
+            });
+        } else if !line.is_empty() {
+            if let Some(info) = &current {
+                map.entry(repo_root.join(line))
+                    .or_insert_with(|| info.clone());
+            }
+        }
+    }
+    map
+}
+
+/// Compact "how long ago" rendering: 42s, 5m, 3h, 12d, 2y
+fn age_string(unix_time: i64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let seconds = (now - unix_time).max(0);
+    if seconds < 60 {
+        format!("{}s ago", seconds)
+    } else if seconds < 3600 {
+        format!("{}m ago", seconds / 60)
+    } else if seconds < 86400 {
+        format!("{}h ago", seconds / 3600)
+    } else if seconds < 365 * 86400 {
+        format!("{}d ago", seconds / 86400)
+    } else {
+        format!("{}y ago", seconds / (365 * 86400))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let raw = " M src/main.rs\0?? notes.txt\0R  new.rs\0old.rs\0A  added.rs\0";
+        let map = parse_porcelain(raw, Path::new("/repo"));
+
+        assert_eq!(
+            map.get(Path::new("/repo/src/main.rs")).map(String::as_str),
+            Some("M")
+        );
+        assert_eq!(
+            map.get(Path::new("/repo/notes.txt")).map(String::as_str),
+            Some("??")
+        );
+        assert_eq!(
+            map.get(Path::new("/repo/new.rs")).map(String::as_str),
+            Some("R")
+        );
+        // The rename's old name is consumed, not treated as an entry
+        assert!(!map.contains_key(Path::new("/repo/old.rs")));
+        assert_eq!(
+            map.get(Path::new("/repo/added.rs")).map(String::as_str),
+            Some("A")
+        );
+    }
+
+    #[test]
+    fn test_parse_ls_files_absolute_paths() {
+        let raw = "src/main.rs\0notes.txt\0\0";
+        let set = parse_ls_files(raw, Path::new("/repo"));
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(Path::new("/repo/src/main.rs")));
+        assert!(set.contains(Path::new("/repo/notes.txt")));
+    }
+
+    #[test]
+    fn test_parse_log_first_sighting_wins() {
+        let raw = "\u{1}1700000100\talice\n\nsrc/main.rs\nREADME.md\n\n\u{1}1700000000\tbob\n\nsrc/main.rs\n";
+        let map = parse_log(raw, Path::new("/repo"));
+
+        let main = map.get(Path::new("/repo/src/main.rs")).unwrap();
+        assert_eq!(main.author, "alice");
+        assert_eq!(main.unix_time, 1700000100);
+        assert_eq!(map.get(Path::new("/repo/README.md")).unwrap().author, "alice");
+    }
+
+    #[test]
+    fn test_age_string_buckets() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        assert!(age_string(now - 30).ends_with("s ago"));
+        assert!(age_string(now - 300).ends_with("m ago"));
+        assert!(age_string(now - 7200).ends_with("h ago"));
+        assert!(age_string(now - 3 * 86400).ends_with("d ago"));
+    }
+}
@@ -257,6 +257,9 @@ fn convert_node(context: &ContextNode, nodes: &mut Vec<crate::FileNode>, depth:
         security_findings: Vec::new(),
         change_status: None,
         content_hash: None,
+        hardlink_id: None,
+        allocated_size: None,
+        xattrs: None,
     };
 
     nodes.push(node);
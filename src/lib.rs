@@ -18,30 +18,54 @@ pub mod cli; // Command-line argument definitions (extracted from main.rs)
 pub mod config; // Unified configuration: API keys, models, daemon settings
 pub mod compression_manager; // Smart global compression for all outputs
 pub mod content_detector; // Content type detection - "Understanding what's in your directories" - Omni
+pub mod content_hasher; // Content hashing for duplicate verification - blake3/sha256/xxhash
+pub mod content_search; // Memory-mapped, memchr-powered keyword search behind --search
 pub mod context;
 pub mod decoders; // Decoders to convert quantum format to other representations
+pub mod display_overrides; // Per-directory .st/display.toml curation (collapse, emoji, annotation)
+pub mod doctor; // `st doctor` - environment checkup with actionable fixes
 pub mod dynamic_tokenizer;
+pub mod exec_policy; // Kill-switch (--no-exec) and allowlist gating every shell-out
 pub mod feature_flags; // Enterprise-friendly feature control and compliance
 pub mod formatters; // Home to all the different ways we can display the tree (Classic, JSON, AI, etc.).
+pub mod fuzzy; // fzf-style ranked filename matching (--fuzzy, TUI picker, MCP fuzzy:true)
+pub mod git_status; // Git status/blame annotations for tree entries (--git-status)
 pub mod inputs; // 🌊 Universal input adapters - QCP, SSE, OpenAPI, MEM8, and more!
+pub mod loc_counter; // tokei-style code/comment/blank line counting (--loc)
+pub mod ls_colors; // LS_COLORS/dircolors parsing - match the user's real ls palette
 pub mod m8_backwards_reader; // Backwards reading - C64 tape style!
 pub mod m8_context_aware; // Context-aware progressive loading
+pub mod media_info; // header-only media metadata probing (--media-info)
 pub mod mega_session_manager; // Mega session persistence in ~/.mem8/
 pub mod memory_manager; // Real memory management for consciousness!
 pub mod quantum_scanner; // The native quantum format tree walker - no intermediate representation!
 pub mod relations; // Code relationship analyzer - "Semantic X-ray vision for codebases" - Omni
+pub mod result_cache; // Cross-invocation scan cache in ~/.st/cache - unchanged tree, instant replay
 pub mod scanner; // The heart of directory traversal and file metadata collection. // For intelligently detecting project context (e.g., Rust, Node.js).
+pub mod live_watch; // `st --watch` - live-updating classic/ls tree (no re-scan per event!)
+pub mod scanner_archive; // Archive expansion - virtual subtrees for zips, tarballs, jars, wheels
+pub mod scanner_events; // Lifecycle hooks (on_enter_dir/on_file/on_error/on_complete) for embedders
 pub mod scanner_interest; // Interest scoring - surfacing what matters
 pub mod scanner_safety; // Safety mechanisms to prevent crashes on large directories
+pub mod token_budget; // Fit rendered output inside an LLM context allowance
 pub mod scanner_state; // Change detection between scans
+pub mod scanner_stream; // Ordered output stage (bounded reordering) for streamed nodes
+pub mod scanner_windows; // Windows-native: junctions, alternate data streams, volume detection
+pub mod search_index; // Persistent trigram index for instant repeated --search queries
 pub mod interest_calculator; // The scoring engine that determines what's interesting
 pub mod hot_watcher; // Wave-powered real-time directory intelligence (MEM8)
 pub mod semantic; // Semantic analysis inspired by Omni's wave-based wisdom!
+pub mod shared_cache; // Multi-user cache with per-user namespaces, locking, and quotas
 pub mod smart; // 🧠 Smart Tools - Context-aware AI collaboration features with 70-90% token reduction!
+#[cfg(feature = "parquet")]
+pub mod parquet_export; // `st --mode parquet --output scan.parquet` - columnar export for Spark and friends
+pub mod sqlite_export; // `st --mode sqlite --output scan.db` - ad-hoc SQL over scan results
 pub mod terminal; // 🚀 Smart Tree Terminal Interface - Your coding companion that anticipates your needs!
+pub mod theme; // ~/.st/theme.toml palettes - categories, extensions, age rules, presets
 pub mod tokenizer; // Smart tokenization for semantic pattern recognition
 pub mod tree_sitter_quantum;
 pub mod universal_chat_scanner; // Finds conversations everywhere!
+pub mod wordcloud; // `st wordcloud` - what is this project ABOUT, as an SVG
 pub mod universal_format_detector; // Detects format by structure! // Semantic-aware quantum compression - "AST meets compression!" - Omni // Dynamic pattern learning - "Every project has its own language!" - Omni
 
 // The `mcp` module for Model Context Protocol integration.
@@ -143,6 +167,9 @@ pub mod mem8_lite;
 // Binary: `n8x` (formerly `tree`, renamed to avoid shadowing Unix tree)
 pub mod tree_agent;
 
+// TUI Explorer - `st --tui`, scanner-backed ncdu/broot replacement
+pub mod tui_explorer;
+
 // Context Gatherer - Searches AI tool directories for project context
 pub mod context_gatherer;
 
@@ -0,0 +1,463 @@
+// -----------------------------------------------------------------------------
+//  LIVE WATCH: The Tree That Repaints Itself! 📺🌲
+//
+//  `st --watch` keeps a classic (or ls) view on screen and updates it in
+//  place as files appear, change, and vanish. One initial scan builds the
+//  in-memory model; after that notify events run through the same
+//  EventAggregator the SSE watcher uses (--watch-debounce window), so
+//  atomic saves land as one change, renames move entries instead of
+//  delete+create, and a build dumping hundreds of artifacts into one
+//  directory collapses into a single subtree resync - no full re-scan
+//  either way. Recently-changed entries glow (inverse video) for a few
+//  seconds so your eye lands on what just happened. Trish calls it "the
+//  lava lamp of directory trees".
+//
+//  Press Ctrl+C to stop watching.
+// -----------------------------------------------------------------------------
+
+use crate::daemon_cli::{build_scanner_config, CliScanRequest};
+use crate::formatters::{
+    classic::ClassicFormatter, ls::LsFormatter, Formatter, PathDisplayMode,
+};
+use crate::mcp::unified_watcher::{AggregateEvent, EventAggregator};
+use crate::scanner::{FileNode, FileType, Scanner, TreeStats};
+use crate::scanner_interest::ChangeType;
+use anyhow::Result;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// How long a changed entry stays highlighted on screen
+const HIGHLIGHT_FOR: Duration = Duration::from_secs(4);
+
+/// How often the loop wakes up to expire highlights and flush the window
+const TICK: Duration = Duration::from_millis(200);
+
+/// More flushed events than this under one directory become a single
+/// subtree resync instead of entry-by-entry patches
+const DIR_STORM_THRESHOLD: usize = 16;
+
+/// Run the live-updating tree until Ctrl+C
+///
+/// Takes the same request the daemon would - so all the usual filters
+/// (depth, hidden, gitignore, patterns) apply to the watched view too.
+pub async fn run_live_watch(req: &CliScanRequest) -> Result<()> {
+    let mode = req.mode.to_lowercase();
+    if !matches!(mode.as_str(), "classic" | "ls") {
+        anyhow::bail!(
+            "--watch currently supports classic and ls modes (got '{}')",
+            req.mode
+        );
+    }
+
+    let root = std::fs::canonicalize(&req.path)?;
+    let config = build_scanner_config(req)?;
+    let show_hidden = config.show_hidden;
+
+    // One full scan seeds the model; everything after is incremental
+    let scanner = Scanner::new(&root, config)?;
+    let (nodes, _stats) = scanner.scan()?;
+    let mut model: BTreeMap<PathBuf, FileNode> = nodes
+        .into_iter()
+        .map(|n| (n.path.clone(), n))
+        .collect();
+
+    // Bridge notify's callback thread into tokio (same dance as the SSE server)
+    let (watch_tx, mut watch_rx) = mpsc::channel(256);
+    let mut watcher = RecommendedWatcher::new(
+        move |res: Result<Event, notify::Error>| {
+            if let Ok(event) = res {
+                let _ = watch_tx.blocking_send(event);
+            }
+        },
+        notify::Config::default(),
+    )?;
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+
+    // --watch-filter: only events in these semantic categories repaint
+    // (the initial tree is unfiltered - the filter fights churn, not layout)
+    let category_filter = crate::semantic::CategoryFilter::new(&req.watch_filter);
+
+    let mut highlights: HashMap<PathBuf, Instant> = HashMap::new();
+    redraw(req, &root, &model, &highlights)?;
+
+    // Same aggregator as the SSE watcher: per-path coalescing, rename
+    // pairing, and per-directory storm collapse inside each window
+    let mut aggregator = EventAggregator::new(Duration::from_millis(req.watch_debounce.max(1)))
+        .with_directory_coalescing(DIR_STORM_THRESHOLD);
+
+    loop {
+        tokio::select! {
+            maybe_event = watch_rx.recv() => {
+                match maybe_event {
+                    Some(mut event) => {
+                        event.paths.retain(|p| wants(&root, p, show_hidden, &category_filter));
+                        if !event.paths.is_empty() {
+                            aggregator.push(&event);
+                        }
+                    }
+                    None => break,
+                }
+            }
+            _ = tokio::time::sleep(TICK) => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        // Fold the rest of the burst into the same window before flushing
+        while let Ok(mut event) = watch_rx.try_recv() {
+            event.paths.retain(|p| wants(&root, p, show_hidden, &category_filter));
+            if !event.paths.is_empty() {
+                aggregator.push(&event);
+            }
+        }
+
+        let mut dirty = false;
+        if aggregator.ready() {
+            for change in aggregator.flush() {
+                dirty |= apply_change(&root, &mut model, &mut highlights, change, show_hidden);
+            }
+        }
+
+        // Expired highlights need one last repaint to fade out
+        let before = highlights.len();
+        highlights.retain(|_, seen| seen.elapsed() < HIGHLIGHT_FOR);
+
+        if dirty || highlights.len() != before {
+            redraw(req, &root, &model, &highlights)?;
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Should this event path repaint the tree at all?
+fn wants(
+    root: &Path,
+    path: &Path,
+    show_hidden: bool,
+    category_filter: &crate::semantic::CategoryFilter,
+) -> bool {
+    if path == root || !is_watchable(root, path, show_hidden) {
+        return false;
+    }
+    // Directories always pass - filtering them would hide whole
+    // subtrees of matching files
+    path.is_dir() || category_filter.allows(path)
+}
+
+/// Patch the in-memory model with one debounced logical change
+///
+/// Returns true if the model actually changed (and a repaint is due).
+fn apply_change(
+    root: &Path,
+    model: &mut BTreeMap<PathBuf, FileNode>,
+    highlights: &mut HashMap<PathBuf, Instant>,
+    change: AggregateEvent,
+    show_hidden: bool,
+) -> bool {
+    match change {
+        AggregateEvent::Created(path) | AggregateEvent::Modified(path) => {
+            // The window may outlive the file - trust the disk, not the label
+            if path.symlink_metadata().is_ok() {
+                upsert(root, model, highlights, &path, show_hidden)
+            } else {
+                remove_subtree(root, model, highlights, &path)
+            }
+        }
+        AggregateEvent::Deleted(path) => remove_subtree(root, model, highlights, &path),
+        AggregateEvent::Renamed { from, to } => {
+            // A move, not delete+create: the old subtree follows the name
+            let mut changed = remove_subtree(root, model, highlights, &from);
+            if to.symlink_metadata().is_ok() {
+                changed |= upsert(root, model, highlights, &to, show_hidden);
+            }
+            changed
+        }
+        AggregateEvent::DirectoryChanged { dir, .. } => {
+            // A storm under one directory: cheaper to resync its children
+            // wholesale than to replay every individual event
+            let mut changed = remove_children(model, highlights, &dir);
+            if let Ok(entries) = std::fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    let child = entry.path();
+                    if is_watchable(root, &child, show_hidden) {
+                        changed |= upsert(root, model, highlights, &child, show_hidden);
+                    }
+                }
+            }
+            if changed && dir != root {
+                highlights.insert(dir, Instant::now());
+            }
+            changed
+        }
+    }
+}
+
+/// Drop one entry and everything under it; the parent glows so the
+/// deletion has a visible home
+fn remove_subtree(
+    root: &Path,
+    model: &mut BTreeMap<PathBuf, FileNode>,
+    highlights: &mut HashMap<PathBuf, Instant>,
+    path: &Path,
+) -> bool {
+    let doomed: Vec<PathBuf> = model
+        .range(path.to_path_buf()..)
+        .take_while(|(p, _)| p.starts_with(path))
+        .map(|(p, _)| p.clone())
+        .collect();
+    let changed = !doomed.is_empty();
+    for p in doomed {
+        model.remove(&p);
+        highlights.remove(&p);
+    }
+    if changed {
+        if let Some(parent) = path.parent() {
+            if parent.starts_with(root) {
+                highlights.insert(parent.to_path_buf(), Instant::now());
+            }
+        }
+    }
+    changed
+}
+
+/// Drop everything under a directory but keep the directory itself
+fn remove_children(
+    model: &mut BTreeMap<PathBuf, FileNode>,
+    highlights: &mut HashMap<PathBuf, Instant>,
+    dir: &Path,
+) -> bool {
+    let doomed: Vec<PathBuf> = model
+        .range(dir.to_path_buf()..)
+        .take_while(|(p, _)| p.starts_with(dir))
+        .filter(|(p, _)| p.as_path() != dir)
+        .map(|(p, _)| p.clone())
+        .collect();
+    let changed = !doomed.is_empty();
+    for p in doomed {
+        model.remove(&p);
+        highlights.remove(&p);
+    }
+    changed
+}
+
+/// Insert or refresh a single entry; new directories get a mini-walk so a
+/// `mv`-ed subtree shows up whole instead of as one mysterious folder
+fn upsert(
+    root: &Path,
+    model: &mut BTreeMap<PathBuf, FileNode>,
+    highlights: &mut HashMap<PathBuf, Instant>,
+    path: &Path,
+    show_hidden: bool,
+) -> bool {
+    let Ok(mut node) = node_for(root, path) else {
+        return false;
+    };
+
+    let is_new = !model.contains_key(path);
+    node.change_status = Some(if is_new {
+        ChangeType::Added
+    } else {
+        ChangeType::Modified
+    });
+
+    let walk_children = node.is_dir && is_new;
+    model.insert(path.to_path_buf(), node);
+    highlights.insert(path.to_path_buf(), Instant::now());
+
+    if walk_children {
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                let child = entry.path();
+                if is_watchable(root, &child, show_hidden) {
+                    upsert(root, model, highlights, &child, show_hidden);
+                }
+            }
+        }
+    }
+    true
+}
+
+/// Events we never want to repaint for: hidden entries (unless shown) and
+/// .git internals - git's own churn would turn the tree into a strobe light
+fn is_watchable(root: &Path, path: &Path, show_hidden: bool) -> bool {
+    let Ok(rel) = path.strip_prefix(root) else {
+        return false;
+    };
+    for component in rel.components() {
+        let name = component.as_os_str().to_string_lossy();
+        if name == ".git" {
+            return false;
+        }
+        if !show_hidden && name.starts_with('.') {
+            return false;
+        }
+    }
+    true
+}
+
+/// Build a FileNode for one path - the watcher's lightweight stand-in for
+/// a scanner pass over a single entry
+fn node_for(root: &Path, path: &Path) -> Result<FileNode> {
+    let metadata = path.symlink_metadata()?;
+
+    #[cfg(unix)]
+    let (permissions, uid, gid) = {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        (metadata.permissions().mode() & 0o777, metadata.uid(), metadata.gid())
+    };
+    #[cfg(not(unix))]
+    let (permissions, uid, gid) = (0o755, 0, 0);
+
+    let is_symlink = metadata.file_type().is_symlink();
+    let is_dir = metadata.is_dir();
+    let file_type = if is_dir {
+        FileType::Directory
+    } else if is_symlink {
+        FileType::Symlink
+    } else {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if metadata.permissions().mode() & 0o111 != 0 {
+                FileType::Executable
+            } else {
+                FileType::RegularFile
+            }
+        }
+        #[cfg(not(unix))]
+        FileType::RegularFile
+    };
+
+    let is_hidden = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.starts_with('.'))
+        .unwrap_or(false);
+
+    let depth = path
+        .strip_prefix(root)
+        .map(|rel| rel.components().count())
+        .unwrap_or(0);
+
+    Ok(FileNode {
+        path: path.to_path_buf(),
+        is_dir,
+        size: if is_dir { 0 } else { metadata.len() },
+        permissions,
+        uid,
+        gid,
+        modified: metadata.modified().unwrap_or(std::time::UNIX_EPOCH),
+        is_symlink,
+        is_hidden,
+        permission_denied: false,
+        is_ignored: false,
+        depth,
+        file_type,
+        category: Scanner::get_file_category(path, file_type),
+        search_matches: None,
+        filesystem_type: Scanner::get_filesystem_type(path),
+        git_branch: None,
+        traversal_context: None,
+        interest: None,
+        security_findings: Vec::new(),
+        change_status: None,
+        content_hash: None,
+        hardlink_id: None,
+        allocated_size: None,
+        xattrs: None,
+    })
+}
+
+/// Recompute the summary numbers from the model - cheap compared to a scan
+fn stats_from_nodes(nodes: &[FileNode]) -> TreeStats {
+    let mut stats = TreeStats::default();
+    for node in nodes {
+        if node.is_dir {
+            stats.total_dirs += 1;
+        } else {
+            stats.total_files += 1;
+            stats.total_size += node.size;
+            if let Some(ext) = node.path.extension().and_then(|e| e.to_str()) {
+                *stats.file_types.entry(ext.to_lowercase()).or_insert(0) += 1;
+            }
+        }
+    }
+    stats
+}
+
+/// Render the current model and paint it over the previous frame
+///
+/// Home-cursor plus clear-to-end-of-line beats a full screen clear: the
+/// terminal never shows an empty frame, so there's no flicker.
+fn redraw(
+    req: &CliScanRequest,
+    root: &Path,
+    model: &BTreeMap<PathBuf, FileNode>,
+    highlights: &HashMap<PathBuf, Instant>,
+) -> Result<()> {
+    let nodes: Vec<FileNode> = model.values().cloned().collect();
+    let stats = stats_from_nodes(&nodes);
+
+    let mut buf = Vec::new();
+    match req.mode.to_lowercase().as_str() {
+        "ls" => {
+            let formatter = LsFormatter::new(!req.no_emoji, req.use_color);
+            formatter.format(&mut buf, &nodes, &stats, root)?;
+        }
+        _ => {
+            let formatter = ClassicFormatter::new(req.no_emoji, req.use_color, PathDisplayMode::Off);
+            formatter.format(&mut buf, &nodes, &stats, root)?;
+        }
+    }
+
+    let rendered = String::from_utf8_lossy(&buf);
+    let highlighted = highlight_lines(&rendered, highlights, req.use_color);
+
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "\x1b[H")?;
+    for line in highlighted.lines() {
+        writeln!(stdout, "{}\x1b[K", line)?;
+    }
+    writeln!(stdout, "\x1b[K")?;
+    writeln!(
+        stdout,
+        "👀 watching {} - Ctrl+C to stop\x1b[K",
+        root.display()
+    )?;
+    write!(stdout, "\x1b[J")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Wrap lines mentioning a recently-changed entry in inverse video
+fn highlight_lines(
+    rendered: &str,
+    highlights: &HashMap<PathBuf, Instant>,
+    use_color: bool,
+) -> String {
+    if !use_color || highlights.is_empty() {
+        return rendered.to_string();
+    }
+
+    let names: Vec<String> = highlights
+        .keys()
+        .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(String::from))
+        .collect();
+
+    rendered
+        .lines()
+        .map(|line| {
+            if names.iter().any(|name| line.contains(name.as_str())) {
+                format!("\x1b[7m{}\x1b[27m", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,205 @@
+// -----------------------------------------------------------------------------
+// 📏 LINE COUNTER - tokei-style code/comment/blank accounting (--loc)
+//
+// Post-scan pass, same shape as content_hasher: rayon fans the reads out
+// across the thread pool (bounded by CPU count), languages come from
+// ContentDetector's extension table, and the classifier is the classic
+// line-state machine - blank, comment (line or block), or code.
+//
+// It won't match tokei byte-for-byte on pathological files (a block
+// comment opened inside a string literal, say) - the goal is "which
+// languages is this tree really written in", not a compiler front-end.
+// -----------------------------------------------------------------------------
+
+use crate::content_detector::ContentDetector;
+use crate::scanner::FileNode;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Files larger than this are skipped - generated bundles and vendored
+/// blobs would swamp the numbers anyway.
+const MAX_COUNT_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Line totals for one language (or one file).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineCounts {
+    pub code: u64,
+    pub comments: u64,
+    pub blanks: u64,
+}
+
+impl LineCounts {
+    pub fn total(&self) -> u64 {
+        self.code + self.comments + self.blanks
+    }
+
+    fn merge(&mut self, other: &LineCounts) {
+        self.code += other.code;
+        self.comments += other.comments;
+        self.blanks += other.blanks;
+    }
+}
+
+/// Aggregated counts for one language across the scan.
+#[derive(Debug, Clone)]
+pub struct LanguageLoc {
+    pub language: &'static str,
+    pub files: usize,
+    pub lines: LineCounts,
+}
+
+/// How a language spells its comments.
+pub struct CommentSyntax {
+    line: &'static [&'static str],
+    block: Option<(&'static str, &'static str)>,
+}
+
+/// Comment syntax per ContentDetector language name. Languages not listed
+/// here still count (code vs blank), they just can't distinguish comments.
+fn syntax_for(language: &str) -> CommentSyntax {
+    match language {
+        "Rust" | "C" | "C++" | "C#" | "Go" | "Java" | "JavaScript" | "TypeScript" | "Kotlin"
+        | "Scala" | "Swift" | "PHP" | "Dart" | "Zig" => CommentSyntax {
+            line: &["//"],
+            block: Some(("/*", "*/")),
+        },
+        "Python" | "Ruby" | "Shell" | "Perl" | "R" | "TOML" | "YAML" | "Makefile" => {
+            CommentSyntax {
+                line: &["#"],
+                block: None,
+            }
+        }
+        "Lua" | "SQL" | "Haskell" => CommentSyntax {
+            line: &["--"],
+            block: None,
+        },
+        "HTML" | "XML" => CommentSyntax {
+            line: &[],
+            block: Some(("<!--", "-->")),
+        },
+        "CSS" => CommentSyntax {
+            line: &[],
+            block: Some(("/*", "*/")),
+        },
+        _ => CommentSyntax {
+            line: &[],
+            block: None,
+        },
+    }
+}
+
+/// Classify every line of `content` as code, comment, or blank.
+pub fn count_lines(content: &str, syntax: &CommentSyntax) -> LineCounts {
+    let mut counts = LineCounts::default();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            counts.blanks += 1;
+            continue;
+        }
+
+        if in_block {
+            counts.comments += 1;
+            if let Some((_, close)) = syntax.block {
+                if trimmed.contains(close) {
+                    in_block = false;
+                }
+            }
+            continue;
+        }
+
+        if syntax.line.iter().any(|prefix| trimmed.starts_with(prefix)) {
+            counts.comments += 1;
+            continue;
+        }
+
+        if let Some((open, close)) = syntax.block {
+            if let Some(rest) = trimmed.strip_prefix(open) {
+                counts.comments += 1;
+                // Still open unless the block also closes on this line
+                in_block = !rest.contains(close);
+                continue;
+            }
+        }
+
+        counts.code += 1;
+    }
+    counts
+}
+
+/// Count every recognizable source file and aggregate per language,
+/// sorted by code lines descending.
+pub fn tally_by_language(nodes: &[FileNode]) -> Vec<LanguageLoc> {
+    let per_file: Vec<(&'static str, LineCounts)> = nodes
+        .par_iter()
+        .filter(|node| {
+            !node.is_dir
+                && !node.is_symlink
+                && !node.permission_denied
+                && node.size <= MAX_COUNT_BYTES
+        })
+        .filter_map(|node| {
+            let ext = node.path.extension()?.to_str()?;
+            let language = ContentDetector::language_name(ext)?;
+            let content = std::fs::read_to_string(&node.path).ok()?;
+            Some((language, count_lines(&content, &syntax_for(language))))
+        })
+        .collect();
+
+    let mut by_language: HashMap<&'static str, LanguageLoc> = HashMap::new();
+    for (language, lines) in per_file {
+        let entry = by_language.entry(language).or_insert(LanguageLoc {
+            language,
+            files: 0,
+            lines: LineCounts::default(),
+        });
+        entry.files += 1;
+        entry.lines.merge(&lines);
+    }
+
+    let mut totals: Vec<LanguageLoc> = by_language.into_values().collect();
+    totals.sort_by_key(|l| std::cmp::Reverse(l.lines.code));
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_lines_rust_style() {
+        let source = "// header comment\n\nfn main() {\n    /* block\n       comment */\n    println!(\"hi\"); \n}\n";
+        let counts = count_lines(source, &syntax_for("Rust"));
+
+        assert_eq!(counts.comments, 3);
+        assert_eq!(counts.blanks, 1);
+        assert_eq!(counts.code, 3);
+        assert_eq!(counts.total(), 7);
+    }
+
+    #[test]
+    fn test_count_lines_hash_comments() {
+        let source = "# comment\nvalue = 1\n\n# another\n";
+        let counts = count_lines(source, &syntax_for("Python"));
+
+        assert_eq!(
+            counts,
+            LineCounts {
+                code: 1,
+                comments: 2,
+                blanks: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_block_comment_opening_and_closing_same_line() {
+        let source = "/* one-liner */\nlet x = 1;\n";
+        let counts = count_lines(source, &syntax_for("Rust"));
+
+        assert_eq!(counts.comments, 1);
+        assert_eq!(counts.code, 1);
+    }
+}
@@ -0,0 +1,220 @@
+// -----------------------------------------------------------------------------
+// 🌈 LS_COLORS - Honor Decades of dircolors Muscle Memory!
+// -----------------------------------------------------------------------------
+// Everyone's shell already knows what color a .tar.gz should be - it's in
+// LS_COLORS. This module parses that GNU dircolors format ("di=01;34:
+// *.rs=38;5;208:...") so classic and ls modes can paint entries exactly the
+// way the user's real ls does: directories, symlinks, executables, sticky
+// and other-writable dirs, and extension rules, raw SGR codes and all.
+//
+// Precedence is: ~/.st/theme.toml (explicit opt-in) > LS_COLORS (ambient
+// shell config) > the built-in palettes. Nobody's prompt gets surprised.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::{FileNode, FileType};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Parsed LS_COLORS database: special-type codes plus extension rules.
+/// Values are raw SGR parameter strings like "01;34" or "38;5;208".
+#[derive(Debug, Clone, Default)]
+pub struct LsColors {
+    /// Two-letter type codes: di, ln, ex, st, ow, tw, pi, so, bd, cd, ...
+    types: HashMap<String, String>,
+    /// "*.ext" rules, keyed by lowercase suffix without the '*'
+    suffixes: HashMap<String, String>,
+}
+
+impl LsColors {
+    /// The process-wide database parsed from $LS_COLORS (empty when the
+    /// variable is unset, which disables this whole layer).
+    pub fn global() -> &'static LsColors {
+        static LS_COLORS: OnceLock<LsColors> = OnceLock::new();
+        LS_COLORS.get_or_init(|| {
+            std::env::var("LS_COLORS")
+                .map(|spec| LsColors::parse(&spec))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Parse the colon-separated dircolors format. Malformed entries are
+    /// skipped - real-world LS_COLORS values are full of vendor quirks.
+    pub fn parse(spec: &str) -> LsColors {
+        let mut colors = LsColors::default();
+        for entry in spec.split(':') {
+            let Some((key, value)) = entry.split_once('=') else {
+                continue;
+            };
+            if key.is_empty() || value.is_empty() {
+                continue;
+            }
+            if let Some(suffix) = key.strip_prefix('*') {
+                colors
+                    .suffixes
+                    .insert(suffix.to_lowercase(), value.to_string());
+            } else {
+                colors.types.insert(key.to_string(), value.to_string());
+            }
+        }
+        colors
+    }
+
+    /// True when no rules are loaded (env unset or empty).
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.suffixes.is_empty()
+    }
+
+    /// The SGR parameter string for a bare type code ("di", "ln", ...),
+    /// for callers that color path components rather than whole nodes.
+    pub fn style_for_type(&self, key: &str) -> Option<&str> {
+        self.types.get(key).map(|code| code.as_str())
+    }
+
+    /// The SGR parameter string for a node, following the same resolution
+    /// order as GNU ls: special type first, then longest matching suffix.
+    pub fn style_for(&self, node: &FileNode) -> Option<&str> {
+        if let Some(code) = self.type_style(node) {
+            return Some(code);
+        }
+        // Suffix rules match case-insensitively against the filename so
+        // "*.tar.gz" beats "*.gz" - longest suffix wins, like ls
+        let name = node.path.file_name()?.to_str()?.to_lowercase();
+        self.suffixes
+            .iter()
+            .filter(|(suffix, _)| name.ends_with(suffix.as_str()))
+            .max_by_key(|(suffix, _)| suffix.len())
+            .map(|(_, code)| code.as_str())
+    }
+
+    /// Wrap `text` in the node's SGR sequence, or None when no rule hits.
+    pub fn paint(&self, node: &FileNode, text: &str) -> Option<String> {
+        self.style_for(node)
+            .map(|code| format!("\x1b[{}m{}\x1b[0m", code, text))
+    }
+
+    fn type_style(&self, node: &FileNode) -> Option<&str> {
+        let key = if node.is_symlink {
+            "ln"
+        } else if node.is_dir {
+            let sticky = node.permissions & 0o1000 != 0;
+            let other_writable = node.permissions & 0o002 != 0;
+            match (sticky, other_writable) {
+                (true, true) => "tw",
+                (false, true) => "ow",
+                (true, false) => "st",
+                (false, false) => "di",
+            }
+        } else {
+            match node.file_type {
+                FileType::Executable => "ex",
+                FileType::Pipe => "pi",
+                FileType::Socket => "so",
+                FileType::BlockDevice => "bd",
+                FileType::CharDevice => "cd",
+                FileType::RegularFile if node.permissions & 0o111 != 0 => "ex",
+                _ => return None,
+            }
+        };
+        // Directories with no sticky/ow rule still deserve plain "di"
+        let code = self.types.get(key);
+        if code.is_none() && (key == "tw" || key == "ow" || key == "st") {
+            return self.types.get("di").map(|c| c.as_str());
+        }
+        code.map(|c| c.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn node(name: &str, is_dir: bool, permissions: u32) -> FileNode {
+        FileNode {
+            path: PathBuf::from(name),
+            is_dir,
+            size: 0,
+            permissions,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth: 1,
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_and_type_codes() {
+        let colors = LsColors::parse("di=01;34:ln=01;36:ex=01;32:*.rs=38;5;208");
+        assert_eq!(colors.style_for(&node("src", true, 0o755)), Some("01;34"));
+        assert_eq!(
+            colors.style_for(&node("main.rs", false, 0o644)),
+            Some("38;5;208")
+        );
+        // Executable bit routes to ex before any suffix rule
+        assert_eq!(
+            colors.style_for(&node("deploy.sh", false, 0o755)),
+            Some("01;32")
+        );
+        assert_eq!(colors.style_for(&node("notes.txt", false, 0o644)), None);
+    }
+
+    #[test]
+    fn test_sticky_and_other_writable_dirs() {
+        let colors = LsColors::parse("di=01;34:tw=30;42:ow=34;42");
+        assert_eq!(
+            colors.style_for(&node("/tmp", true, 0o1777)),
+            Some("30;42")
+        );
+        assert_eq!(
+            colors.style_for(&node("shared", true, 0o777)),
+            Some("34;42")
+        );
+        // Sticky-only falls back to plain di when st is not defined
+        assert_eq!(
+            colors.style_for(&node("dropbox", true, 0o1755)),
+            Some("01;34")
+        );
+    }
+
+    #[test]
+    fn test_longest_suffix_wins() {
+        let colors = LsColors::parse("*.gz=01;31:*.tar.gz=01;35");
+        assert_eq!(
+            colors.style_for(&node("backup.tar.gz", false, 0o644)),
+            Some("01;35")
+        );
+        assert_eq!(
+            colors.style_for(&node("page.gz", false, 0o644)),
+            Some("01;31")
+        );
+    }
+
+    #[test]
+    fn test_malformed_entries_skipped() {
+        let colors = LsColors::parse("rubbish:di=01;34::=x:key=");
+        assert_eq!(colors.style_for(&node("src", true, 0o755)), Some("01;34"));
+    }
+}
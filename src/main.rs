@@ -14,8 +14,8 @@ use clap::{CommandFactory, Parser};
 use clap_complete::generate;
 
 // Import CLI definitions from the library
-use st::cli::{Cli, ColorMode, OutputMode, PathMode};
-use std::io::{self, IsTerminal};
+use st::cli::{Cli, ColorMode, PathMode};
+use std::io::{self, IsTerminal, Write};
 use std::path::PathBuf;
 
 // Pulling in the brains of the operation from our library modules.
@@ -35,7 +35,10 @@ use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse the command-line arguments provided by the user.
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+
+    // Arm the shell-out kill-switch before any feature can spawn
+    st::exec_policy::set_no_exec(cli.no_exec);
 
     // Initialize Logging
     let log_level_str = if let Some(level) = cli.log_level {
@@ -142,6 +145,36 @@ async fn main() -> Result<()> {
         return st::spicy_tui_enhanced::run_enhanced_spicy_tui(path).await;
     }
 
+    // Handle TUI explorer mode
+    if cli.tui {
+        let flags = feature_flags::features();
+        if !flags.enable_tui {
+            eprintln!("Error: Terminal UI is disabled by configuration or compliance mode.");
+            eprintln!("Contact your administrator to enable this feature.");
+            return Ok(());
+        }
+        let path = match &cli.path {
+            Some(p) => std::fs::canonicalize(p)?,
+            None => std::env::current_dir()?,
+        };
+        return st::tui_explorer::run_tui_explorer(path);
+    }
+
+    // Fuzzy filename finder - one scan, ranked matches, done
+    if let Some(pattern) = &cli.scan_opts.fuzzy {
+        let path = match &cli.path {
+            Some(p) => std::fs::canonicalize(p)?,
+            None => std::env::current_dir()?,
+        };
+        let scanner = st::Scanner::new(&path, st::ScannerConfig::default())?;
+        let (nodes, _stats) = scanner.scan()?;
+        for (score, idx) in st::fuzzy::rank_nodes(&nodes, &path, pattern) {
+            let rel = nodes[idx].path.strip_prefix(&path).unwrap_or(&nodes[idx].path);
+            println!("{:>6}  {}", score, rel.display());
+        }
+        return Ok(());
+    }
+
     // Initialize logging if requested
     if let Some(log_path) = &cli.log {
         // Check if activity logging is enabled via feature flags
@@ -199,7 +232,7 @@ async fn main() -> Result<()> {
             eprintln!("Contact your administrator to enable this feature.");
             return Ok(());
         }
-        return run_mcp_server().await;
+        return run_mcp_server(cli.listen.as_deref()).await;
     }
     if cli.mcp_install {
         return handle_mcp_install().await;
@@ -210,9 +243,54 @@ async fn main() -> Result<()> {
     if cli.mcp_status {
         return handle_mcp_status().await;
     }
-    // Handle top-level subcommands
-    if let Some(cmd) = cli.cmd {
+    // Handle top-level subcommands. `scan` and `search` adopt their
+    // arguments into the flat namespace and fall through to the normal
+    // scan path below - `st PATH` stays an alias for `st scan PATH`.
+    if let Some(cmd) = cli.cmd.take() {
         match cmd {
+            st::cli::Cmd::Scan { path, scan_opts } => {
+                if path.is_some() {
+                    cli.path = path;
+                }
+                cli.scan_opts = *scan_opts;
+            }
+
+            st::cli::Cmd::Search {
+                pattern,
+                path,
+                scan_opts,
+            } => {
+                if path.is_some() {
+                    cli.path = path;
+                }
+                cli.scan_opts = *scan_opts;
+                cli.scan_opts.search.insert(0, pattern);
+            }
+
+            st::cli::Cmd::Mcp => {
+                let flags = feature_flags::features();
+                if !flags.enable_mcp_server {
+                    eprintln!(
+                        "Error: MCP server is disabled by configuration or compliance mode."
+                    );
+                    eprintln!("Contact your administrator to enable this feature.");
+                    return Ok(());
+                }
+                return run_mcp_server(cli.listen.as_deref()).await;
+            }
+
+            st::cli::Cmd::Daemon(daemon_command) => {
+                let port = cli.scan_opts.sse_port;
+                return match daemon_command {
+                    st::cli::DaemonCmd::Start => handle_daemon_start(port).await,
+                    st::cli::DaemonCmd::Stop => handle_daemon_stop(port).await,
+                    st::cli::DaemonCmd::Status => handle_daemon_status(port).await,
+                    st::cli::DaemonCmd::Context => handle_daemon_context(port).await,
+                    st::cli::DaemonCmd::Projects => handle_daemon_projects(port).await,
+                    st::cli::DaemonCmd::Credits => handle_daemon_credits(port).await,
+                };
+            }
+
             st::cli::Cmd::Service(service_command) => {
                 let result = match service_command {
                     st::cli::Service::Install => service_manager::install(),
@@ -230,6 +308,93 @@ async fn main() -> Result<()> {
                 return Ok(());
             }
 
+            st::cli::Cmd::Decompress { file } => {
+                return handle_decompress(&file);
+            }
+
+            st::cli::Cmd::Decode { file, to } => {
+                return handle_decode(&file, &to);
+            }
+
+            st::cli::Cmd::Audit(audit_command) => {
+                return match audit_command {
+                    st::cli::AuditCmd::Show { path, tail, tool } => {
+                        handle_audit_show(&path, tail, tool.as_deref())
+                    }
+                    st::cli::AuditCmd::Replay { path } => handle_audit_replay(&path),
+                };
+            }
+
+            st::cli::Cmd::Cache(cache_command) => {
+                return match cache_command {
+                    st::cli::CacheCmd::Stats => handle_cache_stats(),
+                    st::cli::CacheCmd::Clear => handle_cache_clear(),
+                };
+            }
+
+            st::cli::Cmd::Dict(dict_command) => {
+                return match dict_command {
+                    st::cli::DictCmd::Train { samples, max_size } => {
+                        handle_dict_train(&samples, max_size)
+                    }
+                };
+            }
+
+            st::cli::Cmd::ExplainFormat { format } => {
+                return match format.as_str() {
+                    "hex" => {
+                        print!("{}", st::formatters::hex::explain_format());
+                        Ok(())
+                    }
+                    other => Err(anyhow::anyhow!(
+                        "No field documentation for '{}' (supported: hex)",
+                        other
+                    )),
+                };
+            }
+
+            st::cli::Cmd::Doctor { json } => {
+                let project = std::env::current_dir()?;
+                return st::doctor::run(&project, json).await;
+            }
+
+            st::cli::Cmd::Index(index_command) => {
+                return match index_command {
+                    st::cli::IndexCmd::Build { path } => handle_index_build(&path),
+                    st::cli::IndexCmd::Status { path } => handle_index_status(&path),
+                    st::cli::IndexCmd::Clear { path } => handle_index_clear(&path),
+                };
+            }
+
+            st::cli::Cmd::Memory(memory_command) => {
+                return match memory_command {
+                    st::cli::Memory::Export {
+                        format: _,
+                        output,
+                        dir,
+                    } => handle_memory_export(output.as_deref(), dir.as_deref()),
+                    st::cli::Memory::Import {
+                        file,
+                        format: _,
+                        dir,
+                    } => handle_memory_import(&file, dir.as_deref()),
+                };
+            }
+
+            st::cli::Cmd::Wordcloud {
+                path,
+                out,
+                theme,
+                stopwords,
+                max_words,
+                shape,
+                source,
+            } => {
+                return handle_wordcloud(
+                    &path, &out, theme, stopwords, max_words, &shape, &source,
+                );
+            }
+
             st::cli::Cmd::ProjectTags(project_tags) => {
                 let project_path = ".";
                 match project_tags {
@@ -346,31 +511,291 @@ async fn main() -> Result<()> {
         return handle_get_frequency(path).await;
     }
 
+    // Hidden stat-only helper, spawned under sudo by --sudo-helper. It walks
+    // one path and prints "<entries> <bytes>" - deliberately nothing more, so
+    // the privileged surface stays tiny.
+    if let Some(path) = &cli.stat_helper {
+        let (entries, bytes, _) = estimate_scan_size(path, u64::MAX, u64::MAX);
+        println!("{} {}", entries, bytes);
+        return Ok(());
+    }
+
+    // Live watch mode - local scan plus incremental repaints, so the daemon
+    // round-trip never sits between a file change and the screen
+    if cli.watch {
+        let request = build_cli_request(&cli)?;
+        return st::live_watch::run_live_watch(&request).await;
+    }
+
+    // -o report.html picks the formatter from the file extension, unless
+    // an explicit --mode (or --smart) already decided
+    if cli.scan_opts.mode.eq_ignore_ascii_case("auto") && !cli.scan_opts.smart {
+        if let Some(mode) = cli
+            .scan_opts
+            .output
+            .as_deref()
+            .and_then(infer_mode_from_extension)
+        {
+            cli.scan_opts.mode = mode.to_string();
+        }
+    }
+
+    // SQLite export - local scan that writes a database file, which can't
+    // ride the daemon's text response
+    if cli.scan_opts.mode.eq_ignore_ascii_case("sqlite") {
+        let Some(db_path) = cli.scan_opts.output.clone() else {
+            anyhow::bail!("--mode sqlite writes a binary database; pass --output <FILE>");
+        };
+        let request = build_cli_request(&cli)?;
+        let scan_id = st::sqlite_export::run_export(&request, &db_path)?;
+        eprintln!(
+            "💾 Scan exported to {} (scan_id {})",
+            db_path.display(),
+            scan_id
+        );
+        return Ok(());
+    }
+
+    // Parquet export - same local-scan path as sqlite, behind the "parquet"
+    // feature so the arrow stack stays out of default builds
+    if cli.scan_opts.mode.eq_ignore_ascii_case("parquet") {
+        let Some(out_path) = cli.scan_opts.output.clone() else {
+            anyhow::bail!("--mode parquet writes a binary file; pass --output <FILE>");
+        };
+        #[cfg(feature = "parquet")]
+        {
+            let request = build_cli_request(&cli)?;
+            let rows = st::parquet_export::run_export(&request, &out_path)?;
+            eprintln!("💾 Scan exported to {} ({} rows)", out_path.display(), rows);
+            return Ok(());
+        }
+        #[cfg(not(feature = "parquet"))]
+        {
+            let _ = out_path;
+            anyhow::bail!(
+                "This build has no Parquet support; rebuild with --features parquet"
+            );
+        }
+    }
+
     // =========================================================================
     // THIN CLIENT - All scanning/formatting happens in the daemon
     // =========================================================================
-    // Ensure daemon is running (always required now)
-    let client = DaemonClient::default_port();
-    client.ensure_running().await.context(
-        "Smart Tree daemon could not be started. Try: std start",
-    )?;
-
     // Build CLI request from arguments
-    let request = build_cli_request(&cli)?;
+    let mut request = build_cli_request(&cli)?;
 
-    // Execute scan via daemon
-    let response = client.cli_scan(request).await.context("Scan failed")?;
+    // --everything on / can melt terminals - estimate first, confirm if huge
+    if cli.scan_opts.everything {
+        everything_guard(&mut request, cli.scan_opts.yes)?;
+    }
 
-    // Print output (already formatted by daemon)
-    print!("{}", response.output);
+    // Persistent result cache: same options + unchanged tree = replay the
+    // stored output without a scan or even a daemon round-trip
+    let cache_key = if cli.extra_paths.is_empty() {
+        st::result_cache::cache_key(&request)
+    } else {
+        None
+    };
+    let cache = cache_key
+        .as_ref()
+        .and_then(|_| st::result_cache::ResultCache::open().ok());
+    let cached = match (&cache, &cache_key) {
+        (Some(cache), Some(key)) => cache.get(key),
+        _ => None,
+    };
+
+    let fail_on_cycles = request.fail_on_cycles;
+
+    // Execute scan via daemon - once per root, `st a b c` renders every
+    // root as a sibling tree with per-root and combined totals at the end
+    let output_text = if let Some(hit) = cached {
+        hit
+    } else {
+        // Ensure daemon is running (always required now)
+        let client = DaemonClient::default_port();
+        client.ensure_running().await.context(
+            "Smart Tree daemon could not be started. Try: std start",
+        )?;
+
+        if cli.extra_paths.is_empty() {
+            let output = client.cli_scan(request).await.context("Scan failed")?.output;
+            if let (Some(cache), Some(key)) = (&cache, &cache_key) {
+                let _ = cache.put(key, &output);
+            }
+            output
+        } else {
+            let mut roots = vec![cli.path.clone().unwrap_or_else(|| ".".to_string())];
+            roots.extend(cli.extra_paths.iter().cloned());
+            multi_root_scan(&client, &request, &roots).await?
+        }
+    };
+
+    let has_cycles = fail_on_cycles && output_text.contains(st::relations::CYCLE_FAIL_MARKER);
+
+    // Print output (already formatted by daemon), or write to --output.
+    // A .gz extension gzips transparently - handy for big CSV exports
+    if let Some(out) = &cli.scan_opts.output {
+        let bytes = if out.extension().and_then(|e| e.to_str()) == Some("gz") {
+            use flate2::{write::GzEncoder, Compression};
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            std::io::Write::write_all(&mut encoder, output_text.as_bytes())?;
+            encoder.finish()?
+        } else {
+            output_text.into_bytes()
+        };
+        // Atomic write: temp file in the same directory, then rename, so a
+        // crash mid-write never leaves a truncated report at the target
+        let tmp = out.with_file_name(format!(
+            ".{}.tmp{}",
+            out.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            std::process::id()
+        ));
+        std::fs::write(&tmp, bytes)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, out)
+            .with_context(|| format!("Failed to move output into {}", out.display()))?;
+    } else {
+        print!("{}", output_text);
+    }
+
+    // --fail-on-cycles CI gate: the daemon renders the output, so the
+    // verdict travels as a marker line the formatters append on cycles
+    if has_cycles {
+        std::process::exit(1);
+    }
 
     Ok(())
 }
 
+/// Scan several roots in one invocation: each root goes through the daemon
+/// with the same options, the formatted trees are stacked as siblings, and
+/// a summary block closes with per-root lines plus the combined totals.
+async fn multi_root_scan(
+    client: &DaemonClient,
+    base: &st::daemon_cli::CliScanRequest,
+    roots: &[String],
+) -> Result<String> {
+    use humansize::{format_size, BINARY};
+
+    let mut sections = Vec::with_capacity(roots.len());
+    let mut summaries = Vec::with_capacity(roots.len());
+    let mut combined = (0u64, 0u64, 0u64); // dirs, files, bytes
+
+    for root in roots {
+        let mut request = base.clone();
+        request.path = if std::path::Path::new(root).is_absolute() {
+            root.clone()
+        } else {
+            std::env::current_dir()
+                .map(|cwd| cwd.join(root).display().to_string())
+                .unwrap_or_else(|_| root.clone())
+        };
+        let response = client
+            .cli_scan(request)
+            .await
+            .with_context(|| format!("Scan failed for {}", root))?;
+
+        let stats = &response.stats;
+        combined.0 += stats.total_dirs;
+        combined.1 += stats.total_files;
+        combined.2 += stats.total_size;
+        summaries.push(format!(
+            "  {}: {} dirs, {} files, {}",
+            root,
+            stats.total_dirs,
+            stats.total_files,
+            format_size(stats.total_size, BINARY)
+        ));
+        sections.push(response.output.trim_end().to_string());
+    }
+
+    let summary = format!(
+        "{}\n  combined: {} dirs, {} files, {}",
+        summaries.join("\n"),
+        combined.0,
+        combined.1,
+        format_size(combined.2, BINARY)
+    );
+
+    // Machine-readable modes must stay parseable - JSON followed by prose
+    // totals is broken output - so the summary block goes to stderr there
+    let machine_mode = matches!(
+        base.mode.to_lowercase().as_str(),
+        "json"
+            | "csv"
+            | "tsv"
+            | "hex"
+            | "digest"
+            | "quantum"
+            | "quantum-semantic"
+            | "quantum_semantic"
+            | "sqlite"
+            | "dot"
+            | "mermaid"
+            | "marqant"
+            | "html"
+    );
+
+    let mut output = sections.join("\n\n");
+    output.push('\n');
+    if machine_mode {
+        eprintln!("{}", summary);
+    } else {
+        output.push('\n');
+        output.push_str(&summary);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Map an --output extension to the formatter that naturally produces it,
+/// so `-o report.html` does what it looks like. A trailing .gz is peeled
+/// first (report.csv.gz infers csv). None means "no opinion" - the normal
+/// auto/smart mode selection applies.
+fn infer_mode_from_extension(out: &std::path::Path) -> Option<&'static str> {
+    let mut out = std::borrow::Cow::Borrowed(out);
+    if out.extension().and_then(|e| e.to_str()) == Some("gz") {
+        out = std::borrow::Cow::Owned(out.with_extension(""));
+    }
+    match out.extension()?.to_str()?.to_lowercase().as_str() {
+        "json" => Some("json"),
+        "html" | "htm" => Some("html"),
+        "csv" => Some("csv"),
+        "tsv" => Some("tsv"),
+        "md" | "markdown" => Some("markdown"),
+        "mq" => Some("marqant"),
+        "sqlite" | "db" => Some("sqlite"),
+        "parquet" => Some("parquet"),
+        "dot" | "gv" => Some("dot"),
+        _ => None,
+    }
+}
+
+/// tree(1) -P/-I patterns match basenames wherever they appear; our glob
+/// filter matches relative paths. Bare patterns get a `**/` anchor so
+/// `-P '*.rs'` finds sources at any depth, while patterns that already
+/// contain a separator pass through untouched.
+fn tree_wildcard_to_glob(pattern: &str) -> String {
+    if pattern.contains('/') {
+        pattern.to_string()
+    } else {
+        format!("**/{pattern}")
+    }
+}
+
 /// Build a CliScanRequest from CLI arguments
 fn build_cli_request(cli: &Cli) -> Result<st::daemon_cli::CliScanRequest> {
     let args = &cli.scan_opts;
 
+    // Named profile from ~/.st/config.toml - fills in whatever the command
+    // line left at its default; explicit flags always win.
+    let profile = match args.profile.as_deref() {
+        Some(name) => st::config::StConfig::load()?.scan_profile(name)?.clone(),
+        None => st::config::ScanProfile::default(),
+    };
+
     // Convert path to absolute - daemon runs in different directory
     let path = cli.path.clone().unwrap_or_else(|| ".".to_string());
     let path = if std::path::Path::new(&path).is_absolute() {
@@ -386,13 +811,34 @@ fn build_cli_request(cli: &Cli) -> Result<st::daemon_cli::CliScanRequest> {
     // Default is now "smart" - surface what matters!
     let mode = if args.smart {
         "smart".to_string()
-    } else if matches!(args.mode, OutputMode::Auto) {
-        // Check environment variable, default to smart
-        std::env::var("ST_DEFAULT_MODE")
-            .unwrap_or_else(|_| "smart".to_string())
-            .to_lowercase()
+    } else if args.mode.eq_ignore_ascii_case("auto") {
+        if let Some(mode) = profile.mode.as_deref() {
+            st::formatters::resolve_mode_name(mode)?
+        } else {
+            // Check environment variable, default to smart
+            std::env::var("ST_DEFAULT_MODE")
+                .unwrap_or_else(|_| "smart".to_string())
+                .to_lowercase()
+        }
+    } else {
+        // Resolve through the formatter registry so plugin modes work
+        st::formatters::resolve_mode_name(&args.mode)?
+    };
+
+    // Compatibility modes: -J and -H are tree's output switches, and
+    // `--compat tree`/`--compat fd` select the byte-compatible renderers.
+    // An explicit --mode still wins so users can mix and match.
+    let mode = if args.tree_json {
+        "json".to_string()
+    } else if args.tree_html.is_some() {
+        "html".to_string()
+    } else if args.mode.eq_ignore_ascii_case("auto") && args.compat.is_some() {
+        match args.compat.as_deref() {
+            Some("fd") => "fd".to_string(),
+            _ => "tree".to_string(),
+        }
     } else {
-        format!("{:?}", args.mode).to_lowercase()
+        mode
     };
 
     // Smart mode implies smart scanning features
@@ -414,38 +860,132 @@ fn build_cli_request(cli: &Cli) -> Result<st::daemon_cli::CliScanRequest> {
     };
 
     // Smart mode defaults to depth 5 for comprehensive but focused scanning
-    let depth = if args.depth == 0 && is_smart_mode {
-        5
+    let depth = if let Some(level) = args.level {
+        // tree -L counts directory levels the same way --depth does
+        level
+    } else if args.depth == 0 {
+        profile.depth.unwrap_or(if is_smart_mode { 5 } else { 0 })
     } else {
         args.depth
     };
 
+    // tree's -P/-I match basenames at any depth, so anchor bare patterns
+    // with `**/` before handing them to the glob filter; -I additionally
+    // prunes whole directories, hence the `/**` twin for ignores.
+    let mut glob = args.glob.clone();
+    glob.extend(profile.glob.iter().cloned());
+    glob.extend(args.tree_pattern.iter().map(|p| tree_wildcard_to_glob(p)));
+    for pattern in &args.tree_ignore {
+        glob.push(format!("!{}", tree_wildcard_to_glob(pattern)));
+        if !pattern.contains('/') {
+            glob.push(format!("!**/{pattern}/**"));
+        }
+    }
+
+    // A profile-supplied ordering yields to an explicit flag for the
+    // opposite one (the two flags conflict on the command line).
+    let dirs_first = args.dirs_first || (!args.files_first && profile.dirs_first.unwrap_or(false));
+    let files_first = args.files_first || (!args.dirs_first && profile.files_first.unwrap_or(false));
+
     Ok(st::daemon_cli::CliScanRequest {
         path,
         mode,
         depth,
-        all: args.all,
-        respect_gitignore: !args.no_ignore,
-        default_ignores: !args.no_default_ignore,
-        show_ignored: args.show_ignored,
-        find: args.find.clone(),
-        file_type: args.filter_type.clone(),
-        entry_type: args.entry_type.clone(),
-        min_size: args.min_size.clone(),
-        max_size: args.max_size.clone(),
-        sort: args.sort.map(|s| format!("{:?}", s).to_lowercase()),
-        top: args.top,
-        search: args.search.clone(),
-        compress: args.compress,
-        no_emoji: args.no_emoji || args.mcp_optimize,
+        // --everything = --all + --no-ignore + --no-default-ignore
+        all: args.all || args.everything || profile.all.unwrap_or(false),
+        respect_gitignore: !args.no_ignore && !args.everything && !profile.no_ignore.unwrap_or(false),
+        default_ignores: !args.no_default_ignore
+            && !args.everything
+            && !profile.no_default_ignore.unwrap_or(false),
+        show_ignored: args.show_ignored || profile.show_ignored.unwrap_or(false),
+        find: args.find.clone().or(profile.find),
+        glob,
+        file_type: args.filter_type.clone().or(profile.file_type),
+        entry_type: args.entry_type.clone().or(profile.entry_type),
+        special_files: args.special_files.clone(),
+        git_filter: args
+            .git_filter
+            .as_ref()
+            .and_then(clap::ValueEnum::to_possible_value)
+            .map(|value| value.get_name().to_string()),
+        min_size: args.min_size.clone().or(profile.min_size),
+        max_size: args.max_size.clone().or(profile.max_size),
+        sort: (!args.sort.is_empty()).then(|| {
+            args.sort
+                .iter()
+                .filter_map(clap::ValueEnum::to_possible_value)
+                .map(|value| value.get_name().to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .or(profile.sort),
+        dirs_first,
+        files_first,
+        prune: args.prune.clone(),
+        max_files_per_dir: args.max_files_per_dir,
+        max_entries: args.max_entries,
+        max_bytes_scanned: args.max_bytes_scanned.clone(),
+        timeout: args.timeout,
+        top: args.top.or(profile.top),
+        search: args.search.first().cloned(),
+        search_patterns: args.search.iter().skip(1).cloned().collect(),
+        search_regex: args.search_regex,
+        search_ignore_case: args.search_ignore_case,
+        search_word: args.search_word,
+        search_all: args.search_all,
+        compress: args.compress || profile.compress.unwrap_or(false),
+        compression: args.compression.clone(),
+        token_budget: args.token_budget,
+        no_emoji: args.no_emoji || args.mcp_optimize || profile.no_emoji.unwrap_or(false),
         use_color,
         path_mode,
         focus: args.focus.as_ref().map(|p| p.display().to_string()),
         relations_filter: args.relations_filter.clone(),
+        dot: args.dot,
+        call_graph: args.call_graph.clone(),
+        fail_on_cycles: args.fail_on_cycles,
+        git_status: args.git_status,
+        git_blame_summary: args.git_blame_summary,
+        watch_filter: cli.watch_filter.clone(),
+        watch_debounce: cli.watch_debounce,
         show_filesystems: args.show_filesystems,
         include_line_content: false, // Not exposed in CLI, used by MCP
         compact: args.compact,
         // Smart scanning options - enabled by default in smart mode
+        scan_archives: args.scan_archives,
+        dedupe_hardlinks: args.dedupe_hardlinks,
+        report_denied: args.report_denied,
+        sudo_helper: args.sudo_helper,
+        du: args.du,
+        dir_sizes: args.dir_sizes,
+        classic_walker: args.classic_walker,
+        ads: args.ads,
+        xattrs: args.xattrs,
+        hash: args.hash.clone(),
+        per_dir: args.per_dir,
+        sbom: args.sbom.clone(),
+        graph: args.graph.clone(),
+        loc: args.loc,
+        detect_content: args.detect_content,
+        mermaid_links: args.mermaid_links.clone(),
+        mermaid_subgraphs: args.mermaid_subgraphs,
+        report_template: args
+            .report_template
+            .as_ref()
+            .map(|p| p.display().to_string()),
+        media_info: args.media_info,
+        csv_delimiter: args.csv_delimiter.clone(),
+        csv_bom: args.csv_bom,
+        columns: args.columns.clone(),
+        si: args.si,
+        kibibytes: args.kibibytes,
+        time_style: args.time_style.clone(),
+        max_name_width: args.max_name_width,
+        hex_fields: args.hex_fields.clone(),
+        heatmap: args.heatmap.clone(),
+        merkle: args.merkle,
+        stats_top: args.stats_top,
+        stats_filter: args.stats_filter.clone(),
         smart: args.smart || is_smart_mode,
         changes_only: args.changes_only,
         min_interest: args.min_interest,
@@ -453,6 +993,96 @@ fn build_cli_request(cli: &Cli) -> Result<st::daemon_cli::CliScanRequest> {
     })
 }
 
+/// Default thresholds above which --everything stops to ask. Tune with
+/// ST_EVERYTHING_MAX_ENTRIES / ST_EVERYTHING_MAX_BYTES.
+const EVERYTHING_MAX_ENTRIES: u64 = 100_000;
+const EVERYTHING_MAX_BYTES: u64 = 10 * 1024 * 1024 * 1024; // 10 GiB
+
+/// Safety rail for `--everything`: a quick estimation pre-pass that warns
+/// (with counts and size) before producing oversized output. Interactive
+/// runs get a confirm prompt and an auto-switch to summary mode; `--yes`
+/// skips both and keeps the requested mode.
+fn everything_guard(request: &mut st::daemon_cli::CliScanRequest, yes: bool) -> Result<()> {
+    let max_entries = env_threshold("ST_EVERYTHING_MAX_ENTRIES", EVERYTHING_MAX_ENTRIES);
+    let max_bytes = env_threshold("ST_EVERYTHING_MAX_BYTES", EVERYTHING_MAX_BYTES);
+
+    let (entries, bytes, capped) =
+        estimate_scan_size(std::path::Path::new(&request.path), max_entries, max_bytes);
+    if entries <= max_entries && bytes <= max_bytes {
+        return Ok(()); // Small enough - no ceremony needed.
+    }
+
+    let qualifier = if capped { "at least " } else { "" };
+    eprintln!(
+        "⚠️  --everything would cover {}{} entries / {} (thresholds: {} entries, {})",
+        qualifier,
+        entries,
+        humansize::format_size(bytes, humansize::BINARY),
+        max_entries,
+        humansize::format_size(max_bytes, humansize::BINARY),
+    );
+
+    if yes {
+        return Ok(()); // User pre-approved - their terminal, their funeral.
+    }
+
+    let interactive = io::stdout().is_terminal() && io::stdin().is_terminal();
+    if !interactive {
+        // Piped/scripted runs can't answer a prompt; the stderr warning above
+        // is all the friction they get.
+        return Ok(());
+    }
+
+    // Full listings at this scale are unreadable anyway - summarize instead.
+    // --yes keeps the explicitly requested mode.
+    if matches!(request.mode.as_str(), "classic" | "ls") {
+        eprintln!("   Switching to summary mode (pass --yes to keep --mode {})", request.mode);
+        request.mode = "summary".to_string();
+    }
+
+    eprint!("   Continue? [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    if !matches!(answer.trim(), "y" | "Y" | "yes" | "YES") {
+        anyhow::bail!("Aborted - rerun with --yes to skip this prompt");
+    }
+    Ok(())
+}
+
+/// Read a numeric threshold override from the environment, falling back to
+/// the built-in default on absence or nonsense.
+fn env_threshold(var: &str, default: u64) -> u64 {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+/// Quick ignore-free walk that stops as soon as either threshold is blown -
+/// we only need "over or under", not an exact census. Returns
+/// (entries, bytes, stopped_early).
+fn estimate_scan_size(root: &std::path::Path, max_entries: u64, max_bytes: u64) -> (u64, u64, bool) {
+    let mut entries = 0u64;
+    let mut bytes = 0u64;
+    for entry in walkdir::WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        entries += 1;
+        if let Ok(metadata) = entry.metadata() {
+            if !metadata.is_dir() {
+                bytes += metadata.len();
+            }
+        }
+        if entries > max_entries || bytes > max_bytes {
+            return (entries, bytes, true);
+        }
+    }
+    (entries, bytes, false)
+}
+
 // =========================================================================
 // HELPER FUNCTIONS
 // =========================================================================
@@ -585,6 +1215,432 @@ async fn show_version_with_updates() -> Result<()> {
     Ok(())
 }
 
+/// Expand a COMPRESSED_V1/V2 payload file back to the original output.
+/// V2 containers carry a self-describing header (mode, root, timestamp,
+/// size, checksum) which is printed to stderr; the content goes to stdout
+/// so it can be piped or redirected cleanly.
+fn handle_decompress(file: &std::path::Path) -> Result<()> {
+    use st::compression_manager::decompress_container;
+    use std::io::Read;
+
+    let input = if file == std::path::Path::new("-") {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        std::fs::read_to_string(file)
+            .with_context(|| format!("Could not read {}", file.display()))?
+    };
+
+    let (content, header) = decompress_container(&input)?;
+
+    if let Some(header) = header {
+        let when = chrono::DateTime::<chrono::Utc>::from_timestamp(header.timestamp as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        eprintln!("📦 Smart Tree container (v2)");
+        eprintln!("   Mode: {}", header.mode);
+        eprintln!("   Root: {}", header.root);
+        eprintln!("   Created: {}", when);
+        eprintln!("   Original size: {} bytes (checksum OK)", header.original_size);
+    } else {
+        eprintln!("📦 Legacy COMPRESSED_V1 payload (no metadata header)");
+    }
+
+    print!("{}", content);
+    Ok(())
+}
+
+/// `st decode FILE [--to json|classic|markdown]` - turn an archived st
+/// output (COMPRESSED container, quantum stream, marqant, summary-ai)
+/// back into something readable. Detection is by header, so the input can
+/// be any of them - including a container wrapping one of the others.
+fn handle_decode(file: &std::path::Path, to: &str) -> Result<()> {
+    use st::decoders::{decode_auto, DecodeTarget};
+    use std::io::Read;
+
+    let input: Vec<u8> = if file == std::path::Path::new("-") {
+        let mut buf = Vec::new();
+        io::stdin().read_to_end(&mut buf)?;
+        buf
+    } else {
+        std::fs::read(file).with_context(|| format!("Could not read {}", file.display()))?
+    };
+
+    let target = match to {
+        "classic" => DecodeTarget::Classic,
+        "json" => DecodeTarget::Json,
+        "markdown" => DecodeTarget::Markdown,
+        _ => DecodeTarget::Auto,
+    };
+
+    let output = decode_auto(&input, target)?;
+    print!("{}", output);
+    if !output.ends_with('\n') {
+        println!();
+    }
+    Ok(())
+}
+
+/// `st dict train` - build the shared zstd dictionary from sample outputs
+/// and store it under ~/.st for every later --compression zstd run.
+fn handle_dict_train(samples: &[PathBuf], max_size: usize) -> Result<()> {
+    use humansize::{format_size, BINARY};
+    use st::compression_manager::{dictionary_path, train_dictionary};
+
+    let mut data = Vec::with_capacity(samples.len());
+    let mut total_bytes = 0usize;
+    for sample in samples {
+        let bytes = std::fs::read(sample)
+            .with_context(|| format!("Could not read sample {}", sample.display()))?;
+        total_bytes += bytes.len();
+        data.push(bytes);
+    }
+
+    if data.len() < 8 {
+        eprintln!(
+            "⚠️  Only {} sample(s) - dictionaries train best on dozens of outputs",
+            data.len()
+        );
+    }
+
+    let dictionary = train_dictionary(&data, max_size)?;
+
+    let path = dictionary_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, &dictionary)
+        .with_context(|| format!("Could not write dictionary to {}", path.display()))?;
+
+    println!(
+        "📖 Trained a {} dictionary from {} samples ({} total)",
+        format_size(dictionary.len() as u64, BINARY),
+        data.len(),
+        format_size(total_bytes as u64, BINARY)
+    );
+    println!("   Saved to {}", path.display());
+    println!("   Future `-z --compression zstd` runs will use it automatically");
+    Ok(())
+}
+
+/// Tools that change the project, for highlighting in audit output
+const AUDIT_WRITE_TOOLS: &[&str] = &[
+    "smart_edit",
+    "apply_patch",
+    "insert_function",
+    "remove_function",
+    "create_file",
+    "track_file_operation",
+    "clean_old_context",
+];
+
+/// The path-ish argument of an audited call, for display
+fn audit_entry_target(entry: &serde_json::Value) -> Option<String> {
+    let args = entry.get("args")?;
+    args["file_path"]
+        .as_str()
+        .or_else(|| args["path"].as_str())
+        .map(String::from)
+}
+
+fn load_audit_entries(path: &std::path::Path) -> Result<Vec<serde_json::Value>> {
+    st::mcp::audit::read_entries(path).with_context(|| {
+        format!(
+            "No audit trail at {} - is auditing enabled and has an MCP session run here?",
+            st::mcp::audit::log_path(path).display()
+        )
+    })
+}
+
+/// `st audit show` - list the recorded tool calls
+fn handle_audit_show(
+    path: &std::path::Path,
+    tail: Option<usize>,
+    tool: Option<&str>,
+) -> Result<()> {
+    let mut entries = load_audit_entries(path)?;
+    if let Some(tool) = tool {
+        entries.retain(|e| e["tool"] == tool);
+    }
+    let skipped = tail.map_or(0, |n| entries.len().saturating_sub(n));
+
+    println!("🧾 Audit trail: {}", st::mcp::audit::log_path(path).display());
+    if skipped > 0 {
+        println!("   … {} earlier entries …", skipped);
+    }
+    for entry in &entries[skipped..] {
+        let hash = entry["args_hash"].as_str().unwrap_or("?");
+        println!(
+            "{}  {:<24} {}  {}{}",
+            entry["timestamp"].as_str().unwrap_or("?"),
+            entry["tool"].as_str().unwrap_or("?"),
+            &hash[..hash.len().min(12)],
+            entry["caller"].as_str().unwrap_or("?"),
+            audit_entry_target(entry)
+                .map(|t| format!("  {}", t))
+                .unwrap_or_default()
+        );
+    }
+    println!("   {} entries shown", entries.len() - skipped);
+    Ok(())
+}
+
+/// `st audit replay` - narrate what the session(s) did, writes first-class
+fn handle_audit_replay(path: &std::path::Path) -> Result<()> {
+    let entries = load_audit_entries(path)?;
+    if entries.is_empty() {
+        println!("Audit trail is empty - nothing to replay");
+        return Ok(());
+    }
+
+    let mut writes = 0usize;
+    let mut touched = std::collections::BTreeSet::new();
+
+    println!("🎬 Replaying {} recorded calls:\n", entries.len());
+    for entry in &entries {
+        let tool = entry["tool"].as_str().unwrap_or("?");
+        let is_write = AUDIT_WRITE_TOOLS.contains(&tool);
+        if is_write {
+            writes += 1;
+        }
+
+        let detail = match audit_entry_target(entry) {
+            Some(target) => {
+                if is_write {
+                    touched.insert(target.clone());
+                }
+                target
+            }
+            // Redacted trail - the hash still proves which exact call it was
+            None => format!(
+                "args sha256:{}",
+                &entry["args_hash"].as_str().unwrap_or("?")[..12.min(
+                    entry["args_hash"].as_str().unwrap_or("?").len()
+                )]
+            ),
+        };
+
+        println!(
+            "{} {}  {} - {}",
+            if is_write { "✏️ " } else { "👁️ " },
+            entry["timestamp"].as_str().unwrap_or("?"),
+            tool,
+            detail
+        );
+    }
+
+    println!(
+        "\n📊 {} calls, {} writes, by {}",
+        entries.len(),
+        writes,
+        entries
+            .last()
+            .and_then(|e| e["caller"].as_str())
+            .unwrap_or("unknown caller")
+    );
+    if !touched.is_empty() {
+        println!("   Files touched:");
+        for file in &touched {
+            println!("   • {}", file);
+        }
+    }
+    Ok(())
+}
+
+/// `st cache stats` - how much disk the persistent result cache is using
+fn handle_cache_stats() -> Result<()> {
+    let stats = st::result_cache::ResultCache::open()?.stats()?;
+    println!("💾 Scan result cache: {}", stats.dir.display());
+    println!("   Entries: {}", stats.entries);
+    println!(
+        "   Size: {} of {} cap",
+        humansize::format_size(stats.total_bytes, humansize::BINARY),
+        humansize::format_size(stats.max_bytes, humansize::BINARY),
+    );
+    Ok(())
+}
+
+/// `st cache clear` - empty the persistent result cache
+fn handle_cache_clear() -> Result<()> {
+    let removed = st::result_cache::ResultCache::open()?.clear()?;
+    println!("🧹 Removed {} cached scan results", removed);
+    Ok(())
+}
+
+/// `st index build` - scan the tree and persist a trigram index so
+/// subsequent --search calls can skip unchanged non-matching files.
+fn handle_index_build(path: &std::path::Path) -> Result<()> {
+    use st::search_index::SearchIndex;
+
+    let config = st::ScannerConfig {
+        max_depth: usize::MAX,
+        respect_gitignore: true,
+        use_default_ignores: true,
+        ..st::ScannerConfig::default()
+    };
+    let scanner = st::Scanner::new(path, config)?;
+    let root = scanner.root().to_path_buf();
+    let (nodes, _) = scanner.scan()?;
+
+    let index = SearchIndex::build(&root, &nodes)?;
+    let saved_to = index.save()?;
+    println!("🗂️  Indexed {} for instant --search", root.display());
+    println!("   Stored at: {}", saved_to.display());
+    Ok(())
+}
+
+/// `st index status` - report how fresh the stored index is.
+fn handle_index_status(path: &std::path::Path) -> Result<()> {
+    use st::search_index::SearchIndex;
+
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Could not resolve {}", path.display()))?;
+    match SearchIndex::status(&root)? {
+        Some(status) => {
+            let built = chrono::DateTime::<chrono::Utc>::from_timestamp(status.built_at as i64, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            println!("🗂️  Search index for {}", root.display());
+            println!("   Stored at: {} ({} bytes)", status.path.display(), status.size_bytes);
+            println!("   Built: {}", built);
+            println!("   Files: {} ({} trigrams)", status.files, status.trigrams);
+            println!(
+                "   Stale: {} changed, {} missing (searched fresh until rebuild)",
+                status.stale, status.missing
+            );
+        }
+        None => {
+            println!("No search index for {} - run: st index build", root.display());
+        }
+    }
+    Ok(())
+}
+
+/// `st index clear` - delete the stored index for a tree.
+fn handle_index_clear(path: &std::path::Path) -> Result<()> {
+    use st::search_index::SearchIndex;
+
+    let root = path
+        .canonicalize()
+        .with_context(|| format!("Could not resolve {}", path.display()))?;
+    if SearchIndex::clear(&root)? {
+        println!("🗑️  Cleared search index for {}", root.display());
+    } else {
+        println!("No search index for {}", root.display());
+    }
+    Ok(())
+}
+
+/// `st wordcloud` - tokenize file names (and README content), render the
+/// frequencies as an SVG cloud.
+fn handle_wordcloud(
+    path: &std::path::Path,
+    out: &str,
+    theme: String,
+    stopwords: String,
+    max_words: usize,
+    shape: &str,
+    source: &str,
+) -> Result<()> {
+    use st::wordcloud::{collect_words, render_svg, ShapeMask, WordCloudOptions, WordSource};
+
+    let options = WordCloudOptions {
+        theme,
+        stopwords,
+        max_words,
+        shape: match shape {
+            "ellipse" => ShapeMask::Ellipse,
+            _ => ShapeMask::Rect,
+        },
+        source: match source {
+            "names" => WordSource::Names,
+            "readme" => WordSource::Readme,
+            _ => WordSource::Both,
+        },
+    };
+
+    let words = collect_words(path, &options)?;
+    if words.is_empty() {
+        anyhow::bail!("No words found under {}", path.display());
+    }
+    let svg = render_svg(&words, &options);
+
+    if out == "-" {
+        print!("{}", svg);
+    } else {
+        std::fs::write(out, svg).with_context(|| format!("Could not write {}", out))?;
+        eprintln!(
+            "☁️  Word cloud: {} unique words from {} -> {}",
+            words.len(),
+            path.display(),
+            out
+        );
+    }
+    Ok(())
+}
+
+/// Export wave memories from a .st/mem8 store as JSONL - one memory per
+/// line in a plain, documented schema so backups outlive the binary format.
+fn handle_memory_export(
+    output: Option<&std::path::Path>,
+    dir: Option<&std::path::Path>,
+) -> Result<()> {
+    use st::mcp::wave_memory::WaveMemoryManager;
+
+    let store_dir = match dir {
+        Some(d) => d.to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let manager = WaveMemoryManager::new_compact(Some(&store_dir));
+
+    let count = match output {
+        Some(path) => {
+            let mut file = std::fs::File::create(path)
+                .with_context(|| format!("Could not create {}", path.display()))?;
+            manager.export_jsonl(&mut file)?
+        }
+        None => manager.export_jsonl(&mut io::stdout().lock())?,
+    };
+
+    match output {
+        Some(path) => eprintln!("📤 Exported {} memories to {}", count, path.display()),
+        None => eprintln!("📤 Exported {} memories", count),
+    }
+    Ok(())
+}
+
+/// Import wave memories from a JSONL backup into a .st/mem8 store.
+/// Existing ids are kept, not overwritten - live memories win.
+fn handle_memory_import(file: &std::path::Path, dir: Option<&std::path::Path>) -> Result<()> {
+    use st::mcp::wave_memory::WaveMemoryManager;
+
+    let store_dir = match dir {
+        Some(d) => d.to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    let mut manager = WaveMemoryManager::new_compact(Some(&store_dir));
+
+    let (imported, skipped) = if file == std::path::Path::new("-") {
+        let stdin = io::stdin();
+        let mut reader = stdin.lock();
+        manager.import_jsonl(&mut reader)?
+    } else {
+        let f = std::fs::File::open(file)
+            .with_context(|| format!("Could not read {}", file.display()))?;
+        manager.import_jsonl(&mut std::io::BufReader::new(f))?
+    };
+
+    manager.save()?;
+
+    eprintln!(
+        "📥 Imported {} memories ({} skipped as already present)",
+        imported, skipped
+    );
+    Ok(())
+}
+
 /// Handle viewing diffs from the .st folder
 async fn handle_view_diffs() -> Result<()> {
     use st::smart_edit_diff::DiffStorage;
@@ -759,8 +1815,9 @@ async fn check_for_updates_cli() -> Result<String> {
 }
 
 /// run_mcp_server is an async function that starts the MCP server.
-/// When --mcp is passed, we start a server that communicates via stdio.
-async fn run_mcp_server() -> Result<()> {
+/// When --mcp is passed, we start a server that communicates via stdio;
+/// with --listen it speaks the same protocol over HTTP+SSE instead.
+async fn run_mcp_server(listen: Option<&str>) -> Result<()> {
     // Import MCP server components. These are only available if "mcp" feature is enabled.
     use st::mcp::{load_config, McpServer};
 
@@ -768,9 +1825,11 @@ async fn run_mcp_server() -> Result<()> {
     let mcp_config = load_config().unwrap_or_default(); // Load or use defaults.
     let server = McpServer::new(mcp_config);
 
-    // Run the MCP server directly - no need for nested runtime!
-    // `run_stdio` handles communication over stdin/stdout.
-    server.run_stdio().await
+    // Same handlers either way - only the transport differs.
+    match listen {
+        Some(addr) => server.run_http(addr).await,
+        None => server.run_stdio().await,
+    }
 }
 
 /// Run the Smart Tree Terminal Interface - Your coding companion! (requires `tui` feature)
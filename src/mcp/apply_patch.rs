@@ -0,0 +1,459 @@
+// apply_patch - standard unified diffs over MCP
+//
+// smart_edit speaks AST; this speaks `diff -u`. Models often already hold a
+// unified diff (from git, from a reviewer, from their own head), and
+// re-expressing it as AST edits would be silly. We validate the whole patch
+// first, apply it with GNU-patch-style fuzz (hunks relocated when line
+// numbers drifted, context trimmed one line per fuzz level), and store the
+// applied diff in the same .st_bumpers audit trail smart_edit uses.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+/// Maximum context lines trimmed from each hunk end while fuzzing,
+/// mirroring `patch`'s default fuzz factor.
+const MAX_FUZZ: usize = 2;
+
+/// One hunk: the 1-based old-file start line and tagged lines
+/// (' ' context, '-' removal, '+' addition).
+#[derive(Debug, Clone)]
+struct Hunk {
+    old_start: usize,
+    lines: Vec<(char, String)>,
+}
+
+/// One file section of a unified diff. `None` paths are /dev/null -
+/// old None means creation, new None means deletion.
+#[derive(Debug)]
+struct FilePatch {
+    old_path: Option<String>,
+    new_path: Option<String>,
+    hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    fn target(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or("/dev/null")
+    }
+}
+
+/// Paths a patch would touch, for scope checks before anything is applied.
+/// An unparseable patch yields nothing - the handler will reject it anyway.
+pub(crate) fn patch_targets(text: &str) -> Vec<PathBuf> {
+    parse_patch(text)
+        .map(|patches| patches.iter().map(|p| PathBuf::from(p.target())).collect())
+        .unwrap_or_default()
+}
+
+/// Strip git's a/ b/ prefixes; /dev/null becomes `None`.
+fn parse_header_path(raw: &str) -> Option<String> {
+    // Headers may carry a timestamp after a tab - drop it
+    let path = raw.split('\t').next().unwrap_or(raw).trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(path.to_string())
+}
+
+/// Parse `@@ -l[,s] +l[,s] @@` into the old-file start line.
+fn parse_hunk_header(line: &str) -> Result<usize> {
+    let inner = line
+        .strip_prefix("@@ -")
+        .and_then(|rest| rest.split(" @@").next())
+        .ok_or_else(|| anyhow!("Malformed hunk header: {}", line))?;
+    let old_part = inner
+        .split(' ')
+        .next()
+        .ok_or_else(|| anyhow!("Malformed hunk header: {}", line))?;
+    let old_start = old_part
+        .split(',')
+        .next()
+        .and_then(|n| n.parse::<usize>().ok())
+        .ok_or_else(|| anyhow!("Malformed hunk header: {}", line))?;
+    Ok(old_start)
+}
+
+/// Parse a (possibly multi-file) unified diff. Lines outside ---/+++/@@
+/// sections (git's `diff --git`, `index`, mail headers) are skipped, as are
+/// `\ No newline at end of file` markers.
+fn parse_patch(text: &str) -> Result<Vec<FilePatch>> {
+    let mut patches: Vec<FilePatch> = Vec::new();
+    let mut lines = text.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(old_raw) = line.strip_prefix("--- ") {
+            let plus = lines
+                .next()
+                .and_then(|l| l.strip_prefix("+++ "))
+                .ok_or_else(|| anyhow!("'--- {}' is not followed by a +++ line", old_raw))?;
+            let mut patch = FilePatch {
+                old_path: parse_header_path(old_raw),
+                new_path: parse_header_path(plus),
+                hunks: Vec::new(),
+            };
+            if patch.old_path.is_none() && patch.new_path.is_none() {
+                anyhow::bail!("Patch section has /dev/null on both sides");
+            }
+
+            while let Some(&next) = lines.peek() {
+                if !next.starts_with("@@ -") {
+                    break;
+                }
+                let header = lines.next().expect("peeked");
+                let mut hunk = Hunk {
+                    old_start: parse_hunk_header(header)?,
+                    lines: Vec::new(),
+                };
+                while let Some(&body) = lines.peek() {
+                    match body.chars().next() {
+                        Some(' ') | Some('-') | Some('+') => {
+                            let body = lines.next().expect("peeked");
+                            hunk.lines
+                                .push((body.chars().next().unwrap(), body[1..].to_string()));
+                        }
+                        Some('\\') => {
+                            // "\ No newline at end of file" - metadata only
+                            lines.next();
+                        }
+                        // Empty lines inside a hunk are context with the
+                        // leading space eaten by transport - keep them
+                        None => {
+                            lines.next();
+                            hunk.lines.push((' ', String::new()));
+                        }
+                        _ => break,
+                    }
+                }
+                if hunk.lines.is_empty() {
+                    anyhow::bail!("Empty hunk in section for {}", patch.target());
+                }
+                patch.hunks.push(hunk);
+            }
+
+            if patch.hunks.is_empty() {
+                anyhow::bail!("No hunks in section for {}", patch.target());
+            }
+            patches.push(patch);
+        }
+    }
+
+    Ok(patches)
+}
+
+/// Apply every hunk of one file section to `content`, returning the new
+/// content plus a per-hunk report (where it landed, offset, fuzz used).
+fn apply_file_patch(content: &str, patch: &FilePatch) -> Result<(String, Vec<Value>)> {
+    // Preserve the file's own convention; a creation from nothing has no
+    // convention to preserve, so the patch lines stand as joined.
+    let keep_trailing_newline = content.ends_with('\n');
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let mut offset: i64 = 0;
+    let mut reports = Vec::new();
+
+    for (index, hunk) in patch.hunks.iter().enumerate() {
+        let old: Vec<&str> = hunk
+            .lines
+            .iter()
+            .filter(|(tag, _)| *tag != '+')
+            .map(|(_, l)| l.as_str())
+            .collect();
+        let new: Vec<&str> = hunk
+            .lines
+            .iter()
+            .filter(|(tag, _)| *tag != '-')
+            .map(|(_, l)| l.as_str())
+            .collect();
+        let leading_ctx = hunk.lines.iter().take_while(|(tag, _)| *tag == ' ').count();
+        let trailing_ctx = hunk
+            .lines
+            .iter()
+            .rev()
+            .take_while(|(tag, _)| *tag == ' ')
+            .count();
+
+        let expected = (hunk.old_start as i64 - 1 + offset).clamp(0, lines.len() as i64) as usize;
+
+        let mut applied = false;
+        for fuzz in 0..=MAX_FUZZ {
+            let lead = fuzz.min(leading_ctx);
+            let trail = fuzz.min(trailing_ctx);
+            if lead + trail > old.len() {
+                break;
+            }
+            let old_f = &old[lead..old.len() - trail];
+            let new_f = &new[lead..new.len() - trail];
+
+            let position = if old_f.is_empty() {
+                // Pure insertion (new or empty file) - trust the header
+                Some(expected.min(lines.len()))
+            } else {
+                find_near(&lines, old_f, expected + lead)
+            };
+
+            if let Some(position) = position {
+                lines.splice(
+                    position..position + old_f.len(),
+                    new_f.iter().map(|l| l.to_string()),
+                );
+                offset += new_f.len() as i64 - old_f.len() as i64;
+                reports.push(json!({
+                    "hunk": index + 1,
+                    "applied_at_line": position + 1,
+                    "offset": position as i64 - (hunk.old_start as i64 - 1 + lead as i64),
+                    "fuzz": fuzz,
+                }));
+                applied = true;
+                break;
+            }
+        }
+
+        if !applied {
+            anyhow::bail!(
+                "Hunk #{} failed to apply to {} (context not found near line {}, even with fuzz {})",
+                index + 1,
+                patch.target(),
+                hunk.old_start,
+                MAX_FUZZ
+            );
+        }
+    }
+
+    let mut result = lines.join("\n");
+    if keep_trailing_newline && !result.is_empty() {
+        result.push('\n');
+    }
+    Ok((result, reports))
+}
+
+/// Find `needle` in `haystack`, preferring the match closest to `expected`
+/// (searching outward in both directions, like patch does).
+fn find_near(haystack: &[String], needle: &[&str], expected: usize) -> Option<usize> {
+    let limit = haystack.len().saturating_sub(needle.len());
+    let matches_at =
+        |pos: usize| haystack[pos..pos + needle.len()].iter().zip(needle).all(|(a, b)| a == *b);
+
+    let start = expected.min(limit);
+    if haystack.len() >= needle.len() && matches_at(start) {
+        return Some(start);
+    }
+    for distance in 1..=limit.max(start) {
+        if distance <= start && matches_at(start - distance) {
+            return Some(start - distance);
+        }
+        let forward = start + distance;
+        if forward <= limit && matches_at(forward) {
+            return Some(forward);
+        }
+    }
+    None
+}
+
+/// The `apply_patch` tool: validate every file section first, then apply
+/// them all (or none). `dry_run` returns the would-be contents unwritten.
+pub async fn handle_apply_patch(params: Option<Value>) -> Result<Value> {
+    let params = params.context("Parameters required")?;
+    let patch_text = params["patch"].as_str().context("patch required")?;
+    let dry_run = params["dry_run"].as_bool().unwrap_or(false);
+
+    let patches = parse_patch(patch_text)?;
+    if patches.is_empty() {
+        anyhow::bail!("No file sections found in patch (expected ---/+++ headers)");
+    }
+
+    // Phase 1: validate - compute every new content before touching disk,
+    // so a failing hunk in file three doesn't leave files one and two
+    // half-patched
+    struct Planned {
+        target: PathBuf,
+        original: String,
+        /// None = delete the file
+        new_content: Option<String>,
+        hunks: Vec<Value>,
+    }
+    let mut planned = Vec::with_capacity(patches.len());
+
+    for patch in &patches {
+        match (&patch.old_path, &patch.new_path) {
+            // Creation: every hunk line must be an addition
+            (None, Some(new_path)) => {
+                let target = PathBuf::from(new_path);
+                if target.exists() {
+                    anyhow::bail!("Patch creates {} but it already exists", new_path);
+                }
+                let (content, hunks) = apply_file_patch("", patch)?;
+                planned.push(Planned {
+                    target,
+                    original: String::new(),
+                    new_content: Some(content),
+                    hunks,
+                });
+            }
+            // Deletion: hunks must match the current content
+            (Some(old_path), None) => {
+                let target = PathBuf::from(old_path);
+                let original = std::fs::read_to_string(&target)
+                    .with_context(|| format!("Cannot read {} (to be deleted)", old_path))?;
+                let (content, hunks) = apply_file_patch(&original, patch)?;
+                if !content.trim().is_empty() {
+                    anyhow::bail!("Patch deletes {} but doesn't remove all content", old_path);
+                }
+                planned.push(Planned {
+                    target,
+                    original,
+                    new_content: None,
+                    hunks,
+                });
+            }
+            (Some(old_path), Some(_)) => {
+                let target = PathBuf::from(patch.target());
+                let original = std::fs::read_to_string(old_path)
+                    .with_context(|| format!("Cannot read {}", old_path))?;
+                let (content, hunks) = apply_file_patch(&original, patch)?;
+                planned.push(Planned {
+                    target,
+                    original,
+                    new_content: Some(content),
+                    hunks,
+                });
+            }
+            (None, None) => unreachable!("rejected in parse_patch"),
+        }
+    }
+
+    // Phase 2: report (dry run) or write, with the same .st_bumpers diff
+    // trail smart_edit keeps
+    let storage = if dry_run {
+        None
+    } else {
+        std::env::current_dir()
+            .ok()
+            .and_then(|root| crate::smart_edit_diff::DiffStorage::new(&root).ok())
+    };
+
+    let mut files = Vec::with_capacity(planned.len());
+    for plan in &planned {
+        let action = match (&plan.new_content, plan.original.is_empty()) {
+            (None, _) => "delete",
+            (Some(_), true) => "create",
+            (Some(_), false) => "modify",
+        };
+        let mut report = json!({
+            "file_path": plan.target.display().to_string(),
+            "action": action,
+            "hunks": plan.hunks,
+        });
+
+        if dry_run {
+            if let Some(ref content) = plan.new_content {
+                report["result"] = json!(content);
+            }
+        } else {
+            if let Some(storage) = &storage {
+                let _ = storage.store_original(&plan.target, &plan.original);
+                let _ = storage.store_diff(
+                    &plan.target,
+                    &plan.original,
+                    plan.new_content.as_deref().unwrap_or(""),
+                );
+            }
+            match &plan.new_content {
+                Some(content) => {
+                    if let Some(parent) = plan.target.parent() {
+                        if !parent.as_os_str().is_empty() {
+                            std::fs::create_dir_all(parent)?;
+                        }
+                    }
+                    std::fs::write(&plan.target, content)?;
+                }
+                None => std::fs::remove_file(&plan.target)?,
+            }
+        }
+        files.push(report);
+    }
+
+    let result = json!({
+        "dry_run": dry_run,
+        "files_patched": files.len(),
+        "files": files,
+    });
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&result)?
+        }]
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = "fn main() {\n    let x = 1;\n    let y = 2;\n    println!(\"{}\", x + y);\n}\n";
+
+    #[test]
+    fn test_apply_clean_patch() {
+        let patch_text = "--- a/main.rs\n+++ b/main.rs\n@@ -1,5 +1,5 @@\n fn main() {\n     let x = 1;\n-    let y = 2;\n+    let y = 3;\n     println!(\"{}\", x + y);\n }\n";
+        let patches = parse_patch(patch_text).unwrap();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].target(), "main.rs");
+
+        let (result, reports) = apply_file_patch(ORIGINAL, &patches[0]).unwrap();
+        assert!(result.contains("let y = 3;"));
+        assert!(!result.contains("let y = 2;"));
+        assert_eq!(reports[0]["fuzz"], 0);
+        assert_eq!(reports[0]["offset"], 0);
+    }
+
+    #[test]
+    fn test_hunk_relocates_when_lines_drifted() {
+        // Same hunk, but the file grew two lines above it
+        let drifted = format!("// new comment\n// another\n{}", ORIGINAL);
+        let patch_text = "--- a/main.rs\n+++ b/main.rs\n@@ -1,5 +1,5 @@\n fn main() {\n     let x = 1;\n-    let y = 2;\n+    let y = 3;\n     println!(\"{}\", x + y);\n }\n";
+        let patches = parse_patch(patch_text).unwrap();
+
+        let (result, reports) = apply_file_patch(&drifted, &patches[0]).unwrap();
+        assert!(result.contains("let y = 3;"));
+        assert!(result.starts_with("// new comment"));
+        assert_eq!(reports[0]["offset"], 2);
+    }
+
+    #[test]
+    fn test_fuzz_tolerates_changed_context() {
+        // Outermost context lines no longer match - fuzz must kick in
+        let changed = ORIGINAL.replace("fn main() {", "fn main() { // entry");
+        let patch_text = "--- a/main.rs\n+++ b/main.rs\n@@ -1,5 +1,5 @@\n fn main() {\n     let x = 1;\n-    let y = 2;\n+    let y = 3;\n     println!(\"{}\", x + y);\n }\n";
+        let patches = parse_patch(patch_text).unwrap();
+
+        let (result, reports) = apply_file_patch(&changed, &patches[0]).unwrap();
+        assert!(result.contains("let y = 3;"));
+        let fuzz = reports[0]["fuzz"].as_u64().unwrap();
+        assert!(fuzz > 0, "expected a fuzzy application, got exact");
+    }
+
+    #[test]
+    fn test_unmatchable_hunk_fails() {
+        let patch_text = "--- a/main.rs\n+++ b/main.rs\n@@ -1,3 +1,3 @@\n struct Totally {\n-    different: bool,\n+    different: u8,\n }\n";
+        let patches = parse_patch(patch_text).unwrap();
+        let err = apply_file_patch(ORIGINAL, &patches[0]).unwrap_err();
+        assert!(err.to_string().contains("failed to apply"));
+    }
+
+    #[test]
+    fn test_parse_multi_file_with_git_noise() {
+        let patch_text = "diff --git a/one.rs b/one.rs\nindex 123..456 100644\n--- a/one.rs\n+++ b/one.rs\n@@ -1,1 +1,1 @@\n-old\n+new\ndiff --git a/two.rs b/two.rs\n--- /dev/null\n+++ b/two.rs\n@@ -0,0 +1,2 @@\n+line one\n+line two\n";
+        let patches = parse_patch(patch_text).unwrap();
+        assert_eq!(patches.len(), 2);
+        assert!(patches[1].old_path.is_none(), "second section is a creation");
+
+        let (created, _) = apply_file_patch("", &patches[1]).unwrap();
+        assert_eq!(created, "line one\nline two");
+    }
+}
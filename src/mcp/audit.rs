@@ -0,0 +1,126 @@
+//! Compliance audit trail for MCP tool calls
+//!
+//! Every tools/call is appended as one JSON line to the project's
+//! `.st/audit.jsonl`: timestamp, tool name, a SHA-256 of the arguments,
+//! and who called (the clientInfo from initialize). With
+//! `audit_redact_args` on, only the hash is kept - enough to prove *what*
+//! ran without storing the contents. `st audit show|replay` reads it back.
+//!
+//! Logging must never break a tool call, so every failure here is
+//! swallowed silently - an audit trail that crashes the server audits
+//! nothing, as Trish would say.
+
+use crate::mcp::McpConfig;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Client identity from the initialize handshake, e.g. "cursor 1.2.3"
+static CALLER: Mutex<Option<String>> = Mutex::new(None);
+
+/// Remember who connected (called from handle_initialize)
+pub fn record_caller(params: &Value) {
+    if let Some(info) = params.get("clientInfo") {
+        let name = info["name"].as_str().unwrap_or("unknown");
+        let version = info["version"].as_str().unwrap_or("?");
+        if let Ok(mut caller) = CALLER.lock() {
+            *caller = Some(format!("{} {}", name, version));
+        }
+    }
+}
+
+fn caller() -> String {
+    CALLER
+        .lock()
+        .ok()
+        .and_then(|c| c.clone())
+        .unwrap_or_else(|| format!("pid:{}", std::process::id()))
+}
+
+/// Where the trail lives for a given project root
+pub fn log_path(project_root: &Path) -> PathBuf {
+    project_root.join(".st").join("audit.jsonl")
+}
+
+/// Hex SHA-256 of the arguments as sent (canonical serde_json string)
+pub fn args_hash(args: &Value) -> String {
+    let serialized = serde_json::to_string(args).unwrap_or_default();
+    hex::encode(Sha256::digest(serialized.as_bytes()))
+}
+
+/// Append one tool call to the trail. No-op when auditing is off;
+/// IO errors are deliberately ignored (see module docs).
+pub fn record_tool_call(tool: &str, args: &Value, config: &McpConfig) {
+    if !config.audit_enabled {
+        return;
+    }
+
+    let mut entry = json!({
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "tool": tool,
+        "args_hash": args_hash(args),
+        "caller": caller(),
+    });
+    if !config.audit_redact_args {
+        entry["args"] = args.clone();
+    }
+
+    let _ = append_entry(&log_path(Path::new(".")), &entry);
+}
+
+fn append_entry(path: &Path, entry: &Value) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", entry)
+}
+
+/// Read the trail back, oldest first. Unparseable lines are skipped so a
+/// half-written tail (server killed mid-append) doesn't hide the rest.
+pub fn read_entries(project_root: &Path) -> std::io::Result<Vec<Value>> {
+    let content = std::fs::read_to_string(log_path(project_root))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_args_hash_is_stable_and_content_sensitive() {
+        let a = json!({"path": "/work", "mode": "ai"});
+        let b = json!({"path": "/work", "mode": "ai"});
+        let c = json!({"path": "/work", "mode": "classic"});
+        assert_eq!(args_hash(&a), args_hash(&b));
+        assert_ne!(args_hash(&a), args_hash(&c));
+        assert_eq!(args_hash(&a).len(), 64); // hex sha256
+    }
+
+    #[test]
+    fn test_append_and_read_roundtrip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = log_path(temp_dir.path());
+
+        append_entry(&path, &json!({"tool": "quick_tree", "args_hash": "abc"})).unwrap();
+        append_entry(&path, &json!({"tool": "smart_edit", "args_hash": "def"})).unwrap();
+        // A torn line must not hide the good ones
+        {
+            let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+            write!(file, "{{\"tool\": \"trunc").unwrap();
+        }
+
+        let entries = read_entries(temp_dir.path()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["tool"], "quick_tree");
+        assert_eq!(entries[1]["tool"], "smart_edit");
+    }
+}
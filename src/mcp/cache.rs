@@ -1,6 +1,9 @@
 //! Cache implementation for MCP server
 
+use crate::scanner::{FileNode, Scanner, ScannerConfig, TreeStats};
 use dashmap::DashMap;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Cached entry with expiration
@@ -93,6 +96,86 @@ impl AnalysisCache {
     }
 }
 
+/// How long a shared scan result stays valid. Deliberately short: the point
+/// is letting quick_tree → get_statistics → analyze_directory reuse one
+/// traversal, not serving stale trees minutes later.
+pub const SCAN_CACHE_TTL_SECS: u64 = 30;
+
+/// One cached traversal, shared by reference with every tool that asks.
+struct ScanEntry {
+    result: Arc<(Vec<FileNode>, TreeStats)>,
+    expires_at: Instant,
+}
+
+/// Short-lived shared scan results, keyed by (path, scanner options).
+///
+/// Agent workflows fire quick_tree, get_statistics, and analyze_directory
+/// back-to-back at the same path; without this each call re-walks the tree.
+/// Any two calls whose `ScannerConfig` matches exactly share one scan -
+/// differing options (depth, hidden, filters) still scan fresh, so no tool
+/// ever sees a tree shaped by another tool's flags.
+pub struct ScanCache {
+    entries: DashMap<String, ScanEntry>,
+    ttl: Duration,
+}
+
+impl ScanCache {
+    /// Create a scan cache with the given TTL in seconds
+    pub fn new(ttl_seconds: u64) -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl: Duration::from_secs(ttl_seconds),
+        }
+    }
+
+    /// Return the cached traversal for this exact (path, options) pair, or
+    /// run the scan and cache it. The result arrives behind an `Arc` so
+    /// concurrent tools share one node list instead of cloning it.
+    pub fn get_or_scan(
+        &self,
+        path: &Path,
+        config: ScannerConfig,
+    ) -> anyhow::Result<Arc<(Vec<FileNode>, TreeStats)>> {
+        // ScannerConfig's Debug output covers every option, so new scanner
+        // flags can never silently alias two different scans to one key.
+        let key = format!("{}|{:?}", path.display(), config);
+
+        if let Some(entry) = self.entries.get(&key) {
+            if entry.expires_at > Instant::now() {
+                return Ok(entry.result.clone());
+            }
+            drop(entry);
+            self.entries.remove(&key);
+        }
+
+        let scanner = Scanner::new(path, config)?;
+        let result = Arc::new(scanner.scan()?);
+        self.entries.insert(
+            key,
+            ScanEntry {
+                result: result.clone(),
+                expires_at: Instant::now() + self.ttl,
+            },
+        );
+        Ok(result)
+    }
+
+    /// Drop every cached scan (e.g. after an edit tool changed the tree)
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached traversals currently held
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the scan cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
 /// Cache statistics
 pub struct CacheStats {
     pub entries: usize,
@@ -3,7 +3,7 @@
 //! Now powered by Wave Memory for semantic storage and resonance-based retrieval!
 
 use crate::context_gatherer::{ContextGatherer, GatherConfig, GatheredContext};
-use crate::mcp::wave_memory::{get_wave_memory, MemoryType};
+use crate::mcp::wave_memory::{get_wave_memory, resolve_scope, MemoryType};
 use anyhow::{Context as _, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -355,6 +355,10 @@ pub struct AnchorMemoryRequest {
     pub origin: String,
     /// Project path to associate with
     pub project_path: Option<String>,
+    /// Memory scope: "global", "user", or "project" (defaults to project when
+    /// a project_path is given, otherwise the current user)
+    #[serde(default)]
+    pub scope: Option<String>,
 }
 
 /// Default origin for collaborative memories - the beautiful human-AI partnership! 🤝
@@ -371,6 +375,7 @@ pub async fn anchor_collaborative_memory(
     _permission_check: impl Fn(serde_json::Value) -> Result<bool>,
 ) -> Result<Value> {
     let project_path = req.project_path.as_ref().map(PathBuf::from);
+    let scope = resolve_scope(req.scope.as_deref(), project_path.as_deref());
 
     // Convert anchor type to memory type for wave storage
     let memory_type = MemoryType::parse(&req.anchor_type);
@@ -392,6 +397,7 @@ pub async fn anchor_collaborative_memory(
             arousal,
             req.origin.clone(),
             project_path.clone(),
+            scope,
         )
     };
 
@@ -542,6 +548,9 @@ pub struct FindMemoriesRequest {
     pub memory_type: Option<String>,
     /// Minimum resonance threshold (0.0 to 1.0, default 0.3)
     pub resonance_threshold: Option<f32>,
+    /// Memory scope to search: "global", "user", or "project"
+    #[serde(default)]
+    pub scope: Option<String>,
 }
 
 /// Find previously anchored collaborative memories
@@ -554,6 +563,10 @@ pub async fn find_collaborative_memories(
     _permission_check: impl Fn(serde_json::Value) -> Result<bool>,
 ) -> Result<Value> {
     let max_results = req.max_results.unwrap_or(10);
+    let scope = resolve_scope(
+        req.scope.as_deref(),
+        req.project_path.as_ref().map(std::path::Path::new),
+    );
 
     // Use wave memory for search
     let wave_memory = get_wave_memory();
@@ -577,6 +590,7 @@ pub async fn find_collaborative_memories(
             memory_type,
             threshold,
             max_results,
+            &scope,
         );
 
         let memories: Vec<_> = results
@@ -607,7 +621,7 @@ pub async fn find_collaborative_memories(
         }))
     } else {
         // Keyword search: fast lookup
-        let results = manager.find_by_keywords(&req.keywords, max_results);
+        let results = manager.find_by_keywords(&req.keywords, max_results, &scope);
 
         let memories: Vec<_> = results
             .iter()
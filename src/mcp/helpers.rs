@@ -64,14 +64,23 @@ impl ScannerConfigBuilder {
                 show_hidden: false,
                 show_ignored: false,
                 find_pattern: None,
+                glob_filter: None,
                 file_type_filter: None,
                 entry_type_filter: None,
+                skip_special_files: false,
+                git_filter: None,
+                detect_content: false,
                 min_size: None,
                 max_size: None,
                 newer_than: None,
                 older_than: None,
                 use_default_ignores: true,
                 search_keyword: None,
+                search_patterns: Vec::new(),
+                search_regex: false,
+                search_ignore_case: false,
+                search_whole_word: false,
+                search_match_all: false,
                 show_filesystems: false,
                 sort_field: None,
                 top_n: None,
@@ -84,6 +93,17 @@ impl ScannerConfigBuilder {
                 changes_only: false,
                 compare_state: None,
                 smart_mode: false,
+                scan_archives: false,
+                dedupe_hardlinks: false,
+                use_allocated_size: false,
+                compute_dir_sizes: false,
+                list_ads: false,
+                collect_xattrs: false,
+                dirs_first: false,
+                files_first: false,
+                prune_patterns: Vec::new(),
+                max_files_per_dir: None,
+                scan_budget: Default::default(),
             },
         }
     }
@@ -139,6 +159,11 @@ impl ScannerConfigBuilder {
         self
     }
 
+    pub fn glob_filter(mut self, filter: Option<crate::scanner::GlobFilter>) -> Self {
+        self.config.glob_filter = filter;
+        self
+    }
+
     pub fn file_type_filter(mut self, filter: Option<String>) -> Self {
         self.config.file_type_filter = filter;
         self
@@ -174,6 +199,27 @@ impl ScannerConfigBuilder {
         self
     }
 
+    pub fn search_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.config.search_patterns = patterns;
+        self
+    }
+
+    /// Set the content-search options (regex, case folding, whole-word,
+    /// AND-across-patterns) in one call.
+    pub fn search_options(
+        mut self,
+        regex: bool,
+        ignore_case: bool,
+        whole_word: bool,
+        match_all: bool,
+    ) -> Self {
+        self.config.search_regex = regex;
+        self.config.search_ignore_case = ignore_case;
+        self.config.search_whole_word = whole_word;
+        self.config.search_match_all = match_all;
+        self
+    }
+
     pub fn include_line_content(mut self, include: bool) -> Self {
         self.config.include_line_content = include;
         self
@@ -195,9 +241,94 @@ impl Default for ScannerConfigBuilder {
     }
 }
 
+/// Cursor-based pagination for tools that return large lists
+///
+/// Opt-in: a tool only paginates when the caller sends `cursor` and/or
+/// `page_size`, so existing clients keep getting full lists. The cursor is
+/// an opaque `st:<offset>` token - echo `next_cursor` from one response as
+/// `cursor` in the next call. Ordering must be stable between calls (sort
+/// before slicing!) or pages will drift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub offset: usize,
+    pub page_size: usize,
+}
+
+/// Default entries per page when `page_size` is omitted
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+/// Hard ceiling on `page_size` - one page should never blow a context window
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+impl PageRequest {
+    /// Build from raw `cursor`/`page_size` values. Returns `None` when the
+    /// caller asked for neither (i.e., wants the whole list).
+    pub fn from_parts(cursor: Option<&str>, page_size: Option<usize>) -> Result<Option<Self>> {
+        if cursor.is_none() && page_size.is_none() {
+            return Ok(None);
+        }
+        let offset = match cursor {
+            Some(token) => token
+                .strip_prefix("st:")
+                .and_then(|o| o.parse().ok())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Invalid cursor '{}' - pass the next_cursor from the previous page",
+                        token
+                    )
+                })?,
+            None => 0,
+        };
+        let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        Ok(Some(Self { offset, page_size }))
+    }
+
+    /// Slice one page and describe it. The metadata carries `total`,
+    /// `returned`, `offset`, and `next_cursor` (null on the last page).
+    pub fn page<'a, T>(&self, items: &'a [T]) -> (&'a [T], serde_json::Value) {
+        let start = self.offset.min(items.len());
+        let end = (start + self.page_size).min(items.len());
+        let next_cursor = if end < items.len() {
+            serde_json::json!(format!("st:{}", end))
+        } else {
+            serde_json::Value::Null
+        };
+        (
+            &items[start..end],
+            serde_json::json!({
+                "total": items.len(),
+                "returned": end - start,
+                "offset": start,
+                "next_cursor": next_cursor,
+            }),
+        )
+    }
+}
+
+/// Read opt-in pagination straight from a tool's raw JSON arguments.
+pub fn page_request(args: &serde_json::Value) -> Result<Option<PageRequest>> {
+    PageRequest::from_parts(
+        args.get("cursor").and_then(|c| c.as_str()),
+        args.get("page_size").and_then(|p| p.as_u64()).map(|p| p as usize),
+    )
+}
+
 /// Scan a directory with the given configuration
 /// Returns (nodes, stats) tuple
 pub fn scan_with_config(path: &Path, config: ScannerConfig) -> Result<(Vec<FileNode>, TreeStats)> {
     let scanner = Scanner::new(path, config)?;
     scanner.scan()
 }
+
+/// Scan through the context's short-lived scan cache, so consecutive tool
+/// calls with identical (path, options) reuse one traversal. Falls back to
+/// a direct scan when caching is disabled in the server config.
+pub fn scan_shared(
+    path: &Path,
+    config: ScannerConfig,
+    ctx: &McpContext,
+) -> Result<std::sync::Arc<(Vec<FileNode>, TreeStats)>> {
+    if !ctx.config.cache_enabled {
+        return Ok(std::sync::Arc::new(scan_with_config(path, config)?));
+    }
+    ctx.scan_cache.get_or_scan(path, config)
+}
@@ -0,0 +1,224 @@
+//! Streamable HTTP + SSE transport for the MCP server
+//!
+//! stdio suits Claude Desktop, but plenty of agents speak MCP over HTTP.
+//! `st --mcp --listen 127.0.0.1:8421` serves the exact same JSON-RPC
+//! handlers here - every request funnels through `McpServer::handle_request`,
+//! so path security, rate limits, session grants and the audit trail behave
+//! identically on both transports.
+//!
+//! Protocol (MCP streamable HTTP):
+//! - POST /mcp with a JSON-RPC body; the reply is plain JSON, or a one-shot
+//!   SSE stream when the client sends `Accept: text/event-stream`
+//! - `initialize` mints an `Mcp-Session-Id` header the client must echo on
+//!   every later request; an unknown id gets 404 (re-initialize)
+//! - GET /mcp opens a keep-alive SSE stream for server notifications
+//! - DELETE /mcp ends the session
+
+use super::McpServer;
+use anyhow::{Context, Result};
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::post,
+    Json, Router,
+};
+use futures::stream;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Transport state: the server itself plus the live session ids
+struct HttpState {
+    server: McpServer,
+    sessions: Mutex<HashSet<String>>,
+}
+
+impl HttpState {
+    fn sessions(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl McpServer {
+    /// Serve MCP over streamable HTTP + SSE instead of stdio
+    pub async fn run_http(self, addr: &str) -> Result<()> {
+        if !(addr.starts_with("127.") || addr.starts_with("localhost") || addr.starts_with("[::1]"))
+        {
+            eprintln!(
+                "⚠️  {} is not loopback - every MCP tool will be reachable from the network",
+                addr
+            );
+        }
+
+        let state = Arc::new(HttpState {
+            server: self,
+            sessions: Mutex::new(HashSet::new()),
+        });
+        let endpoints = post(handle_post).get(handle_open_sse).delete(handle_end_session);
+        let app = Router::new()
+            .route("/mcp", endpoints.clone())
+            .route("/", endpoints)
+            // Bidirectional watch stream - same events as watch_directory_sse
+            .route("/ws/watch", axum::routing::get(handle_ws_upgrade))
+            .with_state(state);
+
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Cannot listen on {}", addr))?;
+        eprintln!(
+            "🌐 MCP over HTTP+SSE at http://{}/mcp (sessions via {} header)",
+            addr, SESSION_HEADER
+        );
+        axum::serve(listener, app).await?;
+        Ok(())
+    }
+}
+
+/// POST / - one JSON-RPC request in, one response out (JSON or SSE)
+async fn handle_post(
+    State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let parsed: Value = match serde_json::from_str(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            return rpc_error(
+                StatusCode::BAD_REQUEST,
+                -32700,
+                format!("Parse error: {}", e),
+            )
+        }
+    };
+
+    // Session discipline: initialize mints an id, everything else echoes it
+    let session_id = if parsed["method"] == "initialize" {
+        let id = uuid::Uuid::new_v4().to_string();
+        state.sessions().insert(id.clone());
+        id
+    } else {
+        match presented_session(&headers) {
+            Some(id) if state.sessions().contains(&id) => id,
+            // 404 tells a well-behaved client to re-initialize
+            Some(_) => {
+                return rpc_error(
+                    StatusCode::NOT_FOUND,
+                    -32001,
+                    "Unknown or expired session - initialize again".to_string(),
+                )
+            }
+            None => {
+                return rpc_error(
+                    StatusCode::BAD_REQUEST,
+                    -32600,
+                    format!("Missing {} header - initialize first", SESSION_HEADER),
+                )
+            }
+        }
+    };
+
+    // Same entry point the stdio loop uses - one string in, one string out
+    let response_body = match state.server.handle_request(&body).await {
+        Ok(s) => s,
+        Err(e) => return rpc_error(StatusCode::INTERNAL_SERVER_ERROR, -32603, e.to_string()),
+    };
+
+    // Notifications produce no response - 202 per the streamable HTTP spec
+    if response_body.is_empty() {
+        return with_session(StatusCode::ACCEPTED.into_response(), &session_id);
+    }
+
+    let wants_sse = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"));
+
+    let response = if wants_sse {
+        let events = stream::iter(vec![Ok::<_, Infallible>(
+            Event::default().event("message").data(response_body),
+        )]);
+        Sse::new(events).into_response()
+    } else {
+        (
+            [(header::CONTENT_TYPE, "application/json")],
+            response_body,
+        )
+            .into_response()
+    };
+    with_session(response, &session_id)
+}
+
+/// GET /ws/watch - upgrade to the WebSocket watch protocol (see
+/// `ws_watch`). No session header needed: the socket carries its own
+/// handshake, and path policy is enforced when the stream starts.
+async fn handle_ws_upgrade(
+    State(state): State<Arc<HttpState>>,
+    ws: axum::extract::ws::WebSocketUpgrade,
+) -> Response {
+    let ctx = state.server.context.clone();
+    ws.on_upgrade(move |socket| super::ws_watch::handle_ws_watch(socket, ctx))
+}
+
+/// GET / - long-lived SSE stream for server-initiated messages
+async fn handle_open_sse(State(state): State<Arc<HttpState>>, headers: HeaderMap) -> Response {
+    match presented_session(&headers) {
+        Some(id) if state.sessions().contains(&id) => {
+            let stream = stream::pending::<std::result::Result<Event, Infallible>>();
+            with_session(
+                Sse::new(stream).keep_alive(KeepAlive::default()).into_response(),
+                &id,
+            )
+        }
+        _ => rpc_error(
+            StatusCode::NOT_FOUND,
+            -32001,
+            "Unknown session - initialize first".to_string(),
+        ),
+    }
+}
+
+/// DELETE / - the client is done with its session
+async fn handle_end_session(
+    State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
+) -> StatusCode {
+    if let Some(id) = presented_session(&headers) {
+        state.sessions().remove(&id);
+    }
+    StatusCode::NO_CONTENT
+}
+
+fn presented_session(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+fn with_session(mut response: Response, session_id: &str) -> Response {
+    if let Ok(value) = HeaderValue::from_str(session_id) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(SESSION_HEADER), value);
+    }
+    response
+}
+
+fn rpc_error(status: StatusCode, code: i64, message: String) -> Response {
+    (
+        status,
+        Json(json!({
+            "jsonrpc": "2.0",
+            "error": { "code": code, "message": message },
+            "id": null,
+        })),
+    )
+        .into_response()
+}
@@ -71,7 +71,9 @@ pub fn fmt_line(n: usize, hex: bool) -> String {
     }
 }
 
+pub mod apply_patch;
 pub mod assistant;
+pub mod audit;
 pub mod cache;
 pub mod consciousness;
 pub mod context_absorber;
@@ -81,12 +83,14 @@ mod enhanced_tool_descriptions;
 mod git_memory_integration;
 mod helpers;
 mod hook_tools;
+mod http_transport;
 mod negotiation;
 pub mod permissions;
 mod proactive_assistant;
 mod prompts;
 mod prompts_enhanced;
-mod resources;
+pub mod rate_limit;
+pub mod resources;
 pub mod session;
 pub mod smart_background_searcher;
 pub mod smart_edit;
@@ -94,10 +98,11 @@ mod smart_edit_diff_viewer;
 pub mod smart_project_detector;
 mod sse;
 mod theme_tools;
-mod tools;
+pub mod tools;
 mod tools_consolidated;
 pub mod tools_consolidated_enhanced;
 pub mod unified_watcher;
+mod ws_watch;
 pub mod wave_memory;
 
 use assistant::*;
@@ -154,6 +159,8 @@ pub struct McpServer {
 pub struct McpContext {
     /// Cache for analysis results
     pub cache: Arc<AnalysisCache>,
+    /// Short-lived shared scan results so back-to-back tools reuse one traversal
+    pub scan_cache: Arc<ScanCache>,
     /// Server configuration
     pub config: Arc<McpConfig>,
     /// Permission cache
@@ -166,6 +173,16 @@ pub struct McpContext {
     pub consciousness: Arc<tokio::sync::Mutex<ConsciousnessManager>>,
     /// Optional bridge to web dashboard for real-time activity visualization
     pub dashboard_bridge: Option<dashboard_bridge::DashboardBridge>,
+    /// Live tree:// resource subscriptions (resources/subscribe)
+    pub subscriptions: Arc<resources::ResourceSubscriptions>,
+    /// When this server instance came to life (for uptime reporting)
+    pub started_at: std::time::Instant,
+    /// Requests handled so far (server_info reports it honestly)
+    pub requests_handled: Arc<std::sync::atomic::AtomicU64>,
+    /// Scoped read/write grants this session holds (request_permissions)
+    pub grants: Arc<std::sync::Mutex<permissions::SessionGrants>>,
+    /// Call-rate and heavy-tool concurrency guards
+    pub rate_limiter: Arc<rate_limit::RateLimiter>,
 }
 
 /// MCP server configuration
@@ -186,6 +203,34 @@ pub struct McpConfig {
     /// Use hexadecimal for all numbers (saves tokens!)
     /// Line 1000 → 3E8, size 1048576 → 100000
     pub hex_numbers: bool,
+    /// Require an explicit session grant (request_permissions) before any
+    /// write-capable tool may touch a path
+    #[serde(default)]
+    pub require_write_grants: bool,
+    /// Append every tool call to the project's .st/audit.jsonl (`st audit`)
+    #[serde(default = "default_audit_enabled")]
+    pub audit_enabled: bool,
+    /// Audit only the args hash, never the argument contents
+    #[serde(default)]
+    pub audit_redact_args: bool,
+    /// Tool calls allowed per minute, 0 = unlimited
+    #[serde(default = "default_rate_limit")]
+    pub rate_limit_per_minute: usize,
+    /// Expensive tools running at once (scans, searches), 0 = unlimited
+    #[serde(default = "default_max_concurrent_heavy")]
+    pub max_concurrent_heavy: usize,
+}
+
+fn default_audit_enabled() -> bool {
+    true
+}
+
+fn default_rate_limit() -> usize {
+    120
+}
+
+fn default_max_concurrent_heavy() -> usize {
+    4
 }
 
 impl Default for McpConfig {
@@ -202,6 +247,11 @@ impl Default for McpConfig {
             ],
             use_consolidated_tools: true, // Default to consolidated for Cursor compatibility
             hex_numbers: true,            // Default to hex for token efficiency!
+            require_write_grants: false,  // Opt-in - existing clients keep working
+            audit_enabled: true,          // Compliance trail on by default
+            audit_redact_args: false,
+            rate_limit_per_minute: default_rate_limit(),
+            max_concurrent_heavy: default_max_concurrent_heavy(),
         }
     }
 }
@@ -241,15 +291,27 @@ impl McpServer {
     pub fn new(config: McpConfig) -> Self {
         // Use silent constructor - MCP protocol requires clean stdout
         let consciousness = Arc::new(tokio::sync::Mutex::new(ConsciousnessManager::new_silent()));
+        let rate_limiter = Arc::new(rate_limit::RateLimiter::new(
+            config.rate_limit_per_minute,
+            config.max_concurrent_heavy,
+        ));
 
         let context = Arc::new(McpContext {
             cache: Arc::new(AnalysisCache::new(config.cache_ttl)),
+            scan_cache: Arc::new(ScanCache::new(cache::SCAN_CACHE_TTL_SECS)),
             config: Arc::new(config),
             permissions: Arc::new(tokio::sync::Mutex::new(PermissionCache::new())),
             sessions: Arc::new(SessionManager::new()),
             assistant: Arc::new(McpAssistant::new()),
             consciousness: consciousness.clone(),
             dashboard_bridge: None,
+            subscriptions: Arc::new(resources::ResourceSubscriptions::new()),
+            started_at: std::time::Instant::now(),
+            requests_handled: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            grants: Arc::new(std::sync::Mutex::new(
+                permissions::SessionGrants::default(),
+            )),
+            rate_limiter,
         });
 
         Self {
@@ -261,9 +323,23 @@ impl McpServer {
     /// Run the MCP server on stdio
     pub async fn run_stdio(&self) -> Result<()> {
         let stdin = io::stdin();
-        let stdout = io::stdout();
+        // Per-write locking (not one long-held lock) so the notification
+        // writer thread below can interleave server-initiated messages
+        let mut stdout = io::stdout();
+
         let mut reader = BufReader::new(stdin);
-        let mut stdout = stdout.lock();
+
+        // Server-initiated notifications (resource subscriptions) flow through
+        // this channel; a dedicated thread serializes them onto stdout
+        let (notify_tx, notify_rx) = std::sync::mpsc::channel::<String>();
+        self.context.subscriptions.attach(notify_tx);
+        std::thread::spawn(move || {
+            let mut out = io::stdout();
+            while let Ok(notification) = notify_rx.recv() {
+                let _ = writeln!(out, "{}", notification);
+                let _ = out.flush();
+            }
+        });
 
         // Restore previous consciousness silently (no output that would break MCP protocol)
         {
@@ -338,6 +414,10 @@ impl McpServer {
         let request: JsonRpcRequest =
             serde_json::from_str(request_str).context("Failed to parse JSON-RPC request")?;
 
+        self.context
+            .requests_handled
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
         // Check for compression support in every request
         if let Some(ref params) = request.params {
             compression_manager::check_client_compression_support(params);
@@ -407,6 +487,20 @@ impl McpServer {
                 handle_resources_read(request.params.unwrap_or(json!({})), self.context.clone())
                     .await
             }
+            "resources/subscribe" => {
+                handle_resources_subscribe(
+                    request.params.unwrap_or(json!({})),
+                    self.context.clone(),
+                )
+                .await
+            }
+            "resources/unsubscribe" => {
+                handle_resources_unsubscribe(
+                    request.params.unwrap_or(json!({})),
+                    self.context.clone(),
+                )
+                .await
+            }
             "prompts/list" => {
                 // Use enhanced prompts by default, fall back to legacy if needed
                 prompts_enhanced::handle_prompts_list(request.params, self.context.clone()).await
@@ -451,16 +545,30 @@ impl McpServer {
                 error: None,
                 id: request.id,
             },
-            Err(e) => JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                result: None,
-                error: Some(JsonRpcError {
-                    code: -32603,
-                    message: e.to_string(),
-                    data: None,
-                }),
-                id: request.id,
-            },
+            Err(e) => {
+                // Rate limits get their own code plus machine-readable
+                // retry info so clients can back off instead of retrying hot
+                let (code, data) = match e.downcast_ref::<rate_limit::RateLimitExceeded>() {
+                    Some(limit) => (
+                        -32029,
+                        Some(json!({
+                            "retry_after_secs": limit.retry_after_secs,
+                            "reason": limit.reason,
+                        })),
+                    ),
+                    None => (-32603, None),
+                };
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code,
+                        message: e.to_string(),
+                        data,
+                    }),
+                    id: request.id,
+                }
+            }
         };
 
         // Smart compress the response if needed
@@ -477,6 +585,8 @@ async fn handle_initialize(params: Option<Value>, _ctx: Arc<McpContext>) -> Resu
     // Check if client supports compression from their request
     if let Some(params) = params {
         compression_manager::check_client_compression_support(&params);
+        // Remember who connected so the audit trail can name the caller
+        audit::record_caller(&params);
     }
 
     // Check for updates when MCP tools initialize (non-blocking)
@@ -492,8 +602,8 @@ async fn handle_initialize(params: Option<Value>, _ctx: Arc<McpContext>) -> Resu
                 "listChanged": false
             },
             "resources": {
-                "subscribe": false,
-                "listChanged": false
+                "subscribe": true,
+                "listChanged": true
             },
             "prompts": {
                 "listChanged": false
@@ -645,6 +755,10 @@ async fn check_for_mcp_updates() -> Value {
 
 /// Check if a path is allowed based on security configuration
 pub fn is_path_allowed(path: &Path, config: &McpConfig) -> bool {
+    // Resolve `..` lexically first - the prefix checks below are purely
+    // component-based and would otherwise let traversal slip past both lists
+    let path = &permissions::normalize_lexically(path);
+
     // Check blocked paths first
     for blocked in &config.blocked_paths {
         if path.starts_with(blocked) {
@@ -667,6 +781,29 @@ pub fn is_path_allowed(path: &Path, config: &McpConfig) -> bool {
     false
 }
 
+/// Enforce session write grants for a path.
+///
+/// Does nothing unless `require_write_grants` is on; with it on, every
+/// write-capable tool must hold a scope from `request_permissions` that
+/// covers the path, and gets a pointer to that tool when it doesn't.
+pub fn ensure_write_scope(path: &Path, ctx: &McpContext) -> Result<()> {
+    if !ctx.config.require_write_grants {
+        return Ok(());
+    }
+    let grants = ctx
+        .grants
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Grant store poisoned"))?;
+    if grants.allows(path, permissions::Access::Write) {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "No write grant covers {} - call request_permissions with \
+         {{\"scopes\": [{{\"path\": \"<prefix>\", \"access\": \"write\"}}]}} first",
+        path.display()
+    )
+}
+
 /// Load MCP configuration from file or use defaults
 pub fn load_config() -> Result<McpConfig> {
     let config_path = dirs::home_dir()
@@ -326,6 +326,265 @@ pub fn get_available_tools(perms: &PathPermissions) -> Vec<ToolAvailability> {
     tools
 }
 
+// =============================================================================
+// Command execution policy - the allowlist behind `run_checks`
+// =============================================================================
+
+/// One allowlisted project command from `.st/checks.toml`.
+///
+/// The command line is parsed shell-style into an argv and spawned directly -
+/// never handed to a shell - so the allowlist entry is exactly what runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckCommand {
+    /// Command line, e.g. "cargo fmt --check"
+    pub command: String,
+    /// What this check verifies, echoed back in results
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Hard wall-clock limit before the process is killed
+    #[serde(default = "default_check_timeout")]
+    pub timeout_secs: u64,
+    /// When true the caller must pass `confirm: true`, i.e. the client has
+    /// shown the user exactly which command is about to run
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+fn default_check_timeout() -> u64 {
+    300
+}
+
+impl CheckCommand {
+    /// Split the command line into argv, rejecting empty or unparseable ones
+    pub fn argv(&self) -> Result<Vec<String>> {
+        let argv = shlex::split(&self.command)
+            .ok_or_else(|| anyhow::anyhow!("Unparseable command line: {:?}", self.command))?;
+        if argv.is_empty() {
+            anyhow::bail!("Empty command line in checks.toml");
+        }
+        Ok(argv)
+    }
+}
+
+/// The project's check allowlist, loaded from `.st/checks.toml`.
+///
+/// This is the entire sandbox model for `run_checks`: the tool is disabled
+/// unless the file exists, callers pick checks by name only (no arguments
+/// ever come from the AI side), and nothing outside this file can run.
+/// Example:
+///
+/// ```toml
+/// [checks.fmt]
+/// command = "cargo fmt --check"
+///
+/// [checks.clippy]
+/// command = "cargo clippy --workspace"
+/// timeout_secs = 600
+/// confirm = true   # client must show the user before running
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CheckPolicy {
+    #[serde(default)]
+    pub checks: std::collections::BTreeMap<String, CheckCommand>,
+}
+
+impl CheckPolicy {
+    /// Where the allowlist lives relative to the project root
+    pub fn config_path(project_root: &Path) -> PathBuf {
+        project_root.join(".st").join("checks.toml")
+    }
+
+    /// Load the allowlist. `Ok(None)` means the project hasn't opted in
+    /// (no `.st/checks.toml`) and `run_checks` must refuse to run anything.
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = Self::config_path(project_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?;
+        let policy: CheckPolicy = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))?;
+        Ok(Some(policy))
+    }
+
+    /// Look up a check by its allowlist name
+    pub fn get(&self, name: &str) -> Option<&CheckCommand> {
+        self.checks.get(name)
+    }
+
+    /// Allowlisted check names, in stable (sorted) order
+    pub fn names(&self) -> Vec<&str> {
+        self.checks.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+// =============================================================================
+// Per-session scoped grants - the model behind `request_permissions`
+// =============================================================================
+
+/// Access level a session can hold on a path prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    Read,
+    /// Write implies read
+    Write,
+}
+
+impl Access {
+    /// Does holding `self` satisfy a request for `wanted`?
+    pub fn covers(self, wanted: Access) -> bool {
+        self == Access::Write || wanted == Access::Read
+    }
+}
+
+/// One granted scope: a path prefix plus the access level it carries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScopeGrant {
+    pub prefix: PathBuf,
+    pub access: Access,
+    pub granted_at: SystemTime,
+}
+
+/// Scopes the current MCP session has been granted.
+///
+/// Starts empty - `request_permissions` fills it after the user approves,
+/// either ahead of time in `~/.st/mcp-grants.toml` or live on the terminal.
+/// Write-capable tools consult this (when `require_write_grants` is on)
+/// before touching disk.
+#[derive(Debug, Default)]
+pub struct SessionGrants {
+    grants: Vec<ScopeGrant>,
+}
+
+impl SessionGrants {
+    /// Record a grant, upgrading an existing prefix instead of duplicating it
+    pub fn grant(&mut self, prefix: PathBuf, access: Access) {
+        if let Some(existing) = self.grants.iter_mut().find(|g| g.prefix == prefix) {
+            if access.covers(existing.access) {
+                existing.access = access;
+                existing.granted_at = SystemTime::now();
+            }
+            return;
+        }
+        self.grants.push(ScopeGrant {
+            prefix,
+            access,
+            granted_at: SystemTime::now(),
+        });
+    }
+
+    /// Does any held scope cover `path` at the `wanted` level? Both sides
+    /// are normalized first so `..` in the request can't escape a prefix.
+    pub fn allows(&self, path: &Path, wanted: Access) -> bool {
+        let path = normalize_lexically(path);
+        self.grants
+            .iter()
+            .any(|g| g.access.covers(wanted) && path.starts_with(normalize_lexically(&g.prefix)))
+    }
+
+    /// Everything granted so far (for reporting back to the client)
+    pub fn list(&self) -> &[ScopeGrant] {
+        &self.grants
+    }
+}
+
+/// One pre-approved scope from `~/.st/mcp-grants.toml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovedScope {
+    pub path: PathBuf,
+    pub access: Access,
+}
+
+/// Scopes the user approved ahead of time, so `request_permissions` doesn't
+/// have to prompt. Example `~/.st/mcp-grants.toml`:
+///
+/// ```toml
+/// [[grants]]
+/// path = "~/projects"
+/// access = "write"
+///
+/// [[grants]]
+/// path = "/var/log"
+/// access = "read"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct ApprovedScopes {
+    #[serde(default)]
+    pub grants: Vec<ApprovedScope>,
+}
+
+impl ApprovedScopes {
+    /// Where the pre-approval file lives
+    pub fn config_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".st")
+            .join("mcp-grants.toml")
+    }
+
+    /// Load the pre-approvals; a missing file just means none
+    pub fn load() -> Result<Self> {
+        let path = Self::config_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?;
+        toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))
+    }
+
+    /// Is `path` at the `wanted` level covered by a pre-approval? Both
+    /// sides are normalized first so `..` in the request can't escape.
+    pub fn approves(&self, path: &Path, wanted: Access) -> bool {
+        let path = normalize_lexically(path);
+        self.grants.iter().any(|g| {
+            g.access.covers(wanted) && path.starts_with(normalize_lexically(&expand_tilde(&g.path)))
+        })
+    }
+}
+
+/// Resolve `.` and `..` components lexically, without touching the
+/// filesystem. Grant checks compare normalized paths, so a request for
+/// `/project/../../etc/passwd` can't ride on a grant for `/project` -
+/// `Path::starts_with` alone is purely component-based and would let
+/// that through.
+pub fn normalize_lexically(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                // `..` at the root (or with nothing left to pop) has no
+                // lexical parent; keep it so the prefix check fails
+                // closed instead of silently matching a grant
+                match normalized.components().next_back() {
+                    Some(Component::Normal(_)) => {
+                        normalized.pop();
+                    }
+                    _ => normalized.push(component),
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Expand a leading `~` to the home directory so grant files stay portable
+fn expand_tilde(path: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
 /// Check if a specific tool is available for a path
 pub fn _is_tool_available(tool_name: &str, perms: &PathPermissions) -> (bool, Option<String>) {
     let tools = get_available_tools(perms);
@@ -361,6 +620,30 @@ mod tests {
         assert!(cache.is_verified(path));
     }
 
+    #[test]
+    fn test_grants_resist_dotdot_traversal() {
+        let mut grants = SessionGrants::default();
+        grants.grant(PathBuf::from("/project"), Access::Write);
+
+        assert!(grants.allows(Path::new("/project/src/main.rs"), Access::Write));
+        assert!(grants.allows(Path::new("/project/./src/../Cargo.toml"), Access::Write));
+
+        // `..` escaping the granted prefix must not pass the check
+        assert!(!grants.allows(Path::new("/project/../../etc/passwd"), Access::Write));
+        assert!(!grants.allows(Path::new("/project/../project-secrets/key"), Access::Write));
+    }
+
+    #[test]
+    fn test_normalize_lexically() {
+        assert_eq!(
+            normalize_lexically(Path::new("/a/./b/../c")),
+            PathBuf::from("/a/c")
+        );
+        // Nothing to pop: `..` is kept so prefix checks fail closed
+        assert_eq!(normalize_lexically(Path::new("/../x")), PathBuf::from("/../x"));
+        assert_eq!(normalize_lexically(Path::new("../x")), PathBuf::from("../x"));
+    }
+
     #[test]
     fn test_tool_availability() {
         // Test with readable directory
@@ -414,4 +697,108 @@ mod tests {
             Some("File is read-only - no write permission".to_string())
         );
     }
+
+    #[test]
+    fn test_check_policy_parsing() {
+        let policy: CheckPolicy = toml::from_str(
+            r#"
+            [checks.fmt]
+            command = "cargo fmt --check"
+
+            [checks.clippy]
+            command = "cargo clippy --workspace"
+            timeout_secs = 600
+            confirm = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.names(), vec!["clippy", "fmt"]);
+
+        let fmt = policy.get("fmt").unwrap();
+        assert_eq!(fmt.argv().unwrap(), vec!["cargo", "fmt", "--check"]);
+        assert_eq!(fmt.timeout_secs, 300); // default
+        assert!(!fmt.confirm);
+
+        let clippy = policy.get("clippy").unwrap();
+        assert_eq!(clippy.timeout_secs, 600);
+        assert!(clippy.confirm);
+
+        // Anything not in the allowlist simply doesn't exist
+        assert!(policy.get("rm -rf").is_none());
+    }
+
+    #[test]
+    fn test_check_command_argv_rejects_empty() {
+        let check = CheckCommand {
+            command: "   ".to_string(),
+            description: None,
+            timeout_secs: 300,
+            confirm: false,
+        };
+        assert!(check.argv().is_err());
+    }
+
+    #[test]
+    fn test_check_policy_absent_means_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(CheckPolicy::load(temp_dir.path()).unwrap().is_none());
+
+        // Writing the file opts the project in
+        let st_dir = temp_dir.path().join(".st");
+        fs::create_dir_all(&st_dir).unwrap();
+        fs::write(
+            st_dir.join("checks.toml"),
+            "[checks.test]\ncommand = \"cargo test\"\n",
+        )
+        .unwrap();
+
+        let policy = CheckPolicy::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(policy.names(), vec!["test"]);
+    }
+
+    #[test]
+    fn test_session_grants_prefix_and_level() {
+        let mut grants = SessionGrants::default();
+        assert!(!grants.allows(Path::new("/work/src/main.rs"), Access::Read));
+
+        grants.grant(PathBuf::from("/work"), Access::Read);
+        assert!(grants.allows(Path::new("/work/src/main.rs"), Access::Read));
+        // Read does not cover write
+        assert!(!grants.allows(Path::new("/work/src/main.rs"), Access::Write));
+        // Prefix means path components, not string prefix
+        assert!(!grants.allows(Path::new("/workspace/main.rs"), Access::Read));
+
+        // Upgrading the same prefix replaces rather than duplicates
+        grants.grant(PathBuf::from("/work"), Access::Write);
+        assert_eq!(grants.list().len(), 1);
+        assert!(grants.allows(Path::new("/work/src/main.rs"), Access::Write));
+        // Write implies read
+        assert!(grants.allows(Path::new("/work/README.md"), Access::Read));
+
+        // A later read grant must not downgrade the write
+        grants.grant(PathBuf::from("/work"), Access::Read);
+        assert!(grants.allows(Path::new("/work/src/main.rs"), Access::Write));
+    }
+
+    #[test]
+    fn test_approved_scopes_parsing() {
+        let approved: ApprovedScopes = toml::from_str(
+            r#"
+            [[grants]]
+            path = "/srv/data"
+            access = "read"
+
+            [[grants]]
+            path = "/work"
+            access = "write"
+            "#,
+        )
+        .unwrap();
+
+        assert!(approved.approves(Path::new("/srv/data/logs/app.log"), Access::Read));
+        assert!(!approved.approves(Path::new("/srv/data/logs/app.log"), Access::Write));
+        assert!(approved.approves(Path::new("/work/src/lib.rs"), Access::Write));
+        assert!(!approved.approves(Path::new("/etc/passwd"), Access::Read));
+    }
 }
@@ -0,0 +1,160 @@
+//! Rate limiting and concurrency control for the MCP server
+//!
+//! Two guards stand between a misbehaving client and a wedged machine:
+//! a sliding-window rate limit over all tool calls, and a bounded permit
+//! pool for the expensive ones (full scans, searches, comparisons).
+//! Both surface as a structured JSON-RPC error carrying retry-after info
+//! so a polite client can back off instead of hammering harder.
+//! Limits live in [`crate::mcp::McpConfig`]; zero disables a guard.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Sliding window length for the call-rate limit
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// A limit was hit. Carries how long the caller should wait; the JSON-RPC
+/// layer forwards that as machine-readable error data.
+#[derive(Debug, thiserror::Error)]
+#[error("{reason} - retry in {retry_after_secs}s")]
+pub struct RateLimitExceeded {
+    pub retry_after_secs: u64,
+    pub reason: String,
+}
+
+/// Per-session guards, shared through McpContext.
+pub struct RateLimiter {
+    /// Calls allowed per minute across all tools (0 = unlimited)
+    max_per_minute: usize,
+    /// Timestamps of calls inside the current window
+    calls: Mutex<VecDeque<Instant>>,
+    /// Permits for expensive tools (None = unlimited)
+    heavy: Option<Arc<Semaphore>>,
+    max_concurrent_heavy: usize,
+}
+
+impl RateLimiter {
+    pub fn new(max_per_minute: usize, max_concurrent_heavy: usize) -> Self {
+        Self {
+            max_per_minute,
+            calls: Mutex::new(VecDeque::new()),
+            heavy: (max_concurrent_heavy > 0)
+                .then(|| Arc::new(Semaphore::new(max_concurrent_heavy))),
+            max_concurrent_heavy,
+        }
+    }
+
+    /// Tools whose cost scales with tree size - these draw from the
+    /// bounded pool so `analyze_directory` on `/` can't eat every core
+    pub fn is_heavy(tool: &str) -> bool {
+        tool.starts_with("find_")
+            || matches!(
+                tool,
+                "analyze_directory"
+                    | "quick_tree"
+                    | "project_overview"
+                    | "project_context_dump"
+                    | "semantic_analysis"
+                    | "search_in_files"
+                    | "get_statistics"
+                    | "directory_size_breakdown"
+                    | "compare_directories"
+                    | "analyze_workspace"
+                    | "analyze_churn"
+                    | "analyze_dependencies"
+            )
+    }
+
+    /// Count one call against the window, or say how long to wait
+    pub fn check_rate(&self) -> Result<(), RateLimitExceeded> {
+        if self.max_per_minute == 0 {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        while calls
+            .front()
+            .is_some_and(|&t| now.duration_since(t) >= RATE_WINDOW)
+        {
+            calls.pop_front();
+        }
+        if calls.len() >= self.max_per_minute {
+            // The window frees up when its oldest call ages out
+            let oldest = calls.front().copied().unwrap_or(now);
+            let retry = RATE_WINDOW.saturating_sub(now.duration_since(oldest));
+            return Err(RateLimitExceeded {
+                retry_after_secs: retry.as_secs().max(1),
+                reason: format!(
+                    "Rate limit exceeded ({} calls/minute)",
+                    self.max_per_minute
+                ),
+            });
+        }
+        calls.push_back(now);
+        Ok(())
+    }
+
+    /// Take a permit from the heavy pool. `Ok(None)` means the tool isn't
+    /// gated (pool disabled); dropping the permit returns it.
+    pub fn heavy_permit(&self) -> Result<Option<OwnedSemaphorePermit>, RateLimitExceeded> {
+        let Some(pool) = &self.heavy else {
+            return Ok(None);
+        };
+        match pool.clone().try_acquire_owned() {
+            Ok(permit) => Ok(Some(permit)),
+            Err(_) => Err(RateLimitExceeded {
+                retry_after_secs: 2,
+                reason: format!(
+                    "All {} heavy-tool slots are busy",
+                    self.max_concurrent_heavy
+                ),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_window() {
+        let limiter = RateLimiter::new(3, 0);
+        assert!(limiter.check_rate().is_ok());
+        assert!(limiter.check_rate().is_ok());
+        assert!(limiter.check_rate().is_ok());
+
+        let err = limiter.check_rate().unwrap_err();
+        assert!(err.retry_after_secs >= 1 && err.retry_after_secs <= 60);
+        assert!(err.reason.contains("3 calls/minute"));
+    }
+
+    #[test]
+    fn test_zero_disables_rate_limit() {
+        let limiter = RateLimiter::new(0, 0);
+        for _ in 0..1000 {
+            assert!(limiter.check_rate().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_heavy_pool_bounds_concurrency() {
+        let limiter = RateLimiter::new(0, 2);
+        let a = limiter.heavy_permit().unwrap();
+        let _b = limiter.heavy_permit().unwrap();
+        assert!(limiter.heavy_permit().is_err());
+
+        drop(a); // Finishing a heavy call frees a slot
+        assert!(limiter.heavy_permit().is_ok());
+    }
+
+    #[test]
+    fn test_heavy_classification() {
+        assert!(RateLimiter::is_heavy("analyze_directory"));
+        assert!(RateLimiter::is_heavy("find_large_files"));
+        assert!(!RateLimiter::is_heavy("server_info"));
+        assert!(!RateLimiter::is_heavy("smart_edit"));
+    }
+}
@@ -1,10 +1,24 @@
 //! MCP resources implementation for Smart Tree
+//!
+//! Besides the static config/cache resources, any directory can be read as
+//! a `tree://<path>` snapshot - and subscribed to! Subscriptions ride on the
+//! same notify backend as the unified watcher, pushing
+//! `notifications/resources/updated` whenever the directory changes.
+//!
+//! The `st://` family surfaces Smart Tree's own artifacts - cached reports
+//! under `.st/reports`, saved scan states, and usage instructions - so
+//! agents can pull context without spending a tool call on a fresh scan.
 
-use super::McpContext;
+use super::helpers::{scan_with_config, ScannerConfigBuilder};
+use super::{is_path_allowed, McpContext};
 use anyhow::Result;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ResourceDefinition {
@@ -35,6 +49,36 @@ pub async fn handle_resources_list(_params: Option<Value>, _ctx: Arc<McpContext>
             description: "Current MCP server configuration".to_string(),
             mime_type: "application/json".to_string(),
         },
+        ResourceDefinition {
+            uri: "tree://.".to_string(),
+            name: "Directory Tree Snapshot".to_string(),
+            description: "Tree snapshot of a directory - any tree://<absolute-path> works, and subscribing pushes updated notifications when the directory changes".to_string(),
+            mime_type: "text/plain".to_string(),
+        },
+        ResourceDefinition {
+            uri: "st://instructions".to_string(),
+            name: "Smart Tree Instructions".to_string(),
+            description: "How to get the most out of this server - recommended tool order, modes, and compression notes".to_string(),
+            mime_type: "text/markdown".to_string(),
+        },
+        ResourceDefinition {
+            uri: "st://snapshots".to_string(),
+            name: "Saved Scan Snapshots".to_string(),
+            description: "Index of saved scan states (~/.st/scan_states) - read st://snapshots/<name> for one".to_string(),
+            mime_type: "application/json".to_string(),
+        },
+        ResourceDefinition {
+            uri: "st://reports/waste/latest".to_string(),
+            name: "Latest Waste Report".to_string(),
+            description: "Most recent waste analysis for the working directory (generated and cached under .st/reports on first read)".to_string(),
+            mime_type: "text/plain".to_string(),
+        },
+        ResourceDefinition {
+            uri: "st://reports/health/latest".to_string(),
+            name: "Latest Project Health Score".to_string(),
+            description: "Most recent project health score for the working directory (generated and cached under .st/reports on first read)".to_string(),
+            mime_type: "application/json".to_string(),
+        },
     ];
 
     Ok(json!({
@@ -47,6 +91,14 @@ pub async fn handle_resources_read(params: Value, ctx: Arc<McpContext>) -> Resul
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("Missing resource URI"))?;
 
+    if let Some(path) = uri.strip_prefix("tree://") {
+        return read_tree_snapshot(uri, path, ctx).await;
+    }
+
+    if let Some(rest) = uri.strip_prefix("st://") {
+        return read_st_artifact(uri, rest, ctx).await;
+    }
+
     match uri {
         "cache://directory_cache" => read_directory_cache(ctx).await,
         "config://ignore_patterns" => read_ignore_patterns(ctx).await,
@@ -55,6 +107,416 @@ pub async fn handle_resources_read(params: Value, ctx: Arc<McpContext>) -> Resul
     }
 }
 
+/// Subscribe to a `tree://<path>` resource - updated notifications follow
+///
+/// An optional `debounce_ms` param sets how long the directory must stay
+/// quiet between notifications (default 2000ms).
+pub async fn handle_resources_subscribe(params: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let uri = params["uri"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing resource URI"))?;
+    let debounce = params["debounce_ms"]
+        .as_u64()
+        .map(|ms| Duration::from_millis(ms.max(1)))
+        .unwrap_or(NOTIFY_COOLDOWN);
+    ctx.subscriptions.subscribe(uri, &ctx, debounce)?;
+    Ok(json!({}))
+}
+
+/// Drop a resource subscription
+pub async fn handle_resources_unsubscribe(params: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let uri = params["uri"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing resource URI"))?;
+    ctx.subscriptions.unsubscribe(uri);
+    Ok(json!({}))
+}
+
+/// Resolve and validate the directory behind a `tree://` URI
+fn tree_uri_path(path_str: &str, ctx: &McpContext) -> Result<PathBuf> {
+    let path = std::fs::canonicalize(path_str)
+        .map_err(|e| anyhow::anyhow!("Cannot resolve '{}': {}", path_str, e))?;
+    if !is_path_allowed(&path, &ctx.config) {
+        anyhow::bail!("Access denied: {} is outside allowed paths", path.display());
+    }
+    if !path.is_dir() {
+        anyhow::bail!("tree:// resources must point at a directory");
+    }
+    Ok(path)
+}
+
+/// Read a directory as a compact AI-format tree snapshot
+async fn read_tree_snapshot(uri: &str, path_str: &str, ctx: Arc<McpContext>) -> Result<Value> {
+    use crate::formatters::{ai::AiFormatter, Formatter, PathDisplayMode};
+
+    let path = tree_uri_path(path_str, &ctx)?;
+    let config = ScannerConfigBuilder::new().max_depth(10).build();
+    let (nodes, stats) = scan_with_config(&path, config)?;
+
+    let formatter = AiFormatter::new(true, PathDisplayMode::Relative);
+    let mut output = Vec::new();
+    formatter.format(&mut output, &nodes, &stats, &path)?;
+
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": "text/plain",
+            "text": String::from_utf8_lossy(&output).to_string()
+        }]
+    }))
+}
+
+/// Dispatch an `st://` artifact URI to its reader
+async fn read_st_artifact(uri: &str, rest: &str, ctx: Arc<McpContext>) -> Result<Value> {
+    match rest {
+        "instructions" => Ok(text_contents(uri, "text/markdown", INSTRUCTIONS.to_string())),
+        "snapshots" => read_snapshot_index(uri),
+        "reports/waste/latest" => read_latest_report(uri, "waste", ctx).await,
+        "reports/health/latest" => read_latest_report(uri, "health", ctx).await,
+        _ => {
+            if let Some(name) = rest.strip_prefix("snapshots/") {
+                return read_snapshot(uri, name);
+            }
+            Err(anyhow::anyhow!("Unknown resource: {}", uri))
+        }
+    }
+}
+
+/// Standard single-item `contents` payload
+fn text_contents(uri: &str, mime_type: &str, text: String) -> Value {
+    json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": mime_type,
+            "text": text
+        }]
+    })
+}
+
+/// Agent-facing quick start - the same advice server_info gives, in a form
+/// clients can pin as context
+const INSTRUCTIONS: &str = "\
+# Smart Tree MCP Server
+
+## Recommended workflow
+1. `quick_tree(path='.')` - 3-level overview with heavy compression. Start here.
+2. `project_overview` or `analyze_workspace` for project type and structure.
+3. Targeted follow-ups: `find_files`, `search_in_files`, `get_statistics`,
+   or `analyze_directory` with `mode='quantum-semantic'` for code analysis.
+
+## Output modes worth knowing
+- `summary-ai`: ~10x compression for large trees - best for LLM transmission.
+- `quantum-semantic`: semantic compression with tokenization - best for code.
+- `ai-table` (find_files/search_in_files format arg): schema line plus
+  columnar rows - ~60% fewer tokens than JSON listings.
+
+## Compression
+Responses may be zlib+base64 compressed when large. Opt out per call with
+`compress=false`, per session via `negotiate_session`, or globally with
+`MCP_NO_COMPRESS=1`.
+
+## Resources
+- `tree://<path>`: live tree snapshot of any allowed directory (subscribable).
+- `st://reports/waste/latest`, `st://reports/health/latest`: cached reports.
+- `st://snapshots`: saved scan states for change tracking.
+";
+
+/// Index of saved scan states - name, size, and age of each
+fn read_snapshot_index(uri: &str) -> Result<Value> {
+    let dir = snapshots_dir();
+    let mut snapshots = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.ends_with(".state.json") {
+                continue;
+            }
+            let meta = entry.metadata().ok();
+            snapshots.push(json!({
+                "name": name.trim_end_matches(".state.json"),
+                "uri": format!("st://snapshots/{}", name.trim_end_matches(".state.json")),
+                "size_bytes": meta.as_ref().map(|m| m.len()),
+                "modified": meta
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs()),
+            }));
+        }
+    }
+    snapshots.sort_by(|a, b| b["modified"].as_u64().cmp(&a["modified"].as_u64()));
+
+    let content = json!({
+        "directory": dir.display().to_string(),
+        "count": snapshots.len(),
+        "snapshots": snapshots,
+    });
+    Ok(text_contents(
+        uri,
+        "application/json",
+        serde_json::to_string_pretty(&content)?,
+    ))
+}
+
+/// Read one saved scan state by name
+fn read_snapshot(uri: &str, name: &str) -> Result<Value> {
+    // Names are flat files - no traversal via the URI
+    if name.contains(['/', '\\']) || name.contains("..") {
+        anyhow::bail!("Invalid snapshot name: {}", name);
+    }
+    let path = snapshots_dir().join(format!("{}.state.json", name));
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Cannot read snapshot '{}': {}", name, e))?;
+    Ok(text_contents(uri, "application/json", text))
+}
+
+fn snapshots_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".st")
+        .join("scan_states")
+}
+
+/// Serve the newest cached report of `kind`, generating one if the cache
+/// under `.st/reports/<kind>` is empty
+async fn read_latest_report(uri: &str, kind: &str, ctx: Arc<McpContext>) -> Result<Value> {
+    let cwd = std::env::current_dir()?;
+    if !is_path_allowed(&cwd, &ctx.config) {
+        anyhow::bail!(
+            "Access denied: {} is outside allowed paths",
+            cwd.display()
+        );
+    }
+
+    let reports_dir = cwd.join(".st").join("reports").join(kind);
+    if let Some((path, text)) = newest_report(&reports_dir)? {
+        let mime = mime_for_report(kind);
+        return Ok(json!({
+            "contents": [{
+                "uri": uri,
+                "mimeType": mime,
+                "text": text,
+                "_meta": { "generated_from": path.display().to_string() }
+            }]
+        }));
+    }
+
+    // Nothing cached yet - generate, persist, and serve
+    let text = generate_report(kind, &cwd)?;
+    std::fs::create_dir_all(&reports_dir)?;
+    let ext = if kind == "health" { "json" } else { "txt" };
+    let filename = format!("{}.{}", chrono::Local::now().format("%Y%m%d-%H%M%S"), ext);
+    std::fs::write(reports_dir.join(&filename), &text)?;
+
+    Ok(text_contents(uri, mime_for_report(kind), text))
+}
+
+fn mime_for_report(kind: &str) -> &'static str {
+    if kind == "health" {
+        "application/json"
+    } else {
+        "text/plain"
+    }
+}
+
+/// Newest file in a report directory, if any
+fn newest_report(dir: &Path) -> Result<Option<(PathBuf, String)>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(None);
+    };
+    let newest = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .max_by_key(|e| {
+            e.metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH)
+        });
+    match newest {
+        Some(entry) => {
+            let path = entry.path();
+            let text = std::fs::read_to_string(&path)?;
+            Ok(Some((path, text)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Generate a report of `kind` for `root` - the same analysis the tools run,
+/// just cached for resource reads
+fn generate_report(kind: &str, root: &Path) -> Result<String> {
+    use crate::formatters::{waste::WasteFormatter, Formatter};
+
+    let config = ScannerConfigBuilder::new().max_depth(10).build();
+    let (nodes, stats) = scan_with_config(root, config)?;
+
+    match kind {
+        "waste" => {
+            let mut output = Vec::new();
+            WasteFormatter::new().format(&mut output, &nodes, &stats, root)?;
+            Ok(String::from_utf8_lossy(&output).to_string())
+        }
+        "health" => Ok(serde_json::to_string_pretty(&health_score(
+            &nodes, &stats, root,
+        ))?),
+        other => Err(anyhow::anyhow!("Unknown report kind: {}", other)),
+    }
+}
+
+/// A quick 0-100 project health score with the penalties that produced it
+fn health_score(
+    nodes: &[crate::scanner::FileNode],
+    stats: &crate::scanner::TreeStats,
+    root: &Path,
+) -> Value {
+    let mut score: i64 = 100;
+    let mut findings = Vec::new();
+
+    let huge_files = stats
+        .largest_files
+        .iter()
+        .filter(|(size, _)| *size > 50 * 1024 * 1024)
+        .count();
+    if huge_files > 0 {
+        score -= (huge_files as i64 * 5).min(20);
+        findings.push(format!("{} file(s) over 50MB", huge_files));
+    }
+
+    let build_dirs: Vec<&str> = ["target", "node_modules", "dist", "build", "__pycache__"]
+        .into_iter()
+        .filter(|name| root.join(name).is_dir())
+        .collect();
+    if !build_dirs.is_empty() {
+        score -= 5;
+        findings.push(format!("build artifacts present: {}", build_dirs.join(", ")));
+    }
+
+    let denied = nodes.iter().filter(|n| n.permission_denied).count();
+    if denied > 0 {
+        score -= 5;
+        findings.push(format!("{} path(s) unreadable", denied));
+    }
+
+    let security_hits: usize = nodes.iter().map(|n| n.security_findings.len()).sum();
+    if security_hits > 0 {
+        score -= (security_hits as i64 * 10).min(30);
+        findings.push(format!("{} security finding(s)", security_hits));
+    }
+
+    json!({
+        "path": root.display().to_string(),
+        "score": score.max(0),
+        "grade": match score.max(0) {
+            90..=100 => "A",
+            75..=89 => "B",
+            60..=74 => "C",
+            40..=59 => "D",
+            _ => "F",
+        },
+        "findings": findings,
+        "totals": {
+            "files": stats.total_files,
+            "dirs": stats.total_dirs,
+            "size_bytes": stats.total_size,
+        },
+        "generated_at": chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    })
+}
+
+/// Default quiet time between updated notifications for one URI - a `cargo
+/// build` shouldn't turn the client's inbox into a firehose. Subscribers
+/// can pick their own window via the `debounce_ms` subscribe param.
+const NOTIFY_COOLDOWN: Duration = Duration::from_secs(2);
+
+/// One live subscription - dropping it drops the watcher
+struct SubscriptionEntry {
+    /// Held only to keep the watch alive
+    _watcher: RecommendedWatcher,
+}
+
+/// Tracks `tree://` subscriptions and pushes updated notifications
+///
+/// The transport attaches a sender at startup (stdio spawns a writer thread
+/// for it); until then subscribe requests are refused rather than silently
+/// swallowed.
+#[derive(Default)]
+pub struct ResourceSubscriptions {
+    notify_tx: Mutex<Option<std::sync::mpsc::Sender<String>>>,
+    entries: Mutex<HashMap<String, SubscriptionEntry>>,
+}
+
+impl ResourceSubscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wire up the channel that carries notifications to the client
+    pub fn attach(&self, tx: std::sync::mpsc::Sender<String>) {
+        *self.notify_tx.lock().unwrap() = Some(tx);
+    }
+
+    /// Start watching the directory behind `uri` (idempotent per URI)
+    pub fn subscribe(&self, uri: &str, ctx: &McpContext, debounce: Duration) -> Result<()> {
+        let Some(path_str) = uri.strip_prefix("tree://") else {
+            anyhow::bail!("Only tree:// resources support subscriptions (got '{}')", uri);
+        };
+        let path = tree_uri_path(path_str, ctx)?;
+
+        let Some(tx) = self.notify_tx.lock().unwrap().clone() else {
+            anyhow::bail!("This transport does not support notifications");
+        };
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.contains_key(uri) {
+            return Ok(()); // Already watching - nothing to do
+        }
+
+        let notification_uri = uri.to_string();
+        let last_sent = Mutex::new(Instant::now() - debounce);
+        let mut watcher = RecommendedWatcher::new(
+            move |res: Result<notify::Event, notify::Error>| {
+                let Ok(event) = res else { return };
+                if !matches!(
+                    event.kind,
+                    notify::EventKind::Create(_)
+                        | notify::EventKind::Modify(_)
+                        | notify::EventKind::Remove(_)
+                ) {
+                    return;
+                }
+                // Debounce: one notification per window is plenty
+                let mut last = last_sent.lock().unwrap();
+                if last.elapsed() < debounce {
+                    return;
+                }
+                *last = Instant::now();
+                let notification = json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": { "uri": notification_uri }
+                });
+                let _ = tx.send(notification.to_string());
+            },
+            notify::Config::default(),
+        )?;
+        watcher.watch(&path, RecursiveMode::Recursive)?;
+
+        entries.insert(uri.to_string(), SubscriptionEntry { _watcher: watcher });
+        Ok(())
+    }
+
+    /// Stop watching - dropping the entry tears down the notify backend
+    pub fn unsubscribe(&self, uri: &str) {
+        self.entries.lock().unwrap().remove(uri);
+    }
+
+    /// URIs currently being watched (server_info reports these)
+    pub fn active_uris(&self) -> Vec<String> {
+        let mut uris: Vec<String> = self.entries.lock().unwrap().keys().cloned().collect();
+        uris.sort();
+        uris
+    }
+}
+
 async fn read_directory_cache(ctx: Arc<McpContext>) -> Result<Value> {
     // Get cache statistics
     let cache_size = ctx.cache.len();
@@ -12,6 +12,7 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::interval;
 
+use super::unified_watcher::{AggregateEvent, BatchSummary, EventAggregator, EventGranularity};
 use super::{is_path_allowed, McpContext};
 use crate::formatters::{ai::AiFormatter, hex::HexFormatter, quantum::QuantumFormatter, Formatter};
 use crate::scanner::{FileNode, Scanner, ScannerConfig};
@@ -28,6 +29,10 @@ pub enum SseEvent {
     Modified { path: String, node: FileNode },
     /// File or directory deleted
     Deleted { path: String },
+    /// File or directory renamed (paired from delete+create or rename halves)
+    Renamed { from: String, to: String },
+    /// One debounce window's worth of changes, summarized
+    Changes { path: String, summary: BatchSummary },
     /// Directory analysis update
     Analysis {
         path: String,
@@ -69,6 +74,24 @@ pub struct SseConfig {
     pub include_patterns: Vec<String>,
     /// File patterns to exclude
     pub exclude_patterns: Vec<String>,
+    /// Semantic categories to emit events for ("source", "tests", "config",
+    /// ...); empty means everything
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Event slicing: raw, debounced (default), or batch summaries
+    #[serde(default)]
+    pub granularity: EventGranularity,
+    /// Debounce window in milliseconds (ignored for raw granularity)
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Collapse more than this many events under one directory into a
+    /// single per-directory summary event; 0 disables coalescing
+    #[serde(default)]
+    pub coalesce_threshold: usize,
+}
+
+pub fn default_debounce_ms() -> u64 {
+    250
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,6 +116,10 @@ impl Default for SseConfig {
             max_depth: None,
             include_patterns: vec![],
             exclude_patterns: vec![],
+            categories: vec![],
+            granularity: EventGranularity::default(),
+            debounce_ms: default_debounce_ms(),
+            coalesce_threshold: 0,
         }
     }
 }
@@ -215,46 +242,154 @@ async fn watch_directory(
 
     watcher.watch(&config.path, RecursiveMode::Recursive)?;
 
-    // Process file system events
-    while let Some(event) = watcher_rx.recv().await {
-        match event.kind {
-            notify::EventKind::Create(_) => {
-                for path in event.paths {
-                    if let Ok(node) = scan_single_path(&path).await {
-                        tx.send(SseEvent::Created {
-                            path: path.display().to_string(),
-                            node,
-                        })
-                        .await?;
+    // Semantic filter: drop events for paths outside the requested
+    // categories before they reach subscribers (build artifacts, assets, ...)
+    let category_filter = crate::semantic::CategoryFilter::new(&config.categories);
+
+    // Raw granularity: forward every notify event as-is (pre-aggregation
+    // behavior, where an editor's atomic save shows as delete+create)
+    if config.granularity == EventGranularity::Raw {
+        while let Some(mut event) = watcher_rx.recv().await {
+            event.paths.retain(|p| category_filter.allows(p));
+            if event.paths.is_empty() {
+                continue;
+            }
+            match event.kind {
+                notify::EventKind::Create(_) => {
+                    for path in event.paths {
+                        if let Ok(node) = scan_single_path(&path).await {
+                            tx.send(SseEvent::Created {
+                                path: path.display().to_string(),
+                                node,
+                            })
+                            .await?;
+                        }
                     }
                 }
-            }
-            notify::EventKind::Modify(_) => {
-                for path in event.paths {
-                    if let Ok(node) = scan_single_path(&path).await {
-                        tx.send(SseEvent::Modified {
+                notify::EventKind::Modify(_) => {
+                    for path in event.paths {
+                        if let Ok(node) = scan_single_path(&path).await {
+                            tx.send(SseEvent::Modified {
+                                path: path.display().to_string(),
+                                node,
+                            })
+                            .await?;
+                        }
+                    }
+                }
+                notify::EventKind::Remove(_) => {
+                    for path in event.paths {
+                        tx.send(SseEvent::Deleted {
                             path: path.display().to_string(),
-                            node,
                         })
                         .await?;
                     }
                 }
+                _ => {}
             }
-            notify::EventKind::Remove(_) => {
-                for path in event.paths {
-                    tx.send(SseEvent::Deleted {
-                        path: path.display().to_string(),
-                    })
-                    .await?;
+        }
+        return Ok(());
+    }
+
+    // Debounced/batch: coalesce events per window, pairing rename halves
+    let mut aggregator = EventAggregator::new(Duration::from_millis(config.debounce_ms.max(1)))
+        .with_directory_coalescing(config.coalesce_threshold);
+    loop {
+        match tokio::time::timeout(aggregator.window(), watcher_rx.recv()).await {
+            Ok(Some(mut event)) => {
+                event.paths.retain(|p| category_filter.allows(p));
+                if !event.paths.is_empty() {
+                    aggregator.push(&event);
                 }
             }
-            _ => {}
+            Ok(None) => break, // Watcher gone - drain below and stop
+            Err(_) => {}       // Window elapsed with no new events
+        }
+
+        if !aggregator.ready() {
+            continue;
+        }
+        let events = aggregator.flush();
+        if config.granularity == EventGranularity::Batch {
+            tx.send(SseEvent::Changes {
+                path: config.path.display().to_string(),
+                summary: BatchSummary::of(&events, aggregator.window()),
+            })
+            .await?;
+            continue;
+        }
+        for event in events {
+            send_aggregate_event(&tx, event).await?;
+        }
+    }
+
+    // Flush whatever the final window collected
+    let events = aggregator.flush();
+    if !events.is_empty() {
+        if config.granularity == EventGranularity::Batch {
+            tx.send(SseEvent::Changes {
+                path: config.path.display().to_string(),
+                summary: BatchSummary::of(&events, aggregator.window()),
+            })
+            .await?;
+        } else {
+            for event in events {
+                send_aggregate_event(&tx, event).await?;
+            }
         }
     }
 
     Ok(())
 }
 
+/// Map one debounced logical change onto the SSE event vocabulary
+#[allow(dead_code)]
+async fn send_aggregate_event(tx: &mpsc::Sender<SseEvent>, event: AggregateEvent) -> Result<()> {
+    match event {
+        AggregateEvent::Created(path) => {
+            if let Ok(node) = scan_single_path(&path).await {
+                tx.send(SseEvent::Created {
+                    path: path.display().to_string(),
+                    node,
+                })
+                .await?;
+            }
+        }
+        AggregateEvent::Modified(path) => {
+            if let Ok(node) = scan_single_path(&path).await {
+                tx.send(SseEvent::Modified {
+                    path: path.display().to_string(),
+                    node,
+                })
+                .await?;
+            }
+        }
+        AggregateEvent::Deleted(path) => {
+            tx.send(SseEvent::Deleted {
+                path: path.display().to_string(),
+            })
+            .await?;
+        }
+        AggregateEvent::Renamed { from, to } => {
+            tx.send(SseEvent::Renamed {
+                from: from.display().to_string(),
+                to: to.display().to_string(),
+            })
+            .await?;
+        }
+        // A coalesced directory storm reuses the batch-summary shape,
+        // scoped to the noisy directory instead of the watch root
+        AggregateEvent::DirectoryChanged { dir, summary } => {
+            tx.send(SseEvent::Changes {
+                path: dir.display().to_string(),
+                summary,
+            })
+            .await?;
+        }
+    }
+    Ok(())
+}
+
 /// Scan a single path and create FileNode
 #[allow(dead_code)]
 async fn scan_single_path(path: &Path) -> Result<FileNode> {
@@ -332,6 +467,9 @@ async fn scan_single_path(path: &Path) -> Result<FileNode> {
         security_findings: Vec::new(),
         change_status: None,
         content_hash: None,
+        hardlink_id: None,
+        allocated_size: None,
+        xattrs: None,
     })
 }
 
@@ -0,0 +1,242 @@
+//! Project check runner - the `run_checks` tool
+//!
+//! Runs fmt/lint/test style commands on behalf of the AI, but only commands
+//! the user has written into `.st/checks.toml` (see
+//! [`crate::mcp::permissions::CheckPolicy`]). No shell, no AI-supplied
+//! arguments, per-check timeouts, and an optional per-check confirmation
+//! gate. Output comes back summarized (exit status plus the tail of the
+//! combined stream) so a noisy test run doesn't flood the context window.
+
+use super::definitions::RunChecksArgs;
+use crate::mcp::helpers::validate_and_convert_path;
+use crate::mcp::permissions::{CheckCommand, CheckPolicy};
+use crate::mcp::McpContext;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Keep at most this many trailing lines of combined output per check
+const MAX_SUMMARY_LINES: usize = 40;
+/// And never more than this many bytes, whichever is smaller
+const MAX_SUMMARY_BYTES: usize = 4096;
+
+/// Run one or all allowlisted project checks
+pub async fn run_checks(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let args: RunChecksArgs = serde_json::from_value(args)?;
+    let project_root = validate_and_convert_path(&args.path, &ctx)?;
+
+    // Opt-in gate: no checks.toml means the tool does nothing
+    let Some(policy) = CheckPolicy::load(&project_root)? else {
+        return Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": format!(
+                    "run_checks is disabled for this project - no {} found.\n\
+                     To opt in, create it with the commands you allow, e.g.:\n\n\
+                     [checks.fmt]\n\
+                     command = \"cargo fmt --check\"\n\n\
+                     [checks.test]\n\
+                     command = \"cargo test\"\n\
+                     confirm = true   # require explicit user confirmation\n\n\
+                     Only commands listed there can ever run.",
+                    CheckPolicy::config_path(&project_root).display()
+                )
+            }]
+        }));
+    };
+
+    // Which checks to run: one by name, or the whole allowlist
+    let selected: Vec<(&str, &CheckCommand)> = match &args.check {
+        Some(name) => match policy.get(name) {
+            Some(check) => vec![(name.as_str(), check)],
+            None => {
+                return Ok(json!({
+                    "content": [{
+                        "type": "text",
+                        "text": format!(
+                            "Unknown check '{}'. Allowlisted checks: {}",
+                            name,
+                            policy.names().join(", ")
+                        )
+                    }]
+                }));
+            }
+        },
+        None => policy
+            .checks
+            .iter()
+            .map(|(name, check)| (name.as_str(), check))
+            .collect(),
+    };
+
+    let mut sections = Vec::new();
+    let mut results = Vec::new();
+
+    for (name, check) in selected {
+        // Confirmation policy: confirm = true checks only run once the
+        // client asserts the user has seen the exact command
+        if check.confirm && !args.confirm {
+            sections.push(format!(
+                "⏸️ {} - needs confirmation\n   Command: {}\n   Show this to the user, \
+                 then call again with confirm: true",
+                name, check.command
+            ));
+            results.push(json!({
+                "check": name,
+                "command": check.command,
+                "status": "needs_confirmation",
+            }));
+            continue;
+        }
+
+        let (section, result) = run_one_check(name, check, &project_root).await;
+        sections.push(section);
+        results.push(result);
+    }
+
+    let passed = results
+        .iter()
+        .filter(|r| r["status"] == "passed")
+        .count();
+    let header = format!(
+        "CHECK RESULTS: {}/{} passed\nProject: {}\n",
+        passed,
+        results.len(),
+        project_root.display()
+    );
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": format!("{}\n{}", header, sections.join("\n\n"))
+        }],
+        "results": results,
+    }))
+}
+
+/// Spawn a single check and turn its outcome into a report section
+async fn run_one_check(
+    name: &str,
+    check: &CheckCommand,
+    project_root: &std::path::Path,
+) -> (String, Value) {
+    let argv = match check.argv() {
+        Ok(argv) => argv,
+        Err(e) => {
+            return (
+                format!("❌ {} - invalid allowlist entry: {}", name, e),
+                json!({ "check": name, "status": "error", "error": e.to_string() }),
+            );
+        }
+    };
+
+    // Checks carry their own user-approved allowlist, but the --no-exec
+    // kill-switch still outranks it - nothing may spawn with it set
+    if crate::exec_policy::no_exec() {
+        return (
+            format!("🚫 {} - skipped: --no-exec is active", name),
+            json!({ "check": name, "status": "skipped", "reason": "no_exec" }),
+        );
+    }
+
+    let started = Instant::now();
+    let mut command = tokio::process::Command::new(&argv[0]);
+    command
+        .args(&argv[1..])
+        .current_dir(project_root)
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(Duration::from_secs(check.timeout_secs), command.output());
+
+    match output.await {
+        Err(_) => (
+            format!(
+                "⏱️ {} - timed out after {}s\n   Command: {}",
+                name, check.timeout_secs, check.command
+            ),
+            json!({
+                "check": name,
+                "command": check.command,
+                "status": "timeout",
+                "timeout_secs": check.timeout_secs,
+            }),
+        ),
+        Ok(Err(e)) => (
+            format!(
+                "❌ {} - failed to start: {}\n   Command: {}",
+                name, e, check.command
+            ),
+            json!({
+                "check": name,
+                "command": check.command,
+                "status": "error",
+                "error": e.to_string(),
+            }),
+        ),
+        Ok(Ok(output)) => {
+            let duration_ms = started.elapsed().as_millis() as u64;
+            let passed = output.status.success();
+            let summary = summarize_output(&output.stdout, &output.stderr);
+
+            let mut section = format!(
+                "{} {} - {} in {}ms (exit {})\n   Command: {}",
+                if passed { "✅" } else { "❌" },
+                name,
+                if passed { "passed" } else { "failed" },
+                duration_ms,
+                output.status.code().map_or("?".to_string(), |c| c.to_string()),
+                check.command
+            );
+            if !summary.is_empty() && (!passed || check.description.is_some()) {
+                section.push_str(&format!("\n{}", indent(&summary)));
+            }
+
+            (
+                section,
+                json!({
+                    "check": name,
+                    "command": check.command,
+                    "status": if passed { "passed" } else { "failed" },
+                    "exit_code": output.status.code(),
+                    "duration_ms": duration_ms,
+                    "output_tail": summary,
+                }),
+            )
+        }
+    }
+}
+
+/// Tail of stdout+stderr, capped by lines and bytes
+fn summarize_output(stdout: &[u8], stderr: &[u8]) -> String {
+    let mut combined = String::from_utf8_lossy(stdout).into_owned();
+    let err = String::from_utf8_lossy(stderr);
+    if !err.trim().is_empty() {
+        if !combined.is_empty() {
+            combined.push('\n');
+        }
+        combined.push_str(&err);
+    }
+
+    let lines: Vec<&str> = combined.lines().collect();
+    let skipped = lines.len().saturating_sub(MAX_SUMMARY_LINES);
+    let mut tail = lines[skipped..].join("\n");
+    if tail.len() > MAX_SUMMARY_BYTES {
+        let mut cut = tail.len() - MAX_SUMMARY_BYTES;
+        while !tail.is_char_boundary(cut) {
+            cut += 1;
+        }
+        tail = format!("…{}", &tail[cut..]);
+    }
+    if skipped > 0 {
+        tail = format!("… {} earlier lines omitted …\n{}", skipped, tail);
+    }
+    tail.trim_end().to_string()
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|l| format!("   {}", l))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
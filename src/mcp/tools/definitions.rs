@@ -105,6 +105,10 @@ pub fn default_stats_interval() -> u64 {
     60
 }
 
+pub fn default_debounce_ms() -> u64 {
+    crate::mcp::sse::default_debounce_ms()
+}
+
 // =============================================================================
 // Shared argument structs
 // =============================================================================
@@ -114,6 +118,9 @@ pub fn default_stats_interval() -> u64 {
 pub struct AnalyzeDirectoryArgs {
     #[serde(default = "default_path")]
     pub path: String,
+    /// Several roots in one call - each renders as its own section
+    #[serde(default)]
+    pub paths: Vec<String>,
     #[serde(default = "default_mode")]
     pub mode: String,
     #[serde(default = "default_max_depth")]
@@ -126,6 +133,14 @@ pub struct AnalyzeDirectoryArgs {
     pub path_mode: String,
     #[serde(default)]
     pub compress: Option<bool>,
+    #[serde(default)]
+    pub loc: bool,
+    /// Named scan profile from ~/.st/config.toml to fill unset parameters
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Keep output under roughly this many tokens by reducing depth
+    #[serde(default)]
+    pub token_budget: Option<usize>,
 }
 
 /// Arguments for project_context_dump tool
@@ -154,6 +169,9 @@ pub struct FindFilesArgs {
     #[serde(default = "default_path")]
     pub path: String,
     pub pattern: Option<String>,
+    /// Glob patterns with include/exclude semantics (`!` prefix excludes)
+    #[serde(default)]
+    pub glob: Vec<String>,
     pub file_type: Option<String>,
     pub entry_type: Option<String>,
     pub min_size: Option<String>,
@@ -162,6 +180,34 @@ pub struct FindFilesArgs {
     pub older_than: Option<String>,
     #[serde(default = "default_max_depth")]
     pub max_depth: usize,
+    /// Result format: "json" (default) or "ai-table" (schema line + columnar rows)
+    pub format: Option<String>,
+    /// Treat `pattern` as an fzf-style fuzzy query instead of a regex and
+    /// rank results by match score
+    #[serde(default)]
+    pub fuzzy: bool,
+    /// Opaque pagination cursor from a previous response's `next_cursor`
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Entries per page; sending either pagination field opts in
+    #[serde(default)]
+    pub page_size: Option<usize>,
+}
+
+/// One scope in a request_permissions call
+#[derive(Debug, Deserialize)]
+pub struct ScopeRequest {
+    /// Path prefix the grant should cover
+    pub path: String,
+    /// "read" or "write" (write implies read)
+    pub access: crate::mcp::permissions::Access,
+}
+
+/// Arguments for request_permissions tool
+#[derive(Debug, Deserialize)]
+pub struct RequestPermissionsArgs {
+    #[serde(default)]
+    pub scopes: Vec<ScopeRequest>,
 }
 
 /// Arguments for verify_permissions tool
@@ -189,6 +235,16 @@ pub struct WatchDirectorySseArgs {
     pub include_patterns: Vec<String>,
     #[serde(default)]
     pub exclude_patterns: Vec<String>,
+    /// Semantic categories to emit events for; empty means everything
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Event slicing: "raw", "debounced" (default), or "batch"
+    pub granularity: Option<String>,
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Collapse >N events under one directory into one summary; 0 = off
+    #[serde(default)]
+    pub coalesce_threshold: usize,
 }
 
 /// Arguments for track_file_operation tool
@@ -238,3 +294,44 @@ pub struct SmartReadArgs {
     #[serde(default)]
     pub hex_line_numbers: Option<bool>,
 }
+
+/// One file in a read_many batch
+#[derive(Debug, Deserialize)]
+pub struct ReadManyEntry {
+    pub file_path: String,
+    /// Explicit 1-based inclusive line ranges, e.g. [[1, 40], [120, 160]].
+    /// When set, the file is sliced instead of smart-read
+    #[serde(default)]
+    pub ranges: Vec<(usize, usize)>,
+    /// Function/cell/section names to expand (AST compression stays on
+    /// for the rest) - ignored when `ranges` is set
+    #[serde(default)]
+    pub expand_functions: Vec<String>,
+}
+
+/// Arguments for run_checks tool
+#[derive(Debug, Deserialize)]
+pub struct RunChecksArgs {
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Allowlisted check name from `.st/checks.toml`; omit to run all
+    #[serde(default)]
+    pub check: Option<String>,
+    /// Assert the user has confirmed - required for checks marked
+    /// `confirm = true` in the allowlist
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Arguments for read_many tool
+#[derive(Debug, Deserialize)]
+pub struct ReadManyArgs {
+    pub files: Vec<ReadManyEntry>,
+    #[serde(default = "default_true")]
+    pub compress: bool,
+    #[serde(default = "default_true")]
+    pub show_line_numbers: bool,
+    /// Use hex line numbers. If not specified, uses MCP config default
+    #[serde(default)]
+    pub hex_line_numbers: Option<bool>,
+}
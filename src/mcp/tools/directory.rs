@@ -7,14 +7,16 @@ use super::definitions::{AnalyzeDirectoryArgs, ProjectContextDumpArgs};
 use super::git::get_git_context;
 use super::statistics::get_statistics;
 use crate::formatters::{
-    ai::AiFormatter, classic::ClassicFormatter, csv::CsvFormatter, digest::DigestFormatter,
-    hex::HexFormatter, json::JsonFormatter, quantum::QuantumFormatter,
+    ai::AiFormatter, classic::ClassicFormatter, csv::CsvFormatter, deps::DepsFormatter,
+    digest::DigestFormatter, hex::HexFormatter, json::JsonFormatter, perms::PermsFormatter,
+    quantum::QuantumFormatter,
     quantum_semantic::QuantumSemanticFormatter, semantic::SemanticFormatter,
     stats::StatsFormatter, summary::SummaryFormatter, summary_ai::SummaryAiFormatter,
     tsv::TsvFormatter, Formatter, PathDisplayMode,
 };
 use crate::mcp::helpers::{
-    scan_with_config, should_use_default_ignores, validate_and_convert_path, ScannerConfigBuilder,
+    scan_shared, scan_with_config, should_use_default_ignores, validate_and_convert_path,
+    ScannerConfigBuilder,
 };
 use crate::mcp::McpContext;
 use anyhow::Result;
@@ -23,18 +25,70 @@ use std::sync::Arc;
 
 /// Main directory analysis tool
 pub async fn analyze_directory(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
-    let args: AnalyzeDirectoryArgs = serde_json::from_value(args)?;
+    let raw = args.clone();
+    let mut args: AnalyzeDirectoryArgs = serde_json::from_value(args)?;
+
+    // Named scan profile: mirrors the CLI's --profile precedence - the
+    // profile only fills parameters the caller left out of the request.
+    if let Some(name) = &args.profile {
+        let config = crate::config::StConfig::load()?;
+        let profile = config.scan_profile(name)?;
+        if raw.get("mode").is_none() {
+            if let Some(mode) = &profile.mode {
+                args.mode = mode.clone();
+            }
+        }
+        if raw.get("max_depth").is_none() {
+            if let Some(depth) = profile.depth {
+                args.max_depth = depth;
+            }
+        }
+        if raw.get("show_hidden").is_none() {
+            if let Some(all) = profile.all {
+                args.show_hidden = all;
+            }
+        }
+        if raw.get("show_ignored").is_none() {
+            if let Some(show_ignored) = profile.show_ignored {
+                args.show_ignored = show_ignored;
+            }
+        }
+    }
+
+    // Several roots in one call: run each through the normal single-path
+    // flow with identical options and stack the rendered sections
+    if !args.paths.is_empty() {
+        let mut sections = Vec::with_capacity(args.paths.len());
+        for path in &args.paths {
+            let mut sub = raw.clone();
+            if let Some(obj) = sub.as_object_mut() {
+                obj.remove("paths");
+                obj.insert("path".to_string(), json!(path));
+            }
+            let result = Box::pin(analyze_directory(sub, ctx.clone())).await?;
+            let text = result["content"][0]["text"].as_str().unwrap_or_default();
+            sections.push(format!("=== {} ===\n{}", path, text.trim_end()));
+        }
+        return Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": sections.join("\n\n")
+            }]
+        }));
+    }
+
     let path = validate_and_convert_path(&args.path, &ctx)?;
 
     // Check cache if enabled
     let cache_key = format!(
-        "{}:{}:{}:{}:{}:{}",
+        "{}:{}:{}:{}:{}:{}:{}",
         path.display(),
         args.mode,
         args.max_depth,
         args.show_hidden,
         args.show_ignored,
-        args.path_mode
+        args.path_mode,
+        args.token_budget.unwrap_or(0)
     );
 
     if ctx.config.cache_enabled {
@@ -62,8 +116,9 @@ pub async fn analyze_directory(args: Value, ctx: Arc<McpContext>) -> Result<Valu
         eprintln!("   Maximum 100k files, 1 minute timeout for MCP operations");
     }
 
-    // Scan directory
-    let (nodes, stats) = scan_with_config(&path, config)?;
+    // Scan directory (shared with other tools hitting the same path/options)
+    let scan = scan_shared(&path, config, &ctx)?;
+    let (nodes, stats) = &*scan;
 
     // Convert path mode
     let path_display_mode = match args.path_mode.as_str() {
@@ -75,24 +130,13 @@ pub async fn analyze_directory(args: Value, ctx: Arc<McpContext>) -> Result<Valu
     // MCP optimizations: no emoji for clean output
     let mcp_no_emoji = true;
 
-    // Compression logic:
-    // 1. If user explicitly sets compress parameter, use that
-    // 2. Otherwise, check MCP_NO_COMPRESS env var
-    // 3. Default: false for ALL modes (decompressed by default)
-    let default_compress = false;
-
-    let mcp_compress = match args.compress {
-        Some(compress) => compress,
-        None => {
-            if std::env::var("MCP_NO_COMPRESS")
-                .is_ok_and(|v| v == "1" || v.to_lowercase() == "true")
-            {
-                false
-            } else {
-                default_compress
-            }
-        }
-    };
+    // Compression negotiation:
+    // 1. Explicit per-call compress parameter wins
+    // 2. Otherwise the session-level preference negotiated at initialize
+    // 3. Compressed output only goes to clients that advertised support -
+    //    anyone else gets decompressed output plus a warning field
+    let negotiated = crate::compression_manager::negotiate_compression(args.compress);
+    let mcp_compress = negotiated.compress;
 
     // Handle summary mode - auto-switch to AI version in MCP context
     let effective_mode = match args.mode.as_str() {
@@ -114,7 +158,7 @@ pub async fn analyze_directory(args: Value, ctx: Arc<McpContext>) -> Result<Valu
             )),
             "json" => Box::new(JsonFormatter::new(false)),
             "ai" => Box::new(AiFormatter::new(mcp_no_emoji, path_display_mode)),
-            "stats" => Box::new(StatsFormatter::new()),
+            "stats" => Box::new(StatsFormatter::new().with_loc(args.loc)),
             "csv" => Box::new(CsvFormatter::new()),
             "tsv" => Box::new(TsvFormatter::new()),
             "digest" => Box::new(DigestFormatter::new()),
@@ -122,10 +166,26 @@ pub async fn analyze_directory(args: Value, ctx: Arc<McpContext>) -> Result<Valu
             "semantic" => Box::new(SemanticFormatter::new(path_display_mode, mcp_no_emoji)),
             "quantum-semantic" => Box::new(QuantumSemanticFormatter::new()),
             "summary" => Box::new(SummaryFormatter::new(!mcp_no_emoji)),
-            "summary-ai" => Box::new(SummaryAiFormatter::new(mcp_compress)),
+            "summary-ai" => Box::new(SummaryAiFormatter::new(mcp_compress).with_loc(args.loc)),
             _ => return Err(anyhow::anyhow!("Invalid mode: {}", args.mode)),
         };
-        formatter.format(&mut output, &nodes, &stats, &path)?;
+        match args.token_budget {
+            // Degrade gracefully: re-render shallower until the token
+            // estimate fits, then report what got cut
+            Some(budget) => {
+                let (fitted, outcome) =
+                    crate::token_budget::fit_to_budget(budget, nodes, |subset| {
+                        let mut buf = Vec::new();
+                        formatter.format(&mut buf, subset, stats, &path)?;
+                        Ok(String::from_utf8_lossy(&buf).into_owned())
+                    })?;
+                output.extend_from_slice(fitted.as_bytes());
+                if let Some(note) = outcome.note() {
+                    output.extend_from_slice(format!("\n{}\n", note).as_bytes());
+                }
+            }
+            None => formatter.format(&mut output, nodes, stats, &path)?,
+        }
     } // formatter dropped here
 
     // Handle different output formats
@@ -174,12 +234,16 @@ pub async fn analyze_directory(args: Value, ctx: Arc<McpContext>) -> Result<Valu
         ctx.cache.set(cache_key, final_output.clone()).await;
     }
 
-    Ok(json!({
+    let mut response = json!({
         "content": [{
             "type": "text",
             "text": final_output
         }]
-    }))
+    });
+    if let Some(warning) = negotiated.warning {
+        response["content"][0]["warning"] = json!(warning);
+    }
+    Ok(response)
 }
 
 /// Quick 3-level directory overview
@@ -589,6 +653,67 @@ pub fn compress_file_quantum(content: &str, filename: &str) -> String {
     }
 }
 
+/// Ownership and permission audit - the `--mode perms` report over MCP
+///
+/// Scans everything (hidden files included - a world-writable `.ssh` is
+/// exactly what an audit must not miss) and returns the prioritized
+/// findings report.
+pub async fn audit_permissions(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let path_str = args["path"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
+    let path = validate_and_convert_path(path_str, &ctx)?;
+    let max_depth = args["max_depth"].as_u64().unwrap_or(20) as usize;
+
+    let config = ScannerConfigBuilder::new()
+        .max_depth(max_depth)
+        .show_hidden(true)
+        .respect_gitignore(false)
+        .build();
+
+    let (nodes, stats) = scan_with_config(&path, config)?;
+
+    let formatter = PermsFormatter::new();
+    let mut output = Vec::new();
+    formatter.format(&mut output, &nodes, &stats, &path)?;
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": String::from_utf8_lossy(&output).to_string()
+        }]
+    }))
+}
+
+/// Cross-project dependency graph - the `--mode deps` report over MCP
+///
+/// Parses every Cargo.toml / package.json / pyproject.toml / go.mod in the
+/// tree and returns the graph as mermaid (default) or DOT, with
+/// mixed-version dependencies flagged.
+pub async fn analyze_dependencies(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let path_str = args["path"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
+    let path = validate_and_convert_path(path_str, &ctx)?;
+    let max_depth = args["max_depth"].as_u64().unwrap_or(20) as usize;
+    let graph = args["graph"].as_str().unwrap_or("mermaid").to_string();
+
+    let config = ScannerConfigBuilder::new().max_depth(max_depth).build();
+
+    let (nodes, stats) = scan_with_config(&path, config)?;
+
+    let formatter = DepsFormatter::new().with_graph(Some(graph));
+    let mut output = Vec::new();
+    formatter.format(&mut output, &nodes, &stats, &path)?;
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": String::from_utf8_lossy(&output).to_string()
+        }]
+    }))
+}
+
 /// Semantic analysis using wave-based grouping
 pub async fn semantic_analysis(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     let path = args["path"]
@@ -1,6 +1,6 @@
 //! Git-related tools
 //!
-//! Contains get_git_context and get_git_status handlers.
+//! Contains get_git_context, get_git_status and analyze_churn handlers.
 
 use super::directory::analyze_directory;
 use crate::mcp::helpers::validate_and_convert_path;
@@ -127,3 +127,39 @@ pub async fn get_git_status(args: Value, ctx: Arc<McpContext>) -> Result<Value>
         }]
     }))
 }
+
+/// Git-history churn hotspots: per-file commit counts, lines changed and
+/// recency-weighted scores. Same engine as `st --mode churn`.
+pub async fn analyze_churn(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    use crate::formatters::churn::ChurnReport;
+
+    let path_str = args["path"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
+    let path = validate_and_convert_path(path_str, &ctx)?;
+    let limit = args["limit"].as_u64().unwrap_or(20) as usize;
+    let treemap = args["format"].as_str() == Some("treemap");
+
+    let Some(report) = ChurnReport::gather(&path) else {
+        return Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": format!("Not a git repository (churn needs history): {}", path.display())
+            }]
+        }));
+    };
+
+    let mut rendered = Vec::new();
+    if treemap {
+        report.render_treemap(&mut rendered, &path, limit)?;
+    } else {
+        report.render_table(&mut rendered, &path, limit)?;
+    }
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": String::from_utf8_lossy(&rendered).to_string()
+        }]
+    }))
+}
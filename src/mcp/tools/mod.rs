@@ -13,8 +13,10 @@
 //! - file_history: File operation tracking
 //! - smart_read: AST-aware file reading
 //! - wave: Wave memory operations
+//! - checks: Allowlisted project command runner (run_checks)
 
 // Submodules
+pub mod checks;
 pub mod compare;
 pub mod definitions;
 pub mod directory;
@@ -32,22 +34,25 @@ pub mod wave;
 pub use definitions::ToolDefinition;
 
 // Re-export handlers that are used externally
+pub use checks::run_checks;
 pub use compare::{analyze_workspace, compare_directories};
 pub use directory::{
-    analyze_directory, project_context_dump, project_overview, quick_tree, semantic_analysis,
+    analyze_dependencies, analyze_directory, audit_permissions, project_context_dump,
+    project_overview, quick_tree,
+    semantic_analysis,
 };
 pub use feedback::{check_for_updates, request_tool, submit_feedback};
 pub use file_history::{get_file_history, get_project_history_summary, track_file_operation};
-pub use git::get_git_status;
+pub use git::{analyze_churn, get_git_status};
 pub use search::{
     find_build_files, find_code_files, find_config_files, find_documentation, find_duplicates,
     find_empty_directories, find_files, find_in_timespan, find_large_files, find_projects,
-    find_recent_changes, find_tests, search_in_files,
+    find_recent_changes, find_secrets, find_tests, search_in_files,
 };
-pub use server::{server_info, verify_permissions};
-pub use smart_read::smart_read;
+pub use server::{request_permissions, server_info, verify_permissions};
+pub use smart_read::{read_many, smart_read};
 pub use sse_tools::watch_directory_sse;
-pub use statistics::{directory_size_breakdown, get_digest, get_statistics};
+pub use statistics::{diff_digests, directory_size_breakdown, get_digest, get_statistics};
 pub use wave::handle_wave_memory;
 
 use super::McpContext;
@@ -58,7 +63,16 @@ use std::sync::Arc;
 
 /// Handle tools/list MCP request
 pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) -> Result<Value> {
-    let tools = vec![
+    Ok(json!({
+        "tools": tool_definitions()
+    }))
+}
+
+/// The canonical tool catalog - the single source of truth for names and
+/// schemas. tools/list serves it verbatim; the consolidated layer audits
+/// itself against it (see `tools_consolidated`).
+pub fn tool_definitions() -> Vec<ToolDefinition> {
+    vec![
         ToolDefinition {
             name: "verify_permissions".to_string(),
             description: "🔐 REQUIRED FIRST STEP: Verify permissions for a path before using other tools. This lightweight check determines which tools are available based on read/write permissions. Always call this first to see what operations are possible!".to_string(),
@@ -73,9 +87,38 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                 "required": ["path"]
             }),
         },
+        ToolDefinition {
+            name: "request_permissions".to_string(),
+            description: "🔑 Request read/write scopes for path prefixes before using write-capable tools. The user approves each scope - ahead of time in ~/.st/mcp-grants.toml or live on their terminal - and grants last for this session only. When the server runs with require_write_grants, smart_edit/create_file/apply_patch and friends refuse paths outside your granted scopes, so ask for the narrowest prefix that covers your work.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "scopes": {
+                        "type": "array",
+                        "description": "Scopes to request",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "path": {
+                                    "type": "string",
+                                    "description": "Path prefix the grant should cover"
+                                },
+                                "access": {
+                                    "type": "string",
+                                    "enum": ["read", "write"],
+                                    "description": "Access level; write implies read"
+                                }
+                            },
+                            "required": ["path", "access"]
+                        }
+                    }
+                },
+                "required": ["scopes"]
+            }),
+        },
         ToolDefinition {
             name: "server_info".to_string(),
-            description: "Get information about the Smart Tree MCP server - shows capabilities, compression options, and performance tips. Call this to understand what Smart Tree can do for you!".to_string(),
+            description: "Get information about the Smart Tree MCP server - live health report (uptime, cache hit rate, watcher status, writable mode, enabled subsystems) plus capabilities, compression options, and performance tips. Call this to understand what Smart Tree can do for you right now!".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -92,6 +135,11 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "string",
                         "description": "Path to the directory to analyze"
                     },
+                    "paths": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Analyze several roots in one call - each is rendered as its own section with the same options applied"
+                    },
                     "mode": {
                         "type": "string",
                         "enum": ["classic", "hex", "json", "ai", "stats", "csv", "tsv", "digest", "quantum", "semantic", "quantum-semantic", "summary", "summary-ai"],
@@ -120,7 +168,7 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     },
                     "compress": {
                         "type": "boolean",
-                        "description": "Compress output with zlib. Default: false (decompressed) for all modes to ensure compatibility with AI systems. Set to true only if your AI can handle base64 compressed content",
+                        "description": "Compress output with zlib. Overrides the session-level preference negotiated at initialize; when unset, that preference applies (default: decompressed). Compressed output is only sent if your client advertised compression support - otherwise you get decompressed output with a warning field",
                         "default": null
                     },
                     "path_mode": {
@@ -129,6 +177,19 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "description": "Path display mode",
                         "default": "off"
                     },
+                    "loc": {
+                        "type": "boolean",
+                        "description": "Add tokei-style code/comment/blank line counts per language (stats and summary-ai modes)",
+                        "default": false
+                    },
+                    "profile": {
+                        "type": "string",
+                        "description": "Named scan profile from ~/.st/config.toml ([profile.NAME] table) supplying mode/depth/filter defaults; explicit parameters override profile values"
+                    },
+                    "token_budget": {
+                        "type": "integer",
+                        "description": "Keep output under roughly this many tokens by reducing tree depth; a note reports what was omitted"
+                    },
                     "page": {
                         "type": "integer",
                         "description": "Page number (1-based) to return when paginating large outputs (works only for non-compressed, non-quantum modes)"
@@ -159,6 +220,11 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "string",
                         "description": "Regex pattern to match file/directory names"
                     },
+                    "glob": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Glob patterns matched against root-relative paths ('!' prefix excludes, braces expand, e.g. ['**/*.{ts,tsx}', '!**/dist/**'])"
+                    },
                     "file_type": {
                         "type": "string",
                         "description": "Filter by file extension (e.g., 'rs', 'py')"
@@ -188,6 +254,25 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "integer",
                         "description": "Maximum depth to traverse (0 = auto, each mode picks ideal depth)",
                         "default": 0
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["json", "ai-table"],
+                        "description": "Result format - 'ai-table' emits a schema line plus columnar rows (~60% fewer tokens than JSON)",
+                        "default": "json"
+                    },
+                    "fuzzy": {
+                        "type": "boolean",
+                        "description": "Treat 'pattern' as an fzf-style fuzzy query and rank results by match score",
+                        "default": false
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -221,6 +306,74 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     "path": {
                         "type": "string",
                         "description": "Path to analyze"
+                    },
+                    "merkle": {
+                        "type": "boolean",
+                        "description": "Return one Merkle digest per directory instead of a single hash - store it and feed it to diff_digests later to localize changes",
+                        "default": false
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "diff_digests".to_string(),
+            description: "🔀 Compare two Merkle digest documents (from get_digest with merkle:true) and pinpoint exactly which subtrees changed - no full diff needed. Omit new_digest to scan the path fresh and compare against right now. Returns changed/added/removed directories plus the deepest 'localized' set where the edits actually live.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "old_digest": {
+                        "type": "string",
+                        "description": "The earlier Merkle digest document"
+                    },
+                    "new_digest": {
+                        "type": "string",
+                        "description": "The later document; omit to scan `path` now instead"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Directory to scan fresh when new_digest is omitted"
+                    }
+                },
+                "required": ["old_digest"]
+            }),
+        },
+        ToolDefinition {
+            name: "audit_permissions".to_string(),
+            description: "🔐 Ownership and permission audit - flags world-writable files and directories, setuid/setgid binaries, entries owned by unexpected UIDs, and self-contradictory directory permissions. Returns a prioritized findings report (CRIT first). Same report as `st --mode perms`. Great first pass on any tree you're about to deploy or share!".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path to audit"
+                    },
+                    "max_depth": {
+                        "type": "integer",
+                        "description": "Maximum traversal depth (default: 20)"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "analyze_dependencies".to_string(),
+            description: "🕸️ Cross-project dependency graph - parses every Cargo.toml, package.json, pyproject.toml, and go.mod in the tree and renders who depends on what as a mermaid flowchart (default) or Graphviz DOT. Dependencies pinned to DIFFERENT versions by different projects are flagged red - instant workspace drift detection! Same graph as `st --mode deps`.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path to the workspace root"
+                    },
+                    "max_depth": {
+                        "type": "integer",
+                        "description": "Maximum traversal depth (default: 20)"
+                    },
+                    "graph": {
+                        "type": "string",
+                        "description": "Graph syntax: mermaid or dot (default: mermaid)",
+                        "enum": ["mermaid", "dot"]
                     }
                 },
                 "required": ["path"]
@@ -333,6 +486,14 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         },
                         "description": "Programming languages to search for",
                         "default": ["all"]
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -347,6 +508,14 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     "path": {
                         "type": "string",
                         "description": "Path to search in"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -361,6 +530,14 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     "path": {
                         "type": "string",
                         "description": "Path to search in"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -380,6 +557,26 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "string",
                         "description": "Keyword or phrase to search for"
                     },
+                    "keywords": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Additional patterns - any may match, or all must with match_all"
+                    },
+                    "regex": {
+                        "type": "boolean",
+                        "description": "Treat patterns as regular expressions (capture groups are reported per match)",
+                        "default": false
+                    },
+                    "whole_word": {
+                        "type": "boolean",
+                        "description": "Match only at word boundaries",
+                        "default": false
+                    },
+                    "match_all": {
+                        "type": "boolean",
+                        "description": "Require every pattern to match in a file (AND); default is any (OR)",
+                        "default": false
+                    },
                     "file_type": {
                         "type": "string",
                         "description": "Limit search to specific file types"
@@ -406,6 +603,20 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "default": 20,
                         "minimum": 1,
                         "maximum": 100
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["json", "ai-table"],
+                        "description": "Result format - 'ai-table' emits a schema line plus columnar match rows (~60% fewer tokens than JSON)",
+                        "default": "json"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path", "keyword"]
@@ -425,6 +636,14 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "string",
                         "description": "Minimum size (e.g., '10M', '1G')",
                         "default": "10M"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -451,7 +670,7 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
         },
         ToolDefinition {
             name: "find_recent_changes".to_string(),
-            description: "📅 Find files modified within the last N days (default: 7). Perfect for understanding recent development activity, tracking changes, or identifying what's been worked on lately. Helps focus attention on active areas of the codebase.".to_string(),
+            description: "📅 Find files modified within the last N days (default: 7), grouped into inferred work sessions with associated git branch/commits. Perfect for answering 'what did we work on yesterday afternoon?' or tracking recent development activity. Set group_by_session=false for a flat file list.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -463,6 +682,24 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "integer",
                         "description": "Files modified within last N days",
                         "default": 7
+                    },
+                    "group_by_session": {
+                        "type": "boolean",
+                        "description": "Cluster changes into work sessions by modification time and attach matching commits (default: true)",
+                        "default": true
+                    },
+                    "session_gap_minutes": {
+                        "type": "integer",
+                        "description": "Gap between modifications that starts a new session (default: 45)",
+                        "default": 45
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -526,15 +763,54 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                 "required": ["path"]
             }),
         },
+        ToolDefinition {
+            name: "analyze_churn".to_string(),
+            description: "🔥 Git-history churn hotspots - per-file commit counts, lines changed, and recency-weighted scores (recent activity counts more than ancient history). PERFECT for spotting risky areas before a refactor: the files everyone keeps editing are the files most likely to bite. Renders as a sorted table or a mermaid treemap.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path inside the git repository to analyze"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Number of hotspots to show",
+                        "default": 20
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["table", "treemap"],
+                        "description": "Sorted table (default) or mermaid treemap",
+                        "default": "table"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
         ToolDefinition {
             name: "find_duplicates".to_string(),
-            description: "🔁 Detect potential duplicate files based on size and name patterns. Helps identify redundant files, backup copies, or files that could be consolidated. Groups files by size for efficient duplicate detection.".to_string(),
+            description: "🔁 Detect duplicate files verified by content hash (not just size!). Uses size grouping plus partial-hash prefiltering, then confirms with a full content hash - no false positives. Helps identify redundant files, backup copies, or files that could be consolidated.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "path": {
                         "type": "string",
                         "description": "Path to search in"
+                    },
+                    "algorithm": {
+                        "type": "string",
+                        "enum": ["blake3", "sha256", "xxhash"],
+                        "description": "Content hash algorithm for verification",
+                        "default": "blake3"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -563,6 +839,14 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     "path": {
                         "type": "string",
                         "description": "Path to search in"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -577,6 +861,14 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     "path": {
                         "type": "string",
                         "description": "Path to search in"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
                     }
                 },
                 "required": ["path"]
@@ -584,13 +876,18 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
         },
         ToolDefinition {
             name: "directory_size_breakdown".to_string(),
-            description: "📊 Get size analysis of immediate subdirectories - shows which folders consume the most space. Perfect for identifying bloated directories, understanding project layout by size, or cleanup opportunities. Returns sorted list with human-readable sizes.".to_string(),
+            description: "📊 Get recursive size analysis of subdirectories - shows which folders consume the most space, nested to the requested depth. Each level carries its own aggregate size, ready for treemap rendering. Hardlink-safe (multiply-linked files counted once). Perfect for identifying bloated directories or cleanup opportunities.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "path": {
                         "type": "string",
                         "description": "Path to analyze"
+                    },
+                    "depth": {
+                        "type": "integer",
+                        "description": "How many directory levels to include in the nested breakdown (1-10)",
+                        "default": 1
                     }
                 },
                 "required": ["path"]
@@ -605,6 +902,36 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     "path": {
                         "type": "string",
                         "description": "Path to search in"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque pagination cursor - echo next_cursor from the previous page (JSON output only)"
+                    },
+                    "page_size": {
+                        "type": "integer",
+                        "description": "Entries per page (default 100, max 1000); sending either pagination field opts in"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDefinition {
+            name: "find_secrets".to_string(),
+            description: "🕵️ Scan file contents for credentials - AWS keys, private key blocks, GitHub/Slack tokens, and generic api_key/password assignments gated by Shannon entropy (placeholders don't fire). Reports file, line, and rule matched. Matched values are REDACTED by default; same sweep as `st --mode secrets`. Run it before every publish!".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path to scan"
+                    },
+                    "max_depth": {
+                        "type": "integer",
+                        "description": "Maximum traversal depth (default: 20)"
+                    },
+                    "redact": {
+                        "type": "boolean",
+                        "description": "Redact matched values to prefix + length (default: true)"
                     }
                 },
                 "required": ["path"]
@@ -814,6 +1141,27 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "array",
                         "items": {"type": "string"},
                         "description": "File patterns to exclude"
+                    },
+                    "categories": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Semantic categories to emit events for (e.g. 'source', 'tests', 'config', 'docs') - build artifacts and other noise are dropped. Empty means everything"
+                    },
+                    "granularity": {
+                        "type": "string",
+                        "enum": ["raw", "debounced", "batch"],
+                        "description": "Event slicing: 'debounced' coalesces per path and pairs renames (atomic saves become one modified event), 'batch' sends one summary per window, 'raw' forwards every notify event",
+                        "default": "debounced"
+                    },
+                    "debounce_ms": {
+                        "type": "integer",
+                        "description": "Debounce window in milliseconds (ignored for raw granularity)",
+                        "default": 250
+                    },
+                    "coalesce_threshold": {
+                        "type": "integer",
+                        "description": "Collapse more than this many events under one directory into a single 'changes' summary for that directory - tames build-output storms. 0 (default) keeps every event individual",
+                        "default": 0
                     }
                 },
                 "required": ["path"]
@@ -883,7 +1231,50 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                 "required": ["project_path"]
             }),
         },
+        // Check runner
+        ToolDefinition {
+            name: "run_checks".to_string(),
+            description: "🧪 Run the project's own fmt/lint/test commands and get summarized results. Strictly opt-in: only commands the user listed in .st/checks.toml can run (no shell, no extra arguments from you), each with its own timeout. Checks marked confirm=true in that file won't run until you show the exact command to the user and call again with confirm:true. Without a checks.toml this tool politely explains how to opt in.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Project root containing .st/checks.toml",
+                        "default": "."
+                    },
+                    "check": {
+                        "type": "string",
+                        "description": "Name of one allowlisted check to run; omit to run them all"
+                    },
+                    "confirm": {
+                        "type": "boolean",
+                        "description": "Assert the user has seen and approved the command - required for checks marked confirm=true",
+                        "default": false
+                    }
+                }
+            }),
+        },
         // Smart edit tools
+        ToolDefinition {
+            name: "apply_patch".to_string(),
+            description: "🩹 Apply a standard unified diff (multi-file, git-style headers welcome). Hunks are validated first and applied with patch-style fuzz: relocated when line numbers drifted, outer context trimmed when it changed. Use dry_run:true to preview the would-be file contents without writing. Applied diffs land in the same .st_bumpers audit trail as smart_edit. When you already hold a diff, this beats re-expressing it as AST edits.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "patch": {
+                        "type": "string",
+                        "description": "Unified diff text (---/+++/@@). /dev/null headers create or delete files; a/ and b/ prefixes are stripped"
+                    },
+                    "dry_run": {
+                        "type": "boolean",
+                        "description": "Validate and return the would-be results without touching disk",
+                        "default": false
+                    }
+                },
+                "required": ["patch"]
+            }),
+        },
         ToolDefinition {
             name: "smart_edit".to_string(),
             description: "🚀 Apply multiple smart code edits using minimal tokens! Uses AST understanding to insert functions, replace bodies, add imports, etc. without sending full diffs. Revolutionary token-efficient editing that understands code structure!
@@ -1287,6 +1678,11 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "description": "Who created this? 'human', 'ai:claude', or 'tandem:human:claude'",
                         "default": "tandem:human:claude"
                     },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "user", "project"],
+                        "description": "Memory namespace: global (everyone), user (just you), project (this project). Default: project when project_path given, else user"
+                    },
                     "project_path": {
                         "type": "string",
                         "description": "Project to associate with (default: current directory)"
@@ -1322,6 +1718,11 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "minimum": 0.0,
                         "maximum": 1.0
                     },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "user", "project"],
+                        "description": "Memory namespace: global (everyone), user (just you), project (this project). Default: project when project_path given, else user"
+                    },
                     "project_path": {
                         "type": "string",
                         "description": "Project path (default: current directory)"
@@ -1384,6 +1785,15 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "minimum": 0.0,
                         "maximum": 1.0
                     },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "user", "project"],
+                        "description": "Memory namespace: global (everyone), user (just you), project (this project). Default: project when project_path given, else user"
+                    },
+                    "project_path": {
+                        "type": "string",
+                        "description": "Project path for project-scoped memories"
+                    },
                     "max_results": {
                         "type": "integer",
                         "description": "Maximum results (default: 10)",
@@ -1417,6 +1827,11 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
             input_schema: json!({
                 "type": "object",
                 "properties": {
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "user", "project"],
+                        "description": "Memory namespace: global (everyone), user (just you), project (this project). Default: project when project_path given, else user"
+                    },
                     "project_path": {
                         "type": "string",
                         "description": "Project path (default: current directory)"
@@ -1436,6 +1851,11 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
             input_schema: json!({
                 "type": "object",
                 "properties": {
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "user", "project"],
+                        "description": "Memory namespace: global (everyone), user (just you), project (this project). Default: project when project_path given, else user"
+                    },
                     "project_path": {
                         "type": "string",
                         "description": "Project path (default: current directory)"
@@ -1466,6 +1886,11 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "items": { "type": "string" },
                         "description": "Keywords describing current work or problem"
                     },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["global", "user", "project"],
+                        "description": "Memory namespace: global (everyone), user (just you), project (this project). Default: project when project_path given, else user"
+                    },
                     "project_path": {
                         "type": "string",
                         "description": "Project path (default: current directory)"
@@ -1539,7 +1964,7 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
         // Smart Read Tool
         ToolDefinition {
             name: "read".to_string(),
-            description: "📖 Smart file reader with AST-aware compression! Reads files and automatically compresses code by collapsing function bodies to signatures. Use expand_functions to expand specific functions, or expand_context to auto-expand functions matching keywords. Returns collapsed code with [fn:name] references that can be expanded. Perfect for understanding large files without burning tokens!".to_string(),
+            description: "📖 Smart file reader with AST-aware compression! Reads files and automatically compresses code by collapsing function bodies to signatures. Format-aware for documents too: notebooks (cells summarized, outputs stripped, [cell:N] refs), markdown (heading outline, [sec:N] refs), and large JSON/YAML (schema summary, [path:a.b] refs). Use expand_functions to expand specific functions/cells/sections/paths, or expand_context to auto-expand matches by keyword. Perfect for understanding large files without burning tokens!".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -1555,7 +1980,7 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                     "expand_functions": {
                         "type": "array",
                         "items": { "type": "string" },
-                        "description": "List of function names to expand fully (e.g., ['main', 'handle_request'])"
+                        "description": "List of items to expand fully: function names for code, or document refs like 'cell:3', 'sec:2', 'path:server.tls'"
                     },
                     "expand_context": {
                         "type": "array",
@@ -1581,16 +2006,78 @@ pub async fn handle_tools_list(_params: Option<Value>, _ctx: Arc<McpContext>) ->
                         "type": "boolean",
                         "description": "Show line numbers",
                         "default": true
+                    },
+                    "hex_line_numbers": {
+                        "type": "boolean",
+                        "description": "Use hexadecimal line numbers for compactness (1000→3E8). Defaults to the server's hex_numbers setting"
                     }
                 },
                 "required": ["file_path"]
             }),
         },
-    ];
+        ToolDefinition {
+            name: "read_many".to_string(),
+            description: "📚 Batched partial reads - several files in ONE call! Each entry is smart-read (AST compression, expand_functions) or sliced to explicit line ranges. Boilerplate headers shared by every file are shown once, and metadata reports a combined token estimate. Use this instead of calling read repeatedly.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "files": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "file_path": {
+                                    "type": "string",
+                                    "description": "Path to the file to read"
+                                },
+                                "ranges": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "array",
+                                        "items": {"type": "integer"},
+                                        "minItems": 2,
+                                        "maxItems": 2
+                                    },
+                                    "description": "Explicit 1-based inclusive line ranges, e.g. [[1, 40], [120, 160]]. When set, the file is sliced instead of smart-read"
+                                },
+                                "expand_functions": {
+                                    "type": "array",
+                                    "items": {"type": "string"},
+                                    "description": "Function/cell/section names to expand fully (ignored when ranges is set)"
+                                }
+                            },
+                            "required": ["file_path"]
+                        },
+                        "description": "Files to read in this batch"
+                    },
+                    "compress": {
+                        "type": "boolean",
+                        "description": "Enable AST-aware compression for entries without ranges",
+                        "default": true
+                    },
+                    "show_line_numbers": {
+                        "type": "boolean",
+                        "description": "Show line numbers",
+                        "default": true
+                    },
+                    "hex_line_numbers": {
+                        "type": "boolean",
+                        "description": "Use hexadecimal line numbers for compactness. Defaults to the server's hex_numbers setting"
+                    }
+                },
+                "required": ["files"]
+            }),
+        },
+    ]
+}
 
-    Ok(json!({
-        "tools": tools
-    }))
+/// Gate a write-capable tool on the session's granted scopes, using the
+/// path in `args[key]`. A no-op unless `require_write_grants` is on.
+fn ensure_write_scope_arg(args: &Value, key: &str, ctx: &McpContext) -> Result<()> {
+    if let Some(path) = args[key].as_str() {
+        crate::mcp::ensure_write_scope(std::path::Path::new(path), ctx)?;
+    }
+    Ok(())
 }
 
 /// Handle tools/call MCP request - dispatches to appropriate handler
@@ -1603,12 +2090,25 @@ pub async fn handle_tools_call(params: Value, ctx: Arc<McpContext>) -> Result<Va
     // Record this tool call for learning
     ctx.assistant.record_call(tool_name).await;
 
+    // And for compliance - one line per call in .st/audit.jsonl
+    crate::mcp::audit::record_tool_call(tool_name, &args, &ctx.config);
+
+    // Abuse guards: per-session call rate, plus a bounded pool for the
+    // expensive tools. The permit rides to the end of this call
+    ctx.rate_limiter.check_rate().map_err(anyhow::Error::new)?;
+    let _heavy_permit = if crate::mcp::rate_limit::RateLimiter::is_heavy(tool_name) {
+        ctx.rate_limiter.heavy_permit().map_err(anyhow::Error::new)?
+    } else {
+        None
+    };
+
     // Clone ctx for the match since we need it again later
     let ctx_clone = ctx.clone();
 
     let result = match tool_name {
         // Server tools
         "verify_permissions" => verify_permissions(args, ctx_clone.clone()).await,
+        "request_permissions" => request_permissions(args, ctx_clone.clone()).await,
         "server_info" => server_info(args, ctx_clone.clone()).await,
 
         // Directory analysis tools
@@ -1617,6 +2117,8 @@ pub async fn handle_tools_call(params: Value, ctx: Arc<McpContext>) -> Result<Va
         "project_overview" => project_overview(args, ctx_clone.clone()).await,
         "project_context_dump" => project_context_dump(args, ctx_clone.clone()).await,
         "semantic_analysis" => semantic_analysis(args, ctx_clone.clone()).await,
+        "audit_permissions" => audit_permissions(args, ctx_clone.clone()).await,
+        "analyze_dependencies" => analyze_dependencies(args, ctx_clone.clone()).await,
 
         // Search tools
         "find_files" => find_files(args, ctx_clone.clone()).await,
@@ -1632,14 +2134,17 @@ pub async fn handle_tools_call(params: Value, ctx: Arc<McpContext>) -> Result<Va
         "find_tests" => find_tests(args, ctx_clone.clone()).await,
         "find_build_files" => find_build_files(args, ctx_clone.clone()).await,
         "find_empty_directories" => find_empty_directories(args, ctx_clone.clone()).await,
+        "find_secrets" => find_secrets(args, ctx_clone.clone()).await,
 
         // Statistics tools
         "get_statistics" => get_statistics(args, ctx_clone.clone()).await,
         "get_digest" => get_digest(args, ctx_clone.clone()).await,
+        "diff_digests" => diff_digests(args, ctx_clone.clone()).await,
         "directory_size_breakdown" => directory_size_breakdown(args, ctx_clone.clone()).await,
 
         // Git tools
         "get_git_status" => get_git_status(args, ctx_clone.clone()).await,
+        "analyze_churn" => analyze_churn(args, ctx_clone.clone()).await,
 
         // Compare tools
         "compare_directories" => compare_directories(args, ctx_clone.clone()).await,
@@ -1663,13 +2168,38 @@ pub async fn handle_tools_call(params: Value, ctx: Arc<McpContext>) -> Result<Va
 
         // Smart read
         "read" => smart_read(args, ctx_clone.clone()).await,
+        "read_many" => read_many(args, ctx_clone.clone()).await,
+
+        // Check runner
+        "run_checks" => run_checks(args, ctx_clone.clone()).await,
 
-        // Smart edit tools (delegated to smart_edit module)
-        "smart_edit" => crate::mcp::smart_edit::handle_smart_edit(Some(args)).await,
+        // Smart edit tools (delegated to smart_edit module). Write grants are
+        // enforced here at the dispatch boundary since the handlers themselves
+        // don't carry the context
+        "smart_edit" => {
+            ensure_write_scope_arg(&args, "file_path", &ctx_clone)?;
+            crate::mcp::smart_edit::handle_smart_edit(Some(args)).await
+        }
+        "apply_patch" => {
+            for target in crate::mcp::apply_patch::patch_targets(args["patch"].as_str().unwrap_or(""))
+            {
+                crate::mcp::ensure_write_scope(&target, &ctx_clone)?;
+            }
+            crate::mcp::apply_patch::handle_apply_patch(Some(args)).await
+        }
         "get_function_tree" => crate::mcp::smart_edit::handle_get_function_tree(Some(args)).await,
-        "insert_function" => crate::mcp::smart_edit::handle_insert_function(Some(args)).await,
-        "remove_function" => crate::mcp::smart_edit::handle_remove_function(Some(args)).await,
-        "create_file" => crate::mcp::smart_edit::handle_create_file(Some(args)).await,
+        "insert_function" => {
+            ensure_write_scope_arg(&args, "file_path", &ctx_clone)?;
+            crate::mcp::smart_edit::handle_insert_function(Some(args)).await
+        }
+        "remove_function" => {
+            ensure_write_scope_arg(&args, "file_path", &ctx_clone)?;
+            crate::mcp::smart_edit::handle_remove_function(Some(args)).await
+        }
+        "create_file" => {
+            ensure_write_scope_arg(&args, "file_path", &ctx_clone)?;
+            crate::mcp::smart_edit::handle_create_file(Some(args)).await
+        }
 
         // Context gathering tools (delegated to context_tools module)
         "gather_project_context" => {
@@ -1687,7 +2217,12 @@ pub async fn handle_tools_call(params: Value, ctx: Arc<McpContext>) -> Result<Va
         "clean_old_context" => {
             let req: crate::mcp::context_tools::CleanOldContextRequest =
                 serde_json::from_value(args)?;
-            let permission_check = |_perm_req| Ok(true);
+            // Deletes under $HOME - require a write grant covering home
+            let perm_ctx = ctx_clone.clone();
+            let permission_check = move |_perm_req| {
+                let home = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+                Ok(crate::mcp::ensure_write_scope(&home, &perm_ctx).is_ok())
+            };
             crate::mcp::context_tools::clean_old_context(req, permission_check).await
         }
         "anchor_collaborative_memory" => {
@@ -5,18 +5,22 @@
 //! find_duplicates, find_empty_directories, find_projects, search_in_files handlers.
 
 use super::definitions::FindFilesArgs;
+use crate::formatters::ai_table::{escape_field, AiTableFormatter};
 use crate::formatters::projects::ProjectsFormatter;
+use crate::formatters::secrets::SecretsFormatter;
 use crate::formatters::Formatter;
 use crate::mcp::helpers::{
-    scan_with_config, should_use_default_ignores, validate_and_convert_path, ScannerConfigBuilder,
+    page_request, scan_with_config, should_use_default_ignores, validate_and_convert_path,
+    PageRequest, ScannerConfigBuilder,
 };
+use crate::content_hasher::{ContentHasher, HashAlgorithm};
 use crate::mcp::{fmt_num, fmt_num64, is_path_allowed, McpContext};
 use crate::parse_size;
 use anyhow::Result;
 use regex::Regex;
 use serde_json::{json, Value};
-use std::collections::HashMap;
-use std::path::PathBuf;
+
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 
@@ -57,7 +61,17 @@ pub async fn find_files(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     let config = ScannerConfigBuilder::new()
         .max_depth(args.max_depth)
         .show_hidden(true)
-        .find_pattern(args.pattern.as_ref().map(|p| Regex::new(p)).transpose()?)
+        .find_pattern(if args.fuzzy {
+            // Fuzzy mode ranks after the scan - the walker must see everything
+            None
+        } else {
+            args.pattern.as_ref().map(|p| Regex::new(p)).transpose()?
+        })
+        .glob_filter(if args.glob.is_empty() {
+            None
+        } else {
+            Some(crate::scanner::GlobFilter::new(&args.glob)?)
+        })
         .file_type_filter(args.file_type)
         .entry_type_filter(args.entry_type)
         .min_size(args.min_size.as_ref().map(|s| parse_size(s)).transpose()?)
@@ -78,9 +92,35 @@ pub async fn find_files(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
         .build();
 
     // Scan directory
-    let (nodes, _stats) = scan_with_config(&path, config)?;
+    let (nodes, stats) = scan_with_config(&path, config)?;
+
+    // Fuzzy ranking: keep only subsequence matches, best score first
+    let nodes: Vec<crate::scanner::FileNode> = match (args.fuzzy, &args.pattern) {
+        (true, Some(pattern)) => crate::fuzzy::rank_nodes(&nodes, &path, pattern)
+            .into_iter()
+            .map(|(_, idx)| nodes[idx].clone())
+            .collect(),
+        _ => nodes,
+    };
+
+    // Columnar ai-table output: schema once, no repeated JSON keys
+    if args
+        .format
+        .as_deref()
+        .is_some_and(|f| f.eq_ignore_ascii_case("ai-table") || f.eq_ignore_ascii_case("aitable"))
+    {
+        let mut output = Vec::new();
+        AiTableFormatter::new().format(&mut output, &nodes, &stats, &path)?;
+        return Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": String::from_utf8_lossy(&output)
+            }]
+        }));
+    }
 
-    // Format results as JSON list
+    // Format results as JSON list (scanner order is stable, so cursors
+    // from one call stay valid for the next)
     let mut results = Vec::new();
     for node in &nodes {
         // Skip the root directory itself
@@ -106,17 +146,36 @@ pub async fn find_files(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     }
 
     let use_hex = ctx.config.hex_numbers;
+    let mut body = json!({
+        "found": fmt_num(results.len(), use_hex),
+        "files": results
+    });
+    if let Some(page) = PageRequest::from_parts(args.cursor.as_deref(), args.page_size)? {
+        let all = body["files"].take();
+        let (items, meta) = page.page(all.as_array().expect("files is an array"));
+        body["files"] = json!(items);
+        body["pagination"] = meta;
+    }
     Ok(json!({
         "content": [{
             "type": "text",
-            "text": serde_json::to_string_pretty(&json!({
-                "found": fmt_num(results.len(), use_hex),
-                "files": results
-            }))?
+            "text": serde_json::to_string_pretty(&body)?
         }]
     }))
 }
 
+/// Forward opt-in pagination params from a delegating tool's raw args into
+/// the find_files request it builds.
+fn forward_pagination(src: &Value, dst: &mut Value) {
+    for key in ["cursor", "page_size"] {
+        if let Some(value) = src.get(key) {
+            if !value.is_null() {
+                dst[key] = value.clone();
+            }
+        }
+    }
+}
+
 /// Find all source code files by programming language
 pub async fn find_code_files(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     let path = args["path"]
@@ -159,15 +218,13 @@ pub async fn find_code_files(args: Value, ctx: Arc<McpContext>) -> Result<Value>
     };
 
     let pattern = format!(r"\.({})$", extensions.join("|"));
-    find_files(
-        json!({
-            "path": path,
-            "pattern": pattern,
-            "max_depth": 20
-        }),
-        ctx,
-    )
-    .await
+    let mut request = json!({
+        "path": path,
+        "pattern": pattern,
+        "max_depth": 20
+    });
+    forward_pagination(&args, &mut request);
+    find_files(request, ctx).await
 }
 
 /// Locate all configuration files
@@ -178,15 +235,13 @@ pub async fn find_config_files(args: Value, ctx: Arc<McpContext>) -> Result<Valu
 
     let pattern =
         r"\.(json|yaml|yml|toml|ini|cfg|conf|config|env|properties|xml)$|^\..*rc$|^.*config.*$";
-    find_files(
-        json!({
-            "path": path,
-            "pattern": pattern,
-            "max_depth": 10
-        }),
-        ctx,
-    )
-    .await
+    let mut request = json!({
+        "path": path,
+        "pattern": pattern,
+        "max_depth": 10
+    });
+    forward_pagination(&args, &mut request);
+    find_files(request, ctx).await
 }
 
 /// Discover all projects across a filesystem
@@ -283,15 +338,13 @@ pub async fn find_documentation(args: Value, ctx: Arc<McpContext>) -> Result<Val
         .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
 
     let pattern = r"(README|readme|CHANGELOG|changelog|LICENSE|license|CONTRIBUTING|contributing|TODO|todo|INSTALL|install|AUTHORS|authors|NOTICE|notice|HISTORY|history)(\.(md|markdown|rst|txt|adoc|org))?$|\.(md|markdown|rst|txt|adoc|org)$";
-    find_files(
-        json!({
-            "path": path,
-            "pattern": pattern,
-            "max_depth": 10
-        }),
-        ctx,
-    )
-    .await
+    let mut request = json!({
+        "path": path,
+        "pattern": pattern,
+        "max_depth": 10
+    });
+    forward_pagination(&args, &mut request);
+    find_files(request, ctx).await
 }
 
 /// Search for keywords within files
@@ -301,11 +354,24 @@ pub async fn search_in_files(args: Value, ctx: Arc<McpContext>) -> Result<Value>
         .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
     let path = validate_and_convert_path(path_str, &ctx)?;
 
-    let keyword = args["keyword"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("Missing keyword"))?;
+    // Patterns: a single `keyword` and/or a `keywords` array, combined per
+    // `match_all` (AND) or the default any-of (OR).
+    let mut patterns: Vec<String> = Vec::new();
+    if let Some(keyword) = args["keyword"].as_str() {
+        patterns.push(keyword.to_string());
+    }
+    if let Some(extra) = args["keywords"].as_array() {
+        patterns.extend(extra.iter().filter_map(|k| k.as_str().map(String::from)));
+    }
+    if patterns.is_empty() {
+        return Err(anyhow::anyhow!("Missing keyword"));
+    }
+    let keyword = patterns[0].clone();
     let file_type = args["file_type"].as_str();
-    let _case_sensitive = args["case_sensitive"].as_bool().unwrap_or(false);
+    let use_regex = args["regex"].as_bool().unwrap_or(false);
+    let case_sensitive = args["case_sensitive"].as_bool().unwrap_or(false);
+    let whole_word = args["whole_word"].as_bool().unwrap_or(false);
+    let match_all = args["match_all"].as_bool().unwrap_or(false);
     let include_content = args["include_content"].as_bool().unwrap_or(true);
     let context_lines = args["context_lines"].as_u64().map(|n| n as usize);
     let max_matches_per_file = args["max_matches_per_file"].as_u64().unwrap_or(20) as usize;
@@ -313,12 +379,57 @@ pub async fn search_in_files(args: Value, ctx: Arc<McpContext>) -> Result<Value>
     // Build scanner configuration using builder
     let config = ScannerConfigBuilder::for_search(&path)
         .file_type_filter(file_type.map(String::from))
-        .search_keyword(Some(keyword.to_string()))
+        .search_keyword(Some(keyword.clone()))
+        .search_patterns(patterns[1..].to_vec())
+        .search_options(use_regex, !case_sensitive, whole_word, match_all)
         .include_line_content(include_content)
         .build();
 
     let (nodes, _) = scan_with_config(&path, config)?;
 
+    // Columnar ai-table output: one row per match line, schema once
+    if args["format"]
+        .as_str()
+        .is_some_and(|f| f.eq_ignore_ascii_case("ai-table") || f.eq_ignore_ascii_case("aitable"))
+    {
+        let mut output = String::new();
+        output.push_str(&format!(
+            "AI_TABLE_V1 cols=path,line,col,content enc=hex keyword={}\n",
+            escape_field(&patterns.join(","))
+        ));
+        let mut files_with_matches = 0u64;
+        for node in &nodes {
+            if let Some(matches) = &node.search_matches {
+                files_with_matches += 1;
+                let relative = node.path.strip_prefix(&path).unwrap_or(&node.path);
+                if let Some(ref lines) = matches.line_content {
+                    for (line_num, content, column) in lines.iter().take(max_matches_per_file) {
+                        output.push_str(&format!(
+                            "{}\t{:X}\t{:X}\t{}\n",
+                            escape_field(&relative.to_string_lossy()),
+                            line_num,
+                            column,
+                            escape_field(content.trim_end())
+                        ));
+                    }
+                } else {
+                    output.push_str(&format!(
+                        "{}\t-\t-\t({:X} matches)\n",
+                        escape_field(&relative.to_string_lossy()),
+                        matches.total_count
+                    ));
+                }
+            }
+        }
+        output.push_str(&format!("END files={:X}\n", files_with_matches));
+        return Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": output
+            }]
+        }));
+    }
+
     // Format results showing files with matches
     let use_hex = ctx.config.hex_numbers;
     let mut results = Vec::new();
@@ -349,20 +460,46 @@ pub async fn search_in_files(args: Value, ctx: Arc<McpContext>) -> Result<Value>
                 file_result["lines"] = json!(line_results);
             }
 
+            // Per-match capture groups, present for regex patterns with groups
+            if let Some(ref caps) = matches.captures {
+                let capture_results: Vec<Value> = caps
+                    .iter()
+                    .take(max_matches_per_file)
+                    .map(|cap| {
+                        json!({
+                            "line": fmt_num(cap.line, use_hex),
+                            "col": fmt_num(cap.column, use_hex),
+                            "groups": cap.groups
+                        })
+                    })
+                    .collect();
+                file_result["captures"] = json!(capture_results);
+            }
+
             results.push(file_result);
         }
     }
 
+    let mut body = json!({
+        "keyword": keyword,
+        "files_with_matches": fmt_num(results.len(), use_hex),
+        "include_content": include_content,
+        "max_per_file": fmt_num(max_matches_per_file, use_hex),
+        "results": results
+    });
+    // Cursor pagination (opt-in) over files with matches - scanner order
+    // is stable, so cursors stay valid across calls
+    if let Some(page) = page_request(&args)? {
+        let all = body["results"].take();
+        let (items, meta) = page.page(all.as_array().expect("results is an array"));
+        body["results"] = json!(items);
+        body["pagination"] = meta;
+    }
+
     Ok(json!({
         "content": [{
             "type": "text",
-            "text": serde_json::to_string_pretty(&json!({
-                "keyword": keyword,
-                "files_with_matches": fmt_num(results.len(), use_hex),
-                "include_content": include_content,
-                "max_per_file": fmt_num(max_matches_per_file, use_hex),
-                "results": results
-            }))?
+            "text": serde_json::to_string_pretty(&body)?
         }]
     }))
 }
@@ -374,38 +511,175 @@ pub async fn find_large_files(args: Value, ctx: Arc<McpContext>) -> Result<Value
         .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
     let min_size = args["min_size"].as_str().unwrap_or("10M");
 
-    find_files(
-        json!({
-            "path": path,
-            "min_size": min_size,
-            "max_depth": 20
-        }),
-        ctx,
-    )
-    .await
+    let mut request = json!({
+        "path": path,
+        "min_size": min_size,
+        "max_depth": 20
+    });
+    forward_pagination(&args, &mut request);
+    find_files(request, ctx).await
 }
 
-/// Find files modified within the last N days
+/// Find files modified within the last N days, grouped into inferred work sessions
+///
+/// Modification times within `session_gap_minutes` of each other are clustered
+/// into one session, and commits that landed during (or just after) a session
+/// are attached to it - so "what did we work on yesterday afternoon?" has a
+/// precise answer instead of a flat file list.
 pub async fn find_recent_changes(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
-    let path = args["path"]
+    let path_str = args["path"]
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
     let days = args["days"].as_u64().unwrap_or(7);
+    let group_by_session = args["group_by_session"].as_bool().unwrap_or(true);
+    let gap_minutes = args["session_gap_minutes"].as_u64().unwrap_or(45).max(1);
 
-    // Calculate date N days ago
-    use chrono::{Duration, Utc};
-    let date = Utc::now() - Duration::days(days as i64);
-    let date_str = date.format("%Y-%m-%d").to_string();
+    use chrono::{Duration, Local, TimeZone, Utc};
+    let cutoff = Utc::now() - Duration::days(days as i64);
 
-    find_files(
-        json!({
-            "path": path,
-            "newer_than": date_str,
+    if !group_by_session {
+        // Flat list - the original behaviour, delegated to find_files
+        let mut request = json!({
+            "path": path_str,
+            "newer_than": cutoff.format("%Y-%m-%d").to_string(),
             "max_depth": 20
-        }),
-        ctx,
-    )
-    .await
+        });
+        forward_pagination(&args, &mut request);
+        return find_files(request, ctx).await;
+    }
+
+    let path = validate_and_convert_path(path_str, &ctx)?;
+    let cutoff_time = SystemTime::UNIX_EPOCH
+        + std::time::Duration::from_secs(cutoff.timestamp().max(0) as u64);
+
+    let config = ScannerConfigBuilder::new()
+        .max_depth(20)
+        .show_hidden(true)
+        .newer_than(Some(cutoff_time))
+        .use_default_ignores(should_use_default_ignores(&path))
+        .build();
+    let (nodes, _stats) = scan_with_config(&path, config)?;
+
+    // (mtime secs, path) for files only, oldest first so clustering is a
+    // single pass over the gaps
+    let mut changed: Vec<(i64, String)> = nodes
+        .iter()
+        .filter(|n| !n.is_dir && n.path != path)
+        .filter_map(|n| {
+            let secs = n
+                .modified
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .ok()?
+                .as_secs() as i64;
+            Some((secs, n.path.display().to_string()))
+        })
+        .collect();
+    changed.sort();
+
+    let gap_secs = gap_minutes as i64 * 60;
+    let mut sessions: Vec<Vec<(i64, String)>> = Vec::new();
+    for entry in changed {
+        match sessions.last_mut() {
+            Some(session)
+                if entry.0 - session.last().map(|(t, _)| *t).unwrap_or(0) <= gap_secs =>
+            {
+                session.push(entry)
+            }
+            _ => sessions.push(vec![entry]),
+        }
+    }
+
+    // Pull recent commits once; each session claims the ones in its window
+    let (branch, commits) = recent_commits(&path, cutoff.timestamp());
+
+    let use_hex = ctx.config.hex_numbers;
+    let fmt_ts = |secs: i64| {
+        Local
+            .timestamp_opt(secs, 0)
+            .single()
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default()
+    };
+
+    let mut session_list = Vec::new();
+    for session in sessions.iter().rev() {
+        // Newest session first
+        let start = session.first().map(|(t, _)| *t).unwrap_or(0);
+        let end = session.last().map(|(t, _)| *t).unwrap_or(0);
+
+        // A commit usually lands at the end of a burst of edits, so the
+        // window extends one gap past the last modification
+        let session_commits: Vec<Value> = commits
+            .iter()
+            .filter(|(t, _, _)| *t >= start && *t <= end + gap_secs)
+            .map(|(t, id, summary)| {
+                json!({
+                    "commit": id,
+                    "time": fmt_ts(*t),
+                    "summary": summary
+                })
+            })
+            .collect();
+
+        session_list.push(json!({
+            "start": fmt_ts(start),
+            "end": fmt_ts(end),
+            "files_changed": fmt_num(session.len(), use_hex),
+            "files": session.iter().rev().map(|(_, p)| p.clone()).collect::<Vec<_>>(),
+            "commits": session_commits
+        }));
+    }
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&json!({
+                "directory": path.display().to_string(),
+                "days": days,
+                "session_gap_minutes": gap_minutes,
+                "branch": branch,
+                "sessions": session_list
+            }))?
+        }]
+    }))
+}
+
+/// Recent commits on HEAD, newest first: (commit epoch secs, short id, summary)
+///
+/// Best effort - a directory that isn't a git repo just yields no commits.
+fn recent_commits(path: &Path, cutoff_secs: i64) -> (Option<String>, Vec<(i64, String, String)>) {
+    let Ok(repo) = gix::discover(path) else {
+        return (None, Vec::new());
+    };
+
+    let branch = repo.head_ref().ok().flatten().map(|r| {
+        let name = r.name().as_bstr().to_string();
+        name.strip_prefix("refs/heads/").unwrap_or(&name).to_string()
+    });
+
+    let mut commits = Vec::new();
+    let Ok(head) = repo.head_commit() else {
+        return (branch, commits);
+    };
+    let Ok(walk) = repo.rev_walk(Some(head.id)).all() else {
+        return (branch, commits);
+    };
+    for info in walk.flatten().take(500) {
+        let Ok(commit) = info.object() else { continue };
+        let Ok(time) = commit.time() else { continue };
+        if time.seconds < cutoff_secs {
+            break;
+        }
+        let summary = commit
+            .message_raw_sloppy()
+            .to_string()
+            .lines()
+            .next()
+            .unwrap_or("")
+            .to_string();
+        commits.push((time.seconds, info.id.to_string()[..8].to_string(), summary));
+    }
+    (branch, commits)
 }
 
 /// Find files modified within a specific time range
@@ -450,35 +724,52 @@ pub async fn find_duplicates(args: Value, ctx: Arc<McpContext>) -> Result<Value>
 
     let (nodes, _) = scan_with_config(&path, config)?;
 
-    // Group files by size and name
-    let mut size_groups: HashMap<u64, Vec<&crate::scanner::FileNode>> = HashMap::new();
-
-    for node in &nodes {
-        if !node.is_dir {
-            size_groups.entry(node.size).or_default().push(node);
-        }
-    }
+    // Verify duplicates by content hash - size grouping alone produces
+    // false positives. Algorithm is selectable (blake3 default).
+    let algorithm: HashAlgorithm = args["algorithm"]
+        .as_str()
+        .map(|s| s.parse())
+        .transpose()?
+        .unwrap_or_default();
+    let mut groups = ContentHasher::new(algorithm).find_duplicate_groups(&nodes);
+    // Hash tiebreak on equal waste: parallel hashing can reorder ties, and
+    // pagination cursors need a deterministic order to slice against
+    groups.sort_by(|a, b| {
+        b.wasted_bytes()
+            .cmp(&a.wasted_bytes())
+            .then_with(|| a.hash.cmp(&b.hash))
+    });
 
-    // Find potential duplicates with hex formatting
     let use_hex = ctx.config.hex_numbers;
-    let mut duplicates = Vec::new();
-    for (size, files) in size_groups.iter() {
-        if files.len() > 1 && *size > 0 {
-            duplicates.push(json!({
-                "sz": fmt_num64(*size, use_hex),
-                "n": fmt_num(files.len(), use_hex),
-                "files": files.iter().map(|f| f.path.display().to_string()).collect::<Vec<_>>()
-            }));
-        }
+    let duplicates: Vec<Value> = groups
+        .iter()
+        .map(|group| {
+            json!({
+                "hash": group.hash,
+                "sz": fmt_num64(group.size, use_hex),
+                "n": fmt_num(group.paths.len(), use_hex),
+                "wasted": fmt_num64(group.wasted_bytes(), use_hex),
+                "files": group.paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut body = json!({
+        "algorithm": algorithm.as_str(),
+        "groups": fmt_num(duplicates.len(), use_hex),
+        "dups": duplicates
+    });
+    if let Some(page) = page_request(&args)? {
+        let all = body["dups"].take();
+        let (items, meta) = page.page(all.as_array().expect("dups is an array"));
+        body["dups"] = json!(items);
+        body["pagination"] = meta;
     }
 
     Ok(json!({
         "content": [{
             "type": "text",
-            "text": serde_json::to_string_pretty(&json!({
-                "groups": fmt_num(duplicates.len(), use_hex),
-                "dups": duplicates
-            }))?
+            "text": serde_json::to_string_pretty(&body)?
         }]
     }))
 }
@@ -490,15 +781,13 @@ pub async fn find_tests(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
         .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
 
     let pattern = r"(test_|_test\.|\.test\.|tests?\.|spec\.|\.spec\.|_spec\.)|(/tests?/|/specs?/)";
-    find_files(
-        json!({
-            "path": path,
-            "pattern": pattern,
-            "max_depth": 20
-        }),
-        ctx,
-    )
-    .await
+    let mut request = json!({
+        "path": path,
+        "pattern": pattern,
+        "max_depth": 20
+    });
+    forward_pagination(&args, &mut request);
+    find_files(request, ctx).await
 }
 
 /// Find all build configuration files
@@ -508,15 +797,13 @@ pub async fn find_build_files(args: Value, ctx: Arc<McpContext>) -> Result<Value
         .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
 
     let pattern = r"^(Makefile|makefile|CMakeLists\.txt|Cargo\.toml|package\.json|pom\.xml|build\.gradle|build\.sbt|setup\.py|requirements\.txt|Gemfile|go\.mod|composer\.json|Dockerfile|docker-compose\.yml)$";
-    find_files(
-        json!({
-            "path": path,
-            "pattern": pattern,
-            "max_depth": 10
-        }),
-        ctx,
-    )
-    .await
+    let mut request = json!({
+        "path": path,
+        "pattern": pattern,
+        "max_depth": 10
+    });
+    forward_pagination(&args, &mut request);
+    find_files(request, ctx).await
 }
 
 /// Find all empty directories
@@ -556,13 +843,53 @@ pub async fn find_empty_directories(args: Value, ctx: Arc<McpContext>) -> Result
     }
 
     let use_hex = ctx.config.hex_numbers;
+    let mut body = json!({
+        "count": fmt_num(empty_dirs.len(), use_hex),
+        "dirs": empty_dirs
+    });
+    if let Some(page) = page_request(&args)? {
+        let all = body["dirs"].take();
+        let (items, meta) = page.page(all.as_array().expect("dirs is an array"));
+        body["dirs"] = json!(items);
+        body["pagination"] = meta;
+    }
     Ok(json!({
         "content": [{
             "type": "text",
-            "text": serde_json::to_string_pretty(&json!({
-                "count": fmt_num(empty_dirs.len(), use_hex),
-                "dirs": empty_dirs
-            }))?
+            "text": serde_json::to_string_pretty(&body)?
+        }]
+    }))
+}
+
+/// Scan file contents for credentials - the `--mode secrets` sweep over MCP
+///
+/// Matched values are redacted by default (prefix + length); pass
+/// `redact: false` only when the caller genuinely needs the raw values.
+pub async fn find_secrets(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let path_str = args["path"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
+    let path = validate_and_convert_path(path_str, &ctx)?;
+    let max_depth = args["max_depth"].as_u64().unwrap_or(20) as usize;
+    let redact = args["redact"].as_bool().unwrap_or(true);
+
+    // Hidden files included - .env and .aws/credentials are the whole point
+    let config = ScannerConfigBuilder::new()
+        .max_depth(max_depth)
+        .show_hidden(true)
+        .use_default_ignores(should_use_default_ignores(&path))
+        .build();
+
+    let (nodes, stats) = scan_with_config(&path, config)?;
+
+    let formatter = SecretsFormatter::new().with_redaction(redact);
+    let mut output = Vec::new();
+    formatter.format(&mut output, &nodes, &stats, &path)?;
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": String::from_utf8_lossy(&output).to_string()
         }]
     }))
 }
@@ -2,8 +2,8 @@
 //!
 //! Contains server_info and verify_permissions handlers.
 
-use super::definitions::VerifyPermissionsArgs;
-use crate::mcp::permissions::get_available_tools;
+use super::definitions::{RequestPermissionsArgs, VerifyPermissionsArgs};
+use crate::mcp::permissions::{get_available_tools, Access, ApprovedScopes};
 use crate::mcp::{is_path_allowed, McpContext};
 use anyhow::Result;
 use serde_json::{json, Value};
@@ -13,6 +13,8 @@ use std::sync::Arc;
 /// Get server information and capabilities
 pub async fn server_info(_args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     let cache_stats = ctx.cache.stats().await;
+    let flags = crate::feature_flags::features();
+    let active_subscriptions = ctx.subscriptions.active_uris();
 
     // Get current date/time for AI assistants
     use chrono::{Local, Utc};
@@ -78,6 +80,33 @@ pub async fn server_info(_args: Value, ctx: Arc<McpContext>) -> Result<Value> {
                 "regex_support": true,
             },
         },
+        "health": {
+            "uptime_seconds": ctx.started_at.elapsed().as_secs(),
+            "requests_handled": ctx.requests_handled.load(std::sync::atomic::Ordering::Relaxed),
+            "writable": {
+                "edit_tools_enabled": flags.mcp_tools.enable_edit,
+                "privacy_mode": flags.privacy_mode,
+                "compliance_mode": flags.compliance_mode.as_ref().map(|m| format!("{:?}", m)),
+            },
+            "watcher": {
+                "file_watching_enabled": flags.enable_file_watching,
+                "active_subscriptions": active_subscriptions.len(),
+                "subscribed_uris": active_subscriptions,
+            },
+            "index_freshness": {
+                "cached_analyses": cache_stats.entries,
+                "cache_ttl_seconds": ctx.config.cache_ttl,
+                "note": "Cached results are at most cache_ttl_seconds old; anything else is scanned live",
+            },
+            "subsystems": {
+                "scanner": { "version": env!("CARGO_PKG_VERSION"), "enabled": true },
+                "formatters": { "version": env!("CARGO_PKG_VERSION"), "enabled": flags.enable_formatters },
+                "quantum": { "version": env!("CARGO_PKG_VERSION"), "enabled": flags.enable_quantum_modes },
+                "memory_manager": { "version": env!("CARGO_PKG_VERSION"), "enabled": flags.enable_memory_manager },
+                "smart_search": { "version": env!("CARGO_PKG_VERSION"), "enabled": flags.enable_smart_search },
+                "consciousness": { "version": env!("CARGO_PKG_VERSION"), "enabled": flags.enable_consciousness },
+            },
+        },
         "configuration": {
             "cache": {
                 "enabled": ctx.config.cache_enabled,
@@ -88,9 +117,12 @@ pub async fn server_info(_args: Value, ctx: Arc<McpContext>) -> Result<Value> {
                 "hit_rate": format!("{:.1}%", cache_stats.hit_rate * 100.0),
             },
             "security": {
-                "allowed_paths": ctx.config.allowed_paths.len(),
-                "blocked_paths": ctx.config.blocked_paths.len(),
-                "default_blocks": ["/etc", "/sys", "/proc"],
+                "allowed_paths": if ctx.config.allowed_paths.is_empty() {
+                    json!("unrestricted")
+                } else {
+                    json!(ctx.config.allowed_paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>())
+                },
+                "blocked_paths": ctx.config.blocked_paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
             },
             "mcp_optimization": {
                 "compression_enabled": !std::env::var("MCP_NO_COMPRESS")
@@ -161,8 +193,8 @@ pub async fn server_info(_args: Value, ctx: Arc<McpContext>) -> Result<Value> {
             ],
         },
         "statistics": {
-            "uptime_seconds": 0,
-            "requests_handled": 0,
+            "uptime_seconds": ctx.started_at.elapsed().as_secs(),
+            "requests_handled": ctx.requests_handled.load(std::sync::atomic::Ordering::Relaxed),
             "cache_hits": cache_stats.hits,
             "cache_misses": cache_stats.misses,
         },
@@ -315,3 +347,149 @@ pub async fn verify_permissions(args: Value, ctx: Arc<McpContext>) -> Result<Val
         }
     }))
 }
+
+/// Request read/write scopes for path prefixes this session wants to use.
+///
+/// Each scope is approved either ahead of time (`~/.st/mcp-grants.toml`) or
+/// live on the user's terminal; anything else is denied. Granted scopes are
+/// what write-capable tools enforce when `require_write_grants` is on.
+pub async fn request_permissions(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let args: RequestPermissionsArgs = serde_json::from_value(args)?;
+    if args.scopes.is_empty() {
+        anyhow::bail!("No scopes requested - pass scopes: [{{path, access}}]");
+    }
+
+    // Pre-approvals load once; a broken grants file should be loud, not silent
+    let approved = ApprovedScopes::load()?;
+
+    let mut lines = Vec::new();
+    let mut outcomes = Vec::new();
+
+    for scope in &args.scopes {
+        let path = PathBuf::from(&scope.path);
+
+        // Blocked paths stay blocked no matter what anyone approves
+        if !is_path_allowed(&path, &ctx.config) {
+            lines.push(format!(
+                "🚫 {} ({:?}) - path is blocked by server configuration",
+                path.display(),
+                scope.access
+            ));
+            outcomes.push(json!({
+                "path": scope.path, "access": scope.access, "status": "blocked"
+            }));
+            continue;
+        }
+
+        // Already covered?
+        let already = {
+            let grants = ctx
+                .grants
+                .lock()
+                .map_err(|_| anyhow::anyhow!("Grant store poisoned"))?;
+            grants.allows(&path, scope.access)
+        };
+        if already {
+            lines.push(format!(
+                "✅ {} ({:?}) - already granted",
+                path.display(),
+                scope.access
+            ));
+            outcomes.push(json!({
+                "path": scope.path, "access": scope.access, "status": "granted",
+                "via": "existing"
+            }));
+            continue;
+        }
+
+        // User approval: config file first, live terminal prompt second
+        let via = if approved.approves(&path, scope.access) {
+            Some("config")
+        } else if prompt_user_for_scope(&path, scope.access) == Some(true) {
+            Some("prompt")
+        } else {
+            None
+        };
+
+        match via {
+            Some(via) => {
+                let mut grants = ctx
+                    .grants
+                    .lock()
+                    .map_err(|_| anyhow::anyhow!("Grant store poisoned"))?;
+                grants.grant(path.clone(), scope.access);
+                lines.push(format!(
+                    "✅ {} ({:?}) - granted via {}",
+                    path.display(),
+                    scope.access,
+                    via
+                ));
+                outcomes.push(json!({
+                    "path": scope.path, "access": scope.access, "status": "granted",
+                    "via": via
+                }));
+            }
+            None => {
+                lines.push(format!(
+                    "❌ {} ({:?}) - denied. Add it to {} or re-run where a terminal \
+                     is available for the approval prompt",
+                    path.display(),
+                    scope.access,
+                    ApprovedScopes::config_path().display()
+                ));
+                outcomes.push(json!({
+                    "path": scope.path, "access": scope.access, "status": "denied"
+                }));
+            }
+        }
+    }
+
+    let held: Vec<Value> = {
+        let grants = ctx
+            .grants
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Grant store poisoned"))?;
+        grants
+            .list()
+            .iter()
+            .map(|g| json!({ "prefix": g.prefix, "access": g.access }))
+            .collect()
+    };
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": format!("🔐 Scope Requests:\n{}", lines.join("\n"))
+        }],
+        "metadata": {
+            "scopes": outcomes,
+            "session_grants": held,
+            "enforced": ctx.config.require_write_grants,
+        }
+    }))
+}
+
+/// Ask the user on their terminal whether to approve a scope.
+/// Returns `None` when no terminal is available (headless server).
+fn prompt_user_for_scope(path: &std::path::Path, access: Access) -> Option<bool> {
+    use std::io::{BufRead, BufReader, Write};
+
+    // stdin/stdout belong to the MCP protocol - go straight to the tty
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    write!(
+        tty,
+        "\nSmart Tree MCP: this session requests {:?} access to {}\nAllow for this session? [y/N] ",
+        access,
+        path.display()
+    )
+    .ok()?;
+    tty.flush().ok()?;
+
+    let mut answer = String::new();
+    BufReader::new(&tty).read_line(&mut answer).ok()?;
+    Some(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
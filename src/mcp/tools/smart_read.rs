@@ -1,8 +1,8 @@
 //! Smart file reading with AST compression
 //!
-//! Contains smart_read handler and AST helper functions.
+//! Contains the smart_read and read_many handlers plus AST helper functions.
 
-use super::definitions::SmartReadArgs;
+use super::definitions::{ReadManyArgs, ReadManyEntry, SmartReadArgs};
 use crate::mcp::{fmt_line, is_path_allowed, McpContext};
 use anyhow::Result;
 use regex::Regex;
@@ -382,6 +382,17 @@ pub async fn smart_read(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     let compressible_lang = language.filter(|l| supports_collapsing(l));
     let should_compress = args.compress && !args.expand_all && compressible_lang.is_some();
 
+    // Format-aware readers for non-code documents: notebooks, markdown, and
+    // large JSON/YAML get their own collapsing under the same expand API
+    let document_result = if !should_compress && args.compress && !args.expand_all {
+        match detect_document_kind(&path) {
+            Some(kind) => collapse_document(kind, &content, &args, &path)?,
+            None => None,
+        }
+    } else {
+        None
+    };
+
     let (output, metadata) = if should_compress {
         // Safe: compressible_lang.is_some() guarantees we have a language
         let lang = compressible_lang.expect("Checked above");
@@ -509,6 +520,8 @@ pub async fn smart_read(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
         });
 
         (output, metadata)
+    } else if let Some(result) = document_result {
+        result
     } else {
         // No compression - output raw content
         let lines: Vec<&str> = content.lines().collect();
@@ -560,3 +573,579 @@ pub async fn smart_read(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
         "metadata": metadata
     }))
 }
+
+/// Batched partial reads: several files in one response, each either
+/// smart-read (AST compression + expand_functions) or sliced to explicit
+/// line ranges. A leading block shared by every file (license banners,
+/// repeated import headers) is hoisted out and shown once, and the
+/// metadata carries a combined token estimate - one call instead of a
+/// round-trip per file.
+pub async fn read_many(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let args: ReadManyArgs = serde_json::from_value(args)?;
+    if args.files.is_empty() {
+        return Err(anyhow::anyhow!("read_many needs at least one file"));
+    }
+
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut per_file = Vec::new();
+
+    for entry in &args.files {
+        let result = if entry.ranges.is_empty() {
+            let sub = json!({
+                "file_path": entry.file_path,
+                "compress": args.compress,
+                "expand_functions": entry.expand_functions,
+                "show_line_numbers": args.show_line_numbers,
+                "hex_line_numbers": args.hex_line_numbers,
+            });
+            smart_read(sub, ctx.clone()).await.map(|result| {
+                let text = result["content"][0]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                (text, result["metadata"].clone())
+            })
+        } else {
+            read_ranges(entry, &args, &ctx)
+        };
+
+        match result {
+            Ok((text, metadata)) => {
+                sections.push((entry.file_path.clone(), text));
+                per_file.push(metadata);
+            }
+            // One bad path shouldn't sink the whole batch - report it in
+            // its section and keep going
+            Err(e) => {
+                sections.push((entry.file_path.clone(), format!("⚠️ {}\n", e)));
+                per_file.push(json!({
+                    "file_path": entry.file_path,
+                    "error": e.to_string()
+                }));
+            }
+        }
+    }
+
+    let shared = shared_leading_block(&sections);
+    let mut output = String::new();
+    if let Some(ref block) = shared {
+        output.push_str(&format!(
+            "=== shared header ({} lines, shown once) ===\n{}",
+            block.lines().count(),
+            block
+        ));
+    }
+    for (path, text) in &sections {
+        let body = match shared {
+            Some(ref block) => text.strip_prefix(block.as_str()).unwrap_or(text),
+            None => text.as_str(),
+        };
+        output.push_str(&format!("=== {} ===\n{}\n", path, body.trim_end()));
+    }
+
+    let estimated_tokens = crate::token_budget::estimate_tokens(&output);
+    let metadata = json!({
+        "files": sections.len(),
+        "estimated_tokens": estimated_tokens,
+        "shared_header_lines": shared.as_ref().map(|b| b.lines().count()).unwrap_or(0),
+        "per_file": per_file,
+    });
+
+    // Same auto-compression as analyze_directory: big batches get squeezed
+    let final_output = if crate::compression_manager::should_compress_response(&output) {
+        crate::compression_manager::compress_string(&output)?
+    } else {
+        output
+    };
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": final_output
+        }],
+        "metadata": metadata
+    }))
+}
+
+/// Slice a file to explicit 1-based inclusive line ranges, with the same
+/// line-number gutter as smart_read and a `⋮` marker between ranges.
+fn read_ranges(
+    entry: &ReadManyEntry,
+    args: &ReadManyArgs,
+    ctx: &Arc<McpContext>,
+) -> Result<(String, Value)> {
+    let path = PathBuf::from(&entry.file_path);
+    if !is_path_allowed(&path, &ctx.config) {
+        return Err(anyhow::anyhow!("Path not allowed: {}", path.display()));
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let use_hex = args.hex_line_numbers.unwrap_or(ctx.config.hex_numbers);
+
+    let mut output = String::new();
+    let mut lines_shown = 0usize;
+    for (i, &(start, end)) in entry.ranges.iter().enumerate() {
+        if start == 0 || start > end {
+            return Err(anyhow::anyhow!(
+                "Invalid range {}-{} (ranges are 1-based and inclusive)",
+                start,
+                end
+            ));
+        }
+        if i > 0 {
+            output.push_str("⋮\n");
+        }
+        let start_idx = (start - 1).min(lines.len());
+        let end_idx = end.min(lines.len());
+        for (j, line) in lines[start_idx..end_idx].iter().enumerate() {
+            if args.show_line_numbers {
+                output.push_str(&format!(
+                    "{}│ {}\n",
+                    format_line_number(start_idx + j + 1, use_hex),
+                    line
+                ));
+            } else {
+                output.push_str(line);
+                output.push('\n');
+            }
+            lines_shown += 1;
+        }
+    }
+
+    Ok((
+        output,
+        json!({
+            "file_path": entry.file_path,
+            "total_lines": lines.len(),
+            "lines_shown": lines_shown,
+            "ranges": entry.ranges.len(),
+        }),
+    ))
+}
+
+/// Longest run of leading lines common to every section, if it's worth
+/// hoisting (at least two files and three lines). License banners and
+/// repeated boilerplate headers land here.
+fn shared_leading_block(sections: &[(String, String)]) -> Option<String> {
+    if sections.len() < 2 {
+        return None;
+    }
+    let first: Vec<&str> = sections[0].1.lines().collect();
+    let mut common = first.len();
+    for (_, text) in &sections[1..] {
+        let count = text
+            .lines()
+            .zip(first.iter())
+            .take_while(|(line, expected)| line == *expected)
+            .count();
+        common = common.min(count);
+        if common < 3 {
+            return None;
+        }
+    }
+    let block: String = first[..common].iter().map(|l| format!("{}\n", l)).collect();
+    Some(block)
+}
+
+/// Non-code document formats with their own collapsing strategy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentKind {
+    /// Jupyter notebook - cells summarized, outputs always stripped
+    Notebook,
+    /// Markdown - heading outline with expandable sections
+    Markdown,
+    /// JSON - schema-like summary with expandable paths
+    Json,
+    /// YAML - schema-like summary with expandable paths
+    Yaml,
+}
+
+/// Detect document formats that get format-aware (non-AST) collapsing
+pub fn detect_document_kind(path: &Path) -> Option<DocumentKind> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| match ext.to_lowercase().as_str() {
+            "ipynb" => Some(DocumentKind::Notebook),
+            "md" | "markdown" => Some(DocumentKind::Markdown),
+            "json" => Some(DocumentKind::Json),
+            "yaml" | "yml" => Some(DocumentKind::Yaml),
+            _ => None,
+        })
+}
+
+/// Small markdown files read fine raw; only outline beyond this
+const MARKDOWN_COLLAPSE_LINES: usize = 80;
+/// JSON/YAML below this stays raw - the summary only pays off for big files
+const STRUCTURED_COLLAPSE_LINES: usize = 120;
+
+/// Dispatch to the format-aware reader for `kind`
+///
+/// Returns `None` when the document is small enough that raw output is
+/// better, which falls through to the normal uncompressed path.
+fn collapse_document(
+    kind: DocumentKind,
+    content: &str,
+    args: &SmartReadArgs,
+    path: &Path,
+) -> Result<Option<(String, Value)>> {
+    let line_count = content.lines().count();
+    match kind {
+        DocumentKind::Notebook => collapse_notebook(content, args, path).map(Some),
+        DocumentKind::Markdown if line_count > MARKDOWN_COLLAPSE_LINES => {
+            Ok(Some(collapse_markdown(content, args, path)))
+        }
+        DocumentKind::Json | DocumentKind::Yaml if line_count > STRUCTURED_COLLAPSE_LINES => {
+            collapse_structured(kind, content, args, path)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Does the expand API name this item? Accepts the bare name or the
+/// bracketed ref form the output shows (e.g. "cell:3" for "[cell:3]")
+fn wants_expand(expand_names: &[String], name: &str) -> bool {
+    expand_names.iter().any(|e| {
+        e.eq_ignore_ascii_case(name)
+            || e.trim_matches(&['[', ']'][..]).eq_ignore_ascii_case(name)
+    })
+}
+
+/// Does any expand_context keyword appear in this text?
+fn context_matches(context_keywords: &[String], text: &str) -> bool {
+    if context_keywords.is_empty() {
+        return false;
+    }
+    let lower = text.to_lowercase();
+    context_keywords
+        .iter()
+        .any(|kw| lower.contains(&kw.to_lowercase()))
+}
+
+/// Jupyter notebook reader: one line per collapsed cell, outputs stripped
+fn collapse_notebook(
+    content: &str,
+    args: &SmartReadArgs,
+    path: &Path,
+) -> Result<(String, Value)> {
+    let notebook: Value = serde_json::from_str(content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse notebook: {}", e))?;
+    let cells = notebook["cells"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Notebook has no cells array"))?;
+
+    let mut output = String::new();
+    let mut refs: Vec<Value> = Vec::new();
+    let mut collapsed_count = 0;
+    let mut expanded_count = 0;
+    let mut outputs_stripped = 0;
+
+    for (idx, cell) in cells.iter().enumerate() {
+        let cell_ref = format!("cell:{}", idx + 1);
+        let cell_type = cell["cell_type"].as_str().unwrap_or("unknown");
+        let source = match &cell["source"] {
+            Value::Array(lines) => lines
+                .iter()
+                .filter_map(|l| l.as_str())
+                .collect::<String>(),
+            Value::String(s) => s.clone(),
+            _ => String::new(),
+        };
+        let output_count = cell["outputs"].as_array().map(|o| o.len()).unwrap_or(0);
+        outputs_stripped += output_count;
+
+        let expand = wants_expand(&args.expand_functions, &cell_ref)
+            || context_matches(&args.expand_context, &source);
+
+        if expand {
+            output.push_str(&format!("── [{}] {} ──\n", cell_ref, cell_type));
+            output.push_str(source.trim_end());
+            output.push('\n');
+            if output_count > 0 {
+                output.push_str(&format!("   ({} outputs stripped)\n", output_count));
+            }
+            expanded_count += 1;
+        } else {
+            let first_line = source
+                .lines()
+                .find(|l| !l.trim().is_empty())
+                .unwrap_or("")
+                .trim();
+            let line_count = source.lines().count();
+            output.push_str(&format!(
+                "[{}] {} | {} | {} lines{}\n",
+                cell_ref,
+                cell_type,
+                truncate(first_line, 60),
+                line_count,
+                if output_count > 0 {
+                    format!(", {} outputs stripped", output_count)
+                } else {
+                    String::new()
+                }
+            ));
+            refs.push(json!({
+                "name": cell_ref,
+                "ref": format!("[{}]", cell_ref),
+                "type": cell_type,
+                "lines": line_count
+            }));
+            collapsed_count += 1;
+        }
+    }
+
+    let metadata = json!({
+        "file_path": path.to_string_lossy(),
+        "document": "notebook",
+        "compression_enabled": true,
+        "cells_total": cells.len(),
+        "cells_collapsed": collapsed_count,
+        "cells_expanded": expanded_count,
+        "outputs_stripped": outputs_stripped,
+        "collapsed_refs": refs,
+        "expand_hint": "Use expand_functions: ['cell:3'] or expand_context: ['keyword'] to expand specific cells"
+    });
+
+    Ok((output, metadata))
+}
+
+/// Markdown reader: heading outline, section bodies collapse behind refs
+fn collapse_markdown(content: &str, args: &SmartReadArgs, path: &Path) -> (String, Value) {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut output = String::new();
+    let mut refs: Vec<Value> = Vec::new();
+    let mut collapsed_count = 0;
+    let mut expanded_count = 0;
+
+    // Section boundaries: every heading starts a new section; the preamble
+    // before the first heading is always shown
+    let mut section_starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with('#'))
+        .map(|(i, _)| i)
+        .collect();
+    let first_heading = section_starts.first().copied().unwrap_or(lines.len());
+    for line in &lines[..first_heading] {
+        output.push_str(line);
+        output.push('\n');
+    }
+    section_starts.push(lines.len());
+
+    for (sec_idx, window) in section_starts.windows(2).enumerate() {
+        let (start, end) = (window[0], window[1]);
+        if start >= lines.len() {
+            break;
+        }
+        let heading = lines[start];
+        let title = heading.trim_start_matches('#').trim();
+        let body = lines[start + 1..end].join("\n");
+        let sec_ref = format!("sec:{}", sec_idx + 1);
+
+        output.push_str(heading);
+        output.push('\n');
+
+        let body_lines = end - start - 1;
+        if body_lines == 0 {
+            continue;
+        }
+
+        let expand = wants_expand(&args.expand_functions, &sec_ref)
+            || wants_expand(&args.expand_functions, title)
+            || context_matches(&args.expand_context, &body)
+            || context_matches(&args.expand_context, title);
+
+        if expand {
+            output.push_str(&body);
+            output.push('\n');
+            expanded_count += 1;
+        } else {
+            output.push_str(&format!("  ... [{}] {} lines collapsed\n", sec_ref, body_lines));
+            refs.push(json!({
+                "name": sec_ref,
+                "ref": format!("[{}]", sec_ref),
+                "title": title,
+                "lines": body_lines
+            }));
+            collapsed_count += 1;
+        }
+    }
+
+    let metadata = json!({
+        "file_path": path.to_string_lossy(),
+        "document": "markdown",
+        "compression_enabled": true,
+        "total_lines": lines.len(),
+        "sections_collapsed": collapsed_count,
+        "sections_expanded": expanded_count,
+        "collapsed_refs": refs,
+        "expand_hint": "Use expand_functions: ['sec:2'] (or the heading title) or expand_context: ['keyword'] to expand sections"
+    });
+
+    (output, metadata)
+}
+
+/// JSON/YAML reader: schema-like summary, subtrees expand by dotted path
+fn collapse_structured(
+    kind: DocumentKind,
+    content: &str,
+    args: &SmartReadArgs,
+    path: &Path,
+) -> Result<Option<(String, Value)>> {
+    let parsed: Value = match kind {
+        DocumentKind::Json => match serde_json::from_str(content) {
+            Ok(v) => v,
+            Err(_) => return Ok(None), // Invalid JSON: fall back to raw
+        },
+        _ => match serde_yaml::from_str(content) {
+            Ok(v) => v,
+            Err(_) => return Ok(None), // Non-mappable YAML: fall back to raw
+        },
+    };
+
+    let mut output = String::new();
+    let mut refs: Vec<Value> = Vec::new();
+    summarize_value(&parsed, "", 0, args, &mut output, &mut refs);
+
+    let metadata = json!({
+        "file_path": path.to_string_lossy(),
+        "document": if kind == DocumentKind::Json { "json" } else { "yaml" },
+        "compression_enabled": true,
+        "total_lines": content.lines().count(),
+        "collapsed_refs": refs,
+        "expand_hint": "Use expand_functions: ['path:server.tls'] (dotted path) or expand_context: ['keyword'] to expand subtrees"
+    });
+
+    Ok(Some((output, metadata)))
+}
+
+/// How deep the structured summary descends before collapsing subtrees
+const STRUCTURED_SUMMARY_DEPTH: usize = 2;
+
+fn summarize_value(
+    value: &Value,
+    dotted_path: &str,
+    depth: usize,
+    args: &SmartReadArgs,
+    output: &mut String,
+    refs: &mut Vec<Value>,
+) {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = if dotted_path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", dotted_path, key)
+                };
+                match child {
+                    Value::Object(inner) => {
+                        let path_ref = format!("path:{}", child_path);
+                        let expand = wants_expand(&args.expand_functions, &path_ref)
+                            || wants_expand(&args.expand_functions, &child_path)
+                            || context_matches(&args.expand_context, key);
+                        if expand {
+                            output.push_str(&format!("{}{}:\n", indent, key));
+                            push_pretty(child, depth + 1, output);
+                        } else if depth < STRUCTURED_SUMMARY_DEPTH {
+                            output.push_str(&format!("{}{}:\n", indent, key));
+                            summarize_value(child, &child_path, depth + 1, args, output, refs);
+                        } else {
+                            output.push_str(&format!(
+                                "{}{}: {{...}} [{}] ({} keys)\n",
+                                indent,
+                                key,
+                                path_ref,
+                                inner.len()
+                            ));
+                            refs.push(json!({
+                                "name": path_ref,
+                                "ref": format!("[{}]", path_ref),
+                                "keys": inner.len()
+                            }));
+                        }
+                    }
+                    Value::Array(items) => {
+                        let path_ref = format!("path:{}", child_path);
+                        let expand = wants_expand(&args.expand_functions, &path_ref)
+                            || wants_expand(&args.expand_functions, &child_path)
+                            || context_matches(&args.expand_context, key);
+                        if expand {
+                            output.push_str(&format!("{}{}:\n", indent, key));
+                            push_pretty(child, depth + 1, output);
+                        } else {
+                            let elem_type = items.first().map(type_name).unwrap_or("empty");
+                            output.push_str(&format!(
+                                "{}{}: [{} items: {}] [{}]\n",
+                                indent,
+                                key,
+                                items.len(),
+                                elem_type,
+                                path_ref
+                            ));
+                            refs.push(json!({
+                                "name": path_ref,
+                                "ref": format!("[{}]", path_ref),
+                                "items": items.len()
+                            }));
+                        }
+                    }
+                    scalar => {
+                        output.push_str(&format!(
+                            "{}{}: {}\n",
+                            indent,
+                            key,
+                            truncate(&scalar_repr(scalar), 60)
+                        ));
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            let elem_type = items.first().map(type_name).unwrap_or("empty");
+            output.push_str(&format!("{}[{} items: {}]\n", indent, items.len(), elem_type));
+        }
+        scalar => {
+            output.push_str(&format!("{}{}\n", indent, truncate(&scalar_repr(scalar), 60)));
+        }
+    }
+}
+
+/// Pretty-print a subtree at the given indent level
+fn push_pretty(value: &Value, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+    for line in pretty.lines() {
+        output.push_str(&indent);
+        output.push_str(line);
+        output.push('\n');
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn scalar_repr(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", cut)
+    }
+}
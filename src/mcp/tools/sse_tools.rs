@@ -9,6 +9,37 @@ use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::sync::Arc;
 
+/// Turn watch arguments into an SseConfig - shared by the SSE tool and
+/// the WebSocket watch endpoint so both speak the same dialect
+pub fn build_sse_config(args: &WatchDirectorySseArgs) -> crate::mcp::sse::SseConfig {
+    let format = match args.format.as_str() {
+        "hex" => crate::mcp::sse::OutputFormat::Hex,
+        "ai" => crate::mcp::sse::OutputFormat::Ai,
+        "quantum" => crate::mcp::sse::OutputFormat::Quantum,
+        "quantum_semantic" => crate::mcp::sse::OutputFormat::QuantumSemantic,
+        "json" => crate::mcp::sse::OutputFormat::Json,
+        "summary" => crate::mcp::sse::OutputFormat::Summary,
+        _ => crate::mcp::sse::OutputFormat::Ai,
+    };
+
+    crate::mcp::sse::SseConfig {
+        path: PathBuf::from(&args.path),
+        format,
+        heartbeat_interval: args.heartbeat_interval,
+        stats_interval: args.stats_interval,
+        include_content: args.include_content,
+        max_depth: args.max_depth,
+        include_patterns: args.include_patterns.clone(),
+        exclude_patterns: args.exclude_patterns.clone(),
+        categories: args.categories.clone(),
+        granularity: crate::mcp::unified_watcher::EventGranularity::parse(
+            args.granularity.as_deref().unwrap_or("debounced"),
+        ),
+        debounce_ms: args.debounce_ms,
+        coalesce_threshold: args.coalesce_threshold,
+    }
+}
+
 /// Watch a directory for real-time changes via SSE
 pub async fn watch_directory_sse(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     let args: WatchDirectorySseArgs =
@@ -28,27 +59,8 @@ pub async fn watch_directory_sse(args: Value, ctx: Arc<McpContext>) -> Result<Va
         return Err(anyhow::anyhow!("Path does not exist: {}", args.path));
     }
 
-    // Parse output format
-    let format = match args.format.as_str() {
-        "hex" => crate::mcp::sse::OutputFormat::Hex,
-        "ai" => crate::mcp::sse::OutputFormat::Ai,
-        "quantum" => crate::mcp::sse::OutputFormat::Quantum,
-        "quantum_semantic" => crate::mcp::sse::OutputFormat::QuantumSemantic,
-        "json" => crate::mcp::sse::OutputFormat::Json,
-        "summary" => crate::mcp::sse::OutputFormat::Summary,
-        _ => crate::mcp::sse::OutputFormat::Ai,
-    };
-
-    let sse_config = crate::mcp::sse::SseConfig {
-        path: path.clone(),
-        format,
-        heartbeat_interval: args.heartbeat_interval,
-        stats_interval: args.stats_interval,
-        include_content: args.include_content,
-        max_depth: args.max_depth,
-        include_patterns: args.include_patterns,
-        exclude_patterns: args.exclude_patterns,
-    };
+    let sse_config = build_sse_config(&args);
+    let granularity = sse_config.granularity;
 
     // Note: In a real implementation, this would start an SSE endpoint
     // For MCP, we'll return instructions on how to use SSE
@@ -69,12 +81,21 @@ pub async fn watch_directory_sse(args: Value, ctx: Arc<McpContext>) -> Result<Va
         Event Types:\n\
         - scan_complete: Initial scan finished\n\
         - created: File/directory created\n\
-        - modified: File/directory modified\n\
+        - modified: File/directory modified (atomic saves arrive as one of these, not delete+create)\n\
         - deleted: File/directory deleted\n\
+        - renamed: File/directory renamed (old -> new paired)\n\
+        - changes: Per-window batch summary (granularity='batch') or a\n\
+          coalesced per-directory storm (coalesce_threshold > 0)\n\
         - analysis: Periodic analysis update\n\
         - stats: Statistics update\n\
-        - heartbeat: Keep-alive signal",
-        args.path, args.format, args.heartbeat_interval, args.stats_interval
+        - heartbeat: Keep-alive signal\n\n\
+        Granularity: {:?} ({}ms debounce window)",
+        args.path,
+        args.format,
+        args.heartbeat_interval,
+        args.stats_interval,
+        granularity,
+        args.debounce_ms
     );
 
     // Store the SSE config in cache for later retrieval
@@ -2,11 +2,17 @@
 //!
 //! Contains get_statistics, get_digest, and directory_size_breakdown handlers.
 
-use crate::formatters::{digest::DigestFormatter, stats::StatsFormatter, Formatter};
-use crate::mcp::helpers::{scan_with_config, should_use_default_ignores, validate_and_convert_path, ScannerConfigBuilder};
+use crate::formatters::digest::{DigestFormatter, MerkleDigest};
+use crate::formatters::{stats::StatsFormatter, Formatter};
+use crate::mcp::helpers::{
+    scan_shared, scan_with_config, should_use_default_ignores, validate_and_convert_path,
+    ScannerConfigBuilder,
+};
 use crate::mcp::{fmt_num64, fmt_size, McpContext};
 use anyhow::Result;
 use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Get comprehensive statistics about a directory
@@ -22,13 +28,14 @@ pub async fn get_statistics(args: Value, ctx: Arc<McpContext>) -> Result<Value>
         .show_hidden(show_hidden)
         .build();
 
-    // Scan directory
-    let (_nodes, stats) = scan_with_config(&path, config)?;
+    // Scan directory (shared with other tools hitting the same path/options)
+    let scan = scan_shared(&path, config, &ctx)?;
+    let (_nodes, stats) = &*scan;
 
     // Use stats formatter
     let formatter = StatsFormatter::new();
     let mut output = Vec::new();
-    formatter.format(&mut output, &[], &stats, &path)?;
+    formatter.format(&mut output, &[], stats, &path)?;
 
     Ok(json!({
         "content": [{
@@ -53,8 +60,10 @@ pub async fn get_digest(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     // Scan directory
     let (nodes, stats) = scan_with_config(&path, config)?;
 
-    // Use digest formatter
-    let formatter = DigestFormatter::new();
+    // Use digest formatter - merkle:true swaps the flat hash for one
+    // digest per directory, the form diff_digests compares
+    let merkle = args["merkle"].as_bool().unwrap_or(false);
+    let formatter = DigestFormatter::new().with_merkle(merkle);
     let mut output = Vec::new();
     formatter.format(&mut output, &nodes, &stats, &path)?;
 
@@ -66,16 +75,93 @@ pub async fn get_digest(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     }))
 }
 
-/// Get size analysis of immediate subdirectories
+/// Compare two Merkle digest documents and localize the changed subtrees.
+///
+/// `old_digest` is a document from an earlier `get_digest {merkle: true}`
+/// call (or `st --mode digest --merkle`). Pass `new_digest` to compare two
+/// stored documents, or just `path` to scan fresh and compare against now.
+pub async fn diff_digests(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
+    let old_text = args["old_digest"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing old_digest"))?;
+    let old = MerkleDigest::parse(old_text)?;
+
+    let new = match args["new_digest"].as_str() {
+        Some(text) => MerkleDigest::parse(text)?,
+        None => {
+            let path_str = args["path"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("Provide new_digest, or path to scan fresh"))?;
+            let path = validate_and_convert_path(path_str, &ctx)?;
+            let config = ScannerConfigBuilder::new()
+                .use_default_ignores(should_use_default_ignores(&path))
+                .build();
+            let (nodes, _stats) = scan_with_config(&path, config)?;
+            MerkleDigest::build(&nodes, &path)
+        }
+    };
+
+    let diff = old.diff(&new);
+    let localized = diff.localized();
+    let mut text = String::new();
+    if diff.is_empty() {
+        text.push_str("✅ Identical - no directory's digest moved\n");
+    } else {
+        text.push_str(&format!(
+            "🔀 {} changed, {} added, {} removed (of {} directories)\n",
+            diff.changed.len(),
+            diff.added.len(),
+            diff.removed.len(),
+            old.dirs.len().max(new.dirs.len()),
+        ));
+        if !localized.is_empty() {
+            text.push_str("\nChanges localize to:\n");
+            for dir in &localized {
+                text.push_str(&format!("  📁 {}\n", dir));
+            }
+        }
+        for (label, dirs) in [("Added", &diff.added), ("Removed", &diff.removed)] {
+            if !dirs.is_empty() {
+                text.push_str(&format!("\n{}:\n", label));
+                for dir in dirs {
+                    text.push_str(&format!("  📁 {}\n", dir));
+                }
+            }
+        }
+    }
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": text
+        }],
+        "metadata": {
+            "identical": diff.is_empty(),
+            "changed": diff.changed,
+            "localized": localized,
+            "added": diff.added,
+            "removed": diff.removed,
+        }
+    }))
+}
+
+/// Get recursive size analysis of subdirectories.
+///
+/// A single deep scan aggregates sizes bottom-up, then a nested structure is
+/// emitted down to the requested `depth` - each level carries its own total,
+/// so the output feeds directly into client-side treemap rendering. Hardlinks
+/// are counted once (by device+inode) and symlinks are never followed, so
+/// cycles can't inflate the totals.
 pub async fn directory_size_breakdown(args: Value, ctx: Arc<McpContext>) -> Result<Value> {
     let path_str = args["path"]
         .as_str()
         .ok_or_else(|| anyhow::anyhow!("Missing path"))?;
     let path = validate_and_convert_path(path_str, &ctx)?;
+    let depth = args["depth"].as_u64().unwrap_or(1).clamp(1, 10) as usize;
 
-    // Get immediate subdirectories
+    // One deep scan - aggregation replaces the old per-subdirectory rescans
     let config = ScannerConfigBuilder::new()
-        .max_depth(1)
+        .max_depth(50)
         .respect_gitignore(false)
         .show_hidden(true)
         .show_ignored(true)
@@ -84,58 +170,112 @@ pub async fn directory_size_breakdown(args: Value, ctx: Arc<McpContext>) -> Resu
 
     let (nodes, _) = scan_with_config(&path, config)?;
 
-    // Calculate size for each subdirectory
-    let mut dir_sizes = Vec::new();
+    // Bottom-up aggregation: every file's size is attributed to each of its
+    // ancestor directories up to the scan root.
+    let mut agg: HashMap<PathBuf, (u64, u64)> = HashMap::new(); // (size, files)
+    #[cfg(unix)]
+    let mut seen_inodes: HashSet<(u64, u64)> = HashSet::new();
+
     for node in &nodes {
-        if node.is_dir && node.path != path {
-            // Get size of this directory
-            let subconfig = ScannerConfigBuilder::new()
-                .respect_gitignore(false)
-                .show_hidden(true)
-                .show_ignored(true)
-                .use_default_ignores(false)
-                .build();
-            let (_, substats) = scan_with_config(&node.path, subconfig)?;
-
-            // Store raw data for sorting, then convert to hex later
-            dir_sizes.push((
-                node.path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("")
-                    .to_string(),
-                node.path.display().to_string(),
-                substats.total_size,
-                substats.total_files,
-            ));
+        if node.is_dir || node.permission_denied || node.is_symlink {
+            continue;
+        }
+
+        // Hardlink safety: count multiply-linked files once.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if let Ok(meta) = std::fs::symlink_metadata(&node.path) {
+                if meta.nlink() > 1 && !seen_inodes.insert((meta.dev(), meta.ino())) {
+                    continue;
+                }
+            }
+        }
+
+        let mut current = node.path.parent();
+        while let Some(dir) = current {
+            let entry = agg.entry(dir.to_path_buf()).or_insert((0, 0));
+            entry.0 += node.size;
+            entry.1 += 1;
+            if dir == path {
+                break;
+            }
+            current = dir.parent();
         }
     }
 
-    // Sort by size (raw u64) descending
-    dir_sizes.sort_by_key(|(_, _, size, _)| std::cmp::Reverse(*size));
+    // Index directories by parent for the nested walk
+    let mut children: HashMap<PathBuf, Vec<&PathBuf>> = HashMap::new();
+    let dir_paths: Vec<PathBuf> = nodes
+        .iter()
+        .filter(|n| n.is_dir && n.path != path)
+        .map(|n| n.path.clone())
+        .collect();
+    for dir in &dir_paths {
+        if let Some(parent) = dir.parent() {
+            children.entry(parent.to_path_buf()).or_default().push(dir);
+        }
+    }
 
-    // Convert to hex-formatted JSON
     let use_hex = ctx.config.hex_numbers;
-    let formatted_dirs: Vec<Value> = dir_sizes
-        .into_iter()
-        .map(|(name, path, size, files)| {
-            json!({
-                "dir": name,
-                "path": path,
-                "size": fmt_num64(size, use_hex),
-                "sz": fmt_size(size, use_hex),  // Human-readable with hex
-                "files": fmt_num64(files, use_hex)
-            })
-        })
-        .collect();
+    let (root_size, root_files) = agg.get(&path).copied().unwrap_or((0, 0));
+    let tree = build_breakdown_node(&path, depth, &children, &agg, use_hex);
 
     Ok(json!({
         "content": [{
             "type": "text",
             "text": serde_json::to_string_pretty(&json!({
                 "directory": path.display().to_string(),
-                "subdirs": formatted_dirs
+                "depth": depth,
+                "size": fmt_num64(root_size, use_hex),
+                "sz": fmt_size(root_size, use_hex),
+                "files": fmt_num64(root_files, use_hex),
+                "subdirs": tree
             }))?
         }]
     }))
 }
+
+/// Recursively build the nested per-directory breakdown (treemap-ready).
+fn build_breakdown_node(
+    dir: &Path,
+    depth_remaining: usize,
+    children: &HashMap<PathBuf, Vec<&PathBuf>>,
+    agg: &HashMap<PathBuf, (u64, u64)>,
+    use_hex: bool,
+) -> Vec<Value> {
+    if depth_remaining == 0 {
+        return Vec::new();
+    }
+
+    let mut subdirs: Vec<(&PathBuf, u64, u64)> = children
+        .get(dir)
+        .map(|dirs| {
+            dirs.iter()
+                .map(|d| {
+                    let (size, files) = agg.get(*d).copied().unwrap_or((0, 0));
+                    (*d, size, files)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Biggest first - that's the treemap ordering too
+    subdirs.sort_by_key(|(_, size, _)| std::cmp::Reverse(*size));
+
+    subdirs
+        .into_iter()
+        .map(|(subdir, size, files)| {
+            let nested =
+                build_breakdown_node(subdir, depth_remaining - 1, children, agg, use_hex);
+            json!({
+                "dir": subdir.file_name().and_then(|n| n.to_str()).unwrap_or(""),
+                "path": subdir.display().to_string(),
+                "size": fmt_num64(size, use_hex),
+                "sz": fmt_size(size, use_hex),
+                "files": fmt_num64(files, use_hex),
+                "subdirs": nested
+            })
+        })
+        .collect()
+}
@@ -7,7 +7,8 @@ use serde_json::{json, Value};
 
 /// Direct access to Wave Memory system
 pub async fn handle_wave_memory(args: Value) -> Result<Value> {
-    use crate::mcp::wave_memory::{get_wave_memory, MemoryType};
+    use crate::mcp::wave_memory::{get_wave_memory, resolve_scope, MemoryType};
+    use std::path::PathBuf;
 
     let operation = args["operation"]
         .as_str()
@@ -43,6 +44,8 @@ pub async fn handle_wave_memory(args: Value) -> Result<Value> {
                 .unwrap_or(MemoryType::Technical);
             let valence = args["valence"].as_f64().unwrap_or(0.0) as f32;
             let arousal = args["arousal"].as_f64().unwrap_or(0.5) as f32;
+            let project_path = args["project_path"].as_str().map(PathBuf::from);
+            let scope = resolve_scope(args["scope"].as_str(), project_path.as_deref());
 
             let id = manager.anchor(
                 content.clone(),
@@ -51,7 +54,8 @@ pub async fn handle_wave_memory(args: Value) -> Result<Value> {
                 valence,
                 arousal,
                 "tandem:human:claude".to_string(),
-                None,
+                project_path,
+                scope.clone(),
             )?;
 
             Ok(json!({
@@ -65,6 +69,7 @@ pub async fn handle_wave_memory(args: Value) -> Result<Value> {
                     "valence": valence,
                     "arousal": arousal,
                 },
+                "scope": scope.describe(),
                 "message": "🌊 Memory anchored as wave",
             }))
         }
@@ -78,8 +83,10 @@ pub async fn handle_wave_memory(args: Value) -> Result<Value> {
                 })
                 .unwrap_or_default();
             let max_results = args["max_results"].as_u64().unwrap_or(10) as usize;
+            let project_path = args["project_path"].as_str().map(PathBuf::from);
+            let scope = resolve_scope(args["scope"].as_str(), project_path.as_deref());
 
-            let results = manager.find_by_keywords(&keywords, max_results);
+            let results = manager.find_by_keywords(&keywords, max_results, &scope);
             let memories: Vec<_> = results
                 .iter()
                 .map(|mem| {
@@ -98,6 +105,7 @@ pub async fn handle_wave_memory(args: Value) -> Result<Value> {
             Ok(json!({
                 "operation": "find",
                 "keywords": keywords,
+                "scope": scope.describe(),
                 "total_found": memories.len(),
                 "memories": memories,
             }))
@@ -119,8 +127,16 @@ pub async fn handle_wave_memory(args: Value) -> Result<Value> {
             let max_results = args["max_results"].as_u64().unwrap_or(10) as usize;
 
             let query = keywords.join(" ");
-            let results =
-                manager.find_by_resonance(&query, &keywords, memory_type, threshold, max_results);
+            let project_path = args["project_path"].as_str().map(PathBuf::from);
+            let scope = resolve_scope(args["scope"].as_str(), project_path.as_deref());
+            let results = manager.find_by_resonance(
+                &query,
+                &keywords,
+                memory_type,
+                threshold,
+                max_results,
+                &scope,
+            );
             let memories: Vec<_> = results
                 .iter()
                 .map(|(mem, resonance)| {
@@ -140,6 +156,7 @@ pub async fn handle_wave_memory(args: Value) -> Result<Value> {
                 "operation": "resonance",
                 "search_mode": "wave_interference",
                 "query": keywords,
+                "scope": scope.describe(),
                 "threshold": threshold,
                 "total_found": memories.len(),
                 "memories": memories,
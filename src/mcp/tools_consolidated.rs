@@ -1,117 +1,183 @@
 // Consolidated MCP Tools - Reducing from 50+ to ~15 tools
 // Each tool now has a 'mode' or 'type' parameter to specify the operation
+//
+// Dispatch is driven by CONSOLIDATED_TOOL_MAP below - one table wiring every
+// consolidated (tool, selector value) pair to its canonical tool. The parity
+// tests at the bottom audit the table and the advertised schemas against
+// `tools::tool_definitions()`, so adding a row or a parameter that the
+// canonical side doesn't accept fails loudly instead of being silently
+// ignored at runtime.
 
 use crate::mcp::McpContext;
 use anyhow::{Context, Result};
 use serde_json::{json, Value};
 use std::sync::Arc;
 
-/// Consolidated find tool - combines all find_* operations
-pub async fn handle_find(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let find_type = params["type"].as_str().context("type parameter required")?;
-
-    // Transform parameters to match original tool format and dispatch
-    let transformed_params = match find_type {
-        "files" => params.clone(),
-        "code" => {
-            // Transform to find_code_files format
-            params.clone()
-        }
-        "config" | "documentation" | "tests" | "build" => {
-            // These just need path
-            json!({ "path": params.get("path").unwrap_or(&json!(".")) })
-        }
-        "large" => {
-            json!({
-                "path": params.get("path").unwrap_or(&json!(".")),
-                "min_size": params.get("min_size").unwrap_or(&json!("10M"))
-            })
-        }
-        "recent" => {
-            json!({
-                "path": params.get("path").unwrap_or(&json!(".")),
-                "days": params.get("days").unwrap_or(&json!(7))
-            })
-        }
-        "timespan" => params.clone(),
-        "duplicates" | "empty_dirs" => {
-            json!({ "path": params.get("path").unwrap_or(&json!(".")) })
+/// One row of the consolidated → canonical mapping table
+pub struct ConsolidatedMapping {
+    /// Consolidated tool name as exposed by the consolidated tools/list
+    pub tool: &'static str,
+    /// Parameter whose value selects the operation, if any
+    pub selector: Option<&'static str>,
+    /// Selector value this row matches (empty when there is no selector)
+    pub value: &'static str,
+    /// Canonical tool the call dispatches to
+    pub target: &'static str,
+}
+
+/// The single source of truth for consolidated dispatch
+///
+/// `hooks` and `unified_watcher` are absent on purpose - they dispatch to
+/// module-level handlers rather than canonical tools.
+pub const CONSOLIDATED_TOOL_MAP: &[ConsolidatedMapping] = &[
+    // find: one tool for all discovery needs
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "files", target: "find_files" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "code", target: "find_code_files" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "config", target: "find_config_files" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "documentation", target: "find_documentation" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "tests", target: "find_tests" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "build", target: "find_build_files" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "large", target: "find_large_files" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "recent", target: "find_recent_changes" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "timespan", target: "find_in_timespan" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "duplicates", target: "find_duplicates" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "empty_dirs", target: "find_empty_directories" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "projects", target: "find_projects" },
+    ConsolidatedMapping { tool: "find", selector: Some("type"), value: "secrets", target: "find_secrets" },
+    // analyze: analysis modes
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "directory", target: "analyze_directory" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "workspace", target: "analyze_workspace" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "statistics", target: "get_statistics" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "git_status", target: "get_git_status" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "digest", target: "get_digest" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "semantic", target: "semantic_analysis" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "quantum-semantic", target: "analyze_directory" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "size_breakdown", target: "directory_size_breakdown" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "permissions", target: "audit_permissions" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "dependencies", target: "analyze_dependencies" },
+    ConsolidatedMapping { tool: "analyze", selector: Some("mode"), value: "ai_tools", target: "analyze_ai_tool_usage" },
+    // overview: quick vs full project
+    ConsolidatedMapping { tool: "overview", selector: Some("mode"), value: "quick", target: "quick_tree" },
+    ConsolidatedMapping { tool: "overview", selector: Some("mode"), value: "project", target: "project_overview" },
+    // edit: Smart Edit operations
+    ConsolidatedMapping { tool: "edit", selector: Some("operation"), value: "smart_edit", target: "smart_edit" },
+    ConsolidatedMapping { tool: "edit", selector: Some("operation"), value: "get_functions", target: "get_function_tree" },
+    ConsolidatedMapping { tool: "edit", selector: Some("operation"), value: "insert_function", target: "insert_function" },
+    ConsolidatedMapping { tool: "edit", selector: Some("operation"), value: "remove_function", target: "remove_function" },
+    ConsolidatedMapping { tool: "edit", selector: Some("operation"), value: "create_file", target: "create_file" },
+    // history: file operation tracking
+    ConsolidatedMapping { tool: "history", selector: Some("operation"), value: "track", target: "track_file_operation" },
+    ConsolidatedMapping { tool: "history", selector: Some("operation"), value: "get_file", target: "get_file_history" },
+    ConsolidatedMapping { tool: "history", selector: Some("operation"), value: "get_project", target: "get_project_history_summary" },
+    // context: project context and collaboration
+    ConsolidatedMapping { tool: "context", selector: Some("operation"), value: "gather_project", target: "gather_project_context" },
+    ConsolidatedMapping { tool: "context", selector: Some("operation"), value: "collaboration_rapport", target: "get_collaboration_rapport" },
+    ConsolidatedMapping { tool: "context", selector: Some("operation"), value: "engagement_heatmap", target: "get_co_engagement_heatmap" },
+    ConsolidatedMapping { tool: "context", selector: Some("operation"), value: "cross_domain_patterns", target: "get_cross_domain_patterns" },
+    ConsolidatedMapping { tool: "context", selector: Some("operation"), value: "suggest_insights", target: "suggest_cross_session_insights" },
+    // memory: collaborative memories
+    ConsolidatedMapping { tool: "memory", selector: Some("operation"), value: "anchor", target: "anchor_collaborative_memory" },
+    ConsolidatedMapping { tool: "memory", selector: Some("operation"), value: "find", target: "find_collaborative_memories" },
+    // feedback
+    ConsolidatedMapping { tool: "feedback", selector: Some("operation"), value: "submit", target: "submit_feedback" },
+    ConsolidatedMapping { tool: "feedback", selector: Some("operation"), value: "request_tool", target: "request_tool" },
+    ConsolidatedMapping { tool: "feedback", selector: Some("operation"), value: "check_updates", target: "check_for_updates" },
+    // 1:1 passthroughs
+    ConsolidatedMapping { tool: "search", selector: None, value: "", target: "search_in_files" },
+    ConsolidatedMapping { tool: "compare", selector: None, value: "", target: "compare_directories" },
+    ConsolidatedMapping { tool: "sse", selector: None, value: "", target: "watch_directory_sse" },
+    ConsolidatedMapping { tool: "server_info", selector: None, value: "", target: "server_info" },
+    ConsolidatedMapping { tool: "verify_permissions", selector: None, value: "", target: "verify_permissions" },
+    ConsolidatedMapping { tool: "read", selector: None, value: "", target: "read" },
+    ConsolidatedMapping { tool: "project_context_dump", selector: None, value: "", target: "project_context_dump" },
+];
+
+/// Parameters that change name on the way through (tool, consolidated, canonical)
+pub const CONSOLIDATED_PARAM_RENAMES: &[(&str, &str, &str)] = &[
+    // analyze exposes the output format as 'format'; analyze_directory calls it 'mode'
+    ("analyze", "format", "mode"),
+    // history documents the file operation as 'op' because 'operation' is its selector
+    ("history", "op", "operation"),
+];
+
+/// Look up the canonical tool for a consolidated call
+pub fn canonical_target(
+    tool: &str,
+    selector_value: Option<&str>,
+) -> Option<&'static ConsolidatedMapping> {
+    CONSOLIDATED_TOOL_MAP.iter().find(|m| {
+        m.tool == tool
+            && match m.selector {
+                Some(_) => selector_value == Some(m.value),
+                None => true,
+            }
+    })
+}
+
+/// Build canonical arguments from consolidated ones: the selector is
+/// stripped (so 'history {operation:track}' can't masquerade as the file
+/// operation) and renamed parameters are carried across
+fn canonical_args(mapping: &ConsolidatedMapping, params: &Value) -> Value {
+    let mut args = params.clone();
+    if let Some(obj) = args.as_object_mut() {
+        if let Some(selector) = mapping.selector {
+            obj.remove(selector);
         }
-        "projects" => {
-            // Projects discovery - finds forgotten 3am coding gems!
-            json!({
-                "path": params.get("path").unwrap_or(&json!(".")),
-                "depth": params.get("depth").unwrap_or(&json!(10))
-            })
+        for (tool, from, to) in CONSOLIDATED_PARAM_RENAMES {
+            if *tool == mapping.tool {
+                if let Some(v) = obj.remove(*from) {
+                    obj.insert(to.to_string(), v);
+                }
+            }
         }
-        _ => return Err(anyhow::anyhow!("Unknown find type: {}", find_type)),
-    };
+    }
+    args
+}
 
-    // Call the appropriate original tool
-    let tool_name = match find_type {
-        "files" => "find_files",
-        "code" => "find_code_files",
-        "config" => "find_config_files",
-        "documentation" => "find_documentation",
-        "tests" => "find_tests",
-        "build" => "find_build_files",
-        "large" => "find_large_files",
-        "recent" => "find_recent_changes",
-        "timespan" => "find_in_timespan",
-        "duplicates" => "find_duplicates",
-        "empty_dirs" => "find_empty_directories",
-        "projects" => "find_projects",
-        _ => return Err(anyhow::anyhow!("Unknown find type: {}", find_type)),
+/// Table-driven dispatch for a selector-based consolidated tool
+async fn dispatch_mapped(
+    tool: &str,
+    params: Option<Value>,
+    ctx: Arc<McpContext>,
+) -> Result<Value> {
+    let params = params.context("Parameters required")?;
+    let selector = CONSOLIDATED_TOOL_MAP
+        .iter()
+        .find(|m| m.tool == tool)
+        .and_then(|m| m.selector);
+
+    let value = match selector {
+        Some(key) => Some(
+            params[key]
+                .as_str()
+                .with_context(|| format!("{} parameter required", key))?,
+        ),
+        None => None,
     };
 
-    // Call through the regular tools handler
-    super::tools::handle_tools_call(
-        json!({ "name": tool_name, "arguments": transformed_params }),
-        ctx,
-    )
-    .await
+    let mapping = canonical_target(tool, value).ok_or_else(|| {
+        anyhow::anyhow!("Unknown {} {}: {}", tool, selector.unwrap_or("call"), value.unwrap_or(""))
+    })?;
+
+    let mut args = canonical_args(mapping, &params);
+
+    // quantum-semantic rides on analyze_directory and must re-assert its mode
+    if mapping.tool == "analyze" && mapping.value == "quantum-semantic" {
+        args["mode"] = json!("quantum-semantic");
+    }
+
+    super::tools::handle_tools_call(json!({ "name": mapping.target, "arguments": args }), ctx)
+        .await
+}
+
+/// Consolidated find tool - combines all find_* operations
+pub async fn handle_find(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
+    dispatch_mapped("find", params, ctx).await
 }
 
 /// Consolidated analyze tool - combines analysis operations
 pub async fn handle_analyze(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let mode = params["mode"].as_str().context("mode parameter required")?;
-
-    // Transform parameters based on the tool
-    let (tool_name, transformed_params) = match mode {
-        "directory" => {
-            // analyze_directory expects 'mode' not 'format', but we rename 'format' to 'mode'
-            let mut p = params.clone();
-            if let Some(format) = p.get("format") {
-                p["mode"] = format.clone();
-                p.as_object_mut().unwrap().remove("format");
-            }
-            // Remove the consolidated 'mode' field as analyze_directory doesn't expect it
-            p.as_object_mut().unwrap().remove("mode");
-            ("analyze_directory", p)
-        }
-        "workspace" => ("analyze_workspace", params.clone()),
-        "statistics" => ("get_statistics", params.clone()),
-        "git_status" => ("get_git_status", params.clone()),
-        "digest" => ("get_digest", params.clone()),
-        "semantic" => ("semantic_analysis", params.clone()),
-        "quantum-semantic" => {
-            // quantum-semantic uses analyze_directory with quantum-semantic mode
-            let mut p = params.clone();
-            p["mode"] = json!("quantum-semantic");
-            ("analyze_directory", p)
-        }
-        "size_breakdown" => ("directory_size_breakdown", params.clone()),
-        "ai_tools" => ("analyze_ai_tool_usage", params.clone()),
-        _ => return Err(anyhow::anyhow!("Unknown analyze mode: {}", mode)),
-    };
-
-    super::tools::handle_tools_call(
-        json!({ "name": tool_name, "arguments": transformed_params }),
-        ctx,
-    )
-    .await
+    dispatch_mapped("analyze", params, ctx).await
 }
 
 /// Consolidated search tool
@@ -125,82 +191,32 @@ pub async fn handle_search(params: Option<Value>, ctx: Arc<McpContext>) -> Resul
 
 /// Consolidated overview tool - quick_tree and project_overview
 pub async fn handle_overview(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let mode = params
-        .get("mode")
-        .and_then(|m| m.as_str())
-        .unwrap_or("quick");
-
-    let tool_name = match mode {
-        "quick" => "quick_tree",
-        "project" => "project_overview",
-        _ => return Err(anyhow::anyhow!("Unknown overview mode: {}", mode)),
-    };
-
-    super::tools::handle_tools_call(json!({ "name": tool_name, "arguments": params }), ctx).await
+    // mode defaults to quick, unlike the other selectors
+    let mut params = params.unwrap_or(json!({}));
+    if params.get("mode").and_then(|m| m.as_str()).is_none() {
+        params["mode"] = json!("quick");
+    }
+    dispatch_mapped("overview", Some(params), ctx).await
 }
 
 /// Consolidated edit tool - combines all Smart Edit operations
 pub async fn handle_edit(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let operation = params["operation"].as_str().context("operation required")?;
-
-    let tool_name = match operation {
-        "smart_edit" => "smart_edit",
-        "get_functions" => "get_function_tree",
-        "insert_function" => "insert_function",
-        "remove_function" => "remove_function",
-        "create_file" => "create_file",
-        _ => return Err(anyhow::anyhow!("Unknown edit operation: {}", operation)),
-    };
-
-    super::tools::handle_tools_call(json!({ "name": tool_name, "arguments": params }), ctx).await
+    dispatch_mapped("edit", params, ctx).await
 }
 
 /// Consolidated history tool - file tracking and history
 pub async fn handle_history(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let operation = params["operation"].as_str().context("operation required")?;
-
-    let tool_name = match operation {
-        "track" => "track_file_operation",
-        "get_file" => "get_file_history",
-        "get_project" => "get_project_history_summary",
-        _ => return Err(anyhow::anyhow!("Unknown history operation: {}", operation)),
-    };
-
-    super::tools::handle_tools_call(json!({ "name": tool_name, "arguments": params }), ctx).await
+    dispatch_mapped("history", params, ctx).await
 }
 
 /// Consolidated context tool - project context and collaboration
 pub async fn handle_context(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let operation = params["operation"].as_str().context("operation required")?;
-
-    let tool_name = match operation {
-        "gather_project" => "gather_project_context",
-        "collaboration_rapport" => "get_collaboration_rapport",
-        "engagement_heatmap" => "get_co_engagement_heatmap",
-        "cross_domain_patterns" => "get_cross_domain_patterns",
-        "suggest_insights" => "suggest_cross_session_insights",
-        _ => return Err(anyhow::anyhow!("Unknown context operation: {}", operation)),
-    };
-
-    super::tools::handle_tools_call(json!({ "name": tool_name, "arguments": params }), ctx).await
+    dispatch_mapped("context", params, ctx).await
 }
 
 /// Consolidated memory tool - collaborative memories
 pub async fn handle_memory(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let operation = params["operation"].as_str().context("operation required")?;
-
-    let tool_name = match operation {
-        "anchor" => "anchor_collaborative_memory",
-        "find" => "find_collaborative_memories",
-        _ => return Err(anyhow::anyhow!("Unknown memory operation: {}", operation)),
-    };
-
-    super::tools::handle_tools_call(json!({ "name": tool_name, "arguments": params }), ctx).await
+    dispatch_mapped("memory", params, ctx).await
 }
 
 /// Consolidated compare tool
@@ -223,422 +239,7 @@ pub async fn handle_sse(params: Option<Value>, ctx: Arc<McpContext>) -> Result<V
 
 /// Consolidated feedback tool
 pub async fn handle_feedback(params: Option<Value>, ctx: Arc<McpContext>) -> Result<Value> {
-    let params = params.context("Parameters required")?;
-    let operation = params["operation"].as_str().context("operation required")?;
-
-    let tool_name = match operation {
-        "submit" => "submit_feedback",
-        "request_tool" => "request_tool",
-        "check_updates" => "check_for_updates",
-        _ => return Err(anyhow::anyhow!("Unknown feedback operation: {}", operation)),
-    };
-
-    super::tools::handle_tools_call(json!({ "name": tool_name, "arguments": params }), ctx).await
-}
-
-/// Get consolidated tool list
-#[allow(dead_code)]
-pub fn get_consolidated_tools() -> Vec<Value> {
-    vec![
-        json!({
-            "name": "find",
-            "description": "Find files, code, config, tests, documentation, duplicates, etc.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "type": {
-                        "type": "string",
-                        "enum": ["files", "code", "config", "documentation", "tests", "build",
-                                 "large", "recent", "timespan", "duplicates", "empty_dirs"],
-                        "description": "Type of items to find"
-                    },
-                    "path": {
-                        "type": "string",
-                        "description": "Path to search in"
-                    },
-                    "pattern": {
-                        "type": "string",
-                        "description": "Pattern to match (for files type)"
-                    },
-                    "languages": {
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Programming languages (for code type)"
-                    },
-                    "days": {
-                        "type": "integer",
-                        "description": "Number of days (for recent type)"
-                    },
-                    "min_size": {
-                        "type": "string",
-                        "description": "Minimum size (for large type)"
-                    },
-                    "start_date": {
-                        "type": "string",
-                        "description": "Start date YYYY-MM-DD (for timespan type)"
-                    },
-                    "end_date": {
-                        "type": "string",
-                        "description": "End date YYYY-MM-DD (for timespan type)"
-                    }
-                },
-                "required": ["type"]
-            }
-        }),
-        json!({
-            "name": "analyze",
-            "description": "Analyze directories, workspaces, statistics, git status, etc.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "mode": {
-                        "type": "string",
-                        "enum": ["directory", "workspace", "statistics", "git_status",
-                                 "digest", "semantic", "size_breakdown", "ai_tools"],
-                        "description": "Analysis mode"
-                    },
-                    "path": {
-                        "type": "string",
-                        "description": "Path to analyze"
-                    },
-                    "format": {
-                        "type": "string",
-                        "description": "Output format (for directory mode)"
-                    },
-                    "max_depth": {
-                        "type": "integer",
-                        "description": "Maximum depth"
-                    },
-                    "show_hidden": {
-                        "type": "boolean",
-                        "description": "Show hidden files"
-                    }
-                },
-                "required": ["mode"]
-            }
-        }),
-        json!({
-            "name": "search",
-            "description": "Search for content within files",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "keyword": {
-                        "type": "string",
-                        "description": "Keyword or pattern to search"
-                    },
-                    "path": {
-                        "type": "string",
-                        "description": "Path to search in"
-                    },
-                    "case_sensitive": {
-                        "type": "boolean",
-                        "description": "Case sensitive search"
-                    },
-                    "file_type": {
-                        "type": "string",
-                        "description": "File type to search"
-                    },
-                    "context_lines": {
-                        "type": "integer",
-                        "description": "Number of context lines"
-                    },
-                    "include_content": {
-                        "type": "boolean",
-                        "description": "Include file content in results"
-                    }
-                },
-                "required": ["keyword"]
-            }
-        }),
-        json!({
-            "name": "overview",
-            "description": "Get quick tree overview or comprehensive project overview",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "mode": {
-                        "type": "string",
-                        "enum": ["quick", "project"],
-                        "description": "Overview mode"
-                    },
-                    "path": {
-                        "type": "string",
-                        "description": "Path to overview"
-                    },
-                    "depth": {
-                        "type": "integer",
-                        "description": "Maximum depth (for quick mode)"
-                    }
-                },
-                "required": []
-            }
-        }),
-        json!({
-            "name": "edit",
-            "description": "Smart code editing operations - AST-aware with 90% token reduction",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "operation": {
-                        "type": "string",
-                        "enum": ["smart_edit", "get_functions", "insert_function", "remove_function"],
-                        "description": "Edit operation"
-                    },
-                    "file_path": {
-                        "type": "string",
-                        "description": "File to edit"
-                    },
-                    "edits": {
-                        "type": "array",
-                        "description": "Array of edit operations (for smart_edit)",
-                        "items": {
-                            "type": "object",
-                            "description": "Individual edit operation"
-                        }
-                    },
-                    "name": {
-                        "type": "string",
-                        "description": "Function name"
-                    },
-                    "body": {
-                        "type": "string",
-                        "description": "Function body"
-                    }
-                },
-                "required": ["operation", "file_path"]
-            }
-        }),
-        json!({
-            "name": "history",
-            "description": "Track and retrieve file operation history",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "operation": {
-                        "type": "string",
-                        "enum": ["track", "get_file", "get_project"],
-                        "description": "History operation"
-                    },
-                    "file_path": {
-                        "type": "string",
-                        "description": "File path"
-                    },
-                    "project_path": {
-                        "type": "string",
-                        "description": "Project path"
-                    },
-                    "op": {
-                        "type": "string",
-                        "description": "File operation type"
-                    },
-                    "old_content": {
-                        "type": "string",
-                        "description": "Previous content"
-                    },
-                    "new_content": {
-                        "type": "string",
-                        "description": "New content"
-                    }
-                },
-                "required": ["operation"]
-            }
-        }),
-        json!({
-            "name": "context",
-            "description": "Project context, collaboration rapport, and cross-domain patterns",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "operation": {
-                        "type": "string",
-                        "enum": ["gather_project", "collaboration_rapport", "engagement_heatmap",
-                                 "cross_domain_patterns", "suggest_insights"],
-                        "description": "Context operation"
-                    },
-                    "project_path": {
-                        "type": "string",
-                        "description": "Project path"
-                    },
-                    "ai_tool": {
-                        "type": "string",
-                        "description": "AI tool name"
-                    },
-                    "keywords": {
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Keywords for search"
-                    }
-                },
-                "required": ["operation"]
-            }
-        }),
-        json!({
-            "name": "memory",
-            "description": "Anchor and find collaborative memories",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "operation": {
-                        "type": "string",
-                        "enum": ["anchor", "find"],
-                        "description": "Memory operation"
-                    },
-                    "context": {
-                        "type": "string",
-                        "description": "Memory context"
-                    },
-                    "keywords": {
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Keywords"
-                    },
-                    "anchor_type": {
-                        "type": "string",
-                        "description": "Type of anchor"
-                    },
-                    "origin": {
-                        "type": "string",
-                        "description": "Who created this? 'human', 'ai:claude', or 'tandem:human:claude' (default: tandem:human:claude)"
-                    }
-                },
-                "required": ["operation", "keywords"]
-            }
-        }),
-        json!({
-            "name": "compare",
-            "description": "Compare two directories",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "path1": {
-                        "type": "string",
-                        "description": "First directory"
-                    },
-                    "path2": {
-                        "type": "string",
-                        "description": "Second directory"
-                    }
-                },
-                "required": ["path1", "path2"]
-            }
-        }),
-        json!({
-            "name": "sse",
-            "description": "Watch directory with Server-Sent Events",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "path": {
-                        "type": "string",
-                        "description": "Path to watch"
-                    },
-                    "format": {
-                        "type": "string",
-                        "description": "Output format"
-                    },
-                    "heartbeat_interval": {
-                        "type": "integer",
-                        "description": "Heartbeat interval in seconds"
-                    }
-                },
-                "required": ["path"]
-            }
-        }),
-        json!({
-            "name": "feedback",
-            "description": "Submit feedback, request tools, or check updates",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "operation": {
-                        "type": "string",
-                        "enum": ["submit", "request_tool", "check_updates"],
-                        "description": "Feedback operation"
-                    },
-                    "category": {
-                        "type": "string",
-                        "description": "Feedback category"
-                    },
-                    "title": {
-                        "type": "string",
-                        "description": "Feedback title"
-                    },
-                    "description": {
-                        "type": "string",
-                        "description": "Detailed description"
-                    }
-                },
-                "required": ["operation"]
-            }
-        }),
-        json!({
-            "name": "server_info",
-            "description": "Get Smart Tree server information and capabilities",
-            "inputSchema": {
-                "type": "object",
-                "properties": {},
-                "required": []
-            }
-        }),
-        json!({
-            "name": "verify_permissions",
-            "description": "Verify permissions for a path",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "path": {
-                        "type": "string",
-                        "description": "Path to verify"
-                    }
-                },
-                "required": ["path"]
-            }
-        }),
-        json!({
-            "name": "project_context_dump",
-            "description": "📦 FULL PROJECT CONTEXT - Get a complete, token-efficient project dump for AI assistants in ONE CALL! Includes directory tree, key files, git info, with configurable compression.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "path": {
-                        "type": "string",
-                        "description": "Path to the project root"
-                    },
-                    "max_depth": {
-                        "type": "integer",
-                        "description": "Maximum directory depth (1-20, default: 5)"
-                    },
-                    "max_files": {
-                        "type": "integer",
-                        "description": "Maximum files to include (10-1000, default: 100)"
-                    },
-                    "include_content": {
-                        "type": "boolean",
-                        "description": "Include contents of key files (default: false)"
-                    },
-                    "compression": {
-                        "type": "string",
-                        "enum": ["auto", "marqant", "summary-ai", "quantum"],
-                        "description": "Compression mode (default: auto = summary-ai)"
-                    },
-                    "token_budget": {
-                        "type": "integer",
-                        "description": "Token budget warning threshold (default: 10000)"
-                    },
-                    "include_git": {
-                        "type": "boolean",
-                        "description": "Include git status info (default: true)"
-                    },
-                    "key_files_only": {
-                        "type": "boolean",
-                        "description": "Only show key project files (default: false)"
-                    }
-                },
-                "required": ["path"]
-            }
-        }),
-    ]
+    dispatch_mapped("feedback", params, ctx).await
 }
 
 /// Handle hooks tool operations
@@ -710,3 +311,149 @@ pub async fn dispatch_consolidated_tool(
         _ => Err(anyhow::anyhow!("Unknown tool: {}", name)),
     }
 }
+
+/// Audit the consolidated layer against the canonical tool catalog
+///
+/// Returns human-readable findings, one per drift: mapping targets that
+/// don't exist, selector enum values without a mapping row (or rows the
+/// schema no longer advertises), and advertised parameters that no mapped
+/// canonical tool accepts (accepted-but-ignored drift). Empty = in parity.
+pub fn audit_consolidated_parity() -> Vec<String> {
+    use std::collections::{HashMap, HashSet};
+
+    // Tools handled by module-level handlers, not canonical dispatch
+    const MODULE_HANDLED: &[&str] = &["hooks", "unified_watcher"];
+
+    let canonical: HashMap<String, HashSet<String>> = super::tools::tool_definitions()
+        .into_iter()
+        .map(|def| {
+            let props = def.input_schema["properties"]
+                .as_object()
+                .map(|o| o.keys().cloned().collect())
+                .unwrap_or_default();
+            (def.name, props)
+        })
+        .collect();
+
+    let mut findings = Vec::new();
+
+    // Every mapping row must point at a real canonical tool
+    for mapping in CONSOLIDATED_TOOL_MAP {
+        if !canonical.contains_key(mapping.target) {
+            findings.push(format!(
+                "{} {} maps to unknown canonical tool '{}'",
+                mapping.tool, mapping.value, mapping.target
+            ));
+        }
+    }
+
+    for tool in super::tools_consolidated_enhanced::get_enhanced_consolidated_tools() {
+        let name = tool["name"].as_str().unwrap_or_default();
+        if MODULE_HANDLED.contains(&name) {
+            continue;
+        }
+
+        let rows: Vec<&ConsolidatedMapping> = CONSOLIDATED_TOOL_MAP
+            .iter()
+            .filter(|m| m.tool == name)
+            .collect();
+        if rows.is_empty() {
+            findings.push(format!("consolidated tool '{}' has no mapping rows", name));
+            continue;
+        }
+
+        let properties = tool["inputSchema"]["properties"]
+            .as_object()
+            .cloned()
+            .unwrap_or_default();
+
+        // Selector enum values and mapping rows must cover each other
+        if let Some(selector) = rows[0].selector {
+            let advertised: HashSet<&str> = properties
+                .get(selector)
+                .and_then(|p| p["enum"].as_array())
+                .map(|vals| vals.iter().filter_map(|v| v.as_str()).collect())
+                .unwrap_or_default();
+            let mapped: HashSet<&str> = rows.iter().map(|m| m.value).collect();
+
+            for value in advertised.difference(&mapped) {
+                findings.push(format!("{} {}='{}' has no mapping row", name, selector, value));
+            }
+            for value in mapped.difference(&advertised) {
+                findings.push(format!(
+                    "{} maps {}='{}' which the schema no longer advertises",
+                    name, selector, value
+                ));
+            }
+        }
+
+        // Every advertised parameter must be accepted by at least one target
+        // (after renames) - otherwise it is documented but silently ignored
+        for param in properties.keys() {
+            if rows[0].selector == Some(param.as_str()) {
+                continue;
+            }
+            let canonical_name = CONSOLIDATED_PARAM_RENAMES
+                .iter()
+                .find(|(tool, from, _)| *tool == name && *from == param)
+                .map(|(_, _, to)| *to)
+                .unwrap_or(param.as_str());
+
+            let accepted = rows.iter().any(|m| {
+                canonical
+                    .get(m.target)
+                    .is_some_and(|props| props.contains(canonical_name))
+            });
+            if !accepted {
+                findings.push(format!(
+                    "{} parameter '{}' is not accepted by any mapped canonical tool",
+                    name, param
+                ));
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consolidated_layer_is_in_parity_with_canonical_tools() {
+        let findings = audit_consolidated_parity();
+        assert!(
+            findings.is_empty(),
+            "consolidated layer drifted from canonical tools:\n{}",
+            findings.join("\n")
+        );
+    }
+
+    #[test]
+    fn canonical_target_lookup() {
+        assert_eq!(
+            canonical_target("find", Some("tests")).unwrap().target,
+            "find_tests"
+        );
+        assert_eq!(
+            canonical_target("search", None).unwrap().target,
+            "search_in_files"
+        );
+        assert!(canonical_target("find", Some("nonsense")).is_none());
+        assert!(canonical_target("nonsense", None).is_none());
+    }
+
+    #[test]
+    fn selector_is_stripped_and_renames_applied() {
+        let mapping = canonical_target("history", Some("track")).unwrap();
+        let args = canonical_args(
+            mapping,
+            &json!({ "operation": "track", "file_path": "a.rs", "op": "create" }),
+        );
+        // 'operation: track' must not leak through as the file operation
+        assert_eq!(args["operation"], json!("create"));
+        assert_eq!(args["file_path"], json!("a.rs"));
+        assert!(args.get("op").is_none());
+    }
+}
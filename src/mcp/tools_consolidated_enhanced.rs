@@ -4,8 +4,8 @@
 use crate::feature_flags;
 use serde_json::{json, Value};
 
-// Re-export the dispatcher from the original consolidated tools
-pub use super::tools_consolidated::dispatch_consolidated_tool;
+// Re-export the dispatcher and parity audit from the original consolidated tools
+pub use super::tools_consolidated::{audit_consolidated_parity, dispatch_consolidated_tool};
 
 /// Get enhanced consolidated tool list with attractive tips and examples
 pub fn get_enhanced_consolidated_tools() -> Vec<Value> {
@@ -76,7 +76,8 @@ EXAMPLES:
                     "type": {
                         "type": "string",
                         "enum": ["files", "code", "config", "documentation", "tests", "build",
-                                 "large", "recent", "timespan", "duplicates", "empty_dirs", "projects"],
+                                 "large", "recent", "timespan", "duplicates", "empty_dirs", "projects",
+                                 "secrets"],
                         "description": "What to find (code/tests/config/docs/etc)"
                     },
                     "path": {
@@ -188,7 +189,8 @@ EXAMPLES:
                     "mode": {
                         "type": "string",
                         "enum": ["directory", "workspace", "statistics", "git_status",
-                                 "digest", "semantic", "size_breakdown", "ai_tools"],
+                                 "digest", "semantic", "quantum-semantic", "size_breakdown", "ai_tools",
+                                 "permissions", "dependencies"],
                         "description": "Analysis type"
                     },
                     "path": {
@@ -2,17 +2,323 @@
 // "The all-seeing eye of Smart Tree!" - Aye
 
 use anyhow::Result;
+use notify::event::{ModifyKind, RenameMode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex as TokioMutex;
 
 use super::context_absorber::ContextAbsorber;
 use super::smart_background_searcher::{SearchConfig, SmartBackgroundSearcher};
 
+/// How finely change events are sliced for subscribers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventGranularity {
+    /// Every raw notify event, unprocessed - the pre-aggregation behavior
+    Raw,
+    /// Coalesced per path within the debounce window (default)
+    #[default]
+    Debounced,
+    /// One summary event per window
+    Batch,
+}
+
+impl EventGranularity {
+    /// Parse a user-supplied granularity name, defaulting to Debounced
+    pub fn parse(raw: &str) -> Self {
+        match raw.to_lowercase().as_str() {
+            "raw" => Self::Raw,
+            "batch" => Self::Batch,
+            _ => Self::Debounced,
+        }
+    }
+}
+
+/// One logical change after debouncing and rename pairing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggregateEvent {
+    Created(PathBuf),
+    Modified(PathBuf),
+    Deleted(PathBuf),
+    Renamed { from: PathBuf, to: PathBuf },
+    /// Many events under one directory, collapsed into a single summary
+    /// (only produced when directory coalescing is enabled)
+    DirectoryChanged { dir: PathBuf, summary: BatchSummary },
+}
+
+/// Totals for one debounce window
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub created: usize,
+    pub modified: usize,
+    pub deleted: usize,
+    pub renamed: usize,
+    pub window_ms: u64,
+}
+
+impl BatchSummary {
+    pub fn of(events: &[AggregateEvent], window: Duration) -> Self {
+        let mut summary = BatchSummary {
+            window_ms: window.as_millis() as u64,
+            ..Default::default()
+        };
+        for event in events {
+            summary.tally(event);
+        }
+        summary
+    }
+
+    /// Count one event into the totals (directory summaries fold in whole)
+    fn tally(&mut self, event: &AggregateEvent) {
+        match event {
+            AggregateEvent::Created(_) => self.created += 1,
+            AggregateEvent::Modified(_) => self.modified += 1,
+            AggregateEvent::Deleted(_) => self.deleted += 1,
+            AggregateEvent::Renamed { .. } => self.renamed += 1,
+            AggregateEvent::DirectoryChanged { summary, .. } => {
+                self.created += summary.created;
+                self.modified += summary.modified;
+                self.deleted += summary.deleted;
+                self.renamed += summary.renamed;
+            }
+        }
+    }
+}
+
+/// What we currently believe happened to one path inside the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// Debounces raw notify events into logical changes
+///
+/// Editors' atomic saves arrive as delete+create (or rename) storms; within
+/// one window this coalesces them per path, pairs rename halves into
+/// `Renamed { from, to }`, and drops create+delete churn entirely. The SSE
+/// watcher, MCP resource subscriptions, and the `--watch` CLI all feed
+/// through here.
+pub struct EventAggregator {
+    window: Duration,
+    window_started: Option<Instant>,
+    pending: HashMap<PathBuf, PendingKind>,
+    renames: Vec<(PathBuf, PathBuf)>,
+    /// First half of a split rename, waiting for its To
+    rename_from: Option<PathBuf>,
+    /// Collapse more than this many events sharing a parent directory into
+    /// one `DirectoryChanged` summary; None leaves events individual
+    coalesce_threshold: Option<usize>,
+}
+
+impl EventAggregator {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            window_started: None,
+            pending: HashMap::new(),
+            renames: Vec::new(),
+            rename_from: None,
+            coalesce_threshold: None,
+        }
+    }
+
+    /// Enable per-directory coalescing: a window that flushes more than
+    /// `threshold` events under one directory (a build dumping artifacts,
+    /// a branch switch) emits a single summarized event for that directory
+    /// instead. 0 disables coalescing.
+    pub fn with_directory_coalescing(mut self, threshold: usize) -> Self {
+        self.coalesce_threshold = if threshold == 0 { None } else { Some(threshold) };
+        self
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Fold one raw notify event into the current window
+    pub fn push(&mut self, event: &notify::Event) {
+        use notify::EventKind;
+
+        match &event.kind {
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                if let Some(path) = event.paths.first() {
+                    self.touch();
+                    // An unpaired From that never gets its To is a delete
+                    if let Some(stale) = self.rename_from.take() {
+                        self.record(stale, PendingKind::Deleted);
+                    }
+                    self.rename_from = Some(path.clone());
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                if let Some(path) = event.paths.first() {
+                    self.touch();
+                    match self.rename_from.take() {
+                        Some(from) => self.pair_rename(from, path.clone()),
+                        // To without a From (moved in from outside the watch)
+                        None => self.record(path.clone(), PendingKind::Created),
+                    }
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                if let [from, to] = event.paths.as_slice() {
+                    self.touch();
+                    self.pair_rename(from.clone(), to.clone());
+                }
+            }
+            EventKind::Create(_) => {
+                for path in &event.paths {
+                    self.touch();
+                    match self.pending.get(path) {
+                        // Delete then create inside one window = atomic save
+                        Some(PendingKind::Deleted) => {
+                            self.pending.insert(path.clone(), PendingKind::Modified);
+                        }
+                        Some(_) => {}
+                        None => {
+                            self.pending.insert(path.clone(), PendingKind::Created);
+                        }
+                    }
+                }
+            }
+            EventKind::Modify(_) => {
+                for path in &event.paths {
+                    self.touch();
+                    // A modify on a freshly created path is still "created"
+                    self.pending
+                        .entry(path.clone())
+                        .or_insert(PendingKind::Modified);
+                }
+            }
+            EventKind::Remove(_) => {
+                for path in &event.paths {
+                    self.touch();
+                    match self.pending.remove(path) {
+                        // Created and deleted inside one window: pure churn
+                        Some(PendingKind::Created) => {}
+                        _ => {
+                            self.pending.insert(path.clone(), PendingKind::Deleted);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn touch(&mut self) {
+        self.window_started.get_or_insert_with(Instant::now);
+    }
+
+    fn record(&mut self, path: PathBuf, kind: PendingKind) {
+        self.pending.insert(path, kind);
+    }
+
+    fn pair_rename(&mut self, from: PathBuf, to: PathBuf) {
+        // Whatever we thought about the old path no longer matters
+        self.pending.remove(&from);
+        self.renames.push((from, to));
+    }
+
+    /// Has the window elapsed with something to report?
+    pub fn ready(&self) -> bool {
+        self.window_started
+            .is_some_and(|started| started.elapsed() >= self.window)
+            && (!self.pending.is_empty() || !self.renames.is_empty())
+    }
+
+    /// Drain the window into logical events (renames first, then sorted paths)
+    pub fn flush(&mut self) -> Vec<AggregateEvent> {
+        self.window_started = None;
+
+        // A From still unpaired at flush time means the file left the watch
+        if let Some(orphan) = self.rename_from.take() {
+            self.pending.insert(orphan, PendingKind::Deleted);
+        }
+
+        let mut events: Vec<AggregateEvent> = self
+            .renames
+            .drain(..)
+            .map(|(from, to)| AggregateEvent::Renamed { from, to })
+            .collect();
+
+        let mut changed: Vec<(PathBuf, PendingKind)> = self.pending.drain().collect();
+        changed.sort_by(|a, b| a.0.cmp(&b.0));
+        for (path, kind) in changed {
+            events.push(match kind {
+                PendingKind::Created => AggregateEvent::Created(path),
+                PendingKind::Modified => AggregateEvent::Modified(path),
+                PendingKind::Deleted => AggregateEvent::Deleted(path),
+            });
+        }
+        self.coalesce_by_directory(events)
+    }
+
+    /// Which directory an event counts against for coalescing
+    fn dir_of(event: &AggregateEvent) -> Option<PathBuf> {
+        let path = match event {
+            AggregateEvent::Created(p)
+            | AggregateEvent::Modified(p)
+            | AggregateEvent::Deleted(p) => p,
+            // A rename lands where it ended up
+            AggregateEvent::Renamed { to, .. } => to,
+            AggregateEvent::DirectoryChanged { .. } => return None,
+        };
+        path.parent().map(Path::to_path_buf)
+    }
+
+    /// Collapse directories that exceeded the threshold into one
+    /// `DirectoryChanged` each, sitting where the directory's first event
+    /// was; quieter directories keep their individual events
+    fn coalesce_by_directory(&self, events: Vec<AggregateEvent>) -> Vec<AggregateEvent> {
+        let Some(threshold) = self.coalesce_threshold else {
+            return events;
+        };
+
+        let mut counts: HashMap<PathBuf, usize> = HashMap::new();
+        for event in &events {
+            if let Some(dir) = Self::dir_of(event) {
+                *counts.entry(dir).or_insert(0) += 1;
+            }
+        }
+        counts.retain(|_, count| *count > threshold);
+        if counts.is_empty() {
+            return events;
+        }
+
+        let mut out: Vec<AggregateEvent> = Vec::with_capacity(events.len());
+        let mut summary_at: HashMap<PathBuf, usize> = HashMap::new();
+        for event in events {
+            match Self::dir_of(&event).filter(|dir| counts.contains_key(dir)) {
+                Some(dir) => {
+                    let index = *summary_at.entry(dir.clone()).or_insert_with(|| {
+                        out.push(AggregateEvent::DirectoryChanged {
+                            dir,
+                            summary: BatchSummary {
+                                window_ms: self.window.as_millis() as u64,
+                                ..Default::default()
+                            },
+                        });
+                        out.len() - 1
+                    });
+                    if let AggregateEvent::DirectoryChanged { summary, .. } = &mut out[index] {
+                        summary.tally(&event);
+                    }
+                }
+                None => out.push(event),
+            }
+        }
+        out
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnifiedWatcherConfig {
     pub project_name: String,
@@ -11,6 +11,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::io::BufRead;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
@@ -64,6 +65,68 @@ impl MemoryType {
     }
 }
 
+/// Namespace a memory lives in - keeps team-shared daemons from leaking
+/// personal context between users and projects
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum MemoryScope {
+    /// Visible to everyone on this daemon
+    #[default]
+    Global,
+    /// Private to one user (by username)
+    User(String),
+    /// Shared within one project (by project root)
+    Project(PathBuf),
+}
+
+impl MemoryScope {
+    /// Scope for the user running this process
+    pub fn current_user() -> Self {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        MemoryScope::User(user)
+    }
+
+    /// Can a query in `query` scope see this memory?
+    ///
+    /// Global memories are visible everywhere; user and project memories
+    /// only within their own namespace. That's the isolation guarantee.
+    pub fn visible_from(&self, query: &MemoryScope) -> bool {
+        matches!(self, MemoryScope::Global) || self == query
+    }
+
+    /// Short human-readable form for stats and tool output
+    pub fn describe(&self) -> String {
+        match self {
+            MemoryScope::Global => "global".to_string(),
+            MemoryScope::User(u) => format!("user:{}", u),
+            MemoryScope::Project(p) => format!("project:{}", p.display()),
+        }
+    }
+}
+
+/// Resolve a scope from tool arguments
+///
+/// Explicit scope wins; otherwise a project path implies project scope and
+/// everything else defaults to the current user (personal by default - no
+/// accidental broadcasting).
+pub fn resolve_scope(scope: Option<&str>, project_path: Option<&Path>) -> MemoryScope {
+    match scope.map(|s| s.to_lowercase()).as_deref() {
+        Some("global") => MemoryScope::Global,
+        Some("user") => MemoryScope::current_user(),
+        Some("project") => MemoryScope::Project(
+            project_path
+                .map(|p| p.to_path_buf())
+                .or_else(|| std::env::current_dir().ok())
+                .unwrap_or_else(|| PathBuf::from(".")),
+        ),
+        _ => match project_path {
+            Some(p) => MemoryScope::Project(p.to_path_buf()),
+            None => MemoryScope::current_user(),
+        },
+    }
+}
+
 /// A memory anchored in the wave grid
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnchoredMemory {
@@ -93,6 +156,9 @@ pub struct AnchoredMemory {
     pub origin: String,
     /// Project path this memory is associated with
     pub project_path: Option<PathBuf>,
+    /// Namespace this memory lives in (legacy files migrate on load)
+    #[serde(default)]
+    pub scope: MemoryScope,
 }
 
 impl AnchoredMemory {
@@ -331,6 +397,7 @@ impl WaveMemoryManager {
         arousal: f32,
         origin: String,
         project_path: Option<PathBuf>,
+        scope: MemoryScope,
     ) -> Result<String> {
         let id = uuid::Uuid::new_v4().to_string();
         let (x, y, z) = AnchoredMemory::calculate_coordinates(&content, &keywords, memory_type);
@@ -350,6 +417,7 @@ impl WaveMemoryManager {
             access_count: 1,
             origin,
             project_path,
+            scope,
         };
 
         // Store in wave grid
@@ -376,9 +444,21 @@ impl WaveMemoryManager {
         &mut self,
         keywords: &[String],
         max_results: usize,
+        scope: &MemoryScope,
     ) -> Vec<AnchoredMemory> {
         let ids = self.keyword_index.find(keywords);
-        let found_ids: Vec<String> = ids.iter().take(max_results).cloned().collect();
+        // Scope isolation: only global memories plus the caller's own namespace
+        let found_ids: Vec<String> = ids
+            .iter()
+            .filter(|id| {
+                self.memories
+                    .get(*id)
+                    .map(|m| m.scope.visible_from(scope))
+                    .unwrap_or(false)
+            })
+            .take(max_results)
+            .cloned()
+            .collect();
 
         // Collect results first
         let results: Vec<AnchoredMemory> = found_ids
@@ -407,6 +487,7 @@ impl WaveMemoryManager {
         query_type: MemoryType,
         threshold: f32,
         max_results: usize,
+        scope: &MemoryScope,
     ) -> Vec<(AnchoredMemory, f32)> {
         // Create a query memory for comparison
         let (x, y, z) =
@@ -426,12 +507,14 @@ impl WaveMemoryManager {
             access_count: 0,
             origin: String::new(),
             project_path: None,
+            scope: scope.clone(),
         };
 
         // Calculate resonance with all memories and collect with IDs
         let mut resonances: Vec<(String, AnchoredMemory, f32)> = self
             .memories
             .values()
+            .filter(|mem| mem.scope.visible_from(scope))
             .map(|mem| (mem.id.clone(), mem.clone(), mem.resonance_with(&query)))
             .filter(|(_, _, r)| *r >= threshold)
             .collect();
@@ -485,9 +568,16 @@ impl WaveMemoryManager {
             0
         };
 
+        let scope_counts: HashMap<String, usize> =
+            self.memories.values().fold(HashMap::new(), |mut acc, mem| {
+                *acc.entry(mem.scope.describe()).or_default() += 1;
+                acc
+            });
+
         serde_json::json!({
             "total_memories": self.memories.len(),
             "active_waves": active_count,
+            "by_scope": scope_counts,
             "unique_keywords": self.keyword_index.keywords.len(),
             "by_type": type_counts,
             "storage_path": self.storage_path.display().to_string(),
@@ -507,7 +597,7 @@ impl WaveMemoryManager {
 
         // Serialize memories and index
         let data = serde_json::json!({
-            "version": 1,
+            "version": 2, // v2 added per-memory scopes
             "memories": self.memories,
             "keyword_index": self.keyword_index,
         });
@@ -543,6 +633,20 @@ impl WaveMemoryManager {
                 .context("Failed to deserialize memories")?;
         }
 
+        // Migrate pre-scope files: project memories keep their project,
+        // everything else becomes personal - nothing silently goes global
+        let version = data.get("version").and_then(|v| v.as_u64()).unwrap_or(1);
+        if version < 2 {
+            let user_scope = MemoryScope::current_user();
+            for memory in self.memories.values_mut() {
+                memory.scope = match &memory.project_path {
+                    Some(p) => MemoryScope::Project(p.clone()),
+                    None => user_scope.clone(),
+                };
+            }
+            self.dirty = true;
+        }
+
         // Load keyword index
         if let Some(index) = data.get("keyword_index") {
             self.keyword_index = serde_json::from_value(index.clone())
@@ -571,6 +675,115 @@ impl WaveMemoryManager {
         self.memories.get(id)
     }
 
+    /// Export all memories as JSONL - one memory per line, oldest first
+    ///
+    /// The schema is just the serialized [`AnchoredMemory`]: id, content,
+    /// keywords, memory_type, valence, arousal, x/y/z coordinates,
+    /// created_at, last_accessed, access_count, origin, project_path,
+    /// scope. Plain JSON means `jq`, spreadsheets, and future-you can all
+    /// read it - no binary lock-in.
+    pub fn export_jsonl(&self, writer: &mut dyn std::io::Write) -> Result<usize> {
+        let mut memories: Vec<&AnchoredMemory> = self.memories.values().collect();
+        memories.sort_by_key(|m| m.created_at);
+
+        for memory in &memories {
+            let line = serde_json::to_string(memory).context("Failed to serialize memory")?;
+            writeln!(writer, "{}", line).context("Failed to write JSONL line")?;
+        }
+        Ok(memories.len())
+    }
+
+    /// Import memories from JSONL - returns (imported, skipped)
+    ///
+    /// Lines are forgiving: only `content` is required. Missing ids get a
+    /// fresh UUID, missing coordinates are recomputed from the content,
+    /// and memories whose id already exists are skipped rather than
+    /// overwritten (your live memories win over the backup).
+    pub fn import_jsonl(&mut self, reader: &mut dyn std::io::BufRead) -> Result<(usize, usize)> {
+        #[derive(Deserialize)]
+        struct PortableMemory {
+            id: Option<String>,
+            content: String,
+            #[serde(default)]
+            keywords: Vec<String>,
+            memory_type: Option<String>,
+            #[serde(default)]
+            valence: f32,
+            arousal: Option<f32>,
+            created_at: Option<DateTime<Utc>>,
+            last_accessed: Option<DateTime<Utc>>,
+            access_count: Option<u32>,
+            origin: Option<String>,
+            project_path: Option<PathBuf>,
+            #[serde(default)]
+            scope: MemoryScope,
+        }
+
+        let mut imported = 0;
+        let mut skipped = 0;
+
+        for (line_no, line) in reader.lines().enumerate() {
+            let line = line.context("Failed to read JSONL line")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let portable: PortableMemory = serde_json::from_str(&line)
+                .with_context(|| format!("Invalid memory on line {}", line_no + 1))?;
+
+            let id = portable
+                .id
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            if self.memories.contains_key(&id) {
+                skipped += 1;
+                continue;
+            }
+
+            let memory_type = portable
+                .memory_type
+                .as_deref()
+                .map(MemoryType::parse)
+                .unwrap_or(MemoryType::Technical);
+            let (x, y, z) = AnchoredMemory::calculate_coordinates(
+                &portable.content,
+                &portable.keywords,
+                memory_type,
+            );
+
+            let memory = AnchoredMemory {
+                id: id.clone(),
+                content: portable.content,
+                keywords: portable.keywords.clone(),
+                memory_type,
+                valence: portable.valence.clamp(-1.0, 1.0),
+                arousal: portable.arousal.unwrap_or(0.5).clamp(0.0, 1.0),
+                x,
+                y,
+                z,
+                created_at: portable.created_at.unwrap_or_else(Utc::now),
+                last_accessed: portable.last_accessed.unwrap_or_else(Utc::now),
+                access_count: portable.access_count.unwrap_or(1),
+                origin: portable.origin.unwrap_or_else(|| "import".to_string()),
+                project_path: portable.project_path,
+                scope: portable.scope,
+            };
+
+            if let Ok(mut grid) = self.wave_grid.write() {
+                grid.store(x, y, z, memory.to_wave());
+            }
+            for keyword in &portable.keywords {
+                self.keyword_index.add(keyword, &id);
+            }
+            self.memories.insert(id, memory);
+            imported += 1;
+        }
+
+        if imported > 0 {
+            self.dirty = true;
+        }
+        Ok((imported, skipped))
+    }
+
     /// Delete a memory
     pub fn delete(&mut self, id: &str) -> bool {
         if let Some(memory) = self.memories.remove(id) {
@@ -632,13 +845,15 @@ mod tests {
                 0.7, // High arousal (exciting!)
                 "tandem:hue:claude".to_string(),
                 None,
+                MemoryScope::Global,
             )
             .unwrap();
 
         assert!(!id.is_empty());
 
         // Find by keywords
-        let results = manager.find_by_keywords(&["auth".to_string(), "jwt".to_string()], 10);
+        let results =
+            manager.find_by_keywords(&["auth".to_string(), "jwt".to_string()], 10, &MemoryScope::Global);
         assert_eq!(results.len(), 1);
         assert!(results[0].content.contains("JWT refresh tokens"));
     }
@@ -658,6 +873,7 @@ mod tests {
                 0.5,
                 "tandem:hue:claude".to_string(),
                 None,
+                MemoryScope::Global,
             )
             .unwrap();
 
@@ -674,6 +890,7 @@ mod tests {
                 0.4,
                 "tandem:hue:claude".to_string(),
                 None,
+                MemoryScope::Global,
             )
             .unwrap();
 
@@ -684,6 +901,7 @@ mod tests {
             MemoryType::Technical,
             0.3, // threshold
             10,
+            &MemoryScope::Global,
         );
 
         // Should find the Rust memory with higher resonance
@@ -707,6 +925,7 @@ mod tests {
                     1.0,
                     "tandem:hue:claude".to_string(),
                     None,
+                    MemoryScope::Global,
                 )
                 .unwrap();
             manager.save().unwrap();
@@ -715,12 +934,61 @@ mod tests {
         // Load and verify
         {
             let mut manager = WaveMemoryManager::new_test(Some(dir.path()));
-            let results = manager.find_by_keywords(&["elvis".to_string()], 10);
+            let results = manager.find_by_keywords(&["elvis".to_string()], 10, &MemoryScope::Global);
             assert_eq!(results.len(), 1);
             assert!(results[0].content.contains("Elvis"));
         }
     }
 
+    #[test]
+    fn test_scope_isolation() {
+        let dir = tempdir().unwrap();
+        let mut manager = WaveMemoryManager::new_test(Some(dir.path()));
+
+        let anchor = |manager: &mut WaveMemoryManager, content: &str, scope: MemoryScope| {
+            manager
+                .anchor(
+                    content.to_string(),
+                    vec!["secret".to_string()],
+                    MemoryType::Technical,
+                    0.0,
+                    0.5,
+                    "test".to_string(),
+                    None,
+                    scope,
+                )
+                .unwrap();
+        };
+
+        anchor(&mut manager, "shared wisdom", MemoryScope::Global);
+        anchor(
+            &mut manager,
+            "hue's private note",
+            MemoryScope::User("hue".to_string()),
+        );
+        anchor(
+            &mut manager,
+            "project convention",
+            MemoryScope::Project(PathBuf::from("/proj/a")),
+        );
+
+        // Another user sees global only - no leaked personal context
+        let other = MemoryScope::User("trish".to_string());
+        let results = manager.find_by_keywords(&["secret".to_string()], 10, &other);
+        assert_eq!(results.len(), 1);
+        assert!(results[0].content.contains("shared"));
+
+        // The owner sees global + their own
+        let hue = MemoryScope::User("hue".to_string());
+        let results = manager.find_by_keywords(&["secret".to_string()], 10, &hue);
+        assert_eq!(results.len(), 2);
+
+        // Project scope sees global + that project
+        let proj = MemoryScope::Project(PathBuf::from("/proj/a"));
+        let results = manager.find_by_keywords(&["secret".to_string()], 10, &proj);
+        assert_eq!(results.len(), 2);
+    }
+
     #[test]
     fn test_memory_types_to_frequencies() {
         assert!(MemoryType::Pattern.frequency() < MemoryType::Solution.frequency());
@@ -0,0 +1,218 @@
+//! WebSocket transport for watch events
+//!
+//! The SSE stream suits browsers, but most agent tooling prefers a
+//! WebSocket it can also talk back on. This endpoint streams the exact
+//! same event schema as `watch_directory_sse` ([`super::sse::SseEvent`],
+//! one JSON text frame per event) and accepts control frames mid-stream:
+//!
+//! ```json
+//! {"type": "start", "path": ".", "format": "ai", "categories": ["source"]}
+//! {"type": "pause"}
+//! {"type": "resume"}
+//! {"type": "set_filters", "categories": ["source", "tests"], "exclude_patterns": ["*.log"]}
+//! {"type": "close"}
+//! ```
+//!
+//! The first frame must be `start` (same fields as watch_directory_sse
+//! arguments); every control frame is acknowledged with
+//! `{"type": "ack", "control": "..."}`. Filter updates apply immediately -
+//! events are re-filtered at this layer, so the underlying watcher never
+//! restarts. Pausing drops events instead of buffering them; a resumed
+//! client should expect a gap, not a replay.
+
+use super::sse::{handle_sse_stream, SseEvent};
+use super::tools::definitions::WatchDirectorySseArgs;
+use super::tools::sse_tools::build_sse_config;
+use super::McpContext;
+use axum::extract::ws::{Message, WebSocket};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Client-to-server control frames
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WatchControl {
+    Pause,
+    Resume,
+    SetFilters {
+        #[serde(default)]
+        categories: Option<Vec<String>>,
+        #[serde(default)]
+        include_patterns: Option<Vec<String>>,
+        #[serde(default)]
+        exclude_patterns: Option<Vec<String>>,
+    },
+    Close,
+}
+
+/// Live per-connection filter state, adjustable mid-stream
+struct SessionFilters {
+    categories: crate::semantic::CategoryFilter,
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl SessionFilters {
+    fn new(categories: &[String], include: &[String], exclude: &[String]) -> Self {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .filter_map(|p| glob::Pattern::new(p).ok())
+                .collect()
+        };
+        Self {
+            categories: crate::semantic::CategoryFilter::new(categories),
+            include: compile(include),
+            exclude: compile(exclude),
+        }
+    }
+
+    fn allows(&self, path: &str) -> bool {
+        let path = Path::new(path);
+        if !self.categories.allows(path) {
+            return false;
+        }
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if self.exclude.iter().any(|p| p.matches(name)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|p| p.matches(name))
+    }
+
+    /// Which paths an event concerns, for filtering; events without a
+    /// path (heartbeats, batch summaries, errors) always pass
+    fn allows_event(&self, event: &SseEvent) -> bool {
+        match event {
+            SseEvent::Created { path, .. }
+            | SseEvent::Modified { path, .. }
+            | SseEvent::Deleted { path } => self.allows(path),
+            SseEvent::Renamed { from, to } => self.allows(from) || self.allows(to),
+            _ => true,
+        }
+    }
+}
+
+/// Drive one WebSocket watch session to completion
+pub async fn handle_ws_watch(mut socket: WebSocket, ctx: Arc<McpContext>) {
+    // First frame must be the start message
+    let args: WatchDirectorySseArgs = loop {
+        match socket.recv().await {
+            Some(Ok(Message::Text(text))) => match serde_json::from_str(&text) {
+                Ok(args) => break args,
+                Err(e) => {
+                    let _ = send_event(
+                        &mut socket,
+                        &SseEvent::Error {
+                            message: format!("Invalid start message: {}", e),
+                        },
+                    )
+                    .await;
+                    return;
+                }
+            },
+            Some(Ok(Message::Ping(data))) => {
+                let _ = socket.send(Message::Pong(data)).await;
+            }
+            Some(Ok(_)) => continue,
+            _ => return,
+        }
+    };
+
+    let config = build_sse_config(&args);
+    let mut filters = SessionFilters::new(
+        &config.categories,
+        &config.include_patterns,
+        &config.exclude_patterns,
+    );
+
+    // The same stream SSE clients get - schema parity is the whole point
+    let mut events = match handle_sse_stream(config, ctx).await {
+        Ok(stream) => Box::pin(stream),
+        Err(e) => {
+            let _ = send_event(
+                &mut socket,
+                &SseEvent::Error {
+                    message: e.to_string(),
+                },
+            )
+            .await;
+            return;
+        }
+    };
+
+    let mut paused = false;
+    // Current filter sources, so a partial set_filters only touches what
+    // it mentions
+    let (mut cur_categories, mut cur_include, mut cur_exclude) = (
+        args.categories.clone(),
+        args.include_patterns.clone(),
+        args.exclude_patterns.clone(),
+    );
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(Ok(event)) = event else { break };
+                // Heartbeats flow even while paused so the link stays warm
+                let is_heartbeat = matches!(event, SseEvent::Heartbeat);
+                if (paused && !is_heartbeat) || !filters.allows_event(&event) {
+                    continue;
+                }
+                if send_event(&mut socket, &event).await.is_err() {
+                    break;
+                }
+            }
+            msg = socket.recv() => {
+                let control = match msg {
+                    Some(Ok(Message::Text(text))) => match serde_json::from_str(&text) {
+                        Ok(control) => control,
+                        Err(e) => {
+                            let _ = send_event(&mut socket, &SseEvent::Error {
+                                message: format!("Invalid control message: {}", e),
+                            }).await;
+                            continue;
+                        }
+                    },
+                    Some(Ok(Message::Ping(data))) => {
+                        let _ = socket.send(Message::Pong(data)).await;
+                        continue;
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) => break,
+                };
+
+                let ack = match &control {
+                    WatchControl::Pause => { paused = true; "pause" }
+                    WatchControl::Resume => { paused = false; "resume" }
+                    WatchControl::SetFilters { categories, include_patterns, exclude_patterns } => {
+                        // Unmentioned filter kinds keep their current value
+                        if let Some(categories) = categories {
+                            cur_categories = categories.clone();
+                        }
+                        if let Some(include) = include_patterns {
+                            cur_include = include.clone();
+                        }
+                        if let Some(exclude) = exclude_patterns {
+                            cur_exclude = exclude.clone();
+                        }
+                        filters = SessionFilters::new(&cur_categories, &cur_include, &cur_exclude);
+                        "set_filters"
+                    }
+                    WatchControl::Close => break,
+                };
+                let ack = json!({ "type": "ack", "control": ack });
+                if socket.send(Message::Text(ack.to_string())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn send_event(socket: &mut WebSocket, event: &SseEvent) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(event).unwrap_or_else(|_| "{}".to_string());
+    socket.send(Message::Text(text)).await
+}
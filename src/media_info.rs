@@ -0,0 +1,642 @@
+// -----------------------------------------------------------------------------
+// 🎞️ MEDIA INFO - Lightweight Metadata Sniffing for Asset-Heavy Trees!
+// -----------------------------------------------------------------------------
+// Opt-in (--media-info) probing of common media formats: image dimensions,
+// an EXIF capture date when a JPEG carries one, and audio/video duration.
+// Everything here reads container HEADERS only - we never decode a single
+// pixel or audio sample, so a directory full of 4K footage probes in
+// milliseconds. Full codec identification (symphonia, image, ffmpeg bindings)
+// is deliberately out of scope; if we ever want it, it goes behind a cargo
+// feature so the default build stays lean.
+//
+// Hue gets "how many hours of footage is this?", Trish gets neat dimension
+// columns in ls mode, and Aye gets to write byte-level parsers. Everyone wins!
+// -----------------------------------------------------------------------------
+
+use crate::scanner::FileNode;
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// How much of a file we read when the format keeps its metadata up front.
+/// JPEGs can bury the SOF marker behind large thumbnails, hence 256KB.
+const MAX_PROBE_BYTES: usize = 256 * 1024;
+
+/// Cap on the `moov` box we load when probing MP4 duration - the box is
+/// usually a few hundred KB even for feature-length files.
+const MAX_MOOV_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Image extensions we know how to measure.
+const IMAGE_EXTS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "webp"];
+/// Audio extensions we know how to time.
+const AUDIO_EXTS: &[&str] = &["wav", "flac", "mp3", "m4a"];
+/// Video extensions we know how to time.
+const VIDEO_EXTS: &[&str] = &["mp4", "mov", "m4v"];
+
+/// Metadata extracted from a single media file. Every field is optional -
+/// a truncated or exotic file simply yields fewer facts, never an error.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaInfo {
+    /// Pixel dimensions as (width, height) for image formats
+    pub dimensions: Option<(u32, u32)>,
+    /// Playback length in seconds for audio/video formats
+    pub duration_secs: Option<f64>,
+    /// Best-effort codec/container label ("pcm", "mp3", container brand)
+    pub codec: Option<String>,
+    /// EXIF DateTime ("YYYY:MM:DD HH:MM:SS") when a JPEG carries one
+    pub exif_date: Option<String>,
+}
+
+impl MediaInfo {
+    /// True when the probe learned nothing worth printing.
+    pub fn is_empty(&self) -> bool {
+        self.dimensions.is_none()
+            && self.duration_secs.is_none()
+            && self.codec.is_none()
+            && self.exif_date.is_none()
+    }
+
+    /// One-line human summary, e.g. "1920x1080, 2023:07:09 14:56:01"
+    /// or "3m42s, mp3". Used as the ls-mode column.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some((w, h)) = self.dimensions {
+            parts.push(format!("{}x{}", w, h));
+        }
+        if let Some(secs) = self.duration_secs {
+            parts.push(format_duration(secs));
+        }
+        if let Some(codec) = &self.codec {
+            parts.push(codec.clone());
+        }
+        if let Some(date) = &self.exif_date {
+            parts.push(date.clone());
+        }
+        parts.join(", ")
+    }
+}
+
+/// Aggregate media totals for the stats report.
+#[derive(Debug, Clone, Default)]
+pub struct MediaSummary {
+    /// Image files probed successfully
+    pub images: u64,
+    /// Audio files with a recoverable duration
+    pub audio_files: u64,
+    /// Video files with a recoverable duration
+    pub video_files: u64,
+    /// Summed audio playback time in seconds
+    pub audio_secs: f64,
+    /// Summed video playback time in seconds
+    pub video_secs: f64,
+}
+
+impl MediaSummary {
+    pub fn is_empty(&self) -> bool {
+        self.images == 0 && self.audio_files == 0 && self.video_files == 0
+    }
+
+    fn merge(mut self, other: MediaSummary) -> MediaSummary {
+        self.images += other.images;
+        self.audio_files += other.audio_files;
+        self.video_files += other.video_files;
+        self.audio_secs += other.audio_secs;
+        self.video_secs += other.video_secs;
+        self
+    }
+}
+
+/// "3m42s" for anything over a minute, "7.3s" below it.
+pub fn format_duration(secs: f64) -> String {
+    if secs >= 60.0 {
+        let total = secs.round() as u64;
+        format!("{}m{:02}s", total / 60, total % 60)
+    } else {
+        format!("{:.1}s", secs)
+    }
+}
+
+/// Probe a single file, dispatching on extension. Returns `None` for
+/// non-media extensions, unreadable files, and headers we don't recognize.
+pub fn probe(path: &Path) -> Option<MediaInfo> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let info = match ext.as_str() {
+        "png" | "gif" | "bmp" | "webp" | "jpg" | "jpeg" => {
+            let buf = read_prefix(path)?;
+            match ext.as_str() {
+                "png" => MediaInfo {
+                    dimensions: png_dimensions(&buf),
+                    ..Default::default()
+                },
+                "gif" => MediaInfo {
+                    dimensions: gif_dimensions(&buf),
+                    ..Default::default()
+                },
+                "bmp" => MediaInfo {
+                    dimensions: bmp_dimensions(&buf),
+                    ..Default::default()
+                },
+                "webp" => MediaInfo {
+                    dimensions: webp_dimensions(&buf),
+                    ..Default::default()
+                },
+                _ => MediaInfo {
+                    dimensions: jpeg_dimensions(&buf),
+                    exif_date: jpeg_exif_date(&buf),
+                    ..Default::default()
+                },
+            }
+        }
+        "wav" => {
+            let buf = read_prefix(path)?;
+            wav_info(&buf, std::fs::metadata(path).ok()?.len())?
+        }
+        "flac" => {
+            let buf = read_prefix(path)?;
+            flac_info(&buf)?
+        }
+        "mp3" => {
+            let buf = read_prefix(path)?;
+            mp3_info(&buf, std::fs::metadata(path).ok()?.len())?
+        }
+        "mp4" | "m4a" | "mov" | "m4v" => {
+            let mut file = File::open(path).ok()?;
+            mp4_info(&mut file)?
+        }
+        _ => return None,
+    };
+    if info.is_empty() {
+        None
+    } else {
+        Some(info)
+    }
+}
+
+/// Walk the scan results and total up media facts, in parallel like the
+/// content hasher. Only files with known media extensions are touched.
+pub fn summarize(nodes: &[FileNode]) -> MediaSummary {
+    nodes
+        .par_iter()
+        .filter(|node| !node.is_dir && !node.is_symlink && !node.permission_denied)
+        .filter_map(|node| {
+            let ext = node.path.extension()?.to_str()?.to_ascii_lowercase();
+            let mut summary = MediaSummary::default();
+            if IMAGE_EXTS.contains(&ext.as_str()) {
+                if probe(&node.path)?.dimensions.is_some() {
+                    summary.images = 1;
+                }
+            } else if AUDIO_EXTS.contains(&ext.as_str()) {
+                summary.audio_secs = probe(&node.path)?.duration_secs?;
+                summary.audio_files = 1;
+            } else if VIDEO_EXTS.contains(&ext.as_str()) {
+                summary.video_secs = probe(&node.path)?.duration_secs?;
+                summary.video_files = 1;
+            } else {
+                return None;
+            }
+            Some(summary)
+        })
+        .reduce(MediaSummary::default, MediaSummary::merge)
+}
+
+/// Read up to MAX_PROBE_BYTES from the front of the file.
+fn read_prefix(path: &Path) -> Option<Vec<u8>> {
+    let file = File::open(path).ok()?;
+    let mut buf = Vec::new();
+    file.take(MAX_PROBE_BYTES as u64).read_to_end(&mut buf).ok()?;
+    if buf.is_empty() {
+        None
+    } else {
+        Some(buf)
+    }
+}
+
+fn be32(buf: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(buf.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le32(buf: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(buf.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be16(buf: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(buf.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le16(buf: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(buf.get(at..at + 2)?.try_into().ok()?))
+}
+
+/// PNG: IHDR is mandatory and always first, so width/height sit at fixed
+/// offsets right after the 8-byte signature and chunk header.
+fn png_dimensions(buf: &[u8]) -> Option<(u32, u32)> {
+    if !buf.starts_with(b"\x89PNG\r\n\x1a\n") || buf.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((be32(buf, 16)?, be32(buf, 20)?))
+}
+
+/// GIF: logical screen size lives in the fixed header.
+fn gif_dimensions(buf: &[u8]) -> Option<(u32, u32)> {
+    if !buf.starts_with(b"GIF87a") && !buf.starts_with(b"GIF89a") {
+        return None;
+    }
+    Some((le16(buf, 6)? as u32, le16(buf, 8)? as u32))
+}
+
+/// BMP: BITMAPINFOHEADER width/height; height may be negative (top-down).
+fn bmp_dimensions(buf: &[u8]) -> Option<(u32, u32)> {
+    if !buf.starts_with(b"BM") {
+        return None;
+    }
+    let width = le32(buf, 18)? as i32;
+    let height = le32(buf, 22)? as i32;
+    Some((width.unsigned_abs(), height.unsigned_abs()))
+}
+
+/// WebP: first chunk after the RIFF header is VP8X (extended), VP8L
+/// (lossless), or VP8 (lossy) - each encodes dimensions differently.
+fn webp_dimensions(buf: &[u8]) -> Option<(u32, u32)> {
+    if !buf.starts_with(b"RIFF") || buf.get(8..12)? != b"WEBP" {
+        return None;
+    }
+    match buf.get(12..16)? {
+        b"VP8X" => {
+            // 24-bit little-endian width-1 / height-1
+            let b = buf.get(24..30)?;
+            let w = 1 + (b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16);
+            let h = 1 + (b[3] as u32 | (b[4] as u32) << 8 | (b[5] as u32) << 16);
+            Some((w, h))
+        }
+        b"VP8L" => {
+            // 1-byte signature then 14-bit width-1 / height-1 bit-packed
+            let b = buf.get(21..25)?;
+            let w = 1 + ((b[0] as u32) | ((b[1] as u32 & 0x3F) << 8));
+            let h = 1 + ((b[1] as u32 >> 6) | ((b[2] as u32) << 2) | ((b[3] as u32 & 0x0F) << 10));
+            Some((w, h))
+        }
+        b"VP8 " => {
+            // 3-byte frame tag, 3-byte start code, then 14-bit dimensions
+            if buf.get(23..26)? != b"\x9d\x01\x2a" {
+                return None;
+            }
+            Some((
+                (le16(buf, 26)? & 0x3FFF) as u32,
+                (le16(buf, 28)? & 0x3FFF) as u32,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// JPEG: walk the marker segments until a start-of-frame marker carries
+/// the dimensions. Skips past embedded thumbnails and metadata blobs.
+fn jpeg_dimensions(buf: &[u8]) -> Option<(u32, u32)> {
+    if !buf.starts_with(b"\xFF\xD8") {
+        return None;
+    }
+    let mut pos = 2;
+    while pos + 4 <= buf.len() {
+        if buf[pos] != 0xFF {
+            return None;
+        }
+        let marker = buf[pos + 1];
+        match marker {
+            // Padding between segments
+            0xFF => {
+                pos += 1;
+                continue;
+            }
+            // SOF0-SOF15 except the huffman/arithmetic table markers
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let height = be16(buf, pos + 5)? as u32;
+                let width = be16(buf, pos + 7)? as u32;
+                return Some((width, height));
+            }
+            _ => {
+                let len = be16(buf, pos + 2)? as usize;
+                pos += 2 + len;
+            }
+        }
+    }
+    None
+}
+
+/// Pull the EXIF DateTime tag (0x0132) out of a JPEG's APP1 segment.
+/// This is a minimal IFD0 walk - enough for "when was this taken".
+fn jpeg_exif_date(buf: &[u8]) -> Option<String> {
+    if !buf.starts_with(b"\xFF\xD8") {
+        return None;
+    }
+    let mut pos = 2;
+    while pos + 4 <= buf.len() {
+        if buf[pos] != 0xFF {
+            return None;
+        }
+        let marker = buf[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        let len = be16(buf, pos + 2)? as usize;
+        if marker == 0xE1 && buf.get(pos + 4..pos + 10)? == b"Exif\0\0" {
+            let tiff = buf.get(pos + 10..pos + 2 + len)?;
+            return tiff_datetime(tiff);
+        }
+        // Stop once compressed image data starts
+        if marker == 0xDA {
+            return None;
+        }
+        pos += 2 + len;
+    }
+    None
+}
+
+/// Scan a TIFF blob's IFD0 for the ASCII DateTime tag.
+fn tiff_datetime(tiff: &[u8]) -> Option<String> {
+    let little = match tiff.get(0..4)? {
+        b"II\x2a\x00" => true,
+        b"MM\x00\x2a" => false,
+        _ => return None,
+    };
+    let read16 = |at: usize| if little { le16(tiff, at) } else { be16(tiff, at) };
+    let read32 = |at: usize| if little { le32(tiff, at) } else { be32(tiff, at) };
+
+    let ifd = read32(4)? as usize;
+    let entries = read16(ifd)? as usize;
+    for i in 0..entries {
+        let entry = ifd + 2 + i * 12;
+        if read16(entry)? == 0x0132 {
+            // ASCII, count includes the NUL terminator
+            let count = read32(entry + 4)? as usize;
+            let offset = read32(entry + 8)? as usize;
+            let raw = tiff.get(offset..offset + count.min(20))?;
+            let text = std::str::from_utf8(raw).ok()?.trim_end_matches('\0');
+            return Some(text.to_string());
+        }
+    }
+    None
+}
+
+/// WAV: duration = data chunk size / byte rate from the fmt chunk.
+fn wav_info(buf: &[u8], file_len: u64) -> Option<MediaInfo> {
+    if !buf.starts_with(b"RIFF") || buf.get(8..12)? != b"WAVE" {
+        return None;
+    }
+    let mut byte_rate = None;
+    let mut codec = None;
+    let mut data_size = None;
+    let mut pos = 12;
+    while pos + 8 <= buf.len() {
+        let id = buf.get(pos..pos + 4)?;
+        let size = le32(buf, pos + 4)? as usize;
+        match id {
+            b"fmt " => {
+                codec = Some(match le16(buf, pos + 8)? {
+                    1 => "pcm".to_string(),
+                    3 => "pcm-float".to_string(),
+                    other => format!("wav-fmt-{}", other),
+                });
+                byte_rate = Some(le32(buf, pos + 16)?);
+            }
+            b"data" => {
+                // The data chunk is usually last and larger than our probe
+                // window - its declared size is all we need
+                data_size = Some((size as u64).min(file_len));
+            }
+            _ => {}
+        }
+        if byte_rate.is_some() && data_size.is_some() {
+            break;
+        }
+        pos += 8 + size + (size & 1);
+    }
+    let rate = byte_rate.filter(|r| *r > 0)?;
+    Some(MediaInfo {
+        duration_secs: Some(data_size? as f64 / rate as f64),
+        codec,
+        ..Default::default()
+    })
+}
+
+/// FLAC: STREAMINFO is the mandatory first metadata block and carries the
+/// sample rate plus total sample count.
+fn flac_info(buf: &[u8]) -> Option<MediaInfo> {
+    if !buf.starts_with(b"fLaC") || buf.get(4)? & 0x7F != 0 {
+        return None;
+    }
+    let info = buf.get(8..42)?;
+    let rate = ((info[10] as u32) << 12) | ((info[11] as u32) << 4) | ((info[12] as u32) >> 4);
+    let samples = (((info[13] & 0x0F) as u64) << 32)
+        | ((info[14] as u64) << 24)
+        | ((info[15] as u64) << 16)
+        | ((info[16] as u64) << 8)
+        | (info[17] as u64);
+    if rate == 0 || samples == 0 {
+        return None;
+    }
+    Some(MediaInfo {
+        duration_secs: Some(samples as f64 / rate as f64),
+        codec: Some("flac".to_string()),
+        ..Default::default()
+    })
+}
+
+/// MPEG-1/2 Layer III bitrate table, kbps, indexed by the frame header.
+const MP3_BITRATES_V1: [u32; 16] = [
+    0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 0,
+];
+const MP3_BITRATES_V2: [u32; 16] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160, 0];
+
+/// MP3: estimate duration from the first frame's bitrate and the audio
+/// payload size. Exact for CBR, approximate for VBR - good enough for a
+/// directory summary.
+fn mp3_info(buf: &[u8], file_len: u64) -> Option<MediaInfo> {
+    let mut audio_start = 0u64;
+    let mut pos = 0usize;
+    // Skip an ID3v2 tag (syncsafe 28-bit size at offset 6)
+    if buf.starts_with(b"ID3") {
+        let size = buf.get(6..10)?.iter().fold(0u32, |acc, b| (acc << 7) | (*b & 0x7F) as u32);
+        pos = 10 + size as usize;
+        audio_start = pos as u64;
+    }
+    // Hunt for the frame sync within our probe window
+    while pos + 4 <= buf.len() {
+        if buf[pos] == 0xFF && buf[pos + 1] & 0xE0 == 0xE0 {
+            let version_v1 = buf[pos + 1] & 0x18 == 0x18;
+            let layer3 = buf[pos + 1] & 0x06 == 0x02;
+            let bitrate_index = (buf[pos + 2] >> 4) as usize;
+            if layer3 {
+                let table = if version_v1 { MP3_BITRATES_V1 } else { MP3_BITRATES_V2 };
+                let kbps = table[bitrate_index];
+                if kbps > 0 {
+                    let payload = file_len.saturating_sub(audio_start);
+                    return Some(MediaInfo {
+                        duration_secs: Some(payload as f64 * 8.0 / (kbps as f64 * 1000.0)),
+                        codec: Some("mp3".to_string()),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        pos += 1;
+    }
+    None
+}
+
+/// MP4/MOV: walk top-level boxes for `ftyp` (brand → codec label) and
+/// `moov`, then read the `mvhd` child for timescale and duration. The moov
+/// box often sits at the end of the file, hence seeking instead of a
+/// prefix read.
+fn mp4_info<R: Read + Seek>(reader: &mut R) -> Option<MediaInfo> {
+    let file_len = reader.seek(SeekFrom::End(0)).ok()?;
+    reader.seek(SeekFrom::Start(0)).ok()?;
+    let mut codec = None;
+    let mut duration = None;
+    let mut pos = 0u64;
+    while pos + 8 <= file_len {
+        let mut header = [0u8; 8];
+        reader.seek(SeekFrom::Start(pos)).ok()?;
+        reader.read_exact(&mut header).ok()?;
+        let mut size = u32::from_be_bytes(header[0..4].try_into().ok()?) as u64;
+        let box_type = [header[4], header[5], header[6], header[7]];
+        let mut body_start = pos + 8;
+        if size == 1 {
+            let mut large = [0u8; 8];
+            reader.read_exact(&mut large).ok()?;
+            size = u64::from_be_bytes(large);
+            body_start = pos + 16;
+        } else if size == 0 {
+            size = file_len - pos;
+        }
+        if size < 8 {
+            return None;
+        }
+        match &box_type {
+            b"ftyp" => {
+                let mut brand = [0u8; 4];
+                reader.read_exact(&mut brand).ok()?;
+                codec = std::str::from_utf8(&brand)
+                    .ok()
+                    .map(|b| b.trim().to_string());
+            }
+            b"moov" => {
+                let body_len = (size - (body_start - pos)).min(MAX_MOOV_BYTES);
+                let mut body = vec![0u8; body_len as usize];
+                reader.seek(SeekFrom::Start(body_start)).ok()?;
+                reader.read_exact(&mut body).ok()?;
+                duration = moov_duration(&body);
+            }
+            _ => {}
+        }
+        pos += size;
+    }
+    if codec.is_none() && duration.is_none() {
+        return None;
+    }
+    Some(MediaInfo {
+        duration_secs: duration,
+        codec,
+        ..Default::default()
+    })
+}
+
+/// Find the mvhd child inside a moov box body and divide duration by
+/// timescale. Handles both version 0 (32-bit) and version 1 (64-bit).
+fn moov_duration(body: &[u8]) -> Option<f64> {
+    let mut pos = 0usize;
+    while pos + 8 <= body.len() {
+        let size = be32(body, pos)? as usize;
+        if size < 8 {
+            return None;
+        }
+        if body.get(pos + 4..pos + 8)? == b"mvhd" {
+            let payload = pos + 8;
+            let (timescale, duration) = match body.get(payload)? {
+                0 => (be32(body, payload + 12)? as u64, be32(body, payload + 16)? as u64),
+                1 => {
+                    let ts = be32(body, payload + 20)? as u64;
+                    let hi = be32(body, payload + 24)? as u64;
+                    let lo = be32(body, payload + 28)? as u64;
+                    (ts, (hi << 32) | lo)
+                }
+                _ => return None,
+            };
+            if timescale == 0 {
+                return None;
+            }
+            return Some(duration as f64 / timescale as f64);
+        }
+        pos += size;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_dimensions() {
+        let mut buf = b"\x89PNG\r\n\x1a\n".to_vec();
+        buf.extend_from_slice(&13u32.to_be_bytes());
+        buf.extend_from_slice(b"IHDR");
+        buf.extend_from_slice(&1920u32.to_be_bytes());
+        buf.extend_from_slice(&1080u32.to_be_bytes());
+        assert_eq!(png_dimensions(&buf), Some((1920, 1080)));
+        assert_eq!(png_dimensions(b"not a png"), None);
+    }
+
+    #[test]
+    fn test_gif_and_bmp_dimensions() {
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&640u16.to_le_bytes());
+        gif.extend_from_slice(&480u16.to_le_bytes());
+        assert_eq!(gif_dimensions(&gif), Some((640, 480)));
+
+        let mut bmp = vec![0u8; 30];
+        bmp[0] = b'B';
+        bmp[1] = b'M';
+        bmp[18..22].copy_from_slice(&800i32.to_le_bytes());
+        // Negative height means top-down rows, not a negative image
+        bmp[22..26].copy_from_slice(&(-600i32).to_le_bytes());
+        assert_eq!(bmp_dimensions(&bmp), Some((800, 600)));
+    }
+
+    #[test]
+    fn test_jpeg_sof_scan() {
+        // SOI, APP0 stub, then SOF0 with 120x80
+        let mut buf = b"\xFF\xD8".to_vec();
+        buf.extend_from_slice(b"\xFF\xE0\x00\x04\x00\x00");
+        buf.extend_from_slice(b"\xFF\xC0\x00\x11\x08");
+        buf.extend_from_slice(&80u16.to_be_bytes());
+        buf.extend_from_slice(&120u16.to_be_bytes());
+        assert_eq!(jpeg_dimensions(&buf), Some((120, 80)));
+    }
+
+    #[test]
+    fn test_wav_duration() {
+        // 16-bit stereo 44.1kHz: byte rate 176400, one second of data
+        let mut buf = b"RIFF\x00\x00\x00\x00WAVE".to_vec();
+        buf.extend_from_slice(b"fmt ");
+        buf.extend_from_slice(&16u32.to_le_bytes());
+        buf.extend_from_slice(&1u16.to_le_bytes()); // pcm
+        buf.extend_from_slice(&2u16.to_le_bytes()); // channels
+        buf.extend_from_slice(&44100u32.to_le_bytes());
+        buf.extend_from_slice(&176400u32.to_le_bytes());
+        buf.extend_from_slice(&4u16.to_le_bytes());
+        buf.extend_from_slice(&16u16.to_le_bytes());
+        buf.extend_from_slice(b"data");
+        buf.extend_from_slice(&176400u32.to_le_bytes());
+        let info = wav_info(&buf, 1_000_000).unwrap();
+        assert_eq!(info.codec.as_deref(), Some("pcm"));
+        assert!((info.duration_secs.unwrap() - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(7.26), "7.3s");
+        assert_eq!(format_duration(222.4), "3m42s");
+    }
+}
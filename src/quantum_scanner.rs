@@ -3,11 +3,12 @@
 
 #![allow(dead_code)] // Many constants and fields are reserved for future use
 
+use crate::scanner::{FileNode, Scanner, ScannerConfig, TreeStats};
 use anyhow::Result;
 use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 // Token ranges as suggested
@@ -338,5 +339,150 @@ impl<W: Write> QuantumScanner<W> {
     }
 }
 
+// -----------------------------------------------------------------------------
+// 🚀 NATIVE NODE WALK - The quantum walker, now for everyone!
+//
+// The byte-emitting scanner above speaks only quantum format. This section
+// lets the same native recursion (plain `fs::read_dir`, no walkdir layer)
+// produce the `FileNode` stream every formatter already understands, with
+// the classic scanner's own rule engine making the ignore/filter/sort
+// decisions - so the two backends can't drift apart. The daemon picks this
+// path by default for large scans; `--classic-walker` forces the old one.
+// -----------------------------------------------------------------------------
+
+/// Entry count past which a scan counts as "large" and defaults to the
+/// native walker.
+pub const LARGE_SCAN_ENTRIES: u64 = 10_000;
+
+/// Can the native walker serve this request with full parity?
+///
+/// Anything outside this set (content search, archives, smart scanning,
+/// symlink following, ...) still goes through the classic walkdir scanner,
+/// which remains the reference implementation for those features.
+pub fn supports(config: &ScannerConfig) -> bool {
+    !config.search_active()
+        && config.compare_state.is_none()
+        && !config.show_ignored
+        && !config.follow_symlinks
+        && !config.scan_archives
+        && !config.compute_interest
+        && !config.security_scan
+        && !config.smart_mode
+        && !config.track_traversal
+        && !config.changes_only
+        && !config.compute_dir_sizes
+        && !config.list_ads
+}
+
+/// Cheap pre-flight probe: walk until `LARGE_SCAN_ENTRIES` entries have been
+/// seen or the tree runs out, whichever comes first. Large trees hit the
+/// threshold almost immediately, so the probe cost stays bounded.
+pub fn looks_large(root: &Path) -> bool {
+    fn count(dir: &Path, seen: &mut u64) {
+        if *seen >= LARGE_SCAN_ENTRIES {
+            return;
+        }
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            *seen += 1;
+            if *seen >= LARGE_SCAN_ENTRIES {
+                return;
+            }
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                count(&entry.path(), seen);
+            }
+        }
+    }
+
+    let mut seen = 0;
+    count(root, &mut seen);
+    seen >= LARGE_SCAN_ENTRIES
+}
+
+/// Scan natively and return the same `(nodes, stats)` pair `Scanner::scan`
+/// produces. Ignore rules, filters, sorting, and node construction are all
+/// delegated to the classic scanner's engine - only the walk itself differs.
+pub fn scan_nodes(root: &Path, config: ScannerConfig) -> Result<(Vec<FileNode>, TreeStats)> {
+    let rules = Scanner::new(root, config.clone())?;
+    let mut collected = Vec::new();
+    let mut pruned_stats = TreeStats::default();
+    walk_native(&rules, &config, root, 0, &mut collected, &mut pruned_stats)?;
+
+    // Same post-processing the classic scan() applies.
+    let (nodes, mut stats) = if rules.has_active_filters() {
+        rules.filter_nodes_and_calculate_stats(collected)
+    } else {
+        let mut stats = TreeStats::default();
+        for node in &collected {
+            if !node.permission_denied || node.is_dir {
+                stats.update_file(node);
+            }
+        }
+        (collected, stats)
+    };
+    stats.pruned = pruned_stats.pruned;
+
+    Ok((rules.apply_sorting_and_limit(nodes), stats))
+}
+
+/// Depth-first recursion in sorted name order (matching the quantum
+/// emitter's deterministic ordering and walkdir's parent-before-child
+/// contract, which tree formatters rely on).
+fn walk_native(
+    rules: &Scanner,
+    config: &ScannerConfig,
+    path: &Path,
+    depth: usize,
+    nodes: &mut Vec<FileNode>,
+    pruned_stats: &mut TreeStats,
+) -> Result<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => {
+            nodes.push(rules.create_permission_denied_node(path, depth));
+            return Ok(());
+        }
+    };
+
+    let is_hidden = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'));
+    if is_hidden && !config.show_hidden {
+        return Ok(());
+    }
+
+    // `supports` rules out show_ignored, so pruning is always a plain skip.
+    if let Some(rule) = rules.ignore_reason(path)? {
+        let bytes = if metadata.is_dir() { 0 } else { metadata.len() };
+        pruned_stats.record_pruned(rule, bytes);
+        return Ok(());
+    }
+
+    let node = rules.node_from_metadata(path, &metadata, depth, false, is_hidden);
+    // Reparse points (Windows junctions) loop like symlinks - same rule.
+    let descend = node.is_dir
+        && !node.permission_denied
+        && depth < config.max_depth
+        && (!node.is_symlink || config.follow_symlinks);
+    nodes.push(node);
+
+    if descend {
+        let mut children: Vec<PathBuf> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+        children.sort();
+
+        for child in children {
+            walk_native(rules, config, &child, depth + 1, nodes, pruned_stats)?;
+        }
+    }
+
+    Ok(())
+}
+
 // PermissionsExt import removed - not currently used
 // Will be re-added when permission handling is implemented
@@ -3,12 +3,13 @@
 
 use anyhow::Result;
 use regex::Regex;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Types of relationships between files
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RelationType {
     /// Direct import/use/require
     Imports,
@@ -25,7 +26,7 @@ pub enum RelationType {
 }
 
 /// A relationship between two files
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileRelation {
     /// Source file path
     pub source: PathBuf,
@@ -47,6 +48,70 @@ pub struct RelationAnalyzer {
     parsers: HashMap<String, Box<dyn LanguageParser>>,
     /// File cache to avoid re-reading
     file_cache: HashMap<PathBuf, String>,
+    /// Root of the current analyze_directory pass
+    root: Option<PathBuf>,
+    /// tsconfig.json path aliases: pattern ("@app/*") -> substitutions
+    ts_aliases: Vec<(String, Vec<String>)>,
+    /// Directory tsconfig baseUrl resolves against
+    ts_base: Option<PathBuf>,
+    /// go.mod module path, for resolving absolute Go imports
+    go_module: Option<String>,
+}
+
+/// Bump when the parsers or FileRelation layout change shape, so stale
+/// caches are discarded instead of misread.
+const RELATIONS_CACHE_VERSION: u32 = 2;
+
+/// Persisted relation graph (`.st/relations`), keyed per source file with
+/// its content hash. Imports are the expensive per-file parse; the derived
+/// passes (coupling, test links) are cheap and recomputed every run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RelationsCache {
+    version: u32,
+    /// blake3 hex per analyzed file - the invalidation key
+    hashes: HashMap<PathBuf, String>,
+    /// Parsed relations keyed by source file
+    relations: HashMap<PathBuf, Vec<FileRelation>>,
+}
+
+impl RelationsCache {
+    fn path(root: &Path) -> PathBuf {
+        root.join(".st").join("relations")
+    }
+
+    /// Load the cache for `root`; anything unreadable, unparsable, or
+    /// from another version starts fresh.
+    fn load(root: &Path) -> Self {
+        let loaded = fs::read_to_string(Self::path(root))
+            .ok()
+            .and_then(|content| serde_json::from_str::<RelationsCache>(&content).ok())
+            .unwrap_or_default();
+        if loaded.version == RELATIONS_CACHE_VERSION {
+            loaded
+        } else {
+            Self {
+                version: RELATIONS_CACHE_VERSION,
+                ..Self::default()
+            }
+        }
+    }
+
+    /// Best-effort save - a read-only checkout just misses the warm start.
+    fn save(&self, root: &Path) {
+        let path = Self::path(root);
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(content) = serde_json::to_string(self) {
+            let _ = fs::write(path, content);
+        }
+    }
+
+    /// Drop entries for files that no longer exist in this scan.
+    fn retain_files(&mut self, live: &HashMap<PathBuf, String>) {
+        self.hashes.retain(|path, _| live.contains_key(path));
+        self.relations.retain(|path, _| live.contains_key(path));
+    }
 }
 
 /// Language-specific parsing trait
@@ -267,6 +332,298 @@ impl LanguageParser for PythonParser {
     }
 }
 
+/// TypeScript / JavaScript parser (shared - the import grammar is the same)
+struct TypeScriptParser;
+
+/// Pull imported names out of an import clause: "Default, { a, b as c }",
+/// "* as ns", "type { T }"
+fn ts_clause_items(clause: &str) -> Vec<String> {
+    clause
+        .split([',', '{', '}'])
+        .filter_map(|part| {
+            let mut tokens = part.split_whitespace();
+            match (tokens.next(), tokens.next(), tokens.next()) {
+                (Some("*"), Some("as"), Some(ns)) => Some(ns.to_string()),
+                (Some("type"), Some(name), _) => Some(name.to_string()),
+                // "a as b" imports a; the local rename doesn't matter here
+                (Some(name), _, _) => Some(name.to_string()),
+                _ => None,
+            }
+        })
+        .filter(|s| {
+            !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        })
+        .collect()
+}
+
+impl LanguageParser for TypeScriptParser {
+    fn parse_imports(&self, content: &str, _file_path: &Path) -> Vec<(String, Vec<String>)> {
+        let mut imports = Vec::new();
+
+        // import Default, { a, b } from 'mod' / export { x } from 'mod'
+        let from_re =
+            Regex::new(r#"(?m)^\s*(?:import|export)\s+(.+?)\s+from\s+['"]([^'"]+)['"]"#).unwrap();
+        for cap in from_re.captures_iter(content) {
+            let clause = cap.get(1).map_or("", |m| m.as_str());
+            let module = cap.get(2).map_or("", |m| m.as_str());
+            imports.push((module.to_string(), ts_clause_items(clause)));
+        }
+
+        // Side-effect imports: import 'polyfill'
+        let bare_re = Regex::new(r#"(?m)^\s*import\s+['"]([^'"]+)['"]"#).unwrap();
+        for cap in bare_re.captures_iter(content) {
+            imports.push((cap.get(1).map_or("", |m| m.as_str()).to_string(), vec![]));
+        }
+
+        // require('mod') and dynamic import('mod')
+        let call_re = Regex::new(r#"(?:require|import)\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap();
+        for cap in call_re.captures_iter(content) {
+            imports.push((cap.get(1).map_or("", |m| m.as_str()).to_string(), vec![]));
+        }
+
+        imports
+    }
+
+    fn parse_functions(&self, content: &str) -> Vec<String> {
+        let mut functions = Vec::new();
+        let fn_re = Regex::new(r"function\s+([A-Za-z0-9_$]+)").unwrap();
+        functions.extend(
+            fn_re
+                .captures_iter(content)
+                .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string())),
+        );
+        // Arrow/function-expression bindings: const f = (...) =>
+        let arrow_re =
+            Regex::new(r"(?:const|let|var)\s+([A-Za-z0-9_$]+)\s*=\s*(?:async\s+)?(?:function\b|\()")
+                .unwrap();
+        functions.extend(
+            arrow_re
+                .captures_iter(content)
+                .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string())),
+        );
+        functions
+    }
+
+    fn parse_function_calls(&self, content: &str) -> Vec<String> {
+        let call_re = Regex::new(r"([A-Za-z0-9_$]+)\s*\(").unwrap();
+        call_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .filter(|name| {
+                !["if", "for", "while", "switch", "catch", "function", "return", "typeof", "await"]
+                    .contains(&name.as_str())
+            })
+            .collect()
+    }
+
+    fn parse_types(&self, content: &str) -> Vec<String> {
+        let type_re =
+            Regex::new(r"(?:interface|class|enum|type)\s+([A-Z][A-Za-z0-9_]*)").unwrap();
+        type_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .collect()
+    }
+
+    fn parse_type_usages(&self, content: &str) -> Vec<String> {
+        let type_re = Regex::new(r":\s*([A-Z][A-Za-z0-9_]*)").unwrap();
+        type_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .collect()
+    }
+}
+
+/// Go language parser
+struct GoParser;
+
+impl LanguageParser for GoParser {
+    fn parse_imports(&self, content: &str, _file_path: &Path) -> Vec<(String, Vec<String>)> {
+        let mut imports = Vec::new();
+
+        // Single form: import "path" / import alias "path"
+        let single_re = Regex::new(r#"(?m)^\s*import\s+(?:([A-Za-z0-9_.]+)\s+)?"([^"]+)""#).unwrap();
+        for cap in single_re.captures_iter(content) {
+            let alias = cap.get(1).map(|m| vec![m.as_str().to_string()]);
+            let path = cap.get(2).map_or("", |m| m.as_str());
+            imports.push((path.to_string(), alias.unwrap_or_default()));
+        }
+
+        // Block form: import ( ... one per line, optional alias ... )
+        let block_re = Regex::new(r"(?s)import\s*\(([^)]*)\)").unwrap();
+        let line_re = Regex::new(r#"(?:([A-Za-z0-9_.]+)\s+)?"([^"]+)""#).unwrap();
+        for block in block_re.captures_iter(content) {
+            let body = block.get(1).map_or("", |m| m.as_str());
+            for cap in line_re.captures_iter(body) {
+                let alias = cap.get(1).map(|m| vec![m.as_str().to_string()]);
+                let path = cap.get(2).map_or("", |m| m.as_str());
+                imports.push((path.to_string(), alias.unwrap_or_default()));
+            }
+        }
+
+        imports
+    }
+
+    fn parse_functions(&self, content: &str) -> Vec<String> {
+        // Covers both functions and methods (the receiver group is skipped)
+        let fn_re = Regex::new(r"(?m)^func\s+(?:\([^)]*\)\s*)?([A-Za-z0-9_]+)").unwrap();
+        fn_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .collect()
+    }
+
+    fn parse_function_calls(&self, content: &str) -> Vec<String> {
+        let call_re = Regex::new(r"([A-Za-z0-9_]+)\s*\(").unwrap();
+        call_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .filter(|name| {
+                !["if", "for", "switch", "func", "return", "go", "defer", "select", "make", "len"]
+                    .contains(&name.as_str())
+            })
+            .collect()
+    }
+
+    fn parse_types(&self, content: &str) -> Vec<String> {
+        let type_re = Regex::new(r"(?m)^type\s+([A-Z][A-Za-z0-9_]*)").unwrap();
+        type_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .collect()
+    }
+
+    fn parse_type_usages(&self, content: &str) -> Vec<String> {
+        // Struct literals are the strongest usage signal Go's syntax offers
+        let type_re = Regex::new(r"\b([A-Z][A-Za-z0-9_]*)\{").unwrap();
+        type_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+            .collect()
+    }
+}
+
+/// Fold `.` and `..` components without touching the filesystem - the
+/// candidates are checked against the in-memory file cache, not disk.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Extract `compilerOptions.baseUrl` and `compilerOptions.paths` from a
+/// tsconfig.json. tsconfig allows comments and trailing commas, which
+/// serde_json does not, so those are stripped first.
+fn parse_tsconfig_paths(content: &str) -> (Option<String>, Vec<(String, Vec<String>)>) {
+    let no_comments: String = content
+        .lines()
+        .map(|line| {
+            // Naive but sufficient: tsconfig paths rarely contain "//"
+            match line.find("//") {
+                Some(pos) if !line[..pos].contains('"') => &line[..pos],
+                _ => line,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let cleaned = Regex::new(r",\s*([}\]])")
+        .unwrap()
+        .replace_all(&no_comments, "$1")
+        .into_owned();
+
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&cleaned) else {
+        return (None, Vec::new());
+    };
+    let options = &json["compilerOptions"];
+    let base = options["baseUrl"].as_str().map(|s| s.to_string());
+    let aliases = options["paths"]
+        .as_object()
+        .map(|paths| {
+            paths
+                .iter()
+                .map(|(pattern, targets)| {
+                    let substitutions = targets
+                        .as_array()
+                        .map(|array| {
+                            array
+                                .iter()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    (pattern.clone(), substitutions)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (base, aliases)
+}
+
+/// Marker line appended when `--fail-on-cycles` finds cycles. The daemon
+/// renders all output, so the thin client looks for this line to decide
+/// the exit code - keep it in sync with the check in main.rs.
+pub const CYCLE_FAIL_MARKER: &str = "⛔ dependency cycles detected";
+
+/// Bookkeeping for Tarjan's strongly-connected-components algorithm
+#[derive(Default)]
+struct TarjanState<'a> {
+    index: usize,
+    indices: HashMap<&'a PathBuf, usize>,
+    lowlinks: HashMap<&'a PathBuf, usize>,
+    stack: Vec<&'a PathBuf>,
+    on_stack: HashSet<&'a PathBuf>,
+    components: Vec<Vec<PathBuf>>,
+}
+
+/// One DFS visit of Tarjan's algorithm; components of size > 1 (the actual
+/// cycles) are collected into `state.components`.
+fn strongconnect<'a>(
+    node: &'a PathBuf,
+    adjacency: &BTreeMap<&'a PathBuf, Vec<&'a PathBuf>>,
+    state: &mut TarjanState<'a>,
+) {
+    state.indices.insert(node, state.index);
+    state.lowlinks.insert(node, state.index);
+    state.index += 1;
+    state.stack.push(node);
+    state.on_stack.insert(node);
+
+    for &next in adjacency.get(node).map(|v| v.as_slice()).unwrap_or(&[]) {
+        if !state.indices.contains_key(next) {
+            strongconnect(next, adjacency, state);
+            let low = state.lowlinks[next].min(state.lowlinks[node]);
+            state.lowlinks.insert(node, low);
+        } else if state.on_stack.contains(next) {
+            let low = state.indices[next].min(state.lowlinks[node]);
+            state.lowlinks.insert(node, low);
+        }
+    }
+
+    if state.lowlinks[node] == state.indices[node] {
+        let mut component = Vec::new();
+        while let Some(top) = state.stack.pop() {
+            state.on_stack.remove(top);
+            component.push(top.clone());
+            if top == node {
+                break;
+            }
+        }
+        if component.len() > 1 {
+            state.components.push(component);
+        }
+    }
+}
+
 impl Default for RelationAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -279,26 +636,61 @@ impl RelationAnalyzer {
         let mut parsers: HashMap<String, Box<dyn LanguageParser>> = HashMap::new();
         parsers.insert("rs".to_string(), Box::new(RustParser));
         parsers.insert("py".to_string(), Box::new(PythonParser));
+        for ext in ["ts", "tsx", "js", "jsx"] {
+            parsers.insert(ext.to_string(), Box::new(TypeScriptParser));
+        }
+        parsers.insert("go".to_string(), Box::new(GoParser));
 
         Self {
             relations: Vec::new(),
             parsers,
             file_cache: HashMap::new(),
+            root: None,
+            ts_aliases: Vec::new(),
+            ts_base: None,
+            go_module: None,
         }
     }
 
     /// Analyze a directory for code relationships
     pub fn analyze_directory(&mut self, path: &Path) -> Result<()> {
+        // Project-level config first: tsconfig aliases and the go.mod module
+        // path change how imports resolve in the second pass
+        self.load_polyglot_config(path);
+
         // First pass: collect all source files and their content
         self.collect_files(path)?;
 
-        // Second pass: analyze relationships
+        // Second pass: analyze relationships. The persisted graph in
+        // .st/relations lets unchanged files (by content hash) skip the
+        // parse entirely - on a warm repo only edits cost anything.
+        let mut cache = RelationsCache::load(path);
+        cache.version = RELATIONS_CACHE_VERSION;
         let files: Vec<PathBuf> = self.file_cache.keys().cloned().collect();
         for file in &files {
+            let hash = blake3::hash(self.file_cache[file].as_bytes())
+                .to_hex()
+                .to_string();
+            if cache.hashes.get(file) == Some(&hash) {
+                if let Some(cached) = cache.relations.get(file) {
+                    self.relations.extend(cached.iter().cloned());
+                    continue;
+                }
+            }
+            let before = self.relations.len();
             self.analyze_file(file)?;
+            cache.hashes.insert(file.clone(), hash);
+            cache
+                .relations
+                .insert(file.clone(), self.relations[before..].to_vec());
         }
+        cache.retain_files(&self.file_cache);
+        cache.save(path);
 
-        // Third pass: detect coupling and test relationships
+        // Third pass: derived relations - cross-file call edges, coupling,
+        // and test links all depend on the whole file set, so they are
+        // recomputed fresh every run rather than cached per file
+        self.link_function_calls();
         self.detect_coupling();
         self.detect_test_relationships();
 
@@ -375,8 +767,147 @@ impl RelationAnalyzer {
         Ok(())
     }
 
-    /// Resolve an import to a file path
+    /// Read tsconfig.json and go.mod (when present) so the per-language
+    /// resolvers can handle path aliases and module-absolute imports.
+    fn load_polyglot_config(&mut self, root: &Path) {
+        self.root = Some(root.to_path_buf());
+
+        if let Ok(content) = fs::read_to_string(root.join("tsconfig.json")) {
+            let (base, aliases) = parse_tsconfig_paths(&content);
+            self.ts_base = Some(root.join(base.unwrap_or_else(|| ".".to_string())));
+            self.ts_aliases = aliases;
+        }
+
+        if let Ok(content) = fs::read_to_string(root.join("go.mod")) {
+            self.go_module = content
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("module "))
+                .map(|module| module.trim().to_string());
+        }
+    }
+
+    /// Resolve an import to a file path, dispatching on the source language
     fn resolve_import(&self, from_file: &Path, module: &str) -> Option<PathBuf> {
+        match from_file.extension().and_then(|e| e.to_str())? {
+            "rs" => self.resolve_rust_import(from_file, module),
+            "py" => self.resolve_python_import(from_file, module),
+            "ts" | "tsx" | "js" | "jsx" => self.resolve_ts_import(from_file, module),
+            "go" => self.resolve_go_import(module),
+            _ => None,
+        }
+    }
+
+    /// Resolve a Python import. Leading dots walk the package hierarchy;
+    /// absolute imports are tried from the file's package outward to root.
+    fn resolve_python_import(&self, from_file: &Path, module: &str) -> Option<PathBuf> {
+        let dots = module.chars().take_while(|&c| c == '.').count();
+        let rest = &module[dots..];
+
+        if dots > 0 {
+            // One dot is the current package; each extra climbs a level
+            let mut base = from_file.parent()?;
+            for _ in 1..dots {
+                base = base.parent()?;
+            }
+            return self.python_candidate(base, rest);
+        }
+
+        // Absolute: try next to the file, then each ancestor up to root
+        let mut base = from_file.parent();
+        while let Some(dir) = base {
+            if let Some(found) = self.python_candidate(dir, rest) {
+                return Some(found);
+            }
+            if Some(dir) == self.root.as_deref() {
+                break;
+            }
+            base = dir.parent();
+        }
+        None
+    }
+
+    /// Try `<base>/<module as path>` as a module file or a package
+    fn python_candidate(&self, base: &Path, module: &str) -> Option<PathBuf> {
+        let stem = base.join(module.replace('.', "/"));
+        [stem.with_extension("py"), stem.join("__init__.py")]
+            .into_iter()
+            .find(|candidate| self.file_cache.contains_key(candidate))
+    }
+
+    /// Resolve a TypeScript/JavaScript specifier. Relative paths resolve
+    /// against the importing file; non-relative ones go through tsconfig
+    /// path aliases; anything left is an external package.
+    fn resolve_ts_import(&self, from_file: &Path, module: &str) -> Option<PathBuf> {
+        if module.starts_with('.') {
+            let base = from_file.parent()?;
+            return self.ts_candidate(&normalize_path(&base.join(module)));
+        }
+
+        // tsconfig paths: "@app/*" -> ["src/app/*"], matched by '*' prefix
+        for (pattern, substitutions) in &self.ts_aliases {
+            let matched = match pattern.split_once('*') {
+                Some((prefix, _)) => module.strip_prefix(prefix),
+                None => (module == pattern).then_some(""),
+            };
+            let Some(tail) = matched else { continue };
+            let base = self.ts_base.as_deref().or(self.root.as_deref())?;
+            for substitution in substitutions {
+                let target = substitution.replace('*', tail);
+                if let Some(found) = self.ts_candidate(&normalize_path(&base.join(target))) {
+                    return Some(found);
+                }
+            }
+        }
+
+        None // Bare specifier - node_modules, not ours
+    }
+
+    /// Try a path stem with the usual extension and index fallbacks
+    fn ts_candidate(&self, stem: &Path) -> Option<PathBuf> {
+        if self.file_cache.contains_key(stem) {
+            return Some(stem.to_path_buf());
+        }
+        for ext in ["ts", "tsx", "js", "jsx"] {
+            let candidate = stem.with_extension(ext);
+            if self.file_cache.contains_key(&candidate) {
+                return Some(candidate);
+            }
+            let index = stem.join(format!("index.{}", ext));
+            if self.file_cache.contains_key(&index) {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Resolve a Go import path to a file in that package's directory.
+    /// Only paths under the go.mod module are ours; stdlib and external
+    /// modules fall through.
+    fn resolve_go_import(&self, module: &str) -> Option<PathBuf> {
+        let prefix = self.go_module.as_deref()?;
+        let relative = if module == prefix {
+            ""
+        } else {
+            module.strip_prefix(prefix)?.strip_prefix('/')?
+        };
+        let package_dir = self.root.as_deref()?.join(relative);
+
+        // Prefer the file named after the package, else the first .go file
+        let dir_name = package_dir.file_name()?.to_str()?.to_string();
+        let named = package_dir.join(format!("{}.go", dir_name));
+        if self.file_cache.contains_key(&named) {
+            return Some(named);
+        }
+        self.file_cache
+            .keys()
+            .filter(|path| path.parent() == Some(&package_dir))
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("go"))
+            .min()
+            .cloned()
+    }
+
+    /// Resolve a Rust import to a file path
+    fn resolve_rust_import(&self, from_file: &Path, module: &str) -> Option<PathBuf> {
         // Skip external crates
         if !module.starts_with("crate")
             && !module.starts_with("super")
@@ -435,6 +966,85 @@ impl RelationAnalyzer {
         None
     }
 
+    /// Derive cross-file call edges: a function defined in exactly one file
+    /// and called from another becomes a FunctionCall relation carrying the
+    /// function names. Names defined in several files are skipped rather
+    /// than guessed at - a wrong edge is worse than a missing one.
+    fn link_function_calls(&mut self) {
+        let mut definitions: HashMap<String, Vec<&PathBuf>> = HashMap::new();
+        let mut calls_by_file: Vec<(&PathBuf, Vec<String>)> = Vec::new();
+        for (file, content) in &self.file_cache {
+            let ext = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let Some(parser) = self.parsers.get(ext) else {
+                continue;
+            };
+            for function in parser.parse_functions(content) {
+                definitions.entry(function).or_default().push(file);
+            }
+            calls_by_file.push((file, parser.parse_function_calls(content)));
+        }
+
+        // One relation per (caller, callee) pair, items = the functions
+        let mut edges: BTreeMap<(PathBuf, PathBuf), Vec<String>> = BTreeMap::new();
+        for (caller, calls) in calls_by_file {
+            for call in calls {
+                let Some(definers) = definitions.get(&call) else {
+                    continue;
+                };
+                let [definer] = definers.as_slice() else {
+                    continue; // Ambiguous - defined in more than one file
+                };
+                if *definer == caller {
+                    continue; // Intra-file call, not a relationship
+                }
+                let items = edges.entry((caller.clone(), (*definer).clone())).or_default();
+                if !items.contains(&call) {
+                    items.push(call);
+                }
+            }
+        }
+
+        for ((source, target), items) in edges {
+            let strength = (items.len() as u8).min(10);
+            self.relations.push(FileRelation {
+                source,
+                target,
+                relation_type: RelationType::FunctionCall,
+                items,
+                strength,
+            });
+        }
+    }
+
+    /// Strongly-connected components of the import/call graph with more
+    /// than one member - each one is a dependency cycle between modules.
+    /// Components come back sorted internally for stable output.
+    pub fn find_cycles(&self) -> Vec<Vec<PathBuf>> {
+        let mut adjacency: BTreeMap<&PathBuf, Vec<&PathBuf>> = BTreeMap::new();
+        for rel in &self.relations {
+            if matches!(
+                rel.relation_type,
+                RelationType::Imports | RelationType::FunctionCall
+            ) {
+                adjacency.entry(&rel.source).or_default().push(&rel.target);
+                adjacency.entry(&rel.target).or_default();
+            }
+        }
+
+        let mut state = TarjanState::default();
+        let nodes: Vec<&PathBuf> = adjacency.keys().copied().collect();
+        for node in nodes {
+            if !state.indices.contains_key(node) {
+                strongconnect(node, &adjacency, &mut state);
+            }
+        }
+        for component in &mut state.components {
+            component.sort();
+        }
+        state.components.sort();
+        state.components
+    }
+
     /// Detect tightly coupled files
     fn detect_coupling(&mut self) {
         // Count bidirectional imports
@@ -549,4 +1159,212 @@ pub fn process_file() {
         let functions = parser.parse_functions(content);
         assert_eq!(functions, vec!["process_file"]);
     }
+
+    #[test]
+    fn test_typescript_parser() {
+        let parser = TypeScriptParser;
+        let content = r#"
+import React, { useState, useEffect } from 'react';
+import * as utils from './utils';
+import './styles.css';
+const fs = require('fs');
+export { helper } from '../shared/helper';
+
+export function App() {
+    const [count, setCount] = useState(0);
+}
+"#;
+
+        let imports = parser.parse_imports(content, Path::new("app.tsx"));
+        let modules: Vec<&str> = imports.iter().map(|(m, _)| m.as_str()).collect();
+        assert_eq!(
+            modules,
+            vec!["react", "./utils", "../shared/helper", "./styles.css", "fs"]
+        );
+        assert_eq!(imports[0].1, vec!["React", "useState", "useEffect"]);
+        assert_eq!(imports[1].1, vec!["utils"]);
+
+        let functions = parser.parse_functions(content);
+        assert!(functions.contains(&"App".to_string()));
+    }
+
+    #[test]
+    fn test_go_parser() {
+        let parser = GoParser;
+        let content = r#"
+package server
+
+import "fmt"
+
+import (
+    "net/http"
+    log "github.com/acme/monorepo/pkg/logging"
+)
+
+func (s *Server) Handle() {}
+func NewServer() *Server { return &Server{} }
+
+type Server struct{}
+"#;
+
+        let imports = parser.parse_imports(content, Path::new("server.go"));
+        let modules: Vec<&str> = imports.iter().map(|(m, _)| m.as_str()).collect();
+        assert_eq!(
+            modules,
+            vec!["fmt", "net/http", "github.com/acme/monorepo/pkg/logging"]
+        );
+        assert_eq!(imports[2].1, vec!["log"]);
+
+        assert_eq!(parser.parse_functions(content), vec!["Handle", "NewServer"]);
+        assert_eq!(parser.parse_types(content), vec!["Server"]);
+    }
+
+    #[test]
+    fn test_python_import_resolution() {
+        let mut analyzer = RelationAnalyzer::new();
+        let root = PathBuf::from("/repo");
+        analyzer.root = Some(root.clone());
+        for file in ["pkg/__init__.py", "pkg/util.py", "pkg/sub/mod.py"] {
+            analyzer.file_cache.insert(root.join(file), String::new());
+        }
+
+        let from = root.join("pkg/sub/mod.py");
+        // Relative: from ..util import thing
+        assert_eq!(
+            analyzer.resolve_python_import(&from, "..util"),
+            Some(root.join("pkg/util.py"))
+        );
+        // Absolute: import pkg.util, found by climbing to root
+        assert_eq!(
+            analyzer.resolve_python_import(&from, "pkg.util"),
+            Some(root.join("pkg/util.py"))
+        );
+        // Package import lands on its __init__.py
+        assert_eq!(
+            analyzer.resolve_python_import(&from, "pkg"),
+            Some(root.join("pkg/__init__.py"))
+        );
+        assert_eq!(analyzer.resolve_python_import(&from, "numpy"), None);
+    }
+
+    #[test]
+    fn test_ts_alias_resolution() {
+        let (base, aliases) = parse_tsconfig_paths(
+            r#"{
+  // Monorepo aliases
+  "compilerOptions": {
+    "baseUrl": ".",
+    "paths": {
+      "@app/*": ["src/app/*"],
+    },
+  },
+}"#,
+        );
+        assert_eq!(base.as_deref(), Some("."));
+        assert_eq!(aliases, vec![("@app/*".to_string(), vec!["src/app/*".to_string()])]);
+
+        let mut analyzer = RelationAnalyzer::new();
+        let root = PathBuf::from("/repo");
+        analyzer.root = Some(root.clone());
+        analyzer.ts_base = Some(root.clone());
+        analyzer.ts_aliases = aliases;
+        for file in ["src/app/api/client.ts", "src/app/api/index.ts", "src/app/index.ts", "src/main.ts"]
+        {
+            analyzer.file_cache.insert(root.join(file), String::new());
+        }
+
+        let from = root.join("src/main.ts");
+        assert_eq!(
+            analyzer.resolve_ts_import(&from, "@app/api/client"),
+            Some(root.join("src/app/api/client.ts"))
+        );
+        // Relative import with extension inference
+        assert_eq!(
+            analyzer.resolve_ts_import(&root.join("src/app/index.ts"), "./api/client"),
+            Some(root.join("src/app/api/client.ts"))
+        );
+        // Directory import falls back to its index file
+        assert_eq!(
+            analyzer.resolve_ts_import(&root.join("src/app/index.ts"), "./api"),
+            Some(root.join("src/app/api/index.ts"))
+        );
+        assert_eq!(analyzer.resolve_ts_import(&from, "react"), None);
+    }
+
+    #[test]
+    fn test_go_import_resolution() {
+        let mut analyzer = RelationAnalyzer::new();
+        let root = PathBuf::from("/repo");
+        analyzer.root = Some(root.clone());
+        analyzer.go_module = Some("github.com/acme/monorepo".to_string());
+        for file in ["pkg/logging/logging.go", "pkg/api/handlers.go"] {
+            analyzer.file_cache.insert(root.join(file), String::new());
+        }
+
+        // Package file named after the directory wins
+        assert_eq!(
+            analyzer.resolve_go_import("github.com/acme/monorepo/pkg/logging"),
+            Some(root.join("pkg/logging/logging.go"))
+        );
+        // Otherwise any file in the package directory
+        assert_eq!(
+            analyzer.resolve_go_import("github.com/acme/monorepo/pkg/api"),
+            Some(root.join("pkg/api/handlers.go"))
+        );
+        assert_eq!(analyzer.resolve_go_import("fmt"), None);
+        assert_eq!(analyzer.resolve_go_import("github.com/other/dep"), None);
+    }
+
+    #[test]
+    fn test_link_function_calls() {
+        let mut analyzer = RelationAnalyzer::new();
+        analyzer.file_cache.insert(
+            PathBuf::from("/repo/src/util.rs"),
+            "pub fn helper_thing() {}\n".to_string(),
+        );
+        analyzer.file_cache.insert(
+            PathBuf::from("/repo/src/main.rs"),
+            "fn main() {\n    helper_thing();\n}\n".to_string(),
+        );
+
+        analyzer.link_function_calls();
+
+        let calls: Vec<_> = analyzer
+            .get_relations()
+            .iter()
+            .filter(|r| r.relation_type == RelationType::FunctionCall)
+            .collect();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].source, PathBuf::from("/repo/src/main.rs"));
+        assert_eq!(calls[0].target, PathBuf::from("/repo/src/util.rs"));
+        assert_eq!(calls[0].items, vec!["helper_thing"]);
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        let mut analyzer = RelationAnalyzer::new();
+        let (a, b, c, d) = (
+            PathBuf::from("/repo/a.rs"),
+            PathBuf::from("/repo/b.rs"),
+            PathBuf::from("/repo/c.rs"),
+            PathBuf::from("/repo/d.rs"),
+        );
+        let mut import = |source: &PathBuf, target: &PathBuf| {
+            analyzer.relations.push(FileRelation {
+                source: source.clone(),
+                target: target.clone(),
+                relation_type: RelationType::Imports,
+                items: vec![],
+                strength: 8,
+            });
+        };
+        // a <-> b is a cycle; c -> d -> a is acyclic
+        import(&a, &b);
+        import(&b, &a);
+        import(&c, &d);
+        import(&d, &a);
+
+        let cycles = analyzer.find_cycles();
+        assert_eq!(cycles, vec![vec![a, b]]);
+    }
 }
@@ -256,14 +256,23 @@ impl ProjectRenamer {
             show_hidden: false,
             show_ignored: false,
             find_pattern: None,
+            glob_filter: None,
             file_type_filter: None,
             entry_type_filter: None,
+            skip_special_files: false,
+            git_filter: None,
+            detect_content: false,
             min_size: None,
             max_size: Some(10 * 1024 * 1024), // Skip files > 10MB
             newer_than: None,
             older_than: None,
             use_default_ignores: true,
             search_keyword: None,
+            search_patterns: Vec::new(),
+            search_regex: false,
+            search_ignore_case: false,
+            search_whole_word: false,
+            search_match_all: false,
             show_filesystems: false,
             sort_field: None,
             top_n: None,
@@ -276,6 +285,17 @@ impl ProjectRenamer {
             changes_only: false,
             compare_state: None,
             smart_mode: false,
+            scan_archives: false,
+            dedupe_hardlinks: false,
+            use_allocated_size: false,
+            compute_dir_sizes: false,
+            list_ads: false,
+            collect_xattrs: false,
+            dirs_first: false,
+            files_first: false,
+            prune_patterns: Vec::new(),
+            max_files_per_dir: None,
+            scan_budget: Default::default(),
         };
 
         let scanner = Scanner::new(project_path, scanner_config)?;
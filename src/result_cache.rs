@@ -0,0 +1,307 @@
+// -----------------------------------------------------------------------------
+// 💾 Result Cache - cross-invocation scan results keyed by tree digest
+// -----------------------------------------------------------------------------
+// The MCP AnalysisCache only lives as long as its server process. This one
+// lives in `~/.st/cache/`: a formatted scan result is stored under
+// sha256(canonical root + every request option + tree digest), where the
+// tree digest folds each entry's name, type, size and mtime into one hash.
+// An unchanged tree therefore replays its stored output with no scan and no
+// daemon round-trip; touch anything under the root and the digest moves, so
+// the stale entry simply stops being found. Stale entries cost disk, not
+// correctness - and the disk cost is capped with oldest-first eviction.
+// `st cache stats|clear` inspects and empties the store.
+// -----------------------------------------------------------------------------
+
+use crate::daemon_cli::CliScanRequest;
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Evict oldest entries once the cache grows past this many bytes
+const MAX_CACHE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Trees with more entries than this are never cached - digesting them
+/// every invocation would eat the time the cache is meant to save
+const MAX_DIGEST_ENTRIES: usize = 500_000;
+
+/// Cached entries share this extension so stray files are never evicted
+const ENTRY_EXT: &str = "out";
+
+/// The on-disk cache directory, plus read/write/evict plumbing
+pub struct ResultCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+/// What `st cache stats` reports
+pub struct CacheStats {
+    pub dir: PathBuf,
+    pub entries: usize,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+}
+
+impl ResultCache {
+    /// Open (creating if needed) the cache at `~/.st/cache/`
+    pub fn open() -> Result<Self> {
+        let dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".st")
+            .join("cache");
+        Self::at(dir, MAX_CACHE_BYTES)
+    }
+
+    fn at(dir: PathBuf, max_bytes: u64) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Cannot create cache directory {}", dir.display()))?;
+        Ok(Self { dir, max_bytes })
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", key, ENTRY_EXT))
+    }
+
+    /// Fetch a stored result; a hit refreshes the entry's mtime so the
+    /// oldest-first eviction behaves like LRU
+    pub fn get(&self, key: &str) -> Option<String> {
+        let path = self.entry_path(key);
+        let output = std::fs::read_to_string(&path).ok()?;
+        if let Ok(file) = std::fs::OpenOptions::new().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(output)
+    }
+
+    /// Store one result, then trim the cache back under its size cap.
+    /// Atomic write (temp + rename) so a crash never leaves a torn entry.
+    pub fn put(&self, key: &str, output: &str) -> Result<()> {
+        let path = self.entry_path(key);
+        let tmp = self.dir.join(format!(".{}.tmp{}", key, std::process::id()));
+        std::fs::write(&tmp, output)?;
+        std::fs::rename(&tmp, &path)?;
+        self.evict_to_fit()
+    }
+
+    /// Remove oldest entries until total size fits under the cap
+    fn evict_to_fit(&self) -> Result<()> {
+        let mut entries = self.list_entries()?;
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if total <= self.max_bytes {
+            return Ok(());
+        }
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(size);
+            }
+        }
+        Ok(())
+    }
+
+    /// Every cache entry as (path, size, mtime)
+    fn list_entries(&self) -> Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(ENTRY_EXT) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+            entries.push((path, metadata.len(), modified));
+        }
+        Ok(entries)
+    }
+
+    /// Entry count and disk usage for `st cache stats`
+    pub fn stats(&self) -> Result<CacheStats> {
+        let entries = self.list_entries()?;
+        Ok(CacheStats {
+            dir: self.dir.clone(),
+            entries: entries.len(),
+            total_bytes: entries.iter().map(|(_, size, _)| size).sum(),
+            max_bytes: self.max_bytes,
+        })
+    }
+
+    /// Delete every entry; returns how many were removed
+    pub fn clear(&self) -> Result<usize> {
+        let mut removed = 0;
+        for (path, _, _) in self.list_entries()? {
+            if std::fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Can this request's output be cached at all?
+///
+/// Only formats whose output is a pure function of the tree qualify; git
+/// decorations read `.git` state the digest deliberately ignores, so those
+/// always scan fresh.
+pub fn cacheable(req: &CliScanRequest) -> bool {
+    if req.git_status || req.git_blame_summary {
+        return false;
+    }
+    matches!(
+        req.mode.to_lowercase().as_str(),
+        "ai" | "classic"
+            | "ls"
+            | "hex"
+            | "json"
+            | "csv"
+            | "tsv"
+            | "digest"
+            | "quantum"
+            | "quantum-semantic"
+            | "summary-ai"
+    )
+}
+
+/// Compute the cache key for one request: sha256 over the canonical root,
+/// the full serialized request, and the tree digest. None means "don't
+/// cache this one" - uncacheable mode, unreadable root, or a tree too big
+/// to digest cheaply.
+pub fn cache_key(req: &CliScanRequest) -> Option<String> {
+    if !cacheable(req) {
+        return None;
+    }
+    let root = std::fs::canonicalize(&req.path).ok()?;
+    if !root.is_dir() {
+        return None;
+    }
+    let options = serde_json::to_string(req).ok()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(root.to_string_lossy().as_bytes());
+    hasher.update([0]);
+    hasher.update(options.as_bytes());
+    hasher.update([0]);
+    hasher.update(tree_digest(&root)?.as_bytes());
+    Some(hex::encode(hasher.finalize()))
+}
+
+/// Hash every entry's relative path, kind, size, and mtime into one digest
+///
+/// `.git` internals are skipped - their constant churn would invalidate the
+/// cache on every git command, and the cacheable formats never show them.
+/// Hidden and ignored files are included even when the view hides them:
+/// that only risks a spurious miss, never a stale hit.
+fn tree_digest(root: &Path) -> Option<String> {
+    let mut hasher = Sha256::new();
+    let mut stack = vec![root.to_path_buf()];
+    let mut seen = 0usize;
+
+    while let Some(dir) = stack.pop() {
+        let Ok(read) = std::fs::read_dir(&dir) else {
+            // Unreadable directory: fold the failure in so a permission
+            // change still moves the digest
+            hasher.update(dir.to_string_lossy().as_bytes());
+            hasher.update(b"!denied");
+            continue;
+        };
+        let mut children: Vec<PathBuf> = read.flatten().map(|e| e.path()).collect();
+        children.sort();
+
+        for child in children {
+            if child.file_name().is_some_and(|n| n == ".git") {
+                continue;
+            }
+            seen += 1;
+            if seen > MAX_DIGEST_ENTRIES {
+                return None;
+            }
+            let Ok(metadata) = child.symlink_metadata() else {
+                continue;
+            };
+            let rel = child.strip_prefix(root).unwrap_or(&child);
+            hasher.update(rel.to_string_lossy().as_bytes());
+            hasher.update([if metadata.is_dir() {
+                b'd'
+            } else if metadata.file_type().is_symlink() {
+                b'l'
+            } else {
+                b'f'
+            }]);
+            hasher.update(metadata.len().to_le_bytes());
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            hasher.update(mtime.to_le_bytes());
+
+            if metadata.is_dir() && !metadata.file_type().is_symlink() {
+                stack.push(child);
+            }
+        }
+    }
+    Some(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_and_clear() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = ResultCache::at(tmp.path().join("cache"), MAX_CACHE_BYTES).unwrap();
+
+        assert!(cache.get("abc123").is_none());
+        cache.put("abc123", "🌳 the tree").unwrap();
+        assert_eq!(cache.get("abc123").as_deref(), Some("🌳 the tree"));
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert!(cache.get("abc123").is_none());
+    }
+
+    #[test]
+    fn test_eviction_keeps_newest() {
+        let tmp = tempfile::tempdir().unwrap();
+        // Cap fits roughly two of the three 40-byte entries
+        let cache = ResultCache::at(tmp.path().join("cache"), 100).unwrap();
+
+        let payload = "x".repeat(40);
+        for key in ["old", "mid", "new"] {
+            cache.put(key, &payload).unwrap();
+            // File mtimes need to actually differ for oldest-first order
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        assert!(cache.get("old").is_none(), "oldest entry should be evicted");
+        assert!(cache.get("new").is_some());
+        assert!(cache.stats().unwrap().total_bytes <= 100);
+    }
+
+    #[test]
+    fn test_tree_digest_tracks_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.rs"), "fn main() {}").unwrap();
+        let before = tree_digest(tmp.path()).unwrap();
+
+        // Same tree, same digest
+        assert_eq!(tree_digest(tmp.path()).unwrap(), before);
+
+        // New file moves the digest; .git churn does not
+        std::fs::write(tmp.path().join("b.rs"), "mod a;").unwrap();
+        let after = tree_digest(tmp.path()).unwrap();
+        assert_ne!(after, before);
+
+        std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+        std::fs::write(tmp.path().join(".git").join("index"), "churn").unwrap();
+        assert_eq!(tree_digest(tmp.path()).unwrap(), after);
+    }
+}
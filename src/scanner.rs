@@ -15,15 +15,17 @@
 
 use crate::interest_calculator::InterestCalculator;
 use crate::scanner_interest::{ChangeType, InterestScore, TraversalContext};
-use crate::scanner_safety::{estimate_node_size, ScannerSafetyLimits, ScannerSafetyTracker};
+use crate::scanner_events::ScanHooks;
+use crate::scanner_safety::{
+    estimate_node_size, ScanBudget, ScannerSafetyLimits, ScannerSafetyTracker,
+};
 use crate::scanner_state::ScanState;
 use crate::security_scan::{SecurityFinding, SecurityScanner};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use globset::{Glob, GlobSet, GlobSetBuilder}; // For powerful gitignore-style pattern matching.
 use regex::Regex; // For user-defined find patterns.
 use std::collections::{HashMap, HashSet}; // Our trusty hash-based collections.
 use std::fs; // Filesystem operations, the bread and butter here.
-use std::io::{BufRead, BufReader}; // For efficient reading, especially for content search.
 use std::path::{Path, PathBuf}; // Path manipulation is key.
 use std::sync::mpsc; // For streaming results from a worker thread.
 use std::time::SystemTime; // To know when files were last touched.
@@ -102,6 +104,36 @@ pub struct FileNode {
     /// Content hash for change detection (Blake3/SHA256)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_hash: Option<String>,
+
+    /// Filesystem identity `(device, inode)` when this file has multiple
+    /// hard links. Only populated with `--dedupe-hardlinks` on unix, so
+    /// size accounting can count each inode once.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub hardlink_id: Option<(u64, u64)>,
+
+    /// Actual allocated size (`st_blocks * 512`), populated with `--du` on
+    /// unix. Differs from `size` for sparse files (smaller) and through
+    /// block rounding (larger) - this is the number `du` would report.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub allocated_size: Option<u64>,
+    /// Extended attributes collected with `--xattrs`, allowlist-filtered
+    /// down to the security-relevant namespaces. POSIX ACLs surface here
+    /// too, as the `system.posix_acl_*` names. `None` when collection is
+    /// off (the default).
+    pub xattrs: Option<Vec<(String, String)>>,
+}
+
+impl FileNode {
+    /// The size a `--du` run should display: allocated blocks when we have
+    /// them, logical size otherwise.
+    pub fn display_size(&self) -> u64 {
+        self.allocated_size.unwrap_or(self.size)
+    }
+
+    /// Sparse file: fewer blocks allocated than the logical size claims.
+    pub fn is_sparse(&self) -> bool {
+        self.allocated_size.is_some_and(|allocated| allocated < self.size)
+    }
 }
 
 /// Information about search matches within a file
@@ -118,6 +150,20 @@ pub struct SearchMatches {
     /// Line content for each match (line number, line content, column) - optional for compatibility
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_content: Option<Vec<(usize, String, usize)>>,
+    /// Capture groups per match, present only for regex patterns with groups
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captures: Option<Vec<MatchCapture>>,
+}
+
+/// One regex match's capture groups, positioned for user display
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MatchCapture {
+    /// 1-based line of the overall match
+    pub line: usize,
+    /// 1-based byte column of the overall match
+    pub column: usize,
+    /// Captured group texts in group order (empty string for non-participating groups)
+    pub groups: Vec<String>,
 }
 
 /// # FileType: Distinguishing Different Kinds of Filesystem Objects
@@ -136,6 +182,42 @@ pub enum FileType {
     CharDevice,  // A character special file (e.g., /dev/tty).
 }
 
+impl FileType {
+    /// The `ls -l` mode-string type letter: d, l, s, p, b, c, or '-'.
+    pub fn mode_char(&self) -> char {
+        match self {
+            FileType::Directory => 'd',
+            FileType::Symlink => 'l',
+            FileType::Socket => 's',
+            FileType::Pipe => 'p',
+            FileType::BlockDevice => 'b',
+            FileType::CharDevice => 'c',
+            FileType::Executable | FileType::RegularFile => '-',
+        }
+    }
+
+    /// True for the special files `--special-files skip` removes:
+    /// devices, sockets, and FIFOs.
+    pub fn is_special(&self) -> bool {
+        matches!(
+            self,
+            FileType::Socket | FileType::Pipe | FileType::BlockDevice | FileType::CharDevice
+        )
+    }
+
+    /// Bracketed marker tree-style formatters append after the name;
+    /// None for ordinary entries.
+    pub fn special_marker(&self) -> Option<&'static str> {
+        match self {
+            FileType::Socket => Some("[socket]"),
+            FileType::Pipe => Some("[fifo]"),
+            FileType::BlockDevice => Some("[block]"),
+            FileType::CharDevice => Some("[char]"),
+            _ => None,
+        }
+    }
+}
+
 /// # FilesystemType: Identifying the underlying filesystem
 ///
 /// This enum represents different filesystem types with single-character codes
@@ -149,6 +231,7 @@ pub enum FilesystemType {
     Btrfs,   // 'B' - Btrfs (B-tree filesystem)
     Zfs,     // 'Z' - ZFS filesystem
     Ntfs,    // 'N' - Windows NTFS
+    Refs,    // 'W' - Windows ReFS (Resilient File System)
     Fat32,   // 'F' - FAT32
     ExFat,   // 'E' - exFAT
     Apfs,    // 'A' - Apple File System
@@ -160,6 +243,7 @@ pub enum FilesystemType {
     Sysfs,   // 'Y' - /sys virtual filesystem
     Devfs,   // 'D' - /dev virtual filesystem
     Mem8,    // 'M' - MEM|8 filesystem (Coming soon - Quantum File System) - https://m8.is
+    Archive, // 'a' - Virtual entry inside an archive (--scan-archives)
     Unknown, // '?' - Unknown filesystem
 }
 
@@ -174,6 +258,7 @@ impl FilesystemType {
             FilesystemType::Btrfs => 'B',
             FilesystemType::Zfs => 'Z',
             FilesystemType::Ntfs => 'N',
+            FilesystemType::Refs => 'W',
             FilesystemType::Fat32 => 'F',
             FilesystemType::ExFat => 'E',
             FilesystemType::Apfs => 'A',
@@ -185,6 +270,7 @@ impl FilesystemType {
             FilesystemType::Sysfs => 'Y',
             FilesystemType::Devfs => 'D',
             FilesystemType::Mem8 => 'M',
+            FilesystemType::Archive => 'a',
             FilesystemType::Unknown => '?',
         }
     }
@@ -233,6 +319,7 @@ impl FilesystemType {
             matches!(
                 self,
                 FilesystemType::Ntfs
+                    | FilesystemType::Refs
                     | FilesystemType::Fat32
                     | FilesystemType::ExFat
                     | FilesystemType::Mem8
@@ -370,6 +457,39 @@ pub struct TreeStats {
     pub newest_files: Vec<(SystemTime, PathBuf)>,
     /// Top N oldest files found (path and modification time).
     pub oldest_files: Vec<(SystemTime, PathBuf)>,
+    /// Total size with each hard-linked inode counted once. Matches
+    /// `total_size` (the apparent size) unless `--dedupe-hardlinks` found
+    /// multi-link files.
+    pub disk_usage: u64,
+    /// Directory entries whose inode was already counted in `disk_usage`.
+    pub hardlink_duplicates: u64,
+    /// Inodes already charged to `disk_usage` (dedupe bookkeeping).
+    pub seen_inodes: HashSet<(u64, u64)>,
+    /// Total allocated size (`st_blocks * 512`). Equals `total_size` unless
+    /// `--du` populated allocation data.
+    pub allocated_size: u64,
+    /// Files with fewer blocks allocated than their logical size (sparse).
+    pub sparse_files: u64,
+    /// What the ignore rules kept out of the scan, keyed by rule family
+    /// (e.g. "default ignores", ".gitignore"). Empty when nothing was pruned
+    /// or when `--show-ignored` put the entries on screen anyway.
+    pub pruned: HashMap<String, PrunedCount>,
+    /// True when a safety limit or explicit budget (`--max-entries`,
+    /// `--max-bytes-scanned`, `--timeout`) stopped the scan early. The
+    /// numbers above are valid but cover only the walked portion.
+    pub truncated: bool,
+}
+
+/// Tally of entries one ignore-rule family pruned from a scan.
+///
+/// Pruned directories count as a single entry and contribute zero bytes:
+/// their contents are never walked, which is the whole point of pruning.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PrunedCount {
+    /// Files and directories skipped by this rule.
+    pub entries: u64,
+    /// Bytes the skipped files would have added to the total size.
+    pub bytes: u64,
 }
 
 impl TreeStats {
@@ -381,7 +501,24 @@ impl TreeStats {
         } else {
             // It's a file!
             self.total_files += 1;
-            self.total_size += node.size;
+            self.total_size += node.size; // Apparent size - every entry counts.
+
+            // Disk usage counts each hard-linked inode once. `hardlink_id`
+            // is only set with --dedupe-hardlinks, so this is a no-op split
+            // otherwise.
+            match node.hardlink_id {
+                Some(id) if !self.seen_inodes.insert(id) => {
+                    self.hardlink_duplicates += 1; // Already charged this inode.
+                }
+                _ => self.disk_usage += node.size,
+            }
+
+            // Allocated (du-style) accounting - falls back to logical size
+            // when --du didn't populate block counts.
+            self.allocated_size += node.display_size();
+            if node.is_sparse() {
+                self.sparse_files += 1;
+            }
 
             // Track file extensions for type distribution.
             if let Some(ext) = node.path.extension() {
@@ -409,6 +546,62 @@ impl TreeStats {
             self.oldest_files.truncate(10);
         }
     }
+
+    /// Records one entry that an ignore rule pruned from the scan, so stats
+    /// and summary modes can report what the user *didn't* see.
+    pub fn record_pruned(&mut self, rule: &str, bytes: u64) {
+        let slot = self.pruned.entry(rule.to_string()).or_default();
+        slot.entries += 1;
+        slot.bytes += bytes;
+    }
+}
+
+/// Compiled `--glob` patterns with include/exclude semantics.
+///
+/// Patterns are matched against root-relative paths; `!`-prefixed patterns
+/// exclude. With no include patterns at all, everything not excluded passes.
+/// globset gives us `**` recursion and `{a,b}` brace alternation for free,
+/// and `*` never crosses a `/` - exactly how ripgrep reads globs.
+#[derive(Debug, Clone)]
+pub struct GlobFilter {
+    includes: globset::GlobSet,
+    has_includes: bool,
+    excludes: globset::GlobSet,
+}
+
+impl GlobFilter {
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut includes = globset::GlobSetBuilder::new();
+        let mut excludes = globset::GlobSetBuilder::new();
+        let mut has_includes = false;
+        for pattern in patterns {
+            let (builder, pattern) = match pattern.strip_prefix('!') {
+                Some(rest) => (&mut excludes, rest),
+                None => {
+                    has_includes = true;
+                    (&mut includes, pattern.as_str())
+                }
+            };
+            let glob = globset::GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid glob pattern '{}'", pattern))?;
+            builder.add(glob);
+        }
+        Ok(Self {
+            includes: includes.build()?,
+            has_includes,
+            excludes: excludes.build()?,
+        })
+    }
+
+    /// Does this root-relative path survive the include/exclude rules?
+    pub fn matches(&self, rel_path: &Path) -> bool {
+        if self.excludes.is_match(rel_path) {
+            return false;
+        }
+        !self.has_includes || self.includes.is_match(rel_path)
+    }
 }
 
 /// # ScannerConfig: The Rider for our Rock Star Scanner
@@ -417,7 +610,7 @@ impl TreeStats {
 /// "I only want to see files bigger than a tour bus," "Ignore the messy backstage
 /// area (`.gitignore`)." We build this from the user's command-line arguments
 /// to make sure the scanner puts on the exact show the user wants to see.
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct ScannerConfig {
     /// Maximum depth to traverse into subdirectories.
     pub max_depth: usize,
@@ -431,10 +624,23 @@ pub struct ScannerConfig {
     pub show_ignored: bool,
     /// An optional regex pattern to filter files/directories by name.
     pub find_pattern: Option<Regex>,
+    /// Optional glob patterns (`--glob`), matched against root-relative
+    /// paths, with `!`-prefixed excludes.
+    pub glob_filter: Option<GlobFilter>,
     /// An optional file extension to filter by (e.g., "rs").
     pub file_type_filter: Option<String>,
     /// Optional entry type filter ("f" for files, "d" for directories).
     pub entry_type_filter: Option<String>,
+    /// Skip device files, sockets, and FIFOs entirely (`--special-files
+    /// skip`) - scanning /dev or application runtime dirs without the noise.
+    pub skip_special_files: bool,
+    /// Optional git state filter ("tracked", "untracked", "ignored",
+    /// "modified") - gathered once per scan via `git ls-files`
+    /// (`--git-filter`).
+    pub git_filter: Option<String>,
+    /// Sniff magic bytes so extension-less and mislabeled files get a real
+    /// `FileCategory` (`--detect-content`). Costs one small read per file.
+    pub detect_content: bool,
     /// Optional minimum file size filter.
     pub min_size: Option<u64>,
     /// Optional maximum file size filter.
@@ -448,6 +654,18 @@ pub struct ScannerConfig {
     pub use_default_ignores: bool,
     /// An optional keyword to search for within file contents.
     pub search_keyword: Option<String>,
+    /// Additional content-search patterns beyond `search_keyword`
+    /// (repeated `--search`) - combined per `search_match_all`.
+    pub search_patterns: Vec<String>,
+    /// Treat search patterns as regular expressions (`--search-regex`).
+    pub search_regex: bool,
+    /// Case-insensitive content search (`--search-ignore-case`).
+    pub search_ignore_case: bool,
+    /// Match patterns only at word boundaries (`--search-word`).
+    pub search_whole_word: bool,
+    /// Require every pattern to match in a file - AND instead of the
+    /// default OR (`--search-all`).
+    pub search_match_all: bool,
     /// Should filesystem type indicators be shown?
     pub show_filesystems: bool,
     /// Sort field for results (name, size, date, type)
@@ -473,6 +691,61 @@ pub struct ScannerConfig {
     pub compare_state: Option<PathBuf>,
     /// Enable smart mode - groups by interest, shows changes, minimal output
     pub smart_mode: bool,
+    /// Descend into archives (.zip, .tar.gz, .jar, .whl) and present their
+    /// contents as virtual nodes (opt-in via `--scan-archives`)
+    pub scan_archives: bool,
+    /// Count each hard-linked inode once in size totals (opt-in via
+    /// `--dedupe-hardlinks`) - backup trees full of hard links otherwise
+    /// overcount massively
+    pub dedupe_hardlinks: bool,
+    /// Report allocated block sizes (`st_blocks * 512`) like `du` instead of
+    /// logical sizes, with sparse-file detection (opt-in via `--du`)
+    pub use_allocated_size: bool,
+    /// Roll file sizes up into their ancestor directories, ncdu-style
+    /// (opt-in via `--dir-sizes`) - directory nodes then carry recursive
+    /// totals instead of their inode size
+    pub compute_dir_sizes: bool,
+    /// List NTFS alternate data streams as virtual child entries
+    /// (`--ads`, Windows only - a no-op elsewhere)
+    pub list_ads: bool,
+    /// Collect extended attributes and POSIX ACLs per entry (`--xattrs`,
+    /// Linux only) - filtered through `XATTR_ALLOWED_PREFIXES` so a tree
+    /// full of exotic attributes stays readable
+    pub collect_xattrs: bool,
+    /// List directories before files in sorted output (`--dirs-first`)
+    pub dirs_first: bool,
+    /// List files before directories in sorted output (`--files-first`)
+    pub files_first: bool,
+    /// Stop descending into directories whose name matches one of these
+    /// globs (`--prune`) - the directory entry itself stays in the output,
+    /// carrying the aggregate size of everything underneath it
+    pub prune_patterns: Vec<String>,
+    /// Cap how many direct entries each directory contributes to the
+    /// output (`--max-files-per-dir N`) - overflow collapses into a
+    /// single "(+12,345 more)" summary node
+    pub max_files_per_dir: Option<usize>,
+    /// Hard scan budget (`--max-entries` / `--max-bytes-scanned` /
+    /// `--timeout`). Empty by default; set caps override the automatic
+    /// safety limits and mark the resulting stats as truncated when hit.
+    pub scan_budget: ScanBudget,
+}
+
+impl ScannerConfig {
+    /// True when any content-search pattern is configured - the scan then
+    /// acts as a filter (only matching files and their ancestors survive).
+    pub fn search_active(&self) -> bool {
+        self.search_keyword.is_some() || !self.search_patterns.is_empty()
+    }
+
+    /// Every configured search pattern in user order (`search_keyword`
+    /// first, then the extra `search_patterns`).
+    pub fn all_search_patterns(&self) -> Vec<String> {
+        self.search_keyword
+            .iter()
+            .chain(self.search_patterns.iter())
+            .cloned()
+            .collect()
+    }
 }
 
 // --- Default Ignore Patterns: The "Please Don't Play These Songs" List ---
@@ -624,6 +897,12 @@ pub struct Scanner {
     security_scanner: Option<SecurityScanner>,
     /// Interest calculator for scoring file relevance
     interest_calculator: Option<InterestCalculator>,
+    /// Paths in the requested git state, when `--git-filter` is active
+    git_filter_set: Option<crate::git_status::GitFileSet>,
+    /// Compiled content-search query, built once from the search options
+    search_query: Option<crate::content_search::SearchQuery>,
+    /// Lifecycle callbacks for embedding applications (progress UIs, indexers)
+    hooks: ScanHooks,
 }
 
 impl Scanner {
@@ -632,6 +911,13 @@ impl Scanner {
         &self.root
     }
 
+    /// Attach lifecycle hooks (see [`ScanHooks`]) - embedding applications
+    /// use these to drive progress UIs or custom indexing while a scan runs.
+    pub fn with_hooks(mut self, hooks: ScanHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
     /// Quick scan for basic project analysis - lighter weight than full scan
     /// Returns only basic stats and key files for faster integration
     pub fn quick_scan(&self) -> Result<(Vec<FileNode>, TreeStats)> {
@@ -708,7 +994,7 @@ impl Scanner {
     /// This function uses a series of heuristics based on file extensions and common names
     /// to classify files into broad categories, useful for display and understanding content.
     /// It's like a quick identification guide for files!
-    fn get_file_category(path: &Path, file_type: FileType) -> FileCategory {
+    pub(crate) fn get_file_category(path: &Path, file_type: FileType) -> FileCategory {
         // Directories don't get a specific content category here; their content defines them.
         if matches!(file_type, FileType::Directory) {
             return FileCategory::Unknown;
@@ -925,7 +1211,9 @@ impl Scanner {
             } else {
                 // Regular directories can use default limits
                 ScannerSafetyLimits::default()
-            };
+            }
+            // Explicit CI budgets (--max-entries etc.) trump the automatics.
+            .with_budget(&config.scan_budget);
 
         // Initialize security scanner if enabled
         let security_scanner = if config.security_scan {
@@ -948,6 +1236,35 @@ impl Scanner {
             None
         };
 
+        // Gather the git state set up front when --git-filter is active.
+        // Outside a work tree there is nothing to filter against, so warn
+        // rather than silently returning an empty tree.
+        let git_filter_set = config.git_filter.as_deref().and_then(|mode| {
+            let set = crate::git_status::GitFileSet::gather(&canonical_root, mode);
+            if set.is_none() {
+                eprintln!(
+                    "Warning: --git-filter {} ignored - {} is not inside a git work tree",
+                    mode,
+                    canonical_root.display()
+                );
+            }
+            set
+        });
+
+        // Compile the content-search query up front so bad patterns fail
+        // loudly here instead of silently matching nothing per file.
+        let search_query = if config.search_active() {
+            Some(crate::content_search::SearchQuery::build(
+                &config.all_search_patterns(),
+                config.search_regex,
+                config.search_ignore_case,
+                config.search_whole_word,
+                config.search_match_all,
+            )?)
+        } else {
+            None
+        };
+
         Ok(Self {
             config,
             gitignore,
@@ -958,6 +1275,9 @@ impl Scanner {
             safety_limits,
             security_scanner,
             interest_calculator,
+            git_filter_set,
+            search_query,
+            hooks: ScanHooks::default(),
         })
     }
 
@@ -1039,7 +1359,7 @@ impl Scanner {
         let mut stats = TreeStats::default();
 
         // When searching, we need to collect all nodes first to determine which directories to show
-        if self.config.search_keyword.is_some() {
+        if self.config.search_active() {
             // Use the non-streaming scan and then send results in order
             let (nodes, stats) = self.scan()?;
             for node in nodes {
@@ -1053,6 +1373,14 @@ impl Scanner {
         // Initialize safety tracker for streaming mode
         let safety_tracker = ScannerSafetyTracker::new(self.safety_limits.clone());
 
+        // Ordered output stage: every node gets the sequence number the
+        // traversal assigned it, and the emitter restores that order before
+        // the streaming formatter sees anything. Sequential traversal makes
+        // this a passthrough today; parallel workers will emit out of order
+        // and lean on the reordering buffer.
+        let mut emitter = crate::scanner_stream::OrderedEmitter::new(sender);
+        let mut next_seq: u64 = 0;
+
         // Original streaming logic for non-search cases
         let mut walker = WalkDir::new(&self.root)
             .max_depth(self.config.max_depth)
@@ -1075,7 +1403,8 @@ impl Scanner {
                     let path = entry.path();
 
                     // Determine if this entry should be ignored based on various rules.
-                    let is_ignored_by_rules = self.should_ignore(path)?;
+                    let ignore_reason = self.ignore_reason(path)?;
+                    let is_ignored_by_rules = ignore_reason.is_some();
 
                     if is_ignored_by_rules {
                         // The entry matches an ignore rule.
@@ -1093,11 +1422,21 @@ impl Scanner {
                                 safety_tracker.add_file(estimate_node_size(
                                     node.path.to_string_lossy().len(),
                                 ));
+                                if !node.is_dir {
+                                    safety_tracker.add_bytes(node.size);
+                                }
+
+                                if node.is_dir {
+                                    self.hooks.notify_enter_dir(&node.path, depth);
+                                } else {
+                                    self.hooks.notify_file(&node);
+                                }
 
-                                // Send the (ignored) node through the channel.
-                                if sender.send(node.clone()).is_err() {
+                                // Send the (ignored) node through the ordered stage.
+                                if !emitter.emit(next_seq, node.clone()) {
                                     break; // Receiver has disconnected, stop scanning.
                                 }
+                                next_seq += 1;
 
                                 // Update stats for ignored items if they aren't permission-denied.
                                 // This ensures `show_ignored` gives a full picture.
@@ -1112,6 +1451,10 @@ impl Scanner {
                             }
                         } else {
                             // We are *not* showing ignored items, and this one is ignored.
+                            // Track what the rule pruned, then skip.
+                            if let Some(rule) = ignore_reason {
+                                stats.record_pruned(rule, entry_byte_size(&entry));
+                            }
                             // If it's a directory, skip its contents. Otherwise, just continue.
                             if entry.file_type().is_dir() {
                                 walker.skip_current_dir();
@@ -1135,7 +1478,7 @@ impl Scanner {
                                 .is_some_and(|m| m.total_count > 0);
 
                             // If we have a search keyword, only include files with matches
-                            let should_include_file = if self.config.search_keyword.is_some() {
+                            let should_include_file = if self.config.search_active() {
                                 has_search_match
                             } else {
                                 self.should_include(&node)
@@ -1146,11 +1489,21 @@ impl Scanner {
                                 safety_tracker.add_file(estimate_node_size(
                                     node.path.to_string_lossy().len(),
                                 ));
+                                if !node.is_dir {
+                                    safety_tracker.add_bytes(node.size);
+                                }
 
-                                // Send the processed node through the channel.
-                                if sender.send(node.clone()).is_err() {
+                                if node.is_dir {
+                                    self.hooks.notify_enter_dir(&node.path, depth);
+                                } else {
+                                    self.hooks.notify_file(&node);
+                                }
+
+                                // Send the processed node through the ordered stage.
+                                if !emitter.emit(next_seq, node.clone()) {
                                     break; // Receiver disconnected.
                                 }
+                                next_seq += 1;
 
                                 // Update statistics for included, non-permission-denied items.
                                 if !node.permission_denied {
@@ -1170,6 +1523,7 @@ impl Scanner {
                     // An error occurred trying to access a directory entry (e.g., permission denied).
                     if let Some(path) = e.path() {
                         let depth = e.depth();
+                        self.hooks.notify_error(path, &e.to_string());
 
                         // Check if this is a "directory contents" error vs "directory entry" error.
                         // If this is a permission error, it's likely we already processed the directory
@@ -1185,9 +1539,10 @@ impl Scanner {
                             safety_tracker
                                 .add_file(estimate_node_size(node.path.to_string_lossy().len()));
 
-                            if sender.send(node.clone()).is_err() {
+                            if !emitter.emit(next_seq, node.clone()) {
                                 break; // Receiver disconnected.
                             }
+                            next_seq += 1;
                             // Still update stats (e.g., directory count) for permission-denied entries if shown.
                             stats.update_file(&node);
                         }
@@ -1200,7 +1555,10 @@ impl Scanner {
                 }
             }
         }
-        // Scan complete, return the accumulated statistics.
+        // Scan complete - drain anything still held by the ordered stage.
+        emitter.finish();
+        stats.truncated = safety_tracker.limit_hit();
+        self.hooks.notify_complete(&stats);
         Ok(stats)
     }
 
@@ -1208,8 +1566,8 @@ impl Scanner {
     /// This function is called before `search_in_file` to decide if it's worth attempting a search.
     /// It checks if a search keyword is configured and if the file is likely text-based.
     fn should_search_file(&self, node: &FileNode) -> bool {
-        // No search keyword? No search.
-        if self.config.search_keyword.is_none() {
+        // No search query? No search.
+        if self.search_query.is_none() {
             return false;
         }
 
@@ -1259,113 +1617,14 @@ impl Scanner {
 
     /// ## `search_in_file`
     ///
-    /// Searches for the configured keyword within a file and returns match information.
-    /// Returns line and column positions for each match, up to a reasonable limit.
-    /// The search is case-sensitive. Optionally includes the actual line content.
+    /// Searches for the configured keyword within a file and returns match
+    /// information. The heavy lifting lives in [`crate::content_search`]:
+    /// memory-mapped IO, SIMD substring matching, binary/size guards. Used
+    /// directly by the streaming path; `scan()` batches the same work
+    /// through `content_search::search_nodes` instead.
     fn search_in_file(&self, path: &Path) -> Option<SearchMatches> {
-        // Ensure there's a keyword to search for.
-        let keyword = self.config.search_keyword.as_ref()?;
-        if keyword.is_empty() {
-            return None;
-        }
-
-        // Attempt to open the file for reading.
-        let file = match fs::File::open(path) {
-            Ok(f) => f,
-            Err(_) => return None,
-        };
-
-        let mut positions = Vec::new();
-        let mut line_content_vec = Vec::new();
-        let reader = BufReader::new(file);
-        let mut line_number = 1;
-        let mut first_match: Option<(usize, usize)> = None;
-        let mut total_count = 0;
-
-        // Read and process the file line by line.
-        for line_result in reader.lines() {
-            match line_result {
-                Ok(line_content) => {
-                    // Find all occurrences of the keyword in the current line.
-                    let mut line_has_match = false;
-                    let mut first_column_in_line = None;
-
-                    for (column_index, _) in line_content.match_indices(keyword) {
-                        total_count += 1;
-                        line_has_match = true;
-
-                        // Column numbers are 1-based for user display
-                        let match_pos = (line_number, column_index + 1);
-
-                        if first_match.is_none() {
-                            first_match = Some(match_pos);
-                        }
-
-                        if first_column_in_line.is_none() {
-                            first_column_in_line = Some(column_index + 1);
-                        }
-
-                        // Only store first 100 positions to prevent memory issues
-                        if positions.len() < 100 {
-                            positions.push(match_pos);
-                        }
-
-                        // Stop processing this file if we've found too many matches
-                        if total_count > 100 {
-                            let line_content_option = if self.config.include_line_content {
-                                Some(line_content_vec)
-                            } else {
-                                None
-                            };
-
-                            return Some(SearchMatches {
-                                first_match: first_match.unwrap(),
-                                total_count,
-                                positions,
-                                truncated: true,
-                                line_content: line_content_option,
-                            });
-                        }
-                    }
-
-                    // If this line has matches and we're including content, add it
-                    if line_has_match
-                        && self.config.include_line_content
-                        && line_content_vec.len() < 100
-                    {
-                        line_content_vec.push((
-                            line_number,
-                            line_content.clone(),
-                            first_column_in_line.unwrap(),
-                        ));
-                    }
-
-                    line_number += 1;
-                }
-                Err(_) => {
-                    // Invalid UTF-8 or other error, stop searching this file
-                    break;
-                }
-            }
-        }
-
-        // Return matches if any were found
-        first_match.map(|first| {
-            let line_content_option =
-                if self.config.include_line_content && !line_content_vec.is_empty() {
-                    Some(line_content_vec)
-                } else {
-                    None
-                };
-
-            SearchMatches {
-                first_match: first,
-                total_count,
-                positions,
-                truncated: false,
-                line_content: line_content_option,
-            }
-        })
+        let query = self.search_query.as_ref()?;
+        crate::content_search::search_file(path, query, self.config.include_line_content)
     }
 
     /// ## `enrich_with_smart_scanning` - Add Security & Interest Data
@@ -1445,6 +1704,7 @@ impl Scanner {
     ///    It's thorough and great for when you need the whole picture before making decisions.
     pub fn scan(&self) -> Result<(Vec<FileNode>, TreeStats)> {
         let mut all_nodes_collected = Vec::new(); // Stores all nodes initially encountered.
+        let mut pruned_stats = TreeStats::default(); // Only its `pruned` map gets used.
                                                   // `ignored_dirs` was here, but its primary use with `skip_current_dir` is within the loop.
                                                   // If we need to track them for other reasons post-loop, it could be reinstated.
 
@@ -1468,26 +1728,37 @@ impl Scanner {
                 Ok(entry) => {
                     let depth = entry.depth();
                     let path = entry.path();
-                    let is_ignored_by_rules = self.should_ignore(path)?;
+                    let ignore_reason = self.ignore_reason(path)?;
+                    let is_ignored_by_rules = ignore_reason.is_some();
 
                     if is_ignored_by_rules {
                         if self.config.show_ignored {
                             // Process and add the ignored entry.
                             if let Some(mut node) = self.process_entry(&entry, depth, true)? {
-                                if !node.is_dir && self.should_search_file(&node) {
-                                    node.search_matches = self.search_in_file(&node.path);
-                                }
                                 // Smart scanning even for ignored files (they might have security issues!)
                                 self.enrich_with_smart_scanning(&mut node);
                                 safety_tracker.add_file(estimate_node_size(
                                     node.path.to_string_lossy().len(),
                                 ));
+                                if !node.is_dir {
+                                    safety_tracker.add_bytes(node.size);
+                                }
+                                if node.is_dir {
+                                    self.hooks.notify_enter_dir(&node.path, depth);
+                                } else {
+                                    self.hooks.notify_file(&node);
+                                }
                                 all_nodes_collected.push(node);
                             }
                             if entry.file_type().is_dir() {
                                 walker.skip_current_dir(); // Don't descend into ignored dirs if showing them.
                             }
                         } else {
+                            // Not showing ignored: remember what the rule pruned so
+                            // stats/summary modes can report it.
+                            if let Some(rule) = ignore_reason {
+                                pruned_stats.record_pruned(rule, entry_byte_size(&entry));
+                            }
                             // Not showing ignored, and it's a directory: skip its contents.
                             if entry.file_type().is_dir() {
                                 walker.skip_current_dir();
@@ -1497,11 +1768,18 @@ impl Scanner {
                     } else {
                         // Not ignored by rules, process normally.
                         if let Some(mut node) = self.process_entry(&entry, depth, false)? {
-                            if !node.is_dir && self.should_search_file(&node) {
-                                node.search_matches = self.search_in_file(&node.path);
-                            }
                             // Smart scanning: add security findings and interest scores
                             self.enrich_with_smart_scanning(&mut node);
+                            safety_tracker
+                                .add_file(estimate_node_size(node.path.to_string_lossy().len()));
+                            if !node.is_dir {
+                                safety_tracker.add_bytes(node.size);
+                            }
+                            if node.is_dir {
+                                self.hooks.notify_enter_dir(&node.path, depth);
+                            } else {
+                                self.hooks.notify_file(&node);
+                            }
                             all_nodes_collected.push(node);
                         } else {
                             // process_entry returned None, which means this is a hidden entry and show_hidden is false
@@ -1516,6 +1794,7 @@ impl Scanner {
                     // Handle errors like permission denied.
                     if let Some(path) = e.path() {
                         let depth = e.depth();
+                        self.hooks.notify_error(path, &e.to_string());
                         all_nodes_collected.push(self.create_permission_denied_node(path, depth));
                         if e.io_error().is_some_and(|io_err| {
                             io_err.kind() == std::io::ErrorKind::PermissionDenied
@@ -1527,10 +1806,78 @@ impl Scanner {
             }
         }
 
+        // Content search: now that the walk is done, fan the per-file keyword
+        // search across all cores in one rayon pass. Streaming mode searches
+        // inline (nodes leave immediately); here we have the full list, so
+        // the parallel batch engine wins.
+        if let Some(query) = &self.search_query {
+            // Persistent trigram index (st index build), when one exists:
+            // unchanged files provably lacking the pattern's trigrams are
+            // pruned before the content pass. The index only ever excludes
+            // work - new, changed, and unindexed files search normally.
+            let index = crate::search_index::SearchIndex::load(&self.root).unwrap_or(None);
+            crate::content_search::search_nodes(
+                &mut all_nodes_collected,
+                query,
+                self.config.include_line_content,
+                |node| {
+                    self.should_search_file(node)
+                        && index.as_ref().is_none_or(|ix| ix.may_match(node, query))
+                },
+            );
+        }
+
+        // Archive expansion: if requested, open supported archives and append
+        // their contents as virtual nodes. Done before filtering/stats so the
+        // virtual entries participate in both, just like real files.
+        if self.config.scan_archives {
+            // Rebuild the list with virtual entries inserted directly after
+            // their archive, preserving the parent-before-child ordering that
+            // tree formatters rely on.
+            let mut with_virtuals = Vec::with_capacity(all_nodes_collected.len());
+            for node in all_nodes_collected {
+                let expandable =
+                    !node.is_dir && crate::scanner_archive::is_supported_archive(&node.path);
+                if expandable {
+                    match crate::scanner_archive::expand_archive(&node) {
+                        Ok(expanded) => {
+                            with_virtuals.push(node);
+                            with_virtuals.extend(expanded);
+                            continue;
+                        }
+                        Err(e) => {
+                            // A corrupt archive shouldn't kill the scan - it just
+                            // stays a plain file in the output.
+                            tracing::debug!("Could not expand {}: {}", node.path.display(), e);
+                        }
+                    }
+                }
+                with_virtuals.push(node);
+            }
+            all_nodes_collected = with_virtuals;
+        }
+
+        // Alternate data streams (--ads): append each file's named NTFS
+        // streams as virtual children, using the same insert-after-parent
+        // pattern as archive expansion. No-op off Windows.
+        if self.config.list_ads {
+            let mut with_streams = Vec::with_capacity(all_nodes_collected.len());
+            for node in all_nodes_collected {
+                let streams = if node.is_dir {
+                    Vec::new()
+                } else {
+                    crate::scanner_windows::stream_nodes(&node)
+                };
+                with_streams.push(node);
+                with_streams.extend(streams);
+            }
+            all_nodes_collected = with_streams;
+        }
+
         // If filters are active, we need a second pass to ensure directories are only included
         // if they contain (or lead to) matching files.
         // Also, calculate stats based on the *final* list of nodes.
-        let (final_nodes, final_stats) = if self.has_active_filters() {
+        let (mut final_nodes, mut final_stats) = if self.has_active_filters() {
             self.filter_nodes_and_calculate_stats(all_nodes_collected)
         } else {
             // No filters, so all collected nodes are final. Calculate stats on them.
@@ -1545,14 +1892,36 @@ impl Scanner {
             (all_nodes_collected, stats)
         };
 
+        // Pruned counts were gathered during the walk; filters don't change them.
+        final_stats.pruned = pruned_stats.pruned;
+
+        // An early stop (safety limit or --max-entries/--max-bytes-scanned/
+        // --timeout budget) still produced valid stats - just flag that they
+        // cover only the walked portion.
+        final_stats.truncated = safety_tracker.limit_hit();
+
+        // Roll file sizes up into their ancestor directories (ncdu-style).
+        // Must happen after stats (which expect per-entry sizes) and before
+        // sorting (which would break the DFS order the roll-up relies on).
+        if self.config.compute_dir_sizes {
+            aggregate_dir_sizes(&mut final_nodes);
+        }
+
         // Apply sorting and top-N filtering if requested
-        let sorted_nodes = self.apply_sorting_and_limit(final_nodes);
+        let mut sorted_nodes = self.apply_sorting_and_limit(final_nodes);
+
+        // Flood control (--prune / --max-files-per-dir): trim the output
+        // without touching the stats - the totals above still describe the
+        // whole tree, which is exactly the point of these flags.
+        self.apply_prune(&mut sorted_nodes);
+        self.apply_max_files_per_dir(&mut sorted_nodes);
 
         // Save scan state for future change detection (if smart mode enabled)
         if self.config.smart_mode || self.config.compute_interest {
             self.save_scan_state(&sorted_nodes);
         }
 
+        self.hooks.notify_complete(&final_stats);
         Ok((sorted_nodes, final_stats))
     }
 
@@ -1582,7 +1951,7 @@ impl Scanner {
     /// This determines if the second filtering pass (`filter_nodes_and_calculate_stats`) is needed.
     /// Note: `search_keyword` is handled slightly differently; it can make a file appear
     /// even if other filters would exclude it, so it's part of `should_include` logic.
-    fn has_active_filters(&self) -> bool {
+    pub(crate) fn has_active_filters(&self) -> bool {
         self.config.find_pattern.is_some()
             || self.config.file_type_filter.is_some()
             || self.config.entry_type_filter.is_some()
@@ -1590,7 +1959,8 @@ impl Scanner {
             || self.config.max_size.is_some()
             || self.config.newer_than.is_some()
             || self.config.older_than.is_some()
-            || self.config.search_keyword.is_some() // Now search_keyword is also a filter
+            || self.config.search_active() // Now content search is also a filter
+            || self.git_filter_set.is_some()
     }
 
     /// ## `filter_nodes_and_calculate_stats` (Formerly `filter_nodes_with_ancestors`)
@@ -1603,7 +1973,7 @@ impl Scanner {
     ///    It then calculates `TreeStats` based on this final, filtered list of nodes.
     ///    This replaces the older `filter_nodes_with_ancestors` to integrate stat calculation
     ///    and clarify the logic for directory inclusion with `--find`.
-    fn filter_nodes_and_calculate_stats(
+    pub(crate) fn filter_nodes_and_calculate_stats(
         &self,
         all_nodes_collected: Vec<FileNode>,
     ) -> (Vec<FileNode>, TreeStats) {
@@ -1647,7 +2017,7 @@ impl Scanner {
             } else {
                 // For files, check if it passes all filters OR has a search match.
                 // If we have a search keyword, ONLY include files with search matches
-                if self.config.search_keyword.is_some() {
+                if self.config.search_active() {
                     if has_search_match {
                         // Even with search matches, the file must still pass other filters
                         if self.should_include(node) {
@@ -1816,12 +2186,41 @@ impl Scanner {
             }
         };
 
-        let file_type = self.determine_file_type(&metadata);
-        let category = Self::get_file_category(path, file_type);
+        Ok(Some(self.node_from_metadata(
+            path,
+            &metadata,
+            depth,
+            is_ignored_by_rules,
+            is_hidden,
+        )))
+    }
+
+    /// Build a `FileNode` from a path plus already-fetched metadata.
+    ///
+    /// Both walk backends funnel through here - the classic walkdir loop via
+    /// `process_entry` and the native quantum walker directly - so the two
+    /// can never drift on how a node gets populated.
+    pub(crate) fn node_from_metadata(
+        &self,
+        path: &Path,
+        metadata: &fs::Metadata,
+        depth: usize,
+        is_ignored_by_rules: bool,
+        is_hidden: bool,
+    ) -> FileNode {
+        let file_type = self.determine_file_type(metadata);
+        let mut category = Self::get_file_category(path, file_type);
+        // --detect-content: let magic bytes correct extension guesses (and
+        // categorize extension-less files the table above can't)
+        if self.config.detect_content && matches!(file_type, FileType::RegularFile) {
+            if let Some(sniffed) = crate::content_detector::ContentDetector::sniff_category(path) {
+                category = sniffed;
+            }
+        }
 
         // Determine the size. For special virtual files (like in /proc or /sys),
         // reported size can be misleading (e.g., 0 or huge). We mark these as size 0.
-        let size = if self.is_special_virtual_file(path, &metadata) {
+        let size = if self.is_special_virtual_file(path, metadata) {
             0
         } else {
             metadata.len()
@@ -1842,15 +2241,40 @@ impl Scanner {
             None
         };
 
-        Ok(Some(FileNode {
+        // Hard-link identity, only when dedupe accounting is on. Files with a
+        // single link can never double-count, so they stay `None`.
+        #[cfg(unix)]
+        let hardlink_id = if self.config.dedupe_hardlinks && !metadata.is_dir() {
+            use std::os::unix::fs::MetadataExt;
+            (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+        } else {
+            None
+        };
+        #[cfg(not(unix))]
+        let hardlink_id = None;
+
+        // du-style accounting: what the file actually occupies on disk.
+        #[cfg(unix)]
+        let allocated_size = if self.config.use_allocated_size && !metadata.is_dir() {
+            use std::os::unix::fs::MetadataExt;
+            Some(metadata.blocks() * 512)
+        } else {
+            None
+        };
+        #[cfg(not(unix))]
+        let allocated_size = None;
+
+        FileNode {
             path: path.to_path_buf(),
             is_dir: metadata.is_dir(),
             size,
-            permissions: Self::get_permissions(&metadata),
-            uid: Self::get_uid(&metadata),
-            gid: Self::get_gid(&metadata),
+            permissions: Self::get_permissions(metadata),
+            uid: Self::get_uid(metadata),
+            gid: Self::get_gid(metadata),
             modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), // Fallback for modified time
-            is_symlink: metadata.file_type().is_symlink(), // Use file_type() for symlink check
+            // Junctions and mount points count too - see scanner_windows.
+            is_symlink: metadata.file_type().is_symlink()
+                || crate::scanner_windows::is_reparse_point(metadata),
             is_hidden,
             permission_denied, // Set based on whether we can read directory contents
             is_ignored: is_ignored_by_rules, // Use the pre-determined ignore status.
@@ -1866,7 +2290,14 @@ impl Scanner {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
-        }))
+            hardlink_id,
+            allocated_size,
+            xattrs: if self.config.collect_xattrs {
+                collect_xattrs(path)
+            } else {
+                None
+            },
+        }
     }
 
     /// ## `get_git_branch`
@@ -1901,7 +2332,7 @@ impl Scanner {
     ///
     /// Detects the filesystem type for a given path
     #[cfg(unix)]
-    fn get_filesystem_type(path: &Path) -> FilesystemType {
+    pub(crate) fn get_filesystem_type(path: &Path) -> FilesystemType {
         // Skip filesystem detection in CI environments to avoid hangs
         if std::env::var("CI").is_ok() || std::env::var("GITHUB_ACTIONS").is_ok() {
             return FilesystemType::Unknown;
@@ -1911,7 +2342,12 @@ impl Scanner {
         {
             Self::get_filesystem_type_linux(path)
         }
-        #[cfg(not(target_os = "linux"))]
+        #[cfg(windows)]
+        {
+            // Drive-letter volume query: NTFS/ReFS/FAT32/exFAT.
+            crate::scanner_windows::detect_filesystem(path).unwrap_or(FilesystemType::Unknown)
+        }
+        #[cfg(not(any(target_os = "linux", windows)))]
         {
             // On non-Linux Unix systems, we can't reliably detect filesystem type
             // Just check for special paths
@@ -2009,7 +2445,7 @@ impl Scanner {
     }
 
     #[cfg(not(unix))]
-    fn get_filesystem_type(_path: &Path) -> FilesystemType {
+    pub(crate) fn get_filesystem_type(_path: &Path) -> FilesystemType {
         // On non-Unix systems, we can't easily detect filesystem type
         FilesystemType::Unknown
     }
@@ -2063,7 +2499,7 @@ impl Scanner {
     /// Helper to create a `FileNode` representing an entry (usually a directory)
     /// that could not be accessed due to permission errors.
     /// These nodes are marked specially so formatters can indicate the issue.
-    fn create_permission_denied_node(&self, path: &Path, depth: usize) -> FileNode {
+    pub(crate) fn create_permission_denied_node(&self, path: &Path, depth: usize) -> FileNode {
         FileNode {
             path: path.to_path_buf(),
             is_dir: true, // Assume it's a directory, as that's common for permission errors during traversal.
@@ -2088,6 +2524,9 @@ impl Scanner {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         }
     }
 
@@ -2098,35 +2537,42 @@ impl Scanner {
     /// "Sorry, `node_modules`, you're not on the list tonight."
     /// It's the first line of defense against clutter.
     fn should_ignore(&self, path: &Path) -> Result<bool> {
+        Ok(self.ignore_reason(path)?.is_some())
+    }
+
+    /// Like `should_ignore`, but names the rule family that matched so the
+    /// pruned-statistics report can break skips down per rule. `None` means
+    /// the path is not ignored.
+    pub(crate) fn ignore_reason(&self, path: &Path) -> Result<Option<&'static str>> {
         // --- Rule 0: Never ignore the root path itself ---
         // If the user explicitly asks to scan a directory, we should show it
         // even if it would normally be ignored (e.g., scanning 'target' directory)
         if path == self.root {
-            return Ok(false);
+            return Ok(None);
         }
 
         // --- Rule 1: Check against specific, always-ignored files (absolute paths) ---
         if self.config.use_default_ignores && self.ignore_files.contains(path) {
-            return Ok(true); // Matches a specific problematic file.
+            return Ok(Some("default ignores")); // Matches a specific problematic file.
         }
 
         // --- Rule 2: ALWAYS skip virtual filesystems like /proc, /sys, /dev ---
         // These are checked regardless of use_default_ignores because they're not real files
         // and can cause issues (huge fake sizes, hangs, etc.)
         if Self::is_virtual_filesystem(path) {
-            return Ok(true);
+            return Ok(Some("virtual filesystem"));
         }
 
         // --- Rule 3: Check against other system paths if using default ignores ---
         if self.config.use_default_ignores {
             // Check for exact match of a system path.
             if self.system_paths.contains(path) {
-                return Ok(true);
+                return Ok(Some("system path"));
             }
             // Check if the current path is a child of any registered system path.
             for system_root_path in &self.system_paths {
                 if path.starts_with(system_root_path) {
-                    return Ok(true); // It's inside /tmp, /var/tmp, etc.
+                    return Ok(Some("system path")); // It's inside /tmp, /var/tmp, etc.
                 }
             }
         }
@@ -2138,14 +2584,14 @@ impl Scanner {
             // (e.g., "node_modules" will match `path/to/project/node_modules`)
             if let Some(file_name) = path.file_name() {
                 if default_ignore_set.is_match(Path::new(file_name)) {
-                    return Ok(true);
+                    return Ok(Some("default ignores"));
                 }
             }
             // Also check the path relative to the scan root against default patterns.
             // This handles patterns like "*.pyc" or "build/outputs/".
             if let Ok(relative_path_to_root) = path.strip_prefix(&self.root) {
                 if default_ignore_set.is_match(relative_path_to_root) {
-                    return Ok(true);
+                    return Ok(Some("default ignores"));
                 }
             }
         }
@@ -2155,14 +2601,14 @@ impl Scanner {
         if let Some(ref gitignore_set) = self.gitignore {
             if let Ok(relative_path_to_root) = path.strip_prefix(&self.root) {
                 if gitignore_set.is_match(relative_path_to_root) {
-                    return Ok(true); // Matches a .gitignore pattern.
+                    return Ok(Some(".gitignore")); // Matches a .gitignore pattern.
                 }
             }
             // If strip_prefix fails (path is not under root), it can't match .gitignore relative patterns.
         }
 
         // If none of the above rules triggered, the path is not ignored.
-        Ok(false)
+        Ok(None)
     }
 
     /// ## `should_include` - The Velvet Rope
@@ -2181,6 +2627,17 @@ impl Scanner {
             }
         }
 
+        // --- Filter by --glob patterns (files only - ancestor directories
+        // of matching files are re-added by the caller, like --find) ---
+        if let Some(ref glob_filter) = self.config.glob_filter {
+            if !node.is_dir {
+                let rel = node.path.strip_prefix(&self.root).unwrap_or(&node.path);
+                if !glob_filter.matches(rel) {
+                    return false; // Path fails the include/exclude globs.
+                }
+            }
+        }
+
         // --- Filter by entry type (--entry-type) ---
         if let Some(ref entry_type) = self.config.entry_type_filter {
             match entry_type.as_str() {
@@ -2198,6 +2655,11 @@ impl Scanner {
             }
         }
 
+        // --- Filter special files (--special-files skip) ---
+        if self.config.skip_special_files && node.file_type.is_special() {
+            return false;
+        }
+
         // --- Filters below only apply to files, not directories ---
         if !node.is_dir {
             // --- Filter by file extension (--type) ---
@@ -2229,6 +2691,15 @@ impl Scanner {
                     return false; // File is too large.
                 }
             }
+
+            // --- Filter by git state (--git-filter) ---
+            // Directories are not filtered here: they are included as
+            // ancestors of matching files by filter_nodes_and_calculate_stats.
+            if let Some(ref git_set) = self.git_filter_set {
+                if !git_set.contains(&node.path) {
+                    return false; // Not in the requested git state.
+                }
+            }
         } // End of file-only filters
 
         // --- Date filters (apply to both files and directories based on their modification time) ---
@@ -2335,68 +2806,32 @@ impl Scanner {
     }
 
     /// Apply sorting and optional top-N limit to the results
-    fn apply_sorting_and_limit(&self, mut nodes: Vec<FileNode>) -> Vec<FileNode> {
-        // If no sort field specified, return as-is
-        let sort_field = match &self.config.sort_field {
-            Some(field) => field,
-            None => return nodes,
-        };
+    pub(crate) fn apply_sorting_and_limit(&self, mut nodes: Vec<FileNode>) -> Vec<FileNode> {
+        let spec = self.config.sort_field.as_deref().unwrap_or("");
 
-        // Sort based on the field
-        match sort_field.as_str() {
-            "name" | "a-to-z" => {
-                // Sort by name alphabetically (A to Z)
-                nodes.sort_by(|a, b| {
-                    let name_a = a.path.file_name().unwrap_or_default().to_string_lossy();
-                    let name_b = b.path.file_name().unwrap_or_default().to_string_lossy();
-                    name_a.cmp(&name_b)
-                });
-            }
-            "z-to-a" => {
-                // Sort by name reverse alphabetically (Z to A)
-                nodes.sort_by(|a, b| {
-                    let name_a = a.path.file_name().unwrap_or_default().to_string_lossy();
-                    let name_b = b.path.file_name().unwrap_or_default().to_string_lossy();
-                    name_b.cmp(&name_a)
-                });
-            }
-            "size" | "largest" => {
-                // Sort by size descending (largest first)
-                nodes.sort_by(|a, b| b.size.cmp(&a.size));
-            }
-            "smallest" => {
-                // Sort by size ascending (smallest first)
-                nodes.sort_by(|a, b| a.size.cmp(&b.size));
-            }
-            "date" | "newest" => {
-                // Sort by modification time descending (newest first)
-                nodes.sort_by(|a, b| b.modified.cmp(&a.modified));
-            }
-            "oldest" => {
-                // Sort by modification time ascending (oldest first)
-                nodes.sort_by(|a, b| a.modified.cmp(&b.modified));
-            }
-            "type" => {
-                // Sort by file extension, then by name
-                nodes.sort_by(|a, b| {
-                    let ext_a = a.path.extension().unwrap_or_default().to_string_lossy();
-                    let ext_b = b.path.extension().unwrap_or_default().to_string_lossy();
-                    match ext_a.cmp(&ext_b) {
-                        std::cmp::Ordering::Equal => {
-                            let name_a = a.path.file_name().unwrap_or_default().to_string_lossy();
-                            let name_b = b.path.file_name().unwrap_or_default().to_string_lossy();
-                            name_a.cmp(&name_b)
-                        }
-                        other => other,
-                    }
-                });
-            }
-            _ => {
-                // Unknown sort field, don't sort
-                eprintln!("Warning: Unknown sort field '{}', ignoring", sort_field);
+        // Nothing to sort by: neither keys nor a dirs/files-first toggle.
+        if spec.is_empty() && !self.config.dirs_first && !self.config.files_first {
+            return nodes;
+        }
+
+        // Warn about unknown keys once, up front, rather than per comparison.
+        for key in sort_keys(spec) {
+            if !is_sort_key(key) {
+                eprintln!("Warning: Unknown sort field '{}', ignoring", key);
             }
         }
 
+        nodes.sort_by(|a, b| {
+            let dir_order = if self.config.dirs_first {
+                b.is_dir.cmp(&a.is_dir)
+            } else if self.config.files_first {
+                a.is_dir.cmp(&b.is_dir)
+            } else {
+                std::cmp::Ordering::Equal
+            };
+            dir_order.then_with(|| compare_nodes(a, b, spec))
+        });
+
         // Apply top-N limit if specified
         if let Some(limit) = self.config.top_n {
             nodes.truncate(limit);
@@ -2404,8 +2839,448 @@ impl Scanner {
 
         nodes
     }
+
+    /// ## `apply_prune` (`--prune PATTERN`)
+    ///
+    /// Fold everything underneath a matching directory into the directory
+    /// node itself. The entry stays visible - carrying its aggregate file
+    /// size so `node_modules` still shows its 2 GB - but its 50,000
+    /// children never reach the formatter. Patterns are globs matched
+    /// against directory names; the scan root itself is never pruned.
+    fn apply_prune(&self, nodes: &mut Vec<FileNode>) {
+        if self.config.prune_patterns.is_empty() {
+            return;
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.config.prune_patterns {
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => eprintln!("Warning: Invalid --prune pattern '{}': {}", pattern, e),
+            }
+        }
+        let Ok(matcher) = builder.build() else {
+            return;
+        };
+
+        // Outermost matches win: a pruned directory nested inside another
+        // pruned directory vanishes with the rest of its ancestor's
+        // contents. Sorting first guarantees ancestors are seen before
+        // their descendants regardless of the active sort spec.
+        let mut matches: Vec<PathBuf> = nodes
+            .iter()
+            .filter(|node| {
+                node.is_dir
+                    && node.path != self.root
+                    && node
+                        .path
+                        .file_name()
+                        .is_some_and(|name| matcher.is_match(Path::new(name)))
+            })
+            .map(|node| node.path.clone())
+            .collect();
+        matches.sort();
+        let mut pruned: Vec<PathBuf> = Vec::new();
+        for path in matches {
+            if !pruned.iter().any(|p| path.starts_with(p)) {
+                pruned.push(path);
+            }
+        }
+        if pruned.is_empty() {
+            return;
+        }
+
+        // Roll descendant file sizes into the surviving pruned directory,
+        // then drop the descendants. `--dir-sizes` has already done the
+        // roll-up, so skip the folding in that case to avoid double counts.
+        let mut folded: HashMap<PathBuf, u64> = HashMap::new();
+        nodes.retain(|node| {
+            let Some(owner) = pruned
+                .iter()
+                .find(|p| node.path.starts_with(p) && node.path != **p)
+            else {
+                return true;
+            };
+            if !node.is_dir && !self.config.compute_dir_sizes {
+                *folded.entry(owner.clone()).or_default() += node.size;
+            }
+            false
+        });
+        for node in nodes.iter_mut() {
+            if let Some(extra) = folded.get(&node.path) {
+                node.size += extra;
+            }
+        }
+    }
+
+    /// ## `apply_max_files_per_dir` (`--max-files-per-dir N`)
+    ///
+    /// Cap each directory's direct entries at N: the first N (in scan
+    /// order) survive and the overflow collapses into one synthetic
+    /// "… (+12,345 more)" summary node per directory, sized as the sum of
+    /// the files it swallowed. The leading ellipsis sorts the summary
+    /// after its ASCII siblings in every formatter.
+    fn apply_max_files_per_dir(&self, nodes: &mut Vec<FileNode>) {
+        let Some(limit) = self.config.max_files_per_dir else {
+            return;
+        };
+        if limit == 0 {
+            return; // A cap of zero would hide everything - treat as "no cap".
+        }
+
+        struct Overflow {
+            count: u64,
+            size: u64,
+            /// First dropped child, cloned - donates depth/timestamps so
+            /// the summary node looks native to its directory.
+            template: FileNode,
+        }
+
+        let mut kept_per_dir: HashMap<PathBuf, usize> = HashMap::new();
+        let mut overflow: HashMap<PathBuf, Overflow> = HashMap::new();
+        // Dropped directories and the directory whose summary node owns
+        // them - their descendants roll into that summary's size.
+        let mut dropped_dirs: Vec<(PathBuf, PathBuf)> = Vec::new();
+
+        let mut kept = Vec::with_capacity(nodes.len());
+        for node in nodes.drain(..) {
+            if node.path == self.root {
+                kept.push(node);
+                continue;
+            }
+            if let Some((_, owner)) = dropped_dirs
+                .iter()
+                .find(|(dir, _)| node.path.starts_with(dir))
+            {
+                if !node.is_dir {
+                    if let Some(entry) = overflow.get_mut(owner) {
+                        entry.size += node.size;
+                    }
+                }
+                continue;
+            }
+            let Some(parent) = node.path.parent().map(Path::to_path_buf) else {
+                kept.push(node);
+                continue;
+            };
+            let seen = kept_per_dir.entry(parent.clone()).or_insert(0);
+            if *seen < limit {
+                *seen += 1;
+                kept.push(node);
+                continue;
+            }
+            if node.is_dir {
+                dropped_dirs.push((node.path.clone(), parent.clone()));
+            }
+            let entry = overflow.entry(parent).or_insert_with(|| Overflow {
+                count: 0,
+                size: 0,
+                template: node.clone(),
+            });
+            entry.count += 1;
+            if !node.is_dir {
+                entry.size += node.size;
+            }
+        }
+
+        for (parent, entry) in overflow {
+            let mut summary = entry.template;
+            summary.path = parent.join(format!("… (+{} more)", group_thousands(entry.count)));
+            summary.is_dir = false;
+            summary.is_symlink = false;
+            summary.size = entry.size;
+            summary.permissions = 0;
+            summary.file_type = FileType::RegularFile;
+            summary.category = FileCategory::Unknown;
+            summary.search_matches = None;
+            summary.git_branch = None;
+            kept.push(summary);
+        }
+
+        *nodes = kept;
+    }
 } // end impl Scanner
 
+/// Comma-group a count for human eyes: 12345 -> "12,345".
+fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    grouped
+}
+
+/// Split a comma-separated sort spec ("type,largest") into its keys.
+fn sort_keys(spec: &str) -> impl Iterator<Item = &str> {
+    spec.split(',').map(str::trim).filter(|key| !key.is_empty())
+}
+
+/// Is this a sort key `compare_nodes` understands?
+fn is_sort_key(key: &str) -> bool {
+    matches!(
+        key,
+        "name"
+            | "a-to-z"
+            | "z-to-a"
+            | "size"
+            | "largest"
+            | "smallest"
+            | "date"
+            | "newest"
+            | "oldest"
+            | "type"
+    )
+}
+
+/// Compare two nodes by a comma-separated key list - later keys break ties
+/// left by earlier ones (`"type,largest"` = by extension, then biggest
+/// first within each extension). Unknown keys are skipped; name comparisons
+/// are natural-number aware. Shared by the scanner and the tree formatters
+/// so every output mode orders entries identically.
+pub fn compare_nodes(a: &FileNode, b: &FileNode, spec: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let name = |node: &FileNode| {
+        node.path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    for key in sort_keys(spec) {
+        let order = match key {
+            "name" | "a-to-z" => natural_cmp(&name(a), &name(b)),
+            "z-to-a" => natural_cmp(&name(b), &name(a)),
+            "size" | "largest" => b.size.cmp(&a.size),
+            "smallest" => a.size.cmp(&b.size),
+            "date" | "newest" => b.modified.cmp(&a.modified),
+            "oldest" => a.modified.cmp(&b.modified),
+            // No name fallback here - same-extension ties fall through to
+            // the next key in the spec (the sort itself is stable anyway)
+            "type" => {
+                let ext_a = a.path.extension().unwrap_or_default().to_string_lossy();
+                let ext_b = b.path.extension().unwrap_or_default().to_string_lossy();
+                ext_a.cmp(&ext_b)
+            }
+            _ => Ordering::Equal, // Unknown key - warned about by the caller.
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Natural-number string comparison: digit runs compare by value, so
+/// `file2` sorts before `file10` instead of after it. Non-digit stretches
+/// compare byte-wise, same as plain `cmp`.
+pub fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            // Collect both digit runs and compare them numerically:
+            // strip leading zeros, then longer run wins, then byte order.
+            let start_a = i;
+            while i < a.len() && a[i].is_ascii_digit() {
+                i += 1;
+            }
+            let start_b = j;
+            while j < b.len() && b[j].is_ascii_digit() {
+                j += 1;
+            }
+            let run_a = trim_leading_zeros(&a[start_a..i]);
+            let run_b = trim_leading_zeros(&b[start_b..j]);
+            let order = run_a.len().cmp(&run_b.len()).then_with(|| run_a.cmp(run_b));
+            if order != std::cmp::Ordering::Equal {
+                return order;
+            }
+        } else {
+            let order = a[i].cmp(&b[j]);
+            if order != std::cmp::Ordering::Equal {
+                return order;
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+
+    (a.len() - i).cmp(&(b.len() - j))
+}
+
+/// Drop leading `0` bytes from a digit run ("007" compares as "7").
+fn trim_leading_zeros(run: &[u8]) -> &[u8] {
+    let first = run.iter().position(|&c| c != b'0').unwrap_or(run.len());
+    &run[first..]
+}
+
+/// Xattr namespaces worth surfacing in a security audit. Everything else
+/// (desktop metadata, overlayfs bookkeeping, cache hints) is noise that
+/// would drown the interesting labels.
+const XATTR_ALLOWED_PREFIXES: &[&str] = &[
+    "security.",         // SELinux labels, capabilities, IMA signatures
+    "system.posix_acl_", // POSIX ACLs (access and default)
+    "trusted.",
+    "user.",
+];
+
+/// Collect the allowlisted extended attributes of `path` (Linux only -
+/// other platforms return `None` and the field stays empty).
+#[cfg(target_os = "linux")]
+fn collect_xattrs(path: &Path) -> Option<Vec<(String, String)>> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+
+    // First call sizes the name list, second call fills it.
+    let len = unsafe { libc::llistxattr(c_path.as_ptr(), std::ptr::null_mut(), 0) };
+    if len <= 0 {
+        return None;
+    }
+    let mut names = vec![0u8; len as usize];
+    let len = unsafe {
+        libc::llistxattr(
+            c_path.as_ptr(),
+            names.as_mut_ptr() as *mut libc::c_char,
+            names.len(),
+        )
+    };
+    if len <= 0 {
+        return None;
+    }
+    names.truncate(len as usize);
+
+    let mut attrs = Vec::new();
+    for name in names.split(|&byte| byte == 0).filter(|n| !n.is_empty()) {
+        let name = String::from_utf8_lossy(name).into_owned();
+        if !XATTR_ALLOWED_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            continue;
+        }
+        let value = read_xattr_value(&c_path, &name);
+        attrs.push((name, value));
+    }
+
+    (!attrs.is_empty()).then_some(attrs)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_xattrs(_path: &Path) -> Option<Vec<(String, String)>> {
+    None
+}
+
+/// Read one xattr value, rendered display-ready: text values come through
+/// as-is (truncated), binary blobs like ACL structs become a byte count.
+#[cfg(target_os = "linux")]
+fn read_xattr_value(c_path: &std::ffi::CStr, name: &str) -> String {
+    const MAX_SHOWN: usize = 64;
+
+    let Ok(c_name) = std::ffi::CString::new(name) else {
+        return String::new();
+    };
+    let len = unsafe {
+        libc::lgetxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if len <= 0 {
+        return String::new();
+    }
+    let mut value = vec![0u8; len as usize];
+    let len = unsafe {
+        libc::lgetxattr(
+            c_path.as_ptr(),
+            c_name.as_ptr(),
+            value.as_mut_ptr() as *mut libc::c_void,
+            value.len(),
+        )
+    };
+    if len <= 0 {
+        return String::new();
+    }
+    value.truncate(len as usize);
+
+    // SELinux labels carry a trailing NUL; drop it before deciding whether
+    // the value is text at all.
+    while value.last() == Some(&0) {
+        value.pop();
+    }
+    if value.iter().any(|&byte| byte == 0 || byte >= 0x80) {
+        // ACL structs, capability sets - binary. Show the size, not mojibake.
+        return format!("({} bytes)", value.len());
+    }
+
+    let text = String::from_utf8_lossy(&value).into_owned();
+    if text.len() > MAX_SHOWN {
+        format!("{}...", &text[..MAX_SHOWN])
+    } else {
+        text
+    }
+}
+
+/// Bytes a pruned entry would have contributed to the scan: file length for
+/// files, zero for directories (their contents are never walked).
+fn entry_byte_size(entry: &DirEntry) -> u64 {
+    match entry.metadata() {
+        Ok(metadata) if !metadata.is_dir() => metadata.len(),
+        _ => 0,
+    }
+}
+
+/// Roll file sizes up into their ancestor directories in a single pass.
+///
+/// Nodes arrive in DFS order (parents before children), so a stack of the
+/// directories on the current root-to-here path is all the bookkeeping we
+/// need: files add to the innermost open directory, and a directory's total
+/// folds into its parent the moment the walk leaves it. Directory nodes end
+/// up carrying recursive totals instead of their inode size - exactly what
+/// `--dir-sizes` wants to display.
+fn aggregate_dir_sizes(nodes: &mut [FileNode]) {
+    let mut stack: Vec<usize> = Vec::new();
+
+    for i in 0..nodes.len() {
+        // Close every directory the walk has left (depth no longer below ours).
+        while stack
+            .last()
+            .is_some_and(|&open| nodes[open].depth >= nodes[i].depth)
+        {
+            let closed = stack.pop().unwrap();
+            if let Some(&parent) = stack.last() {
+                nodes[parent].size += nodes[closed].size;
+            }
+        }
+
+        if nodes[i].is_dir {
+            nodes[i].size = 0; // Inode size out, running total in.
+            stack.push(i);
+        } else if let Some(&parent) = stack.last() {
+            nodes[parent].size += nodes[i].size;
+        }
+    }
+
+    // The rightmost path is still open - fold it up to the root.
+    while let Some(closed) = stack.pop() {
+        if let Some(&parent) = stack.last() {
+            nodes[parent].size += nodes[closed].size;
+        }
+    }
+}
+
 /// # `parse_size` - The Universal Translator for Sizes
 ///
 /// This handy function takes something a human understands, like "2.5M", and
@@ -2491,8 +3366,199 @@ mod tests {
         assert!(parse_size("  ").is_err());
     }
 
+    fn file_node_with_hardlink(path: &str, size: u64, id: Option<(u64, u64)>) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir: false,
+            size,
+            permissions: 0o644,
+            uid: 0,
+            gid: 0,
+            modified: SystemTime::UNIX_EPOCH,
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth: 1,
+            file_type: FileType::RegularFile,
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: id,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_hardlink_dedup_counts_each_inode_once() {
+        // Real hard links through the real scanner - a hand-built FileNode
+        // can't catch create_file_node dropping `hardlink_id` on the floor.
+        let temp_dir = tempfile::tempdir().unwrap();
+        let original = temp_dir.path().join("original.bin");
+        std::fs::write(&original, vec![0u8; 100]).unwrap();
+        std::fs::hard_link(&original, temp_dir.path().join("link.bin")).unwrap();
+        std::fs::write(temp_dir.path().join("single.bin"), vec![0u8; 50]).unwrap();
+
+        // No default ignores (tempdirs live under /tmp, which is on the
+        // system-path ignore list) and show hidden (the tempdir itself is
+        // dot-prefixed).
+        let config = ScannerConfig {
+            max_depth: 2,
+            dedupe_hardlinks: true,
+            use_default_ignores: false,
+            show_hidden: true,
+            ..Default::default()
+        };
+        let scanner = Scanner::new(temp_dir.path(), config).unwrap();
+        let (nodes, stats) = scanner.scan().unwrap();
+
+        // Both links carry the same (dev, ino); the single-link file stays None
+        let linked: Vec<_> = nodes
+            .iter()
+            .filter(|n| !n.is_dir && n.hardlink_id.is_some())
+            .collect();
+        assert_eq!(linked.len(), 2);
+        assert_eq!(linked[0].hardlink_id, linked[1].hardlink_id);
+
+        assert_eq!(stats.total_size, 250); // Apparent size counts every entry.
+        assert_eq!(stats.disk_usage, 150); // The shared inode charged once.
+        assert_eq!(stats.hardlink_duplicates, 1);
+    }
+
+    #[test]
+    fn test_allocated_size_and_sparse_detection() {
+        // 1 MiB logical file with only 8 KiB of blocks behind it = sparse.
+        let mut sparse = file_node_with_hardlink("sparse.img", 1024 * 1024, None);
+        sparse.allocated_size = Some(8192);
+        // Small file rounded up to one 4 KiB block by the filesystem.
+        let mut rounded = file_node_with_hardlink("tiny.txt", 10, None);
+        rounded.allocated_size = Some(4096);
+
+        assert!(sparse.is_sparse());
+        assert!(!rounded.is_sparse());
+        assert_eq!(sparse.display_size(), 8192);
+
+        let mut stats = TreeStats::default();
+        stats.update_file(&sparse);
+        stats.update_file(&rounded);
+        assert_eq!(stats.total_size, 1024 * 1024 + 10);
+        assert_eq!(stats.allocated_size, 8192 + 4096);
+        assert_eq!(stats.sparse_files, 1);
+    }
+
+    #[test]
+    fn test_aggregate_dir_sizes_rolls_up_nested_totals() {
+        // DFS order, as the walker produces it:
+        //   root/ (depth 0)
+        //     a.txt (10)
+        //     sub/ (depth 1)
+        //       b.txt (20)
+        //       deep/ (depth 2)
+        //         c.txt (30)
+        //     d.txt (40)
+        let entry = |path: &str, is_dir: bool, depth: usize, size: u64| {
+            let mut node = file_node_with_hardlink(path, size, None);
+            node.is_dir = is_dir;
+            node.depth = depth;
+            node
+        };
+        let mut nodes = vec![
+            entry("root", true, 0, 4096), // Inode size should be replaced.
+            entry("root/a.txt", false, 1, 10),
+            entry("root/sub", true, 1, 4096),
+            entry("root/sub/b.txt", false, 2, 20),
+            entry("root/sub/deep", true, 2, 4096),
+            entry("root/sub/deep/c.txt", false, 3, 30),
+            entry("root/d.txt", false, 1, 40),
+        ];
+
+        aggregate_dir_sizes(&mut nodes);
+
+        assert_eq!(nodes[4].size, 30); // deep = c.txt
+        assert_eq!(nodes[2].size, 50); // sub = b.txt + deep
+        assert_eq!(nodes[0].size, 100); // root = a + sub + d
+        assert_eq!(nodes[1].size, 10); // Files are untouched.
+    }
+
+    #[test]
+    fn test_natural_cmp_orders_digit_runs_by_value() {
+        use std::cmp::Ordering;
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file2"), Ordering::Greater);
+        assert_eq!(natural_cmp("file007", "file7"), Ordering::Equal);
+        assert_eq!(natural_cmp("alpha", "beta"), Ordering::Less);
+        assert_eq!(natural_cmp("file2a", "file2b"), Ordering::Less);
+        assert_eq!(natural_cmp("file", "file2"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_compare_nodes_multi_key_breaks_ties() {
+        // Same extension: "type" alone leaves them tied, "largest" breaks it.
+        let small = file_node_with_hardlink("a.rs", 10, None);
+        let big = file_node_with_hardlink("b.rs", 100, None);
+        let other = file_node_with_hardlink("z.md", 50, None);
+
+        assert_eq!(
+            compare_nodes(&big, &small, "type,largest"),
+            std::cmp::Ordering::Less
+        );
+        // Different extensions: first key decides, second never consulted.
+        assert_eq!(
+            compare_nodes(&other, &big, "type,largest"),
+            std::cmp::Ordering::Less // .md < .rs
+        );
+        // Unknown keys are skipped rather than poisoning the chain.
+        assert_eq!(
+            compare_nodes(&big, &small, "bogus,smallest"),
+            std::cmp::Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_record_pruned_accumulates_per_rule() {
+        let mut stats = TreeStats::default();
+        stats.record_pruned(".gitignore", 100);
+        stats.record_pruned(".gitignore", 50);
+        stats.record_pruned("default ignores", 0);
+
+        assert_eq!(stats.pruned[".gitignore"].entries, 2);
+        assert_eq!(stats.pruned[".gitignore"].bytes, 150);
+        assert_eq!(stats.pruned["default ignores"].entries, 1);
+        assert_eq!(stats.pruned["default ignores"].bytes, 0);
+    }
+
     // Basic test for Scanner creation. More comprehensive tests would involve
     // creating a temporary directory structure and verifying scan results.
+    #[test]
+    fn test_glob_filter_include_exclude_and_braces() {
+        let filter = GlobFilter::new(&[
+            "src/**/*.{rs,toml}".to_string(),
+            "!src/**/generated_*.rs".to_string(),
+        ])
+        .unwrap();
+
+        assert!(filter.matches(Path::new("src/formatters/hex.rs")));
+        assert!(filter.matches(Path::new("src/Cargo.toml")));
+        assert!(!filter.matches(Path::new("src/formatters/generated_grammar.rs")));
+        assert!(!filter.matches(Path::new("docs/readme.md")));
+        // `*` never crosses a separator; `**` is the recursive spelling.
+        assert!(!GlobFilter::new(&["src/*.rs".to_string()])
+            .unwrap()
+            .matches(Path::new("src/nested/deep.rs")));
+        // Exclude-only filters pass everything not excluded.
+        let exclude_only = GlobFilter::new(&["!**/*.log".to_string()]).unwrap();
+        assert!(exclude_only.matches(Path::new("src/main.rs")));
+        assert!(!exclude_only.matches(Path::new("logs/app.log")));
+    }
+
     #[test]
     fn test_scanner_creation_defaults() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -2503,14 +3569,23 @@ mod tests {
             show_hidden: false,
             show_ignored: false,
             find_pattern: None,
+            glob_filter: None,
             file_type_filter: None,
             entry_type_filter: None,
+            skip_special_files: false,
+            git_filter: None,
+            detect_content: false,
             min_size: None,
             max_size: None,
             newer_than: None,
             older_than: None,
             use_default_ignores: true,
             search_keyword: None,
+            search_patterns: Vec::new(),
+            search_regex: false,
+            search_ignore_case: false,
+            search_whole_word: false,
+            search_match_all: false,
             show_filesystems: false,
             sort_field: None,
             top_n: None,
@@ -2523,6 +3598,17 @@ mod tests {
             changes_only: false,
             compare_state: None,
             smart_mode: false,
+            scan_archives: false,
+            dedupe_hardlinks: false,
+            use_allocated_size: false,
+            compute_dir_sizes: false,
+            list_ads: false,
+            collect_xattrs: false,
+            dirs_first: false,
+            files_first: false,
+            prune_patterns: Vec::new(),
+            max_files_per_dir: None,
+            scan_budget: Default::default(),
         };
         let scanner_result = Scanner::new(temp_dir.path(), config);
         assert!(scanner_result.is_ok());
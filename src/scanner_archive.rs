@@ -0,0 +1,193 @@
+// -----------------------------------------------------------------------------
+// Archive Expansion - Peeking inside the luggage! 🧳
+//
+// When `--scan-archives` is on, the scanner doesn't stop at a .zip or .tar.gz -
+// it opens the lid and presents the contents as *virtual* FileNodes, so
+// formatters can render vendored bundles (jars, wheels, zips) like any other
+// subtree. Essential for auditing dependency bundles without unpacking them.
+//
+// Virtual entries are marked with `FilesystemType::Archive` so downstream
+// consumers (and humans reading `--show-filesystems` output) can tell a real
+// file from one living inside an archive.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::{FileCategory, FileNode, FileType, FilesystemType, Scanner};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// Archive extensions we know how to descend into.
+/// Kept deliberately small: formats with solid Rust readers and real-world
+/// use in dependency auditing (vendored zips, jars, wheels, tarballs).
+const ZIP_EXTENSIONS: &[&str] = &["zip", "jar", "whl"];
+
+/// Does this path look like an archive we can expand?
+pub fn is_supported_archive(path: &Path) -> bool {
+    let name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(n) => n.to_ascii_lowercase(),
+        None => return false,
+    };
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".tar") {
+        return true;
+    }
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| ZIP_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Expand an archive file into virtual `FileNode`s.
+///
+/// The returned nodes use paths *under* the archive (e.g.
+/// `vendor/lib.jar/com/example/Foo.class`), with depths relative to the
+/// archive node, so tree formatters render them as a normal subtree.
+/// Errors opening a corrupt archive are returned to the caller, which
+/// treats them as non-fatal (the archive just stays a plain file).
+pub fn expand_archive(archive: &FileNode) -> Result<Vec<FileNode>> {
+    let name = archive
+        .path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    let entries = if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        let file = File::open(&archive.path)?;
+        let gz = flate2::read::GzDecoder::new(BufReader::new(file));
+        list_tar_entries(tar::Archive::new(gz))?
+    } else if name.ends_with(".tar") {
+        let file = File::open(&archive.path)?;
+        list_tar_entries(tar::Archive::new(BufReader::new(file)))?
+    } else {
+        list_zip_entries(&archive.path)?
+    };
+
+    Ok(build_virtual_nodes(archive, entries))
+}
+
+/// A raw archive entry: relative path inside the archive, is_dir, size.
+type ArchiveEntry = (PathBuf, bool, u64);
+
+fn list_zip_entries(path: &Path) -> Result<Vec<ArchiveEntry>> {
+    let file = File::open(path)?;
+    let mut zip = zip::ZipArchive::new(BufReader::new(file))?;
+    let mut entries = Vec::with_capacity(zip.len());
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)?;
+        // enclosed_name() guards against zip-slip paths like "../../etc/passwd"
+        if let Some(rel) = entry.enclosed_name() {
+            entries.push((rel.to_path_buf(), entry.is_dir(), entry.size()));
+        }
+    }
+    Ok(entries)
+}
+
+fn list_tar_entries<R: std::io::Read>(mut tar: tar::Archive<R>) -> Result<Vec<ArchiveEntry>> {
+    let mut entries = Vec::new();
+    for entry in tar.entries()? {
+        let entry = entry?;
+        let rel = entry.path()?.to_path_buf();
+        // Skip absolute or parent-escaping paths (tar-slip protection)
+        if rel.is_absolute() || rel.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+            continue;
+        }
+        let is_dir = entry.header().entry_type().is_dir();
+        let size = entry.header().size().unwrap_or(0);
+        entries.push((rel, is_dir, size));
+    }
+    Ok(entries)
+}
+
+/// Turn raw archive entries into virtual FileNodes rooted at the archive path.
+///
+/// Zip files often omit explicit directory entries, so we synthesize the
+/// intermediate directories ourselves - formatters expect a complete chain.
+fn build_virtual_nodes(archive: &FileNode, entries: Vec<ArchiveEntry>) -> Vec<FileNode> {
+    let mut nodes = Vec::new();
+    let mut seen_dirs: HashSet<PathBuf> = HashSet::new();
+
+    for (rel, is_dir, size) in entries {
+        // Synthesize any missing ancestor directories first.
+        if let Some(parent) = rel.parent() {
+            let mut prefix = PathBuf::new();
+            for component in parent.components() {
+                prefix.push(component);
+                if seen_dirs.insert(prefix.clone()) {
+                    nodes.push(virtual_node(archive, &prefix, true, 0));
+                }
+            }
+        }
+        if is_dir {
+            if seen_dirs.insert(rel.clone()) {
+                nodes.push(virtual_node(archive, &rel, true, 0));
+            }
+        } else {
+            nodes.push(virtual_node(archive, &rel, false, size));
+        }
+    }
+
+    nodes
+}
+
+/// Build a single virtual FileNode for an archive member.
+fn virtual_node(archive: &FileNode, rel: &Path, is_dir: bool, size: u64) -> FileNode {
+    let path = archive.path.join(rel);
+    let depth = archive.depth + rel.components().count();
+    let category = if is_dir {
+        FileCategory::Unknown
+    } else {
+        Scanner::get_file_category(&path, FileType::RegularFile)
+    };
+
+    FileNode {
+        path,
+        is_dir,
+        size,
+        permissions: 0,
+        uid: archive.uid,
+        gid: archive.gid,
+        modified: archive.modified,
+        is_symlink: false,
+        is_hidden: false,
+        permission_denied: false,
+        is_ignored: false,
+        depth,
+        file_type: if is_dir {
+            FileType::Directory
+        } else {
+            FileType::RegularFile
+        },
+        category,
+        search_matches: None,
+        // The marker that says "I live inside an archive"
+        filesystem_type: FilesystemType::Archive,
+        git_branch: None,
+        traversal_context: None,
+        interest: None,
+        security_findings: Vec::new(),
+        change_status: None,
+        content_hash: None,
+        hardlink_id: None,
+        allocated_size: None,
+        xattrs: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_supported_archives() {
+        assert!(is_supported_archive(Path::new("bundle.zip")));
+        assert!(is_supported_archive(Path::new("lib.JAR")));
+        assert!(is_supported_archive(Path::new("pkg-1.0-py3-none-any.whl")));
+        assert!(is_supported_archive(Path::new("vendor.tar.gz")));
+        assert!(is_supported_archive(Path::new("vendor.tgz")));
+        assert!(is_supported_archive(Path::new("vendor.tar")));
+        assert!(!is_supported_archive(Path::new("notes.txt")));
+        assert!(!is_supported_archive(Path::new("archive.rar")));
+    }
+}
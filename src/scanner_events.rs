@@ -0,0 +1,139 @@
+// -----------------------------------------------------------------------------
+// 🔔 Scanner Events - lifecycle hooks for embedding applications
+// -----------------------------------------------------------------------------
+// Library consumers (tree_agent, the terminal explorer, anyone indexing a
+// tree) kept wanting the same thing: "tell me when you enter a directory,
+// hand me every file as you see it, and say when you're done" - without
+// forking the scanner or parsing its streamed output. `ScanHooks` is that
+// contract: an optional bundle of typed callbacks the scanner fires during
+// traversal, attached with `Scanner::with_hooks`.
+//
+// Hooks observe; they cannot steer. Filtering still belongs to
+// `ScannerConfig`, and a scan with no hooks attached costs four `Option`
+// checks per node. Callbacks must be `Send + Sync` because the scanner is
+// shared across threads (the parallel content search pass borrows it).
+// -----------------------------------------------------------------------------
+
+use crate::scanner::{FileNode, TreeStats};
+use std::path::Path;
+
+/// Fired when the traversal enters a directory: (path, depth from root).
+pub type EnterDirHook = Box<dyn Fn(&Path, usize) + Send + Sync>;
+/// Fired for every non-directory node the scan produces.
+pub type FileHook = Box<dyn Fn(&FileNode) + Send + Sync>;
+/// Fired when an entry cannot be read: (path, human-readable reason).
+pub type ErrorHook = Box<dyn Fn(&Path, &str) + Send + Sync>;
+/// Fired once at the end of the scan with the final statistics.
+pub type CompleteHook = Box<dyn Fn(&TreeStats) + Send + Sync>;
+
+/// Optional lifecycle callbacks for a scan, built fluent-style:
+///
+/// ```ignore
+/// let hooks = ScanHooks::new()
+///     .on_enter_dir(|path, depth| println!("{}↳ {}", "  ".repeat(depth), path.display()))
+///     .on_complete(|stats| println!("{} files", stats.total_files));
+/// let scanner = Scanner::new(&root, config)?.with_hooks(hooks);
+/// ```
+#[derive(Default)]
+pub struct ScanHooks {
+    on_enter_dir: Option<EnterDirHook>,
+    on_file: Option<FileHook>,
+    on_error: Option<ErrorHook>,
+    on_complete: Option<CompleteHook>,
+}
+
+impl ScanHooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a callback for every directory the traversal enters.
+    pub fn on_enter_dir(mut self, hook: impl Fn(&Path, usize) + Send + Sync + 'static) -> Self {
+        self.on_enter_dir = Some(Box::new(hook));
+        self
+    }
+
+    /// Register a callback for every file node the scan produces.
+    pub fn on_file(mut self, hook: impl Fn(&FileNode) + Send + Sync + 'static) -> Self {
+        self.on_file = Some(Box::new(hook));
+        self
+    }
+
+    /// Register a callback for unreadable entries (permission denied etc.).
+    pub fn on_error(mut self, hook: impl Fn(&Path, &str) + Send + Sync + 'static) -> Self {
+        self.on_error = Some(Box::new(hook));
+        self
+    }
+
+    /// Register a callback fired once with the final scan statistics.
+    pub fn on_complete(mut self, hook: impl Fn(&TreeStats) + Send + Sync + 'static) -> Self {
+        self.on_complete = Some(Box::new(hook));
+        self
+    }
+
+    // --- Notification sites (called by the scanner) ---
+
+    pub(crate) fn notify_enter_dir(&self, path: &Path, depth: usize) {
+        if let Some(hook) = &self.on_enter_dir {
+            hook(path, depth);
+        }
+    }
+
+    pub(crate) fn notify_file(&self, node: &FileNode) {
+        if let Some(hook) = &self.on_file {
+            hook(node);
+        }
+    }
+
+    pub(crate) fn notify_error(&self, path: &Path, reason: &str) {
+        if let Some(hook) = &self.on_error {
+            hook(path, reason);
+        }
+    }
+
+    pub(crate) fn notify_complete(&self, stats: &TreeStats) {
+        if let Some(hook) = &self.on_complete {
+            hook(stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_unset_hooks_are_noops() {
+        let hooks = ScanHooks::new();
+        hooks.notify_enter_dir(Path::new("/tmp"), 0);
+        hooks.notify_error(Path::new("/tmp/x"), "permission denied");
+        hooks.notify_complete(&TreeStats::default());
+    }
+
+    #[test]
+    fn test_registered_hooks_fire() {
+        let dirs = Arc::new(AtomicUsize::new(0));
+        let completes = Arc::new(AtomicUsize::new(0));
+        let hooks = ScanHooks::new()
+            .on_enter_dir({
+                let dirs = dirs.clone();
+                move |_, _| {
+                    dirs.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+            .on_complete({
+                let completes = completes.clone();
+                move |_| {
+                    completes.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+
+        hooks.notify_enter_dir(Path::new("/a"), 0);
+        hooks.notify_enter_dir(Path::new("/a/b"), 1);
+        hooks.notify_complete(&TreeStats::default());
+        assert_eq!(dirs.load(Ordering::SeqCst), 2);
+        assert_eq!(completes.load(Ordering::SeqCst), 1);
+    }
+}
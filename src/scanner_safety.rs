@@ -2,10 +2,42 @@
 //!
 //! This module provides safety limits and optimizations to prevent
 //! crashes when scanning very large directories like home directories.
+//!
+//! Two kinds of limits live here:
+//! - **Automatic safety nets** (`ScannerSafetyLimits::default()` and
+//!   friends) - generous ceilings that keep an accidental `st /` from
+//!   eating the machine.
+//! - **Explicit budgets** (`ScanBudget`, from `--max-entries`,
+//!   `--max-bytes-scanned`, and `--timeout`) - hard caps for CI, where a
+//!   predictable partial answer beats an unbounded complete one. A budget
+//!   overrides the corresponding automatic limit, the scan stops cleanly,
+//!   and the resulting `TreeStats` is valid but marked `truncated`.
 
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 
+/// Explicit scan budget - the caller's hard caps, all optional.
+///
+/// `None` fields leave the automatic safety limit in charge; `Some`
+/// replaces it. Built from the CLI flags `--max-entries N`,
+/// `--max-bytes-scanned SIZE`, and `--timeout SECONDS`.
+#[derive(Debug, Default, Clone)]
+pub struct ScanBudget {
+    /// Stop after this many entries have been collected.
+    pub max_entries: Option<usize>,
+    /// Stop after this many file bytes have been accounted.
+    pub max_bytes: Option<u64>,
+    /// Stop after this much wall-clock time.
+    pub timeout: Option<Duration>,
+}
+
+impl ScanBudget {
+    /// True when no cap is set - the common case, costing nothing extra.
+    pub fn is_empty(&self) -> bool {
+        self.max_entries.is_none() && self.max_bytes.is_none() && self.timeout.is_none()
+    }
+}
+
 /// Safety limits for directory scanning
 #[derive(Debug, Clone)]
 pub struct ScannerSafetyLimits {
@@ -13,6 +45,8 @@ pub struct ScannerSafetyLimits {
     pub max_files: usize,
     /// Maximum time to spend scanning
     pub max_duration: Duration,
+    /// Maximum number of file bytes to account (0 = unlimited)
+    pub max_bytes: u64,
     /// Maximum memory usage in bytes (estimated)
     pub max_memory_bytes: usize,
     /// Warn when exceeding this many files
@@ -24,6 +58,7 @@ impl Default for ScannerSafetyLimits {
         Self {
             max_files: 1_000_000,                     // 1 million files max by default
             max_duration: Duration::from_secs(300),   // 5 minutes max
+            max_bytes: 0,                             // No byte cap unless budgeted
             max_memory_bytes: 2 * 1024 * 1024 * 1024, // 2GB max
             warn_threshold: 100_000,                  // Warn at 100k files
         }
@@ -36,6 +71,7 @@ impl ScannerSafetyLimits {
         Self {
             max_files: 0,
             max_duration: Duration::from_secs(u64::MAX),
+            max_bytes: 0,
             max_memory_bytes: usize::MAX,
             warn_threshold: usize::MAX,
         }
@@ -46,6 +82,7 @@ impl ScannerSafetyLimits {
         Self {
             max_files: 500_000,                     // 500k files max for home dirs
             max_duration: Duration::from_secs(120), // 2 minutes max
+            max_bytes: 0,                           // No byte cap unless budgeted
             max_memory_bytes: 1024 * 1024 * 1024,   // 1GB max
             warn_threshold: 50_000,                 // Warn at 50k files
         }
@@ -56,19 +93,40 @@ impl ScannerSafetyLimits {
         Self {
             max_files: 100_000,                    // 100k files max for MCP
             max_duration: Duration::from_secs(60), // 1 minute max
+            max_bytes: 0,                          // No byte cap unless budgeted
             max_memory_bytes: 512 * 1024 * 1024,   // 512MB max
             warn_threshold: 10_000,                // Warn at 10k files
         }
     }
+
+    /// Overlay an explicit budget: every cap the caller set replaces the
+    /// automatic one, including tightening *or loosening* it - a CI job
+    /// that asked for 2 million entries meant it.
+    pub fn with_budget(mut self, budget: &ScanBudget) -> Self {
+        if let Some(max_entries) = budget.max_entries {
+            self.max_files = max_entries;
+        }
+        if let Some(max_bytes) = budget.max_bytes {
+            self.max_bytes = max_bytes;
+        }
+        if let Some(timeout) = budget.timeout {
+            self.max_duration = timeout;
+        }
+        self
+    }
 }
 
 /// Tracks safety metrics during scanning
 pub struct ScannerSafetyTracker {
     start_time: Instant,
     file_count: AtomicUsize,
+    bytes_scanned: AtomicU64,
     estimated_memory: AtomicUsize,
     limits: ScannerSafetyLimits,
     warned: AtomicUsize,
+    /// Set the first time `should_continue` says stop - lets the scanner
+    /// mark its stats as truncated after the loop ends.
+    limit_hit: AtomicBool,
 }
 
 impl ScannerSafetyTracker {
@@ -76,9 +134,11 @@ impl ScannerSafetyTracker {
         Self {
             start_time: Instant::now(),
             file_count: AtomicUsize::new(0),
+            bytes_scanned: AtomicU64::new(0),
             estimated_memory: AtomicUsize::new(0),
             limits,
             warned: AtomicUsize::new(0),
+            limit_hit: AtomicBool::new(false),
         }
     }
 
@@ -87,16 +147,28 @@ impl ScannerSafetyTracker {
         // Check file count
         let count = self.file_count.load(Ordering::Relaxed);
         if self.limits.max_files > 0 && count >= self.limits.max_files {
+            self.limit_hit.store(true, Ordering::Relaxed);
             return Err(format!(
-                "Scan aborted: Reached maximum file limit of {} files",
+                "Scan stopped: Reached maximum entry limit of {} entries",
                 self.limits.max_files
             ));
         }
 
+        // Check byte budget
+        let bytes = self.bytes_scanned.load(Ordering::Relaxed);
+        if self.limits.max_bytes > 0 && bytes >= self.limits.max_bytes {
+            self.limit_hit.store(true, Ordering::Relaxed);
+            return Err(format!(
+                "Scan stopped: Reached byte budget of {} bytes",
+                self.limits.max_bytes
+            ));
+        }
+
         // Check duration
         if self.start_time.elapsed() > self.limits.max_duration {
+            self.limit_hit.store(true, Ordering::Relaxed);
             return Err(format!(
-                "Scan aborted: Exceeded maximum duration of {:?}",
+                "Scan stopped: Exceeded maximum duration of {:?}",
                 self.limits.max_duration
             ));
         }
@@ -104,8 +176,9 @@ impl ScannerSafetyTracker {
         // Check memory (estimated)
         let memory = self.estimated_memory.load(Ordering::Relaxed);
         if memory > self.limits.max_memory_bytes {
+            self.limit_hit.store(true, Ordering::Relaxed);
             return Err(format!(
-                "Scan aborted: Estimated memory usage ({} MB) exceeds limit ({} MB)",
+                "Scan stopped: Estimated memory usage ({} MB) exceeds limit ({} MB)",
                 memory / (1024 * 1024),
                 self.limits.max_memory_bytes / (1024 * 1024)
             ));
@@ -131,6 +204,16 @@ impl ScannerSafetyTracker {
             .fetch_add(estimated_node_size, Ordering::Relaxed);
     }
 
+    /// Account file bytes toward the byte budget.
+    pub fn add_bytes(&self, bytes: u64) {
+        self.bytes_scanned.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Did any limit or budget stop the scan early?
+    pub fn limit_hit(&self) -> bool {
+        self.limit_hit.load(Ordering::Relaxed)
+    }
+
     /// Get current stats
     pub fn stats(&self) -> (usize, Duration, usize) {
         (
@@ -165,6 +248,7 @@ mod tests {
         let limits = ScannerSafetyLimits {
             max_files: 10,
             max_duration: Duration::from_secs(1),
+            max_bytes: 0,
             max_memory_bytes: 1024,
             warn_threshold: 5,
         };
@@ -173,13 +257,51 @@ mod tests {
 
         // Should start OK
         assert!(tracker.should_continue().is_ok());
+        assert!(!tracker.limit_hit());
 
         // Add files until we hit the limit
         for _ in 0..10 {
             tracker.add_file(100);
         }
 
-        // Should now fail
+        // Should now fail, and remember that it did
+        assert!(tracker.should_continue().is_err());
+        assert!(tracker.limit_hit());
+    }
+
+    #[test]
+    fn test_budget_overrides_automatic_limits() {
+        let budget = ScanBudget {
+            max_entries: Some(2_000_000),
+            max_bytes: Some(500),
+            timeout: None,
+        };
+        let limits = ScannerSafetyLimits::default().with_budget(&budget);
+
+        // Loosening is allowed - the caller asked for it explicitly
+        assert_eq!(limits.max_files, 2_000_000);
+        assert_eq!(limits.max_bytes, 500);
+        // Unset budget fields leave the automatic limit alone
+        assert_eq!(limits.max_duration, Duration::from_secs(300));
+
+        assert!(ScanBudget::default().is_empty());
+        assert!(!budget.is_empty());
+    }
+
+    #[test]
+    fn test_byte_budget_stops_scan() {
+        let limits = ScannerSafetyLimits::unlimited().with_budget(&ScanBudget {
+            max_entries: None,
+            max_bytes: Some(1000),
+            timeout: None,
+        });
+        let tracker = ScannerSafetyTracker::new(limits);
+
+        tracker.add_bytes(999);
+        assert!(tracker.should_continue().is_ok());
+
+        tracker.add_bytes(1);
         assert!(tracker.should_continue().is_err());
+        assert!(tracker.limit_hit());
     }
 }
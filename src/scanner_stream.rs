@@ -0,0 +1,175 @@
+// -----------------------------------------------------------------------------
+// 🚦 Scanner Stream - ordered output stage for (future) parallel traversal
+// -----------------------------------------------------------------------------
+// Streaming formatters print nodes the moment they arrive, which is exactly
+// why a parallel scanner would interleave them nondeterministically. This
+// module is the fix: workers tag every node with the sequence number the
+// traversal order assigned, and `OrderedEmitter` holds out-of-order arrivals
+// in a bounded reordering buffer until the gap closes - the formatter always
+// sees sequence 0, 1, 2, ... no matter which worker finished first.
+//
+// The buffer reports saturation instead of growing without bound; the
+// dispatch side uses that as backpressure (stop handing out new sequence
+// numbers until the stragglers land). Single-threaded scans pay one BTreeMap
+// lookup per node, which is noise next to the stat() they just did.
+// -----------------------------------------------------------------------------
+
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+
+/// How many out-of-order items the output stage holds before signalling
+/// backpressure. Generous enough for a worker pool's worth of in-flight
+/// directories, small enough that memory stays flat on huge scans.
+pub const DEFAULT_REORDER_CAPACITY: usize = 1024;
+
+/// Bounded reordering buffer: items tagged with sequence numbers go in,
+/// gap-free runs come out in order.
+pub struct ReorderBuffer<T> {
+    /// The sequence number the consumer must see next.
+    next_seq: u64,
+    /// Out-of-order arrivals waiting for the gap to close, keyed by seq.
+    pending: BTreeMap<u64, T>,
+    /// Pending-size threshold for `is_saturated`.
+    capacity: usize,
+}
+
+impl<T> ReorderBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            next_seq: 0,
+            pending: BTreeMap::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Accept one item. Returns the run of items that became releasable -
+    /// empty while the next expected sequence number is still missing,
+    /// the whole backed-up run once it arrives. Duplicate sequence numbers
+    /// overwrite, which a correct producer never does.
+    pub fn push(&mut self, seq: u64, item: T) -> Vec<T> {
+        self.pending.insert(seq, item);
+        let mut released = Vec::new();
+        while let Some(item) = self.pending.remove(&self.next_seq) {
+            released.push(item);
+            self.next_seq += 1;
+        }
+        released
+    }
+
+    /// True once the buffer holds `capacity` or more stalled items - the
+    /// dispatcher should stop assigning new sequence numbers until this
+    /// clears.
+    pub fn is_saturated(&self) -> bool {
+        self.pending.len() >= self.capacity
+    }
+
+    /// Number of items stalled behind a sequence gap.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Drain whatever is left in ascending sequence order. Only correct at
+    /// end of scan: any remaining gaps mean those nodes were dropped (e.g.
+    /// a worker bailed), and holding their successors forever helps nobody.
+    pub fn flush(&mut self) -> Vec<T> {
+        let drained: Vec<T> = std::mem::take(&mut self.pending).into_values().collect();
+        self.next_seq += drained.len() as u64;
+        drained
+    }
+}
+
+/// An `mpsc::Sender` front-end that restores sequence order before the
+/// receiver (the streaming formatter) sees anything.
+pub struct OrderedEmitter<T> {
+    buffer: ReorderBuffer<T>,
+    sender: mpsc::Sender<T>,
+}
+
+impl<T> OrderedEmitter<T> {
+    pub fn new(sender: mpsc::Sender<T>) -> Self {
+        Self::with_capacity(sender, DEFAULT_REORDER_CAPACITY)
+    }
+
+    pub fn with_capacity(sender: mpsc::Sender<T>, capacity: usize) -> Self {
+        Self {
+            buffer: ReorderBuffer::new(capacity),
+            sender,
+        }
+    }
+
+    /// Submit one item under its traversal sequence number, forwarding any
+    /// newly in-order run downstream. Returns false once the receiver has
+    /// disconnected - the scan should stop, same contract as
+    /// `mpsc::Sender::send`.
+    pub fn emit(&mut self, seq: u64, item: T) -> bool {
+        for ready in self.buffer.push(seq, item) {
+            if self.sender.send(ready).is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Backpressure signal for the dispatch side.
+    pub fn is_saturated(&self) -> bool {
+        self.buffer.is_saturated()
+    }
+
+    /// Forward everything still buffered (end of scan).
+    pub fn finish(mut self) -> bool {
+        for ready in self.buffer.flush() {
+            if self.sender.send(ready).is_err() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_order_passthrough() {
+        let mut buffer = ReorderBuffer::new(4);
+        assert_eq!(buffer.push(0, "a"), vec!["a"]);
+        assert_eq!(buffer.push(1, "b"), vec!["b"]);
+        assert_eq!(buffer.pending_len(), 0);
+    }
+
+    #[test]
+    fn test_out_of_order_released_as_a_run() {
+        let mut buffer = ReorderBuffer::new(4);
+        assert!(buffer.push(2, "c").is_empty());
+        assert!(buffer.push(1, "b").is_empty());
+        assert_eq!(buffer.pending_len(), 2);
+        // The missing head releases the whole backed-up run at once.
+        assert_eq!(buffer.push(0, "a"), vec!["a", "b", "c"]);
+        assert_eq!(buffer.push(3, "d"), vec!["d"]);
+    }
+
+    #[test]
+    fn test_saturation_and_flush() {
+        let mut buffer = ReorderBuffer::new(2);
+        buffer.push(5, "f");
+        assert!(!buffer.is_saturated());
+        buffer.push(3, "d");
+        assert!(buffer.is_saturated());
+        // End-of-scan drain ignores the gaps but keeps ascending order.
+        assert_eq!(buffer.flush(), vec!["d", "f"]);
+        assert_eq!(buffer.pending_len(), 0);
+    }
+
+    #[test]
+    fn test_emitter_orders_across_channel() {
+        let (tx, rx) = mpsc::channel();
+        let mut emitter = OrderedEmitter::with_capacity(tx, 8);
+        assert!(emitter.emit(1, "b"));
+        assert!(emitter.emit(0, "a"));
+        assert!(emitter.emit(2, "c"));
+        assert!(emitter.finish());
+        let received: Vec<_> = rx.iter().collect();
+        assert_eq!(received, vec!["a", "b", "c"]);
+    }
+}
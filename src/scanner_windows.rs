@@ -0,0 +1,255 @@
+// -----------------------------------------------------------------------------
+// 🪟 WINDOWS NATIVE SMARTS - Junctions, Streams, and Drive Letters, Oh My!
+//
+// The POSIX world gets statfs magic numbers and symlink bits for free; this
+// module gives Windows the same respect. Three things live here:
+//
+//   1. Reparse-point detection - junctions and mount points look like plain
+//      directories to naive walkers and cause infinite loops. We flag them
+//      like symlinks so traversal treats them with the same care.
+//   2. Alternate data streams (`--ads`) - NTFS files can carry named side
+//      channels (`file.txt:Zone.Identifier`). Opt-in, they appear as
+//      virtual child entries, the same trick `--scan-archives` uses.
+//   3. Volume detection - NTFS/ReFS/FAT32/exFAT per drive letter via
+//      `GetVolumeInformationW`, feeding the show_filesystems indicators.
+//
+// Everything degrades to a cheap no-op on non-Windows platforms, so callers
+// never need their own cfg gates.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::{FileNode, FilesystemType};
+use std::fs;
+use std::path::Path;
+
+/// FILE_ATTRIBUTE_REPARSE_POINT from winnt.h - set on junctions, mount
+/// points, and symlinks alike.
+#[cfg(windows)]
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x0400;
+
+/// Is this entry a reparse point (junction, mount point, symlink)?
+///
+/// Junctions predate real symlinks and `Metadata::is_symlink` misses some
+/// of them, so we check the attribute bit directly. Always `false` off
+/// Windows.
+pub fn is_reparse_point(metadata: &fs::Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = metadata;
+        false
+    }
+}
+
+/// Detect the filesystem behind a path's drive letter (NTFS, ReFS, FAT32,
+/// exFAT). `None` when the volume can't be queried or the platform isn't
+/// Windows - the caller falls back to its own heuristics.
+pub fn detect_filesystem(path: &Path) -> Option<FilesystemType> {
+    #[cfg(windows)]
+    {
+        use std::path::{Component, Prefix};
+
+        // GetVolumeInformationW wants the volume root ("C:\"), not a file.
+        let root = match path.components().next() {
+            Some(Component::Prefix(prefix)) => match prefix.kind() {
+                Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => {
+                    format!("{}:\\", letter as char)
+                }
+                _ => return None, // UNC shares etc. - treat as unknown.
+            },
+            _ => return None,
+        };
+
+        let mut root_wide: Vec<u16> = root.encode_utf16().collect();
+        root_wide.push(0);
+        let mut fs_name = [0u16; 32];
+
+        let ok = unsafe {
+            ffi::GetVolumeInformationW(
+                root_wide.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                fs_name.as_mut_ptr(),
+                fs_name.len() as u32,
+            )
+        };
+        if ok == 0 {
+            return None;
+        }
+
+        let len = fs_name.iter().position(|&c| c == 0).unwrap_or(fs_name.len());
+        match String::from_utf16_lossy(&fs_name[..len]).to_uppercase().as_str() {
+            "NTFS" => Some(FilesystemType::Ntfs),
+            "REFS" => Some(FilesystemType::Refs),
+            "FAT32" | "FAT" => Some(FilesystemType::Fat32),
+            "EXFAT" => Some(FilesystemType::ExFat),
+            _ => None,
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Enumerate a file's alternate data streams as virtual child nodes
+/// (`--ads`). The unnamed `::$DATA` stream is the file itself and is
+/// skipped. Empty off Windows or on filesystems without streams.
+pub fn stream_nodes(node: &FileNode) -> Vec<FileNode> {
+    list_streams(&node.path)
+        .into_iter()
+        .map(|(name, size)| {
+            let mut stream = node.clone();
+            // "host.txt:Zone.Identifier" - the colon form users know.
+            stream.path = std::path::PathBuf::from(format!("{}{}", node.path.display(), name));
+            stream.size = size;
+            stream.is_dir = false;
+            stream.depth = node.depth + 1;
+            stream.search_matches = None;
+            stream
+        })
+        .collect()
+}
+
+/// Raw stream listing: `(":name", size)` pairs for each *named* stream,
+/// with the `:$DATA` type suffix already trimmed.
+fn list_streams(path: &Path) -> Vec<(String, u64)> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        let mut path_wide: Vec<u16> = path.as_os_str().encode_wide().collect();
+        path_wide.push(0);
+
+        let mut data = ffi::Win32FindStreamData::default();
+        let handle = unsafe {
+            // 0 = FindStreamInfoStandard, the only documented info level.
+            ffi::FindFirstStreamW(path_wide.as_ptr(), 0, &mut data, 0)
+        };
+        if handle == ffi::INVALID_HANDLE_VALUE {
+            return Vec::new();
+        }
+
+        let mut streams = Vec::new();
+        loop {
+            let len = data
+                .stream_name
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(data.stream_name.len());
+            let name = String::from_utf16_lossy(&data.stream_name[..len]);
+
+            // "::$DATA" is the default stream - only named streams are news.
+            if name != "::$DATA" {
+                streams.push((
+                    name.trim_end_matches(":$DATA").to_string(),
+                    data.stream_size.max(0) as u64,
+                ));
+            }
+
+            if unsafe { ffi::FindNextStreamW(handle, &mut data) } == 0 {
+                break;
+            }
+        }
+        unsafe { ffi::FindClose(handle) };
+        streams
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = path;
+        Vec::new()
+    }
+}
+
+#[cfg(windows)]
+mod ffi {
+    use std::ffi::c_void;
+
+    pub const INVALID_HANDLE_VALUE: *mut c_void = -1isize as *mut c_void;
+
+    /// WIN32_FIND_STREAM_DATA from fileapi.h.
+    #[repr(C)]
+    pub struct Win32FindStreamData {
+        pub stream_size: i64,
+        pub stream_name: [u16; 296], // MAX_PATH + 36, per the Windows headers.
+    }
+
+    impl Default for Win32FindStreamData {
+        fn default() -> Self {
+            Self {
+                stream_size: 0,
+                stream_name: [0; 296],
+            }
+        }
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn GetVolumeInformationW(
+            root_path_name: *const u16,
+            volume_name: *mut u16,
+            volume_name_size: u32,
+            volume_serial: *mut u32,
+            max_component_length: *mut u32,
+            filesystem_flags: *mut u32,
+            filesystem_name: *mut u16,
+            filesystem_name_size: u32,
+        ) -> i32;
+
+        pub fn FindFirstStreamW(
+            file_name: *const u16,
+            info_level: u32,
+            find_stream_data: *mut Win32FindStreamData,
+            flags: u32,
+        ) -> *mut c_void;
+
+        pub fn FindNextStreamW(
+            find_stream: *mut c_void,
+            find_stream_data: *mut Win32FindStreamData,
+        ) -> i32;
+
+        pub fn FindClose(find_file: *mut c_void) -> i32;
+    }
+}
+
+// CI exercises these on the windows-latest runners; they are no-ops to
+// compile (and meaningless to run) anywhere else.
+#[cfg(all(test, windows))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_filesystem_on_system_drive() {
+        let system = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".into());
+        let root = std::path::PathBuf::from(format!("{}\\", system));
+        // The system drive is always NTFS or ReFS on supported Windows.
+        assert!(matches!(
+            detect_filesystem(&root),
+            Some(FilesystemType::Ntfs) | Some(FilesystemType::Refs)
+        ));
+    }
+
+    #[test]
+    fn test_list_streams_finds_named_streams() {
+        let dir = std::env::temp_dir().join("st-ads-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("host.txt");
+        std::fs::write(&file, b"main").unwrap();
+        // The colon syntax writes straight into an alternate stream on NTFS.
+        std::fs::write(dir.join("host.txt:extra"), b"sidecar").unwrap();
+
+        let streams = list_streams(&file);
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].0, ":extra");
+        assert_eq!(streams[0].1, 7);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,379 @@
+// -----------------------------------------------------------------------------
+// 🗂️ Search Index - persistent trigrams for instant repeated queries
+// -----------------------------------------------------------------------------
+// `--search` walks every text file on every invocation. For trees you grep
+// again and again (your main project, say), `st index build` stores a
+// trigram index under `~/.st/index/` - every 3-byte window of every text
+// file, case-folded, mapped to the files containing it. At query time a
+// literal pattern's own trigrams prune the candidate list: a file that is
+// unchanged since indexing and lacks even one of the pattern's trigrams
+// cannot match, so it is never opened.
+//
+// The index only ever *excludes* work, never invents results: files that
+// are new, changed (mtime/size mismatch), or unindexed are always searched
+// for real, and regex or sub-3-byte patterns fall back to the full scan.
+// Stale entries therefore cost speed, not correctness.
+// -----------------------------------------------------------------------------
+
+use crate::content_search::{looks_binary, SearchQuery, MAX_SEARCH_FILE_SIZE};
+use crate::scanner::FileNode;
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bump when the on-disk layout changes; older files are rebuilt, not parsed.
+const CURRENT_VERSION: u32 = 1;
+
+/// Metadata snapshot of one indexed file - the invalidation key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    /// Path relative to the indexed root.
+    path: PathBuf,
+    /// Modification time at index build, in whole seconds.
+    mtime_secs: u64,
+    /// Size at index build, in bytes.
+    size: u64,
+}
+
+/// The persistent trigram index for one tree.
+#[derive(Serialize, Deserialize)]
+pub struct SearchIndex {
+    version: u32,
+    /// Canonicalized root this index covers.
+    root: PathBuf,
+    /// When the index was built (seconds since the epoch).
+    built_at: u64,
+    /// Indexed files; positions are the ids used in `postings`.
+    files: Vec<IndexedFile>,
+    /// Trigram -> sorted list of file ids containing it.
+    postings: HashMap<u32, Vec<u32>>,
+    /// Rebuilt after load: relative path -> file id.
+    #[serde(skip)]
+    by_path: HashMap<PathBuf, u32>,
+}
+
+/// Freshness report for `st index status`.
+pub struct IndexStatus {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub built_at: u64,
+    pub files: usize,
+    pub trigrams: usize,
+    /// Indexed files whose mtime/size no longer match (searched fresh).
+    pub stale: usize,
+    /// Indexed files that no longer exist on disk.
+    pub missing: usize,
+}
+
+impl SearchIndex {
+    /// Where the index for `root` lives - same `~/.st` naming scheme as
+    /// scan states.
+    pub fn index_path(root: &Path) -> PathBuf {
+        let index_dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".st")
+            .join("index");
+
+        let safe_name = root
+            .to_string_lossy()
+            .replace(['/', '\\', ':'], "_")
+            .trim_matches('_')
+            .to_string();
+
+        index_dir.join(format!("{}.index.bin", safe_name))
+    }
+
+    /// Build an index from already-scanned nodes. Reads every candidate
+    /// text file once (in parallel); binary and oversized files are left
+    /// out, which just means searches always visit them.
+    pub fn build(root: &Path, nodes: &[FileNode]) -> Result<Self> {
+        let extracted: Vec<(PathBuf, u64, u64, HashSet<u32>)> = nodes
+            .par_iter()
+            .filter(|node| {
+                !node.is_dir
+                    && !node.is_symlink
+                    && !node.permission_denied
+                    && node.size > 0
+                    && node.size <= MAX_SEARCH_FILE_SIZE
+            })
+            .filter_map(|node| {
+                let relative = node.path.strip_prefix(root).ok()?.to_path_buf();
+                let contents = std::fs::read(&node.path).ok()?;
+                if looks_binary(&contents) {
+                    return None;
+                }
+                let mtime = node
+                    .modified
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                Some((relative, mtime, node.size, trigrams(&contents)))
+            })
+            .collect();
+
+        let mut files = Vec::with_capacity(extracted.len());
+        let mut postings: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (path, mtime_secs, size, grams) in extracted {
+            let id = files.len() as u32;
+            files.push(IndexedFile {
+                path,
+                mtime_secs,
+                size,
+            });
+            for gram in grams {
+                postings.entry(gram).or_default().push(id);
+            }
+        }
+        // Ids arrive in insertion order per trigram, so each list is
+        // already sorted for the binary search in `file_has_trigram`.
+
+        let mut index = Self {
+            version: CURRENT_VERSION,
+            root: root.to_path_buf(),
+            built_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            files,
+            postings,
+            by_path: HashMap::new(),
+        };
+        index.rebuild_path_map();
+        Ok(index)
+    }
+
+    /// Persist to `~/.st/index/`, creating the directory as needed.
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::index_path(&self.root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(&path)?;
+        bincode::serialize_into(BufWriter::new(file), self)?;
+        Ok(path)
+    }
+
+    /// Load the stored index for `root`, or None when there is none (or it
+    /// has an incompatible version - stale speed-ups are not worth a parse
+    /// error).
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = Self::index_path(root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let file = File::open(&path)?;
+        let mut index: Self = match bincode::deserialize_from(BufReader::new(file)) {
+            Ok(index) => index,
+            Err(_) => return Ok(None),
+        };
+        if index.version != CURRENT_VERSION {
+            return Ok(None);
+        }
+        index.rebuild_path_map();
+        Ok(Some(index))
+    }
+
+    /// Delete the stored index for `root`. Returns whether one existed.
+    pub fn clear(root: &Path) -> Result<bool> {
+        let path = Self::index_path(root);
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Freshness report for `st index status`.
+    pub fn status(root: &Path) -> Result<Option<IndexStatus>> {
+        let Some(index) = Self::load(root)? else {
+            return Ok(None);
+        };
+        let path = Self::index_path(root);
+        let size_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        let mut stale = 0;
+        let mut missing = 0;
+        for file in &index.files {
+            match std::fs::metadata(root.join(&file.path)) {
+                Ok(meta) => {
+                    let mtime = meta
+                        .modified()
+                        .ok()
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    if mtime != file.mtime_secs || meta.len() != file.size {
+                        stale += 1;
+                    }
+                }
+                Err(_) => missing += 1,
+            }
+        }
+
+        Ok(Some(IndexStatus {
+            path,
+            size_bytes,
+            built_at: index.built_at,
+            files: index.files.len(),
+            trigrams: index.postings.len(),
+            stale,
+            missing,
+        }))
+    }
+
+    /// Can this file possibly match the query? `true` means "search it for
+    /// real" - returned for every unindexed, changed, or unknowable case,
+    /// so the index can only skip work, never drop matches.
+    pub fn may_match(&self, node: &FileNode, query: &SearchQuery) -> bool {
+        // Regex patterns have no usable trigram set.
+        let Some(patterns) = query.literal_patterns() else {
+            return true;
+        };
+
+        let Some(relative) = node.path.strip_prefix(&self.root).ok() else {
+            return true;
+        };
+        let Some(&id) = self.by_path.get(relative) else {
+            return true; // New file since the index was built.
+        };
+
+        // Invalidation: any metadata drift means the trigrams are stale.
+        let file = &self.files[id as usize];
+        let mtime = node
+            .modified
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(u64::MAX);
+        if mtime != file.mtime_secs || node.size != file.size {
+            return true;
+        }
+
+        // Per-pattern verdicts: a pattern "may match" unless the file is
+        // provably missing one of its trigrams. Sub-3-byte patterns have
+        // no trigrams, hence always may match.
+        let pattern_may_match = |pattern: &String| -> bool {
+            trigrams(&pattern.to_ascii_lowercase().into_bytes())
+                .iter()
+                .all(|gram| self.file_has_trigram(id, *gram))
+        };
+
+        if query.requires_all() {
+            patterns.iter().all(pattern_may_match)
+        } else {
+            patterns.iter().any(pattern_may_match)
+        }
+    }
+
+    fn file_has_trigram(&self, id: u32, gram: u32) -> bool {
+        self.postings
+            .get(&gram)
+            .is_some_and(|ids| ids.binary_search(&id).is_ok())
+    }
+
+    fn rebuild_path_map(&mut self) {
+        self.by_path = self
+            .files
+            .iter()
+            .enumerate()
+            .map(|(id, file)| (file.path.clone(), id as u32))
+            .collect();
+    }
+}
+
+/// Every case-folded 3-byte window in `text`.
+fn trigrams(text: &[u8]) -> HashSet<u32> {
+    text.windows(3)
+        .map(|w| {
+            let (a, b, c) = (
+                w[0].to_ascii_lowercase(),
+                w[1].to_ascii_lowercase(),
+                w[2].to_ascii_lowercase(),
+            );
+            ((a as u32) << 16) | ((b as u32) << 8) | (c as u32)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{Scanner, ScannerConfig};
+
+    fn scan(root: &Path) -> Vec<FileNode> {
+        // No default ignores (tempdirs live under /tmp, a system ignore
+        // path) and show hidden (the tempdir itself is dot-prefixed).
+        let config = ScannerConfig {
+            max_depth: 10,
+            use_default_ignores: false,
+            show_hidden: true,
+            ..ScannerConfig::default()
+        };
+        Scanner::new(root, config).unwrap().scan().unwrap().0
+    }
+
+    #[test]
+    fn test_trigram_extraction_case_folds() {
+        let grams = trigrams(b"AbC");
+        assert_eq!(grams.len(), 1);
+        assert!(grams.contains(&(((b'a' as u32) << 16) | ((b'b' as u32) << 8) | b'c' as u32)));
+    }
+
+    #[test]
+    fn test_index_prunes_absent_literals_but_not_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("hit.txt"), "the needle is here\n").unwrap();
+        std::fs::write(root.join("miss.txt"), "nothing interesting\n").unwrap();
+
+        let nodes = scan(&root);
+        let index = SearchIndex::build(&root, &nodes).unwrap();
+        let query = SearchQuery::literal("needle").unwrap();
+
+        let node = |name: &str| nodes.iter().find(|n| n.path.ends_with(name)).unwrap();
+        assert!(index.may_match(node("hit.txt"), &query));
+        assert!(!index.may_match(node("miss.txt"), &query));
+
+        // A changed file must be searched fresh even without the trigrams.
+        std::fs::write(root.join("miss.txt"), "still nothing, but longer\n").unwrap();
+        let rescanned = scan(&root);
+        let changed = rescanned
+            .iter()
+            .find(|n| n.path.ends_with("miss.txt"))
+            .unwrap();
+        assert!(index.may_match(changed, &query));
+
+        // Regex queries never consult the index.
+        let regex_query =
+            SearchQuery::build(&["need.e".to_string()], true, false, false, false).unwrap();
+        assert!(index.may_match(node("miss.txt"), &regex_query));
+    }
+
+    #[test]
+    fn test_save_load_clear_roundtrip() {
+        // Index paths derive from the root, so a unique temp root keeps
+        // this test isolated.
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("a.txt"), "alpha beta gamma\n").unwrap();
+
+        let nodes = scan(&root);
+        let index = SearchIndex::build(&root, &nodes).unwrap();
+        index.save().unwrap();
+
+        let loaded = SearchIndex::load(&root).unwrap().expect("index saved");
+        assert_eq!(loaded.files.len(), index.files.len());
+        let status = SearchIndex::status(&root).unwrap().expect("index saved");
+        assert_eq!(status.files, 1);
+        assert_eq!(status.missing, 0);
+
+        assert!(SearchIndex::clear(&root).unwrap());
+        assert!(SearchIndex::load(&root).unwrap().is_none());
+        assert!(!SearchIndex::clear(&root).unwrap());
+    }
+}
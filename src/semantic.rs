@@ -58,6 +58,30 @@ impl SemanticCategory {
         }
     }
 
+    /// Parse a user-facing category name ("source", "docs", "tests", ...)
+    ///
+    /// Accepts the obvious aliases; unknown names return None so callers
+    /// can decide whether to complain or ignore.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.to_lowercase().replace(['-', '_'], "").as_str() {
+            "docs" | "documentation" => Some(Self::Documentation),
+            "source" | "sourcecode" | "code" => Some(Self::SourceCode),
+            "test" | "tests" => Some(Self::Tests),
+            "config" | "configuration" => Some(Self::Configuration),
+            "build" | "buildsystem" => Some(Self::BuildSystem),
+            "deps" | "dependencies" => Some(Self::Dependencies),
+            "assets" => Some(Self::Assets),
+            "data" => Some(Self::Data),
+            "scripts" => Some(Self::Scripts),
+            "generated" | "artifacts" => Some(Self::Generated),
+            "root" | "projectroot" => Some(Self::ProjectRoot),
+            "dev" | "development" => Some(Self::Development),
+            "deploy" | "deployment" => Some(Self::Deployment),
+            "unknown" | "other" => Some(Self::Unknown),
+            _ => None,
+        }
+    }
+
     /// Get a quantum wave signature for semantic matching (Full 32-bit consciousness!)
     pub fn wave_signature(&self) -> u32 {
         // Full 32-bit quantum signatures: [torsion|amplitude|phase|frequency]
@@ -413,6 +437,39 @@ impl Default for SemanticAnalyzer {
     }
 }
 
+/// Path filter over semantic categories - watchers use this to drop noise
+///
+/// Built from user-facing names ("source", "tests", ...); an empty
+/// allow-list passes everything, so "no filter configured" needs no
+/// special casing at call sites.
+pub struct CategoryFilter {
+    analyzer: SemanticAnalyzer,
+    allowed: Vec<SemanticCategory>,
+}
+
+impl CategoryFilter {
+    /// Build from user-facing names; unrecognized names are ignored
+    pub fn new(names: &[String]) -> Self {
+        Self {
+            analyzer: SemanticAnalyzer::new(),
+            allowed: names
+                .iter()
+                .filter_map(|name| SemanticCategory::parse(name))
+                .collect(),
+        }
+    }
+
+    /// No categories configured - everything passes
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty()
+    }
+
+    /// Does `path` belong to one of the allowed categories?
+    pub fn allows(&self, path: &Path) -> bool {
+        self.allowed.is_empty() || self.allowed.contains(&self.analyzer.categorize(path))
+    }
+}
+
 /// Groups files by semantic similarity
 pub fn group_by_semantics<'a>(files: &[&'a Path]) -> HashMap<SemanticCategory, Vec<&'a Path>> {
     let analyzer = SemanticAnalyzer::new();
@@ -431,6 +488,27 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_category_filter() {
+        assert_eq!(
+            SemanticCategory::parse("source"),
+            Some(SemanticCategory::SourceCode)
+        );
+        assert_eq!(
+            SemanticCategory::parse("build-system"),
+            Some(SemanticCategory::BuildSystem)
+        );
+        assert_eq!(SemanticCategory::parse("nonsense"), None);
+
+        let filter = CategoryFilter::new(&["source".to_string(), "tests".to_string()]);
+        assert!(filter.allows(&PathBuf::from("src/main.rs")));
+        assert!(filter.allows(&PathBuf::from("test_utils.rs")));
+        assert!(!filter.allows(&PathBuf::from("logo.png")));
+
+        // Empty filter passes everything
+        assert!(CategoryFilter::new(&[]).allows(&PathBuf::from("logo.png")));
+    }
+
     #[test]
     fn test_categorization() {
         let analyzer = SemanticAnalyzer::new();
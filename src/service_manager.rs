@@ -5,7 +5,8 @@ use anyhow::{Context, Result};
 use std::env;
 use std::fs;
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use crate::exec_policy;
+use std::process::Stdio;
 use tracing::{error, info, warn};
 
 // =============================================================================
@@ -227,9 +228,8 @@ fn launchd_status() -> Result<()> {
     }
 
     // Check if running
-    let output = Command::new("launchctl")
-        .args(["list"])
-        .output();
+    let output = exec_policy::command("launchctl")
+        .and_then(|mut cmd| cmd.args(["list"]).output().map_err(Into::into));
 
     if let Ok(out) = output {
         let list = String::from_utf8_lossy(&out.stdout);
@@ -288,9 +288,8 @@ fn which_std() -> Result<PathBuf> {
     }
 
     // Try PATH
-    let output = Command::new("which")
-        .arg("std")
-        .output();
+    let output = exec_policy::command("which")
+        .and_then(|mut cmd| cmd.arg("std").output().map_err(Into::into));
 
     if let Ok(out) = output {
         if out.status.success() {
@@ -303,10 +302,11 @@ fn which_std() -> Result<PathBuf> {
     Ok(PathBuf::from("/usr/local/bin/std"))
 }
 
-/// Run a shell command and log its output.
+/// Run a shell command and log its output. Goes through the exec policy,
+/// so --no-exec and the allowlist apply to every `st service` action.
 fn run_command(command: &str, args: &[&str]) -> Result<()> {
     info!("Running command: {} {}", command, args.join(" "));
-    let mut cmd = Command::new(command);
+    let mut cmd = exec_policy::command(command)?;
     cmd.args(args);
     cmd.stdout(Stdio::inherit());
     cmd.stderr(Stdio::inherit());
@@ -604,10 +604,16 @@ pub fn verify_gpg_signature() -> SignatureStatus {
         return SignatureStatus::Unsigned;
     }
 
-    // Try to verify with gpg
-    let output = Command::new("gpg")
-        .args(["--verify", sig_path.to_string_lossy().as_ref(), binary_path.to_string_lossy().as_ref()])
-        .output();
+    // Try to verify with gpg (policy-checked; --no-exec reads as unavailable)
+    let output = exec_policy::command("gpg").and_then(|mut cmd| {
+        cmd.args([
+            "--verify",
+            sig_path.to_string_lossy().as_ref(),
+            binary_path.to_string_lossy().as_ref(),
+        ])
+        .output()
+        .map_err(Into::into)
+    });
 
     match output {
         Ok(result) => {
@@ -761,7 +767,7 @@ pub fn guardian_install() -> Result<()> {
             .context("Could not determine executable path")?;
 
         // Re-run with sudo
-        let status = Command::new("sudo")
+        let status = exec_policy::command("sudo")?
             .args([exe_path.to_string_lossy().as_ref(), "--guardian-install"])
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
@@ -787,7 +793,7 @@ pub fn guardian_install() -> Result<()> {
             .context("Failed to copy binary to /usr/local/bin")?;
 
         // Make executable
-        Command::new("chmod")
+        exec_policy::command("chmod")?
             .args(["755", "/usr/local/bin/st"])
             .status()?;
     }
@@ -840,7 +846,7 @@ pub fn guardian_uninstall() -> Result<()> {
     if !is_root() {
         // Re-run with sudo
         let exe_path = std::env::current_exe()?;
-        let status = Command::new("sudo")
+        let status = exec_policy::command("sudo")?
             .args([exe_path.to_string_lossy().as_ref(), "--guardian-uninstall"])
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
@@ -887,9 +893,11 @@ pub fn guardian_status() -> Result<()> {
     }
 
     // Show systemctl status
-    let output = Command::new("systemctl")
-        .args(["status", GUARDIAN_SERVICE_NAME, "--no-pager"])
-        .output();
+    let output = exec_policy::command("systemctl").and_then(|mut cmd| {
+        cmd.args(["status", GUARDIAN_SERVICE_NAME, "--no-pager"])
+            .output()
+            .map_err(Into::into)
+    });
 
     match output {
         Ok(out) => {
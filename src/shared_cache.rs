@@ -0,0 +1,307 @@
+// -----------------------------------------------------------------------------
+// 📦 SHARED CACHE - One Host, Many Users, Zero Elbow Fights! 🤝
+//
+// On a CI runner ten users can hammer `st` at once, and a single cache
+// directory turns into a demolition derby. This module gives the daemon a
+// cache that survives company: a shared root (or the private ~/.st/cache),
+// one namespace per user underneath it, a simple lock-file protocol so
+// writers never interleave, and a per-user byte quota so nobody's cache
+// eats the disk while everyone else watches.
+//
+// Writes are temp-file + rename, so readers never need the lock - they
+// either see the old entry or the new one, never half of each.
+// -----------------------------------------------------------------------------
+
+use crate::config::CacheConfig;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long we wait for another writer before giving up.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A lock file older than this belongs to a crashed process - steal it.
+const LOCK_STALE: Duration = Duration::from_secs(30);
+
+/// Poll interval while waiting on a held lock.
+const LOCK_RETRY: Duration = Duration::from_millis(50);
+
+/// A cache directory that tolerates multiple users on one host.
+pub struct SharedCache {
+    /// This user's private namespace under the cache root.
+    user_dir: PathBuf,
+    /// Per-user quota in bytes; oldest entries are evicted past it.
+    max_bytes: u64,
+}
+
+impl SharedCache {
+    /// Open (creating if needed) the cache for the current user.
+    ///
+    /// With `shared_dir` configured, entries live in
+    /// `<shared_dir>/users/<uid>` so users never collide; otherwise the
+    /// private `~/.st/cache` is used. If the shared root can't be created
+    /// (read-only mount, missing perms) we fall back to the private dir
+    /// rather than failing the daemon.
+    pub fn open(config: &CacheConfig) -> Result<Self> {
+        let user_dir = match &config.shared_dir {
+            Some(shared_root) => match Self::open_shared(shared_root) {
+                Ok(dir) => dir,
+                Err(e) => {
+                    tracing::warn!(
+                        "Shared cache {} unavailable ({}), using private cache",
+                        shared_root.display(),
+                        e
+                    );
+                    Self::private_dir()?
+                }
+            },
+            None => Self::private_dir()?,
+        };
+        fs::create_dir_all(&user_dir)
+            .with_context(|| format!("Failed to create cache dir {}", user_dir.display()))?;
+
+        // Our namespace is ours alone - no reason for other users to peek.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&user_dir, fs::Permissions::from_mode(0o700));
+        }
+
+        Ok(Self {
+            user_dir,
+            max_bytes: config.max_bytes,
+        })
+    }
+
+    /// Carve out this user's namespace under a shared root.
+    fn open_shared(shared_root: &Path) -> Result<PathBuf> {
+        let users = shared_root.join("users");
+        fs::create_dir_all(&users)?;
+
+        // Like /tmp: everyone may enter, only owners may remove (sticky bit).
+        // Best-effort - the admin may have set something stricter on purpose.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&users, fs::Permissions::from_mode(0o1777));
+        }
+
+        Ok(users.join(Self::user_namespace()))
+    }
+
+    /// The per-user namespace: uid on unix (stable even when $USER lies),
+    /// username elsewhere.
+    fn user_namespace() -> String {
+        #[cfg(unix)]
+        {
+            format!("uid-{}", unsafe { libc::getuid() })
+        }
+        #[cfg(not(unix))]
+        {
+            std::env::var("USERNAME").unwrap_or_else(|_| "default".to_string())
+        }
+    }
+
+    /// Private fallback: ~/.st/cache (same place the rest of st keeps state).
+    fn private_dir() -> Result<PathBuf> {
+        Ok(dirs::home_dir()
+            .context("Could not find home directory")?
+            .join(".st")
+            .join("cache"))
+    }
+
+    /// Where this user's entries live (mostly for logging).
+    pub fn user_dir(&self) -> &Path {
+        &self.user_dir
+    }
+
+    /// Store one entry, atomically, under the writer lock. Evicts oldest
+    /// entries afterwards if the quota is blown.
+    pub fn store(&self, key: &str, bytes: &[u8]) -> Result<PathBuf> {
+        let _guard = self.lock()?;
+
+        let path = self.user_dir.join(sanitize_key(key));
+        let tmp = self.user_dir.join(format!(".tmp-{}", std::process::id()));
+        fs::write(&tmp, bytes)?;
+        fs::rename(&tmp, &path)?; // Atomic - readers never see a torn entry.
+
+        self.enforce_quota()?;
+        Ok(path)
+    }
+
+    /// Read one entry back. No lock needed: writes are rename-atomic, so a
+    /// reader sees either the previous version or the new one.
+    pub fn load(&self, key: &str) -> Option<Vec<u8>> {
+        fs::read(self.user_dir.join(sanitize_key(key))).ok()
+    }
+
+    /// Take the per-user writer lock, stealing it if the holder crashed.
+    fn lock(&self) -> Result<CacheLock> {
+        let lock_path = self.user_dir.join(".lock");
+        let deadline = SystemTime::now() + LOCK_TIMEOUT;
+
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock_path)
+            {
+                Ok(_) => return Ok(CacheLock { path: lock_path }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    // Stale lock from a crashed writer? Steal it.
+                    let stale = fs::metadata(&lock_path)
+                        .and_then(|m| m.modified())
+                        .map(|t| t.elapsed().unwrap_or_default() > LOCK_STALE)
+                        .unwrap_or(true);
+                    if stale {
+                        let _ = fs::remove_file(&lock_path);
+                        continue;
+                    }
+                    if SystemTime::now() > deadline {
+                        anyhow::bail!(
+                            "Cache lock {} held too long - another st writer is stuck?",
+                            lock_path.display()
+                        );
+                    }
+                    std::thread::sleep(LOCK_RETRY);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Delete oldest entries (by mtime) until this user fits their quota.
+    /// Caller must hold the lock.
+    fn enforce_quota(&self) -> Result<()> {
+        let mut entries: Vec<(SystemTime, u64, PathBuf)> = Vec::new();
+        let mut total = 0u64;
+
+        for entry in fs::read_dir(&self.user_dir)?.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name == ".lock" || name.starts_with(".tmp-") {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                continue;
+            }
+            total += metadata.len();
+            entries.push((
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                metadata.len(),
+                path,
+            ));
+        }
+
+        if total <= self.max_bytes {
+            return Ok(());
+        }
+
+        entries.sort_by_key(|(mtime, _, _)| *mtime); // Oldest first.
+        for (_, size, path) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(size);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// RAII writer lock - dropping it releases the lock file.
+struct CacheLock {
+    path: PathBuf,
+}
+
+impl Drop for CacheLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Keys become filenames, so anything path-hostile turns into '-'.
+fn sanitize_key(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_in(dir: &Path, max_bytes: u64) -> SharedCache {
+        SharedCache {
+            user_dir: dir.to_path_buf(),
+            max_bytes,
+        }
+    }
+
+    #[test]
+    fn test_store_load_roundtrip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = cache_in(tmp.path(), 1024 * 1024);
+
+        cache.store("scan:/some/path", b"hello").unwrap();
+        assert_eq!(cache.load("scan:/some/path").unwrap(), b"hello");
+        assert!(cache.load("scan:/other/path").is_none());
+    }
+
+    #[test]
+    fn test_quota_evicts_oldest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = cache_in(tmp.path(), 10); // Tiny quota: one 5-byte entry.
+
+        cache.store("first", b"11111").unwrap();
+        // Backdate the first entry so mtime ordering is unambiguous.
+        let first_path = tmp.path().join("first");
+        let old = SystemTime::now() - Duration::from_secs(60);
+        let file = fs::File::open(&first_path).unwrap();
+        file.set_modified(old).unwrap();
+        drop(file);
+
+        cache.store("second", b"22222").unwrap();
+        cache.store("third", b"33333").unwrap();
+
+        assert!(cache.load("first").is_none(), "oldest entry should go");
+        assert!(cache.load("third").is_some());
+    }
+
+    #[test]
+    fn test_stale_lock_is_stolen() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = cache_in(tmp.path(), 1024);
+
+        // Simulate a crashed writer: a lock file nobody will ever release.
+        let lock_path = tmp.path().join(".lock");
+        fs::write(&lock_path, b"").unwrap();
+        let old = SystemTime::now() - Duration::from_secs(120);
+        fs::File::open(&lock_path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        // Store succeeds because the stale lock gets stolen.
+        cache.store("key", b"value").unwrap();
+        assert_eq!(cache.load("key").unwrap(), b"value");
+    }
+
+    #[test]
+    fn test_sanitize_key() {
+        assert_eq!(sanitize_key("scan:/var/log"), "scan--var-log");
+        assert_eq!(sanitize_key("plain_name.bin"), "plain_name.bin");
+    }
+}
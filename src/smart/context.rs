@@ -552,6 +552,9 @@ mod tests {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         };
 
         let score = analyzer.score_file_relevance(&file_node, &context);
@@ -9,7 +9,7 @@ use super::{SmartResponse, TaskContext, TokenSavings};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use std::process::{Command, Output};
+use std::process::Output;
 
 /// 🔄 Git relay with smart compression and context awareness
 pub struct GitRelay {
@@ -73,7 +73,7 @@ impl GitRelay {
         context: Option<&TaskContext>,
     ) -> Result<GitRelayResponse> {
         // Build git command
-        let mut cmd = Command::new("git");
+        let mut cmd = crate::exec_policy::command("git")?;
         cmd.current_dir(repo_path);
 
         // Add operation-specific arguments
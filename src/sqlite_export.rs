@@ -0,0 +1,262 @@
+//! SQLite export of scan results (`st --mode sqlite --output scan.db`)
+//!
+//! Writes nodes, stats, and the file-type breakdown into a SQLite database
+//! so large scans become queryable with plain SQL. Multiple scans append to
+//! the same database (one row per run in `scans`), which makes diffing
+//! across time a simple self-join:
+//!
+//! ```sql
+//! SELECT a.path, a.size, b.size
+//! FROM nodes a JOIN nodes b ON a.path = b.path
+//! WHERE a.scan_id = 1 AND b.scan_id = 2 AND a.size != b.size;
+//! ```
+
+use crate::daemon_cli::{build_scanner_config, CliScanRequest};
+use crate::scanner::{FileNode, TreeStats};
+use crate::Scanner;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Bump when the table layout changes; readers check `schema_version`
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// Scan locally with the usual filters and export into `db_path`
+///
+/// Like `--watch`, this runs the scan in-process: a SQLite database is
+/// binary, so it can't ride the daemon's text response.
+pub fn run_export(req: &CliScanRequest, db_path: &Path) -> Result<i64> {
+    let root = std::fs::canonicalize(&req.path)?;
+    let config = build_scanner_config(req)?;
+    let scanner = Scanner::new(&root, config)?;
+    let (nodes, stats) = scanner.scan()?;
+    export_scan(db_path, &nodes, &stats, &root)
+}
+
+/// Export one scan into `db_path`, creating the schema if needed
+///
+/// Returns the `scan_id` assigned to this run.
+pub fn export_scan(
+    db_path: &Path,
+    nodes: &[FileNode],
+    stats: &TreeStats,
+    root_path: &Path,
+) -> Result<i64> {
+    let mut conn = Connection::open(db_path)
+        .with_context(|| format!("Failed to open SQLite database: {}", db_path.display()))?;
+
+    create_schema(&conn)?;
+
+    let tx = conn.transaction()?;
+
+    let created_at = std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    tx.execute(
+        "INSERT INTO scans (root, created_at) VALUES (?1, ?2)",
+        params![root_path.to_string_lossy(), created_at],
+    )?;
+    let scan_id = tx.last_insert_rowid();
+
+    {
+        let mut insert_node = tx.prepare(
+            "INSERT INTO nodes (scan_id, path, is_dir, size, mtime, permissions, depth, category)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for node in nodes {
+            let mtime = node
+                .modified
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0);
+            insert_node.execute(params![
+                scan_id,
+                node.path.to_string_lossy(),
+                node.is_dir,
+                node.size as i64,
+                mtime,
+                node.permissions,
+                node.depth as i64,
+                format!("{:?}", node.category),
+            ])?;
+        }
+    }
+
+    tx.execute(
+        "INSERT INTO stats (scan_id, total_files, total_dirs, total_size)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            scan_id,
+            stats.total_files as i64,
+            stats.total_dirs as i64,
+            stats.total_size as i64
+        ],
+    )?;
+
+    {
+        let mut insert_type = tx.prepare(
+            "INSERT INTO file_types (scan_id, extension, count) VALUES (?1, ?2, ?3)",
+        )?;
+        for (ext, count) in &stats.file_types {
+            insert_type.execute(params![scan_id, ext, *count as i64])?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(scan_id)
+}
+
+/// Create tables and indexes; validates the version of an existing database
+fn create_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+             version INTEGER NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS scans (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             root TEXT NOT NULL,
+             created_at INTEGER NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS nodes (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             scan_id INTEGER NOT NULL REFERENCES scans(id),
+             path TEXT NOT NULL,
+             is_dir INTEGER NOT NULL,
+             size INTEGER NOT NULL,
+             mtime INTEGER NOT NULL,
+             permissions INTEGER NOT NULL,
+             depth INTEGER NOT NULL,
+             category TEXT
+         );
+         CREATE INDEX IF NOT EXISTS idx_nodes_path ON nodes(path);
+         CREATE INDEX IF NOT EXISTS idx_nodes_size ON nodes(size);
+         CREATE INDEX IF NOT EXISTS idx_nodes_mtime ON nodes(mtime);
+         CREATE INDEX IF NOT EXISTS idx_nodes_scan ON nodes(scan_id);
+         CREATE TABLE IF NOT EXISTS stats (
+             scan_id INTEGER NOT NULL REFERENCES scans(id),
+             total_files INTEGER NOT NULL,
+             total_dirs INTEGER NOT NULL,
+             total_size INTEGER NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS file_types (
+             scan_id INTEGER NOT NULL REFERENCES scans(id),
+             extension TEXT NOT NULL,
+             count INTEGER NOT NULL
+         );",
+    )?;
+
+    // Stamp fresh databases; refuse ones written by a newer st
+    let existing: Option<i64> = conn
+        .query_row("SELECT version FROM schema_version LIMIT 1", [], |row| {
+            row.get(0)
+        })
+        .ok();
+    match existing {
+        None => {
+            conn.execute(
+                "INSERT INTO schema_version (version) VALUES (?1)",
+                params![SCHEMA_VERSION],
+            )?;
+        }
+        Some(v) if v > SCHEMA_VERSION => {
+            anyhow::bail!(
+                "Database schema version {} is newer than this st supports ({})",
+                v,
+                SCHEMA_VERSION
+            );
+        }
+        Some(_) => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn node(path: &str, is_dir: bool, size: u64) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir,
+            size,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth: 1,
+            file_type: if is_dir {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            },
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    #[test]
+    fn test_export_and_requery() {
+        let dir = std::env::temp_dir().join(format!("st_sqlite_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("scan.db");
+
+        let nodes = vec![
+            node("/proj/src", true, 0),
+            node("/proj/src/main.rs", false, 4096),
+        ];
+        let mut stats = TreeStats::default();
+        for n in &nodes {
+            stats.update_file(n);
+        }
+
+        let first = export_scan(&db_path, &nodes, &stats, Path::new("/proj")).unwrap();
+        let second = export_scan(&db_path, &nodes, &stats, Path::new("/proj")).unwrap();
+        assert!(second > first, "Scans should append, not overwrite");
+
+        let conn = Connection::open(&db_path).unwrap();
+        let version: i64 = conn
+            .query_row("SELECT version FROM schema_version", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+
+        let node_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM nodes WHERE scan_id = ?1",
+                params![first],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(node_count, 2);
+
+        let size: i64 = conn
+            .query_row(
+                "SELECT size FROM nodes WHERE path = '/proj/src/main.rs' AND scan_id = ?1",
+                params![first],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(size, 4096);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,348 @@
+// -----------------------------------------------------------------------------
+// 🎨 THEME SYSTEM - Your Terminal, Your Palette!
+// -----------------------------------------------------------------------------
+// Terminal colors used to be hard-coded deep inside each formatter. Now they
+// route through here: `~/.st/theme.toml` can restyle file categories,
+// individual extensions, and even color-by-age, starting from a builtin
+// preset (default, solarized, dracula, mono). Formatters ask the theme first
+// and only fall back to their original hard-coded colors when it has no
+// opinion - so a missing or empty theme file changes nothing.
+//
+//   # ~/.st/theme.toml
+//   preset = "dracula"          # start here, then override below
+//   directory = "#ffb86c"
+//   size_gradient = false       # kill the tree-connector backgrounds
+//   [categories]
+//   rust = "#cb4b16"
+//   [extensions]
+//   lock = "bright black"
+//   [[age]]                     # newest rule that matches wins
+//   max_days = 7
+//   color = "bright green"
+//
+// Trish runs dracula. Hue runs mono "because SSH". Aye refuses to pick.
+// -----------------------------------------------------------------------------
+
+use crate::scanner::{FileCategory, FileNode};
+use anyhow::Result;
+use colored::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Builtin preset names, for error messages and the docs.
+pub const PRESETS: &[&str] = &["default", "solarized", "dracula", "mono"];
+
+/// Raw `theme.toml` schema - everything optional, colors as strings.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ThemeFile {
+    /// Builtin preset to start from ("default" when unset)
+    preset: Option<String>,
+    /// Directory name color
+    directory: Option<String>,
+    /// Enable/disable size-gradient backgrounds on tree connectors
+    size_gradient: Option<bool>,
+    /// Category name (lowercase, e.g. "rust", "image") -> color
+    #[serde(default)]
+    categories: HashMap<String, String>,
+    /// File extension (no dot) -> color, overrides category
+    #[serde(default)]
+    extensions: HashMap<String, String>,
+    /// Ordered age rules - first rule whose max_days covers the file wins
+    #[serde(default)]
+    age: Vec<AgeRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AgeRule {
+    /// Files modified within this many days get `color`
+    max_days: u64,
+    color: String,
+}
+
+/// The resolved theme formatters consult at render time.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    /// Mono preset: suppress all theme colors and gradients
+    mono: bool,
+    directory: Option<Color>,
+    size_gradient: Option<bool>,
+    categories: HashMap<String, Color>,
+    extensions: HashMap<String, Color>,
+    /// (max_days, color), checked in file order
+    age_rules: Vec<(u64, Color)>,
+}
+
+impl Theme {
+    /// The process-wide theme, loaded from ~/.st/theme.toml once. A broken
+    /// file logs a warning and falls back to the default preset - color
+    /// trouble should never break a scan.
+    pub fn global() -> &'static Theme {
+        static THEME: OnceLock<Theme> = OnceLock::new();
+        THEME.get_or_init(|| match Theme::load() {
+            Ok(theme) => theme,
+            Err(e) => {
+                eprintln!("⚠️  Ignoring ~/.st/theme.toml: {}", e);
+                Theme::default()
+            }
+        })
+    }
+
+    /// Load ~/.st/theme.toml, or the default preset when absent.
+    pub fn load() -> Result<Theme> {
+        let Some(home) = dirs::home_dir() else {
+            return Ok(Theme::default());
+        };
+        let path = home.join(".st").join("theme.toml");
+        if !path.exists() {
+            return Ok(Theme::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        Theme::from_toml(&content)
+    }
+
+    /// Build a theme from TOML: preset first, then per-key overrides.
+    pub fn from_toml(content: &str) -> Result<Theme> {
+        let file: ThemeFile = toml::from_str(content)?;
+        let mut theme = match file.preset.as_deref() {
+            None => Theme::default(),
+            Some(name) => Theme::preset(name).ok_or_else(|| {
+                anyhow::anyhow!("Unknown preset '{}' (available: {})", name, PRESETS.join(", "))
+            })?,
+        };
+        if let Some(dir) = &file.directory {
+            theme.directory = Some(parse_color(dir)?);
+        }
+        if file.size_gradient.is_some() {
+            theme.size_gradient = file.size_gradient;
+        }
+        for (category, color) in &file.categories {
+            theme
+                .categories
+                .insert(category.to_lowercase(), parse_color(color)?);
+        }
+        for (ext, color) in &file.extensions {
+            theme
+                .extensions
+                .insert(ext.to_lowercase(), parse_color(color)?);
+        }
+        for rule in &file.age {
+            theme.age_rules.push((rule.max_days, parse_color(&rule.color)?));
+        }
+        Ok(theme)
+    }
+
+    /// A builtin preset by name, or None for unknown names.
+    pub fn preset(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Theme::default()),
+            "mono" => Some(Theme {
+                mono: true,
+                size_gradient: Some(false),
+                ..Theme::default()
+            }),
+            "solarized" => Some(Theme {
+                directory: hex("#268bd2"),
+                categories: palette(&[
+                    ("rust", "#cb4b16"),
+                    ("python", "#268bd2"),
+                    ("javascript", "#b58900"),
+                    ("typescript", "#268bd2"),
+                    ("go", "#2aa198"),
+                    ("markdown", "#859900"),
+                    ("json", "#b58900"),
+                    ("yaml", "#b58900"),
+                    ("toml", "#6c71c4"),
+                    ("image", "#d33682"),
+                    ("video", "#d33682"),
+                    ("audio", "#d33682"),
+                    ("archive", "#dc322f"),
+                    ("binary", "#586e75"),
+                ]),
+                ..Theme::default()
+            }),
+            "dracula" => Some(Theme {
+                directory: hex("#bd93f9"),
+                categories: palette(&[
+                    ("rust", "#ffb86c"),
+                    ("python", "#8be9fd"),
+                    ("javascript", "#f1fa8c"),
+                    ("typescript", "#8be9fd"),
+                    ("go", "#8be9fd"),
+                    ("markdown", "#50fa7b"),
+                    ("json", "#f1fa8c"),
+                    ("yaml", "#f1fa8c"),
+                    ("toml", "#ff79c6"),
+                    ("image", "#ff79c6"),
+                    ("video", "#ff79c6"),
+                    ("audio", "#ff79c6"),
+                    ("archive", "#ff5555"),
+                    ("binary", "#6272a4"),
+                ]),
+                ..Theme::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// Mono preset in effect - formatters should skip ALL coloring.
+    pub fn suppress_color(&self) -> bool {
+        self.mono
+    }
+
+    /// Themed directory-name color, if the theme sets one.
+    pub fn directory_color(&self) -> Option<Color> {
+        if self.mono {
+            None
+        } else {
+            self.directory
+        }
+    }
+
+    /// Size-gradient backgrounds on/off; None = formatter default.
+    pub fn size_gradient(&self) -> Option<bool> {
+        self.size_gradient
+    }
+
+    /// Themed color for a category, if the theme sets one.
+    pub fn category_color(&self, category: FileCategory) -> Option<Color> {
+        if self.mono {
+            return None;
+        }
+        self.categories
+            .get(&format!("{:?}", category).to_lowercase())
+            .copied()
+    }
+
+    /// Highest-priority themed color for a file node: extension override,
+    /// then the first matching age rule, then the category color.
+    pub fn node_color(&self, node: &FileNode) -> Option<Color> {
+        if self.mono {
+            return None;
+        }
+        if let Some(ext) = node.path.extension().and_then(|e| e.to_str()) {
+            if let Some(color) = self.extensions.get(&ext.to_lowercase()) {
+                return Some(*color);
+            }
+        }
+        if !self.age_rules.is_empty() {
+            if let Ok(age) = node.modified.elapsed() {
+                let days = age.as_secs() / 86400;
+                for (max_days, color) in &self.age_rules {
+                    if days <= *max_days {
+                        return Some(*color);
+                    }
+                }
+            }
+        }
+        self.category_color(node.category)
+    }
+}
+
+/// "#rrggbb" hex or a named ANSI color ("red", "bright blue", ...).
+fn parse_color(spec: &str) -> Result<Color> {
+    let spec = spec.trim();
+    if let Some(hex) = spec.strip_prefix('#') {
+        if hex.len() != 6 {
+            anyhow::bail!("Color '{}' must be #rrggbb", spec);
+        }
+        let r = u8::from_str_radix(&hex[0..2], 16)?;
+        let g = u8::from_str_radix(&hex[2..4], 16)?;
+        let b = u8::from_str_radix(&hex[4..6], 16)?;
+        return Ok(Color::TrueColor { r, g, b });
+    }
+    match spec.to_lowercase().replace('_', " ").as_str() {
+        "black" => Ok(Color::Black),
+        "red" => Ok(Color::Red),
+        "green" => Ok(Color::Green),
+        "yellow" => Ok(Color::Yellow),
+        "blue" => Ok(Color::Blue),
+        "magenta" | "purple" => Ok(Color::Magenta),
+        "cyan" => Ok(Color::Cyan),
+        "white" => Ok(Color::White),
+        "bright black" | "gray" | "grey" => Ok(Color::BrightBlack),
+        "bright red" => Ok(Color::BrightRed),
+        "bright green" => Ok(Color::BrightGreen),
+        "bright yellow" => Ok(Color::BrightYellow),
+        "bright blue" => Ok(Color::BrightBlue),
+        "bright magenta" => Ok(Color::BrightMagenta),
+        "bright cyan" => Ok(Color::BrightCyan),
+        "bright white" => Ok(Color::BrightWhite),
+        other => anyhow::bail!("Unknown color '{}' (use a name or #rrggbb)", other),
+    }
+}
+
+/// Hex literal helper for preset tables - presets are compile-time valid.
+fn hex(spec: &str) -> Option<Color> {
+    Some(parse_color(spec).expect("preset colors are valid hex"))
+}
+
+fn palette(entries: &[(&str, &str)]) -> HashMap<String, Color> {
+    entries
+        .iter()
+        .map(|(name, color)| {
+            (
+                name.to_string(),
+                parse_color(color).expect("preset colors are valid hex"),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(
+            parse_color("#ff4136").unwrap(),
+            Color::TrueColor {
+                r: 0xff,
+                g: 0x41,
+                b: 0x36
+            }
+        );
+        assert_eq!(parse_color("bright green").unwrap(), Color::BrightGreen);
+        assert!(parse_color("chartreuse-ish").is_err());
+        assert!(parse_color("#ff41").is_err());
+    }
+
+    #[test]
+    fn test_preset_plus_overrides() {
+        let theme = Theme::from_toml(
+            "preset = \"dracula\"\n\
+             directory = \"#ffffff\"\n\
+             [categories]\n\
+             rust = \"red\"\n\
+             [extensions]\n\
+             lock = \"gray\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            theme.directory_color(),
+            Some(Color::TrueColor {
+                r: 0xff,
+                g: 0xff,
+                b: 0xff
+            })
+        );
+        assert_eq!(theme.category_color(FileCategory::Rust), Some(Color::Red));
+        // Untouched preset entries survive the overrides
+        assert!(theme.category_color(FileCategory::Python).is_some());
+    }
+
+    #[test]
+    fn test_mono_suppresses_everything() {
+        let theme = Theme::from_toml("preset = \"mono\"").unwrap();
+        assert!(theme.suppress_color());
+        assert_eq!(theme.category_color(FileCategory::Rust), None);
+        assert_eq!(theme.size_gradient(), Some(false));
+    }
+
+    #[test]
+    fn test_unknown_preset_rejected() {
+        let err = Theme::from_toml("preset = \"vaporwave\"").unwrap_err();
+        assert!(err.to_string().contains("Unknown preset"));
+    }
+}
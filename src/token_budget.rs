@@ -0,0 +1,184 @@
+// Token Budget - keep rendered output inside an LLM context allowance
+//
+// project_context_dump already respects a token_budget; this module makes
+// the same idea cross-cutting. Instead of truncating mid-stream (which
+// mangles structured formats), we re-render with progressively shallower
+// depth caps until the estimate fits, then report exactly what was omitted.
+// Token estimation uses the same rough 1 token ≈ 4 characters rule as the
+// compression manager.
+
+use crate::FileNode;
+use anyhow::Result;
+
+/// Rough token estimate: 1 token ≈ 4 characters.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len() / 4
+}
+
+/// What `fit_to_budget` had to do to make the output fit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FitOutcome {
+    /// Estimated tokens of the returned output
+    pub estimated_tokens: usize,
+    /// The budget that was requested
+    pub budget: usize,
+    /// Depth the tree was capped at, if depth reduction was needed
+    pub depth_limit: Option<usize>,
+    /// Entries dropped by the depth cap
+    pub entries_omitted: usize,
+    /// True when even the shallowest render still exceeds the budget
+    pub over_budget: bool,
+}
+
+impl FitOutcome {
+    /// One-line report of the degradation, or `None` if the output fit
+    /// untouched. Appended after the output so readers (and models) know
+    /// the tree is a view, not the whole story.
+    pub fn note(&self) -> Option<String> {
+        if self.depth_limit.is_none() && !self.over_budget {
+            return None;
+        }
+        let mut note = match self.depth_limit {
+            Some(depth) => format!(
+                "⚠️ Token budget {}: depth capped at {}, {} deeper entries omitted (est. {} tokens)",
+                self.budget, depth, self.entries_omitted, self.estimated_tokens
+            ),
+            None => format!(
+                "⚠️ Token budget {}: output is ~{} tokens and has no depth left to trim",
+                self.budget, self.estimated_tokens
+            ),
+        };
+        if self.over_budget && self.depth_limit.is_some() {
+            note.push_str(" - still over budget at minimum depth");
+        }
+        Some(note)
+    }
+}
+
+/// Render within a token budget by degrading gracefully: render at full
+/// depth first, then re-render with the deepest level stripped until the
+/// estimate fits (or only the top level remains). `render` receives the
+/// node subset to format, so it works with any formatter.
+pub fn fit_to_budget<F>(
+    budget: usize,
+    nodes: &[FileNode],
+    mut render: F,
+) -> Result<(String, FitOutcome)>
+where
+    F: FnMut(&[FileNode]) -> Result<String>,
+{
+    let full = render(nodes)?;
+    let mut outcome = FitOutcome {
+        estimated_tokens: estimate_tokens(&full),
+        budget,
+        depth_limit: None,
+        entries_omitted: 0,
+        over_budget: false,
+    };
+    if budget == 0 || outcome.estimated_tokens <= budget {
+        return Ok((full, outcome));
+    }
+
+    let max_depth = nodes.iter().map(|n| n.depth).max().unwrap_or(0);
+    let mut best = full;
+    for depth in (1..max_depth).rev() {
+        let subset: Vec<FileNode> = nodes
+            .iter()
+            .filter(|node| node.depth <= depth)
+            .cloned()
+            .collect();
+        let rendered = render(&subset)?;
+        outcome.estimated_tokens = estimate_tokens(&rendered);
+        outcome.depth_limit = Some(depth);
+        outcome.entries_omitted = nodes.len() - subset.len();
+        best = rendered;
+        if outcome.estimated_tokens <= budget {
+            return Ok((best, outcome));
+        }
+    }
+
+    // Either the tree was already depth 1 or even depth 1 doesn't fit -
+    // return the shallowest render and let the note say so.
+    outcome.over_budget = true;
+    Ok((best, outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileCategory, FileType, FilesystemType};
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn node(path: &str, depth: usize) -> FileNode {
+        FileNode {
+            path: PathBuf::from(path),
+            is_dir: false,
+            size: 0,
+            permissions: 0o644,
+            uid: 1000,
+            gid: 1000,
+            modified: SystemTime::now(),
+            is_symlink: false,
+            is_hidden: false,
+            permission_denied: false,
+            is_ignored: false,
+            depth,
+            file_type: FileType::RegularFile,
+            category: FileCategory::Unknown,
+            search_matches: None,
+            filesystem_type: FilesystemType::Unknown,
+            git_branch: None,
+            traversal_context: None,
+            interest: None,
+            security_findings: Vec::new(),
+            change_status: None,
+            content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
+        }
+    }
+
+    /// Renders ~40 characters (10 tokens) per node - easy budget math.
+    fn render_lines(nodes: &[FileNode]) -> Result<String> {
+        Ok(nodes
+            .iter()
+            .map(|n| format!("{:40}\n", n.path.display()))
+            .collect())
+    }
+
+    #[test]
+    fn test_within_budget_is_untouched() {
+        let nodes = vec![node("a", 1), node("a/b", 2)];
+        let (output, outcome) = fit_to_budget(1000, &nodes, render_lines).unwrap();
+        assert_eq!(output.lines().count(), 2);
+        assert!(outcome.note().is_none());
+        assert_eq!(outcome.depth_limit, None);
+    }
+
+    #[test]
+    fn test_depth_reduction_until_fit() {
+        // 3 nodes per depth level, depths 1-4: 12 nodes ≈ 123 tokens.
+        let nodes: Vec<FileNode> = (1..=4)
+            .flat_map(|d| (0..3).map(move |i| node(&format!("{}/{}", d, i), d)))
+            .collect();
+
+        // 65 tokens fits 6 nodes - depth must drop to 2.
+        let (output, outcome) = fit_to_budget(65, &nodes, render_lines).unwrap();
+        assert_eq!(output.lines().count(), 6);
+        assert_eq!(outcome.depth_limit, Some(2));
+        assert_eq!(outcome.entries_omitted, 6);
+        assert!(!outcome.over_budget);
+        assert!(outcome.note().unwrap().contains("6 deeper entries omitted"));
+    }
+
+    #[test]
+    fn test_reports_when_minimum_depth_still_exceeds() {
+        let nodes: Vec<FileNode> = (0..20).map(|i| node(&format!("f{}", i), 1)).collect();
+        let (output, outcome) = fit_to_budget(10, &nodes, render_lines).unwrap();
+        assert_eq!(output.lines().count(), 20);
+        assert!(outcome.over_budget);
+        assert!(outcome.note().is_some());
+    }
+}
@@ -0,0 +1,595 @@
+// -----------------------------------------------------------------------------
+// 🗂️  TUI Explorer - `st --tui`, the ncdu/broot you never have to leave!
+// -----------------------------------------------------------------------------
+// One real Scanner pass up front, then a pure in-memory explorer over the
+// result: arrow-key tree navigation with expand/collapse, live substring
+// filtering, size sorting, a preview pane that borrows smart_read's function
+// collapsing for code files, and actions - open, delete to trash, copy path.
+//
+// Deletes never unlink anything: entries are MOVED to ~/.st/trash with a
+// timestamp prefix, so "oops" costs a `mv` instead of a backup restore.
+// -----------------------------------------------------------------------------
+
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use humansize::{format_size, BINARY};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Terminal,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use crate::mcp::tools::smart_read::{detect_language, extract_functions, supports_collapsing};
+use crate::scanner::{FileNode, Scanner, ScannerConfig};
+
+/// How many preview lines we bother rendering - the pane is never taller
+const PREVIEW_LINES: usize = 200;
+/// Cap raw preview reads so a stray core dump doesn't freeze the UI
+const PREVIEW_BYTES: u64 = 256 * 1024;
+
+/// One row in the visible list: index into the scan plus its indent depth
+/// (depth is 0 for flat filter results, tree depth otherwise)
+struct VisibleEntry {
+    node_idx: usize,
+    depth: usize,
+}
+
+struct ExplorerApp {
+    root: PathBuf,
+    nodes: Vec<FileNode>,
+    /// Children of each directory, pre-sorted for the current sort mode
+    children: HashMap<PathBuf, Vec<usize>>,
+    expanded: HashSet<PathBuf>,
+    visible: Vec<VisibleEntry>,
+    list_state: ListState,
+    filter: String,
+    filter_input: bool,
+    sort_by_size: bool,
+    preview: Vec<String>,
+    preview_scroll: u16,
+    /// Path awaiting a `y` confirmation before it goes to the trash
+    pending_delete: Option<PathBuf>,
+    status: Option<String>,
+    show_help: bool,
+}
+
+impl ExplorerApp {
+    fn new(root: PathBuf) -> Result<Self> {
+        let mut app = Self {
+            root: root.clone(),
+            nodes: Vec::new(),
+            children: HashMap::new(),
+            expanded: HashSet::from([root]),
+            visible: Vec::new(),
+            list_state: ListState::default(),
+            filter: String::new(),
+            filter_input: false,
+            sort_by_size: false,
+            preview: Vec::new(),
+            preview_scroll: 0,
+            pending_delete: None,
+            status: None,
+            show_help: false,
+        };
+        app.rescan()?;
+        app.list_state.select(Some(0));
+        app.update_preview();
+        Ok(app)
+    }
+
+    /// Full scanner pass - used at startup and after a trash action
+    fn rescan(&mut self) -> Result<()> {
+        let config = ScannerConfig::default();
+        let scanner = Scanner::new(&self.root, config)?;
+        let (nodes, _stats) = scanner.scan()?;
+        self.nodes = nodes;
+        self.rebuild_children();
+        self.rebuild_visible();
+        Ok(())
+    }
+
+    /// Parent→children index, sorted dirs-first by name or by size
+    /// descending when `s` has been pressed
+    fn rebuild_children(&mut self) {
+        let mut children: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if node.path == self.root {
+                continue;
+            }
+            if let Some(parent) = node.path.parent() {
+                children.entry(parent.to_path_buf()).or_default().push(i);
+            }
+        }
+        let sort_by_size = self.sort_by_size;
+        for kids in children.values_mut() {
+            kids.sort_by(|&a, &b| {
+                let (na, nb) = (&self.nodes[a], &self.nodes[b]);
+                if sort_by_size {
+                    nb.size.cmp(&na.size)
+                } else {
+                    nb.is_dir.cmp(&na.is_dir).then_with(|| {
+                        crate::scanner::natural_cmp(
+                            &na.path.file_name().unwrap_or_default().to_string_lossy(),
+                            &nb.path.file_name().unwrap_or_default().to_string_lossy(),
+                        )
+                    })
+                }
+            });
+        }
+        self.children = children;
+    }
+
+    /// Recompute the row list. With a filter active the tree flattens into
+    /// fuzzy-ranked matches (best first, fzf-style); otherwise it is a DFS
+    /// through the expanded directories.
+    fn rebuild_visible(&mut self) {
+        self.visible.clear();
+        if !self.filter.is_empty() {
+            for (_, node_idx) in crate::fuzzy::rank_nodes(&self.nodes, &self.root, &self.filter) {
+                self.visible.push(VisibleEntry { node_idx, depth: 0 });
+            }
+        } else if let Some(root_idx) = self.nodes.iter().position(|n| n.path == self.root) {
+            self.push_subtree(root_idx, 0);
+        }
+        let max = self.visible.len().saturating_sub(1);
+        let selected = self.list_state.selected().unwrap_or(0).min(max);
+        self.list_state.select(Some(selected));
+    }
+
+    fn push_subtree(&mut self, node_idx: usize, depth: usize) {
+        self.visible.push(VisibleEntry { node_idx, depth });
+        let path = self.nodes[node_idx].path.clone();
+        if self.nodes[node_idx].is_dir && self.expanded.contains(&path) {
+            if let Some(kids) = self.children.get(&path).cloned() {
+                for child in kids {
+                    self.push_subtree(child, depth + 1);
+                }
+            }
+        }
+    }
+
+    fn selected_node(&self) -> Option<&FileNode> {
+        let entry = self.visible.get(self.list_state.selected()?)?;
+        self.nodes.get(entry.node_idx)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, self.visible.len() as isize - 1);
+        self.list_state.select(Some(next as usize));
+        self.preview_scroll = 0;
+        self.update_preview();
+    }
+
+    /// Preview pane contents for the selected entry. Code files in a
+    /// language smart_read understands get the collapsed-function overview
+    /// instead of a raw head - same signatures the MCP tool would return.
+    fn update_preview(&mut self) {
+        self.preview.clear();
+        let Some(node) = self.selected_node() else {
+            return;
+        };
+        if node.is_dir {
+            let count = self
+                .children
+                .get(&node.path)
+                .map(|kids| kids.len())
+                .unwrap_or(0);
+            self.preview.push(format!(
+                "📁 {} - {} entries, {}",
+                node.path.display(),
+                count,
+                format_size(node.size, BINARY)
+            ));
+            return;
+        }
+        let path = node.path.clone();
+        let lang = detect_language(&path);
+        let Ok(content) = read_head(&path) else {
+            self.preview.push("(unreadable or binary file)".to_string());
+            return;
+        };
+        if content.contains('\u{0}') {
+            self.preview.push("(binary file)".to_string());
+            return;
+        }
+        if let Some(lang) = lang.filter(|l| supports_collapsing(l)) {
+            let functions = extract_functions(&content, lang);
+            if !functions.is_empty() {
+                self.preview
+                    .push(format!("── {} functions ({}) ──", functions.len(), lang));
+                for f in &functions {
+                    self.preview.push(format!(
+                        "{:>5}: {}",
+                        f.start_line + 1,
+                        f.signature.trim()
+                    ));
+                }
+                self.preview.push(String::new());
+            }
+        }
+        self.preview
+            .extend(content.lines().take(PREVIEW_LINES).map(String::from));
+    }
+
+    fn toggle_expand(&mut self) {
+        let Some(node) = self.selected_node() else {
+            return;
+        };
+        if !node.is_dir {
+            return;
+        }
+        let path = node.path.clone();
+        if !self.expanded.remove(&path) {
+            self.expanded.insert(path);
+        }
+        self.rebuild_visible();
+    }
+
+    /// ← collapses the selected dir, or jumps to the parent of a file
+    fn collapse_or_parent(&mut self) {
+        let Some(node) = self.selected_node() else {
+            return;
+        };
+        let path = node.path.clone();
+        if node.is_dir && self.expanded.remove(&path) {
+            self.rebuild_visible();
+            return;
+        }
+        if let Some(parent) = path.parent() {
+            if let Some(pos) = self
+                .visible
+                .iter()
+                .position(|e| self.nodes[e.node_idx].path == parent)
+            {
+                self.list_state.select(Some(pos));
+                self.update_preview();
+            }
+        }
+    }
+
+    /// Enter: expand a directory, hand a file to the OS default opener
+    fn open_selected(&mut self) {
+        let Some(node) = self.selected_node() else {
+            return;
+        };
+        if node.is_dir {
+            self.toggle_expand();
+            return;
+        }
+        let path = node.path.clone();
+        if crate::exec_policy::no_exec() {
+            self.status = Some("Open disabled by --no-exec".to_string());
+            return;
+        }
+        self.status = match open::that(&path) {
+            Ok(()) => Some(format!("Opened {}", path.display())),
+            Err(e) => Some(format!("Open failed: {}", e)),
+        };
+    }
+
+    /// Move the confirmed entry into ~/.st/trash/<epoch>-<name> and rescan
+    fn trash_selected(&mut self, path: &Path) {
+        let result = (|| -> Result<PathBuf> {
+            let trash_dir = dirs::home_dir()
+                .ok_or_else(|| anyhow::anyhow!("Cannot locate home directory"))?
+                .join(".st")
+                .join("trash");
+            fs::create_dir_all(&trash_dir)?;
+            let stamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs();
+            let name = path
+                .file_name()
+                .ok_or_else(|| anyhow::anyhow!("Refusing to trash {}", path.display()))?;
+            let dest = trash_dir.join(format!("{}-{}", stamp, name.to_string_lossy()));
+            fs::rename(path, &dest)?;
+            Ok(dest)
+        })();
+        self.status = match result {
+            Ok(dest) => {
+                let _ = self.rescan();
+                self.update_preview();
+                Some(format!("Trashed to {}", dest.display()))
+            }
+            Err(e) => Some(format!("Trash failed: {}", e)),
+        };
+    }
+
+    /// Best-effort clipboard: whichever of the usual suspects exists.
+    /// Falls back to showing the path in the status bar so it can at least
+    /// be mouse-selected.
+    fn copy_selected_path(&mut self) {
+        let Some(node) = self.selected_node() else {
+            return;
+        };
+        let path = node.path.display().to_string();
+        for cmd in [
+            vec!["wl-copy"],
+            vec!["xclip", "-selection", "clipboard"],
+            vec!["xsel", "--clipboard", "--input"],
+            vec!["pbcopy"],
+        ] {
+            let Ok(mut clip_cmd) = crate::exec_policy::command(cmd[0]) else {
+                continue;
+            };
+            let spawned = clip_cmd
+                .args(&cmd[1..])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            if let Ok(mut child) = spawned {
+                use std::io::Write;
+                let ok = child
+                    .stdin
+                    .take()
+                    .and_then(|mut stdin| stdin.write_all(path.as_bytes()).ok())
+                    .is_some()
+                    && child.wait().map(|s| s.success()).unwrap_or(false);
+                if ok {
+                    self.status = Some(format!("Copied: {}", path));
+                    return;
+                }
+            }
+        }
+        self.status = Some(format!("No clipboard tool found - path: {}", path));
+    }
+
+    fn toggle_size_sort(&mut self) {
+        self.sort_by_size = !self.sort_by_size;
+        self.rebuild_children();
+        self.rebuild_visible();
+        self.status = Some(if self.sort_by_size {
+            "Sorting by size (largest first)".to_string()
+        } else {
+            "Sorting by name (dirs first)".to_string()
+        });
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        // A pending delete swallows the next key: y confirms, rest cancels
+        if let Some(path) = self.pending_delete.take() {
+            if code == KeyCode::Char('y') {
+                self.trash_selected(&path);
+            } else {
+                self.status = Some("Delete cancelled".to_string());
+            }
+            return true;
+        }
+        if self.filter_input {
+            match code {
+                KeyCode::Esc => {
+                    self.filter_input = false;
+                    self.filter.clear();
+                    self.rebuild_visible();
+                    self.update_preview();
+                }
+                KeyCode::Enter => self.filter_input = false,
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                    self.rebuild_visible();
+                    self.update_preview();
+                }
+                KeyCode::Char(c) => {
+                    self.filter.push(c);
+                    self.rebuild_visible();
+                    self.update_preview();
+                }
+                _ => {}
+            }
+            return true;
+        }
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-20),
+            KeyCode::PageDown => self.move_selection(20),
+            KeyCode::Right => self.toggle_expand(),
+            KeyCode::Left => self.collapse_or_parent(),
+            KeyCode::Enter => self.open_selected(),
+            KeyCode::Char('/') => {
+                self.filter_input = true;
+                self.status = Some("Filter: type to match, Enter to keep, Esc to clear".into());
+            }
+            KeyCode::Char('s') => self.toggle_size_sort(),
+            KeyCode::Char('d') => {
+                if let Some(node) = self.selected_node() {
+                    let path = node.path.clone();
+                    if path == self.root {
+                        self.status = Some("Not trashing the scan root".to_string());
+                    } else {
+                        self.status = Some(format!("Trash {}? (y/N)", path.display()));
+                        self.pending_delete = Some(path);
+                    }
+                }
+            }
+            KeyCode::Char('y') => self.copy_selected_path(),
+            KeyCode::Char('[') => self.preview_scroll = self.preview_scroll.saturating_sub(10),
+            KeyCode::Char(']') => self.preview_scroll = self.preview_scroll.saturating_add(10),
+            KeyCode::Char('?') => self.show_help = !self.show_help,
+            _ => {}
+        }
+        true
+    }
+
+    fn run(&mut self) -> Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        let result = self.event_loop(&mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        result
+    }
+
+    fn event_loop(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(Duration::from_millis(200))? {
+                if let Event::Key(key) = event::read()? {
+                    if !self.handle_key(key.code, key.modifiers) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(frame.size());
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(rows[0]);
+
+        // Tree pane
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|entry| {
+                let node = &self.nodes[entry.node_idx];
+                let name = if self.filter.is_empty() {
+                    node.path
+                        .file_name()
+                        .unwrap_or(node.path.as_os_str())
+                        .to_string_lossy()
+                        .to_string()
+                } else {
+                    // Filter results are flat, so show where each hit lives
+                    node.path
+                        .strip_prefix(&self.root)
+                        .unwrap_or(&node.path)
+                        .display()
+                        .to_string()
+                };
+                let marker = if node.is_dir {
+                    if self.expanded.contains(&node.path) {
+                        "▾ "
+                    } else {
+                        "▸ "
+                    }
+                } else {
+                    "  "
+                };
+                let style = if node.is_dir {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let size = format_size(node.size, BINARY);
+                ListItem::new(Line::from(vec![
+                    Span::raw("  ".repeat(entry.depth)),
+                    Span::raw(marker),
+                    Span::styled(name, style),
+                    Span::styled(format!("  {}", size), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+        let title = if self.filter.is_empty() {
+            format!(" {} ", self.root.display())
+        } else {
+            format!(" filter: {} ({} hits) ", self.filter, self.visible.len())
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Rgb(40, 40, 60))
+                    .add_modifier(Modifier::BOLD),
+            );
+        frame.render_stateful_widget(list, panes[0], &mut self.list_state);
+
+        // Preview pane
+        let preview_title = self
+            .selected_node()
+            .map(|n| format!(" {} ", n.path.display()))
+            .unwrap_or_else(|| " preview ".to_string());
+        let preview_text = if self.show_help {
+            HELP_TEXT.to_string()
+        } else {
+            self.preview.join("\n")
+        };
+        let preview = Paragraph::new(preview_text)
+            .block(Block::default().borders(Borders::ALL).title(preview_title))
+            .wrap(Wrap { trim: false })
+            .scroll((self.preview_scroll, 0));
+        frame.render_widget(preview, panes[1]);
+
+        // Status bar
+        let status = if self.filter_input {
+            format!("/{}", self.filter)
+        } else {
+            self.status.clone().unwrap_or_else(|| {
+                "↑↓ move  ←→ fold  Enter open  / filter  s size-sort  d trash  y copy path  ? help  q quit"
+                    .to_string()
+            })
+        };
+        frame.render_widget(
+            Paragraph::new(status).style(Style::default().fg(Color::Cyan)),
+            rows[1],
+        );
+    }
+}
+
+const HELP_TEXT: &str = "\
+st --tui explorer
+
+  ↑/↓, j/k      move selection
+  PgUp/PgDn     move 20 rows
+  →, Enter      expand directory / open file with the OS handler
+  ←             collapse directory, or jump to parent
+  /             live filter (flat results); Enter keeps, Esc clears
+  s             toggle size sorting (largest first)
+  d, then y     move entry to ~/.st/trash (never unlinks)
+  y             copy full path to the clipboard
+  [ / ]         scroll the preview pane
+  ?             toggle this help
+  q, Esc        quit
+
+Code files show smart_read's collapsed function overview above the raw
+head of the file; everything else gets a plain text preview.";
+
+/// Read up to PREVIEW_BYTES of a file as (lossy) UTF-8
+fn read_head(path: &Path) -> Result<String> {
+    use std::io::Read;
+    let file = fs::File::open(path)?;
+    let mut buf = Vec::new();
+    file.take(PREVIEW_BYTES).read_to_end(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Entry point for `st --tui`
+pub fn run_tui_explorer(path: PathBuf) -> Result<()> {
+    let mut app = ExplorerApp::new(path)?;
+    app.run()
+}
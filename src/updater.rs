@@ -8,8 +8,8 @@ use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
 use std::io::{self, Write};
+use crate::exec_policy;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// GitHub repository for releases
@@ -329,7 +329,7 @@ pub async fn download_and_install(version: &str, yes: bool) -> Result<()> {
     // Extract archive
     #[cfg(unix)]
     {
-        let output = Command::new("tar")
+        let output = exec_policy::command("tar")?
             .args(["-xzf", archive_path.to_str().unwrap()])
             .current_dir(&temp_dir)
             .output()
@@ -346,7 +346,7 @@ pub async fn download_and_install(version: &str, yes: bool) -> Result<()> {
     #[cfg(windows)]
     {
         // On Windows, use powershell to extract zip
-        let output = Command::new("powershell")
+        let output = exec_policy::command("powershell")?
             .args([
                 "-Command",
                 &format!(
@@ -392,11 +392,11 @@ pub async fn download_and_install(version: &str, yes: bool) -> Result<()> {
         #[cfg(unix)]
         {
             if use_sudo {
-                let _ = Command::new("sudo")
+                let _ = exec_policy::command("sudo")?
                     .args(["rm", "-f", dest_path.to_str().unwrap()])
                     .status();
 
-                Command::new("sudo")
+                exec_policy::command("sudo")?
                     .args([
                         "cp",
                         src_path.to_str().unwrap(),
@@ -405,7 +405,7 @@ pub async fn download_and_install(version: &str, yes: bool) -> Result<()> {
                     .status()
                     .context(format!("Failed to install {}", binary))?;
 
-                Command::new("sudo")
+                exec_policy::command("sudo")?
                     .args(["chmod", "+x", dest_path.to_str().unwrap()])
                     .status()?;
             } else {
@@ -52,16 +52,31 @@ async fn ensure_mcp_context(state: &SharedMcpContext) -> Arc<McpContext> {
 
     // Create new context
     let config = McpConfig::default();
+    let (rate_limit_per_minute, max_concurrent_heavy) =
+        (config.rate_limit_per_minute, config.max_concurrent_heavy);
     let consciousness = Arc::new(tokio::sync::Mutex::new(ConsciousnessManager::new_silent()));
 
     let ctx = Arc::new(McpContext {
         cache: Arc::new(crate::mcp::cache::AnalysisCache::new(config.cache_ttl)),
+        scan_cache: Arc::new(crate::mcp::cache::ScanCache::new(
+            crate::mcp::cache::SCAN_CACHE_TTL_SECS,
+        )),
         config: Arc::new(config),
         permissions: Arc::new(tokio::sync::Mutex::new(crate::mcp::permissions::PermissionCache::new())),
         sessions: Arc::new(crate::mcp::session::SessionManager::new()),
         assistant: Arc::new(crate::mcp::assistant::McpAssistant::new()),
         consciousness,
         dashboard_bridge: None,
+        subscriptions: Arc::new(crate::mcp::resources::ResourceSubscriptions::new()),
+        started_at: std::time::Instant::now(),
+        requests_handled: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+        grants: Arc::new(std::sync::Mutex::new(
+            crate::mcp::permissions::SessionGrants::default(),
+        )),
+        rate_limiter: Arc::new(crate::mcp::rate_limit::RateLimiter::new(
+            rate_limit_per_minute,
+            max_concurrent_heavy,
+        )),
     });
 
     let mut write_guard = state.write().await;
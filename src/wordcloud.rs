@@ -0,0 +1,300 @@
+// -----------------------------------------------------------------------------
+// ☁️ WORD CLOUD - `st wordcloud PATH --out cloud.svg`
+//
+// What is this project ABOUT? Tokenize file names (and README content),
+// drop the stopwords, and render the survivors as an SVG cloud - biggest
+// word = most frequent. Trish hangs these on the wall; the rest of us use
+// them to spot a codebase that says "temp", "old", and "final2" too often.
+//
+// Everything that used to be hardcoded is an option now: palette/theme,
+// stopword language, word cap, and the shape mask the layout flows into.
+// -----------------------------------------------------------------------------
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Canvas dimensions - 4:3, looks right both in a README and a browser tab.
+const WIDTH: f32 = 800.0;
+const HEIGHT: f32 = 600.0;
+
+/// Font size range; frequencies are mapped onto it by square root so one
+/// runaway word doesn't dwarf everything else.
+const MIN_FONT: f32 = 12.0;
+const MAX_FONT: f32 = 64.0;
+
+/// Rough glyph width as a fraction of font size - good enough for layout.
+const GLYPH_ASPECT: f32 = 0.6;
+
+/// README files larger than this are skipped rather than tokenized.
+const MAX_README_BYTES: u64 = 256 * 1024;
+
+/// Where the words come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordSource {
+    /// File and directory names only
+    Names,
+    /// README content only
+    Readme,
+    /// Both (the default)
+    Both,
+}
+
+/// Outline the layout flows into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeMask {
+    /// Full rectangle - maximum words per pixel
+    Rect,
+    /// Inscribed ellipse - the classic cloud silhouette
+    Ellipse,
+}
+
+/// Rendering options - each one replaces a former hardcode.
+pub struct WordCloudOptions {
+    /// Palette name: ocean, sunset, forest, mono
+    pub theme: String,
+    /// Stopword list: en, de, fr, es, code, none
+    pub stopwords: String,
+    /// How many words survive into the SVG
+    pub max_words: usize,
+    pub shape: ShapeMask,
+    pub source: WordSource,
+}
+
+impl Default for WordCloudOptions {
+    fn default() -> Self {
+        Self {
+            theme: "ocean".to_string(),
+            stopwords: "en".to_string(),
+            max_words: 60,
+            shape: ShapeMask::Rect,
+            source: WordSource::Both,
+        }
+    }
+}
+
+/// (background, word colors) per theme name; unknown names get ocean.
+fn palette(theme: &str) -> (&'static str, &'static [&'static str]) {
+    match theme {
+        "sunset" => (
+            "#2b1b2f",
+            &["#ff6b6b", "#ffa94d", "#ffd43b", "#ff8787", "#e599f7"],
+        ),
+        "forest" => (
+            "#0b2012",
+            &["#8ce99a", "#69db7c", "#b2f2bb", "#38d9a9", "#d8f5a2"],
+        ),
+        "mono" => (
+            "#ffffff",
+            &["#212529", "#495057", "#868e96", "#343a40", "#adb5bd"],
+        ),
+        // "ocean" and anything unrecognized
+        _ => (
+            "#0b1d2a",
+            &["#74c0fc", "#4dabf7", "#a5d8ff", "#66d9e8", "#99e9f2"],
+        ),
+    }
+}
+
+/// Stopword list per language. "code" drops the words every repository
+/// shares ("src", "test", "main"...); "none" keeps everything.
+fn stopwords(lang: &str) -> &'static [&'static str] {
+    match lang {
+        "en" => &[
+            "the", "and", "for", "are", "with", "this", "that", "from", "you", "your", "was",
+            "were", "has", "have", "had", "not", "but", "can", "will", "all", "its", "use",
+            "using", "how", "what", "when", "where",
+        ],
+        "de" => &[
+            "der", "die", "das", "und", "ist", "mit", "ein", "eine", "für", "von", "auf", "dem",
+            "den", "des", "nicht", "auch", "sich", "werden", "wird", "oder",
+        ],
+        "fr" => &[
+            "les", "des", "une", "est", "pour", "que", "qui", "dans", "avec", "sur", "par",
+            "pas", "sont", "vous", "nous", "cette", "mais", "aux",
+        ],
+        "es" => &[
+            "los", "las", "una", "del", "que", "con", "por", "para", "como", "mas", "este",
+            "esta", "son", "sus", "pero", "muy",
+        ],
+        "code" => &[
+            "src", "lib", "bin", "test", "tests", "main", "index", "mod", "utils", "common",
+            "core", "new", "old", "tmp", "temp", "data", "file", "files",
+        ],
+        // "none" and anything unrecognized
+        _ => &[],
+    }
+}
+
+/// Split a name or line into counting-worthy words: lowercase, split on
+/// anything non-alphabetic, at least 3 chars.
+fn tokenize(text: &str, words: &mut HashMap<String, usize>, stop: &[&str]) {
+    for raw in text.split(|c: char| !c.is_alphabetic()) {
+        if raw.len() < 3 {
+            continue;
+        }
+        let word = raw.to_lowercase();
+        if stop.contains(&word.as_str()) {
+            continue;
+        }
+        *words.entry(word).or_insert(0) += 1;
+    }
+}
+
+/// Collect word frequencies from a directory tree.
+pub fn collect_words(root: &Path, options: &WordCloudOptions) -> Result<HashMap<String, usize>> {
+    let stop = stopwords(&options.stopwords);
+    let mut words = HashMap::new();
+
+    for entry in walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .flatten()
+    {
+        let name = entry.file_name().to_string_lossy();
+
+        if options.source != WordSource::Readme {
+            // File stem only - "parser.rs" should count "parser", not "rs"
+            let stem = name.split('.').next().unwrap_or(&name);
+            tokenize(stem, &mut words, stop);
+        }
+
+        if options.source != WordSource::Names
+            && entry.file_type().is_file()
+            && name.to_lowercase().starts_with("readme")
+            && entry.metadata().map(|m| m.len() <= MAX_README_BYTES).unwrap_or(false)
+        {
+            if let Ok(content) = std::fs::read_to_string(entry.path()) {
+                tokenize(&content, &mut words, stop);
+            }
+        }
+    }
+    Ok(words)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Render frequencies as an SVG cloud - rows of words flowed into the
+/// shape mask, font size by square root of frequency.
+pub fn render_svg(frequencies: &HashMap<String, usize>, options: &WordCloudOptions) -> String {
+    let mut ranked: Vec<(&String, usize)> = frequencies
+        .iter()
+        .map(|(word, count)| (word, *count))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    ranked.truncate(options.max_words);
+
+    let (background, colors) = palette(&options.theme);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         <rect width=\"{w}\" height=\"{h}\" fill=\"{background}\"/>\n",
+        w = WIDTH,
+        h = HEIGHT,
+    );
+
+    let max_count = ranked.first().map(|(_, c)| *c).unwrap_or(1) as f32;
+    let font_for = |count: usize| -> f32 {
+        MIN_FONT + (MAX_FONT - MIN_FONT) * (count as f32 / max_count).sqrt()
+    };
+
+    // Horizontal span available at height y, per the shape mask
+    let row_span = |y: f32| -> f32 {
+        match options.shape {
+            ShapeMask::Rect => WIDTH - 20.0,
+            ShapeMask::Ellipse => {
+                // Inscribed ellipse: chord length at this height
+                let dy = (y - HEIGHT / 2.0) / (HEIGHT / 2.0);
+                (WIDTH - 20.0) * (1.0 - dy * dy).max(0.0).sqrt()
+            }
+        }
+    };
+
+    let mut y = 20.0 + MAX_FONT;
+    let mut index = 0;
+    while index < ranked.len() && y < HEIGHT - 10.0 {
+        let span = row_span(y);
+        let row_start = (WIDTH - span) / 2.0;
+        let mut x = row_start;
+        let mut row_height: f32 = 0.0;
+
+        while index < ranked.len() {
+            let (word, count) = ranked[index];
+            let font = font_for(count);
+            let width = word.chars().count() as f32 * font * GLYPH_ASPECT;
+            if x > row_start && x + width > row_start + span {
+                break; // next row
+            }
+            let color = colors[index % colors.len()];
+            svg.push_str(&format!(
+                "<text x=\"{:.0}\" y=\"{:.0}\" font-family=\"sans-serif\" font-size=\"{:.0}\" fill=\"{}\">{}</text>\n",
+                x,
+                y,
+                font,
+                color,
+                escape_xml(word)
+            ));
+            x += width + font * 0.5;
+            row_height = row_height.max(font);
+            index += 1;
+        }
+        y += row_height + 8.0;
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(words: &HashMap<String, usize>, word: &str) -> usize {
+        words.get(word).copied().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_tokenize_drops_stopwords_and_short_words() {
+        let mut words = HashMap::new();
+        tokenize("the quick_brown-fox v2 ox", &mut words, stopwords("en"));
+
+        assert_eq!(count(&words, "quick"), 1);
+        assert_eq!(count(&words, "brown"), 1);
+        assert_eq!(count(&words, "fox"), 1);
+        assert_eq!(count(&words, "the"), 0); // stopword
+        assert_eq!(count(&words, "ox"), 0); // too short
+    }
+
+    #[test]
+    fn test_render_caps_words_and_uses_theme() {
+        let mut frequencies = HashMap::new();
+        for (i, word) in ["scanner", "formatter", "quantum", "waves"].iter().enumerate() {
+            frequencies.insert(word.to_string(), 10 - i);
+        }
+        let options = WordCloudOptions {
+            theme: "mono".to_string(),
+            max_words: 2,
+            ..Default::default()
+        };
+
+        let svg = render_svg(&frequencies, &options);
+        assert!(svg.contains("scanner"));
+        assert!(svg.contains("formatter"));
+        assert!(!svg.contains("waves")); // beyond max_words
+        assert!(svg.contains("#ffffff")); // mono background, not the ocean default
+    }
+
+    #[test]
+    fn test_ellipse_mask_renders_well_formed_svg() {
+        let options = WordCloudOptions {
+            shape: ShapeMask::Ellipse,
+            ..Default::default()
+        };
+        let mut frequencies = HashMap::new();
+        frequencies.insert("word".to_string(), 1);
+        let svg = render_svg(&frequencies, &options);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,250 @@
+//! Chunked payloads - breaking the 64KB barrier one LF at a time
+//!
+//! A single frame tops out at [`MAX_PAYLOAD_LEN`] bytes, which a big scan
+//! result blows past without trying. Chunking splits the logical payload
+//! across several `Next` (LF, "next item in sequence") frames and stitches
+//! them back together on the far side.
+//!
+//! ## Chunk Frame Format
+//!
+//! Each chunk rides in a `Verb::Next` frame whose payload starts with a
+//! 5-byte header:
+//!
+//! ```text
+//! ┌────────────┬──────────┬──────────┬─────────────┐
+//! │ inner verb │  index   │  total   │  chunk data │
+//! │    1B      │  u16 LE  │  u16 LE  │   N bytes   │
+//! └────────────┴──────────┴──────────┴─────────────┘
+//! ```
+//!
+//! Payloads that fit in one frame are sent as-is - no chunk overhead for
+//! the common case. Up to 65535 chunks of ~64KB each is ~4GB per logical
+//! message, which should hold us until Hue scans the whole internet.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{Frame, Payload, PayloadDecoder, ProtocolError, ProtocolResult, Verb, MAX_PAYLOAD_LEN};
+
+/// Bytes of chunk header before the data: inner verb + index + total
+pub const CHUNK_HEADER_LEN: usize = 5;
+
+/// Maximum data bytes per chunk frame
+pub const MAX_CHUNK_DATA: usize = MAX_PAYLOAD_LEN - CHUNK_HEADER_LEN;
+
+/// Split a logical payload into wire frames
+///
+/// Returns a single plain frame when the payload fits, otherwise a run of
+/// `Verb::Next` chunk frames. Feed the receiving end to a
+/// [`ChunkAssembler`] to get the original frame back.
+pub fn split_into_frames(verb: Verb, data: &[u8]) -> ProtocolResult<Vec<Frame>> {
+    if data.len() <= MAX_PAYLOAD_LEN {
+        return Ok([Frame::new(verb, Payload::from_bytes(data))].into());
+    }
+
+    let total = data.len().div_ceil(MAX_CHUNK_DATA);
+    if total > u16::MAX as usize {
+        return Err(ProtocolError::FrameTooLarge);
+    }
+
+    let mut frames = Vec::with_capacity(total);
+    for (index, chunk) in data.chunks(MAX_CHUNK_DATA).enumerate() {
+        let mut payload = Payload::new();
+        payload.push_byte(verb.as_byte());
+        payload.push_u16_le(index as u16);
+        payload.push_u16_le(total as u16);
+        for &b in chunk {
+            payload.push_byte(b);
+        }
+        frames.push(Frame::new(Verb::Next, payload));
+    }
+    Ok(frames)
+}
+
+/// Header parsed off the front of one chunk frame
+struct ChunkHeader {
+    verb: Verb,
+    index: u16,
+    total: u16,
+}
+
+fn parse_header(frame: &Frame) -> ProtocolResult<(ChunkHeader, usize)> {
+    let mut decoder = PayloadDecoder::new(frame.payload());
+    let verb_byte = decoder.byte().ok_or(ProtocolError::ChunkInvalid)?;
+    let verb = Verb::from_byte(verb_byte).ok_or(ProtocolError::InvalidVerb(verb_byte))?;
+    let index = decoder.u16_le().ok_or(ProtocolError::ChunkInvalid)?;
+    let total = decoder.u16_le().ok_or(ProtocolError::ChunkInvalid)?;
+    if total == 0 || index >= total {
+        return Err(ProtocolError::ChunkInvalid);
+    }
+    Ok((ChunkHeader { verb, index, total }, CHUNK_HEADER_LEN))
+}
+
+/// Reassembles chunk frames back into the original logical frame
+///
+/// Chunks may arrive in any order; duplicates and header mismatches are
+/// rejected loudly rather than corrupting the payload. One assembler
+/// handles one logical message - it resets itself after completion.
+#[derive(Default)]
+pub struct ChunkAssembler {
+    verb: Option<Verb>,
+    total: u16,
+    chunks: BTreeMap<u16, Vec<u8>>,
+}
+
+impl ChunkAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept one frame - plain frames pass straight through
+    ///
+    /// Returns `Ok(Some(frame))` when the logical frame is complete,
+    /// `Ok(None)` when more chunks are still outstanding.
+    pub fn accept(&mut self, frame: Frame) -> ProtocolResult<Option<Frame>> {
+        if frame.verb() != Verb::Next {
+            if self.total != 0 {
+                // A non-chunk frame mid-reassembly means someone lost the plot
+                return Err(ProtocolError::ChunkMismatch);
+            }
+            return Ok(Some(frame));
+        }
+
+        let (header, data_start) = parse_header(&frame)?;
+        match self.verb {
+            None => {
+                self.verb = Some(header.verb);
+                self.total = header.total;
+            }
+            Some(verb) if verb != header.verb || self.total != header.total => {
+                return Err(ProtocolError::ChunkMismatch);
+            }
+            Some(_) => {}
+        }
+
+        if self.chunks.contains_key(&header.index) {
+            return Err(ProtocolError::ChunkDuplicate(header.index));
+        }
+        self.chunks
+            .insert(header.index, frame.payload().as_bytes()[data_start..].to_vec());
+
+        if self.chunks.len() < self.total as usize {
+            return Ok(None); // Still waiting on the stragglers
+        }
+
+        // All present - BTreeMap iteration hands them back in index order
+        let mut data = Vec::new();
+        for chunk in self.chunks.values() {
+            data.extend_from_slice(chunk);
+        }
+        let verb = self.verb.take().unwrap_or(Verb::Ok);
+        self.total = 0;
+        self.chunks.clear();
+        Ok(Some(Frame::new(verb, Payload::from_bytes(&data))))
+    }
+
+    /// Indices we are still waiting for (empty when idle or complete)
+    pub fn missing(&self) -> Vec<u16> {
+        (0..self.total)
+            .filter(|i| !self.chunks.contains_key(i))
+            .collect()
+    }
+
+    /// True while a partially assembled message is pending
+    pub fn in_progress(&self) -> bool {
+        self.total != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_payload_stays_single_frame() {
+        let frames = split_into_frames(Verb::Ok, b"tiny").unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].verb(), Verb::Ok);
+        assert_eq!(frames[0].payload().as_bytes(), b"tiny");
+    }
+
+    #[test]
+    fn test_large_payload_roundtrip() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let frames = split_into_frames(Verb::Format, &data).unwrap();
+        assert!(frames.len() > 1);
+        assert!(frames.iter().all(|f| f.verb() == Verb::Next));
+
+        let mut assembler = ChunkAssembler::new();
+        let mut result = None;
+        for frame in frames {
+            result = assembler.accept(frame).unwrap();
+        }
+        let frame = result.expect("last chunk should complete the message");
+        assert_eq!(frame.verb(), Verb::Format);
+        assert_eq!(frame.payload().as_bytes(), &data[..]);
+    }
+
+    #[test]
+    fn test_out_of_order_chunks_reassemble() {
+        let data: Vec<u8> = (0..150_000u32).map(|i| (i % 97) as u8).collect();
+        let mut frames = split_into_frames(Verb::Scan, &data).unwrap();
+        frames.reverse();
+
+        let mut assembler = ChunkAssembler::new();
+        let mut result = None;
+        for frame in frames {
+            result = assembler.accept(frame).unwrap();
+        }
+        assert_eq!(result.unwrap().payload().as_bytes(), &data[..]);
+    }
+
+    #[test]
+    fn test_missing_chunk_is_reported() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 13) as u8).collect();
+        let mut frames = split_into_frames(Verb::Search, &data).unwrap();
+        frames.remove(1); // Lose the second chunk in transit
+
+        let mut assembler = ChunkAssembler::new();
+        for frame in frames {
+            assert!(assembler.accept(frame).unwrap().is_none());
+        }
+        assert!(assembler.in_progress());
+        assert_eq!(assembler.missing(), vec![1]);
+    }
+
+    #[test]
+    fn test_duplicate_chunk_is_rejected() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 7) as u8).collect();
+        let frames = split_into_frames(Verb::Search, &data).unwrap();
+
+        let mut assembler = ChunkAssembler::new();
+        assembler.accept(frames[0].clone()).unwrap();
+        assert_eq!(
+            assembler.accept(frames[0].clone()),
+            Err(ProtocolError::ChunkDuplicate(0))
+        );
+    }
+
+    #[test]
+    fn test_mismatched_totals_are_rejected() {
+        let big: Vec<u8> = (0..200_000u32).map(|_| 0xAA).collect();
+        let bigger: Vec<u8> = (0..300_000u32).map(|_| 0xBB).collect();
+        let first = split_into_frames(Verb::Scan, &big).unwrap();
+        let second = split_into_frames(Verb::Scan, &bigger).unwrap();
+
+        let mut assembler = ChunkAssembler::new();
+        assembler.accept(first[0].clone()).unwrap();
+        assert_eq!(
+            assembler.accept(second[1].clone()),
+            Err(ProtocolError::ChunkMismatch)
+        );
+    }
+
+    #[test]
+    fn test_plain_frame_passes_through() {
+        let mut assembler = ChunkAssembler::new();
+        let frame = Frame::ping();
+        assert_eq!(assembler.accept(frame.clone()).unwrap(), Some(frame));
+    }
+}
@@ -0,0 +1,131 @@
+//! Frame checksums - integrity for the transports that need it
+//!
+//! A Unix socket never flips a bit, but TCP across a flaky WiFi link or a
+//! serial line to an embedded scanner can. Frames may therefore carry an
+//! optional CRC trailer, negotiated once at handshake time so both sides
+//! agree on the overhead (or on skipping it entirely for local sockets).
+//!
+//! ## Checked Frame Format
+//!
+//! ```text
+//! ┌──────┬─────────────────┬─────────┬──────┐
+//! │ verb │     payload     │   crc   │ 0x00 │
+//! │ 1B   │   N bytes       │ 2B/4B   │ END  │
+//! └──────┴─────────────────┴─────────┴──────┘
+//! ```
+//!
+//! The CRC covers the verb byte plus the *raw* (unescaped) payload and is
+//! appended little-endian, escaped like any other payload bytes. CRC-16 is
+//! XMODEM (poly 0x1021), CRC-32 is IEEE (poly 0xEDB88320) - both bitwise,
+//! no tables, because no_std targets would rather spend the cycles than
+//! the flash.
+
+/// Which checksum trailer frames on this connection carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum ChecksumMode {
+    /// No trailer - the default, and all a Unix socket needs
+    #[default]
+    None = 0x00,
+    /// 2-byte CRC-16/XMODEM trailer
+    Crc16 = 0x01,
+    /// 4-byte CRC-32 (IEEE) trailer
+    Crc32 = 0x02,
+}
+
+impl ChecksumMode {
+    pub fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            0x00 => Some(ChecksumMode::None),
+            0x01 => Some(ChecksumMode::Crc16),
+            0x02 => Some(ChecksumMode::Crc32),
+            _ => None,
+        }
+    }
+
+    pub fn as_byte(self) -> u8 {
+        self as u8
+    }
+
+    /// Raw trailer bytes this mode adds to each frame (before escaping)
+    pub fn trailer_len(self) -> usize {
+        match self {
+            ChecksumMode::None => 0,
+            ChecksumMode::Crc16 => 2,
+            ChecksumMode::Crc32 => 4,
+        }
+    }
+}
+
+/// CRC-16/XMODEM: poly 0x1021, init 0x0000, no reflection
+pub fn crc16(data: &[u8]) -> u16 {
+    crc16_update(0x0000, data)
+}
+
+/// Resume a CRC-16 over another slice - lets frame.rs cover the verb byte
+/// and payload without concatenating them (no alloc in the decode path)
+pub(crate) fn crc16_update(mut crc: u16, data: &[u8]) -> u16 {
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            if crc & 0x8000 != 0 {
+                crc = (crc << 1) ^ 0x1021;
+            } else {
+                crc <<= 1;
+            }
+        }
+    }
+    crc
+}
+
+/// CRC-32 (IEEE): reflected poly 0xEDB88320, init/final-xor 0xFFFFFFFF
+pub fn crc32(data: &[u8]) -> u32 {
+    !crc32_update(0xFFFF_FFFF, data)
+}
+
+/// Resume a CRC-32 over another slice - raw state, caller applies the
+/// 0xFFFFFFFF init and final complement (see [`crc32`])
+pub(crate) fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            if crc & 1 != 0 {
+                crc = (crc >> 1) ^ 0xEDB8_8320;
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Standard check values - every CRC catalogue lists "123456789"
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(crc16(b""), 0x0000);
+        assert_eq!(crc32(b""), 0x0000_0000);
+    }
+
+    #[test]
+    fn mode_byte_roundtrip() {
+        for mode in [ChecksumMode::None, ChecksumMode::Crc16, ChecksumMode::Crc32] {
+            assert_eq!(ChecksumMode::from_byte(mode.as_byte()), Some(mode));
+        }
+        assert_eq!(ChecksumMode::from_byte(0x7F), None);
+        assert_eq!(ChecksumMode::default().trailer_len(), 0);
+    }
+}
@@ -29,6 +29,14 @@ pub enum ProtocolError {
     InvalidSession,
     /// Unknown host in cache
     UnknownHost(u8),
+    /// Chunk frame with a malformed header
+    ChunkInvalid,
+    /// Chunk received twice during reassembly
+    ChunkDuplicate(u16),
+    /// Chunk does not belong to the message being reassembled
+    ChunkMismatch,
+    /// Frame CRC trailer does not match its contents
+    ChecksumMismatch,
     /// I/O error (std only)
     #[cfg(feature = "std")]
     Io(String),
@@ -50,6 +58,10 @@ impl core::fmt::Display for ProtocolError {
             ProtocolError::InsufficientPrivileges => write!(f, "insufficient privileges"),
             ProtocolError::InvalidSession => write!(f, "invalid or expired session"),
             ProtocolError::UnknownHost(idx) => write!(f, "unknown host at index {}", idx),
+            ProtocolError::ChunkInvalid => write!(f, "malformed chunk header"),
+            ProtocolError::ChunkDuplicate(idx) => write!(f, "duplicate chunk {}", idx),
+            ProtocolError::ChunkMismatch => write!(f, "chunk does not match message in progress"),
+            ProtocolError::ChecksumMismatch => write!(f, "frame checksum mismatch"),
             #[cfg(feature = "std")]
             ProtocolError::Io(msg) => write!(f, "I/O error: {}", msg),
         }
@@ -12,6 +12,7 @@
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+use crate::crc::{crc16_update, crc32_update, ChecksumMode};
 use crate::{Verb, Payload, END, ESC, ProtocolError, ProtocolResult, MAX_FRAME_SIZE};
 
 /// A complete protocol frame
@@ -89,6 +90,92 @@ impl Frame {
         Ok(Frame { verb, payload })
     }
 
+    /// Encode with the CRC trailer negotiated for this connection
+    ///
+    /// The checksum covers the verb byte plus the raw (unescaped) payload
+    /// and rides little-endian just before END, escaped like any other
+    /// payload bytes. `ChecksumMode::None` is a plain [`Frame::encode`].
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    pub fn encode_checked(&self, mode: ChecksumMode) -> Vec<u8> {
+        let trailer: Vec<u8> = match mode {
+            ChecksumMode::None => return self.encode(),
+            ChecksumMode::Crc16 => frame_crc16(self.verb, self.payload.as_bytes())
+                .to_le_bytes()
+                .into(),
+            ChecksumMode::Crc32 => frame_crc32(self.verb, self.payload.as_bytes())
+                .to_le_bytes()
+                .into(),
+        };
+
+        let encoded_payload = self.payload.encode();
+        let mut out = Vec::with_capacity(encoded_payload.len() + trailer.len() * 2 + 2);
+
+        out.push(self.verb.as_byte());
+        out.extend_from_slice(&encoded_payload);
+        for &b in &trailer {
+            match b {
+                END => {
+                    out.push(ESC);
+                    out.push(END);
+                }
+                ESC => {
+                    out.push(ESC);
+                    out.push(ESC);
+                }
+                _ => out.push(b),
+            }
+        }
+        out.push(END);
+
+        out
+    }
+
+    /// Decode a frame carrying a CRC trailer, rejecting corruption
+    ///
+    /// Returns [`ProtocolError::ChecksumMismatch`] when the trailer does
+    /// not match - the caller drops the frame instead of acting on garbage.
+    /// `ChecksumMode::None` is a plain [`Frame::decode`].
+    pub fn decode_checked(data: &[u8], mode: ChecksumMode) -> ProtocolResult<Self> {
+        if mode == ChecksumMode::None {
+            return Self::decode(data);
+        }
+
+        if data.len() < 2 {
+            return Err(ProtocolError::FrameTooShort);
+        }
+        if data.len() > MAX_FRAME_SIZE {
+            return Err(ProtocolError::FrameTooLarge);
+        }
+        if data[data.len() - 1] != END {
+            return Err(ProtocolError::MissingEndMarker);
+        }
+
+        let verb = Verb::from_byte(data[0]).ok_or(ProtocolError::InvalidVerb(data[0]))?;
+
+        // Unescape payload + trailer together, then split the trailer off
+        let unescaped = Payload::decode(&data[1..data.len() - 1])?;
+        let raw = unescaped.as_bytes();
+        let trailer_len = mode.trailer_len();
+        if raw.len() < trailer_len {
+            return Err(ProtocolError::FrameTooShort);
+        }
+        let (payload_bytes, trailer) = raw.split_at(raw.len() - trailer_len);
+
+        let matches = match mode {
+            ChecksumMode::Crc16 => trailer == frame_crc16(verb, payload_bytes).to_le_bytes(),
+            ChecksumMode::Crc32 => trailer == frame_crc32(verb, payload_bytes).to_le_bytes(),
+            ChecksumMode::None => unreachable!("handled above"),
+        };
+        if !matches {
+            return Err(ProtocolError::ChecksumMismatch);
+        }
+
+        Ok(Frame {
+            verb,
+            payload: Payload::from_bytes(payload_bytes),
+        })
+    }
+
     /// Check if frame is valid (verb + END marker present)
     pub fn is_valid(data: &[u8]) -> bool {
         if data.len() < 2 {
@@ -117,6 +204,16 @@ impl Frame {
     }
 }
 
+/// CRC-16 over verb + raw payload, resumed so nothing gets concatenated
+fn frame_crc16(verb: Verb, payload: &[u8]) -> u16 {
+    crc16_update(crc16_update(0x0000, &[verb.as_byte()]), payload)
+}
+
+/// CRC-32 over verb + raw payload (init and final complement applied here)
+fn frame_crc32(verb: Verb, payload: &[u8]) -> u32 {
+    !crc32_update(crc32_update(0xFFFF_FFFF, &[verb.as_byte()]), payload)
+}
+
 /// Builder for constructing frames
 pub struct FrameBuilder {
     verb: Verb,
@@ -429,6 +526,50 @@ mod tests {
         assert_eq!(Frame::find_end(&data), Some(4));
     }
 
+    #[test]
+    fn test_checked_roundtrip() {
+        for mode in [ChecksumMode::Crc16, ChecksumMode::Crc32] {
+            let original = Frame::scan("/home/hue", 3);
+            let encoded = original.encode_checked(mode);
+            let decoded = Frame::decode_checked(&encoded, mode).unwrap();
+
+            assert_eq!(decoded.verb(), original.verb());
+            assert_eq!(decoded.payload().as_bytes(), original.payload().as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_checked_none_matches_plain_encode() {
+        let frame = Frame::ping();
+        assert_eq!(frame.encode_checked(ChecksumMode::None), frame.encode());
+    }
+
+    #[test]
+    fn test_corrupted_frame_is_rejected() {
+        let frame = Frame::search("*.rs");
+        let mut encoded = frame.encode_checked(ChecksumMode::Crc32);
+        encoded[2] ^= 0x01; // one bit flip in the payload
+
+        assert_eq!(
+            Frame::decode_checked(&encoded, ChecksumMode::Crc32),
+            Err(ProtocolError::ChecksumMismatch)
+        );
+        // The untouched frame still verifies
+        assert!(Frame::decode_checked(&frame.encode_checked(ChecksumMode::Crc32), ChecksumMode::Crc32).is_ok());
+    }
+
+    #[test]
+    fn test_checked_trailer_is_escaped() {
+        // A payload whose CRC happens to contain END/ESC bytes must still
+        // frame cleanly - find_end has to land on the real END marker
+        for i in 0u16..512 {
+            let frame = Frame::new(Verb::Search, Payload::from_string(&format!("probe-{}", i)));
+            let encoded = frame.encode_checked(ChecksumMode::Crc16);
+            assert_eq!(Frame::find_end(&encoded), Some(encoded.len()));
+            assert!(Frame::decode_checked(&encoded, ChecksumMode::Crc16).is_ok());
+        }
+    }
+
     #[test]
     fn test_builder() {
         let frame = FrameBuilder::new(Verb::Search)
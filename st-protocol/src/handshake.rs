@@ -0,0 +1,516 @@
+//! Hello/Challenge/Auth handshake for session establishment
+//!
+//! Rides inside `Verb::Session` frames - the first payload byte selects the
+//! handshake message, the rest is fixed-layout binary (no JSON, as always).
+//!
+//! ```text
+//! Client                                  Daemon
+//!   |-- SESSION(HELLO, level, client) ---->|
+//!   |<-- SESSION(CHALLENGE, nonce) --------|
+//!   |-- SESSION(AUTH, level, client, mac) >|   mac = HMAC-SHA256(key,
+//!   |<-- SESSION(ESTABLISHED, id, expiry) -|         nonce || client || level)
+//! ```
+//!
+//! The daemon hands out a `SessionId` with an expiry; subsequent Scan/Read
+//! requests present it in their auth block and the daemon enforces both the
+//! verb's security level and `path_auth_level` for the target path.
+//!
+//! Time is passed in explicitly (epoch seconds) so the state machine stays
+//! no_std-friendly - the caller owns the clock.
+
+#[cfg(feature = "std")]
+extern crate std as alloc;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+
+use crate::auth::{path_auth_level, AuthLevel, SessionId};
+use crate::crc::ChecksumMode;
+use crate::{ProtocolError, ProtocolResult, Verb};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How long an established session stays valid
+pub const SESSION_TTL_SECS: u64 = 3600;
+
+/// Client identifier (16 bytes, caller-chosen)
+pub type ClientId = [u8; 16];
+
+/// Handshake message discriminator (first payload byte of a Session frame)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum HandshakeOp {
+    /// Client announces itself and the level it wants
+    Hello = 0x01,
+    /// Daemon answers with a fresh nonce
+    Challenge = 0x02,
+    /// Client proves key possession over the nonce
+    Auth = 0x03,
+    /// Daemon grants a session
+    Established = 0x04,
+    /// Daemon refuses (reason byte follows)
+    Reject = 0x05,
+}
+
+impl HandshakeOp {
+    pub fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            0x01 => Some(HandshakeOp::Hello),
+            0x02 => Some(HandshakeOp::Challenge),
+            0x03 => Some(HandshakeOp::Auth),
+            0x04 => Some(HandshakeOp::Established),
+            0x05 => Some(HandshakeOp::Reject),
+            _ => None,
+        }
+    }
+
+    pub fn as_byte(self) -> u8 {
+        self as u8
+    }
+}
+
+/// HELLO: op + requested level (1B) + client id (16B) + checksum mode (1B)
+///
+/// The checksum byte asks the daemon to CRC-protect frames for the rest of
+/// the session (see [`ChecksumMode`]); [`Established`] echoes the grant.
+#[derive(Debug, Clone, Copy)]
+pub struct Hello {
+    pub level: AuthLevel,
+    pub client: ClientId,
+    pub checksum: ChecksumMode,
+}
+
+impl Hello {
+    pub const SIZE: usize = 1 + 1 + 16 + 1;
+    /// Pre-checksum layout, still accepted on decode
+    const LEGACY_SIZE: usize = 1 + 1 + 16;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::SIZE);
+        out.push(HandshakeOp::Hello.as_byte());
+        out.push(self.level.as_byte());
+        out.extend_from_slice(&self.client);
+        out.push(self.checksum.as_byte());
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> ProtocolResult<Self> {
+        if data.len() < Self::LEGACY_SIZE || data[0] != HandshakeOp::Hello.as_byte() {
+            return Err(ProtocolError::InvalidAuthBlock);
+        }
+        let level = AuthLevel::from_byte(data[1]).ok_or(ProtocolError::InvalidAuthBlock)?;
+        let mut client = [0u8; 16];
+        client.copy_from_slice(&data[2..18]);
+        // Old clients stop at the client id - they get unchecked frames
+        let checksum = match data.get(18) {
+            Some(&b) => ChecksumMode::from_byte(b).ok_or(ProtocolError::InvalidAuthBlock)?,
+            None => ChecksumMode::None,
+        };
+        Ok(Hello {
+            level,
+            client,
+            checksum,
+        })
+    }
+}
+
+/// CHALLENGE: op + nonce (32B)
+#[derive(Debug, Clone, Copy)]
+pub struct Challenge {
+    pub nonce: [u8; 32],
+}
+
+impl Challenge {
+    pub const SIZE: usize = 1 + 32;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::SIZE);
+        out.push(HandshakeOp::Challenge.as_byte());
+        out.extend_from_slice(&self.nonce);
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> ProtocolResult<Self> {
+        if data.len() < Self::SIZE || data[0] != HandshakeOp::Challenge.as_byte() {
+            return Err(ProtocolError::InvalidAuthBlock);
+        }
+        let mut nonce = [0u8; 32];
+        nonce.copy_from_slice(&data[1..33]);
+        Ok(Challenge { nonce })
+    }
+}
+
+/// AUTH: op + level (1B) + client id (16B) + mac (32B)
+#[derive(Debug, Clone, Copy)]
+pub struct Auth {
+    pub level: AuthLevel,
+    pub client: ClientId,
+    pub mac: [u8; 32],
+}
+
+impl Auth {
+    pub const SIZE: usize = 1 + 1 + 16 + 32;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::SIZE);
+        out.push(HandshakeOp::Auth.as_byte());
+        out.push(self.level.as_byte());
+        out.extend_from_slice(&self.client);
+        out.extend_from_slice(&self.mac);
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> ProtocolResult<Self> {
+        if data.len() < Self::SIZE || data[0] != HandshakeOp::Auth.as_byte() {
+            return Err(ProtocolError::InvalidAuthBlock);
+        }
+        let level = AuthLevel::from_byte(data[1]).ok_or(ProtocolError::InvalidAuthBlock)?;
+        let mut client = [0u8; 16];
+        client.copy_from_slice(&data[2..18]);
+        let mut mac = [0u8; 32];
+        mac.copy_from_slice(&data[18..50]);
+        Ok(Auth { level, client, mac })
+    }
+}
+
+/// ESTABLISHED: op + session (16B) + expiry epoch secs (u64 LE) + level (1B)
+/// + checksum mode (1B)
+///
+/// `checksum` is the mode the daemon granted - both sides switch their
+/// frame codecs to it from the next frame onward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Established {
+    pub session: SessionId,
+    pub expires_at: u64,
+    pub level: AuthLevel,
+    pub checksum: ChecksumMode,
+}
+
+impl Established {
+    pub const SIZE: usize = 1 + 16 + 8 + 1 + 1;
+    /// Pre-checksum layout, still accepted on decode
+    const LEGACY_SIZE: usize = 1 + 16 + 8 + 1;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(Self::SIZE);
+        out.push(HandshakeOp::Established.as_byte());
+        out.extend_from_slice(self.session.as_bytes());
+        out.extend_from_slice(&self.expires_at.to_le_bytes());
+        out.push(self.level.as_byte());
+        out.push(self.checksum.as_byte());
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> ProtocolResult<Self> {
+        if data.len() < Self::LEGACY_SIZE || data[0] != HandshakeOp::Established.as_byte() {
+            return Err(ProtocolError::InvalidAuthBlock);
+        }
+        let session =
+            SessionId::from_slice(&data[1..17]).ok_or(ProtocolError::InvalidAuthBlock)?;
+        let mut secs = [0u8; 8];
+        secs.copy_from_slice(&data[17..25]);
+        let level = AuthLevel::from_byte(data[25]).ok_or(ProtocolError::InvalidAuthBlock)?;
+        let checksum = match data.get(26) {
+            Some(&b) => ChecksumMode::from_byte(b).ok_or(ProtocolError::InvalidAuthBlock)?,
+            None => ChecksumMode::None,
+        };
+        Ok(Established {
+            session,
+            expires_at: u64::from_le_bytes(secs),
+            level,
+            checksum,
+        })
+    }
+}
+
+/// The MAC every client must produce: HMAC-SHA256 over nonce || client || level
+pub fn handshake_mac(key: &[u8], nonce: &[u8; 32], client: &ClientId, level: AuthLevel) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(nonce);
+    mac.update(client);
+    mac.update(&[level.as_byte()]);
+    mac.finalize().into_bytes().into()
+}
+
+/// Client-side helper: answer a challenge with a signed AUTH message
+pub fn answer_challenge(
+    key: &[u8],
+    challenge: &Challenge,
+    client: ClientId,
+    level: AuthLevel,
+) -> Auth {
+    Auth {
+        level,
+        client,
+        mac: handshake_mac(key, &challenge.nonce, &client, level),
+    }
+}
+
+/// An established session with its grant and expiry
+#[derive(Debug, Clone, Copy)]
+struct SessionState {
+    level: AuthLevel,
+    expires_at: u64,
+}
+
+/// Daemon-side handshake state machine and session table
+///
+/// One per daemon; shared-key HMAC keeps it dependency-light (ed25519 can
+/// slot in later - the wire format already carries 32-byte proofs).
+pub struct Authenticator {
+    key: Vec<u8>,
+    /// Outstanding challenges by client id (nonce + requested checksum mode)
+    pending: BTreeMap<ClientId, ([u8; 32], ChecksumMode)>,
+    /// Live sessions by session id bytes
+    sessions: BTreeMap<[u8; 16], SessionState>,
+}
+
+impl Authenticator {
+    pub fn new(key: &[u8]) -> Self {
+        Authenticator {
+            key: key.to_vec(),
+            pending: BTreeMap::new(),
+            sessions: BTreeMap::new(),
+        }
+    }
+
+    /// Handle HELLO: remember the nonce we challenge this client with
+    ///
+    /// The caller supplies the nonce so entropy stays outside the protocol
+    /// crate (std daemons use a real RNG, tests use fixed bytes).
+    pub fn begin(&mut self, hello: &Hello, nonce: [u8; 32]) -> Challenge {
+        self.pending.insert(hello.client, (nonce, hello.checksum));
+        Challenge { nonce }
+    }
+
+    /// Handle AUTH: verify the MAC against the outstanding challenge and,
+    /// on success, establish a session valid for [`SESSION_TTL_SECS`]
+    pub fn complete(&mut self, auth: &Auth, now: u64) -> ProtocolResult<Established> {
+        let (nonce, checksum) = self
+            .pending
+            .remove(&auth.client)
+            .ok_or(ProtocolError::AuthRequired)?;
+
+        let expected = handshake_mac(&self.key, &nonce, &auth.client, auth.level);
+        if !constant_time_eq(&expected, &auth.mac) {
+            return Err(ProtocolError::AuthFailed);
+        }
+
+        // Session id derived from the MAC keyed with a distinct label, so it
+        // is unguessable without the shared key even with a known nonce
+        let id_mac = {
+            let mut mac = HmacSha256::new_from_slice(&self.key).expect("any key length");
+            mac.update(b"session-id");
+            mac.update(&nonce);
+            mac.update(&auth.client);
+            mac.finalize().into_bytes()
+        };
+        let mut id = [0u8; 16];
+        id.copy_from_slice(&id_mac[..16]);
+        let session = SessionId::new(id);
+
+        let expires_at = now + SESSION_TTL_SECS;
+        self.sessions.insert(
+            id,
+            SessionState {
+                level: auth.level,
+                expires_at,
+            },
+        );
+
+        Ok(Established {
+            session,
+            expires_at,
+            level: auth.level,
+            // The daemon supports every mode, so the client's ask is granted
+            // as-is; a constrained daemon could downgrade here
+            checksum,
+        })
+    }
+
+    /// Look up a session, rejecting unknown or expired ones
+    pub fn verify_session(&self, session: &SessionId, now: u64) -> ProtocolResult<AuthLevel> {
+        match self.sessions.get(session.as_bytes()) {
+            Some(state) if state.expires_at > now => Ok(state.level),
+            Some(_) => Err(ProtocolError::InvalidSession),
+            None => Err(ProtocolError::InvalidSession),
+        }
+    }
+
+    /// Enforce both the verb's security level and the path's auth level for
+    /// a Scan/Read style request - this is what the daemon calls per frame
+    pub fn authorize(
+        &self,
+        session: Option<&SessionId>,
+        verb: Verb,
+        path: &str,
+        now: u64,
+    ) -> ProtocolResult<()> {
+        let granted = match session {
+            Some(id) => self.verify_session(id, now)?,
+            None => AuthLevel::None,
+        };
+
+        let required_verb = verb.security_level();
+        let required_path = path_auth_level(path).as_byte();
+        let required = required_verb.max(required_path);
+
+        if granted.as_byte() >= required {
+            Ok(())
+        } else if granted == AuthLevel::None {
+            Err(ProtocolError::AuthRequired)
+        } else {
+            Err(ProtocolError::InsufficientPrivileges)
+        }
+    }
+
+    /// Drop expired sessions (call periodically)
+    pub fn expire(&mut self, now: u64) {
+        self.sessions.retain(|_, state| state.expires_at > now);
+    }
+
+    /// Number of live sessions (for stats/health)
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+}
+
+/// Compare MACs without early exit - no timing oracle for the impatient
+fn constant_time_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"correct horse battery staple";
+    const CLIENT: ClientId = [7u8; 16];
+    const NONCE: [u8; 32] = [42u8; 32];
+
+    fn establish(authenticator: &mut Authenticator, level: AuthLevel, now: u64) -> Established {
+        let hello = Hello { level, client: CLIENT, checksum: ChecksumMode::None };
+        let challenge = authenticator.begin(&hello, NONCE);
+        let auth = answer_challenge(KEY, &challenge, CLIENT, level);
+        authenticator.complete(&auth, now).unwrap()
+    }
+
+    #[test]
+    fn full_handshake_roundtrip() {
+        let mut daemon = Authenticator::new(KEY);
+        let established = establish(&mut daemon, AuthLevel::Session, 1000);
+
+        assert_eq!(established.level, AuthLevel::Session);
+        assert_eq!(established.expires_at, 1000 + SESSION_TTL_SECS);
+        assert_eq!(
+            daemon.verify_session(&established.session, 1001).unwrap(),
+            AuthLevel::Session
+        );
+
+        // Wire roundtrip of every message type
+        let hello = Hello { level: AuthLevel::Fido, client: CLIENT, checksum: ChecksumMode::Crc16 };
+        let decoded = Hello::decode(&hello.encode()).unwrap();
+        assert_eq!(decoded.client, CLIENT);
+        assert_eq!(decoded.checksum, ChecksumMode::Crc16);
+        let challenge = Challenge { nonce: NONCE };
+        assert_eq!(Challenge::decode(&challenge.encode()).unwrap().nonce, NONCE);
+        let reencoded = Established::decode(&established.encode()).unwrap();
+        assert_eq!(reencoded.expires_at, established.expires_at);
+    }
+
+    #[test]
+    fn checksum_mode_is_negotiated() {
+        // The client asks for CRC-32; the grant echoes it back
+        let mut daemon = Authenticator::new(KEY);
+        let hello = Hello {
+            level: AuthLevel::Session,
+            client: CLIENT,
+            checksum: ChecksumMode::Crc32,
+        };
+        let challenge = daemon.begin(&hello, NONCE);
+        let auth = answer_challenge(KEY, &challenge, CLIENT, AuthLevel::Session);
+        let established = daemon.complete(&auth, 1000).unwrap();
+        assert_eq!(established.checksum, ChecksumMode::Crc32);
+
+        // A legacy HELLO without the checksum byte still parses - unchecked
+        let legacy = &hello.encode()[..Hello::SIZE - 1];
+        assert_eq!(Hello::decode(legacy).unwrap().checksum, ChecksumMode::None);
+    }
+
+    #[test]
+    fn unauthorized_client_is_rejected() {
+        // Harness: a client that never learned the shared key
+        let mut daemon = Authenticator::new(KEY);
+        let hello = Hello { level: AuthLevel::Session, client: CLIENT, checksum: ChecksumMode::None };
+        let challenge = daemon.begin(&hello, NONCE);
+
+        let forged = answer_challenge(b"wrong key", &challenge, CLIENT, AuthLevel::Session);
+        assert_eq!(daemon.complete(&forged, 1000), Err(ProtocolError::AuthFailed));
+
+        // And with no session at all, protected paths stay closed
+        assert_eq!(
+            daemon.authorize(None, Verb::Scan, "/etc/passwd", 1000),
+            Err(ProtocolError::AuthRequired)
+        );
+        // ...while ordinary reads still work unauthenticated
+        assert!(daemon.authorize(None, Verb::Scan, "/home/hue/projects", 1000).is_ok());
+    }
+
+    #[test]
+    fn replayed_auth_needs_a_fresh_challenge() {
+        let mut daemon = Authenticator::new(KEY);
+        let hello = Hello { level: AuthLevel::Session, client: CLIENT, checksum: ChecksumMode::None };
+        let challenge = daemon.begin(&hello, NONCE);
+        let auth = answer_challenge(KEY, &challenge, CLIENT, AuthLevel::Session);
+
+        assert!(daemon.complete(&auth, 1000).is_ok());
+        // Challenge is consumed - replaying the same AUTH fails
+        assert_eq!(daemon.complete(&auth, 1000), Err(ProtocolError::AuthRequired));
+    }
+
+    #[test]
+    fn sessions_expire() {
+        let mut daemon = Authenticator::new(KEY);
+        let established = establish(&mut daemon, AuthLevel::Session, 1000);
+
+        let after_expiry = established.expires_at + 1;
+        assert_eq!(
+            daemon.verify_session(&established.session, after_expiry),
+            Err(ProtocolError::InvalidSession)
+        );
+
+        daemon.expire(after_expiry);
+        assert_eq!(daemon.session_count(), 0);
+    }
+
+    #[test]
+    fn path_auth_level_is_enforced() {
+        let mut daemon = Authenticator::new(KEY);
+        // A Session-level client tries to scan a FIDO-protected path
+        let established = establish(&mut daemon, AuthLevel::Session, 1000);
+        assert_eq!(
+            daemon.authorize(
+                Some(&established.session),
+                Verb::Scan,
+                "/home/hue/.ssh/id_ed25519",
+                1001
+            ),
+            Err(ProtocolError::InsufficientPrivileges)
+        );
+
+        // A FIDO-level client gets through
+        let mut daemon = Authenticator::new(KEY);
+        let elevated = establish(&mut daemon, AuthLevel::Fido, 1000);
+        assert!(daemon
+            .authorize(Some(&elevated.session), Verb::Scan, "/home/hue/.ssh/known_hosts", 1001)
+            .is_ok());
+    }
+}
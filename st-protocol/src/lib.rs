@@ -39,6 +39,9 @@ mod payload;
 mod address;
 mod error;
 mod auth;
+mod chunk;
+mod crc;
+mod handshake;
 
 pub use verb::Verb;
 pub use frame::{Frame, FrameBuilder};
@@ -47,6 +50,12 @@ pub use address::{Address, AddressString, HostCache};
 pub use error::{ProtocolError, ProtocolResult};
 pub use auth::{AuthLevel, AuthBlock, SecurityContext, SessionId, Signature};
 pub use auth::{is_protected_path, path_auth_level, PROTECTED_PATHS};
+pub use chunk::{split_into_frames, ChunkAssembler, CHUNK_HEADER_LEN, MAX_CHUNK_DATA};
+pub use crc::{crc16, crc32, ChecksumMode};
+pub use handshake::{
+    answer_challenge, handshake_mac, Auth, Authenticator, Challenge, ClientId, Established,
+    HandshakeOp, Hello, SESSION_TTL_SECS,
+};
 
 /// Protocol version
 pub const VERSION: u8 = 1;
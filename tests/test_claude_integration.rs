@@ -155,6 +155,9 @@ fn test_context_mode_output() {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         },
         FileNode {
             path: PathBuf::from("Cargo.toml"),
@@ -179,6 +182,9 @@ fn test_context_mode_output() {
             security_findings: Vec::new(),
             change_status: None,
             content_hash: None,
+            hardlink_id: None,
+            allocated_size: None,
+            xattrs: None,
         },
     ];
 
@@ -190,6 +196,7 @@ fn test_context_mode_output() {
         largest_files: vec![],
         newest_files: vec![],
         oldest_files: vec![],
+        ..TreeStats::default()
     };
 
     // Just verify we can create the test data correctly